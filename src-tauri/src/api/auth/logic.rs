@@ -9,9 +9,9 @@ use reqwest::{
 use crate::definitions::AuthCookies;
 
 use crate::api::common::{
-    check_rate_limit, get_reqwest_client, handle_api_response, record_rate_limit, reset_backoff,
-    API_BASE_URL,
+    acquire_request_slot, get_reqwest_client, handle_api_response, RequestPriority, API_BASE_URL,
 };
+use crate::errors::ApiError;
 
 use super::definitions::{
     CurrentUser, RequiresTwoFactorAuth, TwoFactorAuthVerified, VRChatAuthPhase, VRChatAuthStatus,
@@ -56,10 +56,14 @@ impl VRChatAPIClientAuthenticator {
         self.cookie.clone()
     }
 
-    pub async fn verify_token(&mut self) -> Result<VRChatAuthStatus, String> {
+    pub fn phase(&self) -> VRChatAuthPhase {
+        self.phase
+    }
+
+    pub async fn verify_token(&mut self) -> Result<VRChatAuthStatus, ApiError> {
         const OPERATION: &str = "verify_token";
 
-        check_rate_limit(OPERATION)?;
+        let _permit = acquire_request_slot(OPERATION, RequestPriority::Interactive).await?;
 
         log::info!("Verifying token...");
         let result = self
@@ -73,13 +77,10 @@ impl VRChatAPIClientAuthenticator {
             Ok(response) => response,
             Err(e) => {
                 log::error!("Failed to handle API response: {}", e);
-                record_rate_limit(OPERATION);
                 return Err(e);
             }
         };
 
-        reset_backoff(OPERATION);
-
         if result.status() == StatusCode::UNAUTHORIZED {
             log::info!("Token is invalid or expired.");
             return Ok(VRChatAuthStatus::InvalidCredentials);
@@ -134,10 +135,10 @@ impl VRChatAPIClientAuthenticator {
     pub async fn login_with_password<T: AsRef<str>>(
         &mut self,
         password: T,
-    ) -> Result<VRChatAuthStatus, String> {
+    ) -> Result<VRChatAuthStatus, ApiError> {
         const OPERATION: &str = "login_with_password";
 
-        check_rate_limit(OPERATION)?;
+        let _permit = acquire_request_slot(OPERATION, RequestPriority::Interactive).await?;
 
         log::info!("Logging in with password...");
         let password = password.as_ref().to_string();
@@ -156,13 +157,10 @@ impl VRChatAPIClientAuthenticator {
             Ok(response) => response,
             Err(e) => {
                 log::error!("Failed to handle API response: {}", e);
-                record_rate_limit(OPERATION);
                 return Err(e);
             }
         };
 
-        reset_backoff(OPERATION);
-
         if result.status() == StatusCode::UNAUTHORIZED {
             return Ok(VRChatAuthStatus::InvalidCredentials);
         }
@@ -170,7 +168,9 @@ impl VRChatAPIClientAuthenticator {
         if result.status() == StatusCode::OK {
             let text = match result.text().await {
                 Ok(text) => text,
-                Err(e) => return Err(format!("Failed to read response text: {}", e.to_string())),
+                Err(e) => {
+                    return Err(format!("Failed to read response text: {}", e.to_string()).into())
+                }
             };
 
             if let Ok(requires_2fa) = serde_json::from_str::<RequiresTwoFactorAuth>(&text) {
@@ -192,9 +192,11 @@ impl VRChatAPIClientAuthenticator {
             let cookie_str = match header_value.as_ref() {
                 Some(value) => match value.to_str() {
                     Ok(cookie) => cookie,
-                    Err(e) => return Err(format!("Failed to convert cookie to string: {}", e)),
+                    Err(e) => {
+                        return Err(format!("Failed to convert cookie to string: {}", e).into())
+                    }
                 },
-                None => return Err("No cookies found for the given URL".to_string()),
+                None => return Err("No cookies found for the given URL".to_string().into()),
             };
             let auth_cookies = AuthCookies::from_cookie_str(cookie_str);
 
@@ -213,19 +215,21 @@ impl VRChatAPIClientAuthenticator {
                 "Unknown error occurred: {}",
                 text
             ))),
-            Err(e) => Err(format!("Failed to read response text: {}", e.to_string())),
+            Err(e) => Err(format!("Failed to read response text: {}", e.to_string()).into()),
         }
     }
 
     pub async fn login_with_email_2fa<T: AsRef<str>>(
         &mut self,
         code: T,
-    ) -> Result<VRChatAuthStatus, String> {
+    ) -> Result<VRChatAuthStatus, ApiError> {
         const OPERATION: &str = "login_with_2fa";
 
+        let _permit = acquire_request_slot(OPERATION, RequestPriority::Interactive).await?;
+
         log::info!("Logging in with email 2FA...");
         if self.phase != VRChatAuthPhase::Email2FA {
-            return Err("Not in email 2FA phase".to_string());
+            return Err("Not in email 2FA phase".to_string().into());
         }
 
         let code = code.as_ref().to_string();
@@ -246,27 +250,24 @@ impl VRChatAPIClientAuthenticator {
             Ok(response) => response,
             Err(e) => {
                 log::error!("Failed to handle API response: {}", e);
-                record_rate_limit(OPERATION);
                 return Err(e);
             }
         };
 
-        reset_backoff(OPERATION);
-
         self.process_2fa_response(response).await
     }
 
     pub async fn login_with_2fa<T: AsRef<str>>(
         &mut self,
         code: T,
-    ) -> Result<VRChatAuthStatus, String> {
+    ) -> Result<VRChatAuthStatus, ApiError> {
         const OPERATION: &str = "login_with_2fa";
 
-        check_rate_limit(OPERATION)?;
+        let _permit = acquire_request_slot(OPERATION, RequestPriority::Interactive).await?;
 
         log::info!("Logging in with 2FA...");
         if self.phase != VRChatAuthPhase::TwoFactorAuth {
-            return Err("Not in 2FA phase".to_string());
+            return Err("Not in 2FA phase".to_string().into());
         }
 
         let code = code.as_ref().to_string();
@@ -284,12 +285,47 @@ impl VRChatAPIClientAuthenticator {
             Ok(response) => response,
             Err(e) => {
                 log::error!("Failed to handle API response: {}", e);
-                record_rate_limit(OPERATION);
                 return Err(e);
             }
         };
 
-        reset_backoff(OPERATION);
+        self.process_2fa_response(response).await
+    }
+
+    /// Completes two-factor authentication with one of the account's recovery codes, for when
+    /// the authenticator app is not at hand
+    /// Each recovery code works only once
+    pub async fn login_with_recovery_code<T: AsRef<str>>(
+        &mut self,
+        code: T,
+    ) -> Result<VRChatAuthStatus, ApiError> {
+        const OPERATION: &str = "login_with_2fa";
+
+        let _permit = acquire_request_slot(OPERATION, RequestPriority::Interactive).await?;
+
+        log::info!("Logging in with a recovery code...");
+        if self.phase != VRChatAuthPhase::TwoFactorAuth {
+            return Err("Not in 2FA phase".to_string().into());
+        }
+
+        let body = serde_json::json!({ "code": code.as_ref().trim() }).to_string();
+
+        let response = self
+            .client
+            .post(format!("{}/auth/twofactorauth/otp/verify", API_BASE_URL))
+            .header("Content-Type", "application/json")
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to send login request: {}", e))?;
+
+        let response = match handle_api_response(response, OPERATION).await {
+            Ok(response) => response,
+            Err(e) => {
+                log::error!("Failed to handle API response: {}", e);
+                return Err(e);
+            }
+        };
 
         self.process_2fa_response(response).await
     }
@@ -305,7 +341,7 @@ impl VRChatAPIClientAuthenticator {
     async fn process_2fa_response(
         &mut self,
         response: Response,
-    ) -> Result<VRChatAuthStatus, String> {
+    ) -> Result<VRChatAuthStatus, ApiError> {
         if response.status() == StatusCode::OK {
             let text = response
                 .text()
@@ -325,7 +361,7 @@ impl VRChatAPIClientAuthenticator {
                 Some(value) => value
                     .to_str()
                     .map_err(|e| format!("Failed to convert cookie to string: {}", e))?,
-                None => return Err("No cookies found in the response".to_string()),
+                None => return Err("No cookies found in the response".to_string().into()),
             };
             let auth_cookies = AuthCookies::from_cookie_str(cookie_str);
 
@@ -345,15 +381,15 @@ impl VRChatAPIClientAuthenticator {
                 "Unknown error occurred: {}",
                 text
             ))),
-            Err(e) => Err(format!("Failed to read response text: {}", e.to_string())),
+            Err(e) => Err(format!("Failed to read response text: {}", e.to_string()).into()),
         }
     }
 }
 
-pub async fn logout(jar: &Arc<Jar>) -> Result<(), String> {
+pub async fn logout(jar: &Arc<Jar>) -> Result<(), ApiError> {
     const OPERATION: &str = "logout";
 
-    check_rate_limit(OPERATION)?;
+    let _permit = acquire_request_slot(OPERATION, RequestPriority::Interactive).await?;
 
     log::info!("Logging out...");
     let client = get_reqwest_client(&jar);
@@ -368,20 +404,17 @@ pub async fn logout(jar: &Arc<Jar>) -> Result<(), String> {
         Ok(response) => response,
         Err(e) => {
             log::error!("Failed to handle API response: {}", e);
-            record_rate_limit(OPERATION);
             return Err(e);
         }
     };
 
-    reset_backoff(OPERATION);
-
     if result.status() == StatusCode::OK {
         log::info!("Logout successful");
         return Ok(());
     }
 
     match result.text().await {
-        Ok(text) => Err(format!("Failed to logout: {}", text)),
-        Err(e) => Err(format!("Failed to read response text: {}", e.to_string())),
+        Ok(text) => Err(format!("Failed to logout: {}", text).into()),
+        Err(e) => Err(format!("Failed to read response text: {}", e.to_string()).into()),
     }
 }
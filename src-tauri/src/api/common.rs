@@ -1,11 +1,17 @@
-use chrono::Utc;
-use reqwest::{cookie::Jar, Response, StatusCode};
-use std::sync::Arc;
+use chrono::{Duration as ChronoDuration, Utc};
+use reqwest::{cookie::Jar, header::RETRY_AFTER, Method, RequestBuilder, Response, StatusCode};
+use std::collections::{BTreeMap, VecDeque};
+use std::fmt;
+use std::sync::{Arc, Mutex};
 use tauri::{AppHandle, Manager};
-use tokio::time::{sleep, Duration};
+use tauri_specta::Event;
+use tokio::sync::Notify;
+use tokio::time::{sleep, timeout, Duration, Instant};
 
-use crate::api::RateLimitStore;
-use crate::RATE_LIMIT_STORE;
+use crate::api::{ApiQueueChanged, RateLimitStatus, RateLimitStore};
+use crate::errors::ApiError;
+use crate::services::MetricsService;
+use crate::{RATE_LIMIT_STORE, REQUEST_SCHEDULER};
 
 pub const API_BASE_URL: &str = "https://api.vrchat.cloud/api/1";
 
@@ -20,18 +26,53 @@ pub fn get_reqwest_client(cookies: &Arc<Jar>) -> reqwest::Client {
 }
 
 /// Helper to handle response status and extract rate limit information
-pub async fn handle_api_response(response: Response, operation: &str) -> Result<Response, String> {
+/// A rate limited response holds the operation's requests for its backoff, and pauses
+/// background requests for the server's Retry-After, capped at `MAX_QUEUE_PAUSE`. A successful
+/// response resets the operation's backoff
+pub async fn handle_api_response(
+    response: Response,
+    operation: &str,
+) -> Result<Response, ApiError> {
     let status = response.status();
+    MetricsService::record_api_response(operation, status.as_u16());
 
     // Check for rate limit
     if status == StatusCode::TOO_MANY_REQUESTS {
-        return Err(format!("Rate limit exceeded for {}", operation));
+        let backoff_ms = record_rate_limit(operation);
+        if let Some(scheduler) = REQUEST_SCHEDULER.try_get() {
+            scheduler.hold(operation, Duration::from_millis(backoff_ms));
+            if let Some(retry_after) = retry_after(&response) {
+                scheduler.pause(retry_after.min(MAX_QUEUE_PAUSE));
+            }
+        }
+        return Err(ApiError::RateLimited {
+            message: format!("Rate limit exceeded for {}", operation),
+            retry_after: Some(backoff_ms / 1000 + 1),
+        });
+    }
+
+    // Errors such as a 5xx say nothing about whether the rate limit has passed
+    if status.is_success() {
+        reset_backoff(operation);
     }
 
     // Pass through other responses
     Ok(response)
 }
 
+/// The delay in seconds from a response's Retry-After header, if it has one
+fn retry_after(response: &Response) -> Option<Duration> {
+    let seconds = response
+        .headers()
+        .get(RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse()
+        .ok()?;
+    Some(Duration::from_secs(seconds))
+}
+
 /// Record a rate limit for an endpoint and calculate backoff
 pub fn record_rate_limit(endpoint: &str) -> u64 {
     let mut store = RATE_LIMIT_STORE.get().write().unwrap();
@@ -39,8 +80,11 @@ pub fn record_rate_limit(endpoint: &str) -> u64 {
     {
         let data = store.endpoints.entry(endpoint.to_string()).or_default();
 
-        data.last_rate_limited = Some(Utc::now());
+        let now = Utc::now();
+        data.last_rate_limited = Some(now);
         data.consecutive_failures += 1;
+        data.recent_rate_limits.retain(|t| now - *t < ChronoDuration::hours(24));
+        data.recent_rate_limits.push(now);
 
         // Calculate new backoff with exponential increase
         let base_backoff = 600000; // 10 minutes in milliseconds
@@ -69,25 +113,6 @@ pub fn record_rate_limit(endpoint: &str) -> u64 {
     temp
 }
 
-/// Check if we should wait before making a request
-pub fn should_backoff(endpoint: &str) -> Option<u64> {
-    let store = RATE_LIMIT_STORE.get().read().unwrap();
-
-    if let Some(data) = store.endpoints.get(endpoint) {
-        if let Some(last_limited) = data.last_rate_limited {
-            let elapsed = (Utc::now() - last_limited).num_milliseconds() as u64;
-
-            // If we're still in the backoff period
-            if elapsed < data.current_backoff_ms {
-                let remaining = data.current_backoff_ms - elapsed;
-                return Some(remaining);
-            }
-        }
-    }
-
-    None
-}
-
 /// Reset the backoff for an endpoint after successful request
 pub fn reset_backoff(endpoint: &str) {
     let mut store = RATE_LIMIT_STORE.get().write().unwrap();
@@ -104,16 +129,69 @@ pub fn reset_backoff(endpoint: &str) {
     }
 }
 
-/// Check if an endpoint is rate limited and return a formatted error if it is
-pub fn check_rate_limit(endpoint: &str) -> Result<(), String> {
-    if let Some(backoff_ms) = should_backoff(endpoint) {
-        let seconds = (backoff_ms / 1000) + 1; // Round up to nearest second
-        return Err(format!(
-            "Rate limit active for {}. Please try again in {} seconds.",
-            endpoint, seconds
-        ));
+/// How long until each operation's active backoff has passed
+/// Used to hold rate limited operations again after a restart, as backoffs are persisted
+pub fn remaining_backoffs() -> Vec<(String, Duration)> {
+    let store = RATE_LIMIT_STORE.get().read().unwrap();
+    let now = Utc::now();
+
+    store
+        .endpoints
+        .iter()
+        .filter_map(|(operation, data)| {
+            let next_allowed_at = data.last_rate_limited?
+                + ChronoDuration::milliseconds(data.current_backoff_ms as i64);
+            let remaining = (next_allowed_at - now).to_std().ok()?;
+            Some((operation.clone(), remaining))
+        })
+        .collect()
+}
+
+/// Get the rate limit state of every operation which has been rate limited
+pub fn get_rate_limit_status() -> Vec<RateLimitStatus> {
+    let store = RATE_LIMIT_STORE.get().read().unwrap();
+    let now = Utc::now();
+
+    let mut statuses: Vec<RateLimitStatus> = store
+        .endpoints
+        .iter()
+        .map(|(operation, data)| {
+            let next_allowed_at = data
+                .last_rate_limited
+                .map(|last| last + ChronoDuration::milliseconds(data.current_backoff_ms as i64))
+                .filter(|next| *next > now);
+            let recent_rate_limit_count = data
+                .recent_rate_limits
+                .iter()
+                .filter(|t| now - **t < ChronoDuration::hours(24))
+                .count() as u32;
+
+            RateLimitStatus {
+                operation: operation.clone(),
+                consecutive_failures: data.consecutive_failures,
+                current_backoff_ms: data.current_backoff_ms,
+                last_rate_limited: data.last_rate_limited,
+                next_allowed_at,
+                recent_rate_limit_count,
+            }
+        })
+        .collect();
+
+    statuses.sort_by(|a, b| a.operation.cmp(&b.operation));
+    statuses
+}
+
+/// Clear the backoff state of every operation
+/// This is an escape hatch for when the user is sure the rate limit has passed
+pub fn reset_rate_limits() {
+    let mut store = RATE_LIMIT_STORE.get().write().unwrap();
+    store.endpoints.clear();
+    store.save();
+    if let Some(scheduler) = REQUEST_SCHEDULER.try_get() {
+        scheduler.resume();
+        scheduler.close_circuits();
     }
-    Ok(())
+    log::info!("Reset all rate limit backoffs");
 }
 
 pub fn apply_jitter(backoff_ms: u64) -> u64 {
@@ -129,3 +207,584 @@ pub fn apply_jitter(backoff_ms: u64) -> u64 {
 
     half_backoff + jitter
 }
+
+/// Attempts made for a request which keeps failing with a transient error
+const MAX_ATTEMPTS: u32 = 3;
+
+/// Delay before the first retry, doubled for each retry after it
+const RETRY_BASE_DELAY_MS: u64 = 1000;
+
+/// Consecutive failed requests after which an operation's circuit opens
+const CIRCUIT_FAILURE_THRESHOLD: u32 = 5;
+
+/// How long an open circuit fails requests before letting a probe through
+const CIRCUIT_OPEN_DURATION: Duration = Duration::from_secs(60);
+
+/// Error of a request sent with `send_with_retry`
+#[derive(Debug)]
+pub enum RequestError {
+    /// The operation kept failing, so its requests fail without being sent for a while
+    CircuitOpen {
+        operation: String,
+        retry_in_secs: u64,
+    },
+    /// The operation is held after a rate limit
+    QueuePaused {
+        operation: String,
+        retry_in_secs: u64,
+    },
+    /// The request could not be sent or no response was received
+    Send(reqwest::Error),
+}
+
+impl fmt::Display for RequestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RequestError::CircuitOpen {
+                operation,
+                retry_in_secs,
+            } => write!(
+                f,
+                "VRChat is unavailable for {} after repeated failures, retrying in {} seconds",
+                operation, retry_in_secs
+            ),
+            RequestError::QueuePaused {
+                operation,
+                retry_in_secs,
+            } => write!(
+                f,
+                "Rate limit active for {}. Please try again in {} seconds.",
+                operation, retry_in_secs
+            ),
+            RequestError::Send(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl From<RequestError> for ApiError {
+    fn from(error: RequestError) -> Self {
+        match error {
+            RequestError::CircuitOpen { retry_in_secs, .. }
+            | RequestError::QueuePaused { retry_in_secs, .. } => ApiError::RateLimited {
+                message: error.to_string(),
+                retry_after: Some(retry_in_secs),
+            },
+            RequestError::Send(e) => ApiError::ResponseError(e.to_string()),
+        }
+    }
+}
+
+/// Tracks the failures of an operation
+/// After `CIRCUIT_FAILURE_THRESHOLD` consecutive failures the circuit opens and requests fail
+/// fast. Once `CIRCUIT_OPEN_DURATION` has passed a single probe request is let through, which
+/// closes the circuit if it succeeds and opens it again if it fails
+#[derive(Debug, Default)]
+pub struct CircuitBreaker {
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+    /// When the probe in flight was let through, if any
+    probe_started: Option<Instant>,
+}
+
+impl CircuitBreaker {
+    /// Checks whether a request may be sent
+    ///
+    /// # Returns
+    /// Ok if the request may be sent, otherwise how long until one may
+    pub fn try_acquire(&mut self, now: Instant) -> Result<(), Duration> {
+        let Some(opened_at) = self.opened_at else {
+            return Ok(());
+        };
+
+        let open_for = now.duration_since(opened_at);
+        if open_for < CIRCUIT_OPEN_DURATION {
+            return Err(CIRCUIT_OPEN_DURATION - open_for);
+        }
+
+        // A probe whose caller gave up never reports back, so it is only waited for so long
+        if let Some(started) = self.probe_started {
+            let probing_for = now.duration_since(started);
+            if probing_for < CIRCUIT_OPEN_DURATION {
+                return Err(CIRCUIT_OPEN_DURATION - probing_for);
+            }
+        }
+
+        self.probe_started = Some(now);
+        Ok(())
+    }
+
+    /// Closes the circuit after a request got a response
+    pub fn record_success(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Counts a failed request, opening the circuit if it failed too often or was a probe
+    ///
+    /// # Returns
+    /// Whether the circuit is now open
+    pub fn record_failure(&mut self, now: Instant) -> bool {
+        self.consecutive_failures += 1;
+        self.probe_started = None;
+        if self.opened_at.is_some() || self.consecutive_failures >= CIRCUIT_FAILURE_THRESHOLD {
+            self.opened_at = Some(now);
+        }
+        self.opened_at.is_some()
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.opened_at.is_some()
+    }
+}
+
+/// Server errors which usually pass, e.g. while VRChat is deploying or overloaded
+fn is_transient_status(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::REQUEST_TIMEOUT
+            | StatusCode::INTERNAL_SERVER_ERROR
+            | StatusCode::BAD_GATEWAY
+            | StatusCode::SERVICE_UNAVAILABLE
+            | StatusCode::GATEWAY_TIMEOUT
+    )
+}
+
+/// Sends a request, retrying transient failures with jittered exponential backoff
+/// POST and PATCH requests are sent once, as a retry could create something twice
+/// Requests whose operation has failed too often fail fast, see `CircuitBreaker`
+/// Each attempt waits for its own slot in the request queue, held until its response arrives,
+/// so retries count toward the request budget and the slot is free while waiting to retry
+///
+/// # Arguments
+/// * `operation` - The operation the request is for, as used for rate limiting
+/// * `priority` - The priority of the request in the request queue
+/// * `request` - The request to send
+///
+/// # Returns
+/// The response, which may still have an error status once the retries are used up
+///
+/// # Errors
+/// Returns `RequestError::CircuitOpen` if the operation's circuit is open
+/// Returns `RequestError::QueuePaused` if the operation is held after a rate limit
+/// Returns `RequestError::Send` if the request could not be sent
+pub async fn send_with_retry(
+    operation: &str,
+    priority: RequestPriority,
+    request: RequestBuilder,
+) -> Result<Response, RequestError> {
+    send_queued(REQUEST_SCHEDULER.try_get(), operation, priority, request).await
+}
+
+/// Sends a request through the given queue, or unqueued if there is none, e.g. in tests
+async fn send_queued(
+    scheduler: Option<&RequestScheduler>,
+    operation: &str,
+    priority: RequestPriority,
+    request: RequestBuilder,
+) -> Result<Response, RequestError> {
+    let (client, request) = request.build_split();
+    let mut request = request.map_err(RequestError::Send)?;
+    let retryable = !matches!(*request.method(), Method::POST | Method::PATCH);
+
+    let mut attempt = 1;
+    loop {
+        let permit = match scheduler {
+            Some(scheduler) => {
+                scheduler.acquire_circuit(operation)?;
+                Some(scheduler.acquire_slot(operation, priority).await?)
+            }
+            None => None,
+        };
+
+        // Streamed bodies cannot be cloned, so those requests are not retried either
+        let next = if retryable && attempt < MAX_ATTEMPTS {
+            request.try_clone()
+        } else {
+            None
+        };
+
+        let result = client.execute(request).await;
+        drop(permit);
+        let transient = match &result {
+            Ok(response) => is_transient_status(response.status()),
+            Err(e) => e.is_timeout() || e.is_connect(),
+        };
+        if !transient {
+            if let (Some(scheduler), Ok(_)) = (scheduler, &result) {
+                scheduler.record_circuit_success(operation);
+            }
+            return result.map_err(RequestError::Send);
+        }
+
+        let Some(next) = next else {
+            if let Some(scheduler) = scheduler {
+                scheduler.record_circuit_failure(operation);
+            }
+            return result.map_err(RequestError::Send);
+        };
+
+        let delay = apply_jitter(RETRY_BASE_DELAY_MS << (attempt - 1));
+        log::warn!(
+            "Transient failure for {} on attempt {}, retrying in {}ms",
+            operation,
+            attempt,
+            delay
+        );
+        sleep(Duration::from_millis(delay)).await;
+        request = next;
+        attempt += 1;
+    }
+}
+
+/// Default global budget of VRChat API requests per minute
+pub const DEFAULT_REQUESTS_PER_MINUTE: u32 = 60;
+
+/// Default number of VRChat API requests in flight at once
+pub const DEFAULT_CONCURRENT_REQUESTS: u32 = 4;
+
+/// Most VRChat API requests allowed in flight at once, to stay a polite client
+pub const MAX_CONCURRENT_REQUESTS: u32 = 5;
+
+/// The window over which the request budget is enforced
+const BUDGET_WINDOW: Duration = Duration::from_secs(60);
+
+/// Longest a rate limited response pauses background requests, whatever its Retry-After
+const MAX_QUEUE_PAUSE: Duration = Duration::from_secs(5);
+
+/// Priority of a request in the global request queue
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RequestPriority {
+    /// Triggered directly by the user, jumps ahead of background requests
+    Interactive,
+    /// Refreshes and other requests nobody is waiting on
+    Background,
+}
+
+struct SchedulerState {
+    requests_per_minute: u32,
+    dispatched: VecDeque<Instant>,
+    waiting_interactive: u32,
+    waiting_background: u32,
+    max_in_flight: u32,
+    in_flight: u32,
+    /// Set after a rate limited response, no background request is dispatched until then
+    paused_until: Option<Instant>,
+    /// Operations which were rate limited, their requests fail fast until the given time
+    held: BTreeMap<String, Instant>,
+}
+
+/// Sends all VRChat API calls through a single queue
+/// At most `max_in_flight` requests are in flight at a time, at most `requests_per_minute`
+/// requests are sent per minute, and interactive requests are always dispatched before
+/// background ones. After a rate limited response the operation's requests fail fast until
+/// its backoff has passed, and background requests wait for a short pause. Operations which
+/// keep failing get their circuit opened, see `CircuitBreaker`
+pub struct RequestScheduler {
+    state: Mutex<SchedulerState>,
+    /// Circuit breaker of every operation which has failed, by operation
+    circuits: Mutex<BTreeMap<String, CircuitBreaker>>,
+    notify: Notify,
+    app_handle: Option<AppHandle>,
+}
+
+/// Held while a request is in flight, releases the queue when dropped
+pub struct RequestPermit<'a> {
+    scheduler: &'a RequestScheduler,
+    /// The operation and when it was dispatched, for usage metrics
+    dispatched: Option<(String, Instant)>,
+}
+
+impl Drop for RequestPermit<'_> {
+    fn drop(&mut self) {
+        if let Some((operation, dispatched)) = &self.dispatched {
+            MetricsService::record_api_request(operation, dispatched.elapsed());
+        }
+        let mut state = self.scheduler.state.lock().unwrap();
+        state.in_flight = state.in_flight.saturating_sub(1);
+        drop(state);
+        self.scheduler.notify.notify_waiters();
+    }
+}
+
+/// Counts a request as waiting until it is dispatched or cancelled
+struct WaitingGuard<'a> {
+    scheduler: &'a RequestScheduler,
+    priority: RequestPriority,
+}
+
+impl Drop for WaitingGuard<'_> {
+    fn drop(&mut self) {
+        self.scheduler.update_waiting(self.priority, false);
+    }
+}
+
+impl RequestScheduler {
+    pub fn new(
+        app_handle: Option<AppHandle>,
+        requests_per_minute: u32,
+        max_in_flight: u32,
+    ) -> Self {
+        Self {
+            state: Mutex::new(SchedulerState {
+                requests_per_minute: requests_per_minute.max(1),
+                dispatched: VecDeque::new(),
+                waiting_interactive: 0,
+                waiting_background: 0,
+                max_in_flight: max_in_flight.clamp(1, MAX_CONCURRENT_REQUESTS),
+                in_flight: 0,
+                paused_until: None,
+                held: BTreeMap::new(),
+            }),
+            circuits: Mutex::new(BTreeMap::new()),
+            notify: Notify::new(),
+            app_handle,
+        }
+    }
+
+    pub fn requests_per_minute(&self) -> u32 {
+        self.state.lock().unwrap().requests_per_minute
+    }
+
+    pub fn set_requests_per_minute(&self, requests_per_minute: u32) {
+        self.state.lock().unwrap().requests_per_minute = requests_per_minute.max(1);
+        self.notify.notify_waiters();
+    }
+
+    pub fn max_in_flight(&self) -> u32 {
+        self.state.lock().unwrap().max_in_flight
+    }
+
+    pub fn set_max_in_flight(&self, max_in_flight: u32) {
+        self.state.lock().unwrap().max_in_flight = max_in_flight.clamp(1, MAX_CONCURRENT_REQUESTS);
+        self.notify.notify_waiters();
+    }
+
+    /// Stops dispatching background requests for the given duration, e.g. after a rate limited
+    /// response. Interactive requests are still dispatched. An existing longer pause is kept
+    pub fn pause(&self, duration: Duration) {
+        let until = Instant::now() + duration;
+        let mut state = self.state.lock().unwrap();
+        state.paused_until = Some(state.paused_until.unwrap_or(until).max(until));
+        log::warn!("Background requests paused for {}ms", duration.as_millis());
+    }
+
+    /// Fails the operation's requests fast for the given duration, e.g. for its backoff after a
+    /// rate limited response. An existing longer hold is kept
+    pub fn hold(&self, operation: &str, duration: Duration) {
+        let until = Instant::now() + duration;
+        let mut state = self.state.lock().unwrap();
+        let held_until = state.held.entry(operation.to_string()).or_insert(until);
+        *held_until = (*held_until).max(until);
+        log::warn!(
+            "Requests for {} held for {}ms",
+            operation,
+            duration.as_millis()
+        );
+    }
+
+    /// Lifts the pause and every hold before they have passed
+    pub fn resume(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.paused_until = None;
+        state.held.clear();
+        drop(state);
+        self.notify.notify_waiters();
+    }
+
+    /// Closes every open circuit before its cooldown has passed
+    pub fn close_circuits(&self) {
+        self.circuits.lock().unwrap().clear();
+    }
+
+    /// Sends a request through this queue, see `send_with_retry`
+    pub async fn send_with_retry(
+        &self,
+        operation: &str,
+        priority: RequestPriority,
+        request: RequestBuilder,
+    ) -> Result<Response, RequestError> {
+        send_queued(Some(self), operation, priority, request).await
+    }
+
+    fn acquire_circuit(&self, operation: &str) -> Result<(), RequestError> {
+        let mut circuits = self.circuits.lock().unwrap();
+        let Some(breaker) = circuits.get_mut(operation) else {
+            return Ok(());
+        };
+        breaker
+            .try_acquire(Instant::now())
+            .map_err(|wait| RequestError::CircuitOpen {
+                operation: operation.to_string(),
+                retry_in_secs: wait.as_secs() + 1,
+            })
+    }
+
+    fn record_circuit_success(&self, operation: &str) {
+        if let Some(breaker) = self.circuits.lock().unwrap().remove(operation) {
+            if breaker.is_open() {
+                log::info!(
+                    "Circuit closed for {}, requests are succeeding again",
+                    operation
+                );
+            }
+        }
+    }
+
+    fn record_circuit_failure(&self, operation: &str) {
+        let mut circuits = self.circuits.lock().unwrap();
+        let breaker = circuits.entry(operation.to_string()).or_default();
+        if breaker.record_failure(Instant::now()) {
+            log::warn!(
+                "Circuit open for {} after {} consecutive failures",
+                operation,
+                breaker.consecutive_failures
+            );
+        }
+    }
+
+    /// Waits for the queue to allow a request for the operation, counting it in usage metrics
+    async fn acquire_slot(
+        &self,
+        operation: &str,
+        priority: RequestPriority,
+    ) -> Result<RequestPermit<'_>, RequestError> {
+        let mut permit =
+            self.acquire(operation, priority)
+                .await
+                .map_err(|wait| RequestError::QueuePaused {
+                    operation: operation.to_string(),
+                    retry_in_secs: wait.as_secs() + 1,
+                })?;
+        permit.dispatched = Some((operation.to_string(), Instant::now()));
+        Ok(permit)
+    }
+
+    fn update_waiting(&self, priority: RequestPriority, increment: bool) {
+        let (interactive, background) = {
+            let mut state = self.state.lock().unwrap();
+            let counter = match priority {
+                RequestPriority::Interactive => &mut state.waiting_interactive,
+                RequestPriority::Background => &mut state.waiting_background,
+            };
+            if increment {
+                *counter += 1;
+            } else {
+                *counter = counter.saturating_sub(1);
+            }
+            (state.waiting_interactive, state.waiting_background)
+        };
+
+        // A dispatched or cancelled interactive request may unblock background requests
+        self.notify.notify_waiters();
+
+        if let Some(app_handle) = &self.app_handle {
+            let event = ApiQueueChanged {
+                interactive,
+                background,
+            };
+            if let Err(e) = event.emit(app_handle) {
+                log::error!("Failed to emit API queue event: {}", e);
+            }
+        }
+    }
+
+    /// Waits until a request for the operation with the given priority may be sent
+    ///
+    /// # Errors
+    /// Returns how long until the operation's hold has passed if it is held
+    pub async fn acquire(
+        &self,
+        operation: &str,
+        priority: RequestPriority,
+    ) -> Result<RequestPermit<'_>, Duration> {
+        self.update_waiting(priority, true);
+        let waiting = WaitingGuard {
+            scheduler: self,
+            priority,
+        };
+
+        loop {
+            // Register for wake-ups before checking the state, so a release in between is not missed
+            let notified = self.notify.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
+
+            let wait = {
+                let mut state = self.state.lock().unwrap();
+                let now = Instant::now();
+                if let Some(held_until) = state.held.get(operation).copied() {
+                    if held_until > now {
+                        return Err(held_until - now);
+                    }
+                    state.held.remove(operation);
+                }
+                let pause_wait = match state.paused_until {
+                    Some(paused_until) if paused_until > now => Some(paused_until - now),
+                    Some(_) => {
+                        state.paused_until = None;
+                        None
+                    }
+                    None => None,
+                };
+                while state
+                    .dispatched
+                    .front()
+                    .is_some_and(|t| now.duration_since(*t) >= BUDGET_WINDOW)
+                {
+                    state.dispatched.pop_front();
+                }
+
+                let my_turn = state.in_flight < state.max_in_flight
+                    && (priority == RequestPriority::Interactive
+                        || (state.waiting_interactive == 0 && pause_wait.is_none()));
+                let within_budget = (state.dispatched.len() as u32) < state.requests_per_minute;
+
+                if my_turn && within_budget {
+                    state.in_flight += 1;
+                    state.dispatched.push_back(now);
+                    drop(state);
+                    drop(waiting);
+                    return Ok(RequestPermit {
+                        scheduler: self,
+                        dispatched: None,
+                    });
+                }
+
+                if priority == RequestPriority::Background && pause_wait.is_some() {
+                    pause_wait
+                } else if my_turn {
+                    state
+                        .dispatched
+                        .front()
+                        .map(|t| BUDGET_WINDOW.saturating_sub(now.duration_since(*t)))
+                } else {
+                    None
+                }
+            };
+
+            match wait {
+                Some(wait) => {
+                    let _ = timeout(wait, notified).await;
+                }
+                None => notified.await,
+            }
+        }
+    }
+}
+
+/// Waits for the global request queue to allow a request for the given operation
+/// Returns None if the scheduler has not been initialized, e.g. in tests
+///
+/// Requests sent with `send_with_retry` take their slots there instead
+///
+/// # Errors
+/// Returns `ApiError::RateLimited` with the retry delay if the operation is held after a rate limit
+pub async fn acquire_request_slot(
+    operation: &str,
+    priority: RequestPriority,
+) -> Result<Option<RequestPermit<'static>>, ApiError> {
+    let Some(scheduler) = REQUEST_SCHEDULER.try_get() else {
+        return Ok(None);
+    };
+    let permit = scheduler.acquire_slot(operation, priority).await?;
+    Ok(Some(permit))
+}
@@ -1,12 +1,18 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use specta::Type;
 use std::{collections::HashMap, fs, path::PathBuf};
 
+use crate::services::Persistence;
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct RateLimitData {
     pub last_rate_limited: Option<DateTime<Utc>>,
     pub consecutive_failures: u32,
     pub current_backoff_ms: u64,
+    /// Times at which a 429 was received, pruned to the last 24 hours
+    #[serde(default)]
+    pub recent_rate_limits: Vec<DateTime<Utc>>,
 }
 
 impl Default for RateLimitData {
@@ -15,10 +21,36 @@ impl Default for RateLimitData {
             last_rate_limited: None,
             consecutive_failures: 0,
             current_backoff_ms: 600000, // 10 minutes
+            recent_rate_limits: vec![],
         }
     }
 }
 
+/// Emitted whenever the number of API requests waiting in the global queue changes
+#[derive(Debug, Clone, Serialize, Type, tauri_specta::Event)]
+pub struct ApiQueueChanged {
+    pub interactive: u32,
+    pub background: u32,
+}
+
+/// Rate limit state of a single API operation, as shown to the user
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct RateLimitStatus {
+    pub operation: String,
+    #[serde(rename = "consecutiveFailures")]
+    pub consecutive_failures: u32,
+    #[serde(rename = "currentBackoffMs")]
+    pub current_backoff_ms: u64,
+    #[serde(rename = "lastRateLimited")]
+    pub last_rate_limited: Option<DateTime<Utc>>,
+    /// When requests for this operation will be allowed again, None if they are allowed now
+    #[serde(rename = "nextAllowedAt")]
+    pub next_allowed_at: Option<DateTime<Utc>>,
+    /// Number of 429 responses received in the last 24 hours
+    #[serde(rename = "recentRateLimitCount")]
+    pub recent_rate_limit_count: u32,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct RateLimitStore {
     pub endpoints: HashMap<String, RateLimitData>,
@@ -64,9 +96,8 @@ impl RateLimitStore {
                     }
                 }
 
-                if let Err(e) = fs::write(path, data) {
-                    log::error!("Failed to save rate limit data: {}", e);
-                }
+                // Saved on every request, so it is not worth waiting for
+                Persistence::write_deferred(path.clone(), data);
             }
         }
     }
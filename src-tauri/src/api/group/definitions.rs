@@ -89,6 +89,23 @@ pub struct GroupInstancePermissionInfo {
     pub roles: Vec<GroupRole>,
 }
 
+/// An instance hosted by a group, as listed by the group's instances endpoint
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct GroupInstance {
+    /// The world and instance ID, e.g. "wrld_...:12345~group(grp_...)"
+    pub location: String,
+}
+
+impl GroupInstance {
+    /// The ID of the world the instance is of
+    pub fn world_id(&self) -> Option<&str> {
+        self.location
+            .split(':')
+            .next()
+            .filter(|id| id.starts_with("wrld_"))
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, Type)]
 pub struct GroupDetails {
     pub id: String,
@@ -158,3 +175,26 @@ pub enum GroupPermission {
     GroupRolesAssign,
     GroupRolesManage,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_group_instance_world_id() {
+        let instance: GroupInstance = serde_json::from_str(
+            r#"{
+                "instanceId": "12345~group(grp_1)~groupAccessType(public)",
+                "location": "wrld_1:12345~group(grp_1)~groupAccessType(public)",
+                "memberCount": 3
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(instance.world_id(), Some("wrld_1"));
+
+        let offline = GroupInstance {
+            location: "offline".to_string(),
+        };
+        assert_eq!(offline.world_id(), None);
+    }
+}
@@ -3,56 +3,52 @@ use std::sync::Arc;
 use reqwest::cookie::Jar;
 
 use crate::api::common::{
-    check_rate_limit, get_reqwest_client, handle_api_response, record_rate_limit, reset_backoff,
-    API_BASE_URL,
+    get_reqwest_client, handle_api_response, send_with_retry, RequestPriority, API_BASE_URL,
 };
+use crate::errors::ApiError;
 
 use super::definitions::{
-    GroupDetails, GroupInstanceCreatePermission, GroupInstancePermissionInfo, GroupPermission,
-    UserGroup,
+    GroupDetails, GroupInstance, GroupInstanceCreatePermission, GroupInstancePermissionInfo,
+    GroupPermission, UserGroup,
 };
 
 pub async fn get_user_groups<J: Into<Arc<Jar>>>(
     cookie: J,
     user_id: &str,
-) -> Result<Vec<UserGroup>, String> {
+) -> Result<Vec<UserGroup>, ApiError> {
     const OPERATION: &str = "get_user_groups";
 
-    check_rate_limit(OPERATION)?;
-
     let cookie_jar: Arc<Jar> = cookie.into();
     let client = get_reqwest_client(&cookie_jar);
 
     log::info!("Fetching groups for user: {}", user_id);
 
     if user_id.contains("/") {
-        return Err("User ID cannot contain '/'".to_string());
+        return Err("User ID cannot contain '/'".to_string().into());
     }
 
-    let result = client
-        .get(format!("{API_BASE_URL}/users/{user_id}/groups"))
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
+    let result = send_with_retry(
+        OPERATION,
+        RequestPriority::Background,
+        client.get(format!("{API_BASE_URL}/users/{user_id}/groups")),
+    )
+    .await?;
 
     let result = match handle_api_response(result, OPERATION).await {
         Ok(response) => response,
         Err(e) => {
             log::error!("Failed to handle API response: {}", e);
-            record_rate_limit(OPERATION);
             return Err(e);
         }
     };
 
-    reset_backoff(OPERATION);
-
     log::info!("API Response status: {}", result.status());
 
     let text = result.text().await;
 
     if let Err(e) = text {
         log::info!("Failed to read response text: {}", e);
-        return Err(format!("Failed to get user groups: {}", e.to_string()));
+        return Err(format!("Failed to get user groups: {}", e.to_string()).into());
     }
 
     let text = text.unwrap();
@@ -65,46 +61,84 @@ pub async fn get_user_groups<J: Into<Arc<Jar>>>(
         Err(e) => {
             log::info!("Failed to parse user groups: {}", e);
             log::info!("Response that failed parsing: {}", text);
-            return Err(format!("Failed to parse user groups: {}", e.to_string()));
+            return Err(format!("Failed to parse user groups: {}", e.to_string()).into());
         }
     };
 
     Ok(parsed)
 }
 
+/// Fetches the instances a group is hosting, which the user can see as a member
+pub async fn get_group_instances<J: Into<Arc<Jar>>>(
+    cookie: J,
+    group_id: &str,
+) -> Result<Vec<GroupInstance>, ApiError> {
+    const OPERATION: &str = "get_group_instances";
+
+    if group_id.contains('/') {
+        return Err("Group ID cannot contain '/'".to_string().into());
+    }
+
+    let cookie_jar: Arc<Jar> = cookie.into();
+    let client = get_reqwest_client(&cookie_jar);
+
+    let result = send_with_retry(
+        OPERATION,
+        RequestPriority::Background,
+        client.get(format!("{API_BASE_URL}/groups/{group_id}/instances")),
+    )
+    .await
+    .map_err(|e| ApiError::from(e).context("Failed to fetch group instances"))?;
+
+    let result = match handle_api_response(result, OPERATION).await {
+        Ok(response) => response,
+        Err(e) => {
+            log::error!("Failed to handle API response: {}", e);
+            return Err(e);
+        }
+    };
+
+    let text = result
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read response: {}", e))?;
+
+    serde_json::from_str(&text).map_err(|e| {
+        log::info!("Response that failed parsing: {}", text);
+        format!("Failed to parse group instances: {}", e).into()
+    })
+}
+
 pub async fn get_permission_for_create_group_instance(
     cookie: Arc<Jar>,
     group_id: &str,
-) -> Result<GroupInstancePermissionInfo, String> {
+) -> Result<GroupInstancePermissionInfo, ApiError> {
     const OPERATION: &str = "get_permission_for_create_group_instance";
 
-    check_rate_limit(OPERATION)?;
-
     log::info!("Fetching permissions for group: {}", group_id);
     let client = get_reqwest_client(&cookie);
 
-    let result = client
-        .get(format!(
+    let result = send_with_retry(
+        OPERATION,
+        RequestPriority::Interactive,
+        client.get(format!(
             "{API_BASE_URL}/groups/{group_id}?includeRoles=true"
-        ))
-        .send()
-        .await
-        .map_err(|e| {
-            log::info!("Failed to send request: {}", e);
-            format!("Failed to fetch group: {}", e)
-        })?;
+        )),
+    )
+    .await
+    .map_err(|e| {
+        log::info!("Failed to send request: {}", e);
+        format!("Failed to fetch group: {}", e)
+    })?;
 
     let result = match handle_api_response(result, OPERATION).await {
         Ok(response) => response,
         Err(e) => {
             log::error!("Failed to handle API response: {}", e);
-            record_rate_limit(OPERATION);
             return Err(e);
         }
     };
 
-    reset_backoff(OPERATION);
-
     log::info!("API Response status: {}", result.status());
 
     let text = result.text().await.map_err(|e| {
@@ -136,7 +170,8 @@ pub async fn get_permission_for_create_group_instance(
                 e,
                 e.line(),
                 e.column()
-            ));
+            )
+            .into());
         }
     };
 
@@ -1,6 +1,7 @@
 mod definitions;
 mod logic;
 
+pub use definitions::GroupInstance;
 pub use definitions::GroupInstanceCreateAllowedType;
 pub use definitions::GroupInstanceCreatePermission;
 pub use definitions::GroupInstancePermissionInfo;
@@ -8,5 +9,6 @@ pub use definitions::GroupMemberVisibility;
 pub use definitions::GroupRole;
 pub use definitions::UserGroup;
 
+pub use logic::get_group_instances;
 pub use logic::get_permission_for_create_group_instance;
 pub use logic::get_user_groups;
@@ -4,23 +4,21 @@ use reqwest::cookie::Jar;
 
 use crate::api::{
     common::{
-        check_rate_limit, get_reqwest_client, handle_api_response, record_rate_limit,
-        reset_backoff, API_BASE_URL,
+        get_reqwest_client, handle_api_response, send_with_retry, RequestPriority, API_BASE_URL,
     },
     instance::definitions::GetInstanceShortNameResponse,
     world,
 };
+use crate::errors::ApiError;
 
 use super::definitions::{CreateInstanceRequest, Instance};
 
 pub async fn create_instance<J: Into<Arc<Jar>>>(
     cookie: J,
     request: CreateInstanceRequest,
-) -> Result<Instance, String> {
+) -> Result<Instance, ApiError> {
     const OPERATION: &str = "create_instance";
 
-    check_rate_limit(OPERATION)?;
-
     let cookie_jar: Arc<Jar> = cookie.into();
     let client = get_reqwest_client(&cookie_jar);
 
@@ -28,29 +26,29 @@ pub async fn create_instance<J: Into<Arc<Jar>>>(
         Ok(body) => body,
         Err(e) => {
             log::info!("Failed to serialize request: {}", e.to_string());
-            return Err(format!("Failed to serialize request: {}", e.to_string()));
+            return Err(format!("Failed to serialize request: {}", e.to_string()).into());
         }
     };
 
-    let result = client
-        .post(format!("{API_BASE_URL}/instances"))
-        .header("Content-Type", "application/json")
-        .body(body)
-        .send()
-        .await
-        .map_err(|e| format!("Failed to send create instance request: {}", e))?;
+    let result = send_with_retry(
+        OPERATION,
+        RequestPriority::Interactive,
+        client
+            .post(format!("{API_BASE_URL}/instances"))
+            .header("Content-Type", "application/json")
+            .body(body),
+    )
+    .await
+    .map_err(|e| ApiError::from(e).context("Failed to send create instance request"))?;
 
     let result = match handle_api_response(result, OPERATION).await {
         Ok(response) => response,
         Err(e) => {
             log::error!("Failed to handle API response: {}", e);
-            record_rate_limit(OPERATION);
             return Err(e);
         }
     };
 
-    reset_backoff(OPERATION);
-
     let text = result
         .text()
         .await
@@ -60,7 +58,7 @@ pub async fn create_instance<J: Into<Arc<Jar>>>(
         Err(e) => {
             log::info!("Failed to parse instance: {}", e.to_string());
             log::info!("Response: {text}");
-            return Err(format!("Failed to parse instance: {}", e.to_string()));
+            return Err(format!("Failed to parse instance: {}", e.to_string()).into());
         }
     };
 
@@ -71,32 +69,25 @@ pub async fn get_instance_short_name<J: Into<Arc<Jar>>>(
     cookie: J,
     world_id: &str,
     instance_id: &str,
-) -> Result<String, String> {
+) -> Result<String, ApiError> {
     const OPERATION: &str = "get_instance_short_name";
 
-    check_rate_limit(OPERATION)?;
-
     let cookie_jar: Arc<Jar> = cookie.into();
     let client = get_reqwest_client(&cookie_jar);
 
     let url = format!("{API_BASE_URL}/instances/{world_id}:{instance_id}/shortName");
-    let result = client
-        .get(&url)
-        .send()
+    let result = send_with_retry(OPERATION, RequestPriority::Interactive, client.get(&url))
         .await
-        .map_err(|e| format!("Failed to send get instance short name request: {}", e))?;
+        .map_err(|e| ApiError::from(e).context("Failed to send get instance short name request"))?;
 
     let result = match handle_api_response(result, OPERATION).await {
         Ok(response) => response,
         Err(e) => {
             log::error!("Failed to handle API response: {}", e);
-            record_rate_limit(OPERATION);
             return Err(e);
         }
     };
 
-    reset_backoff(OPERATION);
-
     let text = result
         .text()
         .await
@@ -112,7 +103,8 @@ pub async fn get_instance_short_name<J: Into<Arc<Jar>>>(
             return Err(format!(
                 "Failed to parse get instance short name response: {}",
                 e.to_string()
-            ));
+            )
+            .into());
         }
     };
 
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use specta::Type;
 
 #[derive(Debug, Deserialize)]
 pub struct SelfInviteResponse {
@@ -32,3 +33,102 @@ pub enum NotificationType {
     #[serde(rename = "votetokick")]
     VoteToKick,
 }
+
+/// One of the user's saved invite messages
+/// VRChat keeps 12 slots per message type, which can be edited once an hour each
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct InviteMessage {
+    pub id: String,
+    pub slot: u8,
+    pub message: String,
+    pub message_type: InviteMessageType,
+    #[serde(default)]
+    pub can_be_updated: bool,
+    #[serde(default)]
+    pub remaining_cooldown_minutes: u32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub enum InviteMessageType {
+    /// Sent with an invite
+    Message,
+    /// Sent when declining an invite request
+    Response,
+    /// Sent with a request for an invite
+    Request,
+    /// Sent when declining an invite
+    RequestResponse,
+}
+
+impl InviteMessageType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            InviteMessageType::Message => "message",
+            InviteMessageType::Response => "response",
+            InviteMessageType::Request => "request",
+            InviteMessageType::RequestResponse => "requestResponse",
+        }
+    }
+}
+
+/// Number of invite message slots per message type
+pub const INVITE_MESSAGE_SLOTS: u8 = 12;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct InviteRequest {
+    pub instance_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message_slot: Option<u8>,
+}
+
+#[derive(Debug, Serialize)]
+pub(super) struct UpdateInviteMessageRequest {
+    pub message: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_invite_request_omits_default_slot() {
+        let request = InviteRequest {
+            instance_id: "wrld_1:12345".to_string(),
+            message_slot: None,
+        };
+        assert_eq!(
+            serde_json::to_string(&request).unwrap(),
+            r#"{"instanceId":"wrld_1:12345"}"#
+        );
+
+        let request = InviteRequest {
+            instance_id: "wrld_1:12345".to_string(),
+            message_slot: Some(3),
+        };
+        assert_eq!(
+            serde_json::to_string(&request).unwrap(),
+            r#"{"instanceId":"wrld_1:12345","messageSlot":3}"#
+        );
+    }
+
+    #[test]
+    fn test_parse_invite_message() {
+        let json = r#"{
+            "canBeUpdated": false,
+            "id": "invm_1",
+            "message": "Event starting now!",
+            "messageType": "message",
+            "remainingCooldownMinutes": 42,
+            "slot": 2,
+            "updatedAt": "2024-01-01T00:00:00.000Z"
+        }"#;
+        let message: InviteMessage = serde_json::from_str(json).unwrap();
+        assert_eq!(message.slot, 2);
+        assert_eq!(message.message_type, InviteMessageType::Message);
+        assert_eq!(message.remaining_cooldown_minutes, 42);
+        assert!(!message.can_be_updated);
+    }
+}
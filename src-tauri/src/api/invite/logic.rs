@@ -1,8 +1,10 @@
-use super::definitions::SelfInviteResponse;
+use super::definitions::{
+    InviteMessage, InviteMessageType, InviteRequest, SelfInviteResponse, UpdateInviteMessageRequest,
+};
 use crate::api::common::{
-    check_rate_limit, get_reqwest_client, handle_api_response, record_rate_limit, reset_backoff,
-    API_BASE_URL,
+    get_reqwest_client, handle_api_response, send_with_retry, RequestPriority, API_BASE_URL,
 };
+use crate::errors::ApiError;
 use reqwest::cookie::Jar;
 use std::sync::Arc;
 
@@ -10,38 +12,34 @@ pub async fn invite_self_to_instance<J: Into<Arc<Jar>>>(
     cookie: J,
     world_id: &str,
     instance_id: &str,
-) -> Result<SelfInviteResponse, String> {
+) -> Result<SelfInviteResponse, ApiError> {
     const OPERATION: &str = "invite_self_to_instance";
 
-    check_rate_limit(OPERATION)?;
-
     let cookie_jar: Arc<Jar> = cookie.into();
     let client = get_reqwest_client(&cookie_jar);
 
-    let result = client
-        .post(format!(
+    let result = send_with_retry(
+        OPERATION,
+        RequestPriority::Interactive,
+        client.post(format!(
             "{}/invite/myself/to/{}:{}",
             API_BASE_URL, world_id, instance_id
-        ))
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
+        )),
+    )
+    .await?;
 
     let result = match handle_api_response(result, OPERATION).await {
         Ok(response) => response,
         Err(e) => {
             log::error!("Failed to handle API response: {}", e);
-            record_rate_limit(OPERATION);
             return Err(e);
         }
     };
 
-    reset_backoff(OPERATION);
-
     let text = result.text().await;
 
     if let Err(e) = text {
-        return Err(format!("Failed to send invite request: {}", e.to_string()));
+        return Err(format!("Failed to send invite request: {}", e.to_string()).into());
     }
 
     let text = text.unwrap();
@@ -51,12 +49,152 @@ pub async fn invite_self_to_instance<J: Into<Arc<Jar>>>(
         Err(e) => {
             log::info!("Failed to parse invite response: {}", e.to_string());
             log::info!("Response: {}", text);
-            return Err(format!(
-                "Failed to parse invite response: {}",
-                e.to_string()
-            ));
+            return Err(format!("Failed to parse invite response: {}", e.to_string()).into());
         }
     };
 
     Ok(response)
 }
+
+/// Invites a user to an instance, with the message in `message_slot` if given
+/// The invite has no message when the slot is None
+pub async fn invite_user_to_instance<J: Into<Arc<Jar>>>(
+    cookie: J,
+    user_id: &str,
+    world_id: &str,
+    instance_id: &str,
+    message_slot: Option<u8>,
+) -> Result<(), ApiError> {
+    const OPERATION: &str = "invite_user_to_instance";
+
+    let cookie_jar: Arc<Jar> = cookie.into();
+    let client = get_reqwest_client(&cookie_jar);
+
+    let request = InviteRequest {
+        instance_id: format!("{}:{}", world_id, instance_id),
+        message_slot,
+    };
+    let body = serde_json::to_string(&request)
+        .map_err(|e| format!("Failed to serialize request: {}", e))?;
+
+    let result = send_with_retry(
+        OPERATION,
+        RequestPriority::Background,
+        client
+            .post(format!("{}/invite/{}", API_BASE_URL, user_id))
+            .header("Content-Type", "application/json")
+            .body(body),
+    )
+    .await
+    .map_err(|e| ApiError::from(e).context("Failed to send invite request"))?;
+
+    if let Err(e) = handle_api_response(result, OPERATION).await {
+        log::error!("Failed to handle API response: {}", e);
+        return Err(e);
+    }
+
+    Ok(())
+}
+
+/// Gets the user's saved messages of a type, one per slot
+pub async fn get_invite_messages<J: Into<Arc<Jar>>>(
+    cookie: J,
+    user_id: &str,
+    message_type: InviteMessageType,
+) -> Result<Vec<InviteMessage>, ApiError> {
+    const OPERATION: &str = "get_invite_messages";
+
+    let cookie_jar: Arc<Jar> = cookie.into();
+    let client = get_reqwest_client(&cookie_jar);
+
+    let result = send_with_retry(
+        OPERATION,
+        RequestPriority::Background,
+        client.get(format!(
+            "{}/message/{}/{}",
+            API_BASE_URL,
+            user_id,
+            message_type.as_str()
+        )),
+    )
+    .await
+    .map_err(|e| ApiError::from(e).context("Failed to fetch invite messages"))?;
+
+    let result = match handle_api_response(result, OPERATION).await {
+        Ok(response) => response,
+        Err(e) => {
+            log::error!("Failed to handle API response: {}", e);
+            return Err(e);
+        }
+    };
+
+    parse_invite_messages(result).await
+}
+
+/// Replaces the message in a slot
+/// VRChat rejects the change if the slot was edited less than an hour ago
+///
+/// # Returns
+/// All of the user's messages of the type, after the change
+pub async fn update_invite_message<J: Into<Arc<Jar>>>(
+    cookie: J,
+    user_id: &str,
+    message_type: InviteMessageType,
+    slot: u8,
+    message: String,
+) -> Result<Vec<InviteMessage>, ApiError> {
+    const OPERATION: &str = "update_invite_message";
+
+    let cookie_jar: Arc<Jar> = cookie.into();
+    let client = get_reqwest_client(&cookie_jar);
+
+    let body = serde_json::to_string(&UpdateInviteMessageRequest { message })
+        .map_err(|e| format!("Failed to serialize request: {}", e))?;
+
+    let result = send_with_retry(
+        OPERATION,
+        RequestPriority::Background,
+        client
+            .put(format!(
+                "{}/message/{}/{}/{}",
+                API_BASE_URL,
+                user_id,
+                message_type.as_str(),
+                slot
+            ))
+            .header("Content-Type", "application/json")
+            .body(body),
+    )
+    .await
+    .map_err(|e| ApiError::from(e).context("Failed to update invite message"))?;
+
+    let result = match handle_api_response(result, OPERATION).await {
+        Ok(response) => response,
+        Err(e) => {
+            log::error!("Failed to handle API response: {}", e);
+            return Err(e);
+        }
+    };
+
+    parse_invite_messages(result).await
+}
+
+async fn parse_invite_messages(
+    response: reqwest::Response,
+) -> Result<Vec<InviteMessage>, ApiError> {
+    let text = response
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read invite messages: {}", e))?;
+
+    let mut messages: Vec<InviteMessage> = match serde_json::from_str(&text) {
+        Ok(messages) => messages,
+        Err(e) => {
+            log::info!("Failed to parse invite messages: {}", e);
+            log::info!("Response: {}", text);
+            return Err(format!("Failed to parse invite messages: {}", e).into());
+        }
+    };
+    messages.sort_by_key(|m| m.slot);
+    Ok(messages)
+}
@@ -1,7 +1,13 @@
 mod definitions;
 mod logic;
 
+pub use definitions::InviteMessage;
+pub use definitions::InviteMessageType;
 pub use definitions::NotificationType;
 pub use definitions::SelfInviteResponse;
+pub use definitions::INVITE_MESSAGE_SLOTS;
 
+pub use logic::get_invite_messages;
 pub use logic::invite_self_to_instance;
+pub use logic::invite_user_to_instance;
+pub use logic::update_invite_message;
@@ -3,9 +3,14 @@ mod definitions;
 #[cfg(test)]
 mod tests;
 
-pub use definitions::RateLimitStore;
+pub use common::{
+    get_rate_limit_status, remaining_backoffs, reset_rate_limits, RequestScheduler,
+    DEFAULT_CONCURRENT_REQUESTS, DEFAULT_REQUESTS_PER_MINUTE, MAX_CONCURRENT_REQUESTS,
+};
+pub use definitions::{ApiQueueChanged, RateLimitStatus, RateLimitStore};
 pub mod auth;
 pub mod group;
 pub mod instance;
 pub mod invite;
+pub mod user;
 pub mod world;
@@ -1,2 +1,4 @@
 #[cfg(test)]
 mod rate_limit_tests;
+#[cfg(test)]
+mod retry_tests;
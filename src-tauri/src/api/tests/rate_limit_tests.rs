@@ -1,13 +1,14 @@
 use crate::api::common::{
-    check_rate_limit, get_reqwest_client, handle_api_response, record_rate_limit, reset_backoff,
+    get_rate_limit_status, get_reqwest_client, handle_api_response, record_rate_limit,
+    reset_backoff, RequestPriority, RequestScheduler,
 };
+use crate::errors::ApiError;
 use crate::{api::RateLimitStore, RATE_LIMIT_STORE};
 use chrono::Utc;
 use reqwest::cookie::Jar;
 use std::sync::{Arc, RwLock};
 use std::time::Duration;
 use tempfile::tempdir;
-use tokio::time::sleep;
 use wiremock::{
     matchers::{method, path},
     Mock, MockServer, ResponseTemplate,
@@ -64,11 +65,51 @@ async fn test_rate_limit_detection() {
 
     // Verify that it was detected as a rate limit
     assert!(response.is_err());
-    let error_msg = response.unwrap_err();
-    assert!(error_msg.contains("Rate limit exceeded"));
+    let error = response.unwrap_err();
+    assert!(matches!(error, ApiError::RateLimited { .. }));
+    assert!(error.to_string().contains("Rate limit exceeded"));
+
+    // The rate limit is recorded for the operation's backoff
+    let store = RATE_LIMIT_STORE.get().read().unwrap();
+    let data = store
+        .endpoints
+        .get(endpoint)
+        .expect("Rate limit should be recorded");
+    assert_eq!(data.consecutive_failures, 1);
+}
 
-    // Record manually for subsequent tests
+#[tokio::test]
+async fn test_server_error_keeps_backoff() {
+    // Initialize store before test
+    init_rate_limit_store();
+
+    let mock_server = setup_mock_server().await;
+    Mock::given(method("GET"))
+        .and(path("/api/1/unavailable"))
+        .respond_with(ResponseTemplate::new(503))
+        .mount(&mock_server)
+        .await;
+
+    let endpoint = "test_server_error_keeps_backoff";
     record_rate_limit(endpoint);
+
+    let client = get_reqwest_client(&Arc::new(Jar::default()));
+    let response = client
+        .get(format!("{}/api/1/unavailable", mock_server.uri()))
+        .send()
+        .await
+        .unwrap();
+    let response = handle_api_response(response, endpoint).await.unwrap();
+    assert_eq!(response.status(), 503);
+
+    // Only a successful response shows the rate limit has passed
+    let store = RATE_LIMIT_STORE.get().read().unwrap();
+    let data = store
+        .endpoints
+        .get(endpoint)
+        .expect("Rate limit should be recorded");
+    assert_eq!(data.consecutive_failures, 1);
+    assert!(data.last_rate_limited.is_some());
 }
 
 #[tokio::test]
@@ -99,28 +140,93 @@ async fn test_exponential_backoff() {
 }
 
 #[tokio::test]
-async fn test_check_rate_limit() {
-    // Initialize store before test
-    init_rate_limit_store();
+async fn test_request_scheduler_holds_rate_limited_operation() {
+    let scheduler = RequestScheduler::new(None, 10, 1);
 
-    let endpoint = "test_check_rate_limit";
+    // Initially, requests are dispatched
+    drop(
+        scheduler
+            .acquire("get_favorite_worlds", RequestPriority::Background)
+            .await
+            .unwrap(),
+    );
 
-    // Initially, there should be no rate limit
-    let result = check_rate_limit(endpoint);
-    assert!(result.is_ok());
+    // While held, the operation's requests fail fast with the time until the hold has passed
+    scheduler.hold("get_favorite_worlds", Duration::from_secs(600));
+    let wait = scheduler
+        .acquire("get_favorite_worlds", RequestPriority::Background)
+        .await
+        .err()
+        .expect("A held operation should not dispatch requests");
+    assert!(wait > Duration::from_secs(590));
+
+    // Other operations are still dispatched
+    drop(
+        scheduler
+            .acquire("get_world_by_id", RequestPriority::Background)
+            .await
+            .unwrap(),
+    );
+    drop(
+        scheduler
+            .acquire("create_instance", RequestPriority::Interactive)
+            .await
+            .unwrap(),
+    );
 
-    // Record a rate limit
-    record_rate_limit(endpoint);
+    // A shorter hold does not cut the current one short
+    scheduler.hold("get_favorite_worlds", Duration::from_secs(1));
+    assert!(scheduler
+        .acquire("get_favorite_worlds", RequestPriority::Background)
+        .await
+        .is_err());
 
-    // Now check_rate_limit should return an error
-    let result = check_rate_limit(endpoint);
-    assert!(result.is_err());
-    let error = result.unwrap_err();
-    assert!(error.contains("Rate limit active"));
-    assert!(error.contains("Please try again in"));
+    // Resuming dispatches the operation's requests again
+    scheduler.resume();
+    assert!(scheduler
+        .acquire("get_favorite_worlds", RequestPriority::Background)
+        .await
+        .is_ok());
+}
 
-    // Clean up
-    reset_backoff(endpoint);
+#[tokio::test]
+async fn test_request_scheduler_pause_keeps_interactive_lane_open() {
+    let scheduler = RequestScheduler::new(None, 10, 1);
+
+    scheduler.pause(Duration::from_secs(600));
+
+    // Interactive requests are dispatched while the queue is paused
+    let interactive = tokio::time::timeout(
+        Duration::from_millis(50),
+        scheduler.acquire("create_instance", RequestPriority::Interactive),
+    )
+    .await;
+    assert!(
+        matches!(interactive, Ok(Ok(_))),
+        "Interactive requests should not be paused"
+    );
+    drop(interactive);
+
+    // Background requests wait for the pause rather than failing
+    let background = tokio::time::timeout(
+        Duration::from_millis(50),
+        scheduler.acquire("get_favorite_worlds", RequestPriority::Background),
+    )
+    .await;
+    assert!(background.is_err(), "Background requests should wait");
+
+    // A short pause passes by itself
+    scheduler.resume();
+    scheduler.pause(Duration::from_millis(20));
+    let background = tokio::time::timeout(
+        Duration::from_millis(500),
+        scheduler.acquire("get_favorite_worlds", RequestPriority::Background),
+    )
+    .await;
+    assert!(
+        matches!(background, Ok(Ok(_))),
+        "Background requests should be dispatched after the pause"
+    );
 }
 
 #[tokio::test]
@@ -131,6 +237,7 @@ async fn test_full_flow() {
     // Start mock server
     let mock_server = setup_mock_server().await;
     let endpoint = "test_full_flow";
+    let scheduler = RequestScheduler::new(None, 10, 1);
 
     // First set up a 429 response
     Mock::given(method("GET"))
@@ -144,14 +251,17 @@ async fn test_full_flow() {
     let jar = Arc::new(Jar::default());
     let client = get_reqwest_client(&jar);
 
-    // Helper function to make API calls with rate limit handling
+    // Helper function to make API calls through the scheduler, as `acquire_request_slot` does
     async fn make_api_call(
+        scheduler: &RequestScheduler,
         client: &reqwest::Client,
         url: &str,
         endpoint: &str,
     ) -> Result<String, String> {
-        // Check for rate limit first
-        check_rate_limit(endpoint)?;
+        let _permit = scheduler
+            .acquire(endpoint, RequestPriority::Background)
+            .await
+            .map_err(|wait| format!("Rate limit active, try again in {:?}", wait))?;
 
         // Make the request
         let response = client
@@ -161,15 +271,15 @@ async fn test_full_flow() {
             .map_err(|e| format!("Request failed: {}", e))?;
 
         // Handle response
-        let response = handle_api_response(response, endpoint).await?;
-
-        // Success
-        reset_backoff(endpoint);
+        let response = handle_api_response(response, endpoint)
+            .await
+            .map_err(|e| e.to_string())?;
         Ok(response.status().to_string())
     }
 
     // First call should trigger rate limit
     let result = make_api_call(
+        &scheduler,
         &client,
         &format!("{}/api/1/test", mock_server.uri()),
         endpoint,
@@ -178,49 +288,30 @@ async fn test_full_flow() {
     assert!(result.is_err());
     assert!(result.unwrap_err().contains("Rate limit exceeded"));
 
-    // Record the rate limit
-    record_rate_limit(endpoint);
-
-    // Let's add a small delay to ensure any async operations complete
-    sleep(Duration::from_millis(10)).await;
-
-    // Debug the store state
-    {
-        let store = RATE_LIMIT_STORE.get().read().unwrap();
-        let data = store.endpoints.get(endpoint);
-        println!("Rate limit data before second call: {:?}", data);
-    }
+    // The global scheduler holds the operation in `handle_api_response`, this one by hand
+    let backoff_ms = RATE_LIMIT_STORE
+        .get()
+        .read()
+        .unwrap()
+        .endpoints
+        .get(endpoint)
+        .expect("Rate limit should be recorded")
+        .current_backoff_ms;
+    scheduler.hold(endpoint, Duration::from_millis(backoff_ms));
 
     // Second call should be rejected due to active rate limit
     let result = make_api_call(
+        &scheduler,
         &client,
         &format!("{}/api/1/test", mock_server.uri()),
         endpoint,
     )
     .await;
     assert!(result.is_err());
-    let err_msg = result.unwrap_err();
-    println!("Second call error: {}", err_msg);
-    assert!(err_msg.contains("Rate limit active"));
-
-    // Manually reset the backoff to test successful call
-    reset_backoff(endpoint);
+    assert!(result.unwrap_err().contains("Rate limit active"));
 
-    // Debug after reset
-    {
-        let store = RATE_LIMIT_STORE.get().read().unwrap();
-        let data = store.endpoints.get(endpoint);
-        println!("Rate limit data after reset: {:?}", data);
-
-        // Verify timestamp is cleared
-        if let Some(data) = data {
-            assert!(
-                data.last_rate_limited.is_none(),
-                "last_rate_limited should be None after reset, but was {:?}",
-                data.last_rate_limited
-            );
-        }
-    }
+    // Lift the hold, as `reset_rate_limits` does
+    scheduler.resume();
 
     // ⚠️ IMPORTANT: Reset the mock server to clear previous expectations ⚠️
     mock_server.reset().await;
@@ -232,19 +323,24 @@ async fn test_full_flow() {
         .mount(&mock_server)
         .await;
 
-    println!("Making third API call...");
     let result = make_api_call(
+        &scheduler,
         &client,
         &format!("{}/api/1/test", mock_server.uri()),
         endpoint,
     )
     .await;
-
-    // Print out the result for debugging
-    println!("Third call result: {:?}", result);
-
     assert!(result.is_ok());
     assert_eq!(result.unwrap(), "200 OK");
+
+    // A successful response resets the operation's backoff
+    let store = RATE_LIMIT_STORE.get().read().unwrap();
+    let data = store
+        .endpoints
+        .get(endpoint)
+        .expect("Rate limit entry should still exist");
+    assert!(data.last_rate_limited.is_none());
+    assert_eq!(data.consecutive_failures, 0);
 }
 
 // Add this test to verify reset_backoff works properly
@@ -286,3 +382,139 @@ async fn test_reset_backoff() {
         assert_eq!(data.consecutive_failures, 0, "Failures should be reset");
     }
 }
+
+#[tokio::test]
+async fn test_get_rate_limit_status() {
+    // Initialize store before test
+    init_rate_limit_store();
+
+    let endpoint = "test_rate_limit_status";
+
+    record_rate_limit(endpoint);
+    record_rate_limit(endpoint);
+
+    let status = get_rate_limit_status()
+        .into_iter()
+        .find(|s| s.operation == endpoint)
+        .expect("Status should be reported for rate limited operation");
+    assert_eq!(status.consecutive_failures, 2);
+    assert_eq!(status.recent_rate_limit_count, 2);
+    assert!(
+        status.next_allowed_at.is_some(),
+        "Operation should still be backing off"
+    );
+
+    // After a reset the operation is allowed again, but the 429 history is kept
+    reset_backoff(endpoint);
+    let status = get_rate_limit_status()
+        .into_iter()
+        .find(|s| s.operation == endpoint)
+        .expect("Status should still be reported after reset");
+    assert!(status.next_allowed_at.is_none());
+    assert_eq!(status.recent_rate_limit_count, 2);
+}
+
+#[tokio::test]
+async fn test_rate_limit_status_counts_last_day_only() {
+    // Initialize store before test
+    init_rate_limit_store();
+
+    let endpoint = "test_rate_limit_status_window";
+
+    // A 429 from two days ago, as loaded from disk
+    {
+        let mut store = RATE_LIMIT_STORE.get().write().unwrap();
+        let data = store.endpoints.entry(endpoint.to_string()).or_default();
+        data.recent_rate_limits
+            .push(Utc::now() - chrono::Duration::days(2));
+    }
+
+    let status = get_rate_limit_status()
+        .into_iter()
+        .find(|s| s.operation == endpoint)
+        .expect("Status should be reported for a stored operation");
+    assert_eq!(status.recent_rate_limit_count, 0);
+    assert!(
+        status.next_allowed_at.is_none(),
+        "Operation was never backing off"
+    );
+
+    // Recording a new 429 prunes the old one from the store
+    record_rate_limit(endpoint);
+    let store = RATE_LIMIT_STORE.get().read().unwrap();
+    assert_eq!(store.endpoints[endpoint].recent_rate_limits.len(), 1);
+}
+
+#[tokio::test]
+async fn test_request_scheduler_serializes_requests() {
+    let scheduler = RequestScheduler::new(None, 10, 1);
+
+    let permit = scheduler
+        .acquire("test", RequestPriority::Background)
+        .await
+        .unwrap();
+    let second = tokio::time::timeout(
+        Duration::from_millis(50),
+        scheduler.acquire("test", RequestPriority::Interactive),
+    )
+    .await;
+    assert!(
+        second.is_err(),
+        "A second request should wait while one is in flight"
+    );
+
+    drop(permit);
+    let second = tokio::time::timeout(
+        Duration::from_millis(50),
+        scheduler.acquire("test", RequestPriority::Interactive),
+    )
+    .await;
+    assert!(
+        second.is_ok(),
+        "The queue should be released when the permit is dropped"
+    );
+}
+
+#[tokio::test]
+async fn test_request_scheduler_allows_concurrent_requests() {
+    let scheduler = RequestScheduler::new(None, 10, 2);
+
+    let _first = scheduler
+        .acquire("test", RequestPriority::Background)
+        .await
+        .unwrap();
+    let second = tokio::time::timeout(
+        Duration::from_millis(50),
+        scheduler.acquire("test", RequestPriority::Background),
+    )
+    .await;
+    assert!(
+        second.is_ok(),
+        "A second request should be sent alongside the first"
+    );
+
+    let third = tokio::time::timeout(
+        Duration::from_millis(50),
+        scheduler.acquire("test", RequestPriority::Background),
+    )
+    .await;
+    assert!(third.is_err(), "Requests beyond the limit should wait");
+}
+
+#[tokio::test]
+async fn test_request_scheduler_enforces_budget() {
+    let scheduler = RequestScheduler::new(None, 1, 1);
+
+    drop(
+        scheduler
+            .acquire("test", RequestPriority::Interactive)
+            .await
+            .unwrap(),
+    );
+    let second = tokio::time::timeout(
+        Duration::from_millis(50),
+        scheduler.acquire("test", RequestPriority::Interactive),
+    )
+    .await;
+    assert!(second.is_err(), "Requests beyond the budget should wait");
+}
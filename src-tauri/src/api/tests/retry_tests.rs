@@ -0,0 +1,152 @@
+use crate::api::common::{
+    get_reqwest_client, send_with_retry, CircuitBreaker, RequestError, RequestPriority,
+    RequestScheduler,
+};
+use reqwest::cookie::Jar;
+use std::sync::Arc;
+use tokio::time::{Duration, Instant};
+use wiremock::{
+    matchers::{method, path},
+    Mock, MockServer, ResponseTemplate,
+};
+
+#[tokio::test]
+async fn test_transient_errors_are_retried() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/flaky"))
+        .respond_with(ResponseTemplate::new(503))
+        .up_to_n_times(1)
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/flaky"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = get_reqwest_client(&Arc::new(Jar::default()));
+    let response = send_with_retry(
+        "test_transient_errors_are_retried",
+        RequestPriority::Background,
+        client.get(format!("{}/flaky", mock_server.uri())),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), 200);
+}
+
+#[tokio::test]
+async fn test_post_requests_are_not_retried() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/create"))
+        .respond_with(ResponseTemplate::new(503))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = get_reqwest_client(&Arc::new(Jar::default()));
+    let response = send_with_retry(
+        "test_post_requests_are_not_retried",
+        RequestPriority::Background,
+        client.post(format!("{}/create", mock_server.uri())),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), 503);
+}
+
+#[tokio::test]
+async fn test_client_errors_are_not_retried() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/missing"))
+        .respond_with(ResponseTemplate::new(404))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = get_reqwest_client(&Arc::new(Jar::default()));
+    let response = send_with_retry(
+        "test_client_errors_are_not_retried",
+        RequestPriority::Background,
+        client.get(format!("{}/missing", mock_server.uri())),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), 404);
+}
+
+#[tokio::test]
+async fn test_open_circuit_fails_fast() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/down"))
+        .respond_with(ResponseTemplate::new(502))
+        .expect(5)
+        .mount(&mock_server)
+        .await;
+
+    let scheduler = RequestScheduler::new(None, 600, 5);
+    let client = get_reqwest_client(&Arc::new(Jar::default()));
+    for _ in 0..5 {
+        let response = scheduler
+            .send_with_retry(
+                "test_open_circuit_fails_fast",
+                RequestPriority::Background,
+                client.post(format!("{}/down", mock_server.uri())),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 502);
+    }
+
+    let result = scheduler
+        .send_with_retry(
+            "test_open_circuit_fails_fast",
+            RequestPriority::Background,
+            client.post(format!("{}/down", mock_server.uri())),
+        )
+        .await;
+    assert!(matches!(result, Err(RequestError::CircuitOpen { .. })));
+}
+
+#[test]
+fn test_circuit_breaker_probes_after_cooldown() {
+    let mut breaker = CircuitBreaker::default();
+    let start = Instant::now();
+
+    for _ in 0..4 {
+        assert!(!breaker.record_failure(start));
+    }
+    assert!(breaker.try_acquire(start).is_ok());
+    assert!(breaker.record_failure(start));
+    assert!(breaker
+        .try_acquire(start + Duration::from_secs(30))
+        .is_err());
+
+    // Only one probe is let through once the circuit has been open long enough
+    let after_cooldown = start + Duration::from_secs(61);
+    assert!(breaker.try_acquire(after_cooldown).is_ok());
+    assert!(breaker.try_acquire(after_cooldown).is_err());
+
+    // A failed probe opens the circuit again
+    assert!(breaker.record_failure(after_cooldown));
+    assert!(breaker
+        .try_acquire(after_cooldown + Duration::from_secs(1))
+        .is_err());
+
+    // A successful probe closes it
+    let after_second_cooldown = after_cooldown + Duration::from_secs(61);
+    assert!(breaker.try_acquire(after_second_cooldown).is_ok());
+    breaker.record_success();
+    assert!(!breaker.is_open());
+    assert!(breaker.try_acquire(after_second_cooldown).is_ok());
+}
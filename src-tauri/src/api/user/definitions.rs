@@ -0,0 +1,128 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+/// Trust rank of a VRChat user, derived from their system tags
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub enum TrustRank {
+    Visitor,
+    NewUser,
+    User,
+    KnownUser,
+    TrustedUser,
+}
+
+impl TrustRank {
+    /// Finds the highest trust rank among a user's tags
+    pub fn from_tags(tags: &[String]) -> Self {
+        let has_tag = |tag: &str| tags.iter().any(|t| t == tag);
+
+        if has_tag("system_trust_veteran") {
+            TrustRank::TrustedUser
+        } else if has_tag("system_trust_trusted") {
+            TrustRank::KnownUser
+        } else if has_tag("system_trust_known") {
+            TrustRank::User
+        } else if has_tag("system_trust_basic") {
+            TrustRank::NewUser
+        } else {
+            TrustRank::Visitor
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub(super) struct CurrentUserResponse {
+    pub id: String,
+    #[serde(rename = "displayName")]
+    pub display_name: String,
+    #[serde(rename = "currentAvatarThumbnailImageUrl", default)]
+    pub current_avatar_thumbnail_image_url: String,
+    #[serde(rename = "profilePicOverrideThumbnail", default)]
+    pub profile_pic_override_thumbnail: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub friends: Vec<String>,
+}
+
+/// Profile of the logged-in VRChat account
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Type)]
+pub struct UserProfile {
+    #[serde(rename = "userId")]
+    pub user_id: String,
+    #[serde(rename = "displayName")]
+    pub display_name: String,
+    /// The profile picture if one is set, otherwise the current avatar
+    #[serde(rename = "thumbnailUrl")]
+    pub thumbnail_url: String,
+    #[serde(rename = "trustRank")]
+    pub trust_rank: TrustRank,
+    #[serde(rename = "friendCount")]
+    pub friend_count: u32,
+}
+
+impl From<CurrentUserResponse> for UserProfile {
+    fn from(user: CurrentUserResponse) -> Self {
+        let thumbnail_url = if user.profile_pic_override_thumbnail.is_empty() {
+            user.current_avatar_thumbnail_image_url
+        } else {
+            user.profile_pic_override_thumbnail
+        };
+
+        Self {
+            user_id: user.id,
+            display_name: user.display_name,
+            thumbnail_url,
+            trust_rank: TrustRank::from_tags(&user.tags),
+            friend_count: user.friends.len() as u32,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trust_rank_from_tags() {
+        let tags = |tags: &[&str]| tags.iter().map(|t| t.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(TrustRank::from_tags(&[]), TrustRank::Visitor);
+        assert_eq!(
+            TrustRank::from_tags(&tags(&["system_trust_basic", "system_trust_known"])),
+            TrustRank::User
+        );
+        assert_eq!(
+            TrustRank::from_tags(&tags(&[
+                "system_trust_basic",
+                "system_trust_known",
+                "system_trust_trusted",
+                "system_trust_veteran"
+            ])),
+            TrustRank::TrustedUser
+        );
+    }
+
+    #[test]
+    fn test_user_profile_from_response() {
+        let example = r#"
+  {
+    "id": "usr_00000000-0000-0000-0000-000000000000",
+    "displayName": "Example",
+    "currentAvatarThumbnailImageUrl": "https://example.com/avatar.png",
+    "profilePicOverrideThumbnail": "",
+    "tags": ["system_trust_basic"],
+    "friends": ["usr_a", "usr_b"]
+  }
+  "#;
+
+        let response: CurrentUserResponse = serde_json::from_str(example).unwrap();
+        let profile = UserProfile::from(response);
+
+        assert_eq!(profile.display_name, "Example");
+        assert_eq!(profile.thumbnail_url, "https://example.com/avatar.png");
+        assert_eq!(profile.trust_rank, TrustRank::NewUser);
+        assert_eq!(profile.friend_count, 2);
+    }
+}
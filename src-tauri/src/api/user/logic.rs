@@ -0,0 +1,53 @@
+use std::sync::Arc;
+
+use reqwest::cookie::Jar;
+
+use crate::api::common::{
+    acquire_request_slot, get_reqwest_client, handle_api_response, RequestPriority, API_BASE_URL,
+};
+use crate::errors::ApiError;
+
+use super::definitions::{CurrentUserResponse, UserProfile};
+
+pub async fn get_current_user_profile<J: Into<Arc<Jar>>>(
+    cookie: J,
+) -> Result<UserProfile, ApiError> {
+    const OPERATION: &str = "get_current_user_profile";
+
+    let _permit = acquire_request_slot(OPERATION, RequestPriority::Interactive).await?;
+
+    let cookie_jar: Arc<Jar> = cookie.into();
+    let client = get_reqwest_client(&cookie_jar);
+
+    let result = client
+        .get(format!("{API_BASE_URL}/auth/user"))
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let result = match handle_api_response(result, OPERATION).await {
+        Ok(response) => response,
+        Err(e) => {
+            log::error!("Failed to handle API response: {}", e);
+            return Err(e);
+        }
+    };
+
+    if result.status() == reqwest::StatusCode::UNAUTHORIZED {
+        return Err(ApiError::Unauthorized("Not logged in".to_string()));
+    }
+
+    let text = result
+        .text()
+        .await
+        .map_err(|e| format!("Failed to get current user: {}", e))?;
+
+    // A session which still needs 2FA returns a different shape, which fails to parse here
+    match serde_json::from_str::<CurrentUserResponse>(&text) {
+        Ok(user) => Ok(user.into()),
+        Err(e) => {
+            log::info!("Failed to parse current user: {}", e);
+            Err(format!("Failed to parse current user: {}", e).into())
+        }
+    }
+}
@@ -0,0 +1,7 @@
+mod definitions;
+mod logic;
+
+pub use definitions::TrustRank;
+pub use definitions::UserProfile;
+
+pub use logic::get_current_user_profile;
@@ -2,7 +2,9 @@ use chrono::DateTime;
 use serde::{Deserialize, Serialize};
 use specta::Type;
 
-use crate::definitions::{Platform, WorldApiData, WorldDisplayData};
+use crate::definitions::{
+    Platform, WorldApiData, WorldAvailability, WorldDisplayData, WorldPackageInfo,
+};
 use std::collections::HashSet;
 use std::fmt::Display;
 
@@ -15,6 +17,18 @@ pub enum ReleaseStatus {
     All,
 }
 
+impl std::fmt::Display for ReleaseStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            ReleaseStatus::Public => "public",
+            ReleaseStatus::Private => "private",
+            ReleaseStatus::Hidden => "hidden",
+            ReleaseStatus::All => "all",
+        };
+        write!(f, "{}", s)
+    }
+}
+
 impl Default for ReleaseStatus {
     fn default() -> Self {
         Self::Public
@@ -25,6 +39,52 @@ impl Default for ReleaseStatus {
 pub struct UnityPackage {
     #[serde(rename = "platform")]
     pub platform: String,
+    #[serde(rename = "assetUrl", default, skip_serializing_if = "Option::is_none")]
+    pub asset_url: Option<String>,
+    #[serde(
+        rename = "performanceRating",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub performance_rating: Option<String>,
+}
+
+impl UnityPackage {
+    /// The file ID and version of the package's asset, parsed from its URL
+    /// e.g. ("file_xxx", "12") from "https://api.vrchat.cloud/api/1/file/file_xxx/12/file"
+    pub fn file_version(&self) -> Option<(&str, &str)> {
+        let url = self.asset_url.as_deref()?;
+        let mut segments = url.split('/').skip_while(|s| *s != "file").skip(1);
+        let file_id = segments.next().filter(|s| s.starts_with("file_"))?;
+        let version = segments.next().filter(|s| s.parse::<u32>().is_ok())?;
+        Some((file_id, version))
+    }
+
+    /// One entry per platform, without sizes as those need to be fetched separately
+    fn package_infos(packages: &[UnityPackage]) -> Vec<WorldPackageInfo> {
+        let mut seen = HashSet::new();
+        packages
+            .iter()
+            .filter(|p| seen.insert(p.platform.clone()))
+            .map(|p| WorldPackageInfo {
+                platform: p.platform.clone(),
+                file_size: None,
+                uncompressed_size: None,
+                performance_rating: p.performance_rating.clone(),
+            })
+            .collect()
+    }
+}
+
+/// The analysis of an uploaded asset file, from the `/analysis` endpoint
+#[derive(Default, Debug, PartialEq, Eq, Deserialize)]
+pub struct FileAnalysis {
+    #[serde(rename = "fileSize", default)]
+    pub file_size: Option<u64>,
+    #[serde(rename = "uncompressedSize", default)]
+    pub uncompressed_size: Option<u64>,
+    #[serde(rename = "performanceRating", default)]
+    pub performance_rating: Option<String>,
 }
 
 #[derive(Default, Debug, PartialEq, Eq, Deserialize)]
@@ -103,6 +163,8 @@ impl TryInto<WorldApiData> for FavoriteWorld {
             _ => None,
         };
 
+        let packages = UnityPackage::package_infos(&self.unity_packages);
+
         Ok(WorldApiData {
             image_url: self.image_url,
             world_name: self.name,
@@ -118,6 +180,8 @@ impl TryInto<WorldApiData> for FavoriteWorld {
             visits: self.visits,
             favorites: self.favorites,
             platform,
+            packages,
+            availability: WorldAvailability::Public,
         })
     }
 }
@@ -218,6 +282,8 @@ impl TryInto<WorldApiData> for WorldDetails {
                 .collect()
         };
 
+        let packages = UnityPackage::package_infos(&self.unity_packages);
+
         Ok(WorldApiData {
             image_url: self.image_url,
             world_name: self.name,
@@ -233,6 +299,8 @@ impl TryInto<WorldApiData> for WorldDetails {
             visits: self.visits,
             favorites: self.favorites,
             platform,
+            packages,
+            availability: WorldAvailability::Public,
         })
     }
 }
@@ -265,6 +333,8 @@ pub struct VRChatWorld {
     pub popularity: i32,
     #[serde(rename = "publicationDate")]
     pub publication_date: String,
+    #[serde(rename = "labsPublicationDate", default)]
+    pub labs_publication_date: String,
     #[serde(rename = "releaseStatus")]
     pub release_status: ReleaseStatus,
     #[serde(rename = "tags")]
@@ -295,6 +365,7 @@ impl TryInto<WorldDisplayData> for VRChatWorld {
             name: self.name.clone(),
             thumbnail_url: self.image_url.clone(),
             author_name: self.author_name.clone(),
+            author_id: self.author_id.clone(),
             favorites: self.favorites,
             last_updated: self.updated_at,
             visits: self.visits.unwrap_or(0),
@@ -314,6 +385,13 @@ impl TryInto<WorldDisplayData> for VRChatWorld {
             is_photographed: false,
             is_shared: false,
             is_favorite: false,
+            is_event_critical: false,
+            has_memo: false,
+            rating: None,
+            local_visit_count: 0,
+            pc_download_size: None,
+            quest_download_size: None,
+            availability: WorldAvailability::Public,
         })
     }
 }
@@ -330,6 +408,12 @@ pub struct WorldSearchParameters {
     pub platform: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub search: Option<String>,
+    #[serde(rename = "userId", skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub featured: Option<bool>,
+    #[serde(rename = "releaseStatus", skip_serializing_if = "Option::is_none")]
+    pub release_status: Option<ReleaseStatus>,
 }
 
 impl WorldSearchParameters {
@@ -352,6 +436,15 @@ impl WorldSearchParameters {
         if let Some(ref search) = self.search {
             query.push(format!("search={}", urlencoding::encode(search)));
         }
+        if let Some(ref user_id) = self.user_id {
+            query.push(format!("userId={}", urlencoding::encode(user_id)));
+        }
+        if let Some(featured) = self.featured {
+            query.push(format!("featured={}", featured));
+        }
+        if let Some(ref release_status) = self.release_status {
+            query.push(format!("releaseStatus={}", release_status));
+        }
 
         query.join("&")
     }
@@ -363,6 +456,9 @@ pub struct WorldSearchParametersBuilder {
     pub notag: Option<String>,
     pub platform: Option<String>,
     pub search: Option<String>,
+    pub user_id: Option<String>,
+    pub featured: Option<bool>,
+    pub release_status: Option<ReleaseStatus>,
 }
 
 impl WorldSearchParametersBuilder {
@@ -373,6 +469,9 @@ impl WorldSearchParametersBuilder {
             notag: None,
             platform: None,
             search: None,
+            user_id: None,
+            featured: None,
+            release_status: None,
         }
     }
 
@@ -401,6 +500,21 @@ impl WorldSearchParametersBuilder {
         self
     }
 
+    pub fn user_id<S: AsRef<str>>(mut self, user_id: S) -> Self {
+        self.user_id = Some(user_id.as_ref().to_string());
+        self
+    }
+
+    pub fn featured(mut self, featured: bool) -> Self {
+        self.featured = Some(featured);
+        self
+    }
+
+    pub fn release_status(mut self, release_status: ReleaseStatus) -> Self {
+        self.release_status = Some(release_status);
+        self
+    }
+
     pub fn build(self) -> WorldSearchParameters {
         WorldSearchParameters {
             sort: self.sort,
@@ -408,6 +522,9 @@ impl WorldSearchParametersBuilder {
             notag: self.notag,
             platform: self.platform,
             search: self.search,
+            user_id: self.user_id,
+            featured: self.featured,
+            release_status: self.release_status,
         }
     }
 }
@@ -527,4 +644,33 @@ mod tests {
             "https://assets.vrchat.com/default/unavailable-world.png"
         );
     }
+
+    #[test]
+    fn test_search_parameters_query_string() {
+        let parameters = WorldSearchParametersBuilder::new()
+            .search("club")
+            .featured(true)
+            .release_status(ReleaseStatus::Public)
+            .build();
+
+        assert_eq!(
+            parameters.to_query_string(),
+            "search=club&featured=true&releaseStatus=public"
+        );
+        assert_eq!(WorldSearchParametersBuilder::new().build().to_query_string(), "");
+    }
+
+    #[test]
+    fn test_author_worlds_query_string() {
+        // As sent by `ApiService::get_worlds_by_author`
+        let parameters = WorldSearchParametersBuilder::new()
+            .sort(SearchWorldSort::PublicationDate)
+            .user_id("usr_a b")
+            .build();
+
+        assert_eq!(
+            parameters.to_query_string(),
+            "sort=publicationDate&userId=usr_a%20b"
+        );
+    }
 }
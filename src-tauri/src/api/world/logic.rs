@@ -5,95 +5,49 @@ use reqwest::cookie::Jar;
 use serde::Deserialize;
 
 use crate::api::common::{
-    check_rate_limit, get_reqwest_client, handle_api_response, record_rate_limit, reset_backoff,
-    API_BASE_URL,
+    get_reqwest_client, handle_api_response, send_with_retry, RequestPriority, API_BASE_URL,
 };
+use crate::errors::ApiError;
 
 use super::definitions::{
-    FavoriteWorld, FavoriteWorldParser, VRChatWorld, WorldDetails, WorldSearchParameters,
+    FavoriteWorld, FavoriteWorldParser, FileAnalysis, HiddenWorld, ReleaseStatus, VRChatWorld,
+    WorldDetails, WorldSearchParameters,
 };
 
+/// Number of favorites requested per page
+pub const FAVORITES_PAGE_SIZE: usize = 100;
+
+/// Number of worlds requested per page of a search
+pub const SEARCH_PAGE_SIZE: usize = 100;
+
+/// VRChat only allows max 400 favorites
+pub const MAX_FAVORITES_PAGES: usize = 4;
+
+/// Safeguard against fetching an endless list of the user's own worlds
+pub const MAX_MY_WORLDS_PAGES: usize = 10;
+
 pub async fn get_favorite_worlds<J: Into<Arc<Jar>>>(
     cookie: J,
-) -> Result<Vec<FavoriteWorld>, String> {
-    const OPERATION: &str = "get_favorite_worlds";
-
+) -> Result<Vec<FavoriteWorld>, ApiError> {
     let cookie_jar: Arc<Jar> = cookie.into();
-    let client = get_reqwest_client(&cookie_jar);
     let mut all_favorites = Vec::new();
-    let mut offset = 0;
-    let n = 100; // Set page size to 100
     let mut current_page = 0;
-    let max_pages = 4; // VRChat only allows max 400 favorites
 
     loop {
-        log::info!(
-            "Fetching favorite worlds page {} (offset {})",
-            current_page + 1,
-            offset
-        );
-
-        check_rate_limit(OPERATION)?;
-
-        let result = client
-            .get(format!(
-                "{}/worlds/favorites?offset={}&n={}",
-                API_BASE_URL, offset, n
-            ))
-            .send()
-            .await
-            .map_err(|e| e.to_string())?;
-
-        let result = match handle_api_response(result, OPERATION).await {
-            Ok(response) => response,
-            Err(e) => {
-                log::error!("Failed to handle API response: {}", e);
-                record_rate_limit(OPERATION);
-                return Err(e);
-            }
-        };
-
-        reset_backoff(OPERATION);
-
-        let text = result
-            .text()
-            .await
-            .map_err(|e| format!("Failed to get favorite worlds: {}", e.to_string()))?;
-
-        let parsed: Vec<FavoriteWorldParser> = match serde_json::from_str(&text) {
-            Ok(worlds) => worlds,
-            Err(e) => {
-                log::error!("Failed to parse favorite worlds: {}", e.to_string());
-                log::info!("Response: {}", text);
-                return Err(format!(
-                    "Failed to parse favorite worlds: {}",
-                    e.to_string()
-                ));
-            }
-        };
-
-        // Process this page of results
-        let page_size = parsed.len();
-        for world in parsed {
-            match world {
-                FavoriteWorldParser::World(favorite_world) => all_favorites.push(favorite_world),
-                FavoriteWorldParser::HiddenWorld(_) => (),
-            }
-        }
-
-        // Increment for next page
-        offset += n;
+        let (favorites, _, page_size) =
+            get_favorite_worlds_page(cookie_jar.clone(), current_page).await?;
+        all_favorites.extend(favorites);
         current_page += 1;
 
         // Stop conditions
-        if page_size < n {
+        if page_size < FAVORITES_PAGE_SIZE {
             // Received fewer results than requested, must be the last page
             break;
         }
 
         // Safeguard: Stop after max_pages to avoid excessive API calls
-        if current_page >= max_pages {
-            log::info!("Reached maximum page limit of {} pages", max_pages);
+        if current_page >= MAX_FAVORITES_PAGES {
+            log::info!("Reached maximum page limit of {} pages", MAX_FAVORITES_PAGES);
             break;
         }
     }
@@ -106,41 +60,96 @@ pub async fn get_favorite_worlds<J: Into<Arc<Jar>>>(
     Ok(all_favorites)
 }
 
+/// Fetches a single page of favorite worlds
+/// Returns the favorites on the page, the hidden worlds on it, which VRChat only gives a
+/// few details of, and the number of entries VRChat returned, so callers can tell whether
+/// another page follows
+pub async fn get_favorite_worlds_page<J: Into<Arc<Jar>>>(
+    cookie: J,
+    page: usize,
+) -> Result<(Vec<FavoriteWorld>, Vec<HiddenWorld>, usize), ApiError> {
+    const OPERATION: &str = "get_favorite_worlds";
+
+    let cookie_jar: Arc<Jar> = cookie.into();
+    let client = get_reqwest_client(&cookie_jar);
+    let n = FAVORITES_PAGE_SIZE;
+    let offset = page * n;
+
+    log::info!("Fetching favorite worlds page {} (offset {})", page + 1, offset);
+
+    let result = send_with_retry(
+        OPERATION,
+        RequestPriority::Background,
+        client.get(format!(
+            "{}/worlds/favorites?offset={}&n={}",
+            API_BASE_URL, offset, n
+        )),
+    )
+    .await?;
+
+    let result = match handle_api_response(result, OPERATION).await {
+        Ok(response) => response,
+        Err(e) => {
+            log::error!("Failed to handle API response: {}", e);
+            return Err(e);
+        }
+    };
+
+    let text = result
+        .text()
+        .await
+        .map_err(|e| format!("Failed to get favorite worlds: {}", e.to_string()))?;
+
+    let parsed: Vec<FavoriteWorldParser> = match serde_json::from_str(&text) {
+        Ok(worlds) => worlds,
+        Err(e) => {
+            log::error!("Failed to parse favorite worlds: {}", e.to_string());
+            log::info!("Response: {}", text);
+            return Err(format!("Failed to parse favorite worlds: {}", e.to_string()).into());
+        }
+    };
+
+    let page_size = parsed.len();
+    let mut favorites = vec![];
+    let mut hidden = vec![];
+    for world in parsed {
+        match world {
+            FavoriteWorldParser::World(favorite_world) => favorites.push(favorite_world),
+            FavoriteWorldParser::HiddenWorld(hidden_world) => hidden.push(hidden_world),
+        }
+    }
+
+    Ok((favorites, hidden, page_size))
+}
+
 pub async fn get_recently_visited_worlds<J: Into<Arc<Jar>>>(
     cookie: J,
-) -> Result<Vec<VRChatWorld>, String> {
+) -> Result<Vec<VRChatWorld>, ApiError> {
     const OPERATION: &str = "get_recently_visited_worlds";
 
-    // Check for rate limit
-    check_rate_limit(OPERATION)?;
-
     let cookie_jar: Arc<Jar> = cookie.into();
     let client = get_reqwest_client(&cookie_jar);
 
-    let result = client
-        .get(format!("{}/worlds/recent?n=100", API_BASE_URL))
-        .send()
-        .await
-        .map_err(|e| format!("Failed to get recently visited worlds: {}", e.to_string()))?;
+    let result = send_with_retry(
+        OPERATION,
+        RequestPriority::Background,
+        client.get(format!("{}/worlds/recent?n=100", API_BASE_URL)),
+    )
+    .await
+    .map_err(|e| ApiError::from(e).context("Failed to get recently visited worlds"))?;
 
     let result = match handle_api_response(result, OPERATION).await {
         Ok(response) => response,
         Err(e) => {
             log::error!("Failed to handle API response: {}", e);
-            record_rate_limit(OPERATION);
             return Err(e);
         }
     };
 
-    reset_backoff(OPERATION);
-
     let text = result.text().await;
 
     if let Err(e) = text {
-        return Err(format!(
-            "Failed to get recently visited worlds: {}",
-            e.to_string()
-        ));
+        return Err(format!("Failed to get recently visited worlds: {}", e.to_string()).into());
     }
 
     let text = text.unwrap();
@@ -150,45 +159,102 @@ pub async fn get_recently_visited_worlds<J: Into<Arc<Jar>>>(
         Err(e) => {
             log::error!("Failed to parse vrchat worlds: {}", e.to_string());
             log::info!("Response: {}", text);
-            return Err(format!("Failed to parse vrchat worlds: {}", e.to_string()));
+            return Err(format!("Failed to parse vrchat worlds: {}", e.to_string()).into());
         }
     };
 
     Ok(worlds)
 }
 
+/// Fetches every world uploaded by the logged-in user, whatever its release status
+pub async fn get_my_worlds<J: Into<Arc<Jar>>>(cookie: J) -> Result<Vec<VRChatWorld>, ApiError> {
+    const OPERATION: &str = "get_my_worlds";
+
+    let cookie_jar: Arc<Jar> = cookie.into();
+    let client = get_reqwest_client(&cookie_jar);
+    let mut all_worlds = Vec::new();
+
+    for page in 0..MAX_MY_WORLDS_PAGES {
+        let result = send_with_retry(
+            OPERATION,
+            RequestPriority::Background,
+            client.get(format!(
+                "{}/worlds?user=me&releaseStatus={}&offset={}&n={}",
+                API_BASE_URL,
+                ReleaseStatus::All,
+                page * SEARCH_PAGE_SIZE,
+                SEARCH_PAGE_SIZE
+            )),
+        )
+        .await
+        .map_err(|e| ApiError::from(e).context("Failed to get own worlds"))?;
+
+        let result = match handle_api_response(result, OPERATION).await {
+            Ok(response) => response,
+            Err(e) => {
+                log::error!("Failed to handle API response: {}", e);
+                return Err(e);
+            }
+        };
+
+        let text = result
+            .text()
+            .await
+            .map_err(|e| format!("Failed to get own worlds: {}", e))?;
+
+        let worlds: Vec<VRChatWorld> = serde_json::from_str(&text).map_err(|e| {
+            log::error!("Failed to parse own worlds: {}", e);
+            log::info!("Response: {}", text);
+            format!("Failed to parse own worlds: {}", e)
+        })?;
+
+        let page_size = worlds.len();
+        all_worlds.extend(worlds);
+        if page_size < SEARCH_PAGE_SIZE {
+            break;
+        }
+    }
+
+    log::info!("Fetched {} own worlds", all_worlds.len());
+    Ok(all_worlds)
+}
+
 pub async fn get_world_by_id<J: Into<Arc<Jar>>, S: AsRef<str>>(
     cookie: J,
     id: S,
-) -> Result<WorldDetails, String> {
+) -> Result<WorldDetails, ApiError> {
     const OPERATION: &str = "get_world_by_id";
 
-    check_rate_limit(OPERATION)?;
-
     let cookie_jar: Arc<Jar> = cookie.into();
     let client = get_reqwest_client(&cookie_jar);
 
-    let result = client
-        .get(format!("{}/worlds/{}", API_BASE_URL, id.as_ref()))
-        .send()
-        .await
-        .map_err(|e| format!("Failed to get world by ID: {}", e.to_string()))?;
+    let result = send_with_retry(
+        OPERATION,
+        RequestPriority::Background,
+        client.get(format!("{}/worlds/{}", API_BASE_URL, id.as_ref())),
+    )
+    .await
+    .map_err(|e| ApiError::from(e).context("Failed to get world by ID"))?;
 
     let result = match handle_api_response(result, OPERATION).await {
         Ok(response) => response,
         Err(e) => {
             log::error!("Failed to handle API response: {}", e);
-            record_rate_limit(OPERATION);
             return Err(e);
         }
     };
 
-    reset_backoff(OPERATION);
+    if result.status() == reqwest::StatusCode::NOT_FOUND {
+        return Err(ApiError::NotFound(format!(
+            "World {} not found",
+            id.as_ref()
+        )));
+    }
 
     let text = result.text().await;
 
     if let Err(e) = text {
-        return Err(format!("Failed to get world by ID: {}", e.to_string()));
+        return Err(format!("Failed to get world by ID: {}", e.to_string()).into());
     }
 
     let text = text.unwrap();
@@ -198,60 +264,128 @@ pub async fn get_world_by_id<J: Into<Arc<Jar>>, S: AsRef<str>>(
         Err(e) => {
             log::error!("Failed to parse vrchat world: {}", e.to_string());
             log::info!("Response: {}", text);
-            return Err(format!("Failed to parse vrchat world: {}", e.to_string()));
+            return Err(format!("Failed to parse vrchat world: {}", e.to_string()).into());
         }
     };
 
     Ok(world)
 }
 
+/// Fetches the size and performance analysis of an uploaded world package
+/// The file ID and version come from the package's asset URL, see `UnityPackage::file_version`
+pub async fn get_file_analysis<J: Into<Arc<Jar>>>(
+    cookie: J,
+    file_id: &str,
+    version: &str,
+) -> Result<FileAnalysis, ApiError> {
+    const OPERATION: &str = "get_file_analysis";
+
+    let cookie_jar: Arc<Jar> = cookie.into();
+    let client = get_reqwest_client(&cookie_jar);
+
+    let result = send_with_retry(
+        OPERATION,
+        RequestPriority::Background,
+        client.get(format!("{}/analysis/{}/{}", API_BASE_URL, file_id, version)),
+    )
+    .await
+    .map_err(|e| ApiError::from(e).context("Failed to get file analysis"))?;
+
+    let result = match handle_api_response(result, OPERATION).await {
+        Ok(response) => response,
+        Err(e) => {
+            return Err(e);
+        }
+    };
+
+    let text = result
+        .text()
+        .await
+        .map_err(|e| format!("Failed to get file analysis: {}", e))?;
+
+    serde_json::from_str(&text).map_err(|e| format!("Failed to parse file analysis: {}", e).into())
+}
+
+/// Downloads a world's image, e.g. the URL of its `thumbnailUrl`
+/// The URLs may point at VRChat's file API, so the request is sent with the session cookies
+pub async fn get_world_image<J: Into<Arc<Jar>>>(cookie: J, url: &str) -> Result<Vec<u8>, ApiError> {
+    const OPERATION: &str = "get_world_image";
+
+    let cookie_jar: Arc<Jar> = cookie.into();
+    let client = get_reqwest_client(&cookie_jar);
+
+    let result = send_with_retry(OPERATION, RequestPriority::Background, client.get(url))
+        .await
+        .map_err(|e| ApiError::from(e).context("Failed to get world image"))?;
+
+    let result = match handle_api_response(result, OPERATION).await {
+        Ok(response) => response,
+        Err(e) => {
+            return Err(e);
+        }
+    };
+
+    if result.status() == reqwest::StatusCode::NOT_FOUND {
+        return Err(ApiError::NotFound(format!(
+            "World image not found: {}",
+            url
+        )));
+    }
+    if !result.status().is_success() {
+        return Err(format!("Failed to get world image: {}", result.status()).into());
+    }
+
+    let bytes = result
+        .bytes()
+        .await
+        .map_err(|e| format!("Failed to get world image: {}", e))?;
+    Ok(bytes.to_vec())
+}
+
 pub async fn search_worlds<J: Into<Arc<Jar>>>(
     cookie: J,
     search_parameters: &WorldSearchParameters,
     page: usize,
-) -> Result<Vec<VRChatWorld>, String> {
+) -> Result<Vec<VRChatWorld>, ApiError> {
     const OPERATION: &str = "search_worlds";
 
-    check_rate_limit(OPERATION)?;
-
     let cookie_jar: Arc<Jar> = cookie.into();
     let client = get_reqwest_client(&cookie_jar);
 
-    let offset = page.saturating_sub(1) * 100;
+    let offset = page.saturating_sub(1) * SEARCH_PAGE_SIZE;
 
     info!("search parameters: {:?}", search_parameters);
 
     let search_parameters_string: &str = &search_parameters.to_query_string();
 
     info!(
-        "URL: {}/worlds?offset={}&n=100&{}",
-        API_BASE_URL, offset, search_parameters_string
+        "URL: {}/worlds?offset={}&n={}&{}",
+        API_BASE_URL, offset, SEARCH_PAGE_SIZE, search_parameters_string
     );
 
-    let result = client
-        .get(format!(
-            "{}/worlds?offset={}&n=100&{}",
-            API_BASE_URL, offset, search_parameters_string
-        ))
-        .send()
-        .await
-        .expect("Failed to search worlds");
+    let result = send_with_retry(
+        OPERATION,
+        RequestPriority::Background,
+        client.get(format!(
+            "{}/worlds?offset={}&n={}&{}",
+            API_BASE_URL, offset, SEARCH_PAGE_SIZE, search_parameters_string
+        )),
+    )
+    .await
+    .map_err(|e| ApiError::from(e).context("Failed to search worlds"))?;
 
     let result = match handle_api_response(result, OPERATION).await {
         Ok(response) => response,
         Err(e) => {
             log::error!("Failed to handle API response: {}", e);
-            record_rate_limit(OPERATION);
             return Err(e);
         }
     };
 
-    reset_backoff(OPERATION);
-
     let text = result.text().await;
 
     if let Err(e) = text {
-        return Err(format!("Failed to search worlds: {}", e.to_string()));
+        return Err(format!("Failed to search worlds: {}", e.to_string()).into());
     }
 
     let text = text.unwrap();
@@ -261,7 +395,7 @@ pub async fn search_worlds<J: Into<Arc<Jar>>>(
         Err(e) => {
             log::error!("Failed to parse vrchat worlds: {}", e.to_string());
             log::info!("Response: {}", text);
-            return Err(format!("Failed to parse vrchat worlds: {}", e.to_string()));
+            return Err(format!("Failed to parse vrchat worlds: {}", e.to_string()).into());
         }
     };
 
@@ -2,14 +2,22 @@ mod definitions;
 mod logic;
 
 pub use definitions::FavoriteWorld;
+pub use definitions::FileAnalysis;
+pub use definitions::HiddenWorld;
 pub use definitions::ReleaseStatus;
 pub use definitions::SearchWorldSort;
+pub use definitions::UnityPackage;
 pub use definitions::VRChatWorld;
 pub use definitions::WorldDetails;
 pub use definitions::WorldSearchParameters;
 pub use definitions::WorldSearchParametersBuilder;
 
 pub use logic::get_favorite_worlds;
+pub use logic::get_favorite_worlds_page;
+pub use logic::get_file_analysis;
+pub use logic::get_my_worlds;
 pub use logic::get_recently_visited_worlds;
 pub use logic::get_world_by_id;
+pub use logic::get_world_image;
 pub use logic::search_worlds;
+pub use logic::{FAVORITES_PAGE_SIZE, MAX_FAVORITES_PAGES, SEARCH_PAGE_SIZE};
@@ -0,0 +1,313 @@
+use std::sync::{Arc, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard, TryLockError};
+
+use crate::definitions::{
+    DiscoveryCursors, FolderModel, FollowedAuthor, Notification, Playlist, PreferenceModel,
+    SavedAccounts, WorldBlacklist, WorldModel,
+};
+use crate::errors::ConcurrencyError;
+use crate::services::IntegrityService;
+
+type Guard<'a, T> = Result<RwLockReadGuard<'a, T>, ConcurrencyError>;
+type GuardMut<'a, T> = Result<RwLockWriteGuard<'a, T>, ConcurrencyError>;
+
+/// The user's library, preferences and the rest of their data, managed by Tauri and passed
+/// to commands as `State`
+///
+/// Locks are only taken through the methods below. When both folders and worlds are
+/// needed, `library` and `library_mut` take them in the one order used everywhere,
+/// folders before worlds, so two commands can never deadlock on them. Services given the
+/// raw stores hold both through `read_library` and `write_library` for the same reason.
+/// Cloning is cheap and shares the same data, for moving the state into background tasks
+///
+/// A panic while a lock is held poisons it. Every accessor first recovers poisoned locks,
+/// so one failed operation does not leave the app unusable until restart
+#[derive(Debug, Clone)]
+pub struct AppState {
+    preferences: Arc<RwLock<PreferenceModel>>,
+    folders: Arc<RwLock<Vec<FolderModel>>>,
+    worlds: Arc<RwLock<Vec<WorldModel>>>,
+    blacklist: Arc<RwLock<WorldBlacklist>>,
+    followed_authors: Arc<RwLock<Vec<FollowedAuthor>>>,
+    discovery_cursors: Arc<RwLock<DiscoveryCursors>>,
+    notifications: Arc<RwLock<Vec<Notification>>>,
+    playlist: Arc<RwLock<Option<Playlist>>>,
+    accounts: Arc<RwLock<SavedAccounts>>,
+}
+
+impl AppState {
+    pub fn new(
+        preferences: PreferenceModel,
+        folders: Vec<FolderModel>,
+        worlds: Vec<WorldModel>,
+    ) -> Self {
+        Self {
+            preferences: Arc::new(RwLock::new(preferences)),
+            folders: Arc::new(RwLock::new(folders)),
+            worlds: Arc::new(RwLock::new(worlds)),
+            blacklist: Arc::new(RwLock::new(WorldBlacklist::new())),
+            followed_authors: Arc::new(RwLock::new(vec![])),
+            discovery_cursors: Arc::new(RwLock::new(DiscoveryCursors::default())),
+            notifications: Arc::new(RwLock::new(vec![])),
+            playlist: Arc::new(RwLock::new(None)),
+            accounts: Arc::new(RwLock::new(SavedAccounts::default())),
+        }
+    }
+
+    pub fn preferences(&self) -> Guard<'_, PreferenceModel> {
+        self.recover();
+        self.preferences
+            .read()
+            .map_err(|_| ConcurrencyError::PoisonedLock)
+    }
+
+    pub fn preferences_mut(&self) -> GuardMut<'_, PreferenceModel> {
+        self.recover();
+        self.preferences
+            .write()
+            .map_err(|_| ConcurrencyError::PoisonedLock)
+    }
+
+    pub fn folders(&self) -> Guard<'_, Vec<FolderModel>> {
+        self.recover();
+        self.folders
+            .read()
+            .map_err(|_| ConcurrencyError::PoisonedLock)
+    }
+
+    pub fn worlds(&self) -> Guard<'_, Vec<WorldModel>> {
+        self.recover();
+        self.worlds
+            .read()
+            .map_err(|_| ConcurrencyError::PoisonedLock)
+    }
+
+    /// Reads folders and worlds together, in lock order
+    pub fn library(
+        &self,
+    ) -> Result<
+        (
+            RwLockReadGuard<'_, Vec<FolderModel>>,
+            RwLockReadGuard<'_, Vec<WorldModel>>,
+        ),
+        ConcurrencyError,
+    > {
+        self.recover();
+        read_library(&self.folders, &self.worlds)
+    }
+
+    /// Writes folders and worlds together, in lock order
+    pub fn library_mut(
+        &self,
+    ) -> Result<
+        (
+            RwLockWriteGuard<'_, Vec<FolderModel>>,
+            RwLockWriteGuard<'_, Vec<WorldModel>>,
+        ),
+        ConcurrencyError,
+    > {
+        self.recover();
+        write_library(&self.folders, &self.worlds)
+    }
+
+    /// Replaces everything at once, e.g. after the library was reloaded from disk
+    /// Poisoned locks are cleared, as their contents are discarded anyway
+    pub fn replace(
+        &self,
+        preferences: PreferenceModel,
+        folders: Vec<FolderModel>,
+        worlds: Vec<WorldModel>,
+    ) {
+        *self
+            .preferences
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = preferences;
+        let mut folders_lock = self.folders.write().unwrap_or_else(PoisonError::into_inner);
+        let mut worlds_lock = self.worlds.write().unwrap_or_else(PoisonError::into_inner);
+        *folders_lock = folders;
+        *worlds_lock = worlds;
+        self.preferences.clear_poison();
+        self.folders.clear_poison();
+        self.worlds.clear_poison();
+    }
+
+    /// The preferences lock, for services that take their own locks
+    pub fn preference_store(&self) -> &RwLock<PreferenceModel> {
+        self.recover();
+        &self.preferences
+    }
+
+    /// The folders lock, for services that take their own locks
+    pub fn folder_store(&self) -> &RwLock<Vec<FolderModel>> {
+        self.recover();
+        &self.folders
+    }
+
+    /// The worlds lock, for services that take their own locks
+    pub fn world_store(&self) -> &RwLock<Vec<WorldModel>> {
+        self.recover();
+        &self.worlds
+    }
+
+    /// The blacklist lock, for services that take their own locks
+    pub fn blacklist_store(&self) -> &RwLock<WorldBlacklist> {
+        self.recover();
+        &self.blacklist
+    }
+
+    /// The followed authors lock, for services that take their own locks
+    pub fn followed_author_store(&self) -> &RwLock<Vec<FollowedAuthor>> {
+        self.recover();
+        &self.followed_authors
+    }
+
+    /// The discovery cursors lock, for services that take their own locks
+    pub fn discovery_cursor_store(&self) -> &RwLock<DiscoveryCursors> {
+        self.recover();
+        &self.discovery_cursors
+    }
+
+    /// The notifications lock, for services that take their own locks
+    pub fn notification_store(&self) -> &RwLock<Vec<Notification>> {
+        self.recover();
+        &self.notifications
+    }
+
+    /// The playlist lock, for services that take their own locks
+    pub fn playlist_store(&self) -> &RwLock<Option<Playlist>> {
+        self.recover();
+        &self.playlist
+    }
+
+    /// The accounts lock, for services that take their own locks
+    pub fn account_store(&self) -> &RwLock<SavedAccounts> {
+        self.recover();
+        &self.accounts
+    }
+
+    /// Clears the poison a panic left on any lock
+    /// A panic midway through an update may have left folders and worlds out of step,
+    /// so they are reconciled before the library is used again.
+    /// Locks held by another operation are skipped, and recovered on a later call
+    fn recover(&self) {
+        clear_poison(&self.preferences, "preferences");
+        clear_poison(&self.blacklist, "blacklist");
+        clear_poison(&self.followed_authors, "followed authors");
+        clear_poison(&self.discovery_cursors, "discovery cursors");
+        clear_poison(&self.notifications, "notifications");
+        clear_poison(&self.playlist, "playlist");
+        clear_poison(&self.accounts, "accounts");
+
+        if !self.folders.is_poisoned() && !self.worlds.is_poisoned() {
+            return;
+        }
+        let (Some(mut folders), Some(mut worlds)) =
+            (try_write(&self.folders), try_write(&self.worlds))
+        else {
+            return;
+        };
+        log::warn!("Recovering library after a panic while it was locked");
+        self.folders.clear_poison();
+        self.worlds.clear_poison();
+
+        let report = IntegrityService::reconcile(&mut folders, &mut worlds);
+        if !report.is_clean() {
+            log::warn!(
+                "Fixed library inconsistencies left by the panic: {:?}",
+                report
+            );
+        }
+    }
+}
+
+/// Reads folders and worlds together, in lock order
+/// Services that hold both locks at once take them through here or `write_library`
+pub fn read_library<'a>(
+    folders: &'a RwLock<Vec<FolderModel>>,
+    worlds: &'a RwLock<Vec<WorldModel>>,
+) -> Result<
+    (
+        RwLockReadGuard<'a, Vec<FolderModel>>,
+        RwLockReadGuard<'a, Vec<WorldModel>>,
+    ),
+    ConcurrencyError,
+> {
+    let folders = folders.read().map_err(|_| ConcurrencyError::PoisonedLock)?;
+    let worlds = worlds.read().map_err(|_| ConcurrencyError::PoisonedLock)?;
+    Ok((folders, worlds))
+}
+
+/// Writes folders and worlds together, in lock order
+pub fn write_library<'a>(
+    folders: &'a RwLock<Vec<FolderModel>>,
+    worlds: &'a RwLock<Vec<WorldModel>>,
+) -> Result<
+    (
+        RwLockWriteGuard<'a, Vec<FolderModel>>,
+        RwLockWriteGuard<'a, Vec<WorldModel>>,
+    ),
+    ConcurrencyError,
+> {
+    let folders = folders
+        .write()
+        .map_err(|_| ConcurrencyError::PoisonedLock)?;
+    let worlds = worlds.write().map_err(|_| ConcurrencyError::PoisonedLock)?;
+    Ok((folders, worlds))
+}
+
+/// Clears the poison of a lock whose contents stay valid after a panic midway through an update
+fn clear_poison<T>(lock: &RwLock<T>, name: &str) {
+    if lock.is_poisoned() {
+        log::warn!("Recovering {} after a panic while locked", name);
+        lock.clear_poison();
+    }
+}
+
+/// Takes a write lock without blocking, ignoring poison
+fn try_write<T>(lock: &RwLock<T>) -> Option<RwLockWriteGuard<'_, T>> {
+    match lock.try_write() {
+        Ok(guard) => Some(guard),
+        Err(TryLockError::Poisoned(e)) => Some(e.into_inner()),
+        Err(TryLockError::WouldBlock) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shared_between_clones() {
+        let state = AppState::new(PreferenceModel::new(), vec![], vec![]);
+        let task_state = state.clone();
+
+        task_state
+            .library_mut()
+            .unwrap()
+            .0
+            .push(FolderModel::new("Shared".to_string()));
+        assert_eq!(state.folders().unwrap()[0].folder_name, "Shared");
+
+        let (folders, worlds) = state.library().unwrap();
+        assert_eq!((folders.len(), worlds.len()), (1, 0));
+    }
+
+    #[test]
+    fn test_recovers_poisoned_library() {
+        let state = AppState::new(PreferenceModel::new(), vec![], vec![]);
+
+        let task_state = state.clone();
+        let _ = std::thread::spawn(move || {
+            let (mut folders, _worlds) = task_state.library_mut().unwrap();
+            let mut folder = FolderModel::new("Half done".to_string());
+            folder.world_ids.push("wrld_missing".to_string());
+            folders.push(folder);
+            panic!("interrupted midway");
+        })
+        .join();
+        assert!(state.folder_store().read().is_ok());
+
+        // The orphaned entry left by the panic is removed when recovering
+        let folders = state.folders().unwrap();
+        assert!(folders[0].world_ids.is_empty());
+    }
+}
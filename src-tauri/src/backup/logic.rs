@@ -75,7 +75,7 @@ pub fn restore_from_backup(
             FileService::write_custom_data(&custom_data).map_err(|e| e.to_string())?;
             log::info!("Restored custom_data.json");
 
-            // Apply folder colors to in-memory folders
+            // Apply folder colors, icons and descriptions to in-memory folders
             {
                 let mut folders_lock = folders.write().map_err(|e| {
                     log::error!("Failed to acquire write lock for folders: {}", e);
@@ -86,8 +86,14 @@ pub fn restore_from_backup(
                     if let Some(color) = custom_data.get_folder_color(&folder.folder_name) {
                         folder.color = Some(color.clone());
                     }
+                    folder.icon = custom_data.get_folder_icon(&folder.folder_name).cloned();
+                    folder.description = custom_data
+                        .get_folder_description(&folder.folder_name)
+                        .cloned();
                 }
-                log::info!("Applied folder colors from custom_data to in-memory folders");
+                log::info!(
+                    "Applied folder colors, icons and descriptions from custom_data to in-memory folders"
+                );
             }
         } else {
             // If custom_data.json doesn't exist in backup, we might want to clear existing custom data
@@ -1,3 +1,4 @@
+use std::cmp::Reverse;
 use std::time::Duration;
 
 use semver::Version;
@@ -11,6 +12,97 @@ const VERSION: &str = env!("CARGO_PKG_VERSION");
 #[cfg(test)]
 const VERSION: &str = "1.5.0";
 
+/// The changelog at build time, for showing release notes without a connection
+const EMBEDDED_CHANGELOG: &str = include_str!("../../../changelog.json");
+
+/// The current app version
+pub fn app_version() -> &'static str {
+    VERSION
+}
+
+/// Parses the changelog the app was built with
+pub fn embedded_changelog() -> Vec<ChangelogVersion> {
+    serde_json::from_str(EMBEDDED_CHANGELOG).unwrap_or_else(|e| {
+        log::error!("Failed to parse embedded changelog: {}", e);
+        Vec::new()
+    })
+}
+
+/// Combines the embedded and the fetched changelog, newest version first
+/// Fetched entries replace embedded ones of the same version, as they may have been corrected
+pub fn merge_changelogs(
+    embedded: Vec<ChangelogVersion>,
+    fetched: Vec<ChangelogVersion>,
+) -> Vec<ChangelogVersion> {
+    let mut merged = fetched;
+    for item in embedded {
+        if !merged.iter().any(|fetched| fetched.version == item.version) {
+            merged.push(item);
+        }
+    }
+    // Unparsable versions are kept at the end
+    merged.sort_by_cached_key(|item| Reverse(Version::parse(&item.version).ok()));
+    merged
+}
+
+/// Picks the changes of versions after `since` up to and including `until`, newest first
+///
+/// # Arguments
+/// * `changelog` - The changelog, newest version first
+/// * `since` - Versions up to and including this one are skipped
+/// * `until` - Versions after this one are skipped
+/// * `preferred_language` - The language to pick translations in
+/// * `skip_pre_releases` - Whether pre-release versions are skipped
+///
+/// # Errors
+/// Returns an error if `since` or `until` is not a valid version
+pub fn pick_changes_between(
+    changelog: &[ChangelogVersion],
+    since: &str,
+    until: &str,
+    preferred_language: &str,
+    skip_pre_releases: bool,
+) -> Result<Vec<LocalizedChanges>, String> {
+    let since = Version::parse(since).map_err(|e| format!("Failed to parse version: {}", e))?;
+    let until =
+        Version::parse(until).map_err(|e| format!("Failed to parse target version: {}", e))?;
+
+    let mut changes = Vec::new();
+    for item in changelog.iter().rev() {
+        let cursor_version = match Version::parse(&item.version) {
+            Ok(v) => v,
+            Err(e) => {
+                log::error!("Failed to parse version: {}", e);
+                continue;
+            }
+        };
+        if cursor_version <= since || until < cursor_version {
+            continue;
+        }
+        if skip_pre_releases && item.pre_release {
+            continue;
+        }
+        // The changelog lists some versions twice
+        if changes
+            .iter()
+            .any(|c: &LocalizedChanges| c.version == item.version)
+        {
+            continue;
+        }
+
+        changes.push(LocalizedChanges::new(
+            item.version.clone(),
+            item.pre_release,
+            localize_entries(&item.features, preferred_language),
+            localize_entries(&item.fixes, preferred_language),
+            localize_entries(&item.others, preferred_language),
+        ));
+    }
+
+    changes.reverse();
+    Ok(changes)
+}
+
 pub async fn fetch_and_parse_changelog() -> Result<Vec<ChangelogVersion>, String> {
     let changelog_body = fetch_changelogs().await?;
     let changelog = parse_changelog(changelog_body).await?;
@@ -56,64 +148,23 @@ pub async fn pick_changes_in_preferred_lang<S>(
 where
     S: AsRef<str>,
 {
-    let mut changes = Vec::new();
-
-    let current_version =
-        Version::parse(VERSION).map_err(|e| format!("Failed to parse version: {}", e))?;
-    let target_version = Version::parse(target_version.as_ref())
-        .map_err(|e| format!("Failed to parse target version: {}", e))?;
-
-    for item in changelog.iter().rev() {
-        let cursor_version = match Version::parse(&item.version) {
-            Ok(v) => v,
-            Err(e) => {
-                log::error!("Failed to parse version: {}", e);
-                continue;
-            }
-        };
-
-        // changelog.json では新しいバージョンが前、古いバージョンが後ろにある
-        // changelog.iter().rev() で逆順にしているため、バージョンが古い順に処理される
-        // そのため、バージョンが現在のバージョンよりも古い場合は continue、新しい場合は break する
-        if cursor_version <= current_version {
-            continue;
-        }
-        if target_version < cursor_version {
-            break;
-        }
-
-        let pre_release = item.pre_release;
-
-        if skip_pre_releases && pre_release {
-            continue;
-        }
-
-        let features = localize_entries(&item.features, &preferred_language);
-        let fixes = localize_entries(&item.fixes, &preferred_language);
-        let others = localize_entries(&item.others, &preferred_language);
-
-        changes.push(LocalizedChanges::new(
-            item.version.clone(),
-            pre_release,
-            features,
-            fixes,
-            others,
-        ));
-    }
-
-    changes.reverse();
-
-    Ok(changes)
+    pick_changes_between(
+        &changelog,
+        VERSION,
+        target_version.as_ref(),
+        preferred_language,
+        skip_pre_releases,
+    )
 }
 
-fn localize_entries(entries: &[ChangelogEntry], preferred_language: &String) -> Vec<String> {
+fn localize_entries(entries: &[ChangelogEntry], preferred_language: &str) -> Vec<String> {
     entries
         .iter()
         .map(|entry| {
             let localized = entry
                 .langs
                 .iter()
-                .find(|lang| lang.lang.is_language_supported(&preferred_language))
+                .find(|lang| lang.lang.is_language_supported(preferred_language))
                 .map(|lang| lang.text.clone());
 
             localized.unwrap_or_else(|| entry.text.clone())
@@ -128,3 +179,53 @@ fn get_reqwest_client() -> Result<reqwest::Client, String> {
         .build()
         .map_err(|e| format!("Failed to create reqwest client: {}", e))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_embedded_changelog_parses() {
+        assert!(!embedded_changelog().is_empty());
+    }
+
+    #[test]
+    fn test_pick_changes_between() {
+        let changelog = merge_changelogs(embedded_changelog(), Vec::new());
+
+        let changes = pick_changes_between(&changelog, "1.2.0", "1.2.4", "ja-JP", true).unwrap();
+        let versions: Vec<&str> = changes.iter().map(|c| c.version.as_str()).collect();
+        assert_eq!(versions, vec!["1.2.4", "1.2.2", "1.2.1"]);
+        assert!(pick_changes_between(&changelog, "latest", "1.2.4", "ja-JP", true).is_err());
+    }
+
+    #[test]
+    fn test_merge_changelogs() {
+        let embedded: Vec<ChangelogVersion> = serde_json::from_str(
+            r#"[
+                {"version": "1.1.0", "features": [{"text": "Embedded"}]},
+                {"version": "1.0.0"}
+            ]"#,
+        )
+        .unwrap();
+        let fetched: Vec<ChangelogVersion> = serde_json::from_str(
+            r#"[
+                {"version": "1.1.0", "features": [{"text": "Corrected"}]},
+                {"version": "next"},
+                {"version": "1.2.0"}
+            ]"#,
+        )
+        .unwrap();
+
+        let merged = merge_changelogs(embedded, fetched);
+        let versions: Vec<&str> = merged.iter().map(|c| c.version.as_str()).collect();
+        assert_eq!(versions, vec!["1.2.0", "1.1.0", "1.0.0", "next"]);
+        assert_eq!(merged[1].features[0].text, "Corrected");
+
+        // Unparsable versions are skipped when picking changes
+        let changes = pick_changes_between(&merged, "1.0.0", "1.2.0", "en", false).unwrap();
+        let versions: Vec<&str> = changes.iter().map(|c| c.version.as_str()).collect();
+        assert_eq!(versions, vec!["1.2.0", "1.1.0"]);
+        assert_eq!(changes[1].features, vec!["Corrected"]);
+    }
+}
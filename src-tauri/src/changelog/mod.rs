@@ -1,7 +1,11 @@
 mod common;
 mod definitions;
 
+pub use common::app_version;
+pub use common::embedded_changelog;
 pub use common::fetch_and_parse_changelog;
+pub use common::merge_changelogs;
+pub use common::pick_changes_between;
 pub use common::pick_changes_in_preferred_lang;
 pub use definitions::ChangelogVersion;
 pub use definitions::LocalizedChanges;
@@ -0,0 +1,327 @@
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+
+use directories::BaseDirs;
+use reqwest::cookie::Jar;
+
+use crate::api::world::ReleaseStatus;
+use crate::app_state::AppState;
+use crate::definitions::NotificationKind;
+use crate::services::{
+    initialize_service, ApiService, ExportService, FileService, FolderManager, LibraryEncryption,
+    NotificationService, Persistence, WorldWatchService,
+};
+use crate::{api, backup, RATE_LIMIT_STORE};
+
+/// Read by `--backup`, `--import` etc. when the library is encrypted
+const PASSPHRASE_VAR: &str = "VRC_WORLDS_MANAGER_PASSPHRASE";
+
+/// Where Tauri keeps the app data of this app, see `identifier` in tauri.conf.json
+const APP_IDENTIFIER: &str = "com.raifaworks.vrc-worlds-manager";
+
+const USAGE: &str = "Usage:
+  --export-folder <folder> <path>  Write a folder as PortalLibrarySystem JSON to <path>
+  --backup <directory>             Create a backup inside <directory>
+  --import <path>                  Replace the library with a backup or export
+  --refresh-stale                  Refresh worlds which were not checked for 4 hours
+  --help                           Show this message
+
+Set VRC_WORLDS_MANAGER_PASSPHRASE if the library is encrypted.";
+
+/// A batch operation run against the library without starting the GUI
+#[derive(Debug, PartialEq)]
+pub enum CliCommand {
+    ExportFolder { folder: String, path: PathBuf },
+    Backup { directory: PathBuf },
+    Import { path: PathBuf },
+    RefreshStale,
+    Help,
+}
+
+impl CliCommand {
+    /// Parses the arguments after the binary name
+    /// Returns None when the app should start normally, e.g. for deep links
+    pub fn parse(args: &[String]) -> Option<Result<Self, String>> {
+        let (flag, rest) = args.split_first()?;
+        let command = match flag.as_str() {
+            "--export-folder" => match rest {
+                [folder, path] => Ok(CliCommand::ExportFolder {
+                    folder: folder.clone(),
+                    path: PathBuf::from(path),
+                }),
+                _ => Err("--export-folder takes a folder name and a path".to_string()),
+            },
+            "--backup" => match rest {
+                [directory] => Ok(CliCommand::Backup {
+                    directory: PathBuf::from(directory),
+                }),
+                _ => Err("--backup takes a directory".to_string()),
+            },
+            "--import" => match rest {
+                [path] => Ok(CliCommand::Import {
+                    path: PathBuf::from(path),
+                }),
+                _ => Err("--import takes a path".to_string()),
+            },
+            "--refresh-stale" if rest.is_empty() => Ok(CliCommand::RefreshStale),
+            "--refresh-stale" => Err("--refresh-stale takes no arguments".to_string()),
+            "--help" | "-h" => Ok(CliCommand::Help),
+            _ => return None,
+        };
+        Some(command)
+    }
+}
+
+/// Release builds on Windows are GUI programs, which start without a console, so the
+/// console of the shell that ran the command is attached for the output to show up there
+#[cfg(all(windows, not(debug_assertions)))]
+fn attach_parent_console() {
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn AttachConsole(process_id: u32) -> i32;
+    }
+    const ATTACH_PARENT_PROCESS: u32 = u32::MAX;
+
+    // Fails when there is no parent console, e.g. when started from Explorer, which is fine
+    // as there is nowhere to show the output then
+    unsafe {
+        AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
+
+/// Runs a batch operation and reports the result on stdout, or stderr if it failed
+///
+/// # Returns
+/// The process exit code
+pub fn run(command: Result<CliCommand, String>) -> i32 {
+    #[cfg(all(windows, not(debug_assertions)))]
+    attach_parent_console();
+
+    let command = match command {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("Error: {}\n\n{}", e, USAGE);
+            return 2;
+        }
+    };
+    if let Err(e) = simplelog::TermLogger::init(
+        log::LevelFilter::Warn,
+        simplelog::Config::default(),
+        simplelog::TerminalMode::Stderr,
+        simplelog::ColorChoice::Auto,
+    ) {
+        eprintln!("Failed to initialize logging: {}", e);
+    }
+
+    let result = execute(command);
+    // Writes are queued to the persistence thread, which would die with the process
+    if let Err(e) = tauri::async_runtime::block_on(Persistence::flush()) {
+        eprintln!("Failed to flush pending writes: {}", e);
+        return 1;
+    }
+    match result {
+        Ok(message) => {
+            println!("{}", message);
+            0
+        }
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            1
+        }
+    }
+}
+
+fn execute(command: CliCommand) -> Result<String, String> {
+    if command == CliCommand::Help {
+        return Ok(USAGE.to_string());
+    }
+
+    LibraryEncryption::init();
+    if LibraryEncryption::is_locked() {
+        let passphrase = std::env::var(PASSPHRASE_VAR)
+            .map_err(|_| format!("The library is encrypted, set {}", PASSPHRASE_VAR))?;
+        LibraryEncryption::unlock(&passphrase).map_err(|e| e.to_string())?;
+    }
+    let (preferences, folders, worlds, cookies, _) = initialize_service::initialize_app()
+        .map_err(|e| format!("Failed to load the library: {}", e))?;
+    let state = AppState::new(preferences, folders, worlds);
+
+    match command {
+        CliCommand::ExportFolder { folder, path } => {
+            if !state
+                .folders()
+                .map_err(|e| e.to_string())?
+                .iter()
+                .any(|f| f.folder_name == folder)
+            {
+                return Err(format!("Folder not found: {}", folder));
+            }
+            let json = ExportService::portal_library_system_json(
+                vec![folder.clone()],
+                state.folder_store(),
+                state.world_store(),
+                "name".to_string(),
+                "asc".to_string(),
+            )?;
+            std::fs::write(&path, json)
+                .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+            Ok(format!("Exported {} to {}", folder, path.display()))
+        }
+        CliCommand::Backup { directory } => {
+            backup::create_backup(
+                directory.to_string_lossy().into_owned(),
+                state.world_store(),
+                state.folder_store(),
+            )?;
+            Ok(format!("Backup created in {}", directory.display()))
+        }
+        CliCommand::Import { path } => {
+            let directory = backup_directory(&path);
+            backup::restore_from_backup(
+                directory.to_string_lossy().into_owned(),
+                state.world_store(),
+                state.folder_store(),
+            )?;
+            Ok(format!(
+                "Imported {} worlds and {} folders",
+                state.worlds().map_err(|e| e.to_string())?.len(),
+                state.folders().map_err(|e| e.to_string())?.len()
+            ))
+        }
+        CliCommand::RefreshStale => {
+            if cookies.auth_token.is_none() {
+                return Err("Not logged in, log in from the app first".to_string());
+            }
+            let cookie_store = ApiService::initialize_with_cookies(cookies);
+            tauri::async_runtime::block_on(refresh_stale(&state, cookie_store))
+        }
+        // Answered before loading the library
+        CliCommand::Help => Ok(USAGE.to_string()),
+    }
+}
+
+/// Backups and exports are directories, the files inside them are accepted as well
+fn backup_directory(path: &Path) -> PathBuf {
+    if path.is_file() {
+        path.parent().map(Path::to_path_buf).unwrap_or_default()
+    } else {
+        path.to_path_buf()
+    }
+}
+
+/// Refreshes every stale visible world, like refreshing a folder does in the app
+/// Updates and worlds which are no longer public are added to the notification inbox
+async fn refresh_stale(state: &AppState, cookie_store: Arc<Jar>) -> Result<String, String> {
+    let rate_limit_path = FileService::get_rate_limit_path(
+        &BaseDirs::new()
+            .map(|dirs| dirs.data_dir().join(APP_IDENTIFIER))
+            .unwrap_or_else(std::env::temp_dir),
+    );
+    RATE_LIMIT_STORE.set(RwLock::new(api::RateLimitStore::load(rate_limit_path)));
+    *state.blacklist_store().write().map_err(|e| e.to_string())? = FileService::read_blacklist();
+    *state
+        .notification_store()
+        .write()
+        .map_err(|e| e.to_string())? = FileService::read_notifications();
+
+    let stale: Vec<String> = state
+        .worlds()?
+        .iter()
+        .filter(|w| !w.user_data.hidden && w.user_data.needs_update())
+        .map(|w| w.api_data.world_id.clone())
+        .collect();
+    let total = stale.len();
+
+    let mut fetched = vec![];
+    let mut unavailable = vec![];
+    let mut failed = 0;
+    for world_id in stale {
+        match ApiService::get_world_with_release_status(cookie_store.clone(), &world_id).await {
+            Ok((new, release_status)) => {
+                if release_status != ReleaseStatus::Public {
+                    unavailable.push(NotificationKind::WorldUnavailable {
+                        world_id,
+                        world_name: new.world_name.clone(),
+                        reason: format!("release status changed to {}", release_status),
+                    });
+                }
+                fetched.push((new, release_status));
+            }
+            Err(e) => {
+                log::warn!("Failed to refresh world {}: {}", world_id, e);
+                failed += 1;
+            }
+        }
+    }
+
+    // Checked before storing, as storing overwrites the watched fields
+    let alerts =
+        WorldWatchService::check_fetched(&fetched, state.folder_store(), state.world_store())
+            .map_err(|e| format!("Failed to check event critical worlds: {}", e))?;
+    for alert in alerts.iter() {
+        eprintln!(
+            "Event critical world {} ({}) changed: {:?}",
+            alert.world_name, alert.world_id, alert.changes
+        );
+    }
+
+    let refreshed = fetched
+        .into_iter()
+        .filter(|(_, release_status)| *release_status == ReleaseStatus::Public)
+        .map(|(world, _)| world)
+        .collect();
+    let updates =
+        FolderManager::add_worlds(state.world_store(), refreshed, state.blacklist_store())
+            .map_err(|e| format!("Failed to store refreshed worlds: {}", e))?;
+    let message = format!(
+        "Checked {} stale worlds: {} updated, {} unavailable, {} failed, {} event critical changed",
+        total,
+        updates.len(),
+        unavailable.len(),
+        failed,
+        alerts.len()
+    );
+
+    let mut notifications: Vec<NotificationKind> = updates.into_iter().map(Into::into).collect();
+    notifications.extend(unavailable);
+    NotificationService::add_notifications(notifications, state.notification_store())
+        .map_err(|e| format!("Failed to add notifications: {}", e))?;
+    Ok(message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|a| a.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_commands() {
+        assert_eq!(
+            CliCommand::parse(&args(&["--export-folder", "Horror", "out.json"])),
+            Some(Ok(CliCommand::ExportFolder {
+                folder: "Horror".to_string(),
+                path: PathBuf::from("out.json"),
+            }))
+        );
+        assert_eq!(
+            CliCommand::parse(&args(&["--refresh-stale"])),
+            Some(Ok(CliCommand::RefreshStale))
+        );
+        assert!(matches!(
+            CliCommand::parse(&args(&["--backup"])),
+            Some(Err(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_leaves_other_args_to_gui() {
+        assert_eq!(CliCommand::parse(&[]), None);
+        assert_eq!(
+            CliCommand::parse(&args(&["vrc-worlds-manager://search/horror"])),
+            None
+        );
+    }
+}
@@ -0,0 +1,75 @@
+use tauri::State;
+
+use crate::app_state::AppState;
+use crate::definitions::AccountInfo;
+use crate::errors::CommandError;
+use crate::services::{AccountManager, ApiService};
+use crate::{AUTHENTICATOR, INITSTATE};
+
+#[tauri::command]
+#[specta::specta]
+pub async fn list_accounts(state: State<'_, AppState>) -> Result<Vec<AccountInfo>, CommandError> {
+    AccountManager::list_accounts(state.account_store()).map_err(|e| {
+        log::error!("Error listing accounts: {}", e);
+        CommandError::from(e)
+    })
+}
+
+/// Saves the currently logged-in session under `name` and marks it as the active account
+#[tauri::command]
+#[specta::specta]
+pub async fn add_account(name: String, state: State<'_, AppState>) -> Result<(), CommandError> {
+    let cookie_store = AUTHENTICATOR.get().read().await.get_cookies();
+
+    // Also confirms the session is still valid before it is saved
+    let profile = ApiService::get_current_user_profile(cookie_store.clone())
+        .await
+        .map_err(|e| {
+            log::info!("{}", e);
+            CommandError::from(e)
+        })?;
+    let cookies = ApiService::get_session_cookies(&cookie_store);
+
+    AccountManager::add_account(name, profile.display_name, cookies, state.account_store()).map_err(
+        |e| {
+            log::error!("Error adding account: {}", e);
+            CommandError::from(e)
+        },
+    )
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn remove_account(name: String, state: State<'_, AppState>) -> Result<(), CommandError> {
+    AccountManager::remove_account(&name, state.account_store()).map_err(|e| {
+        log::error!("Error removing account: {}", e);
+        CommandError::from(e)
+    })
+}
+
+/// Switches to a saved account, or to a logged-out session when `name` is None
+/// so another account can log in and be saved with `add_account`
+/// The session being switched away from stays valid and is kept on its saved account
+#[tauri::command]
+#[specta::specta]
+pub async fn switch_account(
+    name: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<(), CommandError> {
+    let cookie_store = AUTHENTICATOR.get().read().await.get_cookies();
+    let current = ApiService::get_session_cookies(&cookie_store);
+
+    let cookies = AccountManager::save_active_session(current, state.account_store())
+        .and_then(|_| AccountManager::activate(name.as_deref(), state.account_store()))
+        .map_err(|e| {
+            log::error!("Error switching account: {}", e);
+            CommandError::from(e)
+        })?;
+
+    ApiService::switch_session(cookies, AUTHENTICATOR.get(), INITSTATE.get())
+        .await
+        .map_err(|e| {
+            log::info!("Failed to restore account session: {}", e);
+            CommandError::from(e)
+        })
+}
@@ -1,94 +1,409 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, Utc};
+use reqwest::cookie::Jar;
+use tauri::async_runtime::Mutex;
 use tauri::AppHandle;
 use tauri::State;
+use tauri_specta::Event;
+use uuid::Uuid;
 
+use crate::api::auth::VRChatAuthPhase;
 use crate::api::group::GroupInstancePermissionInfo;
 use crate::api::group::UserGroup;
+use crate::api::user::UserProfile;
+use crate::api::world::{self, ReleaseStatus};
+use crate::app_state::AppState;
+use crate::commands::notification_commands::notify;
+use crate::definitions::AuthState;
+use crate::definitions::FavoriteGroupCount;
+use crate::definitions::FavoritesImportBatch;
+use crate::definitions::FavoritesImportFinished;
+use crate::definitions::InstanceLinks;
+use crate::definitions::LibraryChange;
+use crate::definitions::SearchPlatform;
+use crate::definitions::SearchResultPage;
+use crate::definitions::WorldApiData;
 use crate::definitions::WorldDetails;
 use crate::definitions::WorldDisplayData;
+use crate::errors::CommandError;
 use crate::services::api_service::InstanceInfo;
-use crate::services::FolderManager;
+use crate::services::{
+    api_cache, AccountManager, BlacklistManager, ChangeFeed, ConcurrentFetcher, FileService,
+    FolderManager, GuestMode, RecentlyVisitedService, WorldReference,
+};
+use crate::task::cancellable_task::{TaskContainer, TaskProgressReporter};
+use crate::task::definitions::{TaskKind, TaskProgress};
 use crate::ApiService;
 use crate::AUTHENTICATOR;
 use crate::INITSTATE;
-use crate::WORLDS;
 
+/// Gets the cookies of the logged-in session, for commands which need the VRChat API
+/// Commands which only work on the local library never call this, so they work logged out
+///
+/// # Errors
+/// Returns `CommandError::NotLoggedIn` if no user is logged in
+/// Returns `CommandError::GuestMode` in guest mode, so guests cannot use the account
+pub(crate) async fn require_login() -> Result<Arc<Jar>, CommandError> {
+    if GuestMode::is_enabled() {
+        return Err(CommandError::guest_mode());
+    }
+    if INITSTATE.get().read().await.user_id.is_empty() {
+        return Err(CommandError::not_logged_in(
+            "Log in to VRChat to use this feature",
+        ));
+    }
+    Ok(AUTHENTICATOR.get().read().await.get_cookies())
+}
+
+/// Gets how far the user is through logging in, for the UI to show the right screen
+/// A restored session counts as logged in even before its token was verified again
+/// The expected 2FA expiry lets the UI warn before VRChat asks for a code again
 #[tauri::command]
 #[specta::specta]
-pub async fn try_login() -> Result<(), String> {
+pub async fn get_auth_state() -> Result<AuthState, CommandError> {
+    let phase = if !INITSTATE.get().read().await.user_id.is_empty() {
+        VRChatAuthPhase::LoggedIn
+    } else {
+        match AUTHENTICATOR.get().read().await.phase() {
+            // The session has since expired or been logged out
+            VRChatAuthPhase::LoggedIn => VRChatAuthPhase::None,
+            phase => phase,
+        }
+    };
+    let two_factor_expires_at = FileService::read_auth()
+        .ok()
+        .and_then(|cookies| cookies.two_factor_expires_at());
+    Ok(AuthState {
+        phase,
+        two_factor_expires_at,
+    })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn try_login() -> Result<(), CommandError> {
     log::info!("Trying to login...");
     ApiService::login_with_token(AUTHENTICATOR.get(), INITSTATE.get())
         .await
-        .map_err(|e| e.to_string())
         .map(|_| {
             log::info!("Login successful");
         })
         .or_else(|e| {
             log::error!("Login failed: {}", e);
-            Err(format!("Login failed: {}", e))
+            Err(CommandError::from(e.context("Login failed")))
         })
 }
 
 #[tauri::command]
 #[specta::specta]
-pub async fn login_with_credentials(username: String, password: String) -> Result<(), String> {
+pub async fn login_with_credentials(
+    username: String,
+    password: String,
+) -> Result<(), CommandError> {
     ApiService::login_with_credentials(username, password, AUTHENTICATOR.get())
         .await
-        .map_err(|e| e.to_string())
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
 #[specta::specta]
-pub async fn login_with_2fa(code: String, two_factor_type: String) -> Result<(), String> {
+pub async fn login_with_2fa(code: String, two_factor_type: String) -> Result<(), CommandError> {
     if two_factor_type == "emailOtp" {
         ApiService::login_with_email_2fa(code, AUTHENTICATOR.get())
             .await
-            .map_err(|e| e.to_string())?;
+            .map_err(CommandError::from)?;
+    } else if two_factor_type == "otp" {
+        // A recovery code, in place of the authenticator app's code
+        ApiService::login_with_recovery_code(code, AUTHENTICATOR.get())
+            .await
+            .map_err(CommandError::from)?;
     } else {
         ApiService::login_with_2fa(code, AUTHENTICATOR.get())
             .await
-            .map_err(|e| e.to_string())?;
+            .map_err(CommandError::from)?;
     }
     // call login_with_token to set user id information
     ApiService::login_with_token(AUTHENTICATOR.get(), INITSTATE.get())
         .await
-        .map_err(|e| e.to_string())
+        .map_err(CommandError::from)
 }
 
+/// Fetches the profile of the logged-in account, to show which account is in use
 #[tauri::command]
 #[specta::specta]
-pub async fn logout() -> Result<(), String> {
+pub async fn get_account_info() -> Result<UserProfile, CommandError> {
+    let cookie_store = require_login().await?;
+
+    ApiService::get_current_user_profile(cookie_store)
+        .await
+        .map_err(|e| {
+            log::info!("{}", e);
+            CommandError::from(e)
+        })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn logout(state: State<'_, AppState>) -> Result<(), CommandError> {
     ApiService::logout(AUTHENTICATOR.get())
         .await
-        .map_err(|e| e.to_string())
+        .map_err(CommandError::from)?;
+
+    // The saved session is now invalid, so keep it from being overwritten by the blank one
+    AccountManager::activate(None, state.account_store()).map_err(|e| {
+        log::error!("Error deactivating account after logout: {}", e);
+        CommandError::from(e)
+    })?;
+    Ok(())
 }
 
+/// Imports the user's favorite worlds as a cancellable task
+/// Returns the task ID immediately; progress is reported through `TaskStatusChanged`
+///
+/// With `create_group_folders`, each favorite group's worlds are also put in a folder named
+/// after the group, creating it if needed
 #[tauri::command]
 #[specta::specta]
-pub async fn get_favorite_worlds() -> Result<(), String> {
-    let cookie_store = AUTHENTICATOR.get().read().await.get_cookies();
+pub async fn get_favorite_worlds(
+    create_group_folders: Option<bool>,
+    handle: State<'_, AppHandle>,
+    task_container: State<'_, Arc<Mutex<TaskContainer>>>,
+    state: State<'_, AppState>,
+) -> Result<Uuid, CommandError> {
+    let cookie_store = require_login().await?;
+    let handle = (*handle).clone();
+    let state = state.inner().clone();
+
+    task_container
+        .lock()
+        .await
+        .run_with_progress(TaskKind::FavoritesImport, move |reporter| async move {
+            import_favorite_worlds(
+                cookie_store,
+                create_group_folders.unwrap_or(false),
+                Some(reporter),
+                handle,
+                state,
+            )
+            .await
+            .map(|_| ())
+        })
+        .map_err(CommandError::internal)
+}
 
-    let user_id = INITSTATE.get().read().await.user_id.clone();
+/// Fetches the pages of favorite worlds a few at a time, adding each page to the library as
+/// it lands, and emits the worlds each page added as a `FavoritesImportBatch`
+/// Cancelling the task keeps the pages already added
+///
+/// New worlds are dated by their position in the favorites, newest favorite last, so they
+/// keep the order they were favorited in whatever order the pages land in
+///
+/// With the `keepNonPublicFavorites` preference, favorites which are not public are added as
+/// placeholders, which are replaced once the world can be fetched
+///
+/// # Returns
+/// The number of worlds added to the library
+pub(crate) async fn import_favorite_worlds(
+    cookie_store: Arc<Jar>,
+    create_group_folders: bool,
+    reporter: Option<TaskProgressReporter>,
+    handle: AppHandle,
+    state: AppState,
+) -> Result<u32, String> {
+    let task_id = reporter.as_ref().map(TaskProgressReporter::task_id);
+    let started_at = Utc::now();
+    let mut summary = FavoritesImportFinished {
+        task_id,
+        ..Default::default()
+    };
+    let keep_non_public = FileService::read_custom_data()
+        .preferences
+        .keep_non_public_favorites
+        .unwrap_or(false);
+    let mut group_counts: BTreeMap<String, u32> = BTreeMap::new();
+    let mut fetcher = ConcurrentFetcher::new();
+    let mut pages_fetched = 0;
+    let mut last_page = None;
+    let mut next_page = 0;
+
+    // Pages are fetched a few at a time, until one says there are no more after it
+    while last_page.is_none() && next_page < world::MAX_FAVORITES_PAGES {
+        let wave_end = (next_page + fetcher.limit()).min(world::MAX_FAVORITES_PAGES);
+        while next_page < wave_end {
+            let cookie_store = cookie_store.clone();
+            let page = next_page;
+            fetcher.spawn(
+                async move { ApiService::get_favorite_worlds_page(cookie_store, page).await },
+            );
+            next_page += 1;
+        }
 
-    let worlds = match ApiService::get_favorite_worlds(cookie_store, user_id).await {
-        Ok(worlds) => worlds,
-        Err(e) => {
-            log::info!("Failed to fetch favorite worlds: {}", e);
-            return Err(format!("Failed to fetch favorite worlds: {}", e));
+        while let Some((page, result)) = fetcher.next().await {
+            let favorites = result.map_err(|e| {
+                log::info!("Failed to fetch favorite worlds: {}", e);
+                format!("Failed to fetch favorite worlds: {}", e)
+            })?;
+            if !favorites.has_more {
+                last_page = Some(last_page.map_or(page, |last: usize| last.min(page)));
+            }
+            pages_fetched += 1;
+            summary.worlds_received += favorites.worlds.len() as u32;
+            for world in &favorites.worlds {
+                if let Some(group) = favorites.groups.get(&world.world_id) {
+                    *group_counts.entry(group.clone()).or_default() += 1;
+                }
+            }
+
+            let mut worlds = favorites.worlds;
+            if keep_non_public {
+                // Placeholders are never newer than a stored world, so they only add missing ones
+                worlds.extend(favorites.placeholders);
+            } else {
+                summary.non_public_skipped += favorites.non_public as u32;
+            }
+            let world_ids: Vec<String> = worlds.iter().map(|w| w.world_id.clone()).collect();
+            let (added, updated) = add_favorites_page(page, worlds, started_at, &handle, &state)?;
+            if create_group_folders {
+                add_to_group_folders(world_ids, &favorites.groups, &state)?;
+            }
+            summary.worlds_added += added.len() as u32;
+            summary.worlds_updated += updated;
+            let batch = FavoritesImportBatch {
+                task_id,
+                page: page as u32,
+                worlds: added,
+            };
+            if let Err(e) = batch.emit(&handle) {
+                log::error!("Failed to emit FavoritesImportBatch event: {}", e);
+            }
+
+            if let Some(reporter) = &reporter {
+                reporter
+                    .report(TaskProgress::FavoritesImport {
+                        pages_fetched,
+                        worlds_added: summary.worlds_added,
+                    })
+                    .await;
+            }
         }
-    };
+    }
+
+    log::info!(
+        "Imported favorite worlds: {} received, {} added, {} updated, {} not public",
+        summary.worlds_received,
+        summary.worlds_added,
+        summary.worlds_updated,
+        summary.non_public_skipped
+    );
+    summary.groups = group_counts
+        .into_iter()
+        .map(|(group, worlds_received)| FavoriteGroupCount {
+            group,
+            worlds_received,
+        })
+        .collect();
+    let worlds_added = summary.worlds_added;
+    if let Err(e) = summary.emit(&handle) {
+        log::error!("Failed to emit FavoritesImportFinished event: {}", e);
+    }
+    Ok(worlds_added)
+}
 
-    log::info!("Received worlds: {:#?}", worlds); // Debug print the worlds
+/// Adds a page of favorite worlds to the library
+///
+/// # Returns
+/// The display data of the worlds the page added, and how many existing worlds it updated
+fn add_favorites_page(
+    page: usize,
+    worlds: Vec<WorldApiData>,
+    started_at: DateTime<Utc>,
+    handle: &AppHandle,
+    state: &AppState,
+) -> Result<(Vec<WorldDisplayData>, u32), String> {
+    // Favorites are listed newest first, so each one is dated a millisecond before the last
+    let dates: HashMap<String, DateTime<Utc>> = worlds
+        .iter()
+        .enumerate()
+        .map(|(index, w)| {
+            let position = (page * world::FAVORITES_PAGE_SIZE + index) as i64;
+            (
+                w.world_id.clone(),
+                started_at - Duration::milliseconds(position),
+            )
+        })
+        .collect();
 
-    // Reverse the order to preserve the original date added order
-    let worlds = worlds.into_iter().rev().collect::<Vec<_>>();
+    let (updates, added) = FolderManager::add_dated_worlds(
+        state.world_store(),
+        worlds,
+        &dates,
+        state.blacklist_store(),
+    )
+    .map_err(|e| {
+        log::info!("Failed to add worlds to folder: {}", e);
+        format!("Failed to add worlds to folder: {}", e)
+    })?;
+    ChangeFeed::record_worlds_added(
+        state.world_store(),
+        added.iter().map(|w| w.world_id.clone()).collect(),
+    );
+
+    let updated = updates.len() as u32;
+    notify(updates.into_iter().map(Into::into).collect(), handle);
+    Ok((added, updated))
+}
 
-    match FolderManager::add_worlds(WORLDS.get(), worlds) {
-        Ok(_) => Ok(()),
-        Err(e) => {
-            log::info!("Failed to add worlds to folder: {}", e);
-            Err(format!("Failed to add worlds to folder: {}", e))
+/// Puts favorite worlds in the folder named after their favorite group, creating the
+/// folders which do not exist yet
+/// Worlds which are not in the library, e.g. as they are blacklisted, are skipped
+fn add_to_group_folders(
+    world_ids: Vec<String>,
+    groups: &HashMap<String, String>,
+    state: &AppState,
+) -> Result<(), String> {
+    let mut by_group: BTreeMap<&String, Vec<String>> = BTreeMap::new();
+    {
+        let worlds = state.worlds().map_err(|e| e.to_string())?;
+        for world_id in world_ids {
+            let Some(group) = groups.get(&world_id) else {
+                continue;
+            };
+            if worlds.iter().any(|w| w.api_data.world_id == world_id) {
+                by_group.entry(group).or_default().push(world_id);
+            }
         }
     }
+
+    for (group, world_ids) in by_group {
+        let exists = FolderManager::get_folders(state.folder_store(), true)
+            .map_err(|e| e.to_string())?
+            .iter()
+            .any(|f| &f.name == group);
+        let folder_name = if exists {
+            group.clone()
+        } else {
+            let folder_name = FolderManager::create_folder(group.clone(), state.folder_store())
+                .map_err(|e| format!("Failed to create folder {}: {}", group, e))?;
+            ChangeFeed::record(LibraryChange::FolderCreated {
+                folder_name: folder_name.clone(),
+            });
+            folder_name
+        };
+        FolderManager::add_worlds_to_folder(
+            folder_name.clone(),
+            world_ids.clone(),
+            state.folder_store(),
+            state.world_store(),
+        )
+        .map_err(|e| format!("Failed to add worlds to folder {}: {}", folder_name, e))?;
+        ChangeFeed::record(LibraryChange::AddedToFolder {
+            folder_name,
+            world_ids,
+        });
+    }
+    Ok(())
 }
 
 #[tauri::command]
@@ -96,9 +411,15 @@ pub async fn get_favorite_worlds() -> Result<(), String> {
 pub async fn get_world(
     world_id: String,
     dont_save_to_local: Option<bool>,
-) -> Result<WorldDetails, String> {
+    handle: State<'_, AppHandle>,
+    state: State<'_, AppState>,
+) -> Result<WorldDetails, CommandError> {
+    // Pasted URLs and locations are narrowed down to the ID, anything else is left to the API
+    let world_id = WorldReference::parse(&world_id)
+        .map(|reference| reference.world_id)
+        .unwrap_or(world_id);
     let cookie_store = AUTHENTICATOR.get().read().await.get_cookies();
-    let world_copy = WORLDS.get().read().unwrap().clone();
+    let world_copy = state.worlds()?.clone();
 
     let user_id = INITSTATE.get().read().await.user_id.clone();
 
@@ -107,7 +428,7 @@ pub async fn get_world(
         Ok(world) => world,
         Err(e) => {
             log::info!("Failed to fetch world: {}", e);
-            return Err(format!("Failed to fetch world: {}", e));
+            return Err(CommandError::from(e.context("Failed to fetch world")));
         }
     };
 
@@ -119,20 +440,32 @@ pub async fn get_world(
             return Ok(world.to_world_details());
         }
     }
-    match FolderManager::add_worlds(WORLDS.get(), vec![world.clone()]) {
-        Ok(_) => Ok(world.to_world_details()),
+    let missing = ChangeFeed::missing_worlds(state.world_store(), vec![world.world_id.clone()])?;
+    match FolderManager::add_worlds(
+        state.world_store(),
+        vec![world.clone()],
+        state.blacklist_store(),
+    ) {
+        Ok(updates) => {
+            ChangeFeed::record_worlds_added(state.world_store(), missing);
+            notify(updates.into_iter().map(Into::into).collect(), &handle);
+            Ok(world.to_world_details())
+        }
         Err(e) => {
             log::info!("Failed to add world to folder: {}", e);
-            Err(format!("Failed to add world to folder: {}", e))
+            Err(CommandError::from(e))
         }
     }
 }
 
 #[tauri::command]
 #[specta::specta]
-pub async fn check_world_info(world_id: String) -> Result<WorldDetails, String> {
+pub async fn check_world_info(
+    world_id: String,
+    state: State<'_, AppState>,
+) -> Result<WorldDetails, CommandError> {
     let cookie_store = AUTHENTICATOR.get().read().await.get_cookies();
-    let world_copy = WORLDS.get().read().unwrap().clone();
+    let world_copy = state.worlds()?.clone();
 
     let user_id = INITSTATE.get().read().await.user_id.clone();
 
@@ -141,7 +474,7 @@ pub async fn check_world_info(world_id: String) -> Result<WorldDetails, String>
         Ok(world) => world,
         Err(e) => {
             log::info!("Failed to fetch world: {}", e);
-            return Err(format!("Failed to fetch world: {}", e));
+            return Err(CommandError::from(e.context("Failed to fetch world")));
         }
     };
 
@@ -151,30 +484,66 @@ pub async fn check_world_info(world_id: String) -> Result<WorldDetails, String>
 
 #[tauri::command]
 #[specta::specta]
-pub async fn get_recently_visited_worlds() -> Result<Vec<WorldDisplayData>, String> {
-    let cookie_store = AUTHENTICATOR.get().read().await.get_cookies();
+pub async fn get_recently_visited_worlds() -> Result<Vec<WorldDisplayData>, CommandError> {
+    let cookie_store = require_login().await?;
 
     let worlds = match ApiService::get_recently_visited_worlds(cookie_store).await {
         Ok(worlds) => worlds,
         Err(e) => {
             log::info!("Failed to fetch recently visited worlds: {}", e);
-            return Err(format!("Failed to fetch recently visited worlds: {}", e));
+            return Err(CommandError::from(
+                e.context("Failed to fetch recently visited worlds"),
+            ));
         }
     };
 
     Ok(worlds)
 }
 
+/// Imports recently visited worlds into the Recently Visited folder right away,
+/// using the configured cap even if the periodic import is disabled
 #[tauri::command]
 #[specta::specta]
+pub async fn import_recently_visited_worlds(
+    state: State<'_, AppState>,
+) -> Result<Vec<String>, CommandError> {
+    let cookie_store = require_login().await?;
+    let settings = FileService::read_custom_data()
+        .preferences
+        .recently_visited_import
+        .unwrap_or_default();
+
+    RecentlyVisitedService::import_recently_visited(
+        cookie_store,
+        settings.cap as usize,
+        state.folder_store(),
+        state.world_store(),
+        state.blacklist_store(),
+    )
+    .await
+    .map_err(|e| {
+        log::error!("Error importing recently visited worlds: {}", e);
+        CommandError::from(e)
+    })
+}
+
+/// Searches for worlds on the server
+/// `has_more` on the returned page tells whether the next page is worth fetching
+#[tauri::command]
+#[specta::specta]
+#[allow(clippy::too_many_arguments)]
 pub async fn search_worlds(
     sort: String,
     tags: Vec<String>,
     exclude_tags: Vec<String>,
     search: String,
+    platform: Option<SearchPlatform>,
+    featured: Option<bool>,
+    release_status: Option<ReleaseStatus>,
     page: usize,
-) -> Result<Vec<WorldDisplayData>, String> {
-    let cookie_store = AUTHENTICATOR.get().read().await.get_cookies();
+    state: State<'_, AppState>,
+) -> Result<SearchResultPage, CommandError> {
+    let cookie_store = require_login().await?;
 
     let sort = if sort.is_empty() { None } else { Some(sort) };
 
@@ -190,17 +559,41 @@ pub async fn search_worlds(
         Some(search)
     };
 
-    let worlds =
-        match ApiService::search_worlds(cookie_store, sort, tags, exclude_tags, search, page).await
-        {
-            Ok(worlds) => worlds,
-            Err(e) => {
-                log::info!("Failed to fetch worlds: {}", e);
-                return Err(format!("Failed to fetch worlds: {}", e));
-            }
-        };
+    // Fall back to the user's default platform when the search doesn't specify one
+    let platform = match platform {
+        Some(platform) => Some(platform),
+        None => {
+            let preferences = state.preferences()?;
+            preferences.default_search_platform
+        }
+    };
 
-    Ok(worlds)
+    let mut result = match ApiService::search_worlds(
+        cookie_store,
+        sort,
+        tags,
+        exclude_tags,
+        search,
+        platform,
+        featured,
+        release_status,
+        page,
+    )
+    .await
+    {
+        Ok(result) => result,
+        Err(e) => {
+            log::info!("Failed to fetch worlds: {}", e);
+            return Err(CommandError::from(e.context("Failed to fetch worlds")));
+        }
+    };
+
+    result.worlds = BlacklistManager::filter_display_data(result.worlds, state.blacklist_store())
+        .map_err(|e| {
+        log::error!("Error filtering blacklisted worlds: {}", e);
+        CommandError::from(e)
+    })?;
+    Ok(result)
 }
 
 #[tauri::command]
@@ -210,8 +603,8 @@ pub async fn create_world_instance(
     instance_type_str: String,
     region_str: String,
     handle: State<'_, AppHandle>,
-) -> Result<InstanceInfo, String> {
-    let cookie_store = AUTHENTICATOR.get().read().await.get_cookies();
+) -> Result<InstanceInfo, CommandError> {
+    let cookie_store = require_login().await?;
     let user_id = INITSTATE.get().read().await.user_id.clone();
 
     let result = ApiService::create_world_instance(
@@ -228,22 +621,24 @@ pub async fn create_world_instance(
         Ok(info) => Ok(info),
         Err(e) => {
             log::info!("Failed to create world instance: {}", e);
-            Err(format!("Failed to create world instance: {}", e))
+            Err(CommandError::from(
+                e.context("Failed to create world instance"),
+            ))
         }
     }
 }
 
 #[tauri::command]
 #[specta::specta]
-pub async fn get_user_groups() -> Result<Vec<UserGroup>, String> {
-    let cookie_store = AUTHENTICATOR.get().read().await.get_cookies();
+pub async fn get_user_groups() -> Result<Vec<UserGroup>, CommandError> {
+    let cookie_store = require_login().await?;
     let user_id = INITSTATE.get().read().await.user_id.clone();
 
     let groups = match ApiService::get_user_groups(cookie_store, user_id).await {
         Ok(groups) => groups,
         Err(e) => {
             log::info!("Failed to fetch user groups: {}", e);
-            return Err(format!("Failed to fetch user groups: {}", e));
+            return Err(CommandError::from(e.context("Failed to fetch user groups")));
         }
     };
 
@@ -254,16 +649,15 @@ pub async fn get_user_groups() -> Result<Vec<UserGroup>, String> {
 #[specta::specta]
 pub async fn get_permission_for_create_group_instance(
     group_id: String,
-) -> Result<GroupInstancePermissionInfo, String> {
-    let cookie_store = AUTHENTICATOR.get().read().await.get_cookies();
+) -> Result<GroupInstancePermissionInfo, CommandError> {
+    let cookie_store = require_login().await?;
     let permission =
         match ApiService::get_permission_for_create_group_instance(cookie_store, group_id).await {
             Ok(permission) => permission,
             Err(e) => {
                 log::info!("Failed to fetch group instance create permission: {}", e);
-                return Err(format!(
-                    "Failed to fetch group instance create permission: {}",
-                    e
+                return Err(CommandError::from(
+                    e.context("Failed to fetch group instance create permission"),
                 ));
             }
         };
@@ -281,8 +675,8 @@ pub async fn create_group_instance(
     region_str: String,
     queue_enabled: bool,
     handle: State<'_, AppHandle>,
-) -> Result<InstanceInfo, String> {
-    let cookie_store = AUTHENTICATOR.get().read().await.get_cookies();
+) -> Result<InstanceInfo, CommandError> {
+    let cookie_store = require_login().await?;
 
     let result = ApiService::create_group_instance(
         world_id,
@@ -300,7 +694,9 @@ pub async fn create_group_instance(
         Ok(info) => Ok(info),
         Err(e) => {
             log::info!("Failed to create group instance: {}", e);
-            Err(format!("Failed to create group instance: {}", e))
+            Err(CommandError::from(
+                e.context("Failed to create group instance"),
+            ))
         }
     }
 }
@@ -311,9 +707,59 @@ pub async fn open_instance_in_client(
     world_id: String,
     instance_id: String,
     handle: State<'_, AppHandle>,
-) -> Result<String, String> {
-    let cookie_store = AUTHENTICATOR.get().read().await.get_cookies();
+) -> Result<String, CommandError> {
+    let cookie_store = require_login().await?;
 
     ApiService::open_instance_in_client(cookie_store, &world_id, &instance_id, (*handle).clone())
         .await
+        .map_err(CommandError::from)
+}
+
+/// Builds the web and launch links of an instance, for pasting into e.g. Discord
+/// The short name is resolved when logged in, otherwise the links are built without it
+#[tauri::command]
+#[specta::specta]
+pub async fn generate_instance_links(
+    world_id: String,
+    instance_id: String,
+) -> Result<InstanceLinks, CommandError> {
+    let reference = WorldReference::parse(&format!("{}:{}", world_id, instance_id))
+        .map_err(CommandError::validation)?;
+    let Some(instance_id) = reference.instance_id.clone() else {
+        return Err(CommandError::validation("An instance ID is required"));
+    };
+
+    let short_name = match require_login().await {
+        Ok(cookie_store) => {
+            match ApiService::get_instance_short_name(
+                cookie_store,
+                &reference.world_id,
+                &instance_id,
+            )
+            .await
+            {
+                Ok(short_name) => Some(short_name),
+                Err(e) => {
+                    log::warn!("Failed to get instance short name: {}", e);
+                    None
+                }
+            }
+        }
+        Err(_) => None,
+    };
+
+    Ok(InstanceLinks {
+        web_url: reference.web_url(),
+        launch_url: reference.launch_url(short_name.as_deref()),
+        short_url: short_name.map(|short_name| format!("https://vrch.at/{}", short_name)),
+    })
+}
+
+/// Drops every cached search and world response, so the next requests hit the API
+#[tauri::command]
+#[specta::specta]
+pub async fn clear_api_cache() -> Result<(), CommandError> {
+    let cleared = api_cache::clear();
+    log::info!("Cleared {} API cache entries", cleared);
+    Ok(())
 }
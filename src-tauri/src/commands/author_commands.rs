@@ -0,0 +1,105 @@
+use tauri::{AppHandle, State};
+
+use crate::app_state::AppState;
+use crate::commands::api_commands::require_login;
+use crate::commands::notification_commands::notify;
+use crate::definitions::{FollowedAuthor, NotificationKind, WorldDisplayData};
+use crate::errors::CommandError;
+use crate::services::{ApiService, AuthorManager, BlacklistManager};
+
+#[tauri::command]
+#[specta::specta]
+pub async fn follow_author(
+    author_id: String,
+    author_name: String,
+    state: State<'_, AppState>,
+) -> Result<(), CommandError> {
+    AuthorManager::follow_author(author_id, author_name, state.followed_author_store()).map_err(
+        |e| {
+            log::error!("Error following author: {}", e);
+            CommandError::from(e)
+        },
+    )
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn unfollow_author(
+    author_id: String,
+    state: State<'_, AppState>,
+) -> Result<(), CommandError> {
+    AuthorManager::unfollow_author(author_id, state.followed_author_store()).map_err(|e| {
+        log::error!("Error unfollowing author: {}", e);
+        CommandError::from(e)
+    })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_followed_authors(
+    state: State<'_, AppState>,
+) -> Result<Vec<FollowedAuthor>, CommandError> {
+    AuthorManager::get_followed_authors(state.followed_author_store()).map_err(|e| {
+        log::error!("Error getting followed authors: {}", e);
+        CommandError::from(e)
+    })
+}
+
+/// Checks followed authors for new worlds, and adds a notification for each one
+#[tauri::command]
+#[specta::specta]
+pub async fn check_followed_authors_new_worlds(
+    handle: State<'_, AppHandle>,
+    state: State<'_, AppState>,
+) -> Result<Vec<WorldDisplayData>, CommandError> {
+    let cookie_store = require_login().await?;
+
+    let new_worlds = AuthorManager::check_followed_authors_new_worlds(
+        cookie_store,
+        state.followed_author_store(),
+        state.world_store(),
+        state.blacklist_store(),
+    )
+    .await
+    .map_err(|e| {
+        log::error!("Error checking followed authors for new worlds: {}", e);
+        CommandError::from(e)
+    })?;
+
+    notify(
+        new_worlds
+            .iter()
+            .map(|w| NotificationKind::NewWorldFromAuthor {
+                world_id: w.world_id.clone(),
+                world_name: w.name.clone(),
+                author_name: w.author_name.clone(),
+            })
+            .collect(),
+        &handle,
+    );
+    Ok(new_worlds)
+}
+
+/// Fetches a page of the worlds published by an author, for "more by this author"
+/// Blacklisted worlds are left out; use `get_world` to add a result to the library
+#[tauri::command]
+#[specta::specta]
+pub async fn get_worlds_by_author(
+    author_id: String,
+    page: usize,
+    state: State<'_, AppState>,
+) -> Result<Vec<WorldDisplayData>, CommandError> {
+    let cookie_store = require_login().await?;
+
+    let worlds = ApiService::get_worlds_by_author(cookie_store, &author_id, page)
+        .await
+        .map_err(|e| {
+            log::info!("Failed to fetch worlds by author: {}", e);
+            CommandError::from(e)
+        })?;
+
+    BlacklistManager::filter_display_data(worlds, state.blacklist_store()).map_err(|e| {
+        log::error!("Error filtering blacklisted worlds: {}", e);
+        CommandError::from(e)
+    })
+}
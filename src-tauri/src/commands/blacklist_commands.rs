@@ -0,0 +1,94 @@
+use tauri::State;
+
+use crate::app_state::AppState;
+use crate::commands::folder_commands::filtered_world_ids;
+use crate::definitions::WorldFilter;
+use crate::errors::CommandError;
+use crate::services::BlacklistManager;
+
+#[tauri::command]
+#[specta::specta]
+pub async fn add_world_to_blacklist(
+    world_id: String,
+    state: State<'_, AppState>,
+) -> Result<(), CommandError> {
+    BlacklistManager::add_world_to_blacklist(world_id, state.blacklist_store()).map_err(|e| {
+        log::error!("Error adding world to blacklist: {}", e);
+        CommandError::from(e)
+    })
+}
+
+/// Blacklists every world matching the filter
+///
+/// # Returns
+/// The number of worlds added, worlds already blacklisted are not counted
+#[tauri::command]
+#[specta::specta]
+pub async fn blacklist_filtered_worlds(
+    filter: WorldFilter,
+    state: State<'_, AppState>,
+) -> Result<u32, CommandError> {
+    let world_ids = filtered_world_ids(&filter, &state)?;
+    let added = BlacklistManager::add_worlds_to_blacklist(&world_ids, state.blacklist_store())
+        .map_err(|e| {
+            log::error!("Error blacklisting filtered worlds: {}", e);
+            CommandError::from(e)
+        })?;
+    Ok(added.len() as u32)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn remove_world_from_blacklist(
+    world_id: String,
+    state: State<'_, AppState>,
+) -> Result<(), CommandError> {
+    BlacklistManager::remove_world_from_blacklist(world_id, state.blacklist_store()).map_err(|e| {
+        log::error!("Error removing world from blacklist: {}", e);
+        CommandError::from(e)
+    })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_blacklist(state: State<'_, AppState>) -> Result<Vec<String>, CommandError> {
+    BlacklistManager::get_blacklist(state.blacklist_store()).map_err(|e| {
+        log::error!("Error getting blacklist: {}", e);
+        CommandError::from(e)
+    })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn add_author_to_blacklist(
+    author_id: String,
+    state: State<'_, AppState>,
+) -> Result<(), CommandError> {
+    BlacklistManager::add_author_to_blacklist(author_id, state.blacklist_store()).map_err(|e| {
+        log::error!("Error adding author to blacklist: {}", e);
+        CommandError::from(e)
+    })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn remove_author_from_blacklist(
+    author_id: String,
+    state: State<'_, AppState>,
+) -> Result<(), CommandError> {
+    BlacklistManager::remove_author_from_blacklist(author_id, state.blacklist_store()).map_err(
+        |e| {
+            log::error!("Error removing author from blacklist: {}", e);
+            CommandError::from(e)
+        },
+    )
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_author_blacklist(state: State<'_, AppState>) -> Result<Vec<String>, CommandError> {
+    BlacklistManager::get_author_blacklist(state.blacklist_store()).map_err(|e| {
+        log::error!("Error getting author blacklist: {}", e);
+        CommandError::from(e)
+    })
+}
@@ -0,0 +1,43 @@
+use tauri::State;
+
+use crate::app_state::AppState;
+use crate::definitions::{ChangeEvent, WorldTrends};
+use crate::errors::CommandError;
+use crate::services::{ChangeFeed, WorldHistoryService};
+
+/// Gets the changes involving a world, newest first
+#[tauri::command]
+#[specta::specta]
+pub async fn get_change_history(world_id: String) -> Result<Vec<ChangeEvent>, CommandError> {
+    ChangeFeed::history(&world_id).map_err(|e| {
+        log::error!("Error reading change history: {}", e);
+        CommandError::from(e)
+    })
+}
+
+/// Gets the weekly snapshots of a world's favorites, visits and capacity, oldest first
+#[tauri::command]
+#[specta::specta]
+pub async fn get_world_trends(world_id: String) -> Result<WorldTrends, CommandError> {
+    Ok(WorldHistoryService::trends(&world_id))
+}
+
+/// Gets the newest changes to the library, newest first
+#[tauri::command]
+#[specta::specta]
+pub async fn get_recent_changes(limit: u32) -> Result<Vec<ChangeEvent>, CommandError> {
+    ChangeFeed::recent(limit as usize).map_err(|e| {
+        log::error!("Error reading recent changes: {}", e);
+        CommandError::from(e)
+    })
+}
+
+/// Undoes the newest change which was not undone yet, returning it
+#[tauri::command]
+#[specta::specta]
+pub async fn undo_last_change(state: State<'_, AppState>) -> Result<ChangeEvent, CommandError> {
+    ChangeFeed::undo(&state).map_err(|e| {
+        log::error!("Error undoing change: {}", e);
+        CommandError::from(e)
+    })
+}
@@ -3,22 +3,30 @@ use std::sync::Arc;
 use tauri::async_runtime::Mutex;
 use tauri::State;
 
+use semver::Version;
+
+use crate::app_state::AppState;
+use crate::errors::CommandError;
+use crate::services::FileService;
 use crate::{
-    changelog::{fetch_and_parse_changelog, pick_changes_in_preferred_lang, LocalizedChanges},
+    changelog::{
+        app_version, embedded_changelog, fetch_and_parse_changelog, merge_changelogs,
+        pick_changes_between, pick_changes_in_preferred_lang, LocalizedChanges,
+    },
     updater::update_handler::{UpdateChannel, UpdateHandler},
-    PREFERENCES,
 };
 
 #[tauri::command]
 #[specta::specta]
 pub async fn get_changelog(
     update_handler: State<'_, Arc<Mutex<UpdateHandler>>>,
-) -> Result<Vec<LocalizedChanges>, String> {
+    state: State<'_, AppState>,
+) -> Result<Vec<LocalizedChanges>, CommandError> {
     let mut handler = update_handler.lock().await;
     if !handler.is_initialized() {
         let err = "Update handler is not initialized yet.".to_string();
         log::error!("{}", err);
-        return Err(err);
+        return Err(CommandError::internal(err));
     }
 
     let raw_changelog = if let Some(changelog) = handler.get_changelog() {
@@ -27,7 +35,7 @@ pub async fn get_changelog(
         let changelog = fetch_and_parse_changelog().await.map_err(|e| {
             let err = format!("Failed to fetch and parse changelog: {}", e);
             log::error!("{}", err);
-            err
+            CommandError::network(err)
         })?;
 
         handler.set_changelog(changelog.clone());
@@ -40,20 +48,19 @@ pub async fn get_changelog(
         None => {
             let err = "No update available.".to_string();
             log::error!("{}", err);
-            return Err(err);
+            return Err(CommandError::not_found(err));
         }
     };
 
     let (preferred_language, skip_pre_releases) = {
-        let preferences_lock = PREFERENCES.get().read().map_err(|e| {
-            let err = format!("Failed to read preferences: {}", e);
-            log::error!("{}", err);
-            err
+        let preferences = state.preferences().map_err(|e| {
+            log::error!("Failed to read preferences: {}", e);
+            CommandError::from(e)
         })?;
 
         (
-            preferences_lock.language.clone(),
-            preferences_lock.update_channel == UpdateChannel::Stable,
+            preferences.language.clone(),
+            preferences.update_channel == UpdateChannel::Stable,
         )
     };
 
@@ -67,8 +74,89 @@ pub async fn get_changelog(
     .map_err(|e| {
         let err = format!("Failed to pick changes in preferred language: {}", e);
         log::error!("{}", err);
-        err
+        CommandError::internal(err)
     })?;
 
     Ok(changelog)
 }
+
+/// Gets the release notes of the versions after `version` up to the running one, newest first
+/// Without a version, the notes since the version last marked as seen are returned,
+/// or only those of the running version if none was marked yet
+/// Newer notes are fetched when possible, the ones the app was built with are used otherwise
+#[tauri::command]
+#[specta::specta]
+pub async fn get_changelog_since(
+    version: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<LocalizedChanges>, CommandError> {
+    let (preferred_language, skip_pre_releases) = {
+        let preferences = state.preferences().map_err(|e| {
+            log::error!("Failed to read preferences: {}", e);
+            CommandError::from(e)
+        })?;
+        (
+            preferences.language.clone(),
+            preferences.update_channel == UpdateChannel::Stable,
+        )
+    };
+
+    let fetched = fetch_and_parse_changelog().await.unwrap_or_else(|e| {
+        log::warn!("Using embedded changelog, fetching failed: {}", e);
+        Vec::new()
+    });
+    let changelog = merge_changelogs(embedded_changelog(), fetched);
+
+    let since = version.or_else(|| FileService::read_custom_data().changelog_seen_version);
+    let Some(since) = since else {
+        let changes = pick_changes_between(
+            &changelog,
+            "0.0.0",
+            app_version(),
+            &preferred_language,
+            false,
+        )
+        .map_err(CommandError::validation)?;
+        return Ok(changes
+            .into_iter()
+            .filter(|c| c.version == app_version())
+            .collect());
+    };
+
+    pick_changes_between(
+        &changelog,
+        &since,
+        app_version(),
+        &preferred_language,
+        skip_pre_releases,
+    )
+    .map_err(|e| {
+        log::warn!("Failed to pick changes since {}: {}", since, e);
+        CommandError::validation(e)
+    })
+}
+
+/// Records that the user has seen the release notes up to `version`
+/// An older version than the one already recorded is ignored
+#[tauri::command]
+#[specta::specta]
+pub async fn mark_changelog_seen(version: String) -> Result<(), CommandError> {
+    let seen = Version::parse(&version)
+        .map_err(|e| CommandError::validation(format!("Invalid version {}: {}", version, e)))?;
+
+    let mut custom_data = FileService::read_custom_data();
+    let newer = custom_data
+        .changelog_seen_version
+        .as_deref()
+        .and_then(|v| Version::parse(v).ok())
+        .map_or(true, |previous| seen > previous);
+    if !newer {
+        return Ok(());
+    }
+
+    custom_data.changelog_seen_version = Some(version);
+    FileService::write_custom_data(&custom_data).map_err(|e| {
+        log::error!("Error writing custom_data: {}", e);
+        CommandError::from(e)
+    })
+}
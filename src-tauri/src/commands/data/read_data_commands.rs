@@ -1,17 +1,25 @@
+use crate::app_state::AppState;
 use crate::backup;
+use crate::errors::CommandError;
 use crate::migration;
 use crate::services;
-use crate::{FOLDERS, PREFERENCES, WORLDS};
 use directories::BaseDirs;
+use tauri::{AppHandle, Manager};
 
 /// Checks if the app is being run for the first time
 /// As this is called every time / is loaded from the frontend, cache result in the state
 #[tauri::command]
 #[specta::specta]
-pub async fn require_initial_setup() -> bool {
+pub async fn require_initial_setup(app: AppHandle) -> bool {
     //check if the result is already cached
-    let mut preferences_lock = PREFERENCES.get().write();
-    let preference = preferences_lock.as_mut().unwrap();
+    let state = app.state::<AppState>();
+    let mut preference = match state.preferences_mut() {
+        Ok(preference) => preference,
+        Err(e) => {
+            log::error!("Error reading preferences: {}", e);
+            return false;
+        }
+    };
     if !preference.first_time {
         log::info!("Not first time, cached result");
         return false;
@@ -35,28 +43,28 @@ pub async fn require_initial_setup() -> bool {
 /// Returns a boolean indicating if the files have been loaded successfully
 ///
 /// # Errors
-/// Returns a tuple containing a boolean indicating if the files have been loaded, and an error message
+/// Returns an Io error with the message of the failure if the files could not be loaded
 #[tauri::command]
 #[specta::specta]
-pub async fn check_files_loaded() -> Result<bool, String> {
+pub async fn check_files_loaded() -> Result<bool, CommandError> {
     let init_state_lock = crate::INITSTATE.get().read();
     let init_state = init_state_lock.await;
     match init_state.success {
         true => Ok(true),
-        false => Err(init_state.message.clone()),
+        false => Err(CommandError::io(init_state.message.clone())),
     }
 }
 
 #[tauri::command]
 #[specta::specta]
-pub async fn detect_old_installation() -> Result<(String, String), String> {
-    migration::MigrationService::detect_old_installation()
+pub async fn detect_old_installation() -> Result<(String, String), CommandError> {
+    migration::MigrationService::detect_old_installation().map_err(CommandError::io)
 }
 
 #[tauri::command]
 #[specta::specta]
-pub async fn check_existing_data() -> Result<(bool, bool), String> {
-    migration::MigrationService::check_existing_data().map_err(|e| e.to_string())
+pub async fn check_existing_data() -> Result<(bool, bool), CommandError> {
+    migration::MigrationService::check_existing_data().map_err(CommandError::io)
 }
 
 /// Passes the paths to the frontend
@@ -69,7 +77,7 @@ pub async fn check_existing_data() -> Result<(bool, bool), String> {
 /// Returns an error message if the path to the local app data directory could not be found
 #[tauri::command]
 #[specta::specta]
-pub async fn pass_paths() -> Result<String, String> {
+pub async fn pass_paths() -> Result<String, CommandError> {
     let base_dirs = BaseDirs::new().ok_or("Could not get base directories")?;
     base_dirs
         .data_local_dir()
@@ -77,13 +85,15 @@ pub async fn pass_paths() -> Result<String, String> {
         .to_str()
         .ok_or("Could not convert path to string")
         .map(|s| s.to_string())
-        .map_err(|e| e.to_string())
+        .map_err(CommandError::internal)
 }
 
 #[tauri::command]
 #[specta::specta]
-pub async fn get_backup_metadata(backup_path: String) -> Result<backup::BackupMetaData, String> {
-    backup::get_backup_metadata(backup_path).map_err(|e| e.to_string())
+pub async fn get_backup_metadata(
+    backup_path: String,
+) -> Result<backup::BackupMetaData, CommandError> {
+    backup::get_backup_metadata(backup_path).map_err(CommandError::io)
 }
 
 #[tauri::command]
@@ -91,8 +101,29 @@ pub async fn get_backup_metadata(backup_path: String) -> Result<backup::BackupMe
 pub async fn get_migration_metadata(
     worlds_path: String,
     folders_path: String,
-) -> Result<migration::PreviousMetadata, String> {
+) -> Result<migration::PreviousMetadata, CommandError> {
     migration::MigrationService::get_migration_metadata(worlds_path, folders_path)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(CommandError::io)
+}
+
+/// Previews what migrating the old VRC Worlds Manager's data would produce, without migrating
+#[tauri::command]
+#[specta::specta]
+pub async fn preview_migration(
+    worlds_path: String,
+    folders_path: String,
+) -> Result<migration::MigrationPreview, CommandError> {
+    migration::MigrationService::preview_migration(worlds_path, folders_path)
+        .await
+        .map_err(CommandError::io)
+}
+
+/// Gets the directory the library is stored in
+#[tauri::command]
+#[specta::specta]
+pub fn get_data_directory() -> String {
+    services::FileService::get_app_dir()
+        .to_string_lossy()
+        .to_string()
 }
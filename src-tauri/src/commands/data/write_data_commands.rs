@@ -1,33 +1,98 @@
+use std::path::{Path, PathBuf};
+
+use tauri::{AppHandle, Manager, State};
+
+use crate::app_state::AppState;
 use crate::backup;
-use crate::definitions::CardSize;
-use crate::migration::MigrationService;
-use crate::services::{self, ExportService};
-use crate::{FOLDERS, WORLDS};
+use crate::commands::notification_commands::notify;
+use crate::definitions::{
+    CardSize, LibraryMergeSummary, MergePolicy, NotificationKind, WorldListFormat,
+};
+use crate::errors::{CommandError, ConcurrencyError, FileError};
+use crate::migration::{MigrationReport, MigrationService};
+use crate::services::{
+    self, DataDirectoryService, DataWatcher, ExportService, FileService, LibraryEncryption,
+    LibraryMerge,
+};
+use crate::MEMO_MANAGER;
 
 #[tauri::command]
 #[specta::specta]
-pub async fn create_empty_auth() -> Result<(), String> {
-    services::FileService::create_empty_auth_file().map_err(|e| e.to_string())
+pub async fn create_empty_auth() -> Result<(), CommandError> {
+    services::FileService::create_empty_auth_file().map_err(CommandError::from)
 }
 
 #[tauri::command]
 #[specta::specta]
-pub async fn create_empty_files() -> Result<(), String> {
+pub async fn create_empty_files() -> Result<(), CommandError> {
     services::FileService::create_empty_folders_file()
         .and_then(|_| services::FileService::create_empty_worlds_file())
-        .map_err(|e| e.to_string())
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn create_backup(
+    backup_path: String,
+    handle: State<'_, AppHandle>,
+    state: State<'_, AppState>,
+) -> Result<(), CommandError> {
+    backup::create_backup(
+        backup_path.clone(),
+        state.world_store(),
+        state.folder_store(),
+    )
+    .map_err(|e| {
+        notify(
+            vec![NotificationKind::BackupFailed { error: e.clone() }],
+            &handle,
+        );
+        CommandError::io(e)
+    })?;
+    notify(
+        vec![NotificationKind::BackupCompleted { path: backup_path }],
+        &handle,
+    );
+    Ok(())
 }
 
 #[tauri::command]
 #[specta::specta]
-pub async fn create_backup(backup_path: String) -> Result<(), String> {
-    backup::create_backup(backup_path, WORLDS.get(), FOLDERS.get()).map_err(|e| e.to_string())
+pub async fn restore_from_backup(
+    backup_path: String,
+    state: State<'_, AppState>,
+) -> Result<(), CommandError> {
+    backup::restore_from_backup(backup_path, state.world_store(), state.folder_store())
+        .map_err(CommandError::io)
 }
 
+/// Merges the library of another installation, given its data directory, into this one
 #[tauri::command]
 #[specta::specta]
-pub async fn restore_from_backup(backup_path: String) -> Result<(), String> {
-    backup::restore_from_backup(backup_path, WORLDS.get(), FOLDERS.get()).map_err(|e| e.to_string())
+pub async fn merge_library(
+    data_dir: String,
+    policy: MergePolicy,
+    state: State<'_, AppState>,
+) -> Result<LibraryMergeSummary, CommandError> {
+    if LibraryEncryption::is_locked() {
+        return Err(CommandError::from(FileError::LibraryLocked));
+    }
+
+    let mut memos = MEMO_MANAGER
+        .get()
+        .write()
+        .map_err(|_| ConcurrencyError::PoisonedLock)?;
+    LibraryMerge::merge_from(
+        Path::new(&data_dir),
+        policy,
+        state.folder_store(),
+        state.world_store(),
+        &mut memos,
+    )
+    .map_err(|e| {
+        log::error!("Error merging library from {}: {}", data_dir, e);
+        CommandError::from(e)
+    })
 }
 
 #[tauri::command]
@@ -36,35 +101,130 @@ pub fn export_to_portal_library_system(
     folders: Vec<String>,
     sort_field: String,
     sort_direction: String,
-) -> Result<(), String> {
+    state: State<'_, AppState>,
+) -> Result<(), CommandError> {
     ExportService::export_to_portal_library_system(
         folders,
-        FOLDERS.get(),
-        WORLDS.get(),
+        state.folder_store(),
+        state.world_store(),
         sort_field,
         sort_direction,
     )
-    .map_err(|e| e.to_string())
+    .map_err(CommandError::io)
 }
 
+/// Exports a folder as an HTML page or Markdown document listing its worlds
 #[tauri::command]
 #[specta::specta]
-pub async fn migrate_old_data(worlds_path: String, folders_path: String) -> Result<(), String> {
-    MigrationService::migrate_old_data(worlds_path, folders_path, WORLDS.get(), FOLDERS.get())
-        .await
-        .map_err(|e| e.to_string())
+pub fn export_world_list(
+    folder_name: String,
+    format: WorldListFormat,
+    sort_field: String,
+    sort_direction: String,
+    state: State<'_, AppState>,
+) -> Result<(), CommandError> {
+    ExportService::export_world_list(
+        folder_name,
+        format,
+        state.folder_store(),
+        state.world_store(),
+        sort_field,
+        sort_direction,
+    )
+    .map_err(CommandError::io)
+}
+
+/// Exports a folder as a printable PDF run sheet, in playlist order
+#[tauri::command]
+#[specta::specta]
+pub fn export_run_sheet_pdf(
+    folder_name: String,
+    state: State<'_, AppState>,
+) -> Result<(), CommandError> {
+    ExportService::export_run_sheet_pdf(folder_name, state.folder_store(), state.world_store())
+        .map_err(CommandError::io)
 }
 
 #[tauri::command]
 #[specta::specta]
-pub async fn delete_data() -> Result<(), String> {
-    services::delete_data(WORLDS.get(), FOLDERS.get())
+pub async fn migrate_old_data(
+    worlds_path: String,
+    folders_path: String,
+    state: State<'_, AppState>,
+) -> Result<MigrationReport, CommandError> {
+    MigrationService::migrate_old_data(
+        worlds_path,
+        folders_path,
+        state.world_store(),
+        state.folder_store(),
+        MEMO_MANAGER.get(),
+    )
+    .await
+    .map_err(CommandError::io)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn delete_data(state: State<'_, AppState>) -> Result<(), CommandError> {
+    services::delete_data(state.world_store(), state.folder_store())
         .await
-        .map_err(|e| e.to_string())
+        .map_err(CommandError::io)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn export_native_data(path: String) -> Result<(), CommandError> {
+    ExportService::export_native_data(&path).map_err(CommandError::io)
 }
 
+/// Replaces the in-memory library with what is saved on disk, without restarting
+/// Discards any in-memory state left inconsistent by a failed operation
 #[tauri::command]
 #[specta::specta]
-pub async fn export_native_data(path: String) -> Result<(), String> {
-    ExportService::export_native_data(&path).map_err(|e| e.to_string())
+pub async fn reload_data_from_disk(state: State<'_, AppState>) -> Result<(), CommandError> {
+    if LibraryEncryption::is_locked() {
+        return Err(CommandError::from(FileError::LibraryLocked));
+    }
+
+    crate::reload_library(&state).await.map_err(|e| {
+        log::error!("Error reloading data from disk: {}", e);
+        CommandError::io(e)
+    })
+}
+
+/// Moves the library and every other data file to `new_path`, e.g. onto a bigger drive
+/// The files are copied and verified before the app switches to them, and it keeps using
+/// the new directory after restarts
+#[tauri::command]
+#[specta::specta]
+pub async fn move_data_directory(
+    new_path: String,
+    handle: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<(), CommandError> {
+    if LibraryEncryption::is_locked() {
+        return Err(CommandError::from(FileError::LibraryLocked));
+    }
+
+    let target = PathBuf::from(&new_path);
+    DataDirectoryService::validate_target(&FileService::get_app_dir(), &target)
+        .map_err(CommandError::validation)?;
+
+    let rate_limit_dir = handle
+        .path()
+        .app_data_dir()
+        .unwrap_or_else(|_| std::env::temp_dir());
+    let old_dir = DataDirectoryService::move_to(&target, &state, &rate_limit_dir)
+        .await
+        .map_err(|e| {
+            log::error!("Error moving data directory to {}: {}", new_path, e);
+            CommandError::io(e)
+        })?;
+
+    if let Some(watcher) = handle.try_state::<DataWatcher>() {
+        if let Err(e) = watcher.move_to(&old_dir, &target) {
+            log::warn!("Failed to watch the new data directory: {}", e);
+        }
+    }
+    Ok(())
 }
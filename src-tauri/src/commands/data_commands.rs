@@ -1,39 +1,40 @@
 use reqwest::Client;
 
 use crate::definitions::{PatreonData, WorldBlacklist};
+use crate::errors::CommandError;
 
 #[tauri::command]
 #[specta::specta]
-pub async fn fetch_patreon_data() -> Result<PatreonData, String> {
+pub async fn fetch_patreon_data() -> Result<PatreonData, CommandError> {
     let client = Client::new();
     let response = client
         .get("https://data.raifaworks.com/data/patreons.json")
         .send()
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(|e| CommandError::network(e.to_string()))?;
 
     let data = response
         .json::<PatreonData>()
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(|e| CommandError::network(e.to_string()))?;
 
     Ok(data)
 }
 
 #[tauri::command]
 #[specta::specta]
-pub async fn fetch_blacklist() -> Result<WorldBlacklist, String> {
+pub async fn fetch_blacklist() -> Result<WorldBlacklist, CommandError> {
     let client = Client::new();
     let response = client
         .get("https://data.raifaworks.com/data/blacklist.json")
         .send()
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(|e| CommandError::network(e.to_string()))?;
 
     let blacklist = response
         .json::<WorldBlacklist>()
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(|e| CommandError::network(e.to_string()))?;
 
     Ok(blacklist)
 }
@@ -0,0 +1,245 @@
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use tauri::{AppHandle, Manager};
+use tauri_specta::Event;
+
+use crate::app_state::AppState;
+use crate::commands::api_commands::get_world;
+use crate::commands::folder_commands::download_folder;
+use crate::definitions::{DeepLinkAction, DeepLinkHandled};
+use crate::errors::CommandError;
+use crate::services::{GuestMode, InboxService, WorldReference};
+
+pub const DEEP_LINK_SCHEME: &str = "vrc-worlds-manager://";
+
+/// The same link usually arrives twice, through the single instance and deep link plugins
+const DUPLICATE_WINDOW: Duration = Duration::from_secs(5);
+
+static LAST_LINK: Mutex<Option<(String, Instant)>> = Mutex::new(None);
+/// The result for the link the app was started with, emitted before the frontend listens
+static STARTUP_RESULT: Mutex<Option<DeepLinkHandled>> = Mutex::new(None);
+
+/// A link the backend handles itself
+/// Other links, e.g. `search/...`, are left for the frontend
+#[derive(Debug, PartialEq)]
+enum DeepLink {
+    AddWorld(String),
+    ImportShare(String),
+    OpenFolder(String),
+}
+
+impl DeepLink {
+    /// Parses `vrc-worlds-manager://<action>/<argument>`
+    /// Returns None for links with an action the backend does not handle
+    fn parse(url: &str) -> Option<Result<Self, String>> {
+        let rest = url.strip_prefix(DEEP_LINK_SCHEME)?;
+        let rest = rest.split(['?', '#']).next().unwrap_or_default();
+        let (action, argument) = rest.trim_end_matches('/').split_once('/')?;
+        if !matches!(action, "add-world" | "import-share" | "open-folder") {
+            return None;
+        }
+
+        let argument = match urlencoding::decode(argument) {
+            Ok(argument) if !argument.is_empty() => argument.into_owned(),
+            _ => return Some(Err(format!("Invalid argument in deep link: {}", url))),
+        };
+        Some(match action {
+            "add-world" if WorldReference::is_world_id(&argument) => {
+                Ok(DeepLink::AddWorld(argument))
+            }
+            "add-world" => Err(format!("Invalid world ID: {}", argument)),
+            // The share ID becomes part of the download URL
+            "import-share" if is_share_id(&argument) => Ok(DeepLink::ImportShare(argument)),
+            "import-share" => Err(format!("Invalid share ID: {}", argument)),
+            _ => Ok(DeepLink::OpenFolder(argument)),
+        })
+    }
+
+    fn action(&self) -> DeepLinkAction {
+        match self {
+            DeepLink::AddWorld(_) => DeepLinkAction::AddWorld,
+            DeepLink::ImportShare(_) => DeepLinkAction::ImportShare,
+            DeepLink::OpenFolder(_) => DeepLinkAction::OpenFolder,
+        }
+    }
+}
+
+fn is_share_id(id: &str) -> bool {
+    id.chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Checks whether the backend handles this link, in which case it should not be
+/// forwarded to the frontend
+pub fn is_routed(url: &str) -> bool {
+    DeepLink::parse(url).is_some()
+}
+
+/// Performs the action of a deep link and emits a `DeepLinkHandled` event with the result
+/// Links the backend does not handle, and repeats of the link just handled, are ignored
+///
+/// # Arguments
+/// * `handle` - The app handle, to reach the app state and emit the event
+/// * `url` - The full `vrc-worlds-manager://` link
+/// * `startup` - Whether the app was started with this link, to keep the result
+///   for the frontend until it asks for it
+pub async fn handle_deep_link(handle: AppHandle, url: String, startup: bool) {
+    let Some(link) = DeepLink::parse(&url) else {
+        return;
+    };
+    if is_duplicate(&url) {
+        log::info!("Ignoring repeated deep link: {}", url);
+        return;
+    }
+    log::info!("Handling deep link: {}", url);
+
+    let mut result = DeepLinkHandled {
+        url,
+        action: DeepLinkAction::OpenFolder,
+        world_id: None,
+        folder_name: None,
+        already_added: false,
+        error: None,
+    };
+    match link {
+        Ok(link) => {
+            result.action = link.action();
+            if let Err(e) = run(&handle, link, &mut result).await {
+                log::warn!("Failed to handle deep link {}: {}", result.url, e);
+                result.error = Some(e.to_string());
+            }
+        }
+        Err(e) => {
+            log::warn!("{}", e);
+            result.error = Some(e);
+        }
+    }
+
+    if let Err(e) = result.clone().emit(&handle) {
+        log::error!("Failed to emit deep link event: {}", e);
+    }
+    if startup {
+        *STARTUP_RESULT
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = Some(result);
+    }
+}
+
+async fn run(
+    handle: &AppHandle,
+    link: DeepLink,
+    result: &mut DeepLinkHandled,
+) -> Result<(), CommandError> {
+    let state = handle.state::<AppState>();
+    if GuestMode::is_enabled() && !matches!(link, DeepLink::OpenFolder(_)) {
+        return Err(CommandError::guest_mode());
+    }
+    match link {
+        DeepLink::AddWorld(world_id) => {
+            result.world_id = Some(world_id.clone());
+            result.already_added = state
+                .worlds()?
+                .iter()
+                .any(|w| w.api_data.world_id == world_id);
+            if !result.already_added {
+                get_world(world_id.clone(), None, handle.state(), state.clone()).await?;
+                InboxService::add_worlds(&[world_id], state.world_store())?;
+            }
+        }
+        DeepLink::ImportShare(share_id) => {
+            let (folder_name, _) = download_folder(share_id, state).await?;
+            result.folder_name = Some(folder_name);
+        }
+        DeepLink::OpenFolder(folder_name) => {
+            if !state
+                .folders()?
+                .iter()
+                .any(|f| f.folder_name == folder_name)
+            {
+                return Err(CommandError::validation(format!(
+                    "Folder not found: {}",
+                    folder_name
+                )));
+            }
+            result.folder_name = Some(folder_name);
+        }
+    }
+    Ok(())
+}
+
+fn is_duplicate(url: &str) -> bool {
+    // The plugins disagree on trailing slashes
+    let url = url.trim_end_matches('/');
+    let mut last = LAST_LINK.lock().unwrap_or_else(PoisonError::into_inner);
+    let now = Instant::now();
+    let duplicate = matches!(
+        &*last,
+        Some((last_url, at)) if last_url == url && now.duration_since(*at) < DUPLICATE_WINDOW
+    );
+    *last = Some((url.to_string(), now));
+    duplicate
+}
+
+/// Takes the result of the deep link the app was started with, if the backend handled one
+/// Its event is emitted before the frontend starts listening
+#[tauri::command]
+#[specta::specta]
+pub fn take_startup_deep_link_result() -> Option<DeepLinkHandled> {
+    STARTUP_RESULT
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .take()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WORLD_ID: &str = "wrld_4cf554b4-430c-4f8f-b53e-1f294eed230b";
+
+    #[test]
+    fn test_parse_routed_links() {
+        assert_eq!(
+            DeepLink::parse(&format!("{}add-world/{}/", DEEP_LINK_SCHEME, WORLD_ID)),
+            Some(Ok(DeepLink::AddWorld(WORLD_ID.to_string())))
+        );
+        assert_eq!(
+            DeepLink::parse("vrc-worlds-manager://import-share/abc-123"),
+            Some(Ok(DeepLink::ImportShare("abc-123".to_string())))
+        );
+        assert_eq!(
+            DeepLink::parse("vrc-worlds-manager://open-folder/My%20Worlds"),
+            Some(Ok(DeepLink::OpenFolder("My Worlds".to_string())))
+        );
+    }
+
+    #[test]
+    fn test_parse_leaves_other_links_to_frontend() {
+        assert_eq!(DeepLink::parse("vrc-worlds-manager://search/horror"), None);
+        assert_eq!(
+            DeepLink::parse("https://vrchat.com/home/world/wrld_x"),
+            None
+        );
+        assert!(!is_routed("vrc-worlds-manager://add-world"));
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_arguments() {
+        assert!(matches!(
+            DeepLink::parse("vrc-worlds-manager://add-world/wrld_x"),
+            Some(Err(_))
+        ));
+        assert!(matches!(
+            DeepLink::parse("vrc-worlds-manager://import-share/..%2Fadmin"),
+            Some(Err(_))
+        ));
+    }
+
+    #[test]
+    fn test_repeated_link_is_duplicate() {
+        let url = "vrc-worlds-manager://open-folder/Duplicate";
+        assert!(!is_duplicate(url));
+        assert!(is_duplicate(url));
+    }
+}
@@ -0,0 +1,29 @@
+use tauri::{AppHandle, Manager, State};
+
+use crate::app_state::AppState;
+use crate::errors::CommandError;
+use crate::services::{DiagnosticsService, FileService};
+
+/// Zips redacted logs, preferences, library statistics and environment info into the
+/// exports directory, to attach to bug reports, and opens the directory
+/// Returns the path of the zip file
+#[tauri::command]
+#[specta::specta]
+pub async fn create_diagnostics_bundle(
+    handle: State<'_, AppHandle>,
+    state: State<'_, AppState>,
+) -> Result<String, CommandError> {
+    let logs_dir = handle
+        .path()
+        .app_log_dir()
+        .map_err(|_| CommandError::io("Failed to get logs directory"))?;
+    let path = DiagnosticsService::create_bundle(&state, &logs_dir).map_err(|e| {
+        log::error!("Error creating diagnostics bundle: {}", e);
+        CommandError::from(e)
+    })?;
+
+    if let Err(e) = FileService::open_path(FileService::get_exports_dir()) {
+        log::warn!("Failed to open exports directory: {}", e);
+    }
+    Ok(path.to_string_lossy().into_owned())
+}
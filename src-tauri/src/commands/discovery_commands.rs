@@ -0,0 +1,102 @@
+use tauri::State;
+
+use crate::app_state::AppState;
+use crate::commands::api_commands::require_login;
+use crate::definitions::{DiscoveryFeed, Recommendation, SimilarWorld, WorldDisplayData};
+use crate::errors::CommandError;
+use crate::services::{DiscoveryService, RecommendationService};
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_discovery_feed(
+    feed: DiscoveryFeed,
+    state: State<'_, AppState>,
+) -> Result<Vec<WorldDisplayData>, CommandError> {
+    let cookie_store = require_login().await?;
+
+    DiscoveryService::fetch_new_worlds(
+        cookie_store,
+        feed,
+        state.discovery_cursor_store(),
+        state.world_store(),
+        state.blacklist_store(),
+    )
+    .await
+    .map_err(|e| {
+        log::error!("Error fetching discovery feed: {}", e);
+        CommandError::from(e)
+    })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn reset_discovery_feed(
+    feed: DiscoveryFeed,
+    state: State<'_, AppState>,
+) -> Result<(), CommandError> {
+    DiscoveryService::reset_cursor(feed, state.discovery_cursor_store()).map_err(|e| {
+        log::error!("Error resetting discovery feed: {}", e);
+        CommandError::from(e)
+    })
+}
+
+/// Gets the worlds most like a world of the library, for the detail pane
+/// With `include_remote`, worlds found on VRChat by the world's tags are suggested too,
+/// which needs the user to be logged in
+#[tauri::command]
+#[specta::specta]
+pub async fn get_similar_worlds(
+    world_id: String,
+    limit: usize,
+    include_remote: bool,
+    state: State<'_, AppState>,
+) -> Result<Vec<SimilarWorld>, CommandError> {
+    let cookie_store = if include_remote {
+        Some(require_login().await?)
+    } else {
+        None
+    };
+
+    RecommendationService::get_similar_worlds(
+        &world_id,
+        limit,
+        cookie_store,
+        state.world_store(),
+        state.blacklist_store(),
+    )
+    .await
+    .map_err(|e| {
+        log::error!("Error getting similar worlds: {}", e);
+        CommandError::from(e)
+    })
+}
+
+/// Gets worlds matching what the user launches, favorites and rates highly,
+/// each with the reasons it was recommended
+/// With `include_remote`, worlds not in the library yet are recommended too,
+/// which needs the user to be logged in
+#[tauri::command]
+#[specta::specta]
+pub async fn get_recommendations(
+    limit: usize,
+    include_remote: bool,
+    state: State<'_, AppState>,
+) -> Result<Vec<Recommendation>, CommandError> {
+    let cookie_store = if include_remote {
+        Some(require_login().await?)
+    } else {
+        None
+    };
+
+    RecommendationService::get_recommendations(
+        limit,
+        cookie_store,
+        state.world_store(),
+        state.blacklist_store(),
+    )
+    .await
+    .map_err(|e| {
+        log::error!("Error getting recommendations: {}", e);
+        CommandError::from(e)
+    })
+}
@@ -0,0 +1,67 @@
+use tauri::State;
+
+use crate::app_state::AppState;
+use crate::definitions::LibraryEncryptionStatus;
+use crate::errors::CommandError;
+use crate::services::LibraryEncryption;
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_library_encryption_status() -> Result<LibraryEncryptionStatus, CommandError> {
+    LibraryEncryption::status().map_err(|e| {
+        log::error!("Error getting library encryption status: {}", e);
+        CommandError::from(e)
+    })
+}
+
+/// Unlocks an encrypted library and loads it, for use when startup reported it as locked
+#[tauri::command]
+#[specta::specta]
+pub async fn unlock_library(
+    passphrase: String,
+    state: State<'_, AppState>,
+) -> Result<(), CommandError> {
+    if !LibraryEncryption::is_locked() {
+        return Err(CommandError::validation("Library is not locked"));
+    }
+
+    LibraryEncryption::unlock(&passphrase).map_err(|e| {
+        log::warn!("Failed to unlock library: {}", e);
+        CommandError::from(e)
+    })?;
+
+    crate::load_unlocked_library(&state).await.map_err(|e| {
+        log::error!("Error loading library after unlock: {}", e);
+        CommandError::internal(e)
+    })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn enable_library_encryption(passphrase: String) -> Result<(), CommandError> {
+    LibraryEncryption::enable(&passphrase).map_err(|e| {
+        log::error!("Error enabling library encryption: {}", e);
+        CommandError::from(e)
+    })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn change_library_passphrase(
+    current_passphrase: String,
+    new_passphrase: String,
+) -> Result<(), CommandError> {
+    LibraryEncryption::change_passphrase(&current_passphrase, &new_passphrase).map_err(|e| {
+        log::error!("Error changing library passphrase: {}", e);
+        CommandError::from(e)
+    })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn disable_library_encryption(passphrase: String) -> Result<(), CommandError> {
+    LibraryEncryption::disable(&passphrase).map_err(|e| {
+        log::error!("Error disabling library encryption: {}", e);
+        CommandError::from(e)
+    })
+}
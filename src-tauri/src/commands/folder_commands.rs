@@ -1,17 +1,56 @@
-use crate::definitions::{WorldApiData, WorldDisplayData, WorldModel};
+use crate::api::world::ReleaseStatus;
+use crate::app_state::AppState;
+use crate::commands::api_commands::require_login;
+use crate::commands::notification_commands::notify;
+use crate::commands::world_status_commands::emit_event_critical_alerts;
+use crate::definitions::{
+    FolderMembershipChange, FolderNameCollision, FolderRename, FolderSort, FolderStats,
+    LibraryChange, NotificationKind, TagRules, WorldApiData, WorldChanges, WorldDisplayData,
+    WorldFilter, WorldGroup, WorldGroupBy, WorldModel, WorldSummary,
+};
+use crate::errors::CommandError;
 use crate::services::folder_manager::{FolderData, FolderManager};
-use crate::services::share_service;
-use crate::{FOLDERS, PREFERENCES, WORLDS};
+use crate::services::share_service::{self, ShareRequest};
+use crate::services::{
+    ApiService, BlacklistManager, ChangeFeed, ConcurrentFetcher, GroupingService, TagService,
+    ThumbnailCache, WorldChangeService, WorldFilterService, WorldWatchService,
+};
+use crate::task::cancellable_task::{TaskContainer, TaskProgressReporter};
+use crate::task::definitions::{TaskKind, TaskProgress};
+use crate::{AUTHENTICATOR, INITSTATE};
+use chrono::{DateTime, Utc};
+use reqwest::cookie::Jar;
 use std::collections::HashSet;
+use std::sync::Arc;
+use tauri::async_runtime::Mutex;
+use tauri::{AppHandle, State};
+use uuid::Uuid;
+
+/// Icons are a single emoji or a short identifier from the icon set
+const MAX_FOLDER_ICON_LENGTH: usize = 64;
+const MAX_FOLDER_DESCRIPTION_LENGTH: usize = 1000;
 
 #[tauri::command]
 #[specta::specta]
-pub async fn add_world_to_folder(folder_name: String, world_id: String) -> Result<(), String> {
-    match FolderManager::add_world_to_folder(folder_name, world_id, FOLDERS.get(), WORLDS.get()) {
-        Ok(_) => Ok(()),
+pub async fn add_world_to_folder(
+    folder_name: String,
+    world_id: String,
+    state: State<'_, AppState>,
+) -> Result<(), CommandError> {
+    let added = not_in_folder(&state, &folder_name, vec![world_id.clone()])?;
+    match FolderManager::add_world_to_folder(
+        folder_name.clone(),
+        world_id,
+        state.folder_store(),
+        state.world_store(),
+    ) {
+        Ok(_) => {
+            record_added_to_folder(folder_name, added);
+            Ok(())
+        }
         Err(e) => {
             log::error!("Error adding world to folder: {}", e);
-            Err(e.to_string())
+            Err(CommandError::from(e))
         }
     }
 }
@@ -21,199 +60,903 @@ pub async fn add_world_to_folder(folder_name: String, world_id: String) -> Resul
 pub async fn add_worlds_to_folder(
     folder_name: String,
     world_ids: Vec<String>,
-) -> Result<(), String> {
-    match FolderManager::add_worlds_to_folder(folder_name, world_ids, FOLDERS.get(), WORLDS.get()) {
-        Ok(_) => Ok(()),
+    state: State<'_, AppState>,
+) -> Result<(), CommandError> {
+    let added = not_in_folder(&state, &folder_name, world_ids.clone())?;
+    match FolderManager::add_worlds_to_folder(
+        folder_name.clone(),
+        world_ids,
+        state.folder_store(),
+        state.world_store(),
+    ) {
+        Ok(_) => {
+            record_added_to_folder(folder_name, added);
+            Ok(())
+        }
         Err(e) => {
             log::error!("Error adding worlds to folder: {}", e);
-            Err(e.to_string())
+            Err(CommandError::from(e))
         }
     }
 }
 
+/// Adds every world matching the filter to a folder, without the frontend sending their IDs
+///
+/// # Returns
+/// The number of worlds added, worlds already in the folder are not counted
 #[tauri::command]
 #[specta::specta]
-pub async fn remove_world_from_folder(folder_name: String, world_id: String) -> Result<(), String> {
+pub async fn add_filtered_worlds_to_folder(
+    filter: WorldFilter,
+    folder_name: String,
+    state: State<'_, AppState>,
+) -> Result<u32, CommandError> {
+    let world_ids = filtered_world_ids(&filter, &state)?;
+    let added = not_in_folder(&state, &folder_name, world_ids.clone())?;
+    FolderManager::add_worlds_to_folder(
+        folder_name.clone(),
+        world_ids,
+        state.folder_store(),
+        state.world_store(),
+    )
+    .map_err(|e| {
+        log::error!("Error adding filtered worlds to folder: {}", e);
+        CommandError::from(e)
+    })?;
+    let count = added.len() as u32;
+    record_added_to_folder(folder_name, added);
+    Ok(count)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn remove_world_from_folder(
+    folder_name: String,
+    world_id: String,
+    state: State<'_, AppState>,
+) -> Result<(), CommandError> {
     match FolderManager::remove_world_from_folder(
-        folder_name,
-        world_id,
-        FOLDERS.get(),
-        WORLDS.get(),
+        folder_name.clone(),
+        world_id.clone(),
+        state.folder_store(),
+        state.world_store(),
     ) {
-        Ok(_) => Ok(()),
+        Ok(_) => {
+            ChangeFeed::record(LibraryChange::RemovedFromFolder {
+                folder_name,
+                world_id,
+            });
+            Ok(())
+        }
         Err(e) => {
             log::error!("Error removing world from folder: {}", e);
-            Err(e.to_string())
+            Err(CommandError::from(e))
         }
     }
 }
 
+/// Moves a world to another folder in one step, instead of adding and removing it separately
+/// Whether it stays in `from` follows the folder removal preference, `remove` is the answer
+/// of the user when the preference is to ask
+#[tauri::command]
+#[specta::specta]
+pub async fn move_world_between_folders(
+    world_id: String,
+    from: String,
+    to: String,
+    remove: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<FolderMembershipChange, CommandError> {
+    let preference = state.preferences()?.dont_show_remove_from_folder;
+    let change = FolderManager::move_world_between_folders(
+        world_id.clone(),
+        from,
+        to,
+        preference,
+        remove,
+        state.folder_store(),
+        state.world_store(),
+    )
+    .map_err(|e| {
+        log::error!("Error moving world between folders: {}", e);
+        CommandError::from(e)
+    })?;
+    record_membership_change(world_id, &change);
+    Ok(change)
+}
+
+/// Replaces the folders a world is in, e.g. from a folder picker with checkboxes
+#[tauri::command]
+#[specta::specta]
+pub async fn set_world_folders(
+    world_id: String,
+    folder_names: Vec<String>,
+    state: State<'_, AppState>,
+) -> Result<FolderMembershipChange, CommandError> {
+    let change = FolderManager::set_world_folders(
+        world_id.clone(),
+        folder_names,
+        state.folder_store(),
+        state.world_store(),
+    )
+    .map_err(|e| {
+        log::error!("Error setting world folders: {}", e);
+        CommandError::from(e)
+    })?;
+    record_membership_change(world_id, &change);
+    Ok(change)
+}
+
 #[tauri::command]
 #[specta::specta]
-pub async fn hide_world(world_id: String) -> Result<(), String> {
-    match FolderManager::hide_world(world_id, FOLDERS.get(), WORLDS.get()) {
-        Ok(_) => Ok(()),
+pub async fn hide_world(world_id: String, state: State<'_, AppState>) -> Result<(), CommandError> {
+    let folders = FolderManager::get_folders_for_world(world_id.clone(), state.world_store())
+        .map_err(|e| {
+            log::error!("Error hiding world: {}", e);
+            CommandError::from(e)
+        })?;
+    match FolderManager::hide_world(world_id.clone(), state.folder_store(), state.world_store()) {
+        Ok(_) => {
+            ChangeFeed::record(LibraryChange::WorldHidden { world_id, folders });
+            Ok(())
+        }
         Err(e) => {
             log::error!("Error hiding world: {}", e);
-            Err(e.to_string())
+            Err(CommandError::from(e))
         }
     }
 }
 
+/// Hides every world matching the filter, removing them from their folders
+///
+/// # Returns
+/// The number of worlds hidden
 #[tauri::command]
 #[specta::specta]
-pub async fn unhide_world(world_id: String) -> Result<(), String> {
-    match FolderManager::unhide_world(world_id, FOLDERS.get(), WORLDS.get()) {
-        Ok(_) => Ok(()),
+pub async fn hide_filtered_worlds(
+    filter: WorldFilter,
+    state: State<'_, AppState>,
+) -> Result<u32, CommandError> {
+    let world_ids = filtered_world_ids(&filter, &state)?;
+    let hidden = FolderManager::hide_worlds(&world_ids, state.folder_store(), state.world_store())
+        .map_err(|e| {
+            log::error!("Error hiding filtered worlds: {}", e);
+            CommandError::from(e)
+        })?;
+    let count = hidden.len() as u32;
+    for (world_id, folders) in hidden {
+        ChangeFeed::record(LibraryChange::WorldHidden { world_id, folders });
+    }
+    Ok(count)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn unhide_world(
+    world_id: String,
+    state: State<'_, AppState>,
+) -> Result<(), CommandError> {
+    match FolderManager::unhide_world(world_id.clone(), state.folder_store(), state.world_store()) {
+        Ok(_) => {
+            ChangeFeed::record(LibraryChange::WorldUnhidden { world_id });
+            Ok(())
+        }
         Err(e) => {
             log::error!("Error unhiding world: {}", e);
-            Err(e.to_string())
+            Err(CommandError::from(e))
         }
     }
 }
 
+/// Archived folders are only listed when `include_archived` is set
 #[tauri::command]
 #[specta::specta]
-pub async fn get_folders() -> Result<Vec<FolderData>, String> {
-    FolderManager::get_folders(FOLDERS.get()).map_err(|e| {
-        log::error!("Error getting folders: {}", e);
-        e.to_string()
-    })
+pub async fn get_folders(
+    include_archived: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<Vec<FolderData>, CommandError> {
+    FolderManager::get_folders(state.folder_store(), include_archived.unwrap_or(false)).map_err(
+        |e| {
+            log::error!("Error getting folders: {}", e);
+            CommandError::from(e)
+        },
+    )
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn archive_folder(
+    folder_name: String,
+    state: State<'_, AppState>,
+) -> Result<(), CommandError> {
+    set_folder_archived(folder_name, true, &state)
 }
 
 #[tauri::command]
 #[specta::specta]
-pub async fn create_folder(name: String) -> Result<String, String> {
+pub async fn unarchive_folder(
+    folder_name: String,
+    state: State<'_, AppState>,
+) -> Result<(), CommandError> {
+    set_folder_archived(folder_name, false, &state)
+}
+
+fn set_folder_archived(
+    folder_name: String,
+    archived: bool,
+    state: &AppState,
+) -> Result<(), CommandError> {
+    let was_archived =
+        FolderManager::set_folder_archived(folder_name.clone(), archived, state.folder_store())
+            .map_err(|e| {
+                log::error!("Error archiving folder: {}", e);
+                CommandError::from(e)
+            })?;
+    if was_archived != archived {
+        ChangeFeed::record(LibraryChange::FolderArchived {
+            folder_name,
+            archived,
+        });
+    }
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn create_folder(
+    name: String,
+    state: State<'_, AppState>,
+) -> Result<String, CommandError> {
     log::info!("Creating folder: {}", name);
-    FolderManager::create_folder(name, FOLDERS.get()).map_err(|e| {
+    let folder_name = FolderManager::create_folder(name, state.folder_store()).map_err(|e| {
         log::error!("Error creating folder: {}", e);
-        e.to_string()
-    })
+        CommandError::from(e)
+    })?;
+    ChangeFeed::record(LibraryChange::FolderCreated {
+        folder_name: folder_name.clone(),
+    });
+    Ok(folder_name)
 }
 #[tauri::command]
 #[specta::specta]
-pub async fn delete_folder(name: String) -> Result<(), String> {
-    FolderManager::delete_folder(name, FOLDERS.get(), WORLDS.get()).map_err(|e| {
-        log::error!("Error deleting folder: {}", e);
-        e.to_string()
-    })
+pub async fn delete_folder(name: String, state: State<'_, AppState>) -> Result<(), CommandError> {
+    let world_ids = state
+        .folders()?
+        .iter()
+        .find(|f| f.folder_name == name)
+        .map(|f| f.world_ids.clone())
+        .unwrap_or_default();
+    FolderManager::delete_folder(name.clone(), state.folder_store(), state.world_store()).map_err(
+        |e| {
+            log::error!("Error deleting folder: {}", e);
+            CommandError::from(e)
+        },
+    )?;
+    ChangeFeed::record(LibraryChange::FolderDeleted {
+        folder_name: name,
+        world_ids,
+    });
+    Ok(())
 }
 
 #[tauri::command]
 #[specta::specta]
-pub async fn move_folder(folder_name: String, new_index: usize) -> Result<(), String> {
-    FolderManager::move_folder(folder_name, new_index, FOLDERS.get()).map_err(|e| {
+pub async fn move_folder(
+    folder_name: String,
+    new_index: usize,
+    state: State<'_, AppState>,
+) -> Result<(), CommandError> {
+    FolderManager::move_folder(folder_name, new_index, state.folder_store()).map_err(|e| {
         log::error!("Error moving folder: {}", e);
-        e.to_string()
+        CommandError::from(e)
     })
 }
 
+/// Moves a world within a folder, setting the order used when playing the folder as a playlist
+#[tauri::command]
+#[specta::specta]
+pub async fn move_world_in_folder(
+    folder_name: String,
+    world_id: String,
+    new_index: usize,
+    state: State<'_, AppState>,
+) -> Result<(), CommandError> {
+    FolderManager::move_world_in_folder(folder_name, world_id, new_index, state.folder_store())
+        .map_err(|e| {
+            log::error!("Error moving world in folder: {}", e);
+            CommandError::from(e)
+        })
+}
+
+/// Renames a folder, `on_collision` decides what happens if another folder already has the
+/// new name and defaults to rejecting the rename
+///
+/// # Returns
+/// The name the folder has now
 #[tauri::command]
 #[specta::specta]
-pub async fn rename_folder(old_name: String, new_name: String) -> Result<(), String> {
-    FolderManager::rename_folder(
-        old_name,
+pub async fn rename_folder(
+    old_name: String,
+    new_name: String,
+    on_collision: Option<FolderNameCollision>,
+    state: State<'_, AppState>,
+) -> Result<String, CommandError> {
+    let rename = FolderManager::rename_folder(
+        old_name.clone(),
         new_name,
-        FOLDERS.get(),
-        WORLDS.get(),
-        PREFERENCES.get(),
+        on_collision.unwrap_or_default(),
+        state.folder_store(),
+        state.world_store(),
+        state.preference_store(),
     )
     .map_err(|e| {
         log::error!("Error renaming folder: {}", e);
-        e.to_string()
+        CommandError::from(e)
+    })?;
+    match rename {
+        FolderRename::Renamed { folder_name } => {
+            ChangeFeed::record(LibraryChange::FolderRenamed {
+                old_name,
+                new_name: folder_name.clone(),
+            });
+            Ok(folder_name)
+        }
+        FolderRename::Merged {
+            folder_name,
+            world_ids,
+            added,
+        } => {
+            // Undone in reverse, taking the worlds out of the folder and recreating the old one
+            ChangeFeed::record(LibraryChange::FolderDeleted {
+                folder_name: old_name,
+                world_ids,
+            });
+            record_added_to_folder(folder_name.clone(), added);
+            Ok(folder_name)
+        }
+    }
+}
+
+/// Opens a folder in a window of its own, e.g. to compare two folders side by side
+/// The window's label carries the folder name, see `folder_window::label_for`
+#[tauri::command]
+#[specta::specta]
+pub async fn open_folder_window(
+    folder_name: String,
+    handle: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<(), CommandError> {
+    if !state
+        .folders()?
+        .iter()
+        .any(|f| f.folder_name == folder_name)
+    {
+        return Err(CommandError::not_found(format!(
+            "Folder not found: {}",
+            folder_name
+        )));
+    }
+    crate::folder_window::open(&handle, &folder_name).map_err(|e| {
+        log::error!("Error opening folder window: {}", e);
+        CommandError::internal(e.to_string())
     })
 }
 
 #[tauri::command]
 #[specta::specta]
-pub async fn set_folder_color(folder_name: String, color: Option<String>) -> Result<(), String> {
-    FolderManager::set_folder_color(folder_name, color, FOLDERS.get()).map_err(|e| {
+pub async fn set_folder_color(
+    folder_name: String,
+    color: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<(), CommandError> {
+    FolderManager::set_folder_color(folder_name, color, state.folder_store()).map_err(|e| {
         log::error!("Error setting folder color: {}", e);
-        e.to_string()
+        CommandError::from(e)
+    })
+}
+
+/// Sets the emoji or icon identifier shown next to a folder, None or "" resets it
+#[tauri::command]
+#[specta::specta]
+pub async fn set_folder_icon(
+    folder_name: String,
+    icon: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<(), CommandError> {
+    let icon = icon.filter(|i| !i.trim().is_empty());
+    if icon
+        .as_ref()
+        .is_some_and(|i| i.chars().count() > MAX_FOLDER_ICON_LENGTH)
+    {
+        return Err(CommandError::validation(format!(
+            "Folder icon must be at most {} characters",
+            MAX_FOLDER_ICON_LENGTH
+        )));
+    }
+    FolderManager::set_folder_icon(folder_name, icon, state.folder_store()).map_err(|e| {
+        log::error!("Error setting folder icon: {}", e);
+        CommandError::from(e)
     })
 }
 
+/// Sets the description of a folder, None or "" resets it
 #[tauri::command]
 #[specta::specta]
-pub async fn get_worlds(folder_name: String) -> Result<Vec<WorldDisplayData>, String> {
-    FolderManager::get_worlds(folder_name, FOLDERS.get(), WORLDS.get()).map_err(|e| {
+pub async fn set_folder_description(
+    folder_name: String,
+    description: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<(), CommandError> {
+    let description = description.filter(|d| !d.trim().is_empty());
+    if description
+        .as_ref()
+        .is_some_and(|d| d.chars().count() > MAX_FOLDER_DESCRIPTION_LENGTH)
+    {
+        return Err(CommandError::validation(format!(
+            "Folder description must be at most {} characters",
+            MAX_FOLDER_DESCRIPTION_LENGTH
+        )));
+    }
+    FolderManager::set_folder_description(folder_name, description, state.folder_store()).map_err(
+        |e| {
+            log::error!("Error setting folder description: {}", e);
+            CommandError::from(e)
+        },
+    )
+}
+
+/// Sets the sort order of a folder, overriding the global one
+/// A `field` of None makes the folder follow the global sort order again
+#[tauri::command]
+#[specta::specta]
+pub async fn set_folder_sort(
+    folder_name: String,
+    field: Option<String>,
+    direction: String,
+    state: State<'_, AppState>,
+) -> Result<(), CommandError> {
+    let sort = field.map(|field| FolderSort { field, direction });
+    FolderManager::set_folder_sort(folder_name, sort, state.folder_store()).map_err(|e| {
+        log::error!("Error setting folder sort: {}", e);
+        CommandError::from(e)
+    })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_worlds(
+    folder_name: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<WorldDisplayData>, CommandError> {
+    FolderManager::get_worlds(folder_name, state.folder_store(), state.world_store()).map_err(|e| {
         log::error!("Error getting worlds: {}", e);
-        e.to_string()
+        CommandError::from(e)
+    })
+}
+
+/// Gets the worlds of a folder or special folder grouped into sections for the grid
+///
+/// # Arguments
+/// * `source` - "All Worlds", "Unclassified Worlds", "Hidden Worlds" or a folder name
+/// * `group_by` - What to group the worlds by
+#[tauri::command]
+#[specta::specta]
+pub async fn get_worlds_grouped(
+    source: String,
+    group_by: WorldGroupBy,
+    state: State<'_, AppState>,
+) -> Result<Vec<WorldGroup>, CommandError> {
+    GroupingService::get_worlds_grouped(
+        &source,
+        group_by,
+        state.folder_store(),
+        state.world_store(),
+    )
+    .map_err(|e| {
+        log::error!("Error grouping worlds: {}", e);
+        CommandError::from(e)
     })
 }
 
+/// Refreshes the stale worlds of a folder from the API as a cancellable task
+/// Returns the task ID immediately; the number of updated and unavailable worlds is reported
+/// through `TaskStatusChanged` and can be read with `get_task_progress` once completed
 #[tauri::command]
 #[specta::specta]
-pub async fn get_all_worlds() -> Result<Vec<WorldDisplayData>, String> {
-    FolderManager::get_all_worlds(WORLDS.get()).map_err(|e| {
+pub async fn refresh_folder_worlds(
+    folder_name: String,
+    handle: State<'_, AppHandle>,
+    task_container: State<'_, Arc<Mutex<TaskContainer>>>,
+    state: State<'_, AppState>,
+) -> Result<Uuid, CommandError> {
+    let stale = FolderManager::get_stale_folder_worlds(
+        folder_name,
+        state.folder_store(),
+        state.world_store(),
+    )
+    .map_err(|e| {
+        log::error!("Error getting stale folder worlds: {}", e);
+        CommandError::from(e)
+    })?;
+    let cookie_store = require_login().await?;
+    let handle = (*handle).clone();
+    let state = state.inner().clone();
+
+    task_container
+        .lock()
+        .await
+        .run_with_progress(TaskKind::FolderRefresh, move |reporter| {
+            refresh_stale_worlds(stale, cookie_store, reporter, handle, state)
+        })
+        .map_err(CommandError::internal)
+}
+
+/// Fetches each stale world, then stores the public ones in one go
+/// Worlds which are no longer public are left untouched and reported as unavailable,
+/// and worlds which fail to fetch are skipped so they are retried on the next refresh
+async fn refresh_stale_worlds(
+    stale: Vec<WorldApiData>,
+    cookie_store: Arc<Jar>,
+    reporter: TaskProgressReporter,
+    handle: AppHandle,
+    state: AppState,
+) -> Result<(), String> {
+    let total = stale.len() as u32;
+    let mut checked = 0;
+    let mut fetched = vec![];
+    let mut unavailable = vec![];
+
+    let mut fetcher = ConcurrentFetcher::new();
+    for old in &stale {
+        let cookie_store = cookie_store.clone();
+        let world_id = old.world_id.clone();
+        fetcher.spawn(async move {
+            ApiService::get_world_with_release_status(cookie_store, &world_id).await
+        });
+    }
+
+    while let Some((index, result)) = fetcher.next().await {
+        let old = &stale[index];
+        match result {
+            Ok((new, release_status)) => {
+                if release_status != ReleaseStatus::Public {
+                    unavailable.push(NotificationKind::WorldUnavailable {
+                        world_id: old.world_id.clone(),
+                        world_name: new.world_name.clone(),
+                        reason: format!("release status changed to {}", release_status),
+                    });
+                }
+                fetched.push((index, (new, release_status)));
+            }
+            Err(e) => log::warn!("Failed to refresh world {}: {}", old.world_id, e),
+        }
+        checked += 1;
+
+        reporter
+            .report(TaskProgress::FolderRefresh {
+                checked,
+                total,
+                updated: 0,
+                unavailable: unavailable.len() as u32,
+            })
+            .await;
+    }
+
+    // Keep the folder's order, as fetches complete in any order
+    fetched.sort_by_key(|(index, _)| *index);
+    let fetched: Vec<_> = fetched.into_iter().map(|(_, fetched)| fetched).collect();
+
+    // Checked before storing, as storing overwrites the watched fields
+    let alerts =
+        WorldWatchService::check_fetched(&fetched, state.folder_store(), state.world_store())
+            .map_err(|e| {
+                log::error!("Failed to check event critical worlds: {}", e);
+                format!("Failed to check event critical worlds: {}", e)
+            })?;
+    emit_event_critical_alerts(&alerts, &handle);
+
+    let refreshed = fetched
+        .into_iter()
+        .filter(|(_, release_status)| *release_status == ReleaseStatus::Public)
+        .map(|(world, _)| world)
+        .collect();
+    let updates =
+        FolderManager::add_worlds(state.world_store(), refreshed, state.blacklist_store())
+            .map_err(|e| {
+                log::error!("Failed to store refreshed worlds: {}", e);
+                format!("Failed to store refreshed worlds: {}", e)
+            })?;
+    let updated = updates.len() as u32;
+    let unavailable_count = unavailable.len() as u32;
+
+    let mut notifications: Vec<NotificationKind> = updates.into_iter().map(Into::into).collect();
+    notifications.extend(unavailable);
+    notify(notifications, &handle);
+
+    reporter
+        .report(TaskProgress::FolderRefresh {
+            checked,
+            total,
+            updated,
+            unavailable: unavailable_count,
+        })
+        .await;
+    Ok(())
+}
+
+/// Downloads the thumbnails of a folder's worlds ahead of time as a cancellable task, e.g.
+/// before presenting the folder at an event, so no image has to stream in on stage
+/// Returns the task ID immediately; progress is reported through `TaskStatusChanged`
+#[tauri::command]
+#[specta::specta]
+pub async fn prefetch_folder_thumbnails(
+    folder_name: String,
+    task_container: State<'_, Arc<Mutex<TaskContainer>>>,
+    state: State<'_, AppState>,
+) -> Result<Uuid, CommandError> {
+    let worlds = FolderManager::get_worlds(folder_name, state.folder_store(), state.world_store())
+        .map_err(|e| {
+            log::error!("Error getting folder worlds: {}", e);
+            CommandError::from(e)
+        })?;
+    let cookie_store = require_login().await?;
+
+    task_container
+        .lock()
+        .await
+        .run_with_progress(TaskKind::ThumbnailPrefetch, move |reporter| {
+            prefetch_thumbnails(worlds, cookie_store, reporter)
+        })
+        .map_err(CommandError::internal)
+}
+
+/// Downloads each world's thumbnail which is not cached yet
+/// Failed downloads are only counted, as the app falls back to loading the image itself
+async fn prefetch_thumbnails(
+    worlds: Vec<WorldDisplayData>,
+    cookie_store: Arc<Jar>,
+    reporter: TaskProgressReporter,
+) -> Result<(), String> {
+    let total = worlds.len() as u32;
+    let mut checked = 0;
+    let mut downloaded = 0;
+    let mut failed = 0;
+
+    let mut fetcher = ConcurrentFetcher::new();
+    for world in worlds {
+        let cookie_store = cookie_store.clone();
+        fetcher.spawn(async move {
+            ThumbnailCache::fetch(cookie_store, &world.world_id, &world.thumbnail_url)
+                .await
+                .map_err(|e| (world.world_id, e))
+        });
+    }
+
+    while let Some((_, result)) = fetcher.next().await {
+        match result {
+            Ok(true) => downloaded += 1,
+            Ok(false) => {}
+            Err((world_id, e)) => {
+                log::warn!("Failed to prefetch thumbnail of {}: {}", world_id, e);
+                failed += 1;
+            }
+        }
+        checked += 1;
+
+        reporter
+            .report(TaskProgress::ThumbnailPrefetch {
+                checked,
+                total,
+                downloaded,
+                failed,
+            })
+            .await;
+    }
+    Ok(())
+}
+
+/// Gets the path of a world's downloaded thumbnail, None if it is not cached
+#[tauri::command]
+#[specta::specta]
+pub fn get_cached_thumbnail(world_id: String) -> Option<String> {
+    ThumbnailCache::get(&world_id).map(|path| path.to_string_lossy().to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_all_worlds(
+    state: State<'_, AppState>,
+) -> Result<Vec<WorldDisplayData>, CommandError> {
+    FolderManager::get_all_worlds(state.world_store()).map_err(|e| {
         log::error!("Error getting all worlds: {}", e);
-        e.to_string()
+        CommandError::from(e)
+    })
+}
+
+/// Gets summaries of the worlds of a folder or special folder, for the grids of large folders
+/// The full data of the cards shown is fetched with `get_worlds_by_id`
+///
+/// # Arguments
+/// * `source` - "All Worlds", "Unclassified Worlds", "Hidden Worlds" or a folder name
+#[tauri::command]
+#[specta::specta]
+pub async fn get_world_summaries(
+    source: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<WorldSummary>, CommandError> {
+    FolderManager::get_world_summaries(&source, state.folder_store(), state.world_store()).map_err(
+        |e| {
+            log::error!("Error getting world summaries: {}", e);
+            CommandError::from(e)
+        },
+    )
+}
+
+/// Gets the display data of worlds by ID, in the order given
+#[tauri::command]
+#[specta::specta]
+pub async fn get_worlds_by_id(
+    world_ids: Vec<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<WorldDisplayData>, CommandError> {
+    FolderManager::get_worlds_by_id(&world_ids, state.world_store()).map_err(|e| {
+        log::error!("Error getting worlds by ID: {}", e);
+        CommandError::from(e)
     })
 }
 
+/// Gets the worlds changed after `timestamp`, for updating the shown worlds in place
+/// If the result is not complete, every world should be fetched again
 #[tauri::command]
 #[specta::specta]
-pub async fn get_unclassified_worlds() -> Result<Vec<WorldDisplayData>, String> {
-    FolderManager::get_unclassified_worlds(WORLDS.get()).map_err(|e| {
+pub async fn get_worlds_changed_since(
+    timestamp: DateTime<Utc>,
+    state: State<'_, AppState>,
+) -> Result<WorldChanges, CommandError> {
+    WorldChangeService::changed_since(timestamp, state.world_store()).map_err(|e| {
+        log::error!("Error getting changed worlds: {}", e);
+        CommandError::from(e)
+    })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_unclassified_worlds(
+    state: State<'_, AppState>,
+) -> Result<Vec<WorldDisplayData>, CommandError> {
+    FolderManager::get_unclassified_worlds(state.world_store()).map_err(|e| {
         log::error!("Error getting unclassified worlds: {}", e);
-        e.to_string()
+        CommandError::from(e)
     })
 }
 
 #[tauri::command]
 #[specta::specta]
-pub async fn get_hidden_worlds() -> Result<Vec<WorldDisplayData>, String> {
-    FolderManager::get_hidden_worlds(WORLDS.get()).map_err(|e| {
+pub async fn get_hidden_worlds(
+    state: State<'_, AppState>,
+) -> Result<Vec<WorldDisplayData>, CommandError> {
+    FolderManager::get_hidden_worlds(state.world_store()).map_err(|e| {
         log::error!("Error getting hidden worlds: {}", e);
-        e.to_string()
+        CommandError::from(e)
     })
 }
 
+/// Gets the statistics of a folder for its header info panel
 #[tauri::command]
 #[specta::specta]
-pub async fn get_tags_by_count() -> Result<Vec<String>, String> {
-    FolderManager::get_tags_by_count(WORLDS.get()).map_err(|e| {
+pub async fn get_folder_stats(
+    folder_name: String,
+    state: State<'_, AppState>,
+) -> Result<FolderStats, CommandError> {
+    FolderManager::get_folder_stats(folder_name, state.folder_store(), state.world_store()).map_err(
+        |e| {
+            log::error!("Error getting folder stats: {}", e);
+            CommandError::from(e)
+        },
+    )
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_tags_by_count(state: State<'_, AppState>) -> Result<Vec<String>, CommandError> {
+    FolderManager::get_tags_by_count(state.world_store()).map_err(|e| {
         log::error!("Error getting tags by count: {}", e);
-        e.to_string()
+        CommandError::from(e)
     })
 }
 
 #[tauri::command]
 #[specta::specta]
-pub async fn get_authors_by_count() -> Result<Vec<String>, String> {
-    FolderManager::get_authors_by_count(WORLDS.get()).map_err(|e| {
-        log::error!("Error getting authors by count: {}", e);
-        e.to_string()
+pub fn get_tag_rules() -> TagRules {
+    TagService::get_rules()
+}
+
+/// Shows an author tag as another one, e.g. "chill_" as "chill"
+/// Only changes how tags are shown and filtered, not the worlds' VRChat data
+#[tauri::command]
+#[specta::specta]
+pub fn alias_tag(from: String, to: String) -> Result<TagRules, CommandError> {
+    TagService::alias_tag(&from, &to).map_err(|e| {
+        log::error!("Error aliasing tag: {}", e);
+        CommandError::from(e)
     })
 }
 
 #[tauri::command]
 #[specta::specta]
-pub async fn delete_world(world_id: String) -> Result<(), String> {
-    FolderManager::delete_world(world_id, FOLDERS.get(), WORLDS.get()).map_err(|e| {
-        log::error!("Error deleting world: {}", e);
-        e.to_string()
+pub fn remove_tag_alias(tag: String) -> Result<TagRules, CommandError> {
+    TagService::remove_alias(&tag).map_err(|e| {
+        log::error!("Error removing tag alias: {}", e);
+        CommandError::from(e)
     })
 }
 
 #[tauri::command]
 #[specta::specta]
-pub async fn get_folders_for_world(world_id: String) -> Result<Vec<String>, String> {
-    FolderManager::get_folders_for_world(world_id, WORLDS.get()).map_err(|e| {
+pub fn hide_tag(tag: String) -> Result<TagRules, CommandError> {
+    TagService::set_tag_hidden(&tag, true).map_err(|e| {
+        log::error!("Error hiding tag: {}", e);
+        CommandError::from(e)
+    })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn unhide_tag(tag: String) -> Result<TagRules, CommandError> {
+    TagService::set_tag_hidden(&tag, false).map_err(|e| {
+        log::error!("Error unhiding tag: {}", e);
+        CommandError::from(e)
+    })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_authors_by_count(state: State<'_, AppState>) -> Result<Vec<String>, CommandError> {
+    FolderManager::get_authors_by_count(state.world_store()).map_err(|e| {
+        log::error!("Error getting authors by count: {}", e);
+        CommandError::from(e)
+    })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn delete_world(
+    world_id: String,
+    state: State<'_, AppState>,
+) -> Result<(), CommandError> {
+    FolderManager::delete_world(world_id.clone(), state.folder_store(), state.world_store())
+        .map_err(|e| {
+            log::error!("Error deleting world: {}", e);
+            CommandError::from(e)
+        })?;
+    ChangeFeed::record(LibraryChange::WorldDeleted { world_id });
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_folders_for_world(
+    world_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<String>, CommandError> {
+    FolderManager::get_folders_for_world(world_id, state.world_store()).map_err(|e| {
         log::error!("Error getting folders for world: {}", e);
-        e.to_string()
+        CommandError::from(e)
     })
 }
 
 #[tauri::command]
 #[specta::specta]
-pub async fn share_folder(folder_name: String) -> Result<String, String> {
-    let result: Result<(String, String), String> =
-        share_service::share_folder(&folder_name, FOLDERS.get(), WORLDS.get())
+pub async fn share_folder(
+    folder_name: String,
+    state: State<'_, AppState>,
+) -> Result<String, CommandError> {
+    let result: Result<(String, String), CommandError> =
+        share_service::share_folder(&folder_name, state.folder_store(), state.world_store())
             .await
             .map_err(|e| {
                 log::error!("Error sharing folder: {}", e);
-                e.to_string()
+                CommandError::network(e)
             });
     let (share_id, ts) = match &result {
         Ok(s) => s,
@@ -221,34 +964,82 @@ pub async fn share_folder(folder_name: String) -> Result<String, String> {
     };
     FolderManager::set_folder_share(
         folder_name.clone(),
-        FOLDERS.get(),
+        state.folder_store(),
         share_id.clone(),
         ts.clone(),
     )
     .map_err(|e| {
         log::error!("Error setting folder share: {}", e);
-        e.to_string()
+        CommandError::from(e)
     })?;
     Ok(share_id.to_string())
 }
 
 #[tauri::command]
 #[specta::specta]
-pub async fn update_folder_share(folder_name: String) -> Result<Option<String>, String> {
-    let result: Result<Option<String>, String> =
-        FolderManager::update_folder_share(folder_name, FOLDERS.get()).map_err(|e| {
+pub async fn update_folder_share(
+    folder_name: String,
+    state: State<'_, AppState>,
+) -> Result<Option<String>, CommandError> {
+    let result: Result<Option<String>, CommandError> =
+        FolderManager::update_folder_share(folder_name, state.folder_store()).map_err(|e| {
             log::error!("Error updating folder share: {}", e);
-            e.to_string()
+            CommandError::from(e)
         });
     result
 }
 
+/// Replaces the shared data of the worlds not in the library yet with their current data,
+/// as the folder may have been shared long ago
+/// Worlds which fail to fetch or are no longer public keep their shared data, and nothing
+/// is fetched while logged out
+async fn refresh_shared_worlds(
+    worlds: Vec<WorldApiData>,
+    state: &AppState,
+) -> Result<Vec<WorldApiData>, CommandError> {
+    if INITSTATE.get().read().await.user_id.is_empty() {
+        return Ok(worlds);
+    }
+    let new_ids: HashSet<String> = ChangeFeed::missing_worlds(
+        state.world_store(),
+        worlds.iter().map(|w| w.world_id.clone()).collect(),
+    )?
+    .into_iter()
+    .collect();
+    let cookie_store = AUTHENTICATOR.get().read().await.get_cookies();
+
+    let mut fetcher = ConcurrentFetcher::new();
+    let mut fetched_indices = vec![];
+    for (index, world) in worlds.iter().enumerate() {
+        if new_ids.contains(&world.world_id) {
+            let cookie_store = cookie_store.clone();
+            let world_id = world.world_id.clone();
+            fetcher.spawn(async move {
+                ApiService::get_world_with_release_status(cookie_store, &world_id).await
+            });
+            fetched_indices.push(index);
+        }
+    }
+
+    let mut worlds = worlds;
+    while let Some((fetch_index, result)) = fetcher.next().await {
+        let world = &mut worlds[fetched_indices[fetch_index]];
+        match result {
+            Ok((fetched, ReleaseStatus::Public)) => *world = fetched,
+            Ok(_) => log::info!("Keeping shared data of non-public world {}", world.world_id),
+            Err(e) => log::warn!("Failed to refresh shared world {}: {}", world.world_id, e),
+        }
+    }
+    Ok(worlds)
+}
+
 #[tauri::command]
 #[specta::specta]
 /// Downloads a shared folder and adds its worlds to the local database.
 ///
 /// This function attempts to download a folder using the provided `share_id`, creates the folder locally,
 /// adds the worlds from the shared folder to the local world list, and then adds all non-hidden worlds to the new folder.
+/// Blacklisted worlds are dropped entirely.
 /// Worlds that are already hidden are not added to the folder and are returned for further handling.
 ///
 /// # Arguments
@@ -260,25 +1051,38 @@ pub async fn update_folder_share(folder_name: String) -> Result<Option<String>,
 /// `Ok((String, Vec<String>))`: A tuple containing the new folder name and a vector of world IDs that were hidden and not added to the folder.
 ///
 /// # Errors
-/// Returns an error string if any operation fails, such as downloading the folder, creating the folder, adding worlds, or retrieving hidden worlds.
-pub async fn download_folder(share_id: String) -> Result<(String, Vec<WorldDisplayData>), String> {
+/// Returns a `CommandError` if any operation fails, such as downloading the folder, creating the folder, adding worlds, or retrieving hidden worlds.
+pub async fn download_folder(
+    share_id: String,
+    state: State<'_, AppState>,
+) -> Result<(String, Vec<WorldDisplayData>), CommandError> {
     // Download the folder and its worlds
-    let result: Result<(String, Vec<WorldApiData>), String> =
-        share_service::download_folder(&share_id)
-            .await
-            .map_err(|e| {
-                log::error!("Error downloading folder: {}", e);
-                e.to_string()
-            });
-    let (folder_name, mut worlds) = match result {
+    let result: Result<ShareRequest, CommandError> = share_service::download_folder(&share_id)
+        .await
+        .map_err(|e| {
+            log::error!("Error downloading folder: {}", e);
+            CommandError::network(e)
+        });
+    let shared = match result {
         Ok(data) => data,
         Err(e) => return Err(e),
     };
+    let folder_name = shared.name;
+    let worlds = shared.worlds;
+
+    // Drop blacklisted worlds so they are neither stored nor added to the folder
+    let worlds =
+        BlacklistManager::filter_api_data(worlds, state.blacklist_store()).map_err(|e| {
+            log::error!("Error filtering blacklisted worlds: {}", e);
+            CommandError::from(e)
+        })?;
+
+    let mut worlds = refresh_shared_worlds(worlds, &state).await?;
 
     // Get hidden world IDs before adding new worlds
-    let already_hidden = FolderManager::get_hidden_worlds(WORLDS.get()).map_err(|e| {
+    let already_hidden = FolderManager::get_hidden_worlds(state.world_store()).map_err(|e| {
         log::error!("Error getting hidden worlds: {}", e);
-        e.to_string()
+        CommandError::from(e)
     })?;
     let hidden_ids: HashSet<_> = already_hidden.iter().map(|w| &w.world_id).collect();
 
@@ -288,31 +1092,75 @@ pub async fn download_folder(share_id: String) -> Result<(String, Vec<WorldDispl
         .partition(|world| !hidden_ids.contains(&world.world_id));
 
     // Add all worlds to the database in one go
-    FolderManager::add_worlds(WORLDS.get(), non_hidden_worlds.clone()).map_err(|e| {
+    let missing = ChangeFeed::missing_worlds(
+        state.world_store(),
+        non_hidden_worlds
+            .iter()
+            .map(|w| w.world_id.clone())
+            .collect(),
+    )?;
+    FolderManager::add_worlds(
+        state.world_store(),
+        non_hidden_worlds.clone(),
+        state.blacklist_store(),
+    )
+    .map_err(|e| {
         log::error!("Error adding worlds: {}", e);
-        e.to_string()
+        CommandError::from(e)
     })?;
 
     // Create the folder
     let new_folder_name =
-        FolderManager::create_folder(folder_name, FOLDERS.get()).map_err(|e| {
+        FolderManager::create_folder(folder_name, state.folder_store()).map_err(|e| {
             log::error!("Error creating folder: {}", e);
-            e.to_string()
+            CommandError::from(e)
+        })?;
+
+    ChangeFeed::record_worlds_added(state.world_store(), missing);
+    ChangeFeed::record(LibraryChange::FolderCreated {
+        folder_name: new_folder_name.clone(),
+    });
+
+    // Keep the icon and description it was shared with
+    if shared.icon.is_some() {
+        FolderManager::set_folder_icon(new_folder_name.clone(), shared.icon, state.folder_store())
+            .map_err(|e| {
+                log::error!("Error setting folder icon: {}", e);
+                CommandError::from(e)
+            })?;
+    }
+    if shared.description.is_some() {
+        FolderManager::set_folder_description(
+            new_folder_name.clone(),
+            shared.description,
+            state.folder_store(),
+        )
+        .map_err(|e| {
+            log::error!("Error setting folder description: {}", e);
+            CommandError::from(e)
         })?;
+    }
 
     // Add only non-hidden worlds to the folder
     for world in non_hidden_worlds.iter() {
         FolderManager::add_world_to_folder(
             new_folder_name.clone(),
             world.world_id.clone(),
-            FOLDERS.get(),
-            WORLDS.get(),
+            state.folder_store(),
+            state.world_store(),
         )
         .map_err(|e| {
             log::error!("Error adding world to folder: {}", e);
-            e.to_string()
+            CommandError::from(e)
         })?;
     }
+    record_added_to_folder(
+        new_folder_name.clone(),
+        non_hidden_worlds
+            .iter()
+            .map(|w| w.world_id.clone())
+            .collect(),
+    );
 
     // Convert hidden worlds to display data
     let hidden_worlds: Vec<WorldDisplayData> = hidden_worlds
@@ -322,3 +1170,54 @@ pub async fn download_folder(share_id: String) -> Result<(String, Vec<WorldDispl
         .collect();
     Ok((new_folder_name, hidden_worlds))
 }
+
+/// The worlds of `world_ids` which are not in the folder yet, so adding them changes it
+/// Evaluates a filter against the library
+pub(crate) fn filtered_world_ids(
+    filter: &WorldFilter,
+    state: &AppState,
+) -> Result<Vec<String>, CommandError> {
+    WorldFilterService::matching_world_ids(filter, state.folder_store(), state.world_store())
+        .map_err(|e| {
+            log::error!("Error evaluating world filter: {}", e);
+            CommandError::from(e)
+        })
+}
+
+fn not_in_folder(
+    state: &AppState,
+    folder_name: &str,
+    world_ids: Vec<String>,
+) -> Result<Vec<String>, CommandError> {
+    let folders = state.folders()?;
+    let existing = folders
+        .iter()
+        .find(|f| f.folder_name == folder_name)
+        .map(|f| f.world_ids.as_slice())
+        .unwrap_or_default();
+    Ok(world_ids
+        .into_iter()
+        .filter(|id| !existing.contains(id))
+        .collect())
+}
+
+fn record_added_to_folder(folder_name: String, world_ids: Vec<String>) {
+    if !world_ids.is_empty() {
+        ChangeFeed::record(LibraryChange::AddedToFolder {
+            folder_name,
+            world_ids,
+        });
+    }
+}
+
+fn record_membership_change(world_id: String, change: &FolderMembershipChange) {
+    for folder_name in &change.added_to {
+        record_added_to_folder(folder_name.clone(), vec![world_id.clone()]);
+    }
+    for folder_name in &change.removed_from {
+        ChangeFeed::record(LibraryChange::RemovedFromFolder {
+            folder_name: folder_name.clone(),
+            world_id: world_id.clone(),
+        });
+    }
+}
@@ -0,0 +1,61 @@
+use std::collections::HashMap;
+
+use tauri::State;
+
+use crate::app_state::AppState;
+use crate::commands::api_commands::require_login;
+use crate::definitions::{GroupFolderLink, GroupFolderSyncResult};
+use crate::errors::CommandError;
+use crate::services::GroupFolderService;
+
+/// Gets the folders kept filled from a VRChat group, by folder name
+#[tauri::command]
+#[specta::specta]
+pub async fn get_group_folders() -> Result<HashMap<String, GroupFolderLink>, CommandError> {
+    Ok(GroupFolderService::links())
+}
+
+/// Links a folder to a VRChat group and syncs it right away
+/// Without a folder name, a folder named after the group is used, created if needed
+/// Linked folders are synced again every few hours
+#[tauri::command]
+#[specta::specta]
+pub async fn link_group_folder(
+    group_id: String,
+    group_name: String,
+    folder_name: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<GroupFolderSyncResult, CommandError> {
+    let folder_name =
+        GroupFolderService::link(folder_name, group_id, group_name, &state).map_err(|e| {
+            log::error!("Error linking group folder: {}", e);
+            CommandError::from(e)
+        })?;
+    sync_group_folder(folder_name, state).await
+}
+
+/// Stops syncing a folder with its group, keeping the worlds in it
+#[tauri::command]
+#[specta::specta]
+pub async fn unlink_group_folder(folder_name: String) -> Result<(), CommandError> {
+    GroupFolderService::unlink(&folder_name).map_err(|e| {
+        log::error!("Error unlinking group folder: {}", e);
+        CommandError::from(e)
+    })
+}
+
+/// Adds the worlds a folder's group is hosting instances of to the folder
+#[tauri::command]
+#[specta::specta]
+pub async fn sync_group_folder(
+    folder_name: String,
+    state: State<'_, AppState>,
+) -> Result<GroupFolderSyncResult, CommandError> {
+    let cookie_store = require_login().await?;
+    GroupFolderService::sync(&folder_name, cookie_store, &state)
+        .await
+        .map_err(|e| {
+            log::error!("Error syncing group folder: {}", e);
+            CommandError::from(e)
+        })
+}
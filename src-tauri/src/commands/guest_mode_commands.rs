@@ -0,0 +1,42 @@
+use crate::definitions::GuestModeStatus;
+use crate::errors::CommandError;
+use crate::services::GuestMode;
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_guest_mode_status() -> Result<GuestModeStatus, CommandError> {
+    Ok(GuestMode::status())
+}
+
+/// Sets the PIN which leaves guest mode, the current PIN is needed to change it
+#[tauri::command]
+#[specta::specta]
+pub async fn set_guest_mode_pin(
+    current_pin: Option<String>,
+    pin: String,
+) -> Result<(), CommandError> {
+    GuestMode::set_pin(current_pin.as_deref(), &pin).map_err(|e| {
+        log::warn!("Error setting guest mode PIN: {}", e);
+        CommandError::from(e)
+    })
+}
+
+/// Switches the app into read-only guest mode, for showing the library on a shared screen
+/// The UI hides screens needing the VRChat account while `GuestModeStatus::enabled` is set
+#[tauri::command]
+#[specta::specta]
+pub async fn enter_guest_mode() -> Result<(), CommandError> {
+    GuestMode::enter().map_err(|e| {
+        log::error!("Error entering guest mode: {}", e);
+        CommandError::from(e)
+    })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn leave_guest_mode(pin: String) -> Result<(), CommandError> {
+    GuestMode::leave(&pin).map_err(|e| {
+        log::warn!("Error leaving guest mode: {}", e);
+        CommandError::from(e)
+    })
+}
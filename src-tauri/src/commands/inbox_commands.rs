@@ -0,0 +1,39 @@
+use tauri::State;
+
+use crate::app_state::AppState;
+use crate::definitions::{TriageAction, WorldDisplayData};
+use crate::errors::CommandError;
+use crate::services::InboxService;
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_inbox_worlds(
+    state: State<'_, AppState>,
+) -> Result<Vec<WorldDisplayData>, CommandError> {
+    InboxService::get_inbox_worlds(state.world_store()).map_err(|e| {
+        log::error!("Error getting inbox worlds: {}", e);
+        CommandError::from(e)
+    })
+}
+
+/// Takes a world out of the inbox, keeping it (optionally in a folder), hiding it,
+/// or blacklisting and deleting it
+#[tauri::command]
+#[specta::specta]
+pub async fn triage_world(
+    world_id: String,
+    action: TriageAction,
+    state: State<'_, AppState>,
+) -> Result<(), CommandError> {
+    InboxService::triage_world(
+        world_id,
+        action,
+        state.folder_store(),
+        state.world_store(),
+        state.blacklist_store(),
+    )
+    .map_err(|e| {
+        log::error!("Error triaging world: {}", e);
+        CommandError::from(e)
+    })
+}
@@ -0,0 +1,29 @@
+use tauri::State;
+
+use crate::app_state::AppState;
+use crate::definitions::IntegrityReport;
+use crate::errors::CommandError;
+use crate::services::IntegrityService;
+
+/// Cross-checks folders and worlds, reporting every inconsistency without fixing it
+#[tauri::command]
+#[specta::specta]
+pub async fn verify_library_integrity(
+    state: State<'_, AppState>,
+) -> Result<IntegrityReport, CommandError> {
+    IntegrityService::verify(state.folder_store(), state.world_store()).map_err(|e| {
+        log::error!("Error verifying library integrity: {}", e);
+        CommandError::from(e)
+    })
+}
+
+/// Fixes every inconsistency `verify_library_integrity` reports
+/// Returns the issues that were fixed
+#[tauri::command]
+#[specta::specta]
+pub async fn repair_library(state: State<'_, AppState>) -> Result<IntegrityReport, CommandError> {
+    IntegrityService::repair(state.folder_store(), state.world_store()).map_err(|e| {
+        log::error!("Error repairing library: {}", e);
+        CommandError::from(e)
+    })
+}
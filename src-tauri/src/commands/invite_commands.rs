@@ -0,0 +1,75 @@
+use crate::api::invite::{InviteMessage, InviteMessageType};
+use crate::commands::api_commands::require_login;
+use crate::errors::CommandError;
+use crate::services::ApiService;
+use crate::INITSTATE;
+
+/// Gets the user's saved invite messages of a type, ordered by slot
+#[tauri::command]
+#[specta::specta]
+pub async fn get_invite_messages(
+    message_type: InviteMessageType,
+) -> Result<Vec<InviteMessage>, CommandError> {
+    let cookie_store = require_login().await?;
+    let user_id = INITSTATE.get().read().await.user_id.clone();
+
+    ApiService::get_invite_messages(cookie_store, &user_id, message_type)
+        .await
+        .map_err(|e| {
+            log::info!("{}", e);
+            CommandError::from(e)
+        })
+}
+
+/// Replaces the text in one of the user's invite message slots
+/// Each slot can only be edited once an hour, the messages' `remainingCooldownMinutes` tells
+/// how long is left
+#[tauri::command]
+#[specta::specta]
+pub async fn set_invite_message(
+    message_type: InviteMessageType,
+    slot: u8,
+    message: String,
+) -> Result<Vec<InviteMessage>, CommandError> {
+    let cookie_store = require_login().await?;
+    let user_id = INITSTATE.get().read().await.user_id.clone();
+
+    ApiService::set_invite_message(cookie_store, &user_id, message_type, slot, message)
+        .await
+        .map_err(|e| {
+            log::info!("{}", e);
+            CommandError::from(e)
+        })
+}
+
+/// Invites friends to an instance, with the message in `message_slot` if given
+///
+/// # Returns
+/// The IDs of the friends who could not be invited
+#[tauri::command]
+#[specta::specta]
+pub async fn invite_friends_to_instance(
+    user_ids: Vec<String>,
+    world_id: String,
+    instance_id: String,
+    message_slot: Option<u8>,
+) -> Result<Vec<String>, CommandError> {
+    let cookie_store = require_login().await?;
+
+    let mut failed = vec![];
+    for user_id in user_ids {
+        if let Err(e) = ApiService::invite_user_to_instance(
+            cookie_store.clone(),
+            &user_id,
+            &world_id,
+            &instance_id,
+            message_slot,
+        )
+        .await
+        {
+            log::warn!("Failed to invite {}: {}", user_id, e);
+            failed.push(user_id);
+        }
+    }
+    Ok(failed)
+}
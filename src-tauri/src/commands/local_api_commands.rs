@@ -0,0 +1,80 @@
+use tauri::State;
+
+use crate::app_state::AppState;
+use crate::definitions::LocalApiSettings;
+use crate::errors::CommandError;
+use crate::services::{FileService, LocalApiService};
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_local_api_settings() -> Result<LocalApiSettings, CommandError> {
+    Ok(FileService::read_custom_data()
+        .preferences
+        .local_api
+        .unwrap_or_default())
+}
+
+/// Enables or disables the localhost API, restarting it on the new port
+/// A token is generated the first time it is enabled
+#[tauri::command]
+#[specta::specta]
+pub async fn set_local_api_settings(
+    enabled: bool,
+    port: u16,
+    state: State<'_, AppState>,
+) -> Result<LocalApiSettings, CommandError> {
+    if port < 1024 {
+        return Err(CommandError::validation(format!(
+            "Port must be 1024 or higher: {}",
+            port
+        )));
+    }
+
+    let mut custom_data = FileService::read_custom_data();
+    let mut settings = custom_data.preferences.local_api.unwrap_or_default();
+    settings.enabled = enabled;
+    settings.port = port;
+    if settings.token.is_empty() {
+        settings.token = LocalApiService::generate_token();
+    }
+    apply(&settings, &state).await?;
+
+    custom_data.preferences.local_api = Some(settings.clone());
+    FileService::write_custom_data(&custom_data).map_err(|e| {
+        log::error!("Error writing custom_data: {}", e);
+        CommandError::from(e)
+    })?;
+    Ok(settings)
+}
+
+/// Replaces the token, so clients holding the old one are locked out
+#[tauri::command]
+#[specta::specta]
+pub async fn regenerate_local_api_token(
+    state: State<'_, AppState>,
+) -> Result<LocalApiSettings, CommandError> {
+    let mut custom_data = FileService::read_custom_data();
+    let mut settings = custom_data.preferences.local_api.unwrap_or_default();
+    settings.token = LocalApiService::generate_token();
+    apply(&settings, &state).await?;
+
+    custom_data.preferences.local_api = Some(settings.clone());
+    FileService::write_custom_data(&custom_data).map_err(|e| {
+        log::error!("Error writing custom_data: {}", e);
+        CommandError::from(e)
+    })?;
+    Ok(settings)
+}
+
+async fn apply(settings: &LocalApiSettings, state: &AppState) -> Result<(), CommandError> {
+    if !settings.enabled {
+        LocalApiService::stop();
+        return Ok(());
+    }
+    LocalApiService::start(state.clone(), settings)
+        .await
+        .map_err(|e| {
+            log::error!("Error starting local API: {}", e);
+            CommandError::from(e)
+        })
+}
@@ -0,0 +1,39 @@
+use std::collections::BTreeMap;
+
+use crate::errors::CommandError;
+use crate::logging::{self, LogEntry, LogFilter, LogLevel};
+use crate::services::FileService;
+
+/// Gets the log entries of this session which pass the filter, oldest first
+/// Only the latest entries are kept in memory, older ones are in the log files
+#[tauri::command]
+#[specta::specta]
+pub fn get_recent_logs(filter: LogFilter) -> Result<Vec<LogEntry>, CommandError> {
+    Ok(logging::get_recent_logs(&filter))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_log_levels() -> Result<BTreeMap<String, LogLevel>, CommandError> {
+    Ok(FileService::read_custom_data()
+        .preferences
+        .log_levels
+        .unwrap_or_default())
+}
+
+/// Sets the log levels of modules, e.g. "app_lib::api" to Debug
+/// The levels apply from the next start
+#[tauri::command]
+#[specta::specta]
+pub fn set_log_levels(levels: BTreeMap<String, LogLevel>) -> Result<(), CommandError> {
+    if levels.keys().any(|module| module.trim().is_empty()) {
+        return Err(CommandError::validation("Module name must not be empty"));
+    }
+
+    let mut custom_data = FileService::read_custom_data();
+    custom_data.preferences.log_levels = Some(levels);
+    FileService::write_custom_data(&custom_data).map_err(|e| {
+        log::error!("Error writing custom_data: {}", e);
+        CommandError::from(e)
+    })
+}
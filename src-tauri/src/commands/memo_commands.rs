@@ -1,8 +1,10 @@
+use crate::errors::CommandError;
+use crate::services::InputValidator;
 use crate::MEMO_MANAGER;
 
 #[tauri::command]
 #[specta::specta]
-pub fn get_memo(world_id: String) -> Result<String, String> {
+pub fn get_memo(world_id: String) -> Result<String, CommandError> {
     let memo_manager = MEMO_MANAGER.get().read().map_err(|e| e.to_string())?;
     let memo = memo_manager.get_memo(&world_id).unwrap_or("");
     Ok(memo.to_string())
@@ -10,19 +12,21 @@ pub fn get_memo(world_id: String) -> Result<String, String> {
 
 #[tauri::command]
 #[specta::specta]
-pub fn set_memo_and_save(world_id: String, memo: String) -> Result<(), String> {
+pub fn set_memo_and_save(world_id: String, memo: String) -> Result<(), CommandError> {
+    InputValidator::world_id(&world_id)?;
+    InputValidator::memo(&memo)?;
     let mut memo_manager = MEMO_MANAGER.get().write().map_err(|e| e.to_string())?;
     memo_manager.set_memo(&world_id, &memo);
     memo_manager.save().map_err(|e| {
         log::error!("Error saving memo: {}", e);
-        e.to_string()
+        CommandError::io(e)
     })?;
     Ok(())
 }
 
 #[tauri::command]
 #[specta::specta]
-pub fn search_memo_text(search_text: String) -> Result<Vec<String>, String> {
+pub fn search_memo_text(search_text: String) -> Result<Vec<String>, CommandError> {
     let memo_manager = MEMO_MANAGER.get().read().map_err(|e| e.to_string())?;
     Ok(memo_manager.search_memo_text(&search_text))
 }
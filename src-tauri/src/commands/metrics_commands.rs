@@ -0,0 +1,38 @@
+use crate::definitions::AppMetrics;
+use crate::errors::CommandError;
+use crate::services::{FileService, MetricsService};
+
+/// Gets the local usage metrics collected so far
+#[tauri::command]
+#[specta::specta]
+pub fn get_app_metrics() -> Result<AppMetrics, CommandError> {
+    Ok(MetricsService::snapshot())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn reset_app_metrics() -> Result<(), CommandError> {
+    MetricsService::reset();
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_usage_metrics_enabled() -> Result<bool, CommandError> {
+    Ok(MetricsService::is_enabled())
+}
+
+/// Opts in to or out of collecting local usage metrics
+/// The metrics collected so far are kept until they are reset
+#[tauri::command]
+#[specta::specta]
+pub fn set_usage_metrics_enabled(enabled: bool) -> Result<(), CommandError> {
+    let mut custom_data = FileService::read_custom_data();
+    custom_data.preferences.usage_metrics = Some(enabled);
+    FileService::write_custom_data(&custom_data).map_err(|e| {
+        log::error!("Error writing custom_data: {}", e);
+        CommandError::from(e)
+    })?;
+    MetricsService::set_enabled(enabled);
+    Ok(())
+}
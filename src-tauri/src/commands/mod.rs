@@ -1,13 +1,38 @@
+pub mod account_commands;
 pub mod api_commands;
+pub mod author_commands;
+pub mod blacklist_commands;
+pub mod change_commands;
 pub mod changelog;
 pub mod data;
 pub mod data_commands;
+pub mod deep_link_commands;
+pub mod diagnostics_commands;
+pub mod discovery_commands;
+pub mod encryption_commands;
 pub mod folder_commands;
+pub mod group_folder_commands;
+pub mod guest_mode_commands;
+pub mod inbox_commands;
+pub mod integrity_commands;
+pub mod invite_commands;
+pub mod local_api_commands;
+pub mod log_commands;
 pub mod memo_commands;
+pub mod metrics_commands;
+pub mod my_worlds_commands;
+pub mod notification_commands;
 pub mod open_folder_commands;
+pub mod osc_commands;
 pub mod patreon_cache;
+pub mod photo_commands;
+pub mod playlist_commands;
 pub mod preferences_commands;
+pub mod qr_commands;
+pub mod rate_limit_commands;
+pub mod setup_commands;
 pub mod sort_commands;
+pub mod sync_commands;
 pub mod task;
 pub mod update;
 pub mod util_commands;
@@ -21,35 +46,95 @@ pub fn generate_tauri_specta_builder() -> Builder<tauri::Wry> {
         patreon_cache::fetch_patreon_vrchat_names,
         data_commands::fetch_blacklist,
         changelog::get_changelog,
+        changelog::get_changelog_since,
+        changelog::mark_changelog_seen,
         task::get_task_status,
-        task::cancel_task_request,
+        task::list_active_tasks,
+        task::cancel_task,
         task::get_task_error,
+        task::get_task_progress,
         update::check_for_update,
         update::download_update,
         update::install_update,
         update::do_not_notify_update,
+        update::list_releases,
+        update::get_update_policy,
+        update::skip_update_version,
+        update::pin_current_version,
         folder_commands::add_world_to_folder,
         folder_commands::add_worlds_to_folder,
+        folder_commands::add_filtered_worlds_to_folder,
         folder_commands::remove_world_from_folder,
+        folder_commands::move_world_between_folders,
+        folder_commands::set_world_folders,
         folder_commands::hide_world,
+        folder_commands::hide_filtered_worlds,
         folder_commands::unhide_world,
         folder_commands::get_folders,
+        folder_commands::archive_folder,
+        folder_commands::unarchive_folder,
         folder_commands::create_folder,
         folder_commands::delete_folder,
         folder_commands::move_folder,
         folder_commands::rename_folder,
+        folder_commands::open_folder_window,
         folder_commands::set_folder_color,
+        folder_commands::set_folder_icon,
+        folder_commands::set_folder_description,
+        folder_commands::set_folder_sort,
         folder_commands::get_worlds,
+        folder_commands::get_worlds_grouped,
         folder_commands::get_all_worlds,
+        folder_commands::get_world_summaries,
+        folder_commands::get_worlds_by_id,
+        folder_commands::get_worlds_changed_since,
         folder_commands::get_unclassified_worlds,
         folder_commands::get_hidden_worlds,
+        folder_commands::get_folder_stats,
         folder_commands::get_tags_by_count,
+        folder_commands::get_tag_rules,
+        folder_commands::alias_tag,
+        folder_commands::remove_tag_alias,
+        folder_commands::hide_tag,
+        folder_commands::unhide_tag,
         folder_commands::get_authors_by_count,
         folder_commands::delete_world,
         folder_commands::get_folders_for_world,
         folder_commands::share_folder,
         folder_commands::update_folder_share,
         folder_commands::download_folder,
+        folder_commands::move_world_in_folder,
+        folder_commands::refresh_folder_worlds,
+        folder_commands::prefetch_folder_thumbnails,
+        folder_commands::get_cached_thumbnail,
+        blacklist_commands::add_world_to_blacklist,
+        blacklist_commands::blacklist_filtered_worlds,
+        blacklist_commands::remove_world_from_blacklist,
+        blacklist_commands::get_blacklist,
+        blacklist_commands::add_author_to_blacklist,
+        blacklist_commands::remove_author_from_blacklist,
+        blacklist_commands::get_author_blacklist,
+        author_commands::follow_author,
+        author_commands::unfollow_author,
+        author_commands::get_followed_authors,
+        author_commands::check_followed_authors_new_worlds,
+        author_commands::get_worlds_by_author,
+        discovery_commands::get_discovery_feed,
+        discovery_commands::reset_discovery_feed,
+        discovery_commands::get_similar_worlds,
+        discovery_commands::get_recommendations,
+        inbox_commands::get_inbox_worlds,
+        inbox_commands::triage_world,
+        notification_commands::get_notifications,
+        notification_commands::get_unread_notifications,
+        notification_commands::mark_notifications_read,
+        notification_commands::mark_all_notifications_read,
+        notification_commands::clear_read_notifications,
+        notification_commands::check_expiring_shares,
+        notification_commands::get_webhook_settings,
+        notification_commands::set_webhook_settings,
+        notification_commands::test_webhook,
+        preferences_commands::get_all_preferences,
         preferences_commands::get_theme,
         preferences_commands::set_theme,
         preferences_commands::get_language,
@@ -66,49 +151,161 @@ pub fn generate_tauri_specta_builder() -> Builder<tauri::Wry> {
         preferences_commands::set_update_channel,
         preferences_commands::get_sort_preferences,
         preferences_commands::set_sort_preferences,
+        preferences_commands::get_natural_sort,
+        preferences_commands::set_natural_sort,
+        preferences_commands::get_keep_non_public_favorites,
+        preferences_commands::set_keep_non_public_favorites,
         preferences_commands::get_default_instance_type,
         preferences_commands::set_default_instance_type,
+        preferences_commands::get_default_search_platform,
+        preferences_commands::set_default_search_platform,
+        preferences_commands::get_recently_visited_import,
+        preferences_commands::set_recently_visited_import,
+        preferences_commands::get_clipboard_watch,
+        preferences_commands::set_clipboard_watch,
+        preferences_commands::get_tray_settings,
+        preferences_commands::set_tray_settings,
+        preferences_commands::get_pinned_world,
+        preferences_commands::set_pinned_world,
+        preferences_commands::clear_pinned_world,
+        preferences_commands::get_quick_launch_shortcut,
+        preferences_commands::set_quick_launch_shortcut,
         preferences_commands::get_visible_buttons,
         preferences_commands::set_visible_buttons,
+        preferences_commands::export_settings_profile,
+        preferences_commands::import_settings_profile,
+        preferences_commands::get_photo_directory,
+        preferences_commands::set_photo_directory,
+        preferences_commands::get_worlds_storage,
+        preferences_commands::set_worlds_storage,
+        api_commands::get_auth_state,
         api_commands::try_login,
         api_commands::login_with_credentials,
         api_commands::login_with_2fa,
         api_commands::logout,
+        api_commands::get_account_info,
+        account_commands::list_accounts,
+        account_commands::add_account,
+        account_commands::remove_account,
+        account_commands::switch_account,
+        encryption_commands::get_library_encryption_status,
+        encryption_commands::unlock_library,
+        encryption_commands::enable_library_encryption,
+        encryption_commands::change_library_passphrase,
+        encryption_commands::disable_library_encryption,
+        guest_mode_commands::get_guest_mode_status,
+        guest_mode_commands::set_guest_mode_pin,
+        guest_mode_commands::enter_guest_mode,
+        guest_mode_commands::leave_guest_mode,
+        integrity_commands::verify_library_integrity,
+        integrity_commands::repair_library,
+        sync_commands::get_sync_status,
+        sync_commands::set_sync_target,
+        sync_commands::sync_now,
+        sync_commands::resolve_sync_conflict,
+        osc_commands::send_world_to_chatbox,
+        local_api_commands::get_local_api_settings,
+        local_api_commands::set_local_api_settings,
+        local_api_commands::regenerate_local_api_token,
+        log_commands::get_recent_logs,
+        log_commands::get_log_levels,
+        log_commands::set_log_levels,
         api_commands::get_favorite_worlds,
+        setup_commands::run_initial_import,
         api_commands::get_world,
         api_commands::check_world_info,
         api_commands::get_recently_visited_worlds,
+        api_commands::import_recently_visited_worlds,
         api_commands::search_worlds,
         api_commands::create_world_instance,
         api_commands::get_user_groups,
         api_commands::get_permission_for_create_group_instance,
         api_commands::create_group_instance,
         api_commands::open_instance_in_client,
+        api_commands::generate_instance_links,
+        api_commands::clear_api_cache,
+        rate_limit_commands::get_rate_limit_status,
+        rate_limit_commands::reset_rate_limits,
+        rate_limit_commands::get_api_request_budget,
+        rate_limit_commands::set_api_request_budget,
+        rate_limit_commands::get_api_concurrent_requests,
+        rate_limit_commands::set_api_concurrent_requests,
         open_folder_commands::open_logs_directory,
         open_folder_commands::open_folder_directory,
+        diagnostics_commands::create_diagnostics_bundle,
+        metrics_commands::get_app_metrics,
+        metrics_commands::reset_app_metrics,
+        metrics_commands::get_usage_metrics_enabled,
+        metrics_commands::set_usage_metrics_enabled,
+        change_commands::get_change_history,
+        change_commands::get_world_trends,
+        change_commands::get_recent_changes,
+        change_commands::undo_last_change,
         data::read_data_commands::require_initial_setup,
         data::read_data_commands::check_files_loaded,
         data::read_data_commands::detect_old_installation,
         data::read_data_commands::pass_paths,
+        data::read_data_commands::get_data_directory,
         data::read_data_commands::check_existing_data,
         data::read_data_commands::get_backup_metadata,
         data::read_data_commands::get_migration_metadata,
+        data::read_data_commands::preview_migration,
         data::write_data_commands::create_empty_auth,
         data::write_data_commands::create_empty_files,
         data::write_data_commands::create_backup,
         data::write_data_commands::restore_from_backup,
+        data::write_data_commands::merge_library,
         data::write_data_commands::export_to_portal_library_system,
+        data::write_data_commands::export_world_list,
+        data::write_data_commands::export_run_sheet_pdf,
         data::write_data_commands::migrate_old_data,
         data::write_data_commands::delete_data,
         data::write_data_commands::export_native_data,
+        data::write_data_commands::reload_data_from_disk,
+        data::write_data_commands::move_data_directory,
         memo_commands::get_memo,
         memo_commands::set_memo_and_save,
         memo_commands::search_memo_text,
         world_status_commands::set_world_photographed,
         world_status_commands::set_world_shared,
         world_status_commands::set_world_favorite,
+        world_status_commands::set_world_event_critical,
+        world_status_commands::set_world_rating,
+        world_status_commands::set_worlds_flag,
+        world_status_commands::set_world_cover,
+        world_status_commands::clear_world_cover,
+        world_status_commands::lock_world_field,
+        world_status_commands::unlock_world_field,
+        world_status_commands::get_locked_world_fields,
+        world_status_commands::get_field_conflicts,
+        world_status_commands::clear_field_conflicts,
+        world_status_commands::remove_orphaned_covers,
+        photo_commands::scan_photos,
+        photo_commands::get_world_photos,
+        photo_commands::get_photo_counts,
+        world_status_commands::refresh_event_critical_worlds,
+        group_folder_commands::get_group_folders,
+        group_folder_commands::link_group_folder,
+        group_folder_commands::unlink_group_folder,
+        group_folder_commands::sync_group_folder,
+        my_worlds_commands::get_my_worlds,
+        my_worlds_commands::create_my_world_test_instance,
+        invite_commands::get_invite_messages,
+        invite_commands::set_invite_message,
+        invite_commands::invite_friends_to_instance,
+        playlist_commands::start_playlist,
+        playlist_commands::stop_playlist,
+        playlist_commands::get_playlist,
+        playlist_commands::playlist_next,
+        playlist_commands::playlist_previous,
         sort_commands::sort_worlds_display,
+        sort_commands::filter_worlds_by_download_size,
         util_commands::resolve_redirects,
+        util_commands::parse_world_reference,
+        util_commands::copy_to_clipboard,
+        qr_commands::generate_qr_png,
         util_commands::get_startup_deep_link,
+        deep_link_commands::take_startup_deep_link_result,
+        util_commands::flush_pending_writes,
     ])
 }
@@ -0,0 +1,68 @@
+use std::collections::HashSet;
+
+use tauri::{AppHandle, State};
+
+use crate::app_state::AppState;
+use crate::commands::api_commands::require_login;
+use crate::definitions::MyWorld;
+use crate::errors::CommandError;
+use crate::services::api_service::InstanceInfo;
+use crate::services::{ApiService, WorldHistoryService};
+use crate::INITSTATE;
+
+/// Gets the worlds the logged-in user uploaded, private ones included
+/// Each call snapshots their favorites and visits, see `get_world_trends`
+#[tauri::command]
+#[specta::specta]
+pub async fn get_my_worlds(state: State<'_, AppState>) -> Result<Vec<MyWorld>, CommandError> {
+    let cookie_store = require_login().await?;
+
+    let worlds = ApiService::get_my_worlds(cookie_store).await.map_err(|e| {
+        log::info!("Failed to fetch own worlds: {}", e);
+        CommandError::from(e)
+    })?;
+
+    let display: Vec<_> = worlds.iter().map(|(world, _)| world.clone()).collect();
+    WorldHistoryService::record_display(&display);
+
+    let saved: HashSet<String> = state
+        .worlds()?
+        .iter()
+        .map(|w| w.api_data.world_id.clone())
+        .collect();
+    Ok(worlds
+        .into_iter()
+        .map(|(world, release_status)| MyWorld {
+            in_library: saved.contains(&world.world_id),
+            world,
+            release_status,
+        })
+        .collect())
+}
+
+/// Creates an invite instance of one of the user's own worlds, so a private world can be
+/// tested without anyone else joining
+#[tauri::command]
+#[specta::specta]
+pub async fn create_my_world_test_instance(
+    world_id: String,
+    region_str: String,
+    handle: State<'_, AppHandle>,
+) -> Result<InstanceInfo, CommandError> {
+    let cookie_store = require_login().await?;
+    let user_id = INITSTATE.get().read().await.user_id.clone();
+
+    ApiService::create_world_instance(
+        world_id,
+        "invite".to_string(),
+        region_str,
+        cookie_store,
+        user_id,
+        (*handle).clone(),
+    )
+    .await
+    .map_err(|e| {
+        log::info!("Failed to create test instance: {}", e);
+        CommandError::from(e.context("Failed to create test instance"))
+    })
+}
@@ -0,0 +1,132 @@
+use tauri::{AppHandle, Manager, State};
+use tauri_specta::Event;
+use uuid::Uuid;
+
+use crate::app_state::AppState;
+use crate::definitions::{Notification, NotificationKind, WebhookSettings};
+use crate::errors::CommandError;
+use crate::services::{FileService, NotificationService, WebhookService};
+
+/// Adds notifications to the inbox and emits a `Notification` event for each new one
+/// New notifications are also posted to the webhook, if one is set up
+/// Failures are logged, as reporting should never fail the operation that reports
+pub(crate) fn notify(kinds: Vec<NotificationKind>, handle: &AppHandle) {
+    let state = handle.state::<AppState>();
+    let added = match NotificationService::add_notifications(kinds, state.notification_store()) {
+        Ok(added) => added,
+        Err(e) => {
+            log::error!("Error adding notifications: {}", e);
+            return;
+        }
+    };
+
+    WebhookService::dispatch(&added);
+    for notification in added {
+        if let Err(e) = notification.emit(handle) {
+            log::error!("Failed to emit notification event: {}", e);
+        }
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_notifications(
+    state: State<'_, AppState>,
+) -> Result<Vec<Notification>, CommandError> {
+    NotificationService::get_notifications(state.notification_store(), false).map_err(|e| {
+        log::error!("Error getting notifications: {}", e);
+        CommandError::from(e)
+    })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_unread_notifications(
+    state: State<'_, AppState>,
+) -> Result<Vec<Notification>, CommandError> {
+    NotificationService::get_notifications(state.notification_store(), true).map_err(|e| {
+        log::error!("Error getting unread notifications: {}", e);
+        CommandError::from(e)
+    })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn mark_notifications_read(
+    ids: Vec<Uuid>,
+    state: State<'_, AppState>,
+) -> Result<(), CommandError> {
+    NotificationService::mark_read(Some(&ids), state.notification_store()).map_err(|e| {
+        log::error!("Error marking notifications as read: {}", e);
+        CommandError::from(e)
+    })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn mark_all_notifications_read(state: State<'_, AppState>) -> Result<(), CommandError> {
+    NotificationService::mark_read(None, state.notification_store()).map_err(|e| {
+        log::error!("Error marking all notifications as read: {}", e);
+        CommandError::from(e)
+    })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn clear_read_notifications(state: State<'_, AppState>) -> Result<(), CommandError> {
+    NotificationService::clear_read(state.notification_store()).map_err(|e| {
+        log::error!("Error clearing read notifications: {}", e);
+        CommandError::from(e)
+    })
+}
+
+/// Reports folder shares which are about to expire
+#[tauri::command]
+#[specta::specta]
+pub async fn check_expiring_shares(
+    handle: State<'_, AppHandle>,
+    state: State<'_, AppState>,
+) -> Result<(), CommandError> {
+    let expiring =
+        NotificationService::find_expiring_shares(state.folder_store()).map_err(|e| {
+            log::error!("Error checking expiring shares: {}", e);
+            CommandError::from(e)
+        })?;
+    notify(expiring, &handle);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_webhook_settings() -> Result<Option<WebhookSettings>, CommandError> {
+    Ok(FileService::read_custom_data().preferences.webhook)
+}
+
+/// Sets the webhook notifications are posted to, or removes it when `settings` is None
+#[tauri::command]
+#[specta::specta]
+pub async fn set_webhook_settings(settings: Option<WebhookSettings>) -> Result<(), CommandError> {
+    if let Some(settings) = &settings {
+        WebhookService::validate_url(&settings.url).map_err(CommandError::from)?;
+    }
+
+    let mut custom_data = FileService::read_custom_data();
+    custom_data.preferences.webhook = settings;
+    FileService::write_custom_data(&custom_data).map_err(|e| {
+        log::error!("Error writing custom_data: {}", e);
+        CommandError::from(e)
+    })
+}
+
+/// Posts a sample notification to a webhook, so the user can check it before saving it
+#[tauri::command]
+#[specta::specta]
+pub async fn test_webhook(settings: WebhookSettings) -> Result<(), CommandError> {
+    let sample = Notification::new(NotificationKind::BackupCompleted {
+        path: "Webhook test".to_string(),
+    });
+    WebhookService::send(&settings, &sample).await.map_err(|e| {
+        log::warn!("Error testing webhook: {}", e);
+        CommandError::from(e)
+    })
+}
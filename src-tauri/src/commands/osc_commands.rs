@@ -0,0 +1,45 @@
+use tauri::State;
+
+use crate::app_state::AppState;
+use crate::errors::CommandError;
+use crate::services::OscService;
+
+/// Shows a saved world's name, author and link in the user's chatbox in VRChat,
+/// for recommending it to the people in the instance
+/// VRChat must have OSC enabled. Messages sent within the chatbox cooldown are refused
+#[tauri::command]
+#[specta::specta]
+pub async fn send_world_to_chatbox(
+    world_id: String,
+    state: State<'_, AppState>,
+) -> Result<(), CommandError> {
+    let text = {
+        let worlds = state.worlds()?;
+        let world = worlds
+            .iter()
+            .find(|w| w.api_data.world_id == world_id)
+            .ok_or_else(|| {
+                CommandError::not_found(format!("World {} is not in the library", world_id))
+            })?;
+        format!(
+            "{} by {}\nvrchat.com/home/world/{}",
+            world.api_data.world_name, world.api_data.author_name, world.api_data.world_id
+        )
+    };
+
+    if let Err(remaining) = OscService::reserve_chatbox() {
+        let retry_after = remaining.as_secs() + 1;
+        return Err(CommandError::RateLimited {
+            message: format!(
+                "Chatbox is cooling down, try again in {} seconds",
+                retry_after
+            ),
+            localized: None,
+            retry_after: Some(retry_after),
+        });
+    }
+    OscService::send_chatbox(&text).map_err(|e| {
+        log::error!("Error sending world to chatbox: {}", e);
+        CommandError::from(e)
+    })
+}
@@ -1,4 +1,5 @@
 use crate::definitions::PatreonVRChatNames;
+use crate::errors::CommandError;
 use reqwest::Client;
 use std::sync::RwLock;
 use std::time::{Duration, SystemTime};
@@ -50,7 +51,7 @@ pub fn init_cache() {
 
 #[tauri::command]
 #[specta::specta]
-pub async fn fetch_patreon_vrchat_names() -> Result<PatreonVRChatNames, String> {
+pub async fn fetch_patreon_vrchat_names() -> Result<PatreonVRChatNames, CommandError> {
     // Try to get cached data first
     {
         let cache = PATREON_CACHE
@@ -70,14 +71,14 @@ pub async fn fetch_patreon_vrchat_names() -> Result<PatreonVRChatNames, String>
         .get("https://data.raifaworks.com/data/patreons-vrchat-usernames.json")
         .send()
         .await
-        .map_err(|e| e.to_string())?
+        .map_err(|e| CommandError::network(e.to_string()))?
         .error_for_status()
-        .map_err(|e| e.to_string())?;
+        .map_err(|e| CommandError::network(e.to_string()))?;
 
     let data = response
         .json::<PatreonVRChatNames>()
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(|e| CommandError::network(e.to_string()))?;
 
     // Update cache
     {
@@ -0,0 +1,40 @@
+use std::collections::BTreeMap;
+
+use tauri::State;
+
+use crate::app_state::AppState;
+use crate::definitions::{LibraryChange, PhotoScanSummary, WorldFlag, WorldPhoto};
+use crate::errors::CommandError;
+use crate::services::{ChangeFeed, PhotoScanner};
+
+/// Scans the VRChat photo directory, and marks the saved worlds with photos as photographed
+#[tauri::command]
+#[specta::specta]
+pub async fn scan_photos(state: State<'_, AppState>) -> Result<PhotoScanSummary, CommandError> {
+    let summary = PhotoScanner::scan(state.world_store()).map_err(|e| {
+        log::error!("Error scanning photos: {}", e);
+        CommandError::from(e)
+    })?;
+    for world_id in &summary.newly_photographed {
+        ChangeFeed::record(LibraryChange::FlagChanged {
+            world_id: world_id.clone(),
+            flag: WorldFlag::Photographed,
+            value: true,
+        });
+    }
+    Ok(summary)
+}
+
+/// Gets the photos taken in a world, newest first, as found by the last scan
+#[tauri::command]
+#[specta::specta]
+pub fn get_world_photos(world_id: String) -> Result<Vec<WorldPhoto>, CommandError> {
+    Ok(PhotoScanner::world_photos(&world_id)?)
+}
+
+/// Gets the number of photos taken in each world, as found by the last scan
+#[tauri::command]
+#[specta::specta]
+pub fn get_photo_counts() -> Result<BTreeMap<String, u32>, CommandError> {
+    Ok(PhotoScanner::photo_counts()?)
+}
@@ -0,0 +1,136 @@
+use tauri::{AppHandle, State};
+
+use crate::api::instance::InstanceRegion;
+use crate::app_state::AppState;
+use crate::commands::api_commands::require_login;
+use crate::definitions::{DefaultInstanceType, Playlist};
+use crate::errors::CommandError;
+use crate::services::api_service::InstanceInfo;
+use crate::services::{ApiService, PlaylistService};
+use crate::INITSTATE;
+
+/// Starts playing a folder in order, replacing any running playlist
+/// Use `playlist_next` to launch the first world
+#[tauri::command]
+#[specta::specta]
+pub async fn start_playlist(
+    folder_name: String,
+    state: State<'_, AppState>,
+) -> Result<Playlist, CommandError> {
+    PlaylistService::start(folder_name, state.folder_store(), state.playlist_store()).map_err(|e| {
+        log::error!("Error starting playlist: {}", e);
+        CommandError::from(e)
+    })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn stop_playlist(state: State<'_, AppState>) -> Result<(), CommandError> {
+    PlaylistService::stop(state.playlist_store()).map_err(|e| {
+        log::error!("Error stopping playlist: {}", e);
+        CommandError::from(e)
+    })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_playlist(state: State<'_, AppState>) -> Result<Option<Playlist>, CommandError> {
+    PlaylistService::get(state.playlist_store()).map_err(|e| {
+        log::error!("Error getting playlist: {}", e);
+        CommandError::from(e)
+    })
+}
+
+/// Creates an instance of the next world in the playlist, using the default instance type
+/// The playlist only moves on once the instance has been created
+#[tauri::command]
+#[specta::specta]
+pub async fn playlist_next(
+    handle: State<'_, AppHandle>,
+    state: State<'_, AppState>,
+) -> Result<InstanceInfo, CommandError> {
+    let (index, world_id) = PlaylistService::next_world(state.playlist_store()).map_err(|e| {
+        log::error!("Error getting next playlist world: {}", e);
+        CommandError::from(e)
+    })?;
+    launch_playlist_world(index, world_id, (*handle).clone(), &state).await
+}
+
+/// Creates an instance of the previous world in the playlist, using the default instance type
+#[tauri::command]
+#[specta::specta]
+pub async fn playlist_previous(
+    handle: State<'_, AppHandle>,
+    state: State<'_, AppState>,
+) -> Result<InstanceInfo, CommandError> {
+    let (index, world_id) =
+        PlaylistService::previous_world(state.playlist_store()).map_err(|e| {
+            log::error!("Error getting previous playlist world: {}", e);
+            CommandError::from(e)
+        })?;
+    launch_playlist_world(index, world_id, (*handle).clone(), &state).await
+}
+
+async fn launch_playlist_world(
+    index: usize,
+    world_id: String,
+    handle: AppHandle,
+    state: &AppState,
+) -> Result<InstanceInfo, CommandError> {
+    let info = create_default_instance(world_id, handle, state).await?;
+
+    PlaylistService::set_position(index, state.playlist_store()).map_err(|e| {
+        log::error!("Error moving playlist: {}", e);
+        CommandError::from(e)
+    })?;
+    Ok(info)
+}
+
+/// Creates an instance of a world with the default instance type and region,
+/// for launching worlds without asking, e.g. from a playlist or the tray
+pub(crate) async fn create_default_instance(
+    world_id: String,
+    handle: AppHandle,
+    state: &AppState,
+) -> Result<InstanceInfo, CommandError> {
+    let (instance_type, region) = {
+        let preferences = state.preferences()?;
+        let instance_type = match preferences.default_instance_type {
+            DefaultInstanceType::Public => "public",
+            DefaultInstanceType::FriendsPlus => "friends+",
+            DefaultInstanceType::Friends => "friends",
+            DefaultInstanceType::InvitePlus => "invite+",
+            DefaultInstanceType::Invite => "invite",
+            // Group instances need a group to be chosen
+            DefaultInstanceType::Group => {
+                return Err(CommandError::validation(
+                    "Group instances cannot be created without choosing a group, change the default instance type",
+                ))
+            }
+        };
+        let region = match preferences.region {
+            InstanceRegion::UsWest => "us",
+            InstanceRegion::UsEast => "use",
+            InstanceRegion::EU => "eu",
+            InstanceRegion::JP => "jp",
+        };
+        (instance_type.to_string(), region.to_string())
+    };
+
+    let cookie_store = require_login().await?;
+    let user_id = INITSTATE.get().read().await.user_id.clone();
+
+    ApiService::create_world_instance(
+        world_id,
+        instance_type,
+        region,
+        cookie_store,
+        user_id,
+        handle,
+    )
+    .await
+    .map_err(|e| {
+        log::info!("Failed to create instance: {}", e);
+        CommandError::from(e.context("Failed to create world instance"))
+    })
+}
@@ -1,106 +1,194 @@
+use tauri::{AppHandle, State};
+use tauri_specta::Event;
+
 use crate::api::instance::InstanceRegion;
+use crate::app_state::AppState;
+use crate::definitions::AllPreferences;
 use crate::definitions::CardSize;
+use crate::definitions::ClipboardWatch;
 use crate::definitions::DefaultInstanceType;
 use crate::definitions::FilterItemSelectorStarred;
 use crate::definitions::FilterItemSelectorStarredType;
 use crate::definitions::FolderRemovalPreference;
+use crate::definitions::PreferenceModel;
+use crate::definitions::PreferencesChanged;
+use crate::definitions::RecentlyVisitedImport;
+use crate::definitions::SearchPlatform;
+use crate::definitions::TraySettings;
+use crate::definitions::WorldsStorageFormat;
+use crate::errors::CommandError;
+use crate::i18n;
 use crate::services::FileService;
+use crate::services::FolderManager;
+use crate::services::PhotoScanner;
+use crate::services::SettingsProfileService;
+use crate::services::SortingService;
+use crate::services::WorldStorage;
 use crate::updater::update_handler::UpdateChannel;
-use crate::PREFERENCES;
+
+/// Gets every preference at once, instead of one get_* call per preference
+#[tauri::command]
+#[specta::specta]
+pub fn get_all_preferences(state: State<'_, AppState>) -> Result<AllPreferences, CommandError> {
+    let preferences = state.preferences()?;
+    Ok(all_preferences(&preferences))
+}
+
+fn all_preferences(preferences: &PreferenceModel) -> AllPreferences {
+    let custom = FileService::read_custom_data().preferences;
+    AllPreferences {
+        theme: preferences.theme.clone(),
+        language: preferences.language.clone(),
+        card_size: preferences.card_size.clone(),
+        region: preferences.region,
+        filter_item_selector_starred: preferences.filter_item_selector_starred.clone(),
+        dont_show_remove_from_folder: preferences.dont_show_remove_from_folder,
+        update_channel: preferences.update_channel,
+        sort_field: preferences.sort_field.clone(),
+        sort_direction: preferences.sort_direction.clone(),
+        default_instance_type: preferences.default_instance_type.clone(),
+        default_search_platform: preferences.default_search_platform,
+        visible_buttons: preferences.visible_buttons.clone(),
+        natural_sort: SortingService::is_natural_sort(),
+        keep_non_public_favorites: custom.keep_non_public_favorites.unwrap_or(false),
+        recently_visited_import: custom.recently_visited_import.unwrap_or_default(),
+        clipboard_watch: custom.clipboard_watch.unwrap_or_default(),
+        tray: custom.tray.unwrap_or_default(),
+        pinned_world: custom.pinned_world,
+        quick_launch_shortcut: crate::shortcut::saved_binding(),
+        photo_directory: PhotoScanner::directory()
+            .map(|directory| directory.to_string_lossy().to_string()),
+        worlds_storage: preferences.worlds_storage,
+    }
+}
+
+/// Tells every window the preferences changed, so they don't have to poll for it
+fn emit_preferences_changed(handle: &AppHandle, preferences: &PreferenceModel) {
+    let event = PreferencesChanged {
+        preferences: all_preferences(preferences),
+    };
+    if let Err(e) = event.emit(handle) {
+        log::error!("Failed to emit preferences changed event: {}", e);
+    }
+}
 
 #[tauri::command]
 #[specta::specta]
-pub fn get_theme() -> Result<String, String> {
-    let preferences_lock = PREFERENCES.get().read();
-    let preferences = preferences_lock.as_ref().unwrap();
+pub fn get_theme(state: State<'_, AppState>) -> Result<String, CommandError> {
+    let preferences = state.preferences()?;
     Ok(preferences.theme.clone())
 }
 
 #[tauri::command]
 #[specta::specta]
-pub fn set_theme(theme: String) -> Result<(), String> {
-    let mut preferences_lock = PREFERENCES.get().write();
-    let preferences = preferences_lock.as_mut().unwrap();
+pub fn set_theme(
+    theme: String,
+    state: State<'_, AppState>,
+    handle: AppHandle,
+) -> Result<(), CommandError> {
+    let mut preferences = state.preferences_mut()?;
     preferences.theme = theme;
-    FileService::write_preferences(preferences).map_err(|e| {
+    FileService::write_preferences(&preferences).map_err(|e| {
         log::error!("Error writing preferences: {}", e);
-        e.to_string()
+        CommandError::from(e)
     })?;
+    emit_preferences_changed(&handle, &preferences);
     Ok(())
 }
 
 #[tauri::command]
 #[specta::specta]
-pub fn get_language() -> Result<String, String> {
-    let preferences_lock = PREFERENCES.get().read();
-    let preferences = preferences_lock.as_ref().unwrap();
+pub fn get_language(state: State<'_, AppState>) -> Result<String, CommandError> {
+    let preferences = state.preferences()?;
     Ok(preferences.language.clone())
 }
 
 #[tauri::command]
 #[specta::specta]
-pub fn set_language(language: String) -> Result<(), String> {
-    let mut preferences_lock = PREFERENCES.get().write();
-    let preferences = preferences_lock.as_mut().unwrap();
+pub fn set_language(
+    language: String,
+    state: State<'_, AppState>,
+    handle: AppHandle,
+) -> Result<(), CommandError> {
+    let mut preferences = state.preferences_mut()?;
     preferences.language = language;
-    FileService::write_preferences(preferences).map_err(|e| {
+    FileService::write_preferences(&preferences).map_err(|e| {
         log::error!("Error writing preferences: {}", e);
-        e.to_string()
+        CommandError::from(e)
     })?;
+    SortingService::set_language(&preferences.language);
+    i18n::set_language(&preferences.language);
+    emit_preferences_changed(&handle, &preferences);
     Ok(())
 }
 
 #[tauri::command]
 #[specta::specta]
-pub fn get_card_size() -> Result<CardSize, String> {
-    let preferences_lock = PREFERENCES.get().read();
-    let preferences = preferences_lock.as_ref().unwrap();
+pub fn get_card_size(state: State<'_, AppState>) -> Result<CardSize, CommandError> {
+    let preferences = state.preferences()?;
     Ok(preferences.card_size.clone())
 }
 
 #[tauri::command]
 #[specta::specta]
-pub fn set_card_size(card_size: CardSize) -> Result<(), String> {
-    let mut preferences_lock = PREFERENCES.get().write();
-    let preferences = preferences_lock.as_mut().unwrap();
+pub fn set_card_size(
+    card_size: CardSize,
+    state: State<'_, AppState>,
+    handle: AppHandle,
+) -> Result<(), CommandError> {
+    let mut preferences = state.preferences_mut()?;
     preferences.card_size = card_size;
-    FileService::write_preferences(preferences).map_err(|e| {
+    FileService::write_preferences(&preferences).map_err(|e| {
         log::error!("Error writing preferences: {}", e);
-        e.to_string()
+        CommandError::from(e)
     })?;
+    emit_preferences_changed(&handle, &preferences);
     Ok(())
 }
 
 #[tauri::command]
 #[specta::specta]
-pub fn get_region() -> Result<InstanceRegion, String> {
-    let preferences_lock = PREFERENCES.get().read();
-    let preferences = preferences_lock.as_ref().unwrap();
+pub fn get_region(state: State<'_, AppState>) -> Result<InstanceRegion, CommandError> {
+    let preferences = state.preferences()?;
     Ok(preferences.region.clone())
 }
 
 #[tauri::command]
 #[specta::specta]
-pub fn set_region(region: InstanceRegion) -> Result<(), String> {
-    let mut preferences_lock = PREFERENCES.get().write();
-    let preferences = preferences_lock.as_mut().unwrap();
+pub fn set_region(
+    region: InstanceRegion,
+    state: State<'_, AppState>,
+    handle: AppHandle,
+) -> Result<(), CommandError> {
+    let mut preferences = state.preferences_mut()?;
     preferences.region = region;
-    FileService::write_preferences(preferences).map_err(|e| {
+    FileService::write_preferences(&preferences).map_err(|e| {
         log::error!("Error writing preferences: {}", e);
-        e.to_string()
+        CommandError::from(e)
     })?;
+    emit_preferences_changed(&handle, &preferences);
     Ok(())
 }
 
 #[tauri::command]
 #[specta::specta]
-pub fn get_starred_filter_items(id: FilterItemSelectorStarredType) -> Result<Vec<String>, String> {
-    let preferences_lock = PREFERENCES.get().read();
-    let preferences = preferences_lock.as_ref().unwrap();
+pub fn get_starred_filter_items(
+    id: FilterItemSelectorStarredType,
+    state: State<'_, AppState>,
+) -> Result<Vec<String>, CommandError> {
+    let preferences = state.preferences()?;
     if let Some(filter_item_selector_starred) = &preferences.filter_item_selector_starred {
         match id {
             FilterItemSelectorStarredType::Author => {
-                Ok(filter_item_selector_starred.author.clone())
+                // Starred authors are stored by name, so map renamed authors to their current name
+                FolderManager::resolve_author_aliases(
+                    filter_item_selector_starred.author.clone(),
+                    state.world_store(),
+                )
+                .map_err(|e| {
+                    log::error!("Error resolving starred authors: {}", e);
+                    CommandError::from(e)
+                })
             }
             FilterItemSelectorStarredType::Tag => Ok(filter_item_selector_starred.tag.clone()),
             FilterItemSelectorStarredType::ExcludeTag => {
@@ -120,9 +208,10 @@ pub fn get_starred_filter_items(id: FilterItemSelectorStarredType) -> Result<Vec
 pub fn set_starred_filter_items(
     id: FilterItemSelectorStarredType,
     values: Vec<String>,
-) -> Result<(), String> {
-    let mut preferences_lock = PREFERENCES.get().write();
-    let preferences = preferences_lock.as_mut().unwrap();
+    state: State<'_, AppState>,
+    handle: AppHandle,
+) -> Result<(), CommandError> {
+    let mut preferences = state.preferences_mut()?;
 
     if preferences.filter_item_selector_starred.is_none() {
         let (author, tag, exclude_tag, folder) = match id {
@@ -155,18 +244,20 @@ pub fn set_starred_filter_items(
             }
         }
     }
-    FileService::write_preferences(preferences).map_err(|e| {
+    FileService::write_preferences(&preferences).map_err(|e| {
         log::error!("Error writing preferences: {}", e);
-        e.to_string()
+        CommandError::from(e)
     })?;
+    emit_preferences_changed(&handle, &preferences);
     Ok(())
 }
 
 #[tauri::command]
 #[specta::specta]
-pub fn get_folder_removal_preference() -> Result<FolderRemovalPreference, String> {
-    let preferences_lock = PREFERENCES.get().read();
-    let preferences = preferences_lock.as_ref().unwrap();
+pub fn get_folder_removal_preference(
+    state: State<'_, AppState>,
+) -> Result<FolderRemovalPreference, CommandError> {
+    let preferences = state.preferences()?;
     Ok(preferences.dont_show_remove_from_folder)
 }
 
@@ -174,43 +265,47 @@ pub fn get_folder_removal_preference() -> Result<FolderRemovalPreference, String
 #[specta::specta]
 pub fn set_folder_removal_preference(
     dont_show_remove_from_folder: FolderRemovalPreference,
-) -> Result<(), String> {
-    let mut preferences_lock = PREFERENCES.get().write();
-    let preferences = preferences_lock.as_mut().unwrap();
+    state: State<'_, AppState>,
+    handle: AppHandle,
+) -> Result<(), CommandError> {
+    let mut preferences = state.preferences_mut()?;
     preferences.dont_show_remove_from_folder = dont_show_remove_from_folder;
-    FileService::write_preferences(preferences).map_err(|e| {
+    FileService::write_preferences(&preferences).map_err(|e| {
         log::error!("Error writing preferences: {}", e);
-        e.to_string()
+        CommandError::from(e)
     })?;
+    emit_preferences_changed(&handle, &preferences);
     Ok(())
 }
 
 #[tauri::command]
 #[specta::specta]
-pub fn get_update_channel() -> Result<UpdateChannel, String> {
-    let preferences_lock = PREFERENCES.get().read();
-    let preferences = preferences_lock.as_ref().unwrap();
+pub fn get_update_channel(state: State<'_, AppState>) -> Result<UpdateChannel, CommandError> {
+    let preferences = state.preferences()?;
     Ok(preferences.update_channel.clone())
 }
 
 #[tauri::command]
 #[specta::specta]
-pub fn set_update_channel(channel: UpdateChannel) -> Result<(), String> {
-    let mut preferences_lock = PREFERENCES.get().write();
-    let preferences = preferences_lock.as_mut().unwrap();
+pub fn set_update_channel(
+    channel: UpdateChannel,
+    state: State<'_, AppState>,
+    handle: AppHandle,
+) -> Result<(), CommandError> {
+    let mut preferences = state.preferences_mut()?;
     preferences.update_channel = channel;
-    FileService::write_preferences(preferences).map_err(|e| {
+    FileService::write_preferences(&preferences).map_err(|e| {
         log::error!("Error writing preferences: {}", e);
-        e.to_string()
+        CommandError::from(e)
     })?;
+    emit_preferences_changed(&handle, &preferences);
     Ok(())
 }
 
 #[tauri::command]
 #[specta::specta]
-pub fn get_sort_preferences() -> Result<(String, String), String> {
-    let preferences_lock = PREFERENCES.get().read();
-    let preferences = preferences_lock.as_ref().unwrap();
+pub fn get_sort_preferences(state: State<'_, AppState>) -> Result<(String, String), CommandError> {
+    let preferences = state.preferences()?;
     Ok((
         preferences.sort_field.clone(),
         preferences.sort_direction.clone(),
@@ -219,49 +314,108 @@ pub fn get_sort_preferences() -> Result<(String, String), String> {
 
 #[tauri::command]
 #[specta::specta]
-pub fn set_sort_preferences(sort_field: String, sort_direction: String) -> Result<(), String> {
-    let valid_fields = [
-        "name",
-        "authorName",
-        "visits",
-        "favorites",
-        "capacity",
-        "dateAdded",
-        "lastUpdated",
-    ];
+pub fn set_sort_preferences(
+    sort_field: String,
+    sort_direction: String,
+    state: State<'_, AppState>,
+    handle: AppHandle,
+) -> Result<(), CommandError> {
     let valid_directions = ["asc", "desc"];
 
-    if !valid_fields.contains(&sort_field.as_str()) {
-        return Err(format!("Invalid sort_field: {}", sort_field));
+    if !SortingService::SORT_FIELDS.contains(&sort_field.as_str()) {
+        return Err(CommandError::validation(format!(
+            "Invalid sort_field: {}",
+            sort_field
+        )));
     }
     if !valid_directions.contains(&sort_direction.as_str()) {
-        return Err(format!("Invalid sort_direction: {}", sort_direction));
+        return Err(CommandError::validation(format!(
+            "Invalid sort_direction: {}",
+            sort_direction
+        )));
     }
 
-    let mut preferences_lock = PREFERENCES.get().write();
-    let preferences = preferences_lock.as_mut().unwrap();
+    let mut preferences = state.preferences_mut()?;
     preferences.sort_field = sort_field;
     preferences.sort_direction = sort_direction;
-    FileService::write_preferences(preferences).map_err(|e| {
+    FileService::write_preferences(&preferences).map_err(|e| {
         log::error!("Error writing preferences: {}", e);
-        e.to_string()
+        CommandError::from(e)
     })?;
+    emit_preferences_changed(&handle, &preferences);
     Ok(())
 }
 
 #[tauri::command]
 #[specta::specta]
-pub fn get_default_instance_type() -> Result<DefaultInstanceType, String> {
-    let preferences_lock = PREFERENCES.get().read();
-    let preferences = preferences_lock.as_ref().unwrap();
+pub fn get_natural_sort() -> Result<bool, CommandError> {
+    Ok(SortingService::is_natural_sort())
+}
+
+/// Enables or disables comparing numbers in names by value, so "World 2" sorts before "World 10"
+#[tauri::command]
+#[specta::specta]
+pub fn set_natural_sort(
+    enabled: bool,
+    state: State<'_, AppState>,
+    handle: AppHandle,
+) -> Result<(), CommandError> {
+    let mut custom_data = FileService::read_custom_data();
+    custom_data.preferences.natural_sort = Some(enabled);
+    FileService::write_custom_data(&custom_data).map_err(|e| {
+        log::error!("Error writing custom_data: {}", e);
+        CommandError::from(e)
+    })?;
+    SortingService::set_natural_sort(enabled);
+    emit_preferences_changed(&handle, &state.preferences()?);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_keep_non_public_favorites() -> Result<bool, CommandError> {
+    Ok(FileService::read_custom_data()
+        .preferences
+        .keep_non_public_favorites
+        .unwrap_or(false))
+}
+
+/// Sets whether importing favorites keeps the ones which are not public as placeholders
+/// named "???", instead of skipping them
+#[tauri::command]
+#[specta::specta]
+pub fn set_keep_non_public_favorites(
+    enabled: bool,
+    state: State<'_, AppState>,
+    handle: AppHandle,
+) -> Result<(), CommandError> {
+    let mut custom_data = FileService::read_custom_data();
+    custom_data.preferences.keep_non_public_favorites = Some(enabled);
+    FileService::write_custom_data(&custom_data).map_err(|e| {
+        log::error!("Error writing custom_data: {}", e);
+        CommandError::from(e)
+    })?;
+    emit_preferences_changed(&handle, &state.preferences()?);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_default_instance_type(
+    state: State<'_, AppState>,
+) -> Result<DefaultInstanceType, CommandError> {
+    let preferences = state.preferences()?;
     Ok(preferences.default_instance_type.clone())
 }
 
 #[tauri::command]
 #[specta::specta]
-pub fn set_default_instance_type(instance_type: DefaultInstanceType) -> Result<(), String> {
-    let mut preferences_lock = PREFERENCES.get().write();
-    let preferences = preferences_lock.as_mut().unwrap();
+pub fn set_default_instance_type(
+    instance_type: DefaultInstanceType,
+    state: State<'_, AppState>,
+    handle: AppHandle,
+) -> Result<(), CommandError> {
+    let mut preferences = state.preferences_mut()?;
     preferences.default_instance_type = instance_type.clone();
 
     // Write to custom_data.json for backward compatibility
@@ -269,16 +423,264 @@ pub fn set_default_instance_type(instance_type: DefaultInstanceType) -> Result<(
     custom_data.preferences.default_instance_type = instance_type;
     FileService::write_custom_data(&custom_data).map_err(|e| {
         log::error!("Error writing custom_data: {}", e);
-        e.to_string()
+        CommandError::from(e)
     })?;
+    emit_preferences_changed(&handle, &preferences);
     Ok(())
 }
 
 #[tauri::command]
 #[specta::specta]
-pub fn get_visible_buttons() -> Result<crate::definitions::VisibleButtons, String> {
-    let preferences_lock = PREFERENCES.get().read();
-    let preferences = preferences_lock.as_ref().unwrap();
+pub fn get_recently_visited_import() -> Result<RecentlyVisitedImport, CommandError> {
+    Ok(FileService::read_custom_data()
+        .preferences
+        .recently_visited_import
+        .unwrap_or_default())
+}
+
+/// Enables or disables the periodic import of recently visited worlds, and sets the folder cap
+#[tauri::command]
+#[specta::specta]
+pub fn set_recently_visited_import(
+    settings: RecentlyVisitedImport,
+    state: State<'_, AppState>,
+    handle: AppHandle,
+) -> Result<(), CommandError> {
+    if settings.cap == 0 {
+        return Err(CommandError::validation(
+            "The Recently Visited folder must keep at least 1 world",
+        ));
+    }
+
+    let mut custom_data = FileService::read_custom_data();
+    custom_data.preferences.recently_visited_import = Some(settings);
+    FileService::write_custom_data(&custom_data).map_err(|e| {
+        log::error!("Error writing custom_data: {}", e);
+        CommandError::from(e)
+    })?;
+    emit_preferences_changed(&handle, &state.preferences()?);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_clipboard_watch() -> Result<ClipboardWatch, CommandError> {
+    Ok(FileService::read_custom_data()
+        .preferences
+        .clipboard_watch
+        .unwrap_or_default())
+}
+
+/// Enables or disables watching the clipboard for VRChat world URLs, and sets what to do with them
+#[tauri::command]
+#[specta::specta]
+pub fn set_clipboard_watch(
+    settings: ClipboardWatch,
+    state: State<'_, AppState>,
+    handle: AppHandle,
+) -> Result<(), CommandError> {
+    let mut custom_data = FileService::read_custom_data();
+    custom_data.preferences.clipboard_watch = Some(settings);
+    FileService::write_custom_data(&custom_data).map_err(|e| {
+        log::error!("Error writing custom_data: {}", e);
+        CommandError::from(e)
+    })?;
+    emit_preferences_changed(&handle, &state.preferences()?);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_tray_settings() -> Result<TraySettings, CommandError> {
+    Ok(FileService::read_custom_data()
+        .preferences
+        .tray
+        .unwrap_or_default())
+}
+
+/// Sets whether the app starts minimized to the tray and whether closing the window hides it there
+#[tauri::command]
+#[specta::specta]
+pub fn set_tray_settings(
+    settings: TraySettings,
+    state: State<'_, AppState>,
+    handle: AppHandle,
+) -> Result<(), CommandError> {
+    let mut custom_data = FileService::read_custom_data();
+    custom_data.preferences.tray = Some(settings);
+    FileService::write_custom_data(&custom_data).map_err(|e| {
+        log::error!("Error writing custom_data: {}", e);
+        CommandError::from(e)
+    })?;
+    emit_preferences_changed(&handle, &state.preferences()?);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_pinned_world() -> Result<Option<String>, CommandError> {
+    Ok(FileService::read_custom_data().preferences.pinned_world)
+}
+
+/// Pins a saved world, which the quick launch shortcut creates an instance of
+#[tauri::command]
+#[specta::specta]
+pub fn set_pinned_world(
+    world_id: String,
+    state: State<'_, AppState>,
+    handle: AppHandle,
+) -> Result<(), CommandError> {
+    if !state
+        .worlds()?
+        .iter()
+        .any(|w| w.api_data.world_id == world_id)
+    {
+        return Err(CommandError::validation(format!(
+            "World {} is not in the library",
+            world_id
+        )));
+    }
+    write_pinned_world(Some(world_id))?;
+    emit_preferences_changed(&handle, &state.preferences()?);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn clear_pinned_world(
+    state: State<'_, AppState>,
+    handle: AppHandle,
+) -> Result<(), CommandError> {
+    write_pinned_world(None)?;
+    emit_preferences_changed(&handle, &state.preferences()?);
+    Ok(())
+}
+
+fn write_pinned_world(world_id: Option<String>) -> Result<(), CommandError> {
+    let mut custom_data = FileService::read_custom_data();
+    custom_data.preferences.pinned_world = world_id;
+    FileService::write_custom_data(&custom_data).map_err(|e| {
+        log::error!("Error writing custom_data: {}", e);
+        CommandError::from(e)
+    })?;
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_quick_launch_shortcut() -> Result<String, CommandError> {
+    Ok(crate::shortcut::saved_binding())
+}
+
+/// Changes the global shortcut that launches the pinned world, e.g. "CommandOrControl+Shift+L"
+#[tauri::command]
+#[specta::specta]
+pub fn set_quick_launch_shortcut(
+    shortcut: String,
+    handle: State<'_, AppHandle>,
+    state: State<'_, AppState>,
+) -> Result<(), CommandError> {
+    crate::shortcut::rebind(&handle, &shortcut).map_err(|e| {
+        log::warn!("Error changing quick launch shortcut: {}", e);
+        CommandError::validation(e)
+    })?;
+
+    let mut custom_data = FileService::read_custom_data();
+    custom_data.preferences.quick_launch_shortcut = Some(shortcut);
+    FileService::write_custom_data(&custom_data).map_err(|e| {
+        log::error!("Error writing custom_data: {}", e);
+        CommandError::from(e)
+    })?;
+    emit_preferences_changed(&handle, &state.preferences()?);
+    Ok(())
+}
+
+/// Gets the directory VRChat photos are scanned in, Pictures/VRChat unless changed
+#[tauri::command]
+#[specta::specta]
+pub fn get_photo_directory() -> Result<Option<String>, CommandError> {
+    Ok(PhotoScanner::directory().map(|directory| directory.to_string_lossy().to_string()))
+}
+
+/// Changes the directory VRChat photos are scanned in, None for Pictures/VRChat
+#[tauri::command]
+#[specta::specta]
+pub fn set_photo_directory(
+    directory: Option<String>,
+    state: State<'_, AppState>,
+    handle: AppHandle,
+) -> Result<(), CommandError> {
+    PhotoScanner::set_directory(directory).map_err(|e| {
+        log::error!("Error setting photo directory: {}", e);
+        CommandError::from(e)
+    })?;
+    emit_preferences_changed(&handle, &state.preferences()?);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_worlds_storage(state: State<'_, AppState>) -> Result<WorldsStorageFormat, CommandError> {
+    Ok(state.preferences()?.worlds_storage)
+}
+
+/// Changes how worlds.json is stored, rewriting it right away
+/// A copy in the previous format is kept next to it
+#[tauri::command]
+#[specta::specta]
+pub fn set_worlds_storage(
+    format: WorldsStorageFormat,
+    state: State<'_, AppState>,
+    handle: AppHandle,
+) -> Result<(), CommandError> {
+    WorldStorage::set_format(format, state.world_store()).map_err(|e| {
+        log::error!("Error setting worlds storage format: {}", e);
+        CommandError::from(e)
+    })?;
+    let mut preferences = state.preferences_mut()?;
+    preferences.worlds_storage = format;
+    emit_preferences_changed(&handle, &preferences);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_default_search_platform(
+    state: State<'_, AppState>,
+) -> Result<Option<SearchPlatform>, CommandError> {
+    let preferences = state.preferences()?;
+    Ok(preferences.default_search_platform)
+}
+
+/// Sets the platform filter applied to searches which don't specify one
+/// None searches every platform
+#[tauri::command]
+#[specta::specta]
+pub fn set_default_search_platform(
+    platform: Option<SearchPlatform>,
+    state: State<'_, AppState>,
+    handle: AppHandle,
+) -> Result<(), CommandError> {
+    let mut preferences = state.preferences_mut()?;
+    preferences.default_search_platform = platform;
+
+    // Extended preferences live in custom_data.json
+    let mut custom_data = FileService::read_custom_data();
+    custom_data.preferences.default_search_platform = platform;
+    FileService::write_custom_data(&custom_data).map_err(|e| {
+        log::error!("Error writing custom_data: {}", e);
+        CommandError::from(e)
+    })?;
+    emit_preferences_changed(&handle, &preferences);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_visible_buttons(
+    state: State<'_, AppState>,
+) -> Result<crate::definitions::VisibleButtons, CommandError> {
+    let preferences = state.preferences()?;
     Ok(preferences.visible_buttons.clone())
 }
 
@@ -286,13 +688,55 @@ pub fn get_visible_buttons() -> Result<crate::definitions::VisibleButtons, Strin
 #[specta::specta]
 pub fn set_visible_buttons(
     visible_buttons: crate::definitions::VisibleButtons,
-) -> Result<(), String> {
-    let mut preferences_lock = PREFERENCES.get().write();
-    let preferences = preferences_lock.as_mut().unwrap();
+    state: State<'_, AppState>,
+    handle: AppHandle,
+) -> Result<(), CommandError> {
+    let mut preferences = state.preferences_mut()?;
     preferences.visible_buttons = visible_buttons;
-    FileService::write_preferences(preferences).map_err(|e| {
+    FileService::write_preferences(&preferences).map_err(|e| {
         log::error!("Error writing preferences: {}", e);
-        e.to_string()
+        CommandError::from(e)
+    })?;
+    emit_preferences_changed(&handle, &preferences);
+    Ok(())
+}
+
+/// Exports the preferences to a settings profile in `target_dir`, for another machine
+/// The login, webhook URL and local API token are left out
+///
+/// # Returns
+/// The path of the written profile
+#[tauri::command]
+#[specta::specta]
+pub fn export_settings_profile(
+    target_dir: String,
+    state: State<'_, AppState>,
+) -> Result<String, CommandError> {
+    let preferences = state.preferences()?;
+    let path = SettingsProfileService::export(&preferences, &target_dir).map_err(|e| {
+        log::error!("Error exporting settings profile: {}", e);
+        CommandError::from(e)
+    })?;
+    Ok(path.to_string_lossy().to_string())
+}
+
+/// Replaces the preferences with those of a settings profile
+/// Settings of background features such as the tray take effect after a restart
+#[tauri::command]
+#[specta::specta]
+pub fn import_settings_profile(
+    path: String,
+    state: State<'_, AppState>,
+    handle: AppHandle,
+) -> Result<(), CommandError> {
+    let mut preferences = state.preferences_mut()?;
+    SettingsProfileService::import(&path, &mut preferences).map_err(|e| {
+        log::error!("Error importing settings profile: {}", e);
+        CommandError::from(e)
     })?;
+    SortingService::set_language(&preferences.language);
+    i18n::set_language(&preferences.language);
+    SortingService::init();
+    emit_preferences_changed(&handle, &preferences);
     Ok(())
 }
@@ -0,0 +1,66 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+
+use crate::commands::deep_link_commands::DEEP_LINK_SCHEME;
+use crate::definitions::{QrCodeImage, QrPayload};
+use crate::errors::CommandError;
+use crate::services::{QrCodeService, WorldReference};
+
+/// Generates a QR code for a world, instance or shared folder, e.g. for event posters
+/// With `save` the PNG is also written to the exports folder
+#[tauri::command]
+#[specta::specta]
+pub fn generate_qr_png(payload: QrPayload, save: bool) -> Result<QrCodeImage, CommandError> {
+    let (name, content) = payload_content(payload)?;
+    let png_data = QrCodeService::render_png(&content).map_err(|e| {
+        log::error!("Error generating QR code: {}", e);
+        CommandError::from(e)
+    })?;
+
+    let path = if save {
+        let path = QrCodeService::save_png(&name, &png_data).map_err(|e| {
+            log::error!("Error saving QR code: {}", e);
+            CommandError::from(e)
+        })?;
+        Some(path.to_string_lossy().to_string())
+    } else {
+        None
+    };
+
+    Ok(QrCodeImage {
+        content,
+        png_base64: STANDARD.encode(&png_data),
+        path,
+    })
+}
+
+/// Gets the name used in the file name, and the link the code contains
+fn payload_content(payload: QrPayload) -> Result<(String, String), CommandError> {
+    match payload {
+        QrPayload::World { world_id } => {
+            let reference = WorldReference::parse(&world_id).map_err(CommandError::validation)?;
+            Ok((reference.world_id.clone(), reference.web_url()))
+        }
+        QrPayload::Instance {
+            world_id,
+            instance_id,
+        } => {
+            let reference = WorldReference::parse(&format!("{}:{}", world_id, instance_id))
+                .map_err(CommandError::validation)?;
+            Ok((reference.world_id.clone(), reference.web_url()))
+        }
+        QrPayload::Share { share_id } => {
+            if share_id.is_empty()
+                || !share_id
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+            {
+                return Err(CommandError::validation(format!(
+                    "Invalid share ID: {}",
+                    share_id
+                )));
+            }
+            let content = format!("{}import-share/{}", DEEP_LINK_SCHEME, share_id);
+            Ok((share_id, content))
+        }
+    }
+}
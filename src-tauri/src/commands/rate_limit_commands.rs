@@ -0,0 +1,74 @@
+use crate::api::{self, RateLimitStatus};
+use crate::errors::CommandError;
+use crate::services::FileService;
+use crate::REQUEST_SCHEDULER;
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_rate_limit_status() -> Result<Vec<RateLimitStatus>, CommandError> {
+    Ok(api::get_rate_limit_status())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn reset_rate_limits() -> Result<(), CommandError> {
+    api::reset_rate_limits();
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_api_request_budget() -> Result<u32, CommandError> {
+    Ok(REQUEST_SCHEDULER.get().requests_per_minute())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn set_api_request_budget(requests_per_minute: u32) -> Result<(), CommandError> {
+    if requests_per_minute == 0 {
+        return Err(CommandError::validation(
+            "Request budget must be at least 1 request per minute",
+        ));
+    }
+
+    let mut custom_data = FileService::read_custom_data();
+    custom_data.preferences.api_requests_per_minute = Some(requests_per_minute);
+    FileService::write_custom_data(&custom_data).map_err(|e| {
+        log::error!("Error writing custom_data: {}", e);
+        CommandError::from(e)
+    })?;
+
+    REQUEST_SCHEDULER
+        .get()
+        .set_requests_per_minute(requests_per_minute);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_api_concurrent_requests() -> Result<u32, CommandError> {
+    Ok(REQUEST_SCHEDULER.get().max_in_flight())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn set_api_concurrent_requests(concurrent_requests: u32) -> Result<(), CommandError> {
+    if !(1..=api::MAX_CONCURRENT_REQUESTS).contains(&concurrent_requests) {
+        return Err(CommandError::validation(format!(
+            "Concurrent requests must be between 1 and {}",
+            api::MAX_CONCURRENT_REQUESTS
+        )));
+    }
+
+    let mut custom_data = FileService::read_custom_data();
+    custom_data.preferences.api_concurrent_requests = Some(concurrent_requests);
+    FileService::write_custom_data(&custom_data).map_err(|e| {
+        log::error!("Error writing custom_data: {}", e);
+        CommandError::from(e)
+    })?;
+
+    REQUEST_SCHEDULER
+        .get()
+        .set_max_in_flight(concurrent_requests);
+    Ok(())
+}
@@ -0,0 +1,159 @@
+use std::sync::Arc;
+
+use tauri::async_runtime::Mutex;
+use tauri::{AppHandle, State};
+use uuid::Uuid;
+
+use crate::app_state::AppState;
+use crate::commands::api_commands::import_favorite_worlds;
+use crate::definitions::InitialImportOptions;
+use crate::errors::CommandError;
+use crate::migration::MigrationService;
+use crate::services::{FileService, RecentlyVisitedService};
+use crate::task::cancellable_task::{TaskContainer, TaskProgressReporter};
+use crate::task::definitions::{InitialImportStep, TaskKind, TaskProgress};
+use crate::{AUTHENTICATOR, MEMO_MANAGER};
+
+/// Runs the setup page's imports as one cancellable task: the old VRC Worlds Manager's data,
+/// then the user's favorite worlds, then their recently visited worlds
+/// Returns the task ID immediately; progress is reported through `TaskStatusChanged`
+///
+/// A failing step does not stop the ones after it; the task fails once every step has run,
+/// with an error naming the steps which failed
+#[tauri::command]
+#[specta::specta]
+pub async fn run_initial_import(
+    options: InitialImportOptions,
+    handle: State<'_, AppHandle>,
+    task_container: State<'_, Arc<Mutex<TaskContainer>>>,
+    state: State<'_, AppState>,
+) -> Result<Uuid, CommandError> {
+    let steps = steps(&options);
+    if steps.is_empty() {
+        return Err(CommandError::validation("Nothing was selected to import"));
+    }
+    let handle = (*handle).clone();
+    let state = state.inner().clone();
+
+    task_container
+        .lock()
+        .await
+        .run_with_progress(TaskKind::InitialImport, move |reporter| {
+            initial_import(options, steps, reporter, handle, state)
+        })
+        .map_err(CommandError::internal)
+}
+
+/// The steps the options select, in the order they run
+fn steps(options: &InitialImportOptions) -> Vec<InitialImportStep> {
+    let mut steps = vec![];
+    if options.old_worlds_path.is_some() || options.old_folders_path.is_some() {
+        steps.push(InitialImportStep::Migration);
+    }
+    if options.import_favorites {
+        steps.push(InitialImportStep::Favorites);
+    }
+    if options.import_recently_visited {
+        steps.push(InitialImportStep::RecentlyVisited);
+    }
+    steps
+}
+
+async fn initial_import(
+    options: InitialImportOptions,
+    steps: Vec<InitialImportStep>,
+    reporter: TaskProgressReporter,
+    handle: AppHandle,
+    state: AppState,
+) -> Result<(), String> {
+    let total_steps = steps.len() as u32;
+    let mut errors = vec![];
+
+    for (index, step) in steps.into_iter().enumerate() {
+        let failed_steps = errors.len() as u32;
+        reporter
+            .report(TaskProgress::InitialImport {
+                step,
+                completed_steps: index as u32,
+                failed_steps,
+                total_steps,
+            })
+            .await;
+
+        if let Err(e) = run_step(step, &options, &handle, &state).await {
+            log::error!("Initial import step {:?} failed: {}", step, e);
+            errors.push(format!("{:?}: {}", step, e));
+        }
+
+        reporter
+            .report(TaskProgress::InitialImport {
+                step,
+                completed_steps: index as u32 + 1,
+                failed_steps: errors.len() as u32,
+                total_steps,
+            })
+            .await;
+    }
+
+    if errors.is_empty() {
+        log::info!("Initial import completed");
+        Ok(())
+    } else {
+        Err(format!("Some imports failed: {}", errors.join("; ")))
+    }
+}
+
+async fn run_step(
+    step: InitialImportStep,
+    options: &InitialImportOptions,
+    handle: &AppHandle,
+    state: &AppState,
+) -> Result<(), String> {
+    match step {
+        InitialImportStep::Migration => {
+            let (Some(worlds_path), Some(folders_path)) =
+                (&options.old_worlds_path, &options.old_folders_path)
+            else {
+                return Err("Both the worlds and the folders file are needed".to_string());
+            };
+            MigrationService::migrate_old_data(
+                worlds_path.clone(),
+                folders_path.clone(),
+                state.world_store(),
+                state.folder_store(),
+                MEMO_MANAGER.get(),
+            )
+            .await
+            .map(|_| ())
+        }
+        InitialImportStep::Favorites => {
+            let cookie_store = AUTHENTICATOR.get().read().await.get_cookies();
+            import_favorite_worlds(
+                cookie_store,
+                options.create_group_folders,
+                None,
+                handle.clone(),
+                state.clone(),
+            )
+            .await
+            .map(|_| ())
+        }
+        InitialImportStep::RecentlyVisited => {
+            let cookie_store = AUTHENTICATOR.get().read().await.get_cookies();
+            let settings = FileService::read_custom_data()
+                .preferences
+                .recently_visited_import
+                .unwrap_or_default();
+            RecentlyVisitedService::import_recently_visited(
+                cookie_store,
+                settings.cap as usize,
+                state.folder_store(),
+                state.world_store(),
+                state.blacklist_store(),
+            )
+            .await
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+        }
+    }
+}
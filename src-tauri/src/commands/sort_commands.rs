@@ -1,4 +1,5 @@
 use crate::definitions::WorldDisplayData;
+use crate::errors::CommandError;
 use crate::services::SortingService;
 
 #[tauri::command]
@@ -7,10 +8,24 @@ pub fn sort_worlds_display(
     worlds: Vec<WorldDisplayData>,
     sort_field: String,
     sort_direction: String,
-) -> Result<Vec<WorldDisplayData>, String> {
+) -> Result<Vec<WorldDisplayData>, CommandError> {
     Ok(SortingService::sort_world_display_data(
         worlds,
         &sort_field,
         &sort_direction,
     ))
 }
+
+/// Keeps the worlds whose download for the platform is at most `max_size` bytes
+/// Worlds of unknown size are kept
+#[tauri::command]
+#[specta::specta]
+pub fn filter_worlds_by_download_size(
+    worlds: Vec<WorldDisplayData>,
+    max_size: u64,
+    quest: bool,
+) -> Result<Vec<WorldDisplayData>, CommandError> {
+    Ok(SortingService::filter_by_download_size(
+        worlds, max_size, quest,
+    ))
+}
@@ -0,0 +1,47 @@
+use tauri::State;
+
+use crate::app_state::AppState;
+use crate::definitions::{ConflictResolution, SyncStatus, SyncTarget};
+use crate::errors::CommandError;
+use crate::services::SyncService;
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_sync_status() -> Result<SyncStatus, CommandError> {
+    Ok(SyncService::status())
+}
+
+/// Sets the sync folder or WebDAV server, or turns sync off when `target` is None
+#[tauri::command]
+#[specta::specta]
+pub async fn set_sync_target(target: Option<SyncTarget>) -> Result<SyncStatus, CommandError> {
+    SyncService::set_target(target).await.map_err(|e| {
+        log::error!("Error setting sync target: {}", e);
+        CommandError::from(e)
+    })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn sync_now(state: State<'_, AppState>) -> Result<SyncStatus, CommandError> {
+    SyncService::sync(&state).await.map_err(|e| {
+        log::error!("Error syncing library: {}", e);
+        CommandError::from(e)
+    })
+}
+
+/// Resolves a file listed as conflicting in the sync status by keeping one side's copy
+#[tauri::command]
+#[specta::specta]
+pub async fn resolve_sync_conflict(
+    file: String,
+    resolution: ConflictResolution,
+    state: State<'_, AppState>,
+) -> Result<SyncStatus, CommandError> {
+    SyncService::resolve_conflict(&state, file, resolution)
+        .await
+        .map_err(|e| {
+            log::error!("Error resolving sync conflict: {}", e);
+            CommandError::from(e)
+        })
+}
@@ -3,14 +3,25 @@ use std::sync::Arc;
 use tauri::{async_runtime::Mutex, State};
 use uuid::Uuid;
 
-use crate::task::{cancellable_task::TaskContainer, definitions::TaskStatus};
+use crate::errors::CommandError;
+use crate::task::cancellable_task::TaskContainer;
+use crate::task::definitions::{TaskInfo, TaskProgress, TaskStatus};
+
+/// Lists the background tasks which are still running, for the task panel
+#[tauri::command]
+#[specta::specta]
+pub async fn list_active_tasks(
+    task_container: State<'_, Arc<Mutex<TaskContainer>>>,
+) -> Result<Vec<TaskInfo>, CommandError> {
+    Ok(task_container.lock().await.list_active().await)
+}
 
 #[tauri::command]
 #[specta::specta]
 pub async fn get_task_status(
     task_container: State<'_, Arc<Mutex<TaskContainer>>>,
     id: Uuid,
-) -> Result<TaskStatus, String> {
+) -> Result<TaskStatus, CommandError> {
     let container = task_container.lock().await;
     let task = container.get(&id).await;
 
@@ -23,17 +34,17 @@ pub async fn get_task_status(
         None => {
             let err = format!("Task not found: {:?}", id);
             log::error!("{}", err);
-            Err(err)
+            Err(CommandError::not_found(err))
         }
     }
 }
 
 #[tauri::command]
 #[specta::specta]
-pub async fn cancel_task_request(
+pub async fn cancel_task(
     task_container: State<'_, Arc<Mutex<TaskContainer>>>,
     id: Uuid,
-) -> Result<TaskStatus, String> {
+) -> Result<TaskStatus, CommandError> {
     let container = task_container.lock().await;
     let task = container.get(&id).await;
 
@@ -46,7 +57,7 @@ pub async fn cancel_task_request(
         None => {
             let err = format!("Task not found: {:?}", id);
             log::error!("{}", err);
-            Err(err)
+            Err(CommandError::not_found(err))
         }
     }
 }
@@ -56,7 +67,7 @@ pub async fn cancel_task_request(
 pub async fn get_task_error(
     task_container: State<'_, Arc<Mutex<TaskContainer>>>,
     id: Uuid,
-) -> Result<Option<String>, String> {
+) -> Result<Option<String>, CommandError> {
     let container = task_container.lock().await;
     let task = container.get(&id).await;
 
@@ -69,7 +80,29 @@ pub async fn get_task_error(
         None => {
             let err = format!("Task not found: {:?}", id);
             log::error!("{}", err);
-            Err(err)
+            Err(CommandError::not_found(err))
+        }
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_task_progress(
+    task_container: State<'_, Arc<Mutex<TaskContainer>>>,
+    id: Uuid,
+) -> Result<Option<TaskProgress>, CommandError> {
+    let container = task_container.lock().await;
+    let task = container.get(&id).await;
+
+    match task {
+        Some(task) => {
+            let task = task.lock().await;
+            Ok(task.get_progress().await)
+        }
+        None => {
+            let err = format!("Task not found: {:?}", id);
+            log::error!("{}", err);
+            Err(CommandError::not_found(err))
         }
     }
 }
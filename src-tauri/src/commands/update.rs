@@ -1,28 +1,48 @@
 use std::sync::Arc;
 
-use tauri::{async_runtime::Mutex, State};
+use tauri::{async_runtime::Mutex, AppHandle, State};
 use uuid::Uuid;
 
-use crate::{task::cancellable_task::TaskContainer, updater::update_handler::UpdateHandler};
+use crate::app_state::AppState;
+use crate::changelog::app_version;
+use crate::commands::notification_commands::notify;
+use crate::definitions::NotificationKind;
+use crate::errors::CommandError;
+use crate::services::FileService;
+use crate::task::{cancellable_task::TaskContainer, definitions::TaskKind};
+use crate::updater::releases::{self, ReleaseInfo};
+use crate::updater::update_handler::{UpdateChannel, UpdateHandler, UpdatePolicy};
 
 #[tauri::command]
 #[specta::specta]
 pub async fn check_for_update(
     update_handler: State<'_, Arc<Mutex<UpdateHandler>>>,
-) -> Result<bool, String> {
+    handle: State<'_, AppHandle>,
+) -> Result<bool, CommandError> {
     let handler = update_handler.lock().await;
 
     if !handler.is_initialized() {
         let err = "Update handler is not initialized yet.".to_string();
         log::error!("{}", err);
-        return Err(err);
+        return Err(CommandError::internal(err));
     }
 
     if !handler.show_notification().await {
         return Ok(false);
     }
 
-    let new_version_available = handler.update_available();
+    // Skipped versions, and every version while pinned, are not offered
+    let policy = read_update_policy();
+    let new_version_available =
+        handler.update_available() && handler.update_version().is_some_and(|v| policy.allows(v));
+    if let Some(version) = handler.update_version().filter(|_| new_version_available) {
+        notify(
+            vec![NotificationKind::UpdateAvailable {
+                version: version.to_string(),
+            }],
+            &handle,
+        );
+    }
     Ok(new_version_available)
 }
 
@@ -31,26 +51,26 @@ pub async fn check_for_update(
 pub async fn download_update(
     update_handler: State<'_, Arc<Mutex<UpdateHandler>>>,
     task_container: State<'_, Arc<Mutex<TaskContainer>>>,
-) -> Result<Uuid, String> {
+) -> Result<Uuid, CommandError> {
     {
         let handler = update_handler.lock().await;
 
         if !handler.is_initialized() {
             let err = "Update handler is not initialized yet.".to_string();
             log::error!("{}", err);
-            return Err(err);
+            return Err(CommandError::internal(err));
         }
 
         if !handler.update_available() {
             let err = "No update available.".to_string();
             log::error!("{}", err);
-            return Err(err);
+            return Err(CommandError::not_found(err));
         }
     }
 
     let cloned_update_handler = (*update_handler).clone();
 
-    let task = task_container.lock().await.run(async move {
+    let task = task_container.lock().await.run(TaskKind::UpdateDownload, async move {
         let mut handler = cloned_update_handler.lock().await;
 
         handler
@@ -59,25 +79,25 @@ pub async fn download_update(
             .map_err(|e| format!("Failed to download update: {:?}", e))
     });
 
-    task
+    task.map_err(CommandError::internal)
 }
 
 #[tauri::command]
 #[specta::specta]
 pub async fn install_update(
     update_handler: State<'_, Arc<Mutex<UpdateHandler>>>,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
     let handler = update_handler.lock().await;
 
     if !handler.is_initialized() {
         let err = "Update handler is not initialized yet.".to_string();
         log::error!("{}", err);
-        return Err(err);
+        return Err(CommandError::internal(err));
     }
 
     if let Err(e) = handler.install_update() {
         log::error!("{}", e);
-        return Err(e);
+        return Err(CommandError::internal(e));
     }
 
     Ok(())
@@ -87,15 +107,98 @@ pub async fn install_update(
 #[specta::specta]
 pub async fn do_not_notify_update(
     update_handler: State<'_, Arc<Mutex<UpdateHandler>>>,
-) -> Result<bool, String> {
+) -> Result<bool, CommandError> {
     let mut handler = update_handler.lock().await;
 
     if !handler.is_initialized() {
         let err = "Update handler is not initialized yet.".to_string();
         log::error!("{}", err);
-        return Err(err);
+        return Err(CommandError::internal(err));
     }
 
     handler.set_show_notification(false).await;
     Ok(true)
 }
+
+/// Lists the recent releases of a channel, the channel in preferences if not given
+#[tauri::command]
+#[specta::specta]
+pub async fn list_releases(
+    channel: Option<UpdateChannel>,
+    state: State<'_, AppState>,
+) -> Result<Vec<ReleaseInfo>, CommandError> {
+    let channel = match channel {
+        Some(channel) => channel,
+        None => {
+            state
+                .preferences()
+                .map_err(|e| {
+                    log::error!("Failed to read preferences: {}", e);
+                    CommandError::from(e)
+                })?
+                .update_channel
+        }
+    };
+    releases::fetch_releases(channel, &read_update_policy())
+        .await
+        .map_err(|e| {
+            log::error!("Error listing releases: {}", e);
+            CommandError::network(e)
+        })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_update_policy() -> Result<UpdatePolicy, CommandError> {
+    Ok(read_update_policy())
+}
+
+/// Skips a version so it is not offered, or offers it again
+#[tauri::command]
+#[specta::specta]
+pub async fn skip_update_version(
+    version: String,
+    skipped: bool,
+) -> Result<UpdatePolicy, CommandError> {
+    let version = version.trim_start_matches('v').to_string();
+    if semver::Version::parse(&version).is_err() {
+        return Err(CommandError::validation(format!(
+            "Invalid version: {}",
+            version
+        )));
+    }
+    update_policy(|policy| {
+        policy.skipped_versions.retain(|v| *v != version);
+        if skipped {
+            policy.skipped_versions.push(version);
+        }
+    })
+}
+
+/// Pins the app to the installed version, so no update is offered until unpinned
+#[tauri::command]
+#[specta::specta]
+pub async fn pin_current_version(pinned: bool) -> Result<UpdatePolicy, CommandError> {
+    update_policy(|policy| {
+        policy.pinned_version = pinned.then(|| app_version().to_string());
+    })
+}
+
+fn read_update_policy() -> UpdatePolicy {
+    FileService::read_custom_data()
+        .preferences
+        .update_policy
+        .unwrap_or_default()
+}
+
+fn update_policy(change: impl FnOnce(&mut UpdatePolicy)) -> Result<UpdatePolicy, CommandError> {
+    let mut custom_data = FileService::read_custom_data();
+    let mut policy = custom_data.preferences.update_policy.unwrap_or_default();
+    change(&mut policy);
+    custom_data.preferences.update_policy = Some(policy.clone());
+    FileService::write_custom_data(&custom_data).map_err(|e| {
+        log::error!("Error writing custom_data: {}", e);
+        CommandError::from(e)
+    })?;
+    Ok(policy)
+}
@@ -1,10 +1,14 @@
 use reqwest::Client;
 use specta::specta;
-use tauri::command;
+use tauri::{command, AppHandle};
+use tauri_plugin_clipboard_manager::ClipboardExt;
+
+use crate::errors::CommandError;
+use crate::services::{Persistence, WorldReference};
 
 #[command]
 #[specta]
-pub async fn resolve_redirects(url: String) -> Result<String, String> {
+pub async fn resolve_redirects(url: String) -> Result<String, CommandError> {
     log::info!("resolve_redirects called with: {}", url);
 
     let client = Client::builder()
@@ -12,7 +16,7 @@ pub async fn resolve_redirects(url: String) -> Result<String, String> {
         .redirect(reqwest::redirect::Policy::limited(10))
         .cookie_store(true)  // Enable cookies - some redirects need this
         .build()
-        .map_err(|e| e.to_string())?;
+        .map_err(|e| CommandError::network(e.to_string()))?;
 
     // Try following redirects with GET
     let response = client
@@ -24,7 +28,7 @@ pub async fn resolve_redirects(url: String) -> Result<String, String> {
         .header("Accept-Language", "en-US,en;q=0.5")
         .send()
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(|e| CommandError::network(e.to_string()))?;
 
     let final_url = response.url().to_string();
     log::info!("resolve_redirects auto-redirect result: {}", final_url);
@@ -38,7 +42,7 @@ pub async fn resolve_redirects(url: String) -> Result<String, String> {
             .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
             .redirect(reqwest::redirect::Policy::none())
             .build()
-            .map_err(|e| e.to_string())?;
+            .map_err(|e| CommandError::network(e.to_string()))?;
 
         let manual_response = no_redirect_client
             .get(&url)
@@ -48,7 +52,7 @@ pub async fn resolve_redirects(url: String) -> Result<String, String> {
             )
             .send()
             .await
-            .map_err(|e| e.to_string())?;
+            .map_err(|e| CommandError::network(e.to_string()))?;
 
         log::info!("Manual request status: {}", manual_response.status());
 
@@ -64,7 +68,7 @@ pub async fn resolve_redirects(url: String) -> Result<String, String> {
         }
 
         // If still no luck, try parsing HTML for meta refresh
-        let body = manual_response.text().await.map_err(|e| e.to_string())?;
+        let body = manual_response.text().await.map_err(|e| CommandError::network(e.to_string()))?;
         if let Some(meta_url) = extract_meta_refresh(&body) {
             log::info!("Found meta refresh URL: {}", meta_url);
             return Box::pin(resolve_redirects(meta_url)).await;
@@ -99,3 +103,32 @@ fn extract_meta_refresh(html: &str) -> Option<String> {
 pub fn get_startup_deep_link(state: tauri::State<crate::StartupDeepLink>) -> Option<String> {
     state.0.lock().unwrap().take()
 }
+
+/// Resolves once every write queued so far is durably on disk
+/// Await this after critical operations before telling the user their changes are saved
+#[command]
+#[specta]
+pub async fn flush_pending_writes() -> Result<(), CommandError> {
+    Persistence::flush().await.map_err(|e| {
+        log::error!("Error flushing pending writes: {}", e);
+        CommandError::from(e)
+    })
+}
+
+/// Parses a pasted world ID, instance location, or vrchat.com or vrchat:// URL
+/// Short links like vrch.at need `resolve_redirects` first
+#[command]
+#[specta]
+pub fn parse_world_reference(input: String) -> Result<WorldReference, CommandError> {
+    WorldReference::parse(&input).map_err(CommandError::validation)
+}
+
+/// Copies text to the system clipboard, e.g. an instance link
+#[command]
+#[specta]
+pub fn copy_to_clipboard(text: String, handle: AppHandle) -> Result<(), CommandError> {
+    handle.clipboard().write_text(text).map_err(|e| {
+        log::error!("Error copying to clipboard: {}", e);
+        CommandError::internal(e.to_string())
+    })
+}
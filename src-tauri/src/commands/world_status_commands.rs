@@ -1,29 +1,268 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use tauri::{AppHandle, State};
+use tauri_specta::Event;
+
+use crate::api::world::ReleaseStatus;
+use crate::app_state::AppState;
+use crate::commands::api_commands::require_login;
+use crate::commands::notification_commands::notify;
+use crate::definitions::{
+    CriticalWorldChange, EventCriticalWorldChanged, FieldConflict, LibraryChange, NotificationKind,
+    WorldField, WorldFlag, WorldFlagSummary,
+};
+use crate::errors::CommandError;
 use crate::services::folder_manager::FolderManager;
-use crate::WORLDS;
+use crate::services::{ChangeFeed, CoverImageService, FieldLockService, WorldWatchService};
+
+#[tauri::command]
+#[specta::specta]
+pub async fn set_world_photographed(
+    world_id: String,
+    is_photographed: bool,
+    state: State<'_, AppState>,
+) -> Result<(), CommandError> {
+    set_flag(world_id, WorldFlag::Photographed, is_photographed, &state)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn set_world_shared(
+    world_id: String,
+    is_shared: bool,
+    state: State<'_, AppState>,
+) -> Result<(), CommandError> {
+    set_flag(world_id, WorldFlag::Shared, is_shared, &state)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn set_world_favorite(
+    world_id: String,
+    is_favorite: bool,
+    state: State<'_, AppState>,
+) -> Result<(), CommandError> {
+    set_flag(world_id, WorldFlag::Favorite, is_favorite, &state)
+}
 
 #[tauri::command]
 #[specta::specta]
-pub async fn set_world_photographed(world_id: String, is_photographed: bool) -> Result<(), String> {
-    FolderManager::set_world_photographed(world_id, is_photographed, WORLDS.get()).map_err(|e| {
-        log::error!("Error setting world photographed status: {}", e);
-        e.to_string()
+pub async fn set_world_event_critical(
+    world_id: String,
+    is_event_critical: bool,
+    state: State<'_, AppState>,
+) -> Result<(), CommandError> {
+    set_flag(
+        world_id,
+        WorldFlag::EventCritical,
+        is_event_critical,
+        &state,
+    )
+}
+
+/// Rates a world from 1 to 5, None removes the rating
+#[tauri::command]
+#[specta::specta]
+pub async fn set_world_rating(
+    world_id: String,
+    rating: Option<u8>,
+    state: State<'_, AppState>,
+) -> Result<(), CommandError> {
+    FolderManager::set_world_rating(world_id, rating, state.world_store()).map_err(|e| {
+        log::error!("Error setting world rating: {}", e);
+        CommandError::from(e)
+    })
+}
+
+/// Shows a local image on the world's card instead of its VRChat thumbnail
+/// The image is copied into the app's data, so the original can be moved or deleted
+#[tauri::command]
+#[specta::specta]
+pub async fn set_world_cover(
+    world_id: String,
+    image_path: String,
+    state: State<'_, AppState>,
+) -> Result<(), CommandError> {
+    CoverImageService::set(&world_id, Path::new(&image_path), state.world_store()).map_err(|e| {
+        log::error!("Error setting world cover: {}", e);
+        CommandError::from(e)
     })
 }
 
+/// Shows the world's VRChat thumbnail again
 #[tauri::command]
 #[specta::specta]
-pub async fn set_world_shared(world_id: String, is_shared: bool) -> Result<(), String> {
-    FolderManager::set_world_shared(world_id, is_shared, WORLDS.get()).map_err(|e| {
-        log::error!("Error setting world shared status: {}", e);
-        e.to_string()
+pub async fn clear_world_cover(
+    world_id: String,
+    state: State<'_, AppState>,
+) -> Result<(), CommandError> {
+    CoverImageService::clear(&world_id, state.world_store()).map_err(|e| {
+        log::error!("Error clearing world cover: {}", e);
+        CommandError::from(e)
     })
 }
 
+/// Corrects the name or description of a world, keeping it when the world is fetched again
 #[tauri::command]
 #[specta::specta]
-pub async fn set_world_favorite(world_id: String, is_favorite: bool) -> Result<(), String> {
-    FolderManager::set_world_favorite(world_id, is_favorite, WORLDS.get()).map_err(|e| {
-        log::error!("Error setting world favorite status: {}", e);
-        e.to_string()
+pub async fn lock_world_field(
+    world_id: String,
+    field: WorldField,
+    value: String,
+    state: State<'_, AppState>,
+) -> Result<(), CommandError> {
+    FieldLockService::lock_field(&world_id, field, &value, state.world_store()).map_err(|e| {
+        log::error!("Error locking world field: {}", e);
+        CommandError::from(e)
     })
 }
+
+/// Lets the next fetch of the world replace a corrected field again
+#[tauri::command]
+#[specta::specta]
+pub async fn unlock_world_field(world_id: String, field: WorldField) -> Result<(), CommandError> {
+    FieldLockService::unlock_field(&world_id, field).map_err(|e| {
+        log::error!("Error unlocking world field: {}", e);
+        CommandError::from(e)
+    })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_locked_world_fields(world_id: String) -> BTreeMap<WorldField, String> {
+    FieldLockService::get_locked_fields(&world_id)
+}
+
+/// Gets the fetches since startup which brought a different value for a locked field
+#[tauri::command]
+#[specta::specta]
+pub fn get_field_conflicts() -> Vec<FieldConflict> {
+    FieldLockService::get_conflicts()
+}
+
+/// Dismisses the conflicts of a world, or of every world if none is given
+#[tauri::command]
+#[specta::specta]
+pub fn clear_field_conflicts(world_id: Option<String>) {
+    FieldLockService::clear_conflicts(world_id.as_deref());
+}
+
+/// Removes cover images of worlds which are no longer in the library
+///
+/// # Returns
+/// The number of images removed
+#[tauri::command]
+#[specta::specta]
+pub async fn remove_orphaned_covers(state: State<'_, AppState>) -> Result<u32, CommandError> {
+    CoverImageService::remove_orphaned(state.world_store()).map_err(|e| {
+        log::error!("Error removing orphaned covers: {}", e);
+        CommandError::from(e)
+    })
+}
+
+/// Sets a status flag on several worlds at once, e.g. the selected cards
+/// Worlds which are not in the library are skipped and listed in the summary
+#[tauri::command]
+#[specta::specta]
+pub async fn set_worlds_flag(
+    world_ids: Vec<String>,
+    flag: WorldFlag,
+    value: bool,
+    state: State<'_, AppState>,
+) -> Result<WorldFlagSummary, CommandError> {
+    let summary = FolderManager::set_worlds_flag(&world_ids, flag, value, state.world_store())
+        .map_err(|e| {
+            log::error!("Error setting worlds {:?} status: {}", flag, e);
+            CommandError::from(e)
+        })?;
+    for world_id in &summary.changed {
+        ChangeFeed::record(LibraryChange::FlagChanged {
+            world_id: world_id.clone(),
+            flag,
+            value,
+        });
+    }
+    Ok(summary)
+}
+
+/// Sets a status flag of a world, recording the change if the flag was different
+fn set_flag(
+    world_id: String,
+    flag: WorldFlag,
+    value: bool,
+    state: &AppState,
+) -> Result<(), CommandError> {
+    let previous = state
+        .worlds()?
+        .iter()
+        .find(|w| w.api_data.world_id == world_id)
+        .map(|w| w.user_data.flag(flag));
+    ChangeFeed::set_flag(world_id.clone(), flag, value, state).map_err(|e| {
+        log::error!("Error setting world {:?} status: {}", flag, e);
+        CommandError::from(e)
+    })?;
+    if previous != Some(value) {
+        ChangeFeed::record(LibraryChange::FlagChanged {
+            world_id,
+            flag,
+            value,
+        });
+    }
+    Ok(())
+}
+
+/// Refreshes all event critical worlds, emitting a high-priority
+/// `EventCriticalWorldChanged` event for each world whose capacity,
+/// release status or platform support changed
+#[tauri::command]
+#[specta::specta]
+pub async fn refresh_event_critical_worlds(
+    handle: State<'_, AppHandle>,
+    state: State<'_, AppState>,
+) -> Result<Vec<EventCriticalWorldChanged>, CommandError> {
+    let cookie_store = require_login().await?;
+
+    let alerts = WorldWatchService::refresh_event_critical_worlds(
+        cookie_store,
+        state.folder_store(),
+        state.world_store(),
+        state.blacklist_store(),
+    )
+    .await
+    .map_err(|e| {
+        log::error!("Error refreshing event critical worlds: {}", e);
+        CommandError::from(e)
+    })?;
+
+    emit_event_critical_alerts(&alerts, &handle);
+
+    let mut unavailable = vec![];
+    for alert in alerts.iter() {
+        for change in alert.changes.iter() {
+            if let CriticalWorldChange::ReleaseStatus { new } = change {
+                if *new == ReleaseStatus::Public.to_string() {
+                    continue;
+                }
+                unavailable.push(NotificationKind::WorldUnavailable {
+                    world_id: alert.world_id.clone(),
+                    world_name: alert.world_name.clone(),
+                    reason: format!("release status changed to {}", new),
+                });
+            }
+        }
+    }
+    notify(unavailable, &handle);
+
+    Ok(alerts)
+}
+
+/// Emits a high-priority `EventCriticalWorldChanged` event for each alert
+/// Shared by every refresh which checks event critical worlds
+pub(crate) fn emit_event_critical_alerts(alerts: &[EventCriticalWorldChanged], handle: &AppHandle) {
+    for alert in alerts {
+        if let Err(e) = alert.emit(handle) {
+            log::error!("Failed to emit event critical alert: {}", e);
+        }
+    }
+}
@@ -1,17 +1,22 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
-use crate::definitions::DefaultInstanceType;
+use crate::definitions::{DefaultInstanceType, WorldField, WorldFlag};
 
 /// Custom data structure to store app-specific extensions
 /// This is stored separately from the main data files to maintain
 /// backward compatibility with the original VRC World Manager V2.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct CustomData {
-    /// Version of the custom data format for future migrations
+    /// Schema version of custom_data.json, see `DataFile`
     #[serde(default = "default_version")]
     pub version: u32,
 
+    /// Map of file name -> schema version, for worlds.json and folders.json
+    /// Those stay in the original app's format, which has no room for a version
+    #[serde(rename = "schemaVersions", default)]
+    pub schema_versions: HashMap<String, u32>,
+
     /// Map of world_id -> is_favorite status
     #[serde(rename = "worldFavorites", default)]
     pub world_favorites: HashMap<String, bool>,
@@ -20,6 +25,18 @@ pub struct CustomData {
     #[serde(rename = "folderColors", default)]
     pub folder_colors: HashMap<String, String>,
 
+    /// Map of folder_name -> emoji or icon identifier
+    #[serde(rename = "folderIcons", default)]
+    pub folder_icons: HashMap<String, String>,
+
+    /// Map of folder_name -> description
+    #[serde(rename = "folderDescriptions", default)]
+    pub folder_descriptions: HashMap<String, String>,
+
+    /// Map of folder_name -> VRChat group the folder is filled from
+    #[serde(rename = "groupFolders", default)]
+    pub group_folders: HashMap<String, crate::definitions::GroupFolderLink>,
+
     /// Map of world_id -> is_photographed status
     #[serde(rename = "worldPhotographed", default)]
     pub world_photographed: HashMap<String, bool>,
@@ -28,6 +45,50 @@ pub struct CustomData {
     #[serde(rename = "worldShared", default)]
     pub world_shared: HashMap<String, bool>,
 
+    /// Map of world_id -> is_event_critical status
+    #[serde(rename = "worldEventCritical", default)]
+    pub world_event_critical: HashMap<String, bool>,
+
+    /// Map of world_id -> rating from 1 to 5
+    #[serde(rename = "worldRatings", default)]
+    pub world_ratings: HashMap<String, u8>,
+
+    /// Map of world_id -> number of instances launched from the app
+    #[serde(rename = "worldLaunchCounts", default)]
+    pub world_launch_counts: HashMap<String, u32>,
+
+    /// Map of world_id -> file name of the local image shown instead of the thumbnail
+    #[serde(rename = "worldCovers", default)]
+    pub world_covers: HashMap<String, String>,
+
+    /// IDs of worlds added automatically which the user has not triaged yet, oldest first
+    #[serde(rename = "inboxWorlds", default, skip_serializing_if = "Vec::is_empty")]
+    pub inbox_worlds: Vec<String>,
+
+    /// Map of world_id -> fields the user corrected, with the value kept over VRChat's
+    #[serde(rename = "worldFieldLocks", default, skip_serializing_if = "HashMap::is_empty")]
+    pub world_field_locks: HashMap<String, BTreeMap<WorldField, String>>,
+
+    /// Map of author_id -> previous author names, oldest first
+    #[serde(rename = "authorAliases", default)]
+    pub author_aliases: HashMap<String, Vec<String>>,
+
+    /// Map of author tag -> tag it is shown as, both lowercase and without "author_tag_"
+    #[serde(rename = "tagAliases", default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub tag_aliases: BTreeMap<String, String>,
+
+    /// Author tags which are not shown, lowercase and without "author_tag_"
+    #[serde(rename = "hiddenTags", default, skip_serializing_if = "Vec::is_empty")]
+    pub hidden_tags: Vec<String>,
+
+    /// The world an instance was last created for, offered again from the tray
+    #[serde(rename = "lastInstanceWorld", default, skip_serializing_if = "Option::is_none")]
+    pub last_instance_world: Option<String>,
+
+    /// The newest version whose release notes the user has seen
+    #[serde(rename = "changelogSeenVersion", default, skip_serializing_if = "Option::is_none")]
+    pub changelog_seen_version: Option<String>,
+
     /// Extended preferences
     #[serde(default)]
     pub preferences: CustomPreferences,
@@ -50,16 +111,107 @@ pub struct CustomPreferences {
     /// Dont show remove from folder preference
     #[serde(rename = "dontShowRemoveFromFolder", default, skip_serializing_if = "Option::is_none")]
     pub dont_show_remove_from_folder: Option<crate::definitions::FolderRemovalPreference>,
+
+    /// Platform filter for searches which don't specify one, None searches every platform
+    #[serde(rename = "defaultSearchPlatform", default, skip_serializing_if = "Option::is_none")]
+    pub default_search_platform: Option<crate::definitions::SearchPlatform>,
+
+    /// Automatic import of recently visited worlds, disabled when unset
+    #[serde(rename = "recentlyVisitedImport", default, skip_serializing_if = "Option::is_none")]
+    pub recently_visited_import: Option<crate::definitions::RecentlyVisitedImport>,
+
+    /// Global budget of VRChat API requests per minute
+    #[serde(rename = "apiRequestsPerMinute", default, skip_serializing_if = "Option::is_none")]
+    pub api_requests_per_minute: Option<u32>,
+
+    /// Number of VRChat API requests sent at once
+    #[serde(rename = "apiConcurrentRequests", default, skip_serializing_if = "Option::is_none")]
+    pub api_concurrent_requests: Option<u32>,
+
+    /// Watching the clipboard for VRChat world URLs, disabled when unset
+    #[serde(rename = "clipboardWatch", default, skip_serializing_if = "Option::is_none")]
+    pub clipboard_watch: Option<crate::definitions::ClipboardWatch>,
+
+    /// System tray behaviour
+    #[serde(rename = "tray", default, skip_serializing_if = "Option::is_none")]
+    pub tray: Option<crate::definitions::TraySettings>,
+
+    /// World launched by the quick launch shortcut
+    #[serde(rename = "pinnedWorld", default, skip_serializing_if = "Option::is_none")]
+    pub pinned_world: Option<String>,
+
+    /// Global shortcut for launching the pinned world, the default binding when unset
+    #[serde(rename = "quickLaunchShortcut", default, skip_serializing_if = "Option::is_none")]
+    pub quick_launch_shortcut: Option<String>,
+
+    /// Webhook notifications are posted to, disabled when unset
+    #[serde(rename = "webhook", default, skip_serializing_if = "Option::is_none")]
+    pub webhook: Option<crate::definitions::WebhookSettings>,
+
+    /// Localhost server for external tools, disabled when unset
+    #[serde(rename = "localApi", default, skip_serializing_if = "Option::is_none")]
+    pub local_api: Option<crate::definitions::LocalApiSettings>,
+
+    /// Log levels of modules, e.g. "app_lib::api" to Debug, on top of the default Info
+    #[serde(rename = "logLevels", default, skip_serializing_if = "Option::is_none")]
+    pub log_levels: Option<std::collections::BTreeMap<String, crate::logging::LogLevel>>,
+
+    /// Whether local usage metrics are collected, off when unset
+    #[serde(rename = "usageMetrics", default, skip_serializing_if = "Option::is_none")]
+    pub usage_metrics: Option<bool>,
+
+    /// Skipped and pinned versions of the app, every update is offered when unset
+    #[serde(rename = "updatePolicy", default, skip_serializing_if = "Option::is_none")]
+    pub update_policy: Option<crate::updater::update_handler::UpdatePolicy>,
+
+    /// Whether numbers in names are compared by value when sorting, off when unset
+    #[serde(rename = "naturalSort", default, skip_serializing_if = "Option::is_none")]
+    pub natural_sort: Option<bool>,
+
+    /// Whether favorites which are not public are kept as placeholders, off when unset
+    #[serde(rename = "keepNonPublicFavorites", default, skip_serializing_if = "Option::is_none")]
+    pub keep_non_public_favorites: Option<bool>,
+
+    /// Directory VRChat saves photos to, Pictures/VRChat when unset
+    #[serde(rename = "photoDirectory", default, skip_serializing_if = "Option::is_none")]
+    pub photo_directory: Option<String>,
+
+    /// Salted argon2 hash of the PIN which leaves guest mode, as a PHC string, guest mode cannot be entered when unset
+    #[serde(rename = "guestModePin", default, skip_serializing_if = "Option::is_none")]
+    pub guest_mode_pin: Option<String>,
+
+    /// Whether the app is in read-only guest mode, kept so restarting the app does not leave it
+    #[serde(rename = "guestMode", default, skip_serializing_if = "Option::is_none")]
+    pub guest_mode: Option<bool>,
+
+    /// How worlds.json is stored, pretty printed JSON when unset
+    #[serde(rename = "worldsStorage", default, skip_serializing_if = "Option::is_none")]
+    pub worlds_storage: Option<crate::definitions::WorldsStorageFormat>,
 }
 
 impl CustomData {
     pub fn new() -> Self {
         Self {
             version: 1,
+            schema_versions: HashMap::new(),
             world_favorites: HashMap::new(),
             folder_colors: HashMap::new(),
+            folder_icons: HashMap::new(),
+            folder_descriptions: HashMap::new(),
+            group_folders: HashMap::new(),
             world_photographed: HashMap::new(),
             world_shared: HashMap::new(),
+            world_event_critical: HashMap::new(),
+            world_ratings: HashMap::new(),
+            world_launch_counts: HashMap::new(),
+            world_covers: HashMap::new(),
+            inbox_worlds: vec![],
+            world_field_locks: HashMap::new(),
+            author_aliases: HashMap::new(),
+            tag_aliases: BTreeMap::new(),
+            hidden_tags: vec![],
+            last_instance_world: None,
+            changelog_seen_version: None,
             preferences: CustomPreferences::default(),
         }
     }
@@ -109,6 +261,137 @@ impl CustomData {
         self.world_favorites.get(world_id).copied().unwrap_or(false)
     }
 
+    /// Sets the event critical status for a world
+    pub fn set_world_event_critical(&mut self, world_id: &str, is_event_critical: bool) {
+        if is_event_critical {
+            self.world_event_critical.insert(world_id.to_string(), true);
+        } else {
+            self.world_event_critical.remove(world_id);
+        }
+    }
+
+    /// Gets the event critical status for a world
+    pub fn is_world_event_critical(&self, world_id: &str) -> bool {
+        self.world_event_critical
+            .get(world_id)
+            .copied()
+            .unwrap_or(false)
+    }
+
+    /// Sets the rating for a world, None removes it
+    pub fn set_world_rating(&mut self, world_id: &str, rating: Option<u8>) {
+        match rating {
+            Some(r) => {
+                self.world_ratings.insert(world_id.to_string(), r);
+            }
+            None => {
+                self.world_ratings.remove(world_id);
+            }
+        }
+    }
+
+    /// Gets the rating for a world
+    pub fn get_world_rating(&self, world_id: &str) -> Option<u8> {
+        self.world_ratings.get(world_id).copied()
+    }
+
+    /// Counts an instance launched for a world, returning the new count
+    pub fn record_world_launch(&mut self, world_id: &str) -> u32 {
+        let count = self
+            .world_launch_counts
+            .entry(world_id.to_string())
+            .or_default();
+        *count += 1;
+        *count
+    }
+
+    /// Gets the number of instances launched for a world
+    pub fn get_world_launch_count(&self, world_id: &str) -> u32 {
+        self.world_launch_counts.get(world_id).copied().unwrap_or(0)
+    }
+
+    /// Sets the cover image for a world, None removes it
+    pub fn set_world_cover(&mut self, world_id: &str, file_name: Option<&str>) {
+        match file_name {
+            Some(f) => {
+                self.world_covers
+                    .insert(world_id.to_string(), f.to_string());
+            }
+            None => {
+                self.world_covers.remove(world_id);
+            }
+        }
+    }
+
+    /// Gets the file name of the cover image for a world
+    pub fn get_world_cover(&self, world_id: &str) -> Option<&String> {
+        self.world_covers.get(world_id)
+    }
+
+    /// Puts a world into the inbox or takes it out
+    pub fn set_world_in_inbox(&mut self, world_id: &str, in_inbox: bool) {
+        self.inbox_worlds.retain(|id| id != world_id);
+        if in_inbox {
+            self.inbox_worlds.push(world_id.to_string());
+        }
+    }
+
+    /// Gets whether a world is in the inbox
+    pub fn is_world_in_inbox(&self, world_id: &str) -> bool {
+        self.inbox_worlds.iter().any(|id| id == world_id)
+    }
+
+    /// Locks a field of a world to a value, None unlocks it
+    pub fn set_world_field_lock(&mut self, world_id: &str, field: WorldField, value: Option<&str>) {
+        match value {
+            Some(v) => {
+                self.world_field_locks
+                    .entry(world_id.to_string())
+                    .or_default()
+                    .insert(field, v.to_string());
+            }
+            None => {
+                if let Some(locks) = self.world_field_locks.get_mut(world_id) {
+                    locks.remove(&field);
+                    if locks.is_empty() {
+                        self.world_field_locks.remove(world_id);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Gets the locked fields of a world
+    pub fn get_world_field_locks(&self, world_id: &str) -> Option<&BTreeMap<WorldField, String>> {
+        self.world_field_locks.get(world_id)
+    }
+
+    /// Sets any of the status flags for a world
+    pub fn set_world_flag(&mut self, world_id: &str, flag: WorldFlag, value: bool) {
+        match flag {
+            WorldFlag::Photographed => self.set_world_photographed(world_id, value),
+            WorldFlag::Shared => self.set_world_shared(world_id, value),
+            WorldFlag::Favorite => self.set_world_favorite(world_id, value),
+            WorldFlag::EventCritical => self.set_world_event_critical(world_id, value),
+        }
+    }
+
+    /// Records a previous name of an author
+    pub fn add_author_alias(&mut self, author_id: &str, old_name: &str) {
+        let aliases = self.author_aliases.entry(author_id.to_string()).or_default();
+        if !aliases.iter().any(|a| a == old_name) {
+            aliases.push(old_name.to_string());
+        }
+    }
+
+    /// Finds the author whose previous names include the given name
+    pub fn find_author_by_alias(&self, name: &str) -> Option<&String> {
+        self.author_aliases
+            .iter()
+            .find(|(_, aliases)| aliases.iter().any(|a| a == name))
+            .map(|(author_id, _)| author_id)
+    }
+
     /// Sets the color for a folder
     pub fn set_folder_color(&mut self, folder_name: &str, color: Option<&str>) {
         match color {
@@ -127,15 +410,63 @@ impl CustomData {
         self.folder_colors.get(folder_name)
     }
 
-    /// Renames a folder in the color map (used when folder is renamed)
+    /// Sets the icon for a folder
+    pub fn set_folder_icon(&mut self, folder_name: &str, icon: Option<&str>) {
+        match icon {
+            Some(i) => {
+                self.folder_icons
+                    .insert(folder_name.to_string(), i.to_string());
+            }
+            None => {
+                self.folder_icons.remove(folder_name);
+            }
+        }
+    }
+
+    /// Gets the icon for a folder
+    pub fn get_folder_icon(&self, folder_name: &str) -> Option<&String> {
+        self.folder_icons.get(folder_name)
+    }
+
+    /// Sets the description for a folder
+    pub fn set_folder_description(&mut self, folder_name: &str, description: Option<&str>) {
+        match description {
+            Some(d) => {
+                self.folder_descriptions
+                    .insert(folder_name.to_string(), d.to_string());
+            }
+            None => {
+                self.folder_descriptions.remove(folder_name);
+            }
+        }
+    }
+
+    /// Gets the description for a folder
+    pub fn get_folder_description(&self, folder_name: &str) -> Option<&String> {
+        self.folder_descriptions.get(folder_name)
+    }
+
+    /// Renames a folder in the per-folder maps (used when folder is renamed)
     pub fn rename_folder(&mut self, old_name: &str, new_name: &str) {
-        if let Some(color) = self.folder_colors.remove(old_name) {
-            self.folder_colors.insert(new_name.to_string(), color);
+        for map in [
+            &mut self.folder_colors,
+            &mut self.folder_icons,
+            &mut self.folder_descriptions,
+        ] {
+            if let Some(value) = map.remove(old_name) {
+                map.insert(new_name.to_string(), value);
+            }
+        }
+        if let Some(link) = self.group_folders.remove(old_name) {
+            self.group_folders.insert(new_name.to_string(), link);
         }
     }
 
-    /// Removes a folder from the color map (used when folder is deleted)
+    /// Removes a folder from the per-folder maps (used when folder is deleted)
     pub fn remove_folder(&mut self, folder_name: &str) {
         self.folder_colors.remove(folder_name);
+        self.folder_icons.remove(folder_name);
+        self.folder_descriptions.remove(folder_name);
+        self.group_folders.remove(folder_name);
     }
 }
@@ -1,9 +1,14 @@
-use chrono::{DateTime, SecondsFormat, Utc};
+use chrono::{DateTime, Duration, SecondsFormat, Utc};
 use reqwest::cookie::Jar;
 use serde::{Deserialize, Serialize};
 use specta::Type;
+use std::collections::{BTreeMap, HashMap};
+use uuid::Uuid;
 
+use crate::api::auth::VRChatAuthPhase;
 use crate::api::instance::InstanceRegion;
+use crate::api::world::ReleaseStatus;
+use crate::migration::DataFile;
 use crate::updater::update_handler::UpdateChannel;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -33,9 +38,94 @@ pub struct WorldApiData {
     pub visits: Option<i32>,
     pub favorites: i32,
     pub platform: Vec<String>,
+    /// Download size and performance per platform, where the API provided them
+    /// Missing in worlds stored before these were fetched
+    #[serde(rename = "packages", default, skip_serializing_if = "Vec::is_empty")]
+    pub packages: Vec<WorldPackageInfo>,
+    /// Private for placeholders of favorites the API would not show, Public otherwise
+    #[serde(default, skip_serializing_if = "WorldAvailability::is_public")]
+    pub availability: WorldAvailability,
+}
+
+/// Whether the API shows a world to the user
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub enum WorldAvailability {
+    #[default]
+    Public,
+    /// Private or hidden; the world is only known by its ID
+    Private,
+}
+
+impl WorldAvailability {
+    pub fn is_public(&self) -> bool {
+        *self == WorldAvailability::Public
+    }
+}
+
+/// The build of a world for one platform
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, Type)]
+pub struct WorldPackageInfo {
+    /// "standalonewindows" or "android"
+    pub platform: String,
+    /// Compressed size in bytes, which is what is downloaded
+    #[serde(rename = "fileSize", default, skip_serializing_if = "Option::is_none")]
+    pub file_size: Option<u64>,
+    #[serde(
+        rename = "uncompressedSize",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub uncompressed_size: Option<u64>,
+    #[serde(
+        rename = "performanceRating",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub performance_rating: Option<String>,
 }
 
 impl WorldApiData {
+    /// A stand-in for a world the API would not show, e.g. a favorite which was made
+    /// private, so the library keeps a record of it
+    /// Its last update is the epoch, so fetching the world later replaces it
+    pub fn placeholder(world_id: String) -> Self {
+        Self {
+            image_url: "".to_string(),
+            world_name: "???".to_string(),
+            world_id,
+            author_name: "".to_string(),
+            author_id: "".to_string(),
+            capacity: 0,
+            recommended_capacity: None,
+            tags: vec![],
+            publication_date: None,
+            last_update: DateTime::UNIX_EPOCH,
+            description: "".to_string(),
+            visits: None,
+            favorites: 0,
+            platform: vec![],
+            packages: vec![],
+            availability: WorldAvailability::Private,
+        }
+    }
+
+    /// The download size of the world's build for a platform, if known
+    ///
+    /// # Arguments
+    /// * `platform` - "standalonewindows" or "android"
+    pub fn download_size(&self, platform: &str) -> Option<u64> {
+        self.packages
+            .iter()
+            .find(|p| p.platform == platform)
+            .and_then(|p| p.file_size)
+    }
+
+    /// Whether the download size of any platform is known
+    pub fn has_package_sizes(&self) -> bool {
+        self.packages.iter().any(|p| p.file_size.is_some())
+    }
+
     pub fn to_world_details(&self) -> WorldDetails {
         WorldDetails {
             world_id: self.world_id.clone(),
@@ -60,6 +150,7 @@ impl WorldApiData {
             capacity: self.capacity,
             recommended_capacity: self.recommended_capacity,
             publication_date: self.publication_date,
+            packages: self.packages.clone(),
         }
     }
 }
@@ -81,6 +172,21 @@ pub struct WorldUserData {
     /// Favorite status - stored in custom_data.json for backward compatibility
     #[serde(skip)]
     pub is_favorite: bool,
+    /// Event critical status - stored in custom_data.json for backward compatibility
+    #[serde(skip)]
+    pub is_event_critical: bool,
+    /// Rating from 1 to 5 - stored in custom_data.json for backward compatibility
+    #[serde(skip)]
+    pub rating: Option<u8>,
+    /// Instances launched from the app - stored in custom_data.json for backward compatibility
+    #[serde(skip)]
+    pub launch_count: u32,
+    /// File name of the local image shown instead of the thumbnail - stored in custom_data.json
+    #[serde(skip)]
+    pub cover_image: Option<String>,
+    /// Added automatically and not triaged yet - stored in custom_data.json
+    #[serde(skip)]
+    pub in_inbox: bool,
 }
 
 impl WorldUserData {
@@ -89,6 +195,24 @@ impl WorldUserData {
         let duration = now.signed_duration_since(self.last_checked);
         duration.num_hours() >= 4
     }
+
+    pub fn flag(&self, flag: WorldFlag) -> bool {
+        match flag {
+            WorldFlag::Photographed => self.is_photographed,
+            WorldFlag::Shared => self.is_shared,
+            WorldFlag::Favorite => self.is_favorite,
+            WorldFlag::EventCritical => self.is_event_critical,
+        }
+    }
+
+    pub fn set_flag(&mut self, flag: WorldFlag, value: bool) {
+        match flag {
+            WorldFlag::Photographed => self.is_photographed = value,
+            WorldFlag::Shared => self.is_shared = value,
+            WorldFlag::Favorite => self.is_favorite = value,
+            WorldFlag::EventCritical => self.is_event_critical = value,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -112,6 +236,11 @@ impl WorldModel {
                 is_photographed: false,
                 is_shared: false,
                 is_favorite: false,
+                is_event_critical: false,
+                rating: None,
+                launch_count: 0,
+                cover_image: None,
+                in_inbox: false,
             },
         }
     }
@@ -120,8 +249,15 @@ impl WorldModel {
         WorldDisplayData {
             world_id: self.api_data.world_id.clone(),
             name: self.api_data.world_name.clone(),
-            thumbnail_url: self.api_data.image_url.clone(),
+            thumbnail_url: match &self.user_data.cover_image {
+                Some(cover) => crate::services::FileService::get_cover_dir()
+                    .join(cover)
+                    .to_string_lossy()
+                    .to_string(),
+                None => self.api_data.image_url.clone(),
+            },
             author_name: self.api_data.author_name.clone(),
+            author_id: self.api_data.author_id.clone(),
             favorites: self.api_data.favorites,
             last_updated: self.api_data.last_update.format("%Y-%m-%d").to_string(),
             visits: self.api_data.visits.unwrap_or(0),
@@ -142,11 +278,18 @@ impl WorldModel {
                 Platform::PC
             },
             folders: self.user_data.folders.clone(),
-            tags: self.api_data.tags.clone(),
+            tags: crate::services::TagService::apply(&self.api_data.tags),
             capacity: self.api_data.capacity,
             is_photographed: self.user_data.is_photographed,
             is_shared: self.user_data.is_shared,
             is_favorite: self.user_data.is_favorite,
+            is_event_critical: self.user_data.is_event_critical,
+            has_memo: !self.user_data.memo.is_empty(),
+            rating: self.user_data.rating,
+            local_visit_count: self.user_data.launch_count,
+            pc_download_size: self.api_data.download_size("standalonewindows"),
+            quest_download_size: self.api_data.download_size("android"),
+            availability: self.api_data.availability,
         }
     }
 }
@@ -161,6 +304,106 @@ pub enum Platform {
     CrossPlatform,
 }
 
+/// The grid's filters, for applying an operation to every matching world
+/// Empty fields and None don't filter
+#[derive(Debug, Clone, Default, Deserialize, Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct WorldFilter {
+    /// "All Worlds", "Unclassified Worlds", "Hidden Worlds" or a folder name
+    pub source: String,
+    /// Matched against the world and author names, ignoring case
+    pub search_query: String,
+    pub author: String,
+    /// Author tags without the "author_tag_" prefix, every one must be set on the world
+    pub tags: Vec<String>,
+    /// The world must be in every one of these folders
+    pub folders: Vec<String>,
+    pub memo_text: String,
+    pub photographed: Option<bool>,
+    pub shared: Option<bool>,
+    pub favorite: Option<bool>,
+    /// Only worlds which are neither photographed nor shared
+    pub unprocessed: bool,
+    pub platform: Option<SearchPlatform>,
+}
+
+/// Links for sharing an instance, e.g. in Discord
+#[derive(Debug, Clone, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct InstanceLinks {
+    /// The vrchat.com page for joining the instance
+    pub web_url: String,
+    /// Opens the instance in the client
+    pub launch_url: String,
+    /// The vrch.at link, when the short name could be resolved
+    pub short_url: Option<String>,
+}
+
+/// What a QR code links to
+#[derive(Debug, Clone, Deserialize, Type)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum QrPayload {
+    /// The world's vrchat.com page
+    #[serde(rename_all = "camelCase")]
+    World { world_id: String },
+    /// The vrchat.com page for joining the instance
+    #[serde(rename_all = "camelCase")]
+    Instance {
+        world_id: String,
+        instance_id: String,
+    },
+    /// A deep link which imports the shared folder
+    #[serde(rename_all = "camelCase")]
+    Share { share_id: String },
+}
+
+/// A generated QR code
+#[derive(Debug, Clone, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct QrCodeImage {
+    /// The text the code contains
+    pub content: String,
+    pub png_base64: String,
+    /// Where the PNG was saved, when it was saved to the exports folder
+    pub path: Option<String>,
+}
+
+/// A page of remote world search results
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct SearchResultPage {
+    pub worlds: Vec<WorldDisplayData>,
+    pub page: usize,
+    #[serde(rename = "pageSize")]
+    pub page_size: usize,
+    /// Whether the next page may have results
+    /// Based on the number of worlds the API returned, before any filtering
+    #[serde(rename = "hasMore")]
+    pub has_more: bool,
+}
+
+/// Platform filter for remote world searches
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+pub enum SearchPlatform {
+    #[serde(rename = "pc")]
+    Pc,
+    #[serde(rename = "android")]
+    Android,
+    /// Only worlds available on both PC and Android
+    #[serde(rename = "both")]
+    Both,
+}
+
+impl SearchPlatform {
+    /// The platform to send to the search API
+    /// The API filters by a single platform, so `Both` is narrowed down after the search
+    pub fn query_platform(&self) -> &'static str {
+        match self {
+            SearchPlatform::Pc => "standalonewindows",
+            SearchPlatform::Android | SearchPlatform::Both => "android",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
 pub struct WorldDisplayData {
     #[serde(rename = "worldId")]
@@ -170,6 +413,8 @@ pub struct WorldDisplayData {
     pub thumbnail_url: String,
     #[serde(rename = "authorName")]
     pub author_name: String,
+    #[serde(rename = "authorId")]
+    pub author_id: String,
     pub favorites: i32,
     #[serde(rename = "lastUpdated")]
     pub last_updated: String,
@@ -186,6 +431,65 @@ pub struct WorldDisplayData {
     pub is_shared: bool,
     #[serde(rename = "isFavorite")]
     pub is_favorite: bool,
+    #[serde(rename = "isEventCritical")]
+    pub is_event_critical: bool,
+    #[serde(rename = "hasMemo", default)]
+    pub has_memo: bool,
+    #[serde(default)]
+    pub rating: Option<u8>,
+    /// Instances launched from the app
+    #[serde(rename = "localVisitCount", default)]
+    pub local_visit_count: u32,
+    /// In bytes, if known
+    #[serde(rename = "pcDownloadSize", default)]
+    pub pc_download_size: Option<u64>,
+    /// In bytes, if known
+    #[serde(rename = "questDownloadSize", default)]
+    pub quest_download_size: Option<u64>,
+    #[serde(default)]
+    pub availability: WorldAvailability,
+}
+
+/// The fields a world card needs, for the grids of large folders
+/// Tags, folders and statistics are left out; the full data of the cards shown is fetched
+/// with `get_worlds_by_id`, and the details with `get_world`
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct WorldSummary {
+    #[serde(rename = "worldId")]
+    pub world_id: String,
+    pub name: String,
+    #[serde(rename = "thumbnailUrl")]
+    pub thumbnail_url: String,
+    #[serde(rename = "authorName")]
+    pub author_name: String,
+    pub platform: Platform,
+    #[serde(rename = "isPhotographed")]
+    pub is_photographed: bool,
+    #[serde(rename = "isShared")]
+    pub is_shared: bool,
+    #[serde(rename = "isFavorite")]
+    pub is_favorite: bool,
+    #[serde(rename = "isEventCritical")]
+    pub is_event_critical: bool,
+    #[serde(rename = "hasMemo")]
+    pub has_memo: bool,
+}
+
+impl From<WorldDisplayData> for WorldSummary {
+    fn from(world: WorldDisplayData) -> Self {
+        WorldSummary {
+            world_id: world.world_id,
+            name: world.name,
+            thumbnail_url: world.thumbnail_url,
+            author_name: world.author_name,
+            platform: world.platform,
+            is_photographed: world.is_photographed,
+            is_shared: world.is_shared,
+            is_favorite: world.is_favorite,
+            is_event_critical: world.is_event_critical,
+            has_memo: world.has_memo,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
@@ -211,6 +515,7 @@ pub struct WorldDetails {
     pub recommended_capacity: Option<i32>,
     #[serde(rename = "publicationDate")]
     pub publication_date: Option<DateTime<Utc>>,
+    pub packages: Vec<WorldPackageInfo>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -225,6 +530,22 @@ pub struct FolderModel {
     /// Optional folder color (HEX format like "#a855f7") - stored in custom_data.json for backward compatibility
     #[serde(skip)]
     pub color: Option<String>,
+    /// Optional emoji or icon identifier - stored in custom_data.json like the color
+    #[serde(skip)]
+    pub icon: Option<String>,
+    /// Optional free-text description - stored in custom_data.json like the color
+    #[serde(skip)]
+    pub description: Option<String>,
+    /// Sort order of this folder, overriding the global one when set
+    #[serde(rename = "sort", default, skip_serializing_if = "Option::is_none")]
+    pub sort: Option<FolderSort>,
+    /// Archived folders are kept, but hidden from the folder list and background refreshes
+    #[serde(
+        rename = "archived",
+        default,
+        skip_serializing_if = "std::ops::Not::not"
+    )]
+    pub archived: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -242,10 +563,21 @@ impl FolderModel {
             world_ids: vec![],
             share: None,
             color: None,
+            icon: None,
+            description: None,
+            sort: None,
+            archived: false,
         }
     }
 }
 
+/// A sort order stored on a folder, see `SortingService` for the fields
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Type)]
+pub struct FolderSort {
+    pub field: String,
+    pub direction: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
 pub enum CardSize {
     Compact,  // Small preview
@@ -316,8 +648,86 @@ impl Default for VisibleButtons {
     }
 }
 
+/// Settings for automatically importing recently visited worlds into their own folder
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+pub struct RecentlyVisitedImport {
+    pub enabled: bool,
+    /// Maximum number of worlds kept in the folder, the oldest are removed first
+    pub cap: u32,
+}
+
+impl Default for RecentlyVisitedImport {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            cap: 50,
+        }
+    }
+}
+
+/// What the first-run import brings into the library
+#[derive(Debug, Clone, Default, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct InitialImportOptions {
+    /// The old VRC Worlds Manager's worlds and folders files, both or neither
+    pub old_worlds_path: Option<String>,
+    pub old_folders_path: Option<String>,
+    pub import_favorites: bool,
+    /// Put the imported favorites in a folder per favorite group
+    #[serde(default)]
+    pub create_group_folders: bool,
+    pub import_recently_visited: bool,
+}
+
+/// Settings for running from the system tray
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, specta::Type)]
+pub struct TraySettings {
+    /// Hide the window to the tray on startup
+    #[serde(rename = "startMinimized")]
+    pub start_minimized: bool,
+    /// Hide the window to the tray when it is closed, so background jobs keep running
+    #[serde(rename = "closeToTray")]
+    pub close_to_tray: bool,
+}
+
+/// What the clipboard watcher does with a copied VRChat world URL
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub enum ClipboardWatchMode {
+    /// Emit an event offering to add the world
+    #[default]
+    Offer,
+    /// Add the world to the library and the inbox right away
+    AutoAdd,
+}
+
+/// How worlds.json is stored, the largest file of the library
+/// Every format is read regardless of the setting, so switching takes effect on the next save
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub enum WorldsStorageFormat {
+    /// Indented JSON, readable by every version of the app
+    #[default]
+    Pretty,
+    /// JSON without whitespace, readable by every version of the app
+    Compact,
+    /// Gzip compressed JSON in worlds.json.gz, only readable by versions of the app which
+    /// support it
+    Gzip,
+}
+
+/// Settings for watching the clipboard for copied VRChat world URLs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, specta::Type)]
+pub struct ClipboardWatch {
+    pub enabled: bool,
+    pub mode: ClipboardWatchMode,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PreferenceModel {
+    /// Files are upgraded before they are loaded, so this is always the current version
+    #[serde(rename = "schemaVersion", default = "default_schema_version")]
+    pub schema_version: u32,
     #[serde(rename = "firstTime")]
     pub first_time: bool,
     pub theme: String,
@@ -345,8 +755,18 @@ pub struct PreferenceModel {
     /// Default instance type - stored in custom_data.json for backward compatibility
     #[serde(skip)]
     pub default_instance_type: DefaultInstanceType,
+    /// Platform filter for searches which don't specify one - stored in custom_data.json
+    #[serde(skip)]
+    pub default_search_platform: Option<SearchPlatform>,
     #[serde(rename = "visibleButtons", default = "default_visible_buttons", skip)]
     pub visible_buttons: VisibleButtons,
+    /// How worlds.json is stored - stored in custom_data.json
+    #[serde(skip)]
+    pub worlds_storage: WorldsStorageFormat,
+}
+
+fn default_schema_version() -> u32 {
+    DataFile::Preferences.current_version()
 }
 
 fn default_visible_buttons() -> VisibleButtons {
@@ -376,6 +796,7 @@ fn default_sort_direction() -> String {
 impl PreferenceModel {
     pub fn new() -> Self {
         Self {
+            schema_version: DataFile::Preferences.current_version(),
             first_time: true,
             theme: "light".to_string(),
             language: "en".to_string(),
@@ -387,11 +808,71 @@ impl PreferenceModel {
             sort_field: "dateAdded".to_string(),
             sort_direction: "desc".to_string(),
             default_instance_type: DefaultInstanceType::Public,
+            default_search_platform: None,
             visible_buttons: VisibleButtons::default(),
+            worlds_storage: WorldsStorageFormat::default(),
         }
     }
 }
 
+/// Every preference at once, so the frontend can load them in one call
+/// Includes the ones kept in custom_data.json next to the `PreferenceModel` fields
+#[derive(Debug, Clone, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct AllPreferences {
+    pub theme: String,
+    pub language: String,
+    pub card_size: CardSize,
+    pub region: InstanceRegion,
+    pub filter_item_selector_starred: Option<FilterItemSelectorStarred>,
+    pub dont_show_remove_from_folder: FolderRemovalPreference,
+    pub update_channel: UpdateChannel,
+    pub sort_field: String,
+    pub sort_direction: String,
+    pub default_instance_type: DefaultInstanceType,
+    pub default_search_platform: Option<SearchPlatform>,
+    pub visible_buttons: VisibleButtons,
+    pub natural_sort: bool,
+    pub keep_non_public_favorites: bool,
+    pub recently_visited_import: RecentlyVisitedImport,
+    pub clipboard_watch: ClipboardWatch,
+    pub tray: TraySettings,
+    pub pinned_world: Option<String>,
+    pub quick_launch_shortcut: String,
+    pub photo_directory: Option<String>,
+    pub worlds_storage: WorldsStorageFormat,
+}
+
+/// A VRChat photo found by the photo scanner
+#[derive(Debug, Clone, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct WorldPhoto {
+    pub path: String,
+    /// The world the photo was taken in, from the photo's metadata
+    pub world_id: Option<String>,
+    pub taken_at: Option<DateTime<Utc>>,
+}
+
+/// Outcome of scanning the photo directory
+#[derive(Debug, Clone, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct PhotoScanSummary {
+    pub directory: String,
+    pub photos: u32,
+    /// Photos without a world ID, taken before VRChat wrote it into photos
+    pub without_world: u32,
+    /// Number of worlds with photos, whether saved or not
+    pub worlds: u32,
+    /// Saved worlds which were marked as photographed by this scan
+    pub newly_photographed: Vec<String>,
+}
+
+/// Emitted after any preference is changed, so every window shows the same settings
+#[derive(Debug, Clone, Serialize, Type, tauri_specta::Event)]
+pub struct PreferencesChanged {
+    pub preferences: AllPreferences,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct AuthCookies {
     #[serde(rename = "twoFactorAuth")]
@@ -400,17 +881,62 @@ pub struct AuthCookies {
     pub auth_token: Option<String>,
     #[serde(default)]
     pub version: u8, // 0 = plaintext, 1 = AES
+    /// When the twoFactorAuth cookie was first seen, None if unknown
+    #[serde(
+        rename = "twoFactorAuthIssuedAt",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub two_factor_auth_issued_at: Option<DateTime<Utc>>,
 }
 
+/// How long VRChat remembers a device after two-factor authentication
+pub const TWO_FACTOR_REMEMBER_DAYS: i64 = 30;
+
 impl AuthCookies {
     pub fn new() -> Self {
         Self {
             two_factor_auth: None,
             auth_token: None,
             version: 1,
+            two_factor_auth_issued_at: None,
         }
     }
 
+    /// Keeps the issue time of the twoFactorAuth cookie across saves
+    /// A cookie which was not saved before is taken to be issued now
+    ///
+    /// # Arguments
+    /// * `previous` - The cookies saved before, if any
+    /// * `now` - The current time
+    pub fn carry_two_factor_issued_at(
+        &mut self,
+        previous: Option<&AuthCookies>,
+        now: DateTime<Utc>,
+    ) {
+        if self.two_factor_auth.is_none() {
+            self.two_factor_auth_issued_at = None;
+            return;
+        }
+        if self.two_factor_auth_issued_at.is_some() {
+            return;
+        }
+        self.two_factor_auth_issued_at = match previous {
+            // Saved before this was tracked, so its age is unknown
+            Some(previous) if previous.two_factor_auth == self.two_factor_auth => {
+                previous.two_factor_auth_issued_at
+            }
+            _ => Some(now),
+        };
+    }
+
+    /// When two-factor authentication is expected to be asked for again, if known
+    pub fn two_factor_expires_at(&self) -> Option<DateTime<Utc>> {
+        self.two_factor_auth.as_ref()?;
+        self.two_factor_auth_issued_at
+            .map(|issued_at| issued_at + Duration::days(TWO_FACTOR_REMEMBER_DAYS))
+    }
+
     pub fn from_cookie_str(cookie_str: &str) -> Self {
         let mut auth_token = None;
         let mut two_factor_auth = None;
@@ -430,6 +956,7 @@ impl AuthCookies {
             auth_token,
             two_factor_auth,
             version: 1,
+            two_factor_auth_issued_at: None,
         }
     }
 }
@@ -453,6 +980,215 @@ impl Into<Jar> for AuthCookies {
     }
 }
 
+/// A world ID as it appears in a folder
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct FolderWorldRef {
+    #[serde(rename = "folderName")]
+    pub folder_name: String,
+    #[serde(rename = "worldId")]
+    pub world_id: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum TimestampField {
+    DateAdded,
+    LastChecked,
+    UpdatedAt,
+    PublicationDate,
+}
+
+/// A world timestamp that is in the future or predates VRChat
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct InvalidTimestamp {
+    #[serde(rename = "worldId")]
+    pub world_id: String,
+    pub field: TimestampField,
+    pub value: DateTime<Utc>,
+}
+
+/// Inconsistencies between the folders and worlds of the library, grouped by kind
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct IntegrityReport {
+    /// Folder entries whose world is not in the library
+    #[serde(rename = "orphanedIds")]
+    pub orphaned_ids: Vec<FolderWorldRef>,
+    /// Extra copies of a world within the same folder
+    #[serde(rename = "duplicateEntries")]
+    pub duplicate_entries: Vec<FolderWorldRef>,
+    /// Worlds listing a folder that does not exist
+    #[serde(rename = "missingFolders")]
+    pub missing_folders: Vec<FolderWorldRef>,
+    /// Hidden worlds that are still in a folder
+    #[serde(rename = "hiddenInFolders")]
+    pub hidden_in_folders: Vec<FolderWorldRef>,
+    #[serde(rename = "invalidTimestamps")]
+    pub invalid_timestamps: Vec<InvalidTimestamp>,
+}
+
+impl IntegrityReport {
+    pub fn is_clean(&self) -> bool {
+        self.orphaned_ids.is_empty()
+            && self.duplicate_entries.is_empty()
+            && self.missing_folders.is_empty()
+            && self.hidden_in_folders.is_empty()
+            && self.invalid_timestamps.is_empty()
+    }
+}
+
+/// Whether the library files are encrypted with a passphrase, and whether they are unlocked
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct LibraryEncryptionStatus {
+    pub enabled: bool,
+    pub locked: bool,
+}
+
+/// Whether the app is in read-only guest mode, and whether a PIN to leave it is set
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct GuestModeStatus {
+    pub enabled: bool,
+    pub pin_set: bool,
+}
+
+/// A VRChat session saved under a user-chosen name so it can be switched back to later
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SavedAccount {
+    pub name: String,
+    #[serde(rename = "displayName")]
+    pub display_name: String,
+    pub cookies: AuthCookies,
+}
+
+/// All saved accounts, along with the name of the one currently in use
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SavedAccounts {
+    #[serde(default)]
+    pub accounts: Vec<SavedAccount>,
+    #[serde(default)]
+    pub active: Option<String>,
+}
+
+/// A saved account as shown to the frontend, without its session cookies
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct AccountInfo {
+    pub name: String,
+    #[serde(rename = "displayName")]
+    pub display_name: String,
+    pub active: bool,
+}
+
+/// Where the library is mirrored to, so several PCs can share it
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum SyncTarget {
+    /// A folder kept in sync by a client such as Dropbox or Google Drive
+    Folder { path: String },
+    /// A collection on a WebDAV server
+    WebDav {
+        url: String,
+        username: String,
+        password: String,
+    },
+}
+
+impl SyncTarget {
+    /// The folder path or URL, safe to show as it holds no credentials
+    pub fn location(&self) -> String {
+        match self {
+            SyncTarget::Folder { path } => path.clone(),
+            SyncTarget::WebDav { url, .. } => url.clone(),
+        }
+    }
+}
+
+/// Per-device edit counters of a synced file, to tell whether one copy includes
+/// every edit of another or both were edited independently
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct VersionVector(pub BTreeMap<String, u64>);
+
+impl VersionVector {
+    /// Records one more edit made on `device_id`
+    pub fn bumped(&self, device_id: &str) -> Self {
+        let mut bumped = self.clone();
+        *bumped.0.entry(device_id.to_string()).or_default() += 1;
+        bumped
+    }
+
+    /// Combines the edits of both versions
+    pub fn merged(&self, other: &Self) -> Self {
+        let mut merged = self.clone();
+        for (device_id, count) in &other.0 {
+            let entry = merged.0.entry(device_id.clone()).or_default();
+            *entry = (*entry).max(*count);
+        }
+        merged
+    }
+
+    /// Whether every edit of `other` is also part of this version
+    pub fn includes(&self, other: &Self) -> bool {
+        other
+            .0
+            .iter()
+            .all(|(device_id, count)| self.0.get(device_id).copied().unwrap_or(0) >= *count)
+    }
+}
+
+/// Sync settings and the state of each file as of the last sync, stored in sync.json
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SyncConfig {
+    #[serde(rename = "deviceId")]
+    pub device_id: String,
+    pub target: Option<SyncTarget>,
+    #[serde(rename = "lastSynced")]
+    pub last_synced: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub versions: BTreeMap<String, VersionVector>,
+    /// Files edited on both sides, which wait for the user to pick a side
+    #[serde(default)]
+    pub conflicts: Vec<String>,
+}
+
+/// What the sync settings show
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct SyncStatus {
+    /// The sync folder or WebDAV URL, None while sync is not set up
+    pub location: Option<String>,
+    #[serde(rename = "lastSynced")]
+    pub last_synced: Option<DateTime<Utc>>,
+    pub conflicts: Vec<String>,
+}
+
+/// Which copy of a conflicting file to keep
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ConflictResolution {
+    KeepLocal,
+    KeepRemote,
+}
+
+/// Which side wins when both libraries of a merge edited the same memo or setting
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum MergePolicy {
+    /// The copy of the world checked most recently wins
+    PreferNewer,
+    PreferLocal,
+}
+
+/// What merging another library into this one changed
+#[derive(Debug, Clone, Default, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct LibraryMergeSummary {
+    pub worlds_added: usize,
+    pub worlds_updated: usize,
+    pub folders_added: usize,
+    pub folders_merged: usize,
+    pub memos_added: usize,
+    /// Memos and settings both libraries edited, decided by the merge policy
+    pub conflicts: usize,
+}
+
 pub struct InitState {
     pub success: bool,
     pub message: String,
@@ -477,9 +1213,845 @@ impl InitState {
     }
 }
 
-#[derive(Debug, Type, Serialize, Deserialize)]
+/// How far the user is through logging in
+#[derive(Debug, Clone, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthState {
+    pub phase: VRChatAuthPhase,
+    /// When VRChat is expected to ask for two-factor authentication again, None if unknown
+    pub two_factor_expires_at: Option<DateTime<Utc>>,
+}
+
+/// A change detected on an event critical world during refresh
+#[derive(Debug, Clone, PartialEq, Type, Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum CriticalWorldChange {
+    Capacity { old: i32, new: i32 },
+    ReleaseStatus { new: String },
+    Platform { old: Vec<String>, new: Vec<String> },
+}
+
+/// Emitted when the background session check finds the saved login no longer works,
+/// so the user can be asked to log in again
+#[derive(Debug, Clone, Serialize, Type, tauri_specta::Event)]
+pub struct SessionExpired {
+    pub message: String,
+}
+
+/// High-priority alert raised when an event critical world changes
+#[derive(Debug, Clone, Serialize, Type, tauri_specta::Event)]
+pub struct EventCriticalWorldChanged {
+    #[serde(rename = "worldId")]
+    pub world_id: String,
+    #[serde(rename = "worldName")]
+    pub world_name: String,
+    pub changes: Vec<CriticalWorldChange>,
+}
+
+/// Emitted when a refresh changed the data of saved worlds, with only those worlds
+/// Hidden worlds are left out, as they are not shown
+#[derive(Debug, Clone, Serialize, Type, tauri_specta::Event)]
+pub struct WorldUpdated {
+    pub worlds: Vec<WorldDisplayData>,
+    /// When the change was recorded, to pass to `get_worlds_changed_since` later
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Emitted when worlds were added to the library, with the added worlds
+/// Worlds already saved are reported by `WorldUpdated` instead
+#[derive(Debug, Clone, Serialize, Type, tauri_specta::Event)]
+pub struct WorldAdded {
+    pub worlds: Vec<WorldDisplayData>,
+}
+
+/// Emitted when a world was deleted from the library
+#[derive(Debug, Clone, Serialize, Type, tauri_specta::Event)]
+#[serde(rename_all = "camelCase")]
+pub struct WorldRemoved {
+    pub world_id: String,
+}
+
+/// Emitted when a flag was set on worlds, with only the worlds whose flag changed
+#[derive(Debug, Clone, Serialize, Type, tauri_specta::Event)]
+#[serde(rename_all = "camelCase")]
+pub struct WorldFlagChanged {
+    pub world_ids: Vec<String>,
+    pub flag: WorldFlag,
+    pub value: bool,
+}
+
+/// Emitted when a folder was renamed
+#[derive(Debug, Clone, Serialize, Type, tauri_specta::Event)]
+#[serde(rename_all = "camelCase")]
+pub struct FolderRenamed {
+    pub old_name: String,
+    pub new_name: String,
+    /// The folder was merged into an existing folder named `new_name`, and no longer exists
+    pub merged: bool,
+}
+
+/// How renaming a folder handles another folder already having the new name
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub enum FolderNameCollision {
+    /// Fail with a duplicate folder error
+    #[default]
+    Reject,
+    /// Use the next free name like creating a folder does, e.g. "Chill (1)"
+    Increment,
+    /// Move the worlds into the existing folder and delete the renamed one
+    Merge,
+}
+
+/// Emitted when the folders were reordered, with every folder name in the new order
+#[derive(Debug, Clone, Serialize, Type, tauri_specta::Event)]
+#[serde(rename_all = "camelCase")]
+pub struct FolderReordered {
+    pub folder_names: Vec<String>,
+}
+
+/// Emitted by the favorites import as each page of favorites is saved, with the worlds it added
+#[derive(Debug, Clone, Serialize, Type, tauri_specta::Event)]
+pub struct FavoritesImportBatch {
+    /// The import's task, None when it runs as part of the first-run import
+    #[serde(rename = "taskId")]
+    pub task_id: Option<Uuid>,
+    pub page: u32,
+    pub worlds: Vec<WorldDisplayData>,
+}
+
+/// Emitted once the favorites import has saved every page
+#[derive(Debug, Clone, Default, Serialize, Type, tauri_specta::Event)]
+#[serde(rename_all = "camelCase")]
+pub struct FavoritesImportFinished {
+    pub task_id: Option<Uuid>,
+    /// Favorites received, including the ones already in the library
+    pub worlds_received: u32,
+    pub worlds_added: u32,
+    pub worlds_updated: u32,
+    /// Favorites left out as they are not public
+    pub non_public_skipped: u32,
+    /// The public favorites received from each favorite group, by group name
+    pub groups: Vec<FavoriteGroupCount>,
+}
+
+/// How many favorites the import received from one VRChat favorite group
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct FavoriteGroupCount {
+    /// The group's name, e.g. worlds1
+    pub group: String,
+    pub worlds_received: u32,
+}
+
+/// The worlds changed since a point in time
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct WorldChanges {
+    pub worlds: Vec<WorldDisplayData>,
+    /// When the changes were collected, to pass to the next call
+    pub timestamp: DateTime<Utc>,
+    /// False if changes were not tracked back to the requested time, e.g. as the app was
+    /// restarted since, and every world should be fetched again
+    pub complete: bool,
+}
+
+/// Format of a folder exported as a world list
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Type, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum WorldListFormat {
+    /// A standalone page with its styles inlined
+    Html,
+    Markdown,
+}
+
+/// What the worlds of the grid are grouped into sections by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Type, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum WorldGroupBy {
+    Author,
+    Platform,
+    Folder,
+    FirstLetter,
+    AddedMonth,
+    Tag,
+}
+
+/// A section of the world grid
+/// A world is in every group of its folders or tags, and worlds without any are in a group
+/// with an empty key, which is always last
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct WorldGroup {
+    /// The author ID, platform, folder name, letter, "YYYY-MM" or tag the group is for
+    pub key: String,
+    /// The name to show for the group, the author's name when grouped by author
+    pub label: String,
+    pub count: usize,
+    pub worlds: Vec<WorldDisplayData>,
+}
+
+/// Emitted after the library was reloaded because its files were changed outside the app
+#[derive(Debug, Clone, Serialize, Type, tauri_specta::Event)]
+pub struct DataReloaded {
+    /// Names of the changed files
+    pub files: Vec<String>,
+}
+
+/// Emitted while the worlds are loaded in the background on startup
+/// Preferences and folders are loaded before the window opens, worlds are added once
+/// worlds.json has been parsed
+#[derive(Debug, Clone, Serialize, Type, tauri_specta::Event)]
+#[serde(rename_all = "camelCase")]
+pub struct InitProgress {
+    /// Worlds parsed so far
+    pub loaded: u32,
+    /// Worlds in the library, only known on the last event
+    pub total: Option<u32>,
+    /// Set on the last event, once every world is loaded and the library can be changed
+    pub done: bool,
+}
+
+/// Actions a `vrc-worlds-manager://` link can trigger
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub enum DeepLinkAction {
+    AddWorld,
+    ImportShare,
+    OpenFolder,
+}
+
+/// Emitted after the backend handled a deep link, for the frontend to show the result
+#[derive(Debug, Clone, Serialize, Type, tauri_specta::Event)]
+pub struct DeepLinkHandled {
+    pub url: String,
+    pub action: DeepLinkAction,
+    /// The added world
+    #[serde(rename = "worldId")]
+    pub world_id: Option<String>,
+    /// The imported or opened folder
+    #[serde(rename = "folderName")]
+    pub folder_name: Option<String>,
+    /// True if the world was already in the library, so nothing was fetched
+    #[serde(rename = "alreadyAdded")]
+    pub already_added: bool,
+    /// Set if the action failed
+    pub error: Option<String>,
+}
+
+/// Emitted when a VRChat world URL was copied to the clipboard
+#[derive(Debug, Clone, Serialize, Type, tauri_specta::Event)]
+pub struct ClipboardWorldDetected {
+    #[serde(rename = "worldId")]
+    pub world_id: String,
+    /// True if the world was put into the inbox, false if the frontend should offer it
+    pub added: bool,
+}
+
+/// A per-world status flag
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Type, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum WorldFlag {
+    Photographed,
+    Shared,
+    Favorite,
+    EventCritical,
+}
+
+/// Outcome of setting a flag on several worlds at once
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct WorldFlagSummary {
+    /// Worlds whose flag changed
+    pub changed: Vec<String>,
+    /// Number of worlds which already had the flag set to the value
+    pub unchanged: u32,
+    /// Worlds which are not in the library
+    pub not_found: Vec<String>,
+}
+
+/// Folders a world was added to and removed from in one change of its folders
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct FolderMembershipChange {
+    pub added_to: Vec<String>,
+    pub removed_from: Vec<String>,
+}
+
+/// What `FolderManager::rename_folder` did with the folder
+#[derive(Debug, Clone, PartialEq)]
+pub enum FolderRename {
+    /// The folder has a new name, which differs from the requested one if it was incremented
+    Renamed { folder_name: String },
+    /// Another folder had the name, and the folder was merged into it and removed
+    Merged {
+        folder_name: String,
+        /// Every world of the merged folder
+        world_ids: Vec<String>,
+        /// The worlds which were not in the other folder yet
+        added: Vec<String>,
+    },
+}
+
+impl FolderRename {
+    /// The name the folder's worlds are in now
+    pub fn folder_name(&self) -> &str {
+        match self {
+            FolderRename::Renamed { folder_name } | FolderRename::Merged { folder_name, .. } => {
+                folder_name
+            }
+        }
+    }
+}
+
+/// Statistics of a folder, for the info panel in its header
+#[derive(Debug, Clone, PartialEq, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct FolderStats {
+    pub folder_name: String,
+    pub world_count: u32,
+    pub pc_only: u32,
+    pub quest_only: u32,
+    pub cross_platform: u32,
+    pub total_capacity: u32,
+    /// None if the folder has no worlds
+    pub average_capacity: Option<f64>,
+    /// Author tags of the worlds, most used first
+    pub tags: Vec<TagCount>,
+    pub oldest_added: Option<FolderStatsWorld>,
+    pub newest_added: Option<FolderStatsWorld>,
+    /// Worlds which were never launched from the app
+    pub not_visited: u32,
+    /// None if the folder is not shared
+    pub share: Option<FolderShareStatus>,
+}
+
+/// How author tags are consolidated locally, without changing the worlds' VRChat data
+/// Tags are lowercase and without "author_tag_"
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Type)]
+pub struct TagRules {
+    /// Map of tag -> tag it is shown as
+    pub aliases: BTreeMap<String, String>,
+    /// Tags which are not shown
+    pub hidden: Vec<String>,
+}
+
+/// An author tag, and the number of worlds which have it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Type)]
+pub struct TagCount {
+    pub tag: String,
+    pub count: u32,
+}
+
+/// A world of a folder's statistics, with when it was added
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct FolderStatsWorld {
+    pub world_id: String,
+    pub world_name: String,
+    pub date_added: DateTime<Utc>,
+}
+
+/// The share link of a folder
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct FolderShareStatus {
+    pub share_id: String,
+    pub expiry_time: DateTime<Utc>,
+    pub expired: bool,
+}
+
+/// A mutation of the library, as recorded in the change feed
+#[derive(Debug, Clone, PartialEq, Type, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum LibraryChange {
+    #[serde(rename_all = "camelCase")]
+    WorldsAdded { world_ids: Vec<String> },
+    #[serde(rename_all = "camelCase")]
+    WorldDeleted { world_id: String },
+    /// The world was hidden, and removed from `folders`
+    #[serde(rename_all = "camelCase")]
+    WorldHidden {
+        world_id: String,
+        folders: Vec<String>,
+    },
+    #[serde(rename_all = "camelCase")]
+    WorldUnhidden { world_id: String },
+    #[serde(rename_all = "camelCase")]
+    AddedToFolder {
+        folder_name: String,
+        world_ids: Vec<String>,
+    },
+    #[serde(rename_all = "camelCase")]
+    RemovedFromFolder {
+        folder_name: String,
+        world_id: String,
+    },
+    #[serde(rename_all = "camelCase")]
+    FolderCreated { folder_name: String },
+    /// The folder was deleted, holding `world_ids`
+    #[serde(rename_all = "camelCase")]
+    FolderDeleted {
+        folder_name: String,
+        world_ids: Vec<String>,
+    },
+    #[serde(rename_all = "camelCase")]
+    FolderRenamed { old_name: String, new_name: String },
+    #[serde(rename_all = "camelCase")]
+    FolderArchived { folder_name: String, archived: bool },
+    #[serde(rename_all = "camelCase")]
+    FlagChanged {
+        world_id: String,
+        flag: WorldFlag,
+        value: bool,
+    },
+    /// The change with `change_id` was undone
+    #[serde(rename_all = "camelCase")]
+    Undone { change_id: u64 },
+}
+
+impl LibraryChange {
+    /// Whether the change involves the world
+    pub fn concerns_world(&self, id: &str) -> bool {
+        match self {
+            LibraryChange::WorldsAdded { world_ids }
+            | LibraryChange::AddedToFolder { world_ids, .. }
+            | LibraryChange::FolderDeleted { world_ids, .. } => world_ids.iter().any(|w| w == id),
+            LibraryChange::WorldDeleted { world_id }
+            | LibraryChange::WorldHidden { world_id, .. }
+            | LibraryChange::WorldUnhidden { world_id }
+            | LibraryChange::RemovedFromFolder { world_id, .. }
+            | LibraryChange::FlagChanged { world_id, .. } => world_id == id,
+            LibraryChange::FolderCreated { .. }
+            | LibraryChange::FolderRenamed { .. }
+            | LibraryChange::FolderArchived { .. }
+            | LibraryChange::Undone { .. } => false,
+        }
+    }
+}
+
+/// An entry of the change feed
+/// Also emitted to every window when it is recorded, so windows showing the same folders
+/// stay in sync
+#[derive(Debug, Clone, PartialEq, Type, Serialize, Deserialize, tauri_specta::Event)]
+pub struct ChangeEvent {
+    /// Increases with every change
+    pub id: u64,
+    pub timestamp: DateTime<Utc>,
+    pub change: LibraryChange,
+}
+
+/// A saved world which was updated by its author since it was last fetched
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorldUpdate {
+    pub world_id: String,
+    pub world_name: String,
+    pub old_update: DateTime<Utc>,
+    pub new_update: DateTime<Utc>,
+}
+
+/// The typed payload of a notification
+#[derive(Debug, Clone, PartialEq, Type, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum NotificationKind {
+    /// A saved world was updated by its author
+    #[serde(rename_all = "camelCase")]
+    WorldUpdated {
+        world_id: String,
+        world_name: String,
+        old_update: DateTime<Utc>,
+        new_update: DateTime<Utc>,
+    },
+    /// A saved world can no longer be visited, e.g. it was made private
+    #[serde(rename_all = "camelCase")]
+    WorldUnavailable {
+        world_id: String,
+        world_name: String,
+        reason: String,
+    },
+    /// The share link of a folder is about to expire
+    #[serde(rename_all = "camelCase")]
+    ShareExpiringSoon {
+        folder_name: String,
+        share_id: String,
+        expiry_time: DateTime<Utc>,
+    },
+    /// A followed author published a world which is not in the library yet
+    #[serde(rename_all = "camelCase")]
+    NewWorldFromAuthor {
+        world_id: String,
+        world_name: String,
+        author_name: String,
+    },
+    /// A backup was created
+    BackupCompleted { path: String },
+    /// Creating a backup failed
+    BackupFailed { error: String },
+    /// A new version of the app is available
+    UpdateAvailable { version: String },
+}
+
+/// The kinds of notification, without their payload, for filtering
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Type, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum NotificationType {
+    WorldUpdated,
+    WorldUnavailable,
+    ShareExpiringSoon,
+    NewWorldFromAuthor,
+    BackupCompleted,
+    BackupFailed,
+    UpdateAvailable,
+}
+
+impl NotificationKind {
+    pub fn notification_type(&self) -> NotificationType {
+        match self {
+            NotificationKind::WorldUpdated { .. } => NotificationType::WorldUpdated,
+            NotificationKind::WorldUnavailable { .. } => NotificationType::WorldUnavailable,
+            NotificationKind::ShareExpiringSoon { .. } => NotificationType::ShareExpiringSoon,
+            NotificationKind::NewWorldFromAuthor { .. } => NotificationType::NewWorldFromAuthor,
+            NotificationKind::BackupCompleted { .. } => NotificationType::BackupCompleted,
+            NotificationKind::BackupFailed { .. } => NotificationType::BackupFailed,
+            NotificationKind::UpdateAvailable { .. } => NotificationType::UpdateAvailable,
+        }
+    }
+}
+
+/// Payload format of a webhook
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Type, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum WebhookFormat {
+    /// The notification as JSON
+    #[default]
+    Json,
+    /// A Discord message with an embed
+    Discord,
+}
+
+/// A URL notifications are posted to
+#[derive(Debug, Clone, PartialEq, Eq, Type, Serialize, Deserialize)]
+pub struct WebhookSettings {
+    pub enabled: bool,
+    pub url: String,
+    #[serde(default)]
+    pub format: WebhookFormat,
+    /// Notification types to post, every type when empty
+    #[serde(default)]
+    pub events: Vec<NotificationType>,
+}
+
+/// The localhost server external tools read the library from, e.g. stream overlays
+#[derive(Debug, Clone, PartialEq, Eq, Type, Serialize, Deserialize)]
+pub struct LocalApiSettings {
+    pub enabled: bool,
+    pub port: u16,
+    /// Sent by clients as a bearer token, or a `token` query parameter
+    pub token: String,
+}
+
+impl Default for LocalApiSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: 39512,
+            token: String::new(),
+        }
+    }
+}
+
+/// Upper bounds of the duration histogram buckets, in milliseconds
+/// Durations above the last bound are counted in one more bucket
+pub const DURATION_BUCKETS_MS: [u64; 8] = [50, 100, 250, 500, 1000, 2500, 5000, 10000];
+
+/// Counts of durations, bucketed by `DURATION_BUCKETS_MS`
+#[derive(Debug, Clone, PartialEq, Eq, Type, Serialize, Deserialize)]
+pub struct DurationHistogram {
+    #[serde(rename = "boundsMs")]
+    pub bounds_ms: Vec<u64>,
+    /// One more count than bounds, for durations above the last bound
+    pub counts: Vec<u64>,
+    #[serde(rename = "totalMs")]
+    pub total_ms: u64,
+    #[serde(rename = "maxMs")]
+    pub max_ms: u64,
+}
+
+impl Default for DurationHistogram {
+    fn default() -> Self {
+        Self {
+            bounds_ms: DURATION_BUCKETS_MS.to_vec(),
+            counts: vec![0; DURATION_BUCKETS_MS.len() + 1],
+            total_ms: 0,
+            max_ms: 0,
+        }
+    }
+}
+
+impl DurationHistogram {
+    pub fn record(&mut self, duration_ms: u64) {
+        let bucket = self
+            .bounds_ms
+            .iter()
+            .position(|bound| duration_ms <= *bound)
+            .unwrap_or(self.bounds_ms.len());
+        if let Some(count) = self.counts.get_mut(bucket) {
+            *count += 1;
+        }
+        self.total_ms += duration_ms;
+        self.max_ms = self.max_ms.max(duration_ms);
+    }
+}
+
+/// Usage of one VRChat API operation
+#[derive(Debug, Clone, Default, PartialEq, Eq, Type, Serialize, Deserialize)]
+pub struct ApiOperationMetrics {
+    pub requests: u64,
+    /// Requests which got a response, the others failed e.g. with a timeout
+    pub responses: u64,
+    /// Responses with an error status, other than rate limits
+    pub errors: u64,
+    #[serde(rename = "rateLimited")]
+    pub rate_limited: u64,
+    pub durations: DurationHistogram,
+}
+
+/// Local usage metrics, which never leave the machine
+/// Only collected while the user has opted in
+#[derive(Debug, Clone, Default, PartialEq, Eq, Type, Serialize, Deserialize)]
+pub struct AppMetrics {
+    /// When collecting started, or the metrics were last reset
+    pub since: Option<DateTime<Utc>>,
+    /// Invocations of each command
+    pub commands: BTreeMap<String, u64>,
+    /// Requests of each API operation
+    pub api: BTreeMap<String, ApiOperationMetrics>,
+}
+
+/// The VRChat group a folder is kept filled from
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct GroupFolderLink {
+    pub group_id: String,
+    pub group_name: String,
+    /// None until the folder is first synced
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_synced: Option<DateTime<Utc>>,
+}
+
+/// The outcome of syncing a folder with its group
+#[derive(Debug, Clone, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct GroupFolderSyncResult {
+    pub folder_name: String,
+    /// Worlds the group is hosting instances of
+    pub worlds_found: u32,
+    /// Worlds which were not in the folder yet
+    pub worlds_added: u32,
+}
+
+/// A world uploaded by the logged-in user
+#[derive(Debug, Clone, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct MyWorld {
+    pub world: WorldDisplayData,
+    pub release_status: ReleaseStatus,
+    /// Whether the world is saved in the library
+    pub in_library: bool,
+}
+
+/// A world's popularity at one point in time
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct WorldTrendPoint {
+    pub taken_at: DateTime<Utc>,
+    pub favorites: i32,
+    pub visits: Option<i32>,
+    pub capacity: i32,
+}
+
+/// The snapshots taken of a world, oldest first
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct WorldTrends {
+    pub world_id: String,
+    pub points: Vec<WorldTrendPoint>,
+}
+
+impl From<WorldUpdate> for NotificationKind {
+    fn from(update: WorldUpdate) -> Self {
+        NotificationKind::WorldUpdated {
+            world_id: update.world_id,
+            world_name: update.world_name,
+            old_update: update.old_update,
+            new_update: update.new_update,
+        }
+    }
+}
+
+/// An entry in the in-app notification inbox
+/// Also emitted as an event whenever a new notification is added
+#[derive(Debug, Clone, Type, Serialize, Deserialize, tauri_specta::Event)]
+pub struct Notification {
+    pub id: uuid::Uuid,
+    #[serde(rename = "createdAt")]
+    pub created_at: DateTime<Utc>,
+    #[serde(default)]
+    pub read: bool,
+    pub kind: NotificationKind,
+}
+
+impl Notification {
+    pub fn new(kind: NotificationKind) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4(),
+            created_at: Utc::now(),
+            read: false,
+            kind,
+        }
+    }
+}
+
+/// A feed of newly published worlds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Type, Serialize, Deserialize)]
+pub enum DiscoveryFeed {
+    #[serde(rename = "published")]
+    Published,
+    #[serde(rename = "labsPublished")]
+    LabsPublished,
+}
+
+/// A world suggested because it resembles another one, with why it was suggested
+#[derive(Debug, Clone, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SimilarWorld {
+    pub world: WorldDisplayData,
+    /// Higher is more similar; only meaningful relative to the other suggestions
+    pub score: f64,
+    /// Author tags both worlds have, without "author_tag_"
+    pub shared_tags: Vec<String>,
+    pub same_author: bool,
+    /// False for worlds found by searching VRChat
+    pub in_library: bool,
+}
+
+/// A field of a world's VRChat data which can be corrected and locked locally
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub enum WorldField {
+    Name,
+    Description,
+}
+
+/// A fetch of a world brought a different value for a field the user had locked
+#[derive(Debug, Clone, PartialEq, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldConflict {
+    pub world_id: String,
+    pub field: WorldField,
+    /// The locked value, which was kept
+    pub local_value: String,
+    /// The value from VRChat, which was discarded
+    pub remote_value: String,
+    pub detected_at: DateTime<Utc>,
+}
+
+/// What to do with a world taken out of the inbox
+#[derive(Debug, Clone, PartialEq, Deserialize, Type)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum TriageAction {
+    /// Keeps the world in the library, in a folder if one is given
+    #[serde(rename_all = "camelCase")]
+    Keep { folder_name: Option<String> },
+    Hide,
+    /// Blacklists the world and deletes it from the library
+    Blacklist,
+}
+
+/// A world suggested from the user's launches, favorites and ratings
+#[derive(Debug, Clone, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct Recommendation {
+    pub world: WorldDisplayData,
+    /// Higher is a better match; only meaningful relative to the other recommendations
+    pub score: f64,
+    /// Why the world was recommended, the strongest reason first
+    pub reasons: Vec<RecommendationReason>,
+    /// False for worlds found by searching VRChat
+    pub in_library: bool,
+}
+
+/// Why a world was recommended, for the UI to explain it
+/// e.g. "because you launched 12 horror worlds"
+#[derive(Debug, Clone, PartialEq, Serialize, Type)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum RecommendationReason {
+    /// The world has a tag of worlds the user often launches
+    #[serde(rename_all = "camelCase")]
+    LaunchedTag { tag: String, launches: u32 },
+    /// The world has a tag of worlds the user favorited or rated highly
+    #[serde(rename_all = "camelCase")]
+    FavoriteTag { tag: String, worlds: u32 },
+    /// The world is by an author whose worlds the user favorited, launched or rated highly
+    #[serde(rename_all = "camelCase")]
+    LikedAuthor { author_name: String, worlds: u32 },
+    /// The world is in the library but was never launched from the app
+    NeverLaunched,
+}
+
+/// The newest publication date seen so far, per discovery feed
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DiscoveryCursors {
+    #[serde(default)]
+    pub cursors: HashMap<DiscoveryFeed, DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Default, Type, Serialize, Deserialize)]
 pub struct WorldBlacklist {
+    #[serde(default)]
     pub worlds: Vec<String>,
+    #[serde(default)]
+    pub authors: Vec<String>,
+}
+
+impl WorldBlacklist {
+    pub fn new() -> Self {
+        Self {
+            worlds: vec![],
+            authors: vec![],
+        }
+    }
+
+    pub fn contains(&self, world_id: &str) -> bool {
+        self.worlds.iter().any(|id| id == world_id)
+    }
+
+    pub fn contains_author(&self, author_id: &str) -> bool {
+        self.authors.iter().any(|id| id == author_id)
+    }
+
+    /// Whether a world should be skipped, either by its own ID or by its author
+    pub fn is_blocked(&self, world_id: &str, author_id: &str) -> bool {
+        self.contains(world_id) || self.contains_author(author_id)
+    }
+}
+
+#[derive(Debug, Clone, Type, Serialize, Deserialize)]
+pub struct FollowedAuthor {
+    #[serde(rename = "authorId")]
+    pub author_id: String,
+    #[serde(rename = "authorName")]
+    pub author_name: String,
+    #[serde(rename = "lastChecked")]
+    pub last_checked: DateTime<Utc>,
+}
+
+impl FollowedAuthor {
+    pub fn new(author_id: String, author_name: String) -> Self {
+        Self {
+            author_id,
+            author_name,
+            last_checked: Utc::now(),
+        }
+    }
 }
 
 #[derive(Debug, Type, Serialize, Deserialize)]
@@ -509,3 +2081,26 @@ pub struct PatreonVRChatNames {
     #[serde(rename = "basicSupporter")]
     pub basic_supporter: Vec<String>,
 }
+
+/// A folder being played through in order, creating one instance per world
+/// The world IDs are snapshotted when the playlist starts, so editing the folder
+/// does not change a running playlist
+#[derive(Debug, Clone, PartialEq, Eq, Type, Serialize, Deserialize)]
+pub struct Playlist {
+    #[serde(rename = "folderName")]
+    pub folder_name: String,
+    #[serde(rename = "worldIds")]
+    pub world_ids: Vec<String>,
+    /// Index of the world currently being played, None until the first world is launched
+    pub position: Option<usize>,
+}
+
+impl Playlist {
+    pub fn new(folder_name: String, world_ids: Vec<String>) -> Self {
+        Self {
+            folder_name,
+            world_ids,
+            position: None,
+        }
+    }
+}
@@ -2,10 +2,28 @@ mod custom_data;
 mod entities;
 
 pub use entities::{
-    AuthCookies, CardSize, DefaultInstanceType, FilterItemSelectorStarred,
-    FilterItemSelectorStarredType, FolderModel, FolderRemovalPreference, InitState, PatreonData,
-    PatreonVRChatNames, Platform, PreferenceModel, ShareInfo, VisibleButtons, WorldApiData,
-    WorldBlacklist, WorldDetails, WorldDisplayData, WorldModel, WorldUserData,
+    AccountInfo, AllPreferences, ApiOperationMetrics, AppMetrics, AuthCookies, AuthState, CardSize,
+    ChangeEvent, ClipboardWatch, ClipboardWatchMode, ClipboardWorldDetected, ConflictResolution,
+    CriticalWorldChange, DataReloaded, DeepLinkAction, DeepLinkHandled, DefaultInstanceType,
+    DiscoveryCursors, DiscoveryFeed, DurationHistogram, EventCriticalWorldChanged,
+    FavoriteGroupCount, FavoritesImportBatch, FavoritesImportFinished, FieldConflict,
+    FilterItemSelectorStarred, FilterItemSelectorStarredType, FolderMembershipChange, FolderModel,
+    FolderNameCollision, FolderRemovalPreference, FolderRename, FolderRenamed, FolderReordered,
+    FolderShareStatus, FolderSort, FolderStats, FolderStatsWorld, FolderWorldRef, FollowedAuthor,
+    GroupFolderLink, GroupFolderSyncResult, GuestModeStatus, InitProgress, InitState,
+    InitialImportOptions, InstanceLinks, IntegrityReport, InvalidTimestamp, LibraryChange,
+    LibraryEncryptionStatus, LibraryMergeSummary, LocalApiSettings, MergePolicy, MyWorld,
+    Notification, NotificationKind, NotificationType, PatreonData, PatreonVRChatNames,
+    PhotoScanSummary, Platform, Playlist, PreferenceModel, PreferencesChanged, QrCodeImage,
+    QrPayload, RecentlyVisitedImport, Recommendation, RecommendationReason, SavedAccount,
+    SavedAccounts, SearchPlatform, SearchResultPage, SessionExpired, ShareInfo, SimilarWorld,
+    SyncConfig, SyncStatus, SyncTarget, TagCount, TagRules, TimestampField, TraySettings,
+    TriageAction, VersionVector, VisibleButtons, WebhookFormat, WebhookSettings, WorldAdded,
+    WorldApiData, WorldAvailability, WorldBlacklist, WorldChanges, WorldDetails, WorldDisplayData,
+    WorldField, WorldFilter, WorldFlag, WorldFlagChanged, WorldFlagSummary, WorldGroup,
+    WorldGroupBy, WorldListFormat, WorldModel, WorldPackageInfo, WorldPhoto, WorldRemoved,
+    WorldSummary, WorldTrendPoint, WorldTrends, WorldUpdate, WorldUpdated, WorldUserData,
+    WorldsStorageFormat,
 };
 
 pub use custom_data::{CustomData, CustomPreferences};
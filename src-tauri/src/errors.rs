@@ -1,6 +1,9 @@
 use serde::Serialize;
+use specta::Type;
 use std::fmt;
 
+use crate::i18n::LocalizedMessage;
+
 #[derive(Debug, Clone, Serialize)]
 pub enum AppError {
     Storage(FileError),
@@ -9,6 +12,7 @@ pub enum AppError {
     Network(NetworkError),
     Api(ApiError),
     Entity(EntityError),
+    Validation(ValidationError),
 }
 
 /// Represents errors that can occur during file operations
@@ -24,6 +28,12 @@ pub enum FileError {
     AccessDenied,
     /// Error occurred while writing to a file
     FileWriteError,
+    /// The library is encrypted and has not been unlocked with its passphrase
+    LibraryLocked,
+    /// The worlds are still being loaded on startup, so they cannot be written yet
+    LibraryLoading,
+    /// The named file was written by a newer version of the app
+    NewerSchema(String),
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -66,6 +76,38 @@ pub enum ApiError {
     ResponseError(String),
     /// API version mismatch
     VersionMismatch,
+    /// The session or credentials were refused, with the reason from the API layer
+    /// e.g. "2fa-required", which the frontend branches on
+    Unauthorized(String),
+    /// Requests are paused after a rate limit, with the seconds until a retry if known
+    RateLimited {
+        message: String,
+        retry_after: Option<u64>,
+    },
+    /// The requested resource does not exist
+    NotFound(String),
+}
+
+impl ApiError {
+    /// Prefixes the message of an error with what was being done when it occurred
+    /// Errors whose message is matched on, such as `Unauthorized`, are left as they are
+    #[must_use]
+    pub fn context(self, context: &str) -> Self {
+        match self {
+            ApiError::ResponseError(msg) => {
+                ApiError::ResponseError(format!("{}: {}", context, msg))
+            }
+            ApiError::NotFound(msg) => ApiError::NotFound(format!("{}: {}", context, msg)),
+            error => error,
+        }
+    }
+}
+
+/// Plain string errors from the API layer have no more specific kind
+impl From<String> for ApiError {
+    fn from(message: String) -> Self {
+        ApiError::ResponseError(message)
+    }
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -78,12 +120,31 @@ pub enum EntityError {
     DuplicateFolder(String),
     /// Duplicate world in folder
     DuplicateWorld(String),
+    /// Author with specified ID not found
+    AuthorNotFound(String),
+    /// Duplicate author in list
+    DuplicateAuthor(String),
     /// Invalid operation
     InvalidOperation(String),
     /// Invalid date format
     InvalidTimestamp(String),
 }
 
+/// User input rejected before any state was changed, see `InputValidator`
+#[derive(Debug, Serialize, Clone)]
+pub enum ValidationError {
+    /// Folder name is empty or contains control characters
+    InvalidFolderName(String),
+    /// Folder name is longer than the given number of characters
+    FolderNameTooLong(usize),
+    /// World ID is not "wrld_" followed by a UUID
+    InvalidWorldId(String),
+    /// Color is not a hex color such as "#a855f7"
+    InvalidColor(String),
+    /// Memo is longer than the given number of characters
+    MemoTooLong(usize),
+}
+
 pub enum ServiceErrors {
     /// Failed to lock mutex
     LockError,
@@ -99,6 +160,13 @@ impl fmt::Display for FileError {
             FileError::DecryptionError => write!(f, "failed to decrypt file"),
             FileError::AccessDenied => write!(f, "access to file denied"),
             FileError::FileWriteError => write!(f, "failed to write file"),
+            FileError::LibraryLocked => write!(f, "library is locked"),
+            FileError::LibraryLoading => write!(f, "library is still loading"),
+            FileError::NewerSchema(file) => write!(
+                f,
+                "{} was written by a newer version of the app, update the app to open it",
+                file
+            ),
         }
     }
 }
@@ -148,6 +216,9 @@ impl fmt::Display for ApiError {
             ApiError::InvalidRequest(msg) => write!(f, "invalid request: {}", msg),
             ApiError::ResponseError(msg) => write!(f, "API error: {}", msg),
             ApiError::VersionMismatch => write!(f, "API version mismatch"),
+            ApiError::Unauthorized(msg)
+            | ApiError::RateLimited { message: msg, .. }
+            | ApiError::NotFound(msg) => write!(f, "{}", msg),
         }
     }
 }
@@ -161,12 +232,128 @@ impl fmt::Display for EntityError {
             EntityError::WorldNotFound(id) => write!(f, "world not found: {}", id),
             EntityError::DuplicateFolder(name) => write!(f, "duplicate folder: {}", name),
             EntityError::DuplicateWorld(id) => write!(f, "duplicate world: {}", id),
+            EntityError::AuthorNotFound(id) => write!(f, "author not found: {}", id),
+            EntityError::DuplicateAuthor(id) => write!(f, "duplicate author: {}", id),
             EntityError::InvalidOperation(msg) => write!(f, "invalid operation: {}", msg),
             EntityError::InvalidTimestamp(ts) => write!(f, "invalid timestamp format: {}", ts),
         }
     }
 }
 
+impl std::error::Error for ValidationError {}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationError::InvalidFolderName(name) => {
+                write!(f, "invalid folder name: {:?}", name)
+            }
+            ValidationError::FolderNameTooLong(max) => {
+                write!(f, "folder name is longer than {} characters", max)
+            }
+            ValidationError::InvalidWorldId(id) => write!(f, "invalid world ID: {}", id),
+            ValidationError::InvalidColor(color) => write!(f, "invalid color: {}", color),
+            ValidationError::MemoTooLong(max) => {
+                write!(f, "memo is longer than {} characters", max)
+            }
+        }
+    }
+}
+
+impl AppError {
+    /// The error as a key of the translation catalogs, see `i18n`
+    pub fn localized(&self) -> LocalizedMessage {
+        let (key, params) = match self {
+            AppError::Storage(e) => match e {
+                FileError::FileNotFound => ("backend-error:file-not-found", vec![]),
+                FileError::InvalidFile => ("backend-error:invalid-file", vec![]),
+                FileError::DecryptionError => ("backend-error:decryption-failed", vec![]),
+                FileError::AccessDenied => ("backend-error:access-denied", vec![]),
+                FileError::FileWriteError => ("backend-error:file-write-failed", vec![]),
+                FileError::LibraryLocked => ("backend-error:library-locked", vec![]),
+                FileError::LibraryLoading => ("backend-error:library-loading", vec![]),
+                FileError::NewerSchema(file) => ("backend-error:newer-schema", vec![file.clone()]),
+            },
+            AppError::Concurrency(ConcurrencyError::PoisonedLock) => {
+                ("backend-error:poisoned-lock", vec![])
+            }
+            AppError::State(e) => match e {
+                StateError::Inconsistent(msg) => {
+                    ("backend-error:inconsistent-state", vec![msg.to_string()])
+                }
+                StateError::InvalidOperation(msg) => {
+                    ("backend-error:invalid-operation", vec![msg.to_string()])
+                }
+                StateError::InitializationFailed => ("backend-error:initialization-failed", vec![]),
+            },
+            AppError::Network(e) => match e {
+                NetworkError::Timeout => ("backend-error:network-timeout", vec![]),
+                NetworkError::ConnectionFailed => ("backend-error:connection-failed", vec![]),
+                NetworkError::HttpError(code) => {
+                    ("backend-error:http-error", vec![code.to_string()])
+                }
+                NetworkError::InvalidResponse => ("backend-error:invalid-response", vec![]),
+            },
+            AppError::Api(e) => match e {
+                ApiError::AuthenticationFailed => ("backend-error:authentication-failed", vec![]),
+                ApiError::RateLimitExceeded => ("backend-error:rate-limited", vec![]),
+                ApiError::InvalidRequest(msg) => {
+                    ("backend-error:invalid-request", vec![msg.to_string()])
+                }
+                ApiError::ResponseError(msg) => ("backend-error:api-error", vec![msg.clone()]),
+                ApiError::VersionMismatch => ("backend-error:api-version-mismatch", vec![]),
+                ApiError::Unauthorized(_) => ("backend-error:authentication-failed", vec![]),
+                ApiError::RateLimited { .. } => ("backend-error:rate-limited", vec![]),
+                ApiError::NotFound(msg) => ("backend-error:api-error", vec![msg.clone()]),
+            },
+            AppError::Entity(e) => match e {
+                EntityError::FolderNotFound(name) => {
+                    ("backend-error:folder-not-found", vec![name.clone()])
+                }
+                EntityError::WorldNotFound(id) => {
+                    ("backend-error:world-not-found", vec![id.clone()])
+                }
+                EntityError::DuplicateFolder(name) => {
+                    ("backend-error:duplicate-folder", vec![name.clone()])
+                }
+                EntityError::DuplicateWorld(id) => {
+                    ("backend-error:duplicate-world", vec![id.clone()])
+                }
+                EntityError::AuthorNotFound(id) => {
+                    ("backend-error:author-not-found", vec![id.clone()])
+                }
+                EntityError::DuplicateAuthor(id) => {
+                    ("backend-error:duplicate-author", vec![id.clone()])
+                }
+                EntityError::InvalidOperation(msg) => {
+                    ("backend-error:invalid-operation", vec![msg.clone()])
+                }
+                EntityError::InvalidTimestamp(ts) => {
+                    ("backend-error:invalid-timestamp", vec![ts.clone()])
+                }
+            },
+            AppError::Validation(e) => match e {
+                ValidationError::InvalidFolderName(name) => {
+                    ("backend-error:invalid-folder-name", vec![name.clone()])
+                }
+                ValidationError::FolderNameTooLong(max) => {
+                    ("backend-error:folder-name-too-long", vec![max.to_string()])
+                }
+                ValidationError::InvalidWorldId(id) => {
+                    ("backend-error:invalid-world-id", vec![id.clone()])
+                }
+                ValidationError::InvalidColor(color) => {
+                    ("backend-error:invalid-color", vec![color.clone()])
+                }
+                ValidationError::MemoTooLong(max) => {
+                    ("backend-error:memo-too-long", vec![max.to_string()])
+                }
+            },
+        };
+        LocalizedMessage::new(key, params)
+    }
+}
+
 impl std::error::Error for AppError {}
 
 impl fmt::Display for AppError {
@@ -178,6 +365,7 @@ impl fmt::Display for AppError {
             AppError::Network(e) => write!(f, "Network error: {}", e),
             AppError::Api(e) => write!(f, "API error: {}", e),
             AppError::Entity(e) => write!(f, "Entity error: {}", e),
+            AppError::Validation(e) => write!(f, "Validation error: {}", e),
         }
     }
 }
@@ -218,6 +406,270 @@ impl From<EntityError> for AppError {
     }
 }
 
+impl From<ValidationError> for AppError {
+    fn from(error: ValidationError) -> Self {
+        AppError::Validation(error)
+    }
+}
+
+/// Error returned by Tauri commands to the frontend
+/// Tagged by kind so the frontend can branch on it, e.g. prompt re-login on Auth
+/// Where the backend has a translation, `message` is in the language of the preferences
+/// and `localized` holds its key, so the frontend can translate it itself
+#[derive(Debug, Clone, Serialize, Type, PartialEq, Eq)]
+#[serde(tag = "kind")]
+pub enum CommandError {
+    /// The user is not logged in, or the session has expired
+    Auth {
+        message: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        localized: Option<LocalizedMessage>,
+    },
+    /// No user is logged in, so a command needing the VRChat API was not attempted
+    NotLoggedIn {
+        message: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        localized: Option<LocalizedMessage>,
+    },
+    /// The VRChat API is rate limiting requests
+    RateLimited {
+        message: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        localized: Option<LocalizedMessage>,
+        /// Seconds until the request may be retried, if known
+        #[serde(rename = "retryAfter")]
+        retry_after: Option<u64>,
+    },
+    /// The requested folder, world or other entity does not exist
+    NotFound {
+        message: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        localized: Option<LocalizedMessage>,
+    },
+    /// A request to the VRChat API or another server failed
+    Network {
+        message: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        localized: Option<LocalizedMessage>,
+    },
+    /// Reading or writing local files failed
+    Io {
+        message: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        localized: Option<LocalizedMessage>,
+    },
+    /// The library is encrypted and must be unlocked with its passphrase first,
+    /// or is still being loaded
+    Locked {
+        message: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        localized: Option<LocalizedMessage>,
+    },
+    /// The app is in read-only guest mode, so a command changing data or using the
+    /// VRChat account was rejected
+    GuestMode {
+        message: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        localized: Option<LocalizedMessage>,
+    },
+    /// The command was called with invalid input
+    Validation {
+        message: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        localized: Option<LocalizedMessage>,
+    },
+    /// Any other failure
+    Internal {
+        message: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        localized: Option<LocalizedMessage>,
+    },
+}
+
+impl CommandError {
+    pub fn auth(message: impl Into<String>) -> Self {
+        CommandError::Auth {
+            message: message.into(),
+            localized: None,
+        }
+    }
+
+    pub fn not_logged_in(message: impl Into<String>) -> Self {
+        CommandError::NotLoggedIn {
+            message: message.into(),
+            localized: None,
+        }
+    }
+
+    pub fn not_found(message: impl Into<String>) -> Self {
+        CommandError::NotFound {
+            message: message.into(),
+            localized: None,
+        }
+    }
+
+    pub fn network(message: impl Into<String>) -> Self {
+        CommandError::Network {
+            message: message.into(),
+            localized: None,
+        }
+    }
+
+    pub fn io(message: impl Into<String>) -> Self {
+        CommandError::Io {
+            message: message.into(),
+            localized: None,
+        }
+    }
+
+    pub fn validation(message: impl Into<String>) -> Self {
+        CommandError::Validation {
+            message: message.into(),
+            localized: None,
+        }
+    }
+
+    pub fn guest_mode() -> Self {
+        let localized = LocalizedMessage::new("backend-error:guest-mode", vec![]);
+        CommandError::GuestMode {
+            message: localized.text(),
+            localized: Some(localized),
+        }
+    }
+
+    pub fn internal(message: impl Into<String>) -> Self {
+        CommandError::Internal {
+            message: message.into(),
+            localized: None,
+        }
+    }
+
+    /// The human readable message of the error
+    pub fn message(&self) -> &str {
+        match self {
+            CommandError::Auth { message, .. }
+            | CommandError::NotLoggedIn { message, .. }
+            | CommandError::RateLimited { message, .. }
+            | CommandError::NotFound { message, .. }
+            | CommandError::Network { message, .. }
+            | CommandError::Io { message, .. }
+            | CommandError::Locked { message, .. }
+            | CommandError::GuestMode { message, .. }
+            | CommandError::Validation { message, .. }
+            | CommandError::Internal { message, .. } => message,
+        }
+    }
+}
+
+impl std::error::Error for CommandError {}
+
+impl fmt::Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message())
+    }
+}
+
+impl From<AppError> for CommandError {
+    fn from(error: AppError) -> Self {
+        let localized = error.localized();
+        let message = localized.text();
+        let localized = Some(localized);
+        match error {
+            AppError::Storage(FileError::LibraryLocked | FileError::LibraryLoading) => {
+                CommandError::Locked { message, localized }
+            }
+            AppError::Storage(_) => CommandError::Io { message, localized },
+            AppError::Concurrency(_) | AppError::State(_) => {
+                CommandError::Internal { message, localized }
+            }
+            AppError::Network(_) => CommandError::Network { message, localized },
+            AppError::Api(ApiError::AuthenticationFailed) => {
+                CommandError::Auth { message, localized }
+            }
+            AppError::Api(ApiError::RateLimitExceeded) => CommandError::RateLimited {
+                message,
+                localized,
+                retry_after: None,
+            },
+            AppError::Api(ApiError::InvalidRequest(_)) => {
+                CommandError::Validation { message, localized }
+            }
+            // The API layer's own messages have no translation, and some are matched on
+            AppError::Api(ApiError::Unauthorized(msg)) => CommandError::auth(msg),
+            AppError::Api(ApiError::RateLimited {
+                message,
+                retry_after,
+            }) => CommandError::RateLimited {
+                message,
+                localized: None,
+                retry_after,
+            },
+            AppError::Api(ApiError::NotFound(msg)) => CommandError::not_found(msg),
+            AppError::Api(ApiError::ResponseError(msg)) => CommandError::network(msg),
+            AppError::Api(ApiError::VersionMismatch) => {
+                CommandError::Network { message, localized }
+            }
+            AppError::Entity(
+                EntityError::FolderNotFound(_)
+                | EntityError::WorldNotFound(_)
+                | EntityError::AuthorNotFound(_),
+            ) => CommandError::NotFound { message, localized },
+            AppError::Entity(_) | AppError::Validation(_) => {
+                CommandError::Validation { message, localized }
+            }
+        }
+    }
+}
+
+impl From<FileError> for CommandError {
+    fn from(error: FileError) -> Self {
+        AppError::from(error).into()
+    }
+}
+
+impl From<ConcurrencyError> for CommandError {
+    fn from(error: ConcurrencyError) -> Self {
+        AppError::from(error).into()
+    }
+}
+
+impl From<EntityError> for CommandError {
+    fn from(error: EntityError) -> Self {
+        AppError::from(error).into()
+    }
+}
+
+impl From<ApiError> for CommandError {
+    fn from(error: ApiError) -> Self {
+        AppError::from(error).into()
+    }
+}
+
+impl From<ValidationError> for CommandError {
+    fn from(error: ValidationError) -> Self {
+        AppError::from(error).into()
+    }
+}
+
+impl From<std::io::Error> for CommandError {
+    fn from(error: std::io::Error) -> Self {
+        CommandError::io(error.to_string())
+    }
+}
+
+/// Plain string errors have no known kind
+impl From<String> for CommandError {
+    fn from(message: String) -> Self {
+        CommandError::internal(message)
+    }
+}
+
+impl From<&str> for CommandError {
+    fn from(message: &str) -> Self {
+        CommandError::internal(message)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -258,4 +710,69 @@ mod tests {
         assert!(format!("{:?}", NetworkError::Timeout).contains("Timeout"));
         assert!(format!("{:?}", ApiError::VersionMismatch).contains("VersionMismatch"));
     }
+
+    #[test]
+    fn test_command_error_from_app_error() {
+        let error: CommandError = AppError::from(EntityError::FolderNotFound("x".into())).into();
+        assert!(matches!(error, CommandError::NotFound { .. }));
+
+        let error: CommandError = AppError::from(FileError::FileWriteError).into();
+        assert!(matches!(error, CommandError::Io { .. }));
+
+        let error: CommandError = AppError::from(FileError::LibraryLocked).into();
+        assert!(matches!(error, CommandError::Locked { .. }));
+
+        let error: CommandError = AppError::from(ValidationError::MemoTooLong(10)).into();
+        assert!(matches!(error, CommandError::Validation { .. }));
+    }
+
+    #[test]
+    fn test_command_error_from_app_error_is_localized() {
+        let error: CommandError = AppError::from(EntityError::FolderNotFound("x".into())).into();
+        let CommandError::NotFound {
+            localized: Some(localized),
+            ..
+        } = error
+        else {
+            panic!("expected a localized NotFound error");
+        };
+        assert_eq!(localized.key, "backend-error:folder-not-found");
+        assert_eq!(localized.params, vec!["x".to_string()]);
+    }
+
+    #[test]
+    fn test_command_error_from_api_error() {
+        let error = ApiError::RateLimited {
+            message: "Rate limit active for search_worlds. Please try again in 42 seconds."
+                .to_string(),
+            retry_after: Some(42),
+        };
+        assert_eq!(
+            CommandError::from(error),
+            CommandError::RateLimited {
+                message: "Rate limit active for search_worlds. Please try again in 42 seconds."
+                    .to_string(),
+                localized: None,
+                retry_after: Some(42),
+            }
+        );
+        assert_eq!(
+            CommandError::from(ApiError::Unauthorized("2fa-required".to_string())),
+            CommandError::auth("2fa-required")
+        );
+        assert!(matches!(
+            CommandError::from(ApiError::NotFound("world not found".to_string())),
+            CommandError::NotFound { .. }
+        ));
+
+        // Context is added to the message without changing the kind
+        let error =
+            ApiError::from("connection reset".to_string()).context("Failed to search worlds");
+        assert_eq!(
+            CommandError::from(error),
+            CommandError::network("Failed to search worlds: connection reset")
+        );
+        let error = ApiError::Unauthorized("2fa-required".to_string()).context("Login failed");
+        assert_eq!(error.to_string(), "2fa-required");
+    }
 }
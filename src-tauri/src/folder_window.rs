@@ -0,0 +1,62 @@
+use tauri::{AppHandle, Manager, WebviewUrl, WebviewWindowBuilder};
+
+/// Labels of folder windows start with this, followed by the hex encoded folder name
+/// Matched by the `folder-*` entry of the default capability
+const FOLDER_WINDOW_PREFIX: &str = "folder-";
+
+/// The label of the window showing a folder
+/// Labels may only hold letters, digits and `-/:_`, so the name is hex encoded
+pub fn label_for(folder_name: &str) -> String {
+    format!("{}{}", FOLDER_WINDOW_PREFIX, hex::encode(folder_name))
+}
+
+/// The folder shown by a window, or None for the main window
+pub fn folder_of(label: &str) -> Option<String> {
+    let encoded = label.strip_prefix(FOLDER_WINDOW_PREFIX)?;
+    String::from_utf8(hex::decode(encoded).ok()?).ok()
+}
+
+/// Opens a window showing only one folder, or focuses it if it is already open
+/// Every window receives the same events, so changes made in one show up in the others
+///
+/// # Arguments
+/// * `app` - The app handle
+/// * `folder_name` - The name of the folder
+///
+/// # Errors
+/// Returns an error if the window could not be created
+pub fn open(app: &AppHandle, folder_name: &str) -> tauri::Result<()> {
+    let label = label_for(folder_name);
+    if let Some(window) = app.get_webview_window(&label) {
+        let _ = window.unminimize();
+        window.show()?;
+        return window.set_focus();
+    }
+
+    let url = format!(
+        "listview/folders/userFolder?folderName={}",
+        urlencoding::encode(folder_name)
+    );
+    WebviewWindowBuilder::new(app, label, WebviewUrl::App(url.into()))
+        .title(format!("{} - VRC World Manager", folder_name))
+        .inner_size(1100.0, 600.0)
+        .disable_drag_drop_handler()
+        .build()?;
+    log::info!("Opened a window for folder {}", folder_name);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_label_round_trip() {
+        for name in ["Chill", "お気に入り", "a/b c"] {
+            let label = label_for(name);
+            assert!(label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-'));
+            assert_eq!(folder_of(&label).as_deref(), Some(name));
+        }
+        assert_eq!(folder_of("main"), None);
+    }
+}
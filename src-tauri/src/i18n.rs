@@ -0,0 +1,426 @@
+use std::sync::{Mutex, PoisonError};
+
+use serde::Serialize;
+use specta::Type;
+
+/// The language backend messages are written in, from the preferences
+/// Unset until the preferences are loaded, messages are in English until then
+static LANGUAGE: Mutex<Option<String>> = Mutex::new(None);
+
+const ENGLISH: &[(&str, &str)] = &[
+    ("backend-error:file-not-found", "File not found"),
+    (
+        "backend-error:invalid-file",
+        "The file has an invalid format",
+    ),
+    (
+        "backend-error:decryption-failed",
+        "Failed to decrypt the file",
+    ),
+    (
+        "backend-error:access-denied",
+        "Access to the file was denied",
+    ),
+    (
+        "backend-error:file-write-failed",
+        "Failed to write the file",
+    ),
+    ("backend-error:library-locked", "The library is locked"),
+    (
+        "backend-error:library-loading",
+        "The library is still loading, please try again in a moment",
+    ),
+    (
+        "backend-error:newer-schema",
+        "{0} was written by a newer version of the app, update the app to open it",
+    ),
+    (
+        "backend-error:poisoned-lock",
+        "An internal error occurred, please restart the app",
+    ),
+    (
+        "backend-error:inconsistent-state",
+        "Inconsistent state: {0}",
+    ),
+    ("backend-error:invalid-operation", "Invalid operation: {0}"),
+    (
+        "backend-error:initialization-failed",
+        "Failed to initialize the app",
+    ),
+    ("backend-error:network-timeout", "The connection timed out"),
+    ("backend-error:connection-failed", "Failed to connect"),
+    (
+        "backend-error:http-error",
+        "The server returned HTTP error {0}",
+    ),
+    (
+        "backend-error:invalid-response",
+        "The server returned an invalid response",
+    ),
+    (
+        "backend-error:authentication-failed",
+        "Authentication failed",
+    ),
+    (
+        "backend-error:rate-limited",
+        "Too many requests, please wait a moment and try again",
+    ),
+    ("backend-error:invalid-request", "Invalid request: {0}"),
+    ("backend-error:api-error", "VRChat API error: {0}"),
+    (
+        "backend-error:api-version-mismatch",
+        "This version of the VRChat API is not supported",
+    ),
+    ("backend-error:folder-not-found", "Folder not found: {0}"),
+    ("backend-error:world-not-found", "World not found: {0}"),
+    (
+        "backend-error:duplicate-folder",
+        "A folder named {0} already exists",
+    ),
+    (
+        "backend-error:duplicate-world",
+        "The world is already in the folder: {0}",
+    ),
+    ("backend-error:author-not-found", "Author not found: {0}"),
+    (
+        "backend-error:duplicate-author",
+        "The author is already in the list: {0}",
+    ),
+    ("backend-error:invalid-timestamp", "Invalid date: {0}"),
+    (
+        "backend-error:invalid-folder-name",
+        "Folder names must not be empty or contain control characters: {0}",
+    ),
+    (
+        "backend-error:folder-name-too-long",
+        "Folder names must be at most {0} characters",
+    ),
+    ("backend-error:invalid-world-id", "Invalid world ID: {0}"),
+    ("backend-error:invalid-color", "Invalid color: {0}"),
+    (
+        "backend-error:memo-too-long",
+        "Memos must be at most {0} characters",
+    ),
+    (
+        "backend-error:guest-mode",
+        "Not available in guest mode, enter the PIN to leave it",
+    ),
+    ("backend-notification:world-updated:title", "World updated"),
+    (
+        "backend-notification:world-updated:body",
+        "{0} was updated on {1}",
+    ),
+    (
+        "backend-notification:world-unavailable:title",
+        "World unavailable",
+    ),
+    (
+        "backend-notification:world-unavailable:body",
+        "{0} can no longer be visited: {1}",
+    ),
+    (
+        "backend-notification:share-expiring:title",
+        "Share expiring soon",
+    ),
+    (
+        "backend-notification:share-expiring:body",
+        "The share link of {0} expires on {1}",
+    ),
+    (
+        "backend-notification:new-world:title",
+        "New world from a followed author",
+    ),
+    ("backend-notification:new-world:body", "{0} published {1}"),
+    (
+        "backend-notification:backup-completed:title",
+        "Backup completed",
+    ),
+    (
+        "backend-notification:backup-completed:body",
+        "Backup saved to {0}",
+    ),
+    ("backend-notification:backup-failed:title", "Backup failed"),
+    (
+        "backend-notification:update-available:title",
+        "Update available",
+    ),
+    (
+        "backend-notification:update-available:body",
+        "Version {0} is available",
+    ),
+    ("backend-export:author", "by {0}"),
+    ("backend-export:capacity", "Capacity {0}"),
+    (
+        "backend-export:capacity-recommended",
+        "Capacity {0} (recommended {1})",
+    ),
+    ("backend-export:world-count", "{0} worlds"),
+];
+
+const JAPANESE: &[(&str, &str)] = &[
+    ("backend-error:file-not-found", "ファイルが見つかりません"),
+    (
+        "backend-error:invalid-file",
+        "ファイルの形式が正しくありません",
+    ),
+    (
+        "backend-error:decryption-failed",
+        "ファイルの復号に失敗しました",
+    ),
+    (
+        "backend-error:access-denied",
+        "ファイルへのアクセスが拒否されました",
+    ),
+    (
+        "backend-error:file-write-failed",
+        "ファイルの書き込みに失敗しました",
+    ),
+    (
+        "backend-error:library-locked",
+        "ライブラリはロックされています",
+    ),
+    (
+        "backend-error:library-loading",
+        "ライブラリを読み込み中です。しばらくしてから再試行してください",
+    ),
+    (
+        "backend-error:newer-schema",
+        "{0}は新しいバージョンのアプリで保存されています。開くにはアプリを更新してください",
+    ),
+    (
+        "backend-error:poisoned-lock",
+        "内部エラーが発生しました。アプリを再起動してください",
+    ),
+    (
+        "backend-error:inconsistent-state",
+        "状態に不整合があります: {0}",
+    ),
+    ("backend-error:invalid-operation", "無効な操作です: {0}"),
+    (
+        "backend-error:initialization-failed",
+        "アプリの初期化に失敗しました",
+    ),
+    (
+        "backend-error:network-timeout",
+        "接続がタイムアウトしました",
+    ),
+    ("backend-error:connection-failed", "接続に失敗しました"),
+    (
+        "backend-error:http-error",
+        "サーバーがHTTPエラー{0}を返しました",
+    ),
+    ("backend-error:invalid-response", "サーバーの応答が不正です"),
+    ("backend-error:authentication-failed", "認証に失敗しました"),
+    (
+        "backend-error:rate-limited",
+        "リクエストが多すぎます。しばらく待ってから再試行してください",
+    ),
+    ("backend-error:invalid-request", "無効なリクエストです: {0}"),
+    ("backend-error:api-error", "VRChat APIエラー: {0}"),
+    (
+        "backend-error:api-version-mismatch",
+        "このバージョンのVRChat APIには対応していません",
+    ),
+    (
+        "backend-error:folder-not-found",
+        "フォルダが見つかりません: {0}",
+    ),
+    (
+        "backend-error:world-not-found",
+        "ワールドが見つかりません: {0}",
+    ),
+    (
+        "backend-error:duplicate-folder",
+        "{0}という名前のフォルダは既に存在します",
+    ),
+    (
+        "backend-error:duplicate-world",
+        "ワールドは既にフォルダに追加されています: {0}",
+    ),
+    (
+        "backend-error:author-not-found",
+        "作者が見つかりません: {0}",
+    ),
+    (
+        "backend-error:duplicate-author",
+        "作者は既にリストに追加されています: {0}",
+    ),
+    (
+        "backend-error:invalid-timestamp",
+        "日付が正しくありません: {0}",
+    ),
+    (
+        "backend-error:invalid-folder-name",
+        "フォルダ名は空にできず、制御文字も使用できません: {0}",
+    ),
+    (
+        "backend-error:folder-name-too-long",
+        "フォルダ名は{0}文字以内にしてください",
+    ),
+    (
+        "backend-error:invalid-world-id",
+        "ワールドIDが正しくありません: {0}",
+    ),
+    ("backend-error:invalid-color", "色が正しくありません: {0}"),
+    (
+        "backend-error:memo-too-long",
+        "メモは{0}文字以内にしてください",
+    ),
+    (
+        "backend-error:guest-mode",
+        "ゲストモードでは使用できません。PINを入力して解除してください",
+    ),
+    (
+        "backend-notification:world-updated:title",
+        "ワールドが更新されました",
+    ),
+    (
+        "backend-notification:world-updated:body",
+        "{0}が{1}に更新されました",
+    ),
+    (
+        "backend-notification:world-unavailable:title",
+        "ワールドが利用できなくなりました",
+    ),
+    (
+        "backend-notification:world-unavailable:body",
+        "{0}には訪問できなくなりました: {1}",
+    ),
+    (
+        "backend-notification:share-expiring:title",
+        "共有の期限が近づいています",
+    ),
+    (
+        "backend-notification:share-expiring:body",
+        "{0}の共有リンクは{1}に期限切れになります",
+    ),
+    (
+        "backend-notification:new-world:title",
+        "フォロー中の作者の新しいワールド",
+    ),
+    (
+        "backend-notification:new-world:body",
+        "{0}が{1}を公開しました",
+    ),
+    (
+        "backend-notification:backup-completed:title",
+        "バックアップが完了しました",
+    ),
+    (
+        "backend-notification:backup-completed:body",
+        "バックアップを{0}に保存しました",
+    ),
+    (
+        "backend-notification:backup-failed:title",
+        "バックアップに失敗しました",
+    ),
+    (
+        "backend-notification:update-available:title",
+        "アップデートがあります",
+    ),
+    (
+        "backend-notification:update-available:body",
+        "バージョン{0}が利用可能です",
+    ),
+    ("backend-export:author", "作者: {0}"),
+    ("backend-export:capacity", "定員 {0}"),
+    ("backend-export:capacity-recommended", "定員 {0}(推奨 {1})"),
+    ("backend-export:world-count", "{0}ワールド"),
+];
+
+/// A message produced by the backend, as a key of the translation catalogs
+/// Keys and the `{0}` style placeholders follow the frontend's locale files,
+/// so the frontend can show the message in its own language
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Type)]
+pub struct LocalizedMessage {
+    pub key: String,
+    pub params: Vec<String>,
+}
+
+impl LocalizedMessage {
+    pub fn new(key: &str, params: Vec<String>) -> Self {
+        Self {
+            key: key.to_string(),
+            params,
+        }
+    }
+
+    /// The message in the language of the preferences
+    pub fn text(&self) -> String {
+        let language = LANGUAGE
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+            .unwrap_or_default();
+        self.text_in(&language)
+    }
+
+    /// The message in a language, e.g. "ja-JP"
+    /// Falls back to English for other languages and keys without a translation,
+    /// and to the key itself for unknown keys
+    pub fn text_in(&self, language: &str) -> String {
+        let catalog = if language.starts_with("ja") {
+            JAPANESE
+        } else {
+            ENGLISH
+        };
+        let find = |catalog: &[(&str, &'static str)]| {
+            catalog
+                .iter()
+                .find(|(key, _)| *key == self.key)
+                .map(|(_, text)| *text)
+        };
+        let template = find(catalog)
+            .or_else(|| find(ENGLISH))
+            .unwrap_or(self.key.as_str());
+
+        self.params
+            .iter()
+            .enumerate()
+            .fold(template.to_string(), |text, (i, param)| {
+                text.replace(&format!("{{{}}}", i), param)
+            })
+    }
+}
+
+/// Sets the language backend messages are written in, e.g. "ja-JP"
+pub fn set_language(language: &str) {
+    *LANGUAGE.lock().unwrap_or_else(PoisonError::into_inner) = Some(language.to_string());
+}
+
+/// Shorthand for the text of a message in the language of the preferences
+pub fn text(key: &str, params: Vec<String>) -> String {
+    LocalizedMessage::new(key, params).text()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_in_fills_params() {
+        let message =
+            LocalizedMessage::new("backend-error:folder-not-found", vec!["Horror".to_string()]);
+        assert_eq!(message.text_in("en-US"), "Folder not found: Horror");
+        assert_eq!(message.text_in("ja-JP"), "フォルダが見つかりません: Horror");
+    }
+
+    #[test]
+    fn test_text_in_falls_back() {
+        let message = LocalizedMessage::new("backend-error:file-not-found", vec![]);
+        assert_eq!(message.text_in("de-DE"), "File not found");
+
+        let unknown = LocalizedMessage::new("backend-error:unknown", vec![]);
+        assert_eq!(unknown.text_in("ja-JP"), "backend-error:unknown");
+    }
+
+    #[test]
+    fn test_catalogs_have_the_same_keys() {
+        for (key, _) in ENGLISH {
+            assert!(JAPANESE.iter().any(|(k, _)| k == key), "{}", key);
+        }
+        for (key, _) in JAPANESE {
+            assert!(ENGLISH.iter().any(|(k, _)| k == key), "{}", key);
+        }
+    }
+}
@@ -1,11 +1,16 @@
 use api::auth::VRChatAPIClientAuthenticator;
-use commands::generate_tauri_specta_builder;
-use definitions::{FolderModel, InitState, PreferenceModel, WorldModel};
-use directories::BaseDirs;
-use services::ApiService;
+use app_state::AppState;
+use commands::{deep_link_commands, generate_tauri_specta_builder};
+use definitions::{
+    AuthCookies, ChangeEvent, ClipboardWorldDetected, DataReloaded, DeepLinkHandled,
+    EventCriticalWorldChanged, FavoritesImportBatch, FavoritesImportFinished, FolderRenamed,
+    FolderReordered, InitProgress, InitState, Notification, PreferenceModel, PreferencesChanged,
+    SessionExpired, WorldAdded, WorldFlagChanged, WorldRemoved, WorldUpdated,
+};
+use services::{ApiService, FileService};
 use specta_typescript::{BigIntExportBehavior, Typescript};
 use state::InitCell;
-use std::sync::{Arc, RwLock};
+use std::sync::{Arc, PoisonError, RwLock};
 use tauri::async_runtime::Mutex;
 use tauri::{AppHandle, Emitter, Manager};
 use tauri_specta::collect_events;
@@ -15,31 +20,65 @@ use crate::task::cancellable_task::TaskContainer;
 use crate::task::definitions::TaskStatusChanged;
 
 mod api;
+mod app_state;
 mod backup;
 mod changelog;
+mod cli;
 mod commands;
 mod definitions;
 mod errors;
+mod folder_window;
+mod i18n;
 mod logging;
 mod migration;
 mod services;
+mod shortcut;
 mod task;
+#[cfg(desktop)]
+mod tray;
 mod updater;
 
-static PREFERENCES: InitCell<RwLock<PreferenceModel>> = InitCell::new();
-static FOLDERS: InitCell<RwLock<Vec<FolderModel>>> = InitCell::new();
-static WORLDS: InitCell<RwLock<Vec<WorldModel>>> = InitCell::new();
 static INITSTATE: InitCell<tokio::sync::RwLock<InitState>> = InitCell::new();
 static AUTHENTICATOR: InitCell<tokio::sync::RwLock<VRChatAPIClientAuthenticator>> = InitCell::new();
 static RATE_LIMIT_STORE: InitCell<RwLock<api::RateLimitStore>> = InitCell::new();
+static REQUEST_SCHEDULER: InitCell<api::RequestScheduler> = InitCell::new();
 static MEMO_MANAGER: InitCell<RwLock<MemoManager>> = InitCell::new();
 
 // Define state to hold startup deep link
 pub struct StartupDeepLink(pub std::sync::Mutex<Option<String>>);
 
+/// Runs a batch operation if the app was started with one, e.g. `--backup <directory>`
+///
+/// # Returns
+/// The exit code of the operation, or None if the GUI should start
+pub fn run_cli() -> Option<i32> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    cli::CliCommand::parse(&args).map(cli::run)
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
-    let builder = generate_tauri_specta_builder().events(collect_events![TaskStatusChanged]);
+    let builder = generate_tauri_specta_builder().events(collect_events![
+        TaskStatusChanged,
+        ClipboardWorldDetected,
+        EventCriticalWorldChanged,
+        DataReloaded,
+        InitProgress,
+        DeepLinkHandled,
+        Notification,
+        WorldUpdated,
+        WorldAdded,
+        WorldRemoved,
+        WorldFlagChanged,
+        FolderRenamed,
+        FolderReordered,
+        FavoritesImportBatch,
+        FavoritesImportFinished,
+        SessionExpired,
+        PreferencesChanged,
+        ChangeEvent,
+        api::ApiQueueChanged
+    ]);
 
     #[cfg(debug_assertions)]
     builder
@@ -75,7 +114,18 @@ pub fn run() {
 
                 log::info!("Single instance args received: {:?}", args);
 
-                // Emitting all args to frontend to handle logic there
+                // Links the backend handles are performed right away,
+                // all other args are emitted to the frontend to handle logic there
+                let (routed, args): (Vec<String>, Vec<String>) = args
+                    .into_iter()
+                    .partition(|arg| deep_link_commands::is_routed(arg));
+                for url in routed {
+                    tauri::async_runtime::spawn(deep_link_commands::handle_deep_link(
+                        app.clone(),
+                        url,
+                        false,
+                    ));
+                }
                 if !args.is_empty() {
                     let _ = app.emit("deep-link-received", args.clone());
                 }
@@ -84,34 +134,49 @@ pub fn run() {
 
     tauri_builder = tauri_builder.plugin(tauri_plugin_deep_link::init());
 
+    #[cfg(desktop)]
+    {
+        let shortcut_plugin = tauri_plugin_global_shortcut::Builder::new()
+            .with_handler(shortcut::on_shortcut)
+            .build();
+        tauri_builder = tauri_builder
+            .on_window_event(tray::on_window_event)
+            .plugin(shortcut_plugin);
+    }
+
     tauri_builder
         .plugin(tauri_plugin_window_state::Builder::default().build())
         .plugin(tauri_plugin_opener::init())
+        .plugin(tauri_plugin_clipboard_manager::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_shell::init())
-        .invoke_handler(builder.invoke_handler())
-        .plugin(
-            tauri_plugin_log::Builder::new()
-                .target({
-                    let timestamp = chrono::Utc::now()
-                        .format("%Y-%m-%d_%H-%M-%S.%6f")
-                        .to_string();
-                    let log_path = format!("vrc-worlds-manager-{}", timestamp);
-                    tauri_plugin_log::Target::new(tauri_plugin_log::TargetKind::LogDir {
-                        file_name: Some(log_path),
-                    })
-                })
-                .level(log::LevelFilter::Info)
-                .build(),
-        )
+        .invoke_handler({
+            let invoke_handler = builder.invoke_handler();
+            move |invoke| {
+                let command = invoke.message.command();
+                services::MetricsService::record_command(command);
+                if !services::GuestMode::allows(command) {
+                    log::info!("Rejected {} in guest mode", command);
+                    invoke.resolver.reject(errors::CommandError::guest_mode());
+                    return true;
+                }
+                invoke_handler(invoke)
+            }
+        })
+        .plugin(log_plugin())
         .setup(move |app| {
             // Capture startup args
             let args: Vec<String> = std::env::args().collect();
             let mut startup_link = None;
+            let mut routed_link = None;
             for arg in args {
-                if arg.starts_with("vrc-worlds-manager://") {
+                if arg.starts_with(deep_link_commands::DEEP_LINK_SCHEME) {
                     log::info!("Found startup deep link: {}", arg);
-                    startup_link = Some(arg);
+                    if deep_link_commands::is_routed(&arg) {
+                        routed_link = Some(arg);
+                    } else {
+                        startup_link = Some(arg);
+                    }
                 }
             }
             app.manage(StartupDeepLink(std::sync::Mutex::new(startup_link)));
@@ -127,23 +192,123 @@ pub fn run() {
             let logs_dir = handle.path().app_log_dir().unwrap();
             logging::purge_outdated_logs(&logs_dir).expect("Failed to purge outdated logs");
 
+            // Finished at the end of setup, a crash before then counts as a failed start
+            if let Some(installer) = updater::rollback::begin_startup() {
+                match updater::rollback::roll_back(&installer) {
+                    Ok(()) => std::process::exit(0),
+                    Err(e) => log::error!("Failed to roll back: {}", e),
+                }
+            }
+
             let app_data_dir = handle.path().app_data_dir().unwrap_or_else(|_| {
                 log::warn!(
                     "Could not resolve app data directory, using temp dir for rate limit data"
                 );
                 std::env::temp_dir()
             });
-            let rate_limit_path = app_data_dir.join("rate_limits.json");
+            let rate_limit_path = FileService::get_rate_limit_path(&app_data_dir);
             RATE_LIMIT_STORE.set(RwLock::new(api::RateLimitStore::load(rate_limit_path)));
             log::info!("Rate limit store initialized");
 
+            services::LibraryEncryption::init();
+            services::MetricsService::init();
+            services::GuestMode::init();
+            services::SortingService::init();
+            services::TagService::init();
+            services::WorldChangeService::init(handle.clone());
+            services::ChangeFeed::init(handle.clone());
+            services::LibraryEvents::init(handle.clone());
+
+            let custom_preferences = FileService::read_custom_data().preferences;
+            logging::apply_levels(custom_preferences.log_levels.clone().unwrap_or_default());
+            let requests_per_minute = custom_preferences
+                .api_requests_per_minute
+                .unwrap_or(api::DEFAULT_REQUESTS_PER_MINUTE);
+            let concurrent_requests = custom_preferences
+                .api_concurrent_requests
+                .unwrap_or(api::DEFAULT_CONCURRENT_REQUESTS);
+            REQUEST_SCHEDULER.set(api::RequestScheduler::new(
+                Some(handle.clone()),
+                requests_per_minute,
+                concurrent_requests,
+            ));
+            // Backoffs are persisted, so a rate limit from before a restart is still honored
+            for (operation, backoff) in api::remaining_backoffs() {
+                REQUEST_SCHEDULER.get().hold(&operation, backoff);
+            }
+            log::info!("Request scheduler initialized");
+
             commands::patreon_cache::init_cache();
             log::info!("Patreon cache initialized");
 
-            if let Err(e) = initialize_app() {
+            services::api_cache::init_cache();
+            log::info!("API cache initialized");
+
+            if let Err(e) = initialize_app(&handle) {
                 log::error!("Failed to initialize app: {}", e);
             }
 
+            #[cfg(desktop)]
+            {
+                if let Err(e) = tray::create(&handle) {
+                    log::warn!("Failed to create tray icon: {}", e);
+                }
+                tray::apply_start_minimized(&handle);
+                shortcut::register_saved(&handle);
+            }
+
+            match services::DataWatcher::start(handle.clone()) {
+                Ok(watcher) => {
+                    app.manage(watcher);
+                }
+                Err(e) => log::warn!("Failed to watch data directory: {}", e),
+            }
+
+            // Handled once the library is loaded, as the links act on it
+            if let Some(url) = routed_link {
+                tauri::async_runtime::spawn(deep_link_commands::handle_deep_link(
+                    handle.clone(),
+                    url,
+                    true,
+                ));
+            }
+            #[cfg(desktop)]
+            {
+                use tauri_plugin_deep_link::DeepLinkExt;
+                let link_handle = handle.clone();
+                app.deep_link().on_open_url(move |event| {
+                    for url in event.urls() {
+                        tauri::async_runtime::spawn(deep_link_commands::handle_deep_link(
+                            link_handle.clone(),
+                            url.to_string(),
+                            false,
+                        ));
+                    }
+                });
+            }
+
+            let state = handle.state::<AppState>().inner().clone();
+            tauri::async_runtime::spawn(services::RecentlyVisitedService::run_auto_import(
+                state.clone(),
+            ));
+            tauri::async_runtime::spawn(services::GroupFolderService::run_auto_sync(state));
+            tauri::async_runtime::spawn(services::ClipboardWatcher::run(handle.clone()));
+            tauri::async_runtime::spawn(services::SessionService::run_keep_alive(handle.clone()));
+
+            let local_api = services::FileService::read_custom_data()
+                .preferences
+                .local_api
+                .filter(|settings| settings.enabled);
+            if let Some(settings) = local_api {
+                let state = handle.state::<AppState>().inner().clone();
+                tauri::async_runtime::spawn(async move {
+                    if let Err(e) = services::LocalApiService::start(state, &settings).await {
+                        log::warn!("Failed to start local API: {}", e);
+                    }
+                });
+            }
+
+            updater::rollback::complete_startup();
             Ok(())
         })
         .run(tauri::generate_context!())
@@ -151,33 +316,50 @@ pub fn run() {
     log::info!("Application started");
 }
 
-fn initialize_app() -> Result<(), String> {
-    match services::initialize_service::initialize_app() {
-        Ok((preferences, folders, worlds, cookies, init_state)) => {
-            let memo_path = BaseDirs::new()
-                .expect("Failed to get base directories")
-                .data_local_dir()
-                .join("VRC_Worlds_Manager_new")
-                .join("memo.json");
-            let memo_manager = MemoManager::load(memo_path)?;
+/// Writes JSON lines to a log file per session
+/// Records are filtered by `logging::filter`, at the levels applied in setup
+fn log_plugin() -> tauri::plugin::TauriPlugin<tauri::Wry> {
+    let timestamp = chrono::Utc::now()
+        .format("%Y-%m-%d_%H-%M-%S.%6f")
+        .to_string();
+    let log_path = format!("vrc-worlds-manager-{}", timestamp);
+    tauri_plugin_log::Builder::new()
+        .target(tauri_plugin_log::Target::new(
+            tauri_plugin_log::TargetKind::LogDir {
+                file_name: Some(log_path),
+            },
+        ))
+        .format(logging::format_json)
+        .level(log::LevelFilter::Trace)
+        .filter(logging::filter)
+        .build()
+}
+
+/// Loads everything but the worlds, which are streamed in afterwards so the window is
+/// usable before a large library is parsed
+fn initialize_app(app: &AppHandle) -> Result<(), String> {
+    match services::initialize_service::initialize_app_without_worlds() {
+        Ok((preferences, folders, cookies, init_state)) => {
+            let memo_manager = MemoManager::load(FileService::get_memo_path())?;
 
             log::info!("App initialized successfully");
-            PREFERENCES.set(RwLock::new(preferences));
-            FOLDERS.set(RwLock::new(folders));
-            WORLDS.set(RwLock::new(worlds));
+            services::SortingService::set_language(&preferences.language);
+            i18n::set_language(&preferences.language);
+            let state = AppState::new(preferences, folders, vec![]);
+            app.manage(state.clone());
             INITSTATE.set(tokio::sync::RwLock::new(init_state));
             let cookie_store = ApiService::initialize_with_cookies(cookies.clone());
             AUTHENTICATOR.set(tokio::sync::RwLock::new(
                 VRChatAPIClientAuthenticator::from_cookie_store(cookie_store),
             ));
             MEMO_MANAGER.set(RwLock::new(memo_manager));
+            load_user_data(&state);
+            services::initialize_service::stream_worlds(app.clone(), state);
             Ok(())
         }
         Err(e) => {
             log::info!("Error initializing app: {}", e);
-            PREFERENCES.set(RwLock::new(PreferenceModel::new()));
-            FOLDERS.set(RwLock::new(vec![]));
-            WORLDS.set(RwLock::new(vec![]));
+            app.manage(AppState::new(PreferenceModel::new(), vec![], vec![]));
             INITSTATE.set(tokio::sync::RwLock::new(InitState::error(e.clone())));
             AUTHENTICATOR.set(tokio::sync::RwLock::new(VRChatAPIClientAuthenticator::new(
                 String::new(),
@@ -186,3 +368,79 @@ fn initialize_app() -> Result<(), String> {
         }
     }
 }
+
+/// Reloads the library from disk, replacing what is in memory
+/// Used to recover from a bad in-memory state without restarting, the session is left as is
+pub(crate) async fn reload_library(state: &AppState) -> Result<(), String> {
+    load_library(state).await?;
+    log::info!("Library reloaded from disk");
+    Ok(())
+}
+
+/// Loads the library and the saved session after the library has been unlocked
+/// Startup left the state empty, as the library could not be read while locked
+pub(crate) async fn load_unlocked_library(state: &AppState) -> Result<(), String> {
+    let (cookies, init_state) = load_library(state).await?;
+
+    let cookie_store = ApiService::initialize_with_cookies(cookies);
+    *AUTHENTICATOR.get().write().await =
+        VRChatAPIClientAuthenticator::from_cookie_store(cookie_store);
+    *INITSTATE.get().write().await = init_state;
+
+    log::info!("Library loaded after unlock");
+    Ok(())
+}
+
+/// Reads the library from disk into the already initialized state
+/// Returns the saved session, for the caller to restore if needed
+async fn load_library(state: &AppState) -> Result<(AuthCookies, InitState), String> {
+    // The worlds being streamed in would be added to the reloaded ones
+    if FileService::is_loading_worlds() {
+        return Err("The library is still loading".to_string());
+    }
+    // Queued writes are newer than what is on disk
+    services::Persistence::flush()
+        .await
+        .map_err(|e| e.to_string())?;
+    let (preferences, folders, worlds, cookies, init_state) =
+        services::initialize_service::initialize_app()?;
+    let memo_manager = MemoManager::load(FileService::get_memo_path())?;
+
+    services::SortingService::set_language(&preferences.language);
+    i18n::set_language(&preferences.language);
+    services::TagService::init();
+    state.replace(preferences, folders, worlds);
+    match MEMO_MANAGER.try_get() {
+        Some(memo) => reset(memo, memo_manager),
+        None => {
+            MEMO_MANAGER.set(RwLock::new(memo_manager));
+        }
+    }
+    load_user_data(state);
+
+    Ok((cookies, init_state))
+}
+
+/// Reads the user's data besides the library into the state, replacing what is in memory
+fn load_user_data(state: &AppState) {
+    reset(state.blacklist_store(), FileService::read_blacklist());
+    reset(
+        state.followed_author_store(),
+        FileService::read_followed_authors(),
+    );
+    reset(
+        state.discovery_cursor_store(),
+        FileService::read_discovery_cursors(),
+    );
+    reset(
+        state.notification_store(),
+        FileService::read_notifications(),
+    );
+    reset(state.account_store(), FileService::read_accounts());
+}
+
+/// Replaces the contents of a lock, clearing poison as the old contents are discarded
+fn reset<T>(lock: &RwLock<T>, value: T) {
+    *lock.write().unwrap_or_else(PoisonError::into_inner) = value;
+    lock.clear_poison();
+}
@@ -76,6 +76,18 @@ impl Display for LogLevel {
     }
 }
 
+impl From<LogLevel> for log::LevelFilter {
+    fn from(value: LogLevel) -> Self {
+        match value {
+            LogLevel::Error => log::LevelFilter::Error,
+            LogLevel::Warn => log::LevelFilter::Warn,
+            LogLevel::Info => log::LevelFilter::Info,
+            LogLevel::Debug => log::LevelFilter::Debug,
+            LogLevel::Trace => log::LevelFilter::Trace,
+        }
+    }
+}
+
 impl From<log::Level> for LogLevel {
     fn from(value: log::Level) -> Self {
         match value {
@@ -91,21 +103,70 @@ impl From<log::Level> for LogLevel {
 #[derive(Serialize, specta::Type, Clone)]
 pub struct LogEntry {
     #[serde(serialize_with = "to_rfc3339_micros")]
-    time: chrono::DateTime<chrono::Local>,
-    level: LogLevel,
-    target: String,
-    message: String,
+    pub time: chrono::DateTime<chrono::Local>,
+    pub level: LogLevel,
+    pub target: String,
+    pub module: Option<String>,
+    pub message: String,
+    /// The first world ID mentioned in the message
+    #[serde(rename = "worldId", skip_serializing_if = "Option::is_none")]
+    pub world_id: Option<String>,
 }
 
 impl LogEntry {
     pub fn new(record: &Record) -> Self {
+        Self::with_message(record, format!("{}", record.args()))
+    }
+
+    pub fn with_message(record: &Record, message: String) -> Self {
         LogEntry {
             time: chrono::Local::now(),
             level: record.level().into(),
             target: record.target().to_string(),
-            message: format!("{}", record.args()),
+            module: record.module_path().map(str::to_string),
+            world_id: find_world_id(&message),
+            message,
         }
     }
+
+    /// Checks whether the entry passes a log viewer filter
+    pub fn matches(&self, filter: &LogFilter) -> bool {
+        filter.level.map_or(true, |level| self.level <= level)
+            && filter
+                .target
+                .as_ref()
+                .map_or(true, |target| self.target.starts_with(target.as_str()))
+            && filter
+                .world_id
+                .as_ref()
+                .map_or(true, |world_id| self.world_id.as_ref() == Some(world_id))
+            && filter.search.as_ref().map_or(true, |search| {
+                self.message.to_lowercase().contains(&search.to_lowercase())
+            })
+    }
+}
+
+/// Filter for `get_recent_logs`, every field is optional
+#[derive(Deserialize, specta::Type, Debug, Clone, Default)]
+pub struct LogFilter {
+    /// The least severe level to include
+    pub level: Option<LogLevel>,
+    /// Prefix of the target, e.g. "app_lib::services"
+    pub target: Option<String>,
+    #[serde(rename = "worldId")]
+    pub world_id: Option<String>,
+    /// Text the message contains, ignoring case
+    pub search: Option<String>,
+}
+
+fn find_world_id(message: &str) -> Option<String> {
+    message.match_indices("wrld_").find_map(|(start, _)| {
+        let id = message.get(start..start + 41)?;
+        id[5..]
+            .chars()
+            .all(|c| c.is_ascii_hexdigit() || c == '-')
+            .then(|| id.to_string())
+    })
 }
 
 impl Display for LogEntry {
@@ -137,3 +198,58 @@ pub enum LogChannelMessage {
     Log(LogEntry),
     Flush(mpsc::Sender<()>),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WORLD_ID: &str = "wrld_4cf554b4-430c-4f8f-b53e-1f294eed230b";
+
+    fn entry(level: log::Level, target: &str, message: &str) -> LogEntry {
+        LogEntry::new(
+            &Record::builder()
+                .args(format_args!("{}", message))
+                .level(level)
+                .target(target)
+                .build(),
+        )
+    }
+
+    #[test]
+    fn test_entry_finds_world_id() {
+        let found = entry(
+            log::Level::Info,
+            "app_lib",
+            &format!("Failed to refresh world {}: timeout", WORLD_ID),
+        );
+        assert_eq!(found.world_id.as_deref(), Some(WORLD_ID));
+
+        let short = entry(log::Level::Info, "app_lib", "Failed to refresh wrld_x");
+        assert_eq!(short.world_id, None);
+    }
+
+    #[test]
+    fn test_entry_matches_filter() {
+        let warning = entry(
+            log::Level::Warn,
+            "app_lib::services::api_service",
+            &format!("World {} is Private", WORLD_ID),
+        );
+
+        assert!(warning.matches(&LogFilter::default()));
+        assert!(warning.matches(&LogFilter {
+            level: Some(LogLevel::Warn),
+            target: Some("app_lib::services".to_string()),
+            world_id: Some(WORLD_ID.to_string()),
+            search: Some("private".to_string()),
+        }));
+        assert!(!warning.matches(&LogFilter {
+            level: Some(LogLevel::Error),
+            ..Default::default()
+        }));
+        assert!(!warning.matches(&LogFilter {
+            target: Some("app_lib::api".to_string()),
+            ..Default::default()
+        }));
+    }
+}
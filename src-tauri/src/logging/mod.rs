@@ -1,4 +1,5 @@
 mod definitions;
 mod worker;
 
-pub use worker::purge_outdated_logs;
+pub use definitions::{LogEntry, LogFilter, LogLevel};
+pub use worker::{apply_levels, filter, format_json, get_recent_logs, purge_outdated_logs};
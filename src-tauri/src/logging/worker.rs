@@ -10,23 +10,99 @@
  * Further modifications by @Raifa21
  */
 
+use std::collections::BTreeMap;
 use std::path::PathBuf;
-use std::sync::Mutex;
+use std::sync::{Mutex, OnceLock, PoisonError};
 
+use log::LevelFilter;
 use ringbuffer::{ConstGenericRingBuffer, RingBuffer};
 
-use super::definitions::LogEntry;
+use super::definitions::{LogEntry, LogFilter, LogLevel};
 
 const LOG_RETENTION_DAYS: i64 = 14;
 
-static LOG_BUFFER: Mutex<ConstGenericRingBuffer<LogEntry, 256>> =
+/// Level of modules without a level set in the preferences
+const DEFAULT_LEVEL: LevelFilter = LevelFilter::Info;
+
+static LOG_BUFFER: Mutex<ConstGenericRingBuffer<LogEntry, 1024>> =
     Mutex::new(ConstGenericRingBuffer::new());
 
+/// Levels of the modules set in the preferences, see `apply_levels`
+static MODULE_LEVELS: OnceLock<Vec<(String, LevelFilter)>> = OnceLock::new();
+
+/// Applies the log levels set in the preferences, e.g. "app_lib::api" to Debug
+/// Called once from setup, as the preferences can't be read before the log plugin is built.
+/// Later changes apply from the next start
+pub fn apply_levels(levels: BTreeMap<String, LogLevel>) {
+    let levels: Vec<(String, LevelFilter)> = levels
+        .into_iter()
+        .map(|(module, level)| (module, level.into()))
+        .collect();
+    let max_level = levels
+        .iter()
+        .map(|(_, level)| *level)
+        .fold(DEFAULT_LEVEL, LevelFilter::max);
+    if MODULE_LEVELS.set(levels).is_err() {
+        log::warn!("Log levels were already applied");
+        return;
+    }
+    log::set_max_level(max_level);
+}
+
+/// Whether a record is logged, at the level of the most specific module set for its target
+pub fn filter(metadata: &log::Metadata) -> bool {
+    let levels = MODULE_LEVELS.get().map(Vec::as_slice).unwrap_or_default();
+    metadata.level() <= module_level(levels, metadata.target())
+}
+
+fn module_level(levels: &[(String, LevelFilter)], target: &str) -> LevelFilter {
+    levels
+        .iter()
+        .filter(|(module, _)| {
+            target
+                .strip_prefix(module.as_str())
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+        })
+        .max_by_key(|(module, _)| module.len())
+        .map_or(DEFAULT_LEVEL, |(_, level)| *level)
+}
+
 pub fn get_logs() -> Vec<LogEntry> {
     let buffer = LOG_BUFFER.lock().unwrap();
     buffer.iter().cloned().collect()
 }
 
+/// Keeps an entry for the in-app log viewer, dropping the oldest once the buffer is full
+pub fn push_log(entry: LogEntry) {
+    let mut buffer = LOG_BUFFER.lock().unwrap_or_else(PoisonError::into_inner);
+    buffer.push(entry);
+}
+
+/// The buffered entries which pass the filter, oldest first
+pub fn get_recent_logs(filter: &LogFilter) -> Vec<LogEntry> {
+    let buffer = LOG_BUFFER.lock().unwrap_or_else(PoisonError::into_inner);
+    buffer
+        .iter()
+        .filter(|e| e.matches(filter))
+        .cloned()
+        .collect()
+}
+
+/// Formats a record as one JSON line for the log file, and buffers it for the log viewer
+/// Every record passes through here once, whichever targets it is written to
+pub fn format_json(
+    out: tauri_plugin_log::fern::FormatCallback,
+    message: &std::fmt::Arguments,
+    record: &log::Record,
+) {
+    let entry = LogEntry::with_message(record, message.to_string());
+    match serde_json::to_string(&entry) {
+        Ok(line) => out.finish(format_args!("{}", line)),
+        Err(_) => out.finish(format_args!("{}", entry)),
+    }
+    push_log(entry);
+}
+
 pub fn purge_outdated_logs(logs_dir: &PathBuf) -> Result<(), std::io::Error> {
     log::info!("Checking logs in directory: {}", logs_dir.display());
     let readdir = logs_dir.read_dir()?;
@@ -104,4 +180,23 @@ mod tests {
     fn test_invalid_timestamp() {
         assert!(!is_outdated_timestamp("invalid"));
     }
+
+    #[test]
+    fn test_module_level() {
+        let levels = vec![
+            ("app_lib::api".to_string(), LevelFilter::Debug),
+            ("app_lib::api::auth".to_string(), LevelFilter::Warn),
+        ];
+        assert_eq!(module_level(&levels, "app_lib::api"), LevelFilter::Debug);
+        assert_eq!(
+            module_level(&levels, "app_lib::api::world::logic"),
+            LevelFilter::Debug
+        );
+        assert_eq!(
+            module_level(&levels, "app_lib::api::auth::logic"),
+            LevelFilter::Warn
+        );
+        assert_eq!(module_level(&levels, "app_lib::apis"), DEFAULT_LEVEL);
+        assert_eq!(module_level(&[], "app_lib::api"), DEFAULT_LEVEL);
+    }
 }
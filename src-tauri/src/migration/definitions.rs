@@ -101,3 +101,70 @@ pub struct PreviousMetadata {
     pub number_of_folders: u32,
     pub number_of_worlds: u32,
 }
+
+/// A folder as it will be migrated
+#[derive(Debug, Serialize, Type)]
+pub struct MigrationFolderPreview {
+    pub name: String,
+    pub number_of_worlds: u32,
+}
+
+/// A world listed more than once in the old worlds file, which is migrated once
+#[derive(Debug, Serialize, Type)]
+pub struct MigrationDuplicate {
+    pub world_id: String,
+    pub world_name: String,
+    pub occurrences: u32,
+}
+
+/// What migrating the old VRC Worlds Manager's data would produce, without migrating it
+#[derive(Debug, Serialize, Type)]
+pub struct MigrationPreview {
+    /// The folders in their old order, without the Hidden and Unclassified folders
+    pub folders: Vec<MigrationFolderPreview>,
+    pub number_of_worlds: u32,
+    /// Worlds in the old Hidden folder, which are migrated as hidden worlds
+    pub number_of_hidden_worlds: u32,
+    /// Worlds found only in the folders file, which are migrated too
+    pub number_of_worlds_only_in_folders: u32,
+    pub duplicates: Vec<MigrationDuplicate>,
+    /// Worlds without a date added, which are dated a minute apart before `earliest_date_added`
+    pub number_of_worlds_without_date: u32,
+    pub earliest_date_added: Option<DateTime<Utc>>,
+    pub date_order: DateOrder,
+    /// Last update dates which could not be read, and ones whose day and month could be
+    /// either way round while the other dates do not settle the order
+    pub unreadable_dates: Vec<MigrationDateIssue>,
+    pub ambiguous_dates: Vec<MigrationDateIssue>,
+}
+
+/// How the old app wrote the day and month of dates, which followed the system locale
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Type)]
+pub enum DateOrder {
+    MonthFirst,
+    DayFirst,
+}
+
+/// A world whose last update date was unreadable or ambiguous
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct MigrationDateIssue {
+    pub world_id: String,
+    pub world_name: String,
+    /// The date as written in the old worlds file
+    pub value: String,
+}
+
+/// What migrating the old VRC Worlds Manager's data did
+#[derive(Debug, Serialize, Type)]
+pub struct MigrationReport {
+    pub number_of_folders: u32,
+    pub number_of_worlds: u32,
+    pub date_order: DateOrder,
+    /// Worlds whose last update could not be read, which is left unknown until they are
+    /// next fetched
+    pub unreadable_dates: Vec<MigrationDateIssue>,
+    pub ambiguous_dates: Vec<MigrationDateIssue>,
+    /// Old memos of worlds which had none, and old memos appended to an existing one
+    pub memos_imported: u32,
+    pub memos_merged: u32,
+}
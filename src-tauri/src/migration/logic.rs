@@ -1,5 +1,9 @@
-use crate::definitions::{FolderModel, WorldApiData, WorldModel, WorldUserData};
-use crate::migration::{PreviousFolderCollection, PreviousMetadata, PreviousWorldModel};
+use crate::definitions::{FolderModel, WorldApiData, WorldAvailability, WorldModel, WorldUserData};
+use crate::migration::{
+    DateOrder, MigrationDateIssue, MigrationDuplicate, MigrationFolderPreview, MigrationPreview,
+    MigrationReport, PreviousFolderCollection, PreviousMetadata, PreviousWorldModel,
+};
+use crate::services::memo_manager::MemoManager;
 use crate::services::EncryptionService;
 use crate::services::FileService;
 use chrono::{DateTime, Duration, Utc};
@@ -10,6 +14,15 @@ use std::sync::RwLock;
 
 pub struct MigrationService;
 
+/// How a last update date from the old app reads
+#[derive(Debug, PartialEq)]
+enum LastUpdate {
+    Exact(chrono::NaiveDate),
+    /// Day and month could be either way round, so were read in the assumed order
+    Ambiguous(chrono::NaiveDate),
+    Unreadable,
+}
+
 impl MigrationService {
     /// Tries to locate the old VRC Worlds Manager Data
     /// Called from setup page
@@ -135,6 +148,7 @@ impl MigrationService {
         old_world: &PreviousWorldModel,
         date: DateTime<Utc>,
         hidden: bool,
+        date_order: DateOrder,
     ) -> WorldModel {
         WorldModel {
             api_data: WorldApiData {
@@ -147,37 +161,22 @@ impl MigrationService {
                 recommended_capacity: None,
                 tags: vec![],
                 publication_date: None,
-                last_update: {
-                    let parts: Vec<&str> = old_world.last_update.split('/').collect();
-                    if parts.len() == 3 {
-                        // Parse mm/dd/yyyy format
-                        let month: u32 = parts[0].parse().unwrap_or(1);
-                        let day: u32 = parts[1].parse().unwrap_or(1);
-                        let year: i32 = parts[2].parse().unwrap_or(2024);
-
-                        // Create DateTime<Utc> directly
-                        DateTime::from_naive_utc_and_offset(
-                            chrono::NaiveDateTime::new(
-                                chrono::NaiveDate::from_ymd_opt(year, month, day)
-                                    .unwrap_or_default(),
-                                chrono::NaiveTime::from_hms_opt(0, 0, 0).unwrap_or_default(),
-                            ),
-                            Utc,
-                        )
-                    } else {
+                last_update: match Self::parse_last_update(&old_world.last_update, date_order) {
+                    LastUpdate::Exact(date) | LastUpdate::Ambiguous(date) => {
                         DateTime::from_naive_utc_and_offset(
-                            chrono::NaiveDateTime::new(
-                                chrono::NaiveDate::from_ymd_opt(2024, 1, 1).unwrap_or_default(),
-                                chrono::NaiveTime::from_hms_opt(0, 0, 0).unwrap_or_default(),
-                            ),
+                            date.and_hms_opt(0, 0, 0).unwrap_or_default(),
                             Utc,
                         )
                     }
+                    // Unknown until the world is next fetched, as any fetched date is newer
+                    LastUpdate::Unreadable => DateTime::UNIX_EPOCH,
                 },
                 description: old_world.description.clone(),
                 visits: old_world.visits,
                 favorites: old_world.favorites,
                 platform: old_world.platform.clone().unwrap_or_default(),
+                packages: vec![],
+                availability: WorldAvailability::Public,
             },
             user_data: WorldUserData {
                 date_added: date,
@@ -194,10 +193,119 @@ impl MigrationService {
                 is_photographed: false,
                 is_shared: false,
                 is_favorite: false,
+                is_event_critical: false,
+                rating: None,
+                launch_count: 0,
+                cover_image: None,
+                in_inbox: false,
             },
         }
     }
 
+    /// Combines the memo kept for a world with the old app's memo for it
+    /// The old memo is appended below the current one, unless the current one already has it
+    fn merge_memo(current: &str, old: &str) -> String {
+        let old = old.trim();
+        if old.is_empty() || current.contains(old) {
+            current.to_string()
+        } else if current.trim().is_empty() {
+            old.to_string()
+        } else {
+            format!("{}\n\n{}", current.trim_end(), old)
+        }
+    }
+
+    /// Reads a last update date as written by the old app, which followed the system locale:
+    /// month/day/year, day/month/year or year/month/day, separated by `/`, `-` or `.`
+    /// Day and month are told apart by whichever is over 12, otherwise read in `date_order`
+    fn parse_last_update(value: &str, date_order: DateOrder) -> LastUpdate {
+        let Some([first, second, third]) = Self::date_parts(value) else {
+            return LastUpdate::Unreadable;
+        };
+        let (year, month, day, ambiguous) = if first > 31 {
+            (first, second, third, false)
+        } else {
+            let day_first = match (first > 12, second > 12) {
+                (true, false) => true,
+                (false, true) => false,
+                _ => date_order == DateOrder::DayFirst,
+            };
+            let ambiguous = first <= 12 && second <= 12 && first != second;
+            if day_first {
+                (third, second, first, ambiguous)
+            } else {
+                (third, first, second, ambiguous)
+            }
+        };
+
+        match chrono::NaiveDate::from_ymd_opt(year as i32, month, day) {
+            Some(date) if year >= 1000 && ambiguous => LastUpdate::Ambiguous(date),
+            Some(date) if year >= 1000 => LastUpdate::Exact(date),
+            _ => LastUpdate::Unreadable,
+        }
+    }
+
+    /// The three numbers of a date, ignoring any time after it
+    fn date_parts(value: &str) -> Option<[u32; 3]> {
+        let date = value.split_whitespace().next()?;
+        let parts: Vec<u32> = date
+            .split(['/', '-', '.'])
+            .map(|part| part.parse().ok())
+            .collect::<Option<_>>()?;
+        parts.try_into().ok()
+    }
+
+    /// Works out the day and month order from the dates which settle it
+    ///
+    /// # Returns
+    /// The order, and whether the dates settled it, which they do not if none of them has
+    /// a day over 12, or they disagree
+    fn infer_date_order<'a>(values: impl Iterator<Item = &'a str>) -> (DateOrder, bool) {
+        let (mut day_first, mut month_first) = (0, 0);
+        for [first, second, _] in values.filter_map(Self::date_parts) {
+            match (first, second) {
+                (13..=31, 1..=12) => day_first += 1,
+                (1..=12, 13..=31) => month_first += 1,
+                _ => {}
+            }
+        }
+        let order = if day_first > month_first {
+            DateOrder::DayFirst
+        } else {
+            DateOrder::MonthFirst
+        };
+        (order, (day_first == 0) != (month_first == 0))
+    }
+
+    /// Reads the last update dates of the worlds to migrate
+    ///
+    /// # Returns
+    /// The day and month order, the dates which could not be read, and the dates whose
+    /// order is a guess, sorted by world ID
+    fn check_dates<'a>(
+        worlds: impl Iterator<Item = &'a PreviousWorldModel> + Clone,
+    ) -> (DateOrder, Vec<MigrationDateIssue>, Vec<MigrationDateIssue>) {
+        let (date_order, settled) =
+            Self::infer_date_order(worlds.clone().map(|w| w.last_update.as_str()));
+        let mut unreadable = vec![];
+        let mut ambiguous = vec![];
+        for world in worlds {
+            let issue = || MigrationDateIssue {
+                world_id: world.world_id.clone(),
+                world_name: world.world_name.clone(),
+                value: world.last_update.clone(),
+            };
+            match Self::parse_last_update(&world.last_update, date_order) {
+                LastUpdate::Unreadable => unreadable.push(issue()),
+                LastUpdate::Ambiguous(_) if !settled => ambiguous.push(issue()),
+                _ => {}
+            }
+        }
+        unreadable.sort_by(|a, b| a.world_id.cmp(&b.world_id));
+        ambiguous.sort_by(|a, b| a.world_id.cmp(&b.world_id));
+        (date_order, unreadable, ambiguous)
+    }
+
     fn deduplicate_with_pattern(old_worlds: Vec<PreviousWorldModel>) -> Vec<PreviousWorldModel> {
         let mut unique_worlds: HashMap<String, (PreviousWorldModel, usize)> = HashMap::new();
         let mut first_duplicate_idx = None;
@@ -238,6 +346,10 @@ impl MigrationService {
     /// # Arguments
     /// * `path_to_worlds` - The path to the old VRC Worlds Manager Worlds file
     /// * `path_to_folders` - The path to the old VRC Worlds Manager Folders file
+    /// * `memos` - The memos, which the old memos are merged into
+    ///
+    /// # Returns
+    /// Returns the counts of migrated worlds and folders, and the dates which could not be read
     ///
     /// # Errors
     /// Returns an error message if the old VRC Worlds Manager Data could not be migrated
@@ -246,7 +358,8 @@ impl MigrationService {
         path_to_folders: String,
         worlds: &RwLock<Vec<WorldModel>>,
         folders: &RwLock<Vec<FolderModel>>,
-    ) -> Result<(), String> {
+        memos: &RwLock<MemoManager>,
+    ) -> Result<MigrationReport, String> {
         let (worlds_content, folders_content) =
             Self::read_data_files(&path_to_worlds, &path_to_folders).await?;
         log::info!("Reading worlds and folders data...");
@@ -291,6 +404,16 @@ impl MigrationService {
         }
 
         let merged_worlds = Self::deduplicate_with_pattern(merged_worlds);
+        let (date_order, unreadable_dates, ambiguous_dates) =
+            Self::check_dates(merged_worlds.iter());
+        if !unreadable_dates.is_empty() || !ambiguous_dates.is_empty() {
+            log::warn!(
+                "{} unreadable and {} ambiguous last update dates, read as {:?}",
+                unreadable_dates.len(),
+                ambiguous_dates.len(),
+                date_order
+            );
+        }
         for (idx, old_world) in merged_worlds.iter().enumerate() {
             let is_hidden = hidden_world_ids.contains(&old_world.world_id);
             let utc_date = DateTime::from_naive_utc_and_offset(
@@ -300,7 +423,9 @@ impl MigrationService {
                     .unwrap_or_else(|| chrono::Utc::now().naive_utc()),
                 chrono::Utc,
             );
-            new_worlds.push(Self::convert_to_new_model(old_world, utc_date, is_hidden));
+            new_worlds.push(Self::convert_to_new_model(
+                old_world, utc_date, is_hidden, date_order,
+            ));
         }
 
         for folder in old_folders {
@@ -319,10 +444,37 @@ impl MigrationService {
                     world_ids,
                     share: None,
                     color: None,
+                    icon: None,
+                    description: None,
+                    sort: None,
+                    archived: false,
                 });
             }
         }
 
+        let mut memos_lock = memos.write().map_err(|e| {
+            log::error!("Failed to acquire write lock for memos: {}", e);
+            "Failed to acquire write lock for memos".to_string()
+        })?;
+        let (mut memos_imported, mut memos_merged) = (0, 0);
+        for world in new_worlds.iter_mut() {
+            let world_id = &world.api_data.world_id;
+            let current = memos_lock.get_memo(world_id).unwrap_or_default();
+            let memo = Self::merge_memo(current, &world.user_data.memo);
+            if memo != current {
+                if current.trim().is_empty() {
+                    memos_imported += 1;
+                } else {
+                    memos_merged += 1;
+                }
+                memos_lock.set_memo(world_id, &memo);
+            }
+            world.user_data.memo = memo;
+        }
+
+        let new_world_count = new_worlds.len() as u32;
+        let new_folder_count = new_folders.len() as u32;
+
         // Always overwrite both worlds and folders
         {
             let mut worlds_lock = worlds.write().map_err(|e| {
@@ -346,8 +498,22 @@ impl MigrationService {
             FileService::write_folders(&*folders_lock).map_err(|e| e.to_string())?;
             log::info!("Retrieved {} folders", folders_lock.len());
         }
+        memos_lock.save()?;
+        log::info!(
+            "Imported {} memos, merged {} into existing memos",
+            memos_imported,
+            memos_merged
+        );
 
-        Ok(())
+        Ok(MigrationReport {
+            number_of_folders: new_folder_count,
+            number_of_worlds: new_world_count,
+            date_order,
+            unreadable_dates,
+            ambiguous_dates,
+            memos_imported,
+            memos_merged,
+        })
     }
 
     /// Generate metadata from the previous worlds and folders
@@ -408,6 +574,110 @@ impl MigrationService {
             number_of_worlds: merged_worlds.len() as u32,
         })
     }
+
+    /// Previews the migration of the old VRC Worlds Manager Data, without changing anything
+    /// Called from setup page, so users can check the result before migrating
+    ///
+    /// # Arguments
+    /// * old_worlds_path - The path to the old VRC Worlds Manager Worlds file
+    /// * old_folders_path - The path to the old VRC Worlds Manager Folders file
+    ///
+    /// # Returns
+    /// Returns the folders, duplicates and reconstructed dates the migration would produce
+    ///
+    /// # Errors
+    /// Returns an error message if the old VRC Worlds Manager Data could not be read
+    pub async fn preview_migration(
+        old_worlds_path: String,
+        old_folders_path: String,
+    ) -> Result<MigrationPreview, String> {
+        let (worlds_content, folders_content) =
+            Self::read_data_files(&old_worlds_path, &old_folders_path)
+                .await
+                .map_err(|e| format!("Failed to read data files: {}", e))?;
+
+        let old_worlds = Self::parse_world_data(&worlds_content)
+            .map_err(|e| format!("Failed to parse worlds: {}", e))?;
+        let old_folders = Self::parse_folder_data(&folders_content)
+            .map_err(|e| format!("Failed to parse folders: {}", e))?;
+
+        Ok(Self::build_preview(&old_worlds, &old_folders))
+    }
+
+    /// Works out what migrating the given worlds and folders would produce,
+    /// the same way `migrate_old_data` merges and dates them
+    fn build_preview(
+        old_worlds: &[PreviousWorldModel],
+        old_folders: &[PreviousFolderCollection],
+    ) -> MigrationPreview {
+        let mut occurrences: HashMap<&str, (&PreviousWorldModel, u32)> = HashMap::new();
+        let mut duplicate_order = vec![];
+        for world in old_worlds {
+            let entry = occurrences
+                .entry(world.world_id.as_str())
+                .or_insert((world, 0));
+            entry.1 += 1;
+            if entry.1 == 2 {
+                duplicate_order.push(world.world_id.as_str());
+            }
+        }
+        let duplicates = duplicate_order
+            .into_iter()
+            .map(|id| {
+                let (world, count) = occurrences[id];
+                MigrationDuplicate {
+                    world_id: world.world_id.clone(),
+                    world_name: world.world_name.clone(),
+                    occurrences: count,
+                }
+            })
+            .collect();
+
+        let mut world_map: HashMap<&str, &PreviousWorldModel> = occurrences
+            .iter()
+            .map(|(id, (world, _))| (*id, *world))
+            .collect();
+        let mut only_in_folders = 0;
+        for world in old_folders.iter().flat_map(|f| &f.worlds) {
+            if !world_map.contains_key(world.world_id.as_str()) {
+                world_map.insert(world.world_id.as_str(), world);
+                only_in_folders += 1;
+            }
+        }
+
+        let hidden_ids: HashSet<&str> = old_folders
+            .iter()
+            .filter(|f| f.name == "Hidden")
+            .flat_map(|f| f.worlds.iter().map(|w| w.world_id.as_str()))
+            .collect();
+        let folders = old_folders
+            .iter()
+            .filter(|f| f.name != "Hidden" && f.name != "Unclassified")
+            .map(|f| MigrationFolderPreview {
+                name: f.name.clone(),
+                number_of_worlds: f.worlds.len() as u32,
+            })
+            .collect();
+
+        let dated = world_map.values().filter_map(|w| w.date_added);
+        let (date_order, unreadable_dates, ambiguous_dates) =
+            Self::check_dates(world_map.values().copied());
+        MigrationPreview {
+            folders,
+            number_of_worlds: world_map.len() as u32,
+            number_of_hidden_worlds: hidden_ids.len() as u32,
+            number_of_worlds_only_in_folders: only_in_folders,
+            duplicates,
+            number_of_worlds_without_date: world_map
+                .values()
+                .filter(|w| w.date_added.is_none())
+                .count() as u32,
+            earliest_date_added: dated.min(),
+            date_order,
+            unreadable_dates,
+            ambiguous_dates,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -535,8 +805,12 @@ mod tests {
             ..PreviousWorldModel::default()
         };
 
-        let converted =
-            MigrationService::convert_to_new_model(&old_world, DateTime::default(), false);
+        let converted = MigrationService::convert_to_new_model(
+            &old_world,
+            DateTime::default(),
+            false,
+            DateOrder::MonthFirst,
+        );
 
         let expected: DateTime<Utc> = DateTime::from_naive_utc_and_offset(
             chrono::NaiveDate::from_ymd_opt(2024, 3, 14)
@@ -650,4 +924,101 @@ mod tests {
         assert_eq!(folders[1].worlds[0].world_id, "wrld_789");
         Ok(())
     }
+
+    #[test]
+    fn test_build_preview() {
+        let world = |id: &str, last_update: &str| PreviousWorldModel {
+            world_id: id.to_string(),
+            world_name: format!("World {}", id),
+            last_update: last_update.to_string(),
+            ..PreviousWorldModel::default()
+        };
+        let old_worlds = vec![
+            world("wrld_1", "03/11/2024"),
+            world("wrld_2", "unknown"),
+            world("wrld_1", "03/11/2024"),
+        ];
+        let old_folders = vec![
+            PreviousFolderCollection {
+                name: "Favorites".to_string(),
+                worlds: vec![world("wrld_1", "03/11/2024"), world("wrld_3", "01/02/2023")],
+            },
+            PreviousFolderCollection {
+                name: "Hidden".to_string(),
+                worlds: vec![world("wrld_2", "unknown")],
+            },
+        ];
+
+        let preview = MigrationService::build_preview(&old_worlds, &old_folders);
+
+        assert_eq!(preview.number_of_worlds, 3);
+        assert_eq!(preview.number_of_hidden_worlds, 1);
+        assert_eq!(preview.number_of_worlds_only_in_folders, 1);
+        assert_eq!(preview.folders.len(), 1);
+        assert_eq!(preview.folders[0].number_of_worlds, 2);
+        assert_eq!(preview.duplicates.len(), 1);
+        assert_eq!(preview.duplicates[0].world_id, "wrld_1");
+        assert_eq!(preview.duplicates[0].occurrences, 2);
+        assert_eq!(preview.number_of_worlds_without_date, 3);
+        assert_eq!(preview.earliest_date_added, None);
+        assert_eq!(preview.unreadable_dates.len(), 1);
+        assert_eq!(preview.unreadable_dates[0].world_id, "wrld_2");
+    }
+
+    #[test]
+    fn test_parse_last_update_formats() {
+        let date = |y, m, d| chrono::NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        let parse = MigrationService::parse_last_update;
+
+        assert_eq!(
+            parse("14/03/2024", DateOrder::MonthFirst),
+            LastUpdate::Exact(date(2024, 3, 14))
+        );
+        assert_eq!(
+            parse("2024-03-14", DateOrder::DayFirst),
+            LastUpdate::Exact(date(2024, 3, 14))
+        );
+        assert_eq!(
+            parse("04.03.2024 12:00:00", DateOrder::DayFirst),
+            LastUpdate::Ambiguous(date(2024, 3, 4))
+        );
+        assert_eq!(
+            parse("04/03/2024", DateOrder::MonthFirst),
+            LastUpdate::Ambiguous(date(2024, 4, 3))
+        );
+        assert_eq!(parse("", DateOrder::MonthFirst), LastUpdate::Unreadable);
+        assert_eq!(
+            parse("13/13/2024", DateOrder::MonthFirst),
+            LastUpdate::Unreadable
+        );
+    }
+
+    #[test]
+    fn test_merge_memo() {
+        assert_eq!(MigrationService::merge_memo("", " old memo "), "old memo");
+        assert_eq!(MigrationService::merge_memo("new memo", ""), "new memo");
+        assert_eq!(
+            MigrationService::merge_memo("new memo\nold memo", "old memo"),
+            "new memo\nold memo"
+        );
+        assert_eq!(
+            MigrationService::merge_memo("new memo\n", "old memo"),
+            "new memo\n\nold memo"
+        );
+    }
+
+    #[test]
+    fn test_infer_date_order() {
+        let values = ["04/03/2024", "25/12/2023"];
+        assert_eq!(
+            MigrationService::infer_date_order(values.into_iter()),
+            (DateOrder::DayFirst, true)
+        );
+
+        let values = ["04/03/2024", "05/06/2023"];
+        assert_eq!(
+            MigrationService::infer_date_order(values.into_iter()),
+            (DateOrder::MonthFirst, false)
+        );
+    }
 }
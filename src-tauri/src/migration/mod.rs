@@ -1,5 +1,10 @@
 mod definitions;
 mod logic;
+mod schema;
 
-pub use definitions::{PreviousFolderCollection, PreviousMetadata, PreviousWorldModel};
+pub use definitions::{
+    DateOrder, MigrationDateIssue, MigrationDuplicate, MigrationFolderPreview, MigrationPreview,
+    MigrationReport, PreviousFolderCollection, PreviousMetadata, PreviousWorldModel,
+};
 pub use logic::MigrationService;
+pub use schema::{DataFile, SchemaMigrations};
@@ -0,0 +1,262 @@
+use serde_json::Value;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::errors::FileError;
+use crate::services::{FileService, LibraryEncryption, WorldLog, WorldStorage};
+
+/// The data files whose format is versioned
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataFile {
+    Preferences,
+    Worlds,
+    Folders,
+    CustomData,
+}
+
+impl DataFile {
+    /// Custom data comes first, as it holds the versions of worlds.json and folders.json
+    const ALL: [DataFile; 4] = [
+        DataFile::CustomData,
+        DataFile::Preferences,
+        DataFile::Worlds,
+        DataFile::Folders,
+    ];
+
+    pub fn file_name(self) -> &'static str {
+        match self {
+            DataFile::Preferences => "preferences.json",
+            DataFile::Worlds => "worlds.json",
+            DataFile::Folders => "folders.json",
+            DataFile::CustomData => "custom_data.json",
+        }
+    }
+
+    /// The schema version this version of the app reads and writes
+    pub fn current_version(self) -> u32 {
+        match self {
+            DataFile::Preferences => 1,
+            DataFile::Worlds => 1,
+            DataFile::Folders => 1,
+            DataFile::CustomData => 1,
+        }
+    }
+
+    /// The field holding the version, None for files kept in the original app's format
+    /// worlds.json and folders.json stay bare arrays, as older versions reset files they
+    /// cannot parse, so their versions are kept in custom_data.json instead
+    fn version_key(self) -> Option<&'static str> {
+        match self {
+            DataFile::Preferences => Some("schemaVersion"),
+            DataFile::CustomData => Some("version"),
+            DataFile::Worlds | DataFile::Folders => None,
+        }
+    }
+
+    fn path(self) -> PathBuf {
+        let (preferences_path, folders_path, worlds_path, _) = FileService::get_paths();
+        match self {
+            DataFile::Preferences => preferences_path,
+            DataFile::Worlds => worlds_path,
+            DataFile::Folders => folders_path,
+            DataFile::CustomData => FileService::get_custom_data_path(),
+        }
+    }
+}
+
+/// A step upgrading a file from one version to the next
+struct Migration {
+    file: DataFile,
+    from: u32,
+    description: &'static str,
+    apply: fn(&mut Value),
+}
+
+/// Every migration, in order
+/// Files without a version are version 0, the format from before files were versioned
+/// Versions without a step only mark that the format did not change
+///
+/// Steps for worlds.json and folders.json must be safe to run on the current format too,
+/// as files whose version was never recorded are treated as version 0
+const MIGRATIONS: &[Migration] = &[Migration {
+    file: DataFile::Worlds,
+    from: 0,
+    description: "remove duplicate platforms",
+    apply: dedupe_world_platforms,
+}];
+
+fn dedupe_world_platforms(worlds: &mut Value) {
+    let Some(worlds) = worlds.as_array_mut() else {
+        return;
+    };
+    for world in worlds {
+        if let Some(platforms) = world.get_mut("platform").and_then(Value::as_array_mut) {
+            let mut seen = HashSet::new();
+            platforms.retain(|platform| seen.insert(platform.to_string()));
+        }
+    }
+}
+
+/// Upgrades the data files stepwise to the versions this version of the app uses
+pub struct SchemaMigrations;
+
+impl SchemaMigrations {
+    /// Upgrades every data file to its current version, called before the library is loaded
+    /// Each file is copied to `<name>.v<version>.bak` before it is upgraded
+    /// Missing, locked and unreadable files are left to the readers
+    ///
+    /// # Errors
+    /// Returns `FileError::NewerSchema` if a file was written by a newer version of the app,
+    /// after keeping a copy, so it is neither loaded nor overwritten by this version
+    /// Returns a FileError if an upgraded file could not be written
+    pub fn run() -> Result<(), FileError> {
+        let mut array_versions = FileService::read_custom_data().schema_versions;
+        let mut versions_changed = false;
+
+        for file in DataFile::ALL {
+            let recorded = array_versions.get(file.file_name()).copied().unwrap_or(0);
+            let Some(version) = Self::migrate_file(file, recorded)? else {
+                continue;
+            };
+            if file.version_key().is_none() && version != recorded {
+                array_versions.insert(file.file_name().to_string(), version);
+                versions_changed = true;
+            }
+        }
+
+        if versions_changed {
+            let mut custom_data = FileService::read_custom_data();
+            custom_data.schema_versions = array_versions;
+            FileService::write_custom_data(&custom_data)?;
+        }
+        Ok(())
+    }
+
+    /// Upgrades one file, returning its version afterwards, None if it could not be read
+    fn migrate_file(file: DataFile, recorded: u32) -> Result<Option<u32>, FileError> {
+        // Files versioned in custom_data.json are only parsed when they need upgrading,
+        // worlds.json is too large to parse twice on every start
+        if file.version_key().is_none() && recorded == file.current_version() {
+            return Ok(Some(recorded));
+        }
+
+        let path = file.path();
+        let stored_path = match file {
+            DataFile::Worlds => WorldStorage::stored_path(&path),
+            _ => path.clone(),
+        };
+        let Ok(raw) = fs::read(&stored_path) else {
+            return Ok(None);
+        };
+        let Ok(data) = LibraryEncryption::open_bytes(&raw) else {
+            return Ok(None);
+        };
+        let Ok(data) = WorldStorage::decode(&data) else {
+            return Ok(None);
+        };
+        let Ok(mut value) = serde_json::from_str::<Value>(&data) else {
+            return Ok(None);
+        };
+
+        let version = match file.version_key() {
+            Some(key) => value.get(key).and_then(Value::as_u64).unwrap_or(0) as u32,
+            None => recorded,
+        };
+        if version == file.current_version() {
+            return Ok(Some(version));
+        }
+
+        Self::keep_copy(&stored_path, version);
+        if file == DataFile::Worlds {
+            WorldLog::replay_json(&mut value);
+        }
+        Self::upgrade(file, version, &mut value)?;
+        log::info!(
+            "Upgraded {} from schema version {} to {}",
+            file.file_name(),
+            version,
+            file.current_version()
+        );
+
+        let data = serde_json::to_string_pretty(&value).map_err(|_| FileError::InvalidFile)?;
+        FileService::atomic_write(&path, &data)?;
+        Ok(Some(file.current_version()))
+    }
+
+    /// Applies the migrations of a file from `version` to its current version
+    ///
+    /// # Errors
+    /// Returns `FileError::NewerSchema` if the version is newer than the current one
+    fn upgrade(file: DataFile, version: u32, value: &mut Value) -> Result<(), FileError> {
+        let current = file.current_version();
+        if version > current {
+            log::error!(
+                "{} has schema version {}, but this version of the app only supports {}",
+                file.file_name(),
+                version,
+                current
+            );
+            return Err(FileError::NewerSchema(file.file_name().to_string()));
+        }
+
+        for from in version..current {
+            for migration in MIGRATIONS
+                .iter()
+                .filter(|m| m.file == file && m.from == from)
+            {
+                log::info!(
+                    "Migrating {} from schema version {}: {}",
+                    file.file_name(),
+                    from,
+                    migration.description
+                );
+                (migration.apply)(value);
+            }
+        }
+
+        if let (Some(key), Some(object)) = (file.version_key(), value.as_object_mut()) {
+            object.insert(key.to_string(), current.into());
+        }
+        Ok(())
+    }
+
+    /// Copies a file to `<name>.v<version>.bak`, so it can be restored by hand
+    fn keep_copy(path: &Path, version: u32) {
+        let mut backup = path.as_os_str().to_os_string();
+        backup.push(format!(".v{}.bak", version));
+        match fs::copy(path, &backup) {
+            Ok(_) => log::info!("Kept a copy of {:?} at {:?}", path, backup),
+            Err(e) => log::warn!("Failed to keep a copy of {:?}: {}", path, e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_upgrade_unversioned_files() {
+        let mut worlds = json!([{ "id": "wrld_1", "platform": ["android", "android", "pc"] }]);
+        SchemaMigrations::upgrade(DataFile::Worlds, 0, &mut worlds).unwrap();
+        assert_eq!(
+            worlds,
+            json!([{ "id": "wrld_1", "platform": ["android", "pc"] }])
+        );
+
+        let mut preferences = json!({ "theme": "dark" });
+        SchemaMigrations::upgrade(DataFile::Preferences, 0, &mut preferences).unwrap();
+        assert_eq!(preferences["schemaVersion"], json!(1));
+        assert_eq!(preferences["theme"], json!("dark"));
+    }
+
+    #[test]
+    fn test_upgrade_refuses_newer_versions() {
+        let mut preferences = json!({ "schemaVersion": 2 });
+        let result = SchemaMigrations::upgrade(DataFile::Preferences, 2, &mut preferences);
+        assert!(matches!(result, Err(FileError::NewerSchema(_))));
+        assert_eq!(preferences, json!({ "schemaVersion": 2 }));
+    }
+}
@@ -0,0 +1,263 @@
+use std::sync::RwLock;
+
+use crate::definitions::{AccountInfo, AuthCookies, SavedAccount, SavedAccounts};
+use crate::errors::{AppError, ConcurrencyError, EntityError};
+
+use super::FileService;
+
+/// Service for managing the VRChat sessions saved under named accounts
+#[derive(Debug)]
+pub struct AccountManager;
+
+impl AccountManager {
+    /// Lists the saved accounts, marking the one currently in use
+    ///
+    /// # Arguments
+    /// * `accounts` - The saved accounts, as a RwLock
+    ///
+    /// # Returns
+    /// The saved accounts, in the order they were added
+    ///
+    /// # Errors
+    /// Returns an error if the accounts lock is poisoned
+    pub fn list_accounts(accounts: &RwLock<SavedAccounts>) -> Result<Vec<AccountInfo>, AppError> {
+        let accounts_lock = accounts
+            .read()
+            .map_err(|_| ConcurrencyError::PoisonedLock)?;
+
+        Ok(accounts_lock
+            .accounts
+            .iter()
+            .map(|account| AccountInfo {
+                name: account.name.clone(),
+                display_name: account.display_name.clone(),
+                active: accounts_lock.active.as_deref() == Some(account.name.as_str()),
+            })
+            .collect())
+    }
+
+    /// Saves a session under a new account name and marks it as the active account
+    ///
+    /// # Arguments
+    /// * `name` - The name to save the account under
+    /// * `display_name` - The VRChat display name of the logged-in user
+    /// * `cookies` - The session cookies of the logged-in user
+    /// * `accounts` - The saved accounts, as a RwLock
+    ///
+    /// # Returns
+    /// Ok if the account was saved successfully
+    ///
+    /// # Errors
+    /// Returns an error if the name is empty or already in use
+    /// Returns an error if the accounts lock is poisoned
+    pub fn add_account(
+        name: String,
+        display_name: String,
+        cookies: AuthCookies,
+        accounts: &RwLock<SavedAccounts>,
+    ) -> Result<(), AppError> {
+        let name = name.trim().to_string();
+        if name.is_empty() {
+            return Err(EntityError::InvalidOperation("Account name cannot be empty".into()).into());
+        }
+
+        let mut accounts_lock = accounts
+            .write()
+            .map_err(|_| ConcurrencyError::PoisonedLock)?;
+
+        if accounts_lock.accounts.iter().any(|a| a.name == name) {
+            return Err(
+                EntityError::InvalidOperation(format!("Account {} already exists", name)).into(),
+            );
+        }
+
+        accounts_lock.accounts.push(SavedAccount {
+            name: name.clone(),
+            display_name,
+            cookies,
+        });
+        accounts_lock.active = Some(name);
+        FileService::write_accounts(&accounts_lock)?;
+        Ok(())
+    }
+
+    /// Removes a saved account
+    /// The session itself is left valid, so the account can be added again later
+    ///
+    /// # Arguments
+    /// * `name` - The name of the account to remove
+    /// * `accounts` - The saved accounts, as a RwLock
+    ///
+    /// # Returns
+    /// Ok if the account was removed successfully
+    ///
+    /// # Errors
+    /// Returns an error if the account does not exist
+    /// Returns an error if the accounts lock is poisoned
+    pub fn remove_account(name: &str, accounts: &RwLock<SavedAccounts>) -> Result<(), AppError> {
+        let mut accounts_lock = accounts
+            .write()
+            .map_err(|_| ConcurrencyError::PoisonedLock)?;
+
+        let index = Self::find_account(name, &accounts_lock)?;
+        accounts_lock.accounts.remove(index);
+        if accounts_lock.active.as_deref() == Some(name) {
+            accounts_lock.active = None;
+        }
+        FileService::write_accounts(&accounts_lock)?;
+        Ok(())
+    }
+
+    /// Stores the latest cookies of the current session on the active account
+    /// Does nothing if no saved account is active
+    ///
+    /// # Arguments
+    /// * `cookies` - The cookies of the current session
+    /// * `accounts` - The saved accounts, as a RwLock
+    ///
+    /// # Returns
+    /// Ok if the cookies were stored successfully
+    ///
+    /// # Errors
+    /// Returns an error if the accounts lock is poisoned
+    pub fn save_active_session(
+        cookies: AuthCookies,
+        accounts: &RwLock<SavedAccounts>,
+    ) -> Result<(), AppError> {
+        let mut accounts_lock = accounts
+            .write()
+            .map_err(|_| ConcurrencyError::PoisonedLock)?;
+
+        let Some(active) = accounts_lock.active.clone() else {
+            return Ok(());
+        };
+        let Some(account) = accounts_lock.accounts.iter_mut().find(|a| a.name == active) else {
+            return Ok(());
+        };
+        if account.cookies == cookies {
+            return Ok(());
+        }
+
+        account.cookies = cookies;
+        FileService::write_accounts(&accounts_lock)?;
+        Ok(())
+    }
+
+    /// Marks an account as active and returns the cookies to restore its session with
+    ///
+    /// # Arguments
+    /// * `name` - The name of the account to switch to, or None for a fresh session
+    /// * `accounts` - The saved accounts, as a RwLock
+    ///
+    /// # Returns
+    /// The cookies of the account, or empty cookies when switching to a fresh session
+    ///
+    /// # Errors
+    /// Returns an error if the account does not exist
+    /// Returns an error if the accounts lock is poisoned
+    pub fn activate(
+        name: Option<&str>,
+        accounts: &RwLock<SavedAccounts>,
+    ) -> Result<AuthCookies, AppError> {
+        let mut accounts_lock = accounts
+            .write()
+            .map_err(|_| ConcurrencyError::PoisonedLock)?;
+
+        let cookies = match name {
+            Some(name) => {
+                let index = Self::find_account(name, &accounts_lock)?;
+                accounts_lock.accounts[index].cookies.clone()
+            }
+            None => AuthCookies::new(),
+        };
+
+        accounts_lock.active = name.map(str::to_string);
+        FileService::write_accounts(&accounts_lock)?;
+        Ok(cookies)
+    }
+
+    fn find_account(name: &str, accounts: &SavedAccounts) -> Result<usize, AppError> {
+        accounts
+            .accounts
+            .iter()
+            .position(|a| a.name == name)
+            .ok_or_else(|| {
+                EntityError::InvalidOperation(format!("Account {} not found", name)).into()
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cookies(token: &str) -> AuthCookies {
+        AuthCookies {
+            auth_token: Some(token.to_string()),
+            ..AuthCookies::new()
+        }
+    }
+
+    #[test]
+    fn test_add_and_list_accounts() {
+        let accounts = RwLock::new(SavedAccounts::default());
+
+        AccountManager::add_account(
+            "main".to_string(),
+            "Main".to_string(),
+            cookies("a"),
+            &accounts,
+        )
+        .unwrap();
+        AccountManager::add_account("alt".to_string(), "Alt".to_string(), cookies("b"), &accounts)
+            .unwrap();
+        assert!(AccountManager::add_account(
+            "alt".to_string(),
+            "Alt".to_string(),
+            cookies("c"),
+            &accounts
+        )
+        .is_err());
+        assert!(AccountManager::add_account(
+            " ".to_string(),
+            "Blank".to_string(),
+            cookies("d"),
+            &accounts
+        )
+        .is_err());
+
+        let listed = AccountManager::list_accounts(&accounts).unwrap();
+        let summary: Vec<_> = listed.iter().map(|a| (a.name.as_str(), a.active)).collect();
+        assert_eq!(summary, vec![("main", false), ("alt", true)]);
+    }
+
+    #[test]
+    fn test_switch_accounts() {
+        let accounts = RwLock::new(SavedAccounts::default());
+        AccountManager::add_account(
+            "main".to_string(),
+            "Main".to_string(),
+            cookies("a"),
+            &accounts,
+        )
+        .unwrap();
+
+        // The refreshed session is stored before switching away
+        AccountManager::save_active_session(cookies("a2"), &accounts).unwrap();
+        assert_eq!(
+            AccountManager::activate(None, &accounts).unwrap(),
+            AuthCookies::new()
+        );
+        // Without an active account there is nothing to store the session on
+        AccountManager::save_active_session(cookies("x"), &accounts).unwrap();
+
+        let restored = AccountManager::activate(Some("main"), &accounts).unwrap();
+        assert_eq!(restored, cookies("a2"));
+        assert!(AccountManager::activate(Some("missing"), &accounts).is_err());
+        assert_eq!(accounts.read().unwrap().active.as_deref(), Some("main"));
+
+        AccountManager::remove_account("main", &accounts).unwrap();
+        assert!(accounts.read().unwrap().active.is_none());
+        assert!(AccountManager::remove_account("main", &accounts).is_err());
+    }
+}
@@ -0,0 +1,168 @@
+use std::collections::HashMap;
+use std::sync::RwLock;
+use std::time::{Duration, SystemTime};
+
+use crate::definitions::{WorldApiData, WorldDisplayData};
+
+/// How long API responses are reused for
+/// Long enough to page back and forth through a search, short enough that data stays fresh
+const API_CACHE_DURATION: Duration = Duration::from_secs(5 * 60);
+
+/// A short-lived cache of API responses, keyed by normalized request parameters
+pub struct ApiCache<V> {
+    name: &'static str,
+    entries: HashMap<String, (SystemTime, V)>,
+    cache_duration: Duration,
+    hits: u64,
+    misses: u64,
+}
+
+impl<V: Clone> ApiCache<V> {
+    pub fn new(name: &'static str, cache_duration: Duration) -> Self {
+        Self {
+            name,
+            entries: HashMap::new(),
+            cache_duration,
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    fn is_expired(&self, fetched_at: SystemTime) -> bool {
+        SystemTime::now()
+            .duration_since(fetched_at)
+            .map(|elapsed| elapsed >= self.cache_duration)
+            .unwrap_or(true)
+    }
+
+    /// Gets a cached response, counting the lookup as a hit or a miss
+    pub fn get(&mut self, key: &str) -> Option<V> {
+        let cached = match self.entries.get(key) {
+            Some((fetched_at, value)) if !self.is_expired(*fetched_at) => Some(value.clone()),
+            _ => None,
+        };
+
+        if cached.is_some() {
+            self.hits += 1;
+        } else {
+            self.misses += 1;
+        }
+        log::info!(
+            "API cache {} for {} {} (hits: {}, misses: {})",
+            if cached.is_some() { "hit" } else { "miss" },
+            self.name,
+            key,
+            self.hits,
+            self.misses
+        );
+        cached
+    }
+
+    /// Caches a response, dropping any expired entries
+    pub fn insert(&mut self, key: String, value: V) {
+        let now = SystemTime::now();
+        let cache_duration = self.cache_duration;
+        self.entries.retain(|_, (fetched_at, _)| {
+            now.duration_since(*fetched_at)
+                .map(|elapsed| elapsed < cache_duration)
+                .unwrap_or(false)
+        });
+        self.entries.insert(key, (now, value));
+    }
+
+    /// Drops every cached response, returning how many were dropped
+    pub fn clear(&mut self) -> usize {
+        let count = self.entries.len();
+        self.entries.clear();
+        count
+    }
+}
+
+/// A page of search results as returned by the API, along with whether more pages exist
+type SearchPage = (Vec<WorldDisplayData>, bool);
+
+static SEARCH_CACHE: state::InitCell<RwLock<ApiCache<SearchPage>>> = state::InitCell::new();
+static WORLD_CACHE: state::InitCell<RwLock<ApiCache<WorldApiData>>> = state::InitCell::new();
+
+pub fn init_cache() {
+    SEARCH_CACHE.set(RwLock::new(ApiCache::new("search", API_CACHE_DURATION)));
+    WORLD_CACHE.set(RwLock::new(ApiCache::new("world", API_CACHE_DURATION)));
+}
+
+/// Builds the cache key of a search from its query string and page
+/// The query string is built in a fixed order, so equal searches share a key
+pub fn search_key(query: &str, page: usize) -> String {
+    format!("{}&page={}", query, page)
+}
+
+/// Gets a cached search page
+/// Always misses if the cache has not been initialized, e.g. in tests
+pub fn get_search(key: &str) -> Option<SearchPage> {
+    SEARCH_CACHE.try_get()?.write().ok()?.get(key)
+}
+
+pub fn insert_search(key: String, page: SearchPage) {
+    if let Some(mut cache) = SEARCH_CACHE.try_get().and_then(|c| c.write().ok()) {
+        cache.insert(key, page);
+    }
+}
+
+/// Gets a cached world
+/// Always misses if the cache has not been initialized, e.g. in tests
+pub fn get_world(world_id: &str) -> Option<WorldApiData> {
+    WORLD_CACHE.try_get()?.write().ok()?.get(world_id)
+}
+
+pub fn insert_world(world: WorldApiData) {
+    if let Some(mut cache) = WORLD_CACHE.try_get().and_then(|c| c.write().ok()) {
+        cache.insert(world.world_id.clone(), world);
+    }
+}
+
+/// Drops every cached search and world, returning how many entries were dropped
+pub fn clear() -> usize {
+    let searches = SEARCH_CACHE
+        .try_get()
+        .and_then(|c| c.write().ok())
+        .map_or(0, |mut c| c.clear());
+    let worlds = WORLD_CACHE
+        .try_get()
+        .and_then(|c| c.write().ok())
+        .map_or(0, |mut c| c.clear());
+    searches + worlds
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_hit_and_miss() {
+        let mut cache = ApiCache::new("test", Duration::from_secs(60));
+        assert_eq!(cache.get("a"), None);
+
+        cache.insert("a".to_string(), 1);
+        assert_eq!(cache.get("a"), Some(1));
+        assert_eq!((cache.hits, cache.misses), (1, 1));
+
+        assert_eq!(cache.clear(), 1);
+        assert_eq!(cache.get("a"), None);
+    }
+
+    #[test]
+    fn test_cache_expiry() {
+        let mut cache = ApiCache::new("test", Duration::ZERO);
+        cache.insert("a".to_string(), 1);
+        assert_eq!(cache.get("a"), None);
+
+        // Expired entries are dropped on the next insert
+        cache.insert("b".to_string(), 2);
+        assert_eq!(cache.entries.len(), 1);
+    }
+
+    #[test]
+    fn test_search_key() {
+        assert_eq!(search_key("search=club", 2), "search=club&page=2");
+        assert_ne!(search_key("search=club", 1), search_key("search=club", 2));
+    }
+}
@@ -1,17 +1,26 @@
 use crate::api::auth::VRChatAPIClientAuthenticator;
-use crate::api::world::{SearchWorldSort, VRChatWorld, WorldSearchParametersBuilder};
-use crate::api::{auth, group, instance, invite, world};
-use crate::definitions::{AuthCookies, WorldApiData, WorldDisplayData, WorldModel};
+use crate::api::user::UserProfile;
+use crate::api::world::{SearchWorldSort, UnityPackage, VRChatWorld, WorldSearchParametersBuilder};
+use crate::api::{auth, group, instance, invite, user, world};
+use crate::app_state::AppState;
+use crate::definitions::{
+    AuthCookies, DiscoveryFeed, Platform, SearchPlatform, SearchResultPage, WorldApiData,
+    WorldDisplayData, WorldModel,
+};
+use crate::errors::ApiError;
+use chrono::{DateTime, Utc};
 use crate::services::api_service::world::WorldSearchParameters;
 use crate::services::file_service::FileService;
+use crate::services::api_cache;
 use crate::services::FolderManager;
 use crate::InitState;
 use crate::INITSTATE;
 use reqwest::cookie::CookieStore;
 use reqwest::{cookie::Jar, Client, Url};
+use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
 use tauri::http::HeaderValue;
-use tauri::AppHandle;
+use tauri::{AppHandle, Manager};
 use tauri_plugin_opener::OpenerExt;
 use world::ReleaseStatus;
 
@@ -25,6 +34,20 @@ pub struct InstanceInfo {
     pub short_name: Option<String>,
 }
 
+/// A page of the user's favorite worlds
+pub struct FavoritesPage {
+    /// The public worlds on the page
+    pub worlds: Vec<WorldApiData>,
+    /// Favorites left out as they are not public
+    pub non_public: usize,
+    /// Placeholders for the favorites which are not public
+    pub placeholders: Vec<WorldApiData>,
+    /// The favorite group (worlds1, worlds2, ...) of each world on the page, by world ID
+    pub groups: HashMap<String, String>,
+    /// Whether another page should be fetched
+    pub has_more: bool,
+}
+
 impl ApiService {
     /// Saves the cookie store to disk
     ///
@@ -37,13 +60,25 @@ impl ApiService {
     /// # Errors
     /// Returns a string error message if the cookies could not be saved
     async fn save_cookie_store(cookie_store: Arc<Jar>) -> Result<(), String> {
+        let auth = Self::get_session_cookies(&cookie_store);
+        FileService::write_auth(&auth).map_err(|e| e.to_string())
+    }
+
+    /// Reads the VRChat session cookies out of a cookie store
+    ///
+    /// # Arguments
+    /// * `cookie_store` - The cookie store of the current session
+    ///
+    /// # Returns
+    /// Returns the session cookies, empty if the store holds none
+    #[must_use]
+    pub fn get_session_cookies(cookie_store: &Arc<Jar>) -> AuthCookies {
         let cookie_str = cookie_store
             .cookies(&Url::parse("https://api.vrchat.cloud").unwrap())
             .map(|cookies| cookies.to_str().unwrap_or_default().to_string())
             .unwrap_or_default();
         //convert to AuthCookies
-        let auth = AuthCookies::from_cookie_str(&cookie_str);
-        FileService::write_auth(&auth).map_err(|e| e.to_string())
+        AuthCookies::from_cookie_str(&cookie_str)
     }
 
     /// Initializes the API service with the provided cookies
@@ -96,7 +131,7 @@ impl ApiService {
     pub async fn login_with_token(
         auth: &tokio::sync::RwLock<VRChatAPIClientAuthenticator>,
         init: &tokio::sync::RwLock<InitState>,
-    ) -> Result<(), String> {
+    ) -> Result<(), ApiError> {
         let mut auth_lock = auth.write().await;
         let mut init_lock = init.write().await;
         match auth_lock.verify_token().await {
@@ -108,13 +143,19 @@ impl ApiService {
                 init_lock.user_id = user.id.clone();
                 Ok(())
             }
-            Ok(auth::VRChatAuthStatus::Requires2FA) => Err("2fa-required".to_string()),
-            Ok(auth::VRChatAuthStatus::RequiresEmail2FA) => Err("email-2fa-required".to_string()),
+            Ok(auth::VRChatAuthStatus::Requires2FA) => {
+                Err(ApiError::Unauthorized("2fa-required".to_string()))
+            }
+            Ok(auth::VRChatAuthStatus::RequiresEmail2FA) => {
+                Err(ApiError::Unauthorized("email-2fa-required".to_string()))
+            }
             Ok(auth::VRChatAuthStatus::InvalidCredentials) => {
-                Err("Invalid credentials".to_string())
+                Err(ApiError::Unauthorized("Invalid credentials".to_string()))
             }
-            Ok(auth::VRChatAuthStatus::UnknownError(e)) => Err(format!("Login failed: {}", e)),
-            Err(e) => Err(format!("Login failed: {}", e)),
+            Ok(auth::VRChatAuthStatus::UnknownError(e)) => {
+                Err(format!("Login failed: {}", e).into())
+            }
+            Err(e) => Err(e.context("Login failed")),
         }
     }
 
@@ -134,16 +175,13 @@ impl ApiService {
         username: String,
         password: String,
         auth: &tokio::sync::RwLock<VRChatAPIClientAuthenticator>,
-    ) -> Result<(), String> {
+    ) -> Result<(), ApiError> {
         let mut auth_lock = auth.write().await;
         auth_lock.update_user_info(username);
-        let result = auth_lock.login_with_password(&password).await;
-
-        if result.is_err() {
-            return Err(format!("Login failed: {}", result.as_ref().err().unwrap()));
-        }
-
-        let status = result.unwrap();
+        let status = auth_lock
+            .login_with_password(&password)
+            .await
+            .map_err(|e| e.context("Login failed"))?;
 
         match status {
             auth::VRChatAuthStatus::Success(cookies, _user) => {
@@ -157,10 +195,16 @@ impl ApiService {
                     .map_err(|e| e.to_string())?;
                 Ok(())
             }
-            auth::VRChatAuthStatus::Requires2FA => Err("2fa-required".to_string()),
-            auth::VRChatAuthStatus::RequiresEmail2FA => Err("email-2fa-required".to_string()),
-            auth::VRChatAuthStatus::InvalidCredentials => Err("Invalid credentials".to_string()),
-            auth::VRChatAuthStatus::UnknownError(e) => Err(format!("Login failed: {}", e)),
+            auth::VRChatAuthStatus::Requires2FA => {
+                Err(ApiError::Unauthorized("2fa-required".to_string()))
+            }
+            auth::VRChatAuthStatus::RequiresEmail2FA => {
+                Err(ApiError::Unauthorized("email-2fa-required".to_string()))
+            }
+            auth::VRChatAuthStatus::InvalidCredentials => {
+                Err(ApiError::Unauthorized("Invalid credentials".to_string()))
+            }
+            auth::VRChatAuthStatus::UnknownError(e) => Err(format!("Login failed: {}", e).into()),
         }
     }
 
@@ -179,7 +223,7 @@ impl ApiService {
     pub async fn login_with_2fa(
         code: String,
         auth: &tokio::sync::RwLock<VRChatAPIClientAuthenticator>,
-    ) -> Result<(), String> {
+    ) -> Result<(), ApiError> {
         let mut auth_lock = auth.write().await;
         match auth_lock.login_with_2fa(&code).await {
             Ok(auth::VRChatAuthStatus::Success(cookies, user)) => {
@@ -193,14 +237,64 @@ impl ApiService {
                     .map_err(|e| e.to_string())?;
                 Ok(())
             }
-            Ok(auth::VRChatAuthStatus::Requires2FA) => Err("2fa-required".to_string()),
-            Ok(auth::VRChatAuthStatus::RequiresEmail2FA) => Err("email-2fa-required".to_string()),
+            Ok(auth::VRChatAuthStatus::Requires2FA) => {
+                Err(ApiError::Unauthorized("2fa-required".to_string()))
+            }
+            Ok(auth::VRChatAuthStatus::RequiresEmail2FA) => {
+                Err(ApiError::Unauthorized("email-2fa-required".to_string()))
+            }
             Ok(auth::VRChatAuthStatus::InvalidCredentials) => {
-                Err("Invalid credentials".to_string())
+                Err(ApiError::Unauthorized("Invalid credentials".to_string()))
+            }
+            Ok(auth::VRChatAuthStatus::UnknownError(e)) => {
+                Err(format!("Login failed: {}", e).into())
             }
-            Ok(auth::VRChatAuthStatus::UnknownError(e)) => Err(format!("Login failed: {}", e)),
             Err(e) => {
-                let err = format!("Login failed: {}", e);
+                let err = e.context("Login failed");
+                log::info!("{}", err);
+                Err(err)
+            }
+        }
+    }
+
+    /// Logs the user in with one of the account's 2FA recovery codes
+    /// This is used to complete the login process without the authenticator app
+    ///
+    /// # Arguments
+    /// * `code` - The recovery code to use for the login
+    /// * `auth` - The VRChatAPIClientAuthenticator to use for the login
+    ///
+    /// # Errors
+    /// Returns a string error message if the login fails
+    pub async fn login_with_recovery_code(
+        code: String,
+        auth: &tokio::sync::RwLock<VRChatAPIClientAuthenticator>,
+    ) -> Result<(), ApiError> {
+        let mut auth_lock = auth.write().await;
+        match auth_lock.login_with_recovery_code(&code).await {
+            Ok(auth::VRChatAuthStatus::Success(cookies, _user)) => {
+                FileService::write_auth(&cookies).map_err(|e| e.to_string())?;
+
+                let cookie_store = Self::initialize_with_cookies(cookies);
+                Self::save_cookie_store(cookie_store)
+                    .await
+                    .map_err(|e| e.to_string())?;
+                Ok(())
+            }
+            Ok(auth::VRChatAuthStatus::Requires2FA) => {
+                Err(ApiError::Unauthorized("2fa-required".to_string()))
+            }
+            Ok(auth::VRChatAuthStatus::RequiresEmail2FA) => {
+                Err(ApiError::Unauthorized("email-2fa-required".to_string()))
+            }
+            Ok(auth::VRChatAuthStatus::InvalidCredentials) => {
+                Err(ApiError::Unauthorized("Invalid credentials".to_string()))
+            }
+            Ok(auth::VRChatAuthStatus::UnknownError(e)) => {
+                Err(format!("Login failed: {}", e).into())
+            }
+            Err(e) => {
+                let err = e.context("Login failed");
                 log::info!("{}", err);
                 Err(err)
             }
@@ -222,7 +316,7 @@ impl ApiService {
     pub async fn login_with_email_2fa(
         code: String,
         auth: &tokio::sync::RwLock<VRChatAPIClientAuthenticator>,
-    ) -> Result<(), String> {
+    ) -> Result<(), ApiError> {
         let mut auth_lock = auth.write().await;
         match auth_lock.login_with_email_2fa(&code).await {
             Ok(auth::VRChatAuthStatus::Success(cookies, user)) => {
@@ -239,14 +333,20 @@ impl ApiService {
                     .map_err(|e| e.to_string())?;
                 Ok(())
             }
-            Ok(auth::VRChatAuthStatus::Requires2FA) => Err("2fa-required".to_string()),
-            Ok(auth::VRChatAuthStatus::RequiresEmail2FA) => Err("email-2fa-required".to_string()),
+            Ok(auth::VRChatAuthStatus::Requires2FA) => {
+                Err(ApiError::Unauthorized("2fa-required".to_string()))
+            }
+            Ok(auth::VRChatAuthStatus::RequiresEmail2FA) => {
+                Err(ApiError::Unauthorized("email-2fa-required".to_string()))
+            }
             Ok(auth::VRChatAuthStatus::InvalidCredentials) => {
-                Err("Invalid credentials".to_string())
+                Err(ApiError::Unauthorized("Invalid credentials".to_string()))
+            }
+            Ok(auth::VRChatAuthStatus::UnknownError(e)) => {
+                Err(format!("Login failed: {}", e).into())
             }
-            Ok(auth::VRChatAuthStatus::UnknownError(e)) => Err(format!("Login failed: {}", e)),
             Err(e) => {
-                let err = format!("Login failed: {}", e);
+                let err = e.context("Login failed");
                 log::info!("{}", err);
                 Err(err)
             }
@@ -267,13 +367,13 @@ impl ApiService {
     /// Returns a string error message if the logout fails
     pub async fn logout(
         auth: &tokio::sync::RwLock<VRChatAPIClientAuthenticator>,
-    ) -> Result<(), String> {
+    ) -> Result<(), ApiError> {
         let authenticator = auth.read().await;
         let cookie_store = authenticator.get_cookies();
 
         // Call the API logout endpoint
         auth::logout(&cookie_store).await.map_err(|e| {
-            let err = format!("Failed to logout from VRChat: {}", e);
+            let err = e.context("Failed to logout from VRChat");
             log::info!("{}", err);
             err
         })?;
@@ -292,41 +392,132 @@ impl ApiService {
         Ok(())
     }
 
+    /// Replaces the current session with the session held by the provided cookies
+    /// Unlike logout, the current session is not invalidated on the server,
+    /// so it can be restored later
+    ///
+    /// # Arguments
+    /// * `cookies` - The cookies of the session to switch to, empty for a logged-out session
+    /// * `auth` - The authenticator to rebuild
+    /// * `init` - The init state holding the logged-in user ID
+    ///
+    /// # Returns
+    /// Ok if the session was switched, and verified if cookies were provided
+    ///
+    /// # Errors
+    /// Returns a string error message if the cookies could not be saved or the session is invalid
+    pub async fn switch_session(
+        cookies: AuthCookies,
+        auth: &tokio::sync::RwLock<VRChatAPIClientAuthenticator>,
+        init: &tokio::sync::RwLock<InitState>,
+    ) -> Result<(), ApiError> {
+        let has_session = cookies.auth_token.is_some();
+        FileService::write_auth(&cookies).map_err(|e| e.to_string())?;
+        init.write().await.user_id = String::new();
+        *auth.write().await =
+            VRChatAPIClientAuthenticator::from_cookie_store(Self::initialize_with_cookies(cookies));
+
+        // Cached lookups may reflect what the previous account was allowed to see
+        api_cache::clear();
+
+        if has_session {
+            Self::login_with_token(auth, init).await?;
+        }
+        Ok(())
+    }
+
     #[must_use]
     pub async fn get_favorite_worlds(
         cookie_store: Arc<Jar>,
         user_id: String,
-    ) -> Result<Vec<WorldApiData>, String> {
-        let mut worlds = vec![];
-
+    ) -> Result<Vec<WorldApiData>, ApiError> {
         let result = world::get_favorite_worlds(cookie_store).await;
 
         let favorite_worlds = match result {
             Ok(worlds) => worlds,
-            Err(e) => {
-                return Err(format!(
-                    "Failed to parse favorite worlds: {}",
-                    e.to_string()
-                ))
-            }
+            Err(e) => return Err(e.context("Failed to parse favorite worlds")),
         };
 
+        Self::favorites_to_api_data(favorite_worlds).map(|(worlds, _)| worlds)
+    }
+
+    /// Fetches a single page of favorite worlds
+    ///
+    /// # Arguments
+    /// * `cookie_store` - The cookie store to use for the API
+    /// * `page` - The zero-based page to fetch
+    ///
+    /// # Returns
+    /// The public worlds on the page, how many were not public along with placeholders for
+    /// them, the favorite group of each world, and whether another page should be fetched
+    ///
+    /// # Errors
+    /// Returns an error if the request fails or the response could not be parsed
+    pub async fn get_favorite_worlds_page(
+        cookie_store: Arc<Jar>,
+        page: usize,
+    ) -> Result<FavoritesPage, ApiError> {
+        let (favorite_worlds, hidden_worlds, page_size) =
+            world::get_favorite_worlds_page(cookie_store, page)
+                .await
+                .map_err(|e| e.context("Failed to parse favorite worlds"))?;
+
+        let has_more =
+            page_size >= world::FAVORITES_PAGE_SIZE && page + 1 < world::MAX_FAVORITES_PAGES;
+        let groups = favorite_worlds
+            .iter()
+            .map(|w| (w.id.clone(), w.favorite_group.clone()))
+            .chain(
+                hidden_worlds
+                    .iter()
+                    .map(|w| (w.id.clone(), w.favorite_group.clone())),
+            )
+            .collect();
+        let mut placeholders: Vec<WorldApiData> = favorite_worlds
+            .iter()
+            .filter(|w| w.release_status != ReleaseStatus::Public)
+            .map(|w| WorldApiData::placeholder(w.id.clone()))
+            .collect();
+        placeholders.extend(
+            hidden_worlds
+                .into_iter()
+                .map(|w| WorldApiData::placeholder(w.id)),
+        );
+        // Hidden worlds are not public either, so every placeholder counts as one left out
+        let (worlds, _) = Self::favorites_to_api_data(favorite_worlds)?;
+        Ok(FavoritesPage {
+            worlds,
+            non_public: placeholders.len(),
+            placeholders,
+            groups,
+            has_more,
+        })
+    }
+
+    /// Converts favorite worlds, leaving out and counting the ones which are not public
+    fn favorites_to_api_data(
+        favorite_worlds: Vec<world::FavoriteWorld>,
+    ) -> Result<(Vec<WorldApiData>, usize), ApiError> {
+        let mut worlds = vec![];
+        let mut non_public = 0;
+
         for world in favorite_worlds {
             // Only include public worlds
             // Worlds which belong to the user are not included, as only public worlds have the correct format when calling this endpoint.
             if world.release_status != ReleaseStatus::Public {
                 log::info!("Skipping non-public world: {}", world.id);
+                non_public += 1;
 
                 continue;
             }
 
             match world.try_into() {
                 Ok(world_data) => worlds.push(world_data),
-                Err(e) => return Err(format!("Failed to parse world: {}", e)),
+                Err(e) => return Err(format!("Failed to parse world: {}", e).into()),
             }
         }
 
-        Ok(worlds)
+        Ok((worlds, non_public))
     }
 
     #[must_use]
@@ -335,7 +526,7 @@ impl ApiService {
         cookie_store: Arc<Jar>,
         worlds: Vec<WorldModel>,
         user_id: String,
-    ) -> Result<WorldApiData, String> {
+    ) -> Result<WorldApiData, ApiError> {
         // First check if we have a cached version
         if let Some(existing_world) = worlds.iter().find(|w| w.api_data.world_id == world_id) {
             if !existing_world.user_data.needs_update() {
@@ -344,33 +535,296 @@ impl ApiService {
             }
         }
 
+        if let Some(cached_world) = api_cache::get_world(&world_id) {
+            return Ok(cached_world);
+        }
+
         // Fetch from API
-        match world::get_world_by_id(cookie_store, &world_id).await {
+        match world::get_world_by_id(cookie_store.clone(), &world_id).await {
             Ok(world) => {
                 // Check if world is public, or if the user is the owner
                 if world.release_status != ReleaseStatus::Public && world.author_id != user_id {
                     log::info!("World {} is not public", world_id);
-                    return Err("World is not public".to_string());
+                    return Err("World is not public".to_string().into());
                 }
 
+                let unity_packages = world.unity_packages.clone();
                 match world::WorldDetails::try_into(world) {
-                    Ok(world_data) => Ok(world_data),
-                    Err(e) => Err(e.to_string()),
+                    Ok(mut world_data) => {
+                        Self::fetch_package_sizes(cookie_store, &mut world_data, &unity_packages)
+                            .await;
+                        api_cache::insert_world(world_data.clone());
+                        Ok(world_data)
+                    }
+                    Err(e) => Err(e.to_string().into()),
+                }
+            }
+            Err(e) => Err(e.context("Failed to fetch world")),
+        }
+    }
+
+    /// Fills in the download sizes of a world's packages from their file analyses
+    /// Best effort, packages whose analysis cannot be fetched keep an unknown size
+    ///
+    /// # Arguments
+    /// * `cookie_store` - The cookie store to use for the API
+    /// * `world` - The world to fill in, with one package entry per platform
+    /// * `unity_packages` - The packages the world was fetched with, for their asset URLs
+    async fn fetch_package_sizes(
+        cookie_store: Arc<Jar>,
+        world: &mut WorldApiData,
+        unity_packages: &[UnityPackage],
+    ) {
+        for package in unity_packages {
+            let Some((file_id, version)) = package.file_version() else {
+                continue;
+            };
+            let Some(info) = world
+                .packages
+                .iter_mut()
+                .find(|p| p.platform == package.platform && p.file_size.is_none())
+            else {
+                continue;
+            };
+            match world::get_file_analysis(cookie_store.clone(), file_id, version).await {
+                Ok(analysis) => {
+                    info.file_size = analysis.file_size;
+                    info.uncompressed_size = analysis.uncompressed_size;
+                    if analysis.performance_rating.is_some() {
+                        info.performance_rating = analysis.performance_rating;
+                    }
                 }
+                Err(e) => log::warn!("Failed to fetch the size of {}: {}", file_id, e),
             }
-            Err(e) => Err(format!("Failed to fetch world: {}", e)),
         }
     }
 
+    /// Fetches the profile of the logged-in account
+    ///
+    /// # Arguments
+    /// * `cookie_store` - The cookie store to use for the API
+    ///
+    /// # Returns
+    /// Returns a Result containing the profile of the logged-in account
+    ///
+    /// # Errors
+    /// Returns a string error message if the request fails or no account is logged in
+    pub async fn get_current_user_profile(cookie_store: Arc<Jar>) -> Result<UserProfile, ApiError> {
+        user::get_current_user_profile(cookie_store)
+            .await
+            .map_err(|e| e.context("Failed to fetch account info"))
+    }
+
+    /// Fetches the most recently published worlds for a discovery feed
+    ///
+    /// # Arguments
+    /// * `cookie_store` - The cookie store to use for the API
+    /// * `feed` - The discovery feed to fetch
+    ///
+    /// # Returns
+    /// Returns a Result containing each world along with its feed-specific publication date,
+    /// newest first. Worlds without a valid publication date are skipped
+    ///
+    /// # Errors
+    /// Returns a string error message if the request fails
+    pub async fn get_recently_published_worlds(
+        cookie_store: Arc<Jar>,
+        feed: DiscoveryFeed,
+    ) -> Result<Vec<(DateTime<Utc>, WorldDisplayData)>, ApiError> {
+        let parameters = match feed {
+            DiscoveryFeed::Published => WorldSearchParametersBuilder::new()
+                .sort(SearchWorldSort::PublicationDate)
+                .build(),
+            DiscoveryFeed::LabsPublished => WorldSearchParametersBuilder::new()
+                .sort(SearchWorldSort::LabsPublicationDate)
+                .tag("system_labs")
+                .build(),
+        };
+
+        let worlds = world::search_worlds(cookie_store, &parameters, 1)
+            .await
+            .map_err(|e| e.context("Failed to fetch recently published worlds"))?;
+
+        let mut published = vec![];
+        for world in worlds {
+            let date_str = match feed {
+                DiscoveryFeed::Published => &world.publication_date,
+                DiscoveryFeed::LabsPublished => &world.labs_publication_date,
+            };
+            let date = match DateTime::parse_from_rfc3339(date_str) {
+                Ok(date) => date.with_timezone(&Utc),
+                Err(_) => continue,
+            };
+            let world_data: WorldDisplayData = world
+                .try_into()
+                .map_err(|e: chrono::ParseError| format!("Failed to convert world: {}", e))?;
+            published.push((date, world_data));
+        }
+
+        Ok(published)
+    }
+
+    /// Fetches the latest data for a world, regardless of its release status
+    /// Unlike `get_world_by_id`, this never uses the cache and does not reject non-public worlds
+    ///
+    /// # Arguments
+    /// * `cookie_store` - The cookie store to use for the API
+    /// * `world_id` - The ID of the world to fetch
+    ///
+    /// # Returns
+    /// Returns a Result containing the world data and its current release status
+    ///
+    /// # Errors
+    /// Returns a string error message if the request fails
+    pub async fn get_world_with_release_status(
+        cookie_store: Arc<Jar>,
+        world_id: &str,
+    ) -> Result<(WorldApiData, ReleaseStatus), ApiError> {
+        let world = world::get_world_by_id(cookie_store, world_id)
+            .await
+            .map_err(|e| e.context("Failed to fetch world"))?;
+        let release_status = world.release_status.clone();
+        let world_data: WorldApiData = world
+            .try_into()
+            .map_err(|e: chrono::ParseError| e.to_string())?;
+        Ok((world_data, release_status))
+    }
+
+    /// Downloads a world's image
+    ///
+    /// # Arguments
+    /// * `cookie_store` - The cookie store to use for the API
+    /// * `url` - The image URL, e.g. a world's thumbnail URL
+    ///
+    /// # Returns
+    /// Returns the image as it was served
+    ///
+    /// # Errors
+    /// Returns a string error message if the request fails
+    pub async fn get_world_image(cookie_store: Arc<Jar>, url: &str) -> Result<Vec<u8>, ApiError> {
+        world::get_world_image(cookie_store, url).await
+    }
+
     async fn invite_self_to_instance(
         cookie_store: Arc<Jar>,
         world_id: String,
         instance_id: String,
-    ) -> Result<(), String> {
+    ) -> Result<(), ApiError> {
         match invite::invite_self_to_instance(cookie_store, &world_id, &instance_id).await {
             Ok(_) => Ok(()),
-            Err(e) => Err(format!("Failed to invite self to instance: {}", e)),
+            Err(e) => Err(e.context("Failed to invite self to instance")),
+        }
+    }
+
+    /// Invites a friend to an instance
+    ///
+    /// # Arguments
+    /// * `cookie_store` - The cookie store to use for the API
+    /// * `user_id` - The ID of the user to invite
+    /// * `world_id` - The ID of the instance's world
+    /// * `instance_id` - The ID of the instance
+    /// * `message_slot` - The slot of the invite message to send, or None for no message
+    ///
+    /// # Errors
+    /// Returns a string error message if the slot does not exist or the request fails
+    #[must_use]
+    pub async fn invite_user_to_instance(
+        cookie_store: Arc<Jar>,
+        user_id: &str,
+        world_id: &str,
+        instance_id: &str,
+        message_slot: Option<u8>,
+    ) -> Result<(), ApiError> {
+        if let Some(slot) = message_slot {
+            Self::check_invite_message_slot(slot)?;
         }
+        invite::invite_user_to_instance(cookie_store, user_id, world_id, instance_id, message_slot)
+            .await
+            .map_err(|e| e.context("Failed to invite user to instance"))
+    }
+
+    /// Gets the logged-in user's invite messages of a type
+    ///
+    /// # Arguments
+    /// * `cookie_store` - The cookie store to use for the API
+    /// * `user_id` - The ID of the logged-in user
+    /// * `message_type` - The type of messages to get
+    ///
+    /// # Returns
+    /// The messages, ordered by slot
+    ///
+    /// # Errors
+    /// Returns a string error message if the request fails
+    #[must_use]
+    pub async fn get_invite_messages(
+        cookie_store: Arc<Jar>,
+        user_id: &str,
+        message_type: invite::InviteMessageType,
+    ) -> Result<Vec<invite::InviteMessage>, ApiError> {
+        invite::get_invite_messages(cookie_store, user_id, message_type)
+            .await
+            .map_err(|e| e.context("Failed to fetch invite messages"))
+    }
+
+    /// Replaces the text of one of the logged-in user's invite messages
+    ///
+    /// # Arguments
+    /// * `cookie_store` - The cookie store to use for the API
+    /// * `user_id` - The ID of the logged-in user
+    /// * `message_type` - The type of the message
+    /// * `slot` - The slot of the message
+    /// * `message` - The new text
+    ///
+    /// # Returns
+    /// All the messages of the type after the change, ordered by slot
+    ///
+    /// # Errors
+    /// Returns a string error message if the slot does not exist, the text is empty,
+    /// or the request fails, e.g. as the slot was edited less than an hour ago
+    #[must_use]
+    pub async fn set_invite_message(
+        cookie_store: Arc<Jar>,
+        user_id: &str,
+        message_type: invite::InviteMessageType,
+        slot: u8,
+        message: String,
+    ) -> Result<Vec<invite::InviteMessage>, ApiError> {
+        Self::check_invite_message_slot(slot)?;
+        let message = message.trim().to_string();
+        if message.is_empty() {
+            return Err("Invite message cannot be empty".to_string().into());
+        }
+        invite::update_invite_message(cookie_store, user_id, message_type, slot, message)
+            .await
+            .map_err(|e| e.context("Failed to update invite message"))
+    }
+
+    fn check_invite_message_slot(slot: u8) -> Result<(), String> {
+        if slot >= invite::INVITE_MESSAGE_SLOTS {
+            return Err(format!(
+                "Invalid invite message slot {}, expected 0 to {}",
+                slot,
+                invite::INVITE_MESSAGE_SLOTS - 1
+            ));
+        }
+        Ok(())
+    }
+
+    /// Get the short name of an instance, which vrch.at links and private invites use
+    ///
+    /// # Arguments
+    /// * `cookie` - The cookie jar to use for the API
+    /// * `world_id` - The ID of the world of the instance
+    /// * `instance_id` - The ID of the instance
+    ///
+    /// # Errors
+    /// Returns a string error message if the request fails
+    pub async fn get_instance_short_name<J: Into<Arc<Jar>>>(
+        cookie: J,
+        world_id: &str,
+        instance_id: &str,
+    ) -> Result<String, ApiError> {
+        instance::get_instance_short_name(cookie, world_id, instance_id).await
     }
 
     /// Get the instance short name, and open the instance menu in the user's client
@@ -391,7 +845,7 @@ impl ApiService {
         world_id: &str,
         instance_id: &str,
         app: AppHandle,
-    ) -> Result<String, String> {
+    ) -> Result<String, ApiError> {
         let short_name = instance::get_instance_short_name(cookie, world_id, instance_id).await?;
 
         // Open the instance in the user's client
@@ -420,7 +874,7 @@ impl ApiService {
     #[must_use]
     pub async fn get_recently_visited_worlds(
         cookie_store: Arc<Jar>,
-    ) -> Result<Vec<WorldDisplayData>, String> {
+    ) -> Result<Vec<WorldDisplayData>, ApiError> {
         match world::get_recently_visited_worlds(cookie_store).await {
             Ok(worlds) => {
                 let converted_worlds = worlds
@@ -432,42 +886,80 @@ impl ApiService {
                     Ok(worlds_vec) => Ok(worlds_vec),
                     Err(e) => {
                         log::info!("Failed to convert worlds: {}", e);
-                        Err(format!("Failed to convert worlds: {}", e))
+                        Err(format!("Failed to convert worlds: {}", e).into())
                     }
                 }
             }
-            Err(e) => Err(format!("Failed to fetch recently visited worlds: {}", e)),
+            Err(e) => Err(e.context("Failed to fetch recently visited worlds")),
         }
     }
 
+    /// Gets the worlds uploaded by the logged-in user, including private ones
+    ///
+    /// # Arguments
+    /// * `cookie_store` - The cookie store to use for the API
+    ///
+    /// # Returns
+    /// The user's worlds, with their release status
+    ///
+    /// # Errors
+    /// Returns a string error message if the request fails
+    pub async fn get_my_worlds(
+        cookie_store: Arc<Jar>,
+    ) -> Result<Vec<(WorldDisplayData, ReleaseStatus)>, ApiError> {
+        let worlds = world::get_my_worlds(cookie_store)
+            .await
+            .map_err(|e| e.context("Failed to fetch own worlds"))?;
+
+        worlds
+            .into_iter()
+            .map(|world| {
+                let release_status = world.release_status.clone();
+                world
+                    .try_into()
+                    .map(|display: WorldDisplayData| (display, release_status))
+                    .map_err(|e| ApiError::from(format!("Failed to convert worlds: {}", e)))
+            })
+            .collect()
+    }
+
     /// Searches for worlds within the server, using the provided query
     ///
     /// # Arguments
     /// * `cookie_store` - The cookie store to use for the API
     /// * `sort` - The sort priority for the search
     /// * `tag` - The tags that the worlds should have
-    /// * `platform` - The platforms which the worlds should be available on
+    /// * `exclude_tags` - The tags that the worlds should not have
     /// * `search` - The search string to use
+    /// * `platform` - The platform which the worlds should be available on, or None for any
+    /// * `featured` - Whether the worlds should be featured, or None for any
+    /// * `release_status` - The release status the worlds should have, or None for public only
     /// * `page` - The page number to fetch
     ///
     /// # Returns
-    /// Returns a Result containing a vector of WorldDisplayData if the request was successful
+    /// Returns a Result containing the page of results if the request was successful
     ///
     /// # Errors
     /// Returns a string error message if the request fails
     #[must_use]
+    #[allow(clippy::too_many_arguments)]
     pub async fn search_worlds(
         cookie_store: Arc<Jar>,
         sort: Option<String>,
         tags: Option<Vec<String>>,
         exclude_tags: Option<Vec<String>>,
         search: Option<String>,
+        platform: Option<SearchPlatform>,
+        featured: Option<bool>,
+        release_status: Option<ReleaseStatus>,
         page: usize,
-    ) -> Result<Vec<WorldDisplayData>, String> {
+    ) -> Result<SearchResultPage, ApiError> {
         let sort = SearchWorldSort::from_str(sort.unwrap_or_default().as_str());
 
         // tag should be in the form author_tag_{tag}, and made into a single string seperated by commas
-        let tags = if let Some(tags) = tags {
+        // Tags are sorted so the same set of tags always makes the same query
+        let tags = if let Some(mut tags) = tags {
+            tags.sort();
             // For each tag, prepend "author_tag_" and collect into a single string
             Some(
                 tags.into_iter()
@@ -480,7 +972,8 @@ impl ApiService {
         };
 
         // exclude_tags should be in the form author_tag_{tag}, and made into a single string separated by commas
-        let exclude_tags = if let Some(exclude_tags) = exclude_tags {
+        let exclude_tags = if let Some(mut exclude_tags) = exclude_tags {
+            exclude_tags.sort();
             // For each tag, prepend "author_tag_" and collect into a single string
             Some(
                 exclude_tags
@@ -506,24 +999,121 @@ impl ApiService {
         if let Some(search) = search {
             parameter_builder.search = Some(search);
         }
+        if let Some(platform) = platform {
+            parameter_builder.platform = Some(platform.query_platform().to_string());
+        }
+        parameter_builder.featured = featured;
+        parameter_builder.release_status = release_status;
+        let parameters = parameter_builder.build();
 
-        match world::search_worlds(cookie_store, &parameter_builder.build(), page).await {
-            Ok(worlds) => {
-                let converted_worlds = worlds
+        // Both shares its query with Android, so it is filtered after the cache
+        let cache_key = api_cache::search_key(&parameters.to_query_string(), page);
+        let (mut worlds, has_more) = match api_cache::get_search(&cache_key) {
+            Some(cached) => cached,
+            None => {
+                let worlds = world::search_worlds(cookie_store, &parameters, page)
+                    .await
+                    .map_err(|e| e.context("Failed to fetch worlds"))?;
+                let has_more = worlds.len() >= world::SEARCH_PAGE_SIZE;
+                let worlds = worlds
                     .into_iter()
                     .map(|world| world.try_into())
-                    .collect::<Result<Vec<_>, _>>();
-
-                match converted_worlds {
-                    Ok(worlds_vec) => Ok(worlds_vec),
-                    Err(e) => {
+                    .collect::<Result<Vec<WorldDisplayData>, _>>()
+                    .map_err(|e| {
                         log::info!("Failed to convert worlds: {}", e);
-                        Err(format!("Failed to convert worlds: {}", e))
-                    }
-                }
+                        format!("Failed to convert worlds: {}", e)
+                    })?;
+                api_cache::insert_search(cache_key, (worlds.clone(), has_more));
+                (worlds, has_more)
             }
-            Err(e) => Err(format!("Failed to fetch worlds: {}", e)),
+        };
+
+        if platform == Some(SearchPlatform::Both) {
+            worlds.retain(|world| matches!(world.platform, Platform::CrossPlatform));
         }
+        Ok(SearchResultPage {
+            worlds,
+            page,
+            page_size: world::SEARCH_PAGE_SIZE,
+            has_more,
+        })
+    }
+
+    /// Fetches worlds published by an author after a given point in time
+    ///
+    /// # Arguments
+    /// * `cookie_store` - The cookie store to use for the API
+    /// * `author_id` - The ID of the author whose worlds to fetch
+    /// * `since` - Only worlds published after this time are returned
+    ///
+    /// # Returns
+    /// Returns a Result containing the newly published worlds, newest first
+    ///
+    /// # Errors
+    /// Returns a string error message if the request fails
+    pub async fn get_worlds_published_since(
+        cookie_store: Arc<Jar>,
+        author_id: &str,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<WorldDisplayData>, ApiError> {
+        let parameters = WorldSearchParametersBuilder::new()
+            .sort(SearchWorldSort::PublicationDate)
+            .user_id(author_id)
+            .build();
+
+        let worlds = world::search_worlds(cookie_store, &parameters, 1)
+            .await
+            .map_err(|e| e.context("Failed to fetch worlds by author"))?;
+
+        worlds
+            .into_iter()
+            .filter(|world| {
+                DateTime::parse_from_rfc3339(&world.publication_date)
+                    .map(|date| date.with_timezone(&Utc) > since)
+                    .unwrap_or(false)
+            })
+            .map(|world| world.try_into())
+            .collect::<Result<Vec<WorldDisplayData>, _>>()
+            .map_err(|e| {
+                log::info!("Failed to convert worlds: {}", e);
+                ApiError::from(format!("Failed to convert worlds: {}", e))
+            })
+    }
+
+    /// Fetches a page of the worlds published by an author, newest first
+    ///
+    /// # Arguments
+    /// * `cookie_store` - The cookie store to use for the API
+    /// * `author_id` - The ID of the author whose worlds to fetch
+    /// * `page` - The page number to fetch, starting from 1
+    ///
+    /// # Returns
+    /// Returns a Result containing the author's worlds on that page
+    ///
+    /// # Errors
+    /// Returns a string error message if the request fails
+    pub async fn get_worlds_by_author(
+        cookie_store: Arc<Jar>,
+        author_id: &str,
+        page: usize,
+    ) -> Result<Vec<WorldDisplayData>, ApiError> {
+        let parameters = WorldSearchParametersBuilder::new()
+            .sort(SearchWorldSort::PublicationDate)
+            .user_id(author_id)
+            .build();
+
+        let worlds = world::search_worlds(cookie_store, &parameters, page)
+            .await
+            .map_err(|e| e.context("Failed to fetch worlds by author"))?;
+
+        worlds
+            .into_iter()
+            .map(|world| world.try_into())
+            .collect::<Result<Vec<WorldDisplayData>, _>>()
+            .map_err(|e| {
+                log::info!("Failed to convert worlds: {}", e);
+                ApiError::from(format!("Failed to convert worlds: {}", e))
+            })
     }
 
     /// Creates a new instance of a world
@@ -548,7 +1138,7 @@ impl ApiService {
         cookie_store: Arc<Jar>,
         user_id: String,
         app: AppHandle,
-    ) -> Result<InstanceInfo, String> {
+    ) -> Result<InstanceInfo, ApiError> {
         log::info!(
             "Creating instance: {} {} {}",
             world_id,
@@ -561,7 +1151,7 @@ impl ApiService {
             "use" => instance::InstanceRegion::UsEast,
             "eu" => instance::InstanceRegion::EU,
             "jp" => instance::InstanceRegion::JP,
-            _ => return Err("Invalid region".to_string()),
+            _ => return Err("Invalid region".to_string().into()),
         };
         // Create instance type based on string and user_id
         let instance_type = match instance_type_str.as_str() {
@@ -569,29 +1159,37 @@ impl ApiService {
             // The following instance types require a valid user id. If we don't have one, fail early
             "friends+" => {
                 if user_id.is_empty() {
-                    return Err("Not logged in: cannot create friends+ instance".to_string());
+                    return Err(ApiError::Unauthorized(
+                        "Not logged in: cannot create friends+ instance".to_string(),
+                    ));
                 }
                 instance::InstanceType::friends_plus(user_id)
             }
             "friends" => {
                 if user_id.is_empty() {
-                    return Err("Not logged in: cannot create friends instance".to_string());
+                    return Err(ApiError::Unauthorized(
+                        "Not logged in: cannot create friends instance".to_string(),
+                    ));
                 }
                 instance::InstanceType::friends_only(user_id)
             }
             "invite+" => {
                 if user_id.is_empty() {
-                    return Err("Not logged in: cannot create invite+ instance".to_string());
+                    return Err(ApiError::Unauthorized(
+                        "Not logged in: cannot create invite+ instance".to_string(),
+                    ));
                 }
                 instance::InstanceType::invite_plus(user_id)
             }
             "invite" => {
                 if user_id.is_empty() {
-                    return Err("Not logged in: cannot create invite instance".to_string());
+                    return Err(ApiError::Unauthorized(
+                        "Not logged in: cannot create invite instance".to_string(),
+                    ));
                 }
                 instance::InstanceType::invite_only(user_id)
             }
-            _ => return Err("Invalid instance type".to_string()),
+            _ => return Err("Invalid instance type".to_string().into()),
         };
 
         // Create request using builder
@@ -612,6 +1210,14 @@ impl ApiService {
                 )
                 .await?;
 
+                // Remembered for creating another instance from the tray
+                let mut custom_data = FileService::read_custom_data();
+                custom_data.last_instance_world = Some(world_id.clone());
+                if let Err(e) = FileService::write_custom_data(&custom_data) {
+                    log::warn!("Failed to remember last instance world: {}", e);
+                }
+                Self::record_launch(&world_id, &app);
+
                 // Do NOT fetch the short name here. Frontend will request it when user chooses to open in client.
                 Ok(InstanceInfo {
                     world_id,
@@ -619,7 +1225,7 @@ impl ApiService {
                     short_name: None,
                 })
             }
-            Err(e) => Err(format!("Failed to create world instance: {}", e)),
+            Err(e) => Err(e.context("Failed to create world instance")),
         }
     }
 
@@ -638,11 +1244,41 @@ impl ApiService {
     pub async fn get_user_groups(
         cookie_store: Arc<Jar>,
         user_id: String,
-    ) -> Result<Vec<group::UserGroup>, String> {
+    ) -> Result<Vec<group::UserGroup>, ApiError> {
         match group::get_user_groups(cookie_store, &user_id).await {
             Ok(groups) => Ok(groups),
-            Err(e) => Err(format!("Failed to fetch user groups: {}", e)),
+            Err(e) => Err(e.context("Failed to fetch user groups")),
+        }
+    }
+
+    /// Gets the IDs of the worlds a group is hosting instances of
+    ///
+    /// # Arguments
+    /// * `cookie_store` - The cookie store to use for the API
+    /// * `group_id` - The ID of the group
+    ///
+    /// # Returns
+    /// The world IDs, each once, in the order the group's instances are listed
+    ///
+    /// # Errors
+    /// Returns a string error message if the request fails
+    pub async fn get_group_world_ids(
+        cookie_store: Arc<Jar>,
+        group_id: &str,
+    ) -> Result<Vec<String>, ApiError> {
+        let instances = group::get_group_instances(cookie_store, group_id)
+            .await
+            .map_err(|e| e.context("Failed to fetch group instances"))?;
+
+        let mut world_ids: Vec<String> = vec![];
+        for instance in &instances {
+            if let Some(world_id) = instance.world_id() {
+                if !world_ids.iter().any(|id| id == world_id) {
+                    world_ids.push(world_id.to_string());
+                }
+            }
         }
+        Ok(world_ids)
     }
 
     /// Gets the permission for creating a group instance
@@ -660,10 +1296,10 @@ impl ApiService {
     pub async fn get_permission_for_create_group_instance(
         cookie_store: Arc<Jar>,
         group_id: String,
-    ) -> Result<group::GroupInstancePermissionInfo, String> {
+    ) -> Result<group::GroupInstancePermissionInfo, ApiError> {
         match group::get_permission_for_create_group_instance(cookie_store, &group_id).await {
             Ok(permission) => Ok(permission),
-            Err(e) => Err(format!("Failed to fetch group instance permission: {}", e)),
+            Err(e) => Err(e.context("Failed to fetch group instance permission")),
         }
     }
 
@@ -693,7 +1329,7 @@ impl ApiService {
         queue_enabled: bool,
         cookie_store: Arc<Jar>,
         app: AppHandle,
-    ) -> Result<InstanceInfo, String> {
+    ) -> Result<InstanceInfo, ApiError> {
         log::info!(
             "Creating group instance: {} {} {} {} {:?}",
             world_id,
@@ -708,7 +1344,7 @@ impl ApiService {
             "use" => instance::InstanceRegion::UsEast,
             "eu" => instance::InstanceRegion::EU,
             "jp" => instance::InstanceRegion::JP,
-            _ => return Err("Invalid region".to_string()),
+            _ => return Err("Invalid region".to_string().into()),
         };
 
         // Create instance type based on string
@@ -730,7 +1366,7 @@ impl ApiService {
                     instance::InstanceType::GroupOnly(config)
                 }
             }
-            _ => return Err("Invalid instance type".to_string()),
+            _ => return Err("Invalid instance type".to_string().into()),
         };
 
         // Create request using builder
@@ -754,6 +1390,7 @@ impl ApiService {
                     instance_id.clone(),
                 )
                 .await?;
+                Self::record_launch(&world_id, &app);
 
                 // Do NOT fetch the short name here. Frontend will request it when user chooses to open in client.
                 Ok(InstanceInfo {
@@ -762,7 +1399,16 @@ impl ApiService {
                     short_name: None,
                 })
             }
-            Err(e) => Err(format!("Failed to create group instance: {}", e)),
+            Err(e) => Err(e.context("Failed to create group instance")),
+        }
+    }
+
+    /// Counts a launched instance towards the local visit count of its world
+    /// Failures are only logged, as the instance was already created
+    fn record_launch(world_id: &str, app: &AppHandle) {
+        let state = app.state::<AppState>();
+        if let Err(e) = FolderManager::record_world_launch(world_id, state.world_store()) {
+            log::warn!("Failed to count launch of {}: {}", world_id, e);
         }
     }
 
@@ -772,7 +1418,75 @@ impl ApiService {
         world_id: &str,
         instance_id: &str,
         app: AppHandle,
-    ) -> Result<String, String> {
+    ) -> Result<String, ApiError> {
         Self::get_instance_short_name_and_open_client(cookie, world_id, instance_id, app).await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::sorting_service::make_test_world;
+
+    fn search_result(id: &str, platform: Platform) -> WorldDisplayData {
+        let mut world = make_test_world(id).to_display_data();
+        world.platform = platform;
+        world
+    }
+
+    #[tokio::test]
+    async fn test_search_both_platforms_keeps_cross_platform_worlds() {
+        // The page is cached beforehand, so no request is sent
+        api_cache::init_cache();
+        api_cache::insert_search(
+            api_cache::search_key("platform=android&search=both_platforms", 1),
+            (
+                vec![
+                    search_result("wrld_quest", Platform::Quest),
+                    search_result("wrld_cross", Platform::CrossPlatform),
+                ],
+                true,
+            ),
+        );
+
+        let page = ApiService::search_worlds(
+            Arc::new(Jar::default()),
+            None,
+            None,
+            None,
+            Some("both_platforms".to_string()),
+            Some(SearchPlatform::Both),
+            None,
+            None,
+            1,
+        )
+        .await
+        .unwrap();
+
+        let ids: Vec<&str> = page.worlds.iter().map(|w| w.world_id.as_str()).collect();
+        assert_eq!(ids, vec!["wrld_cross"]);
+        // More pages may follow, even though this one was narrowed down
+        assert!(page.has_more);
+        assert_eq!(page.page, 1);
+    }
+
+    #[tokio::test]
+    async fn test_get_world_by_id_reuses_cached_lookup() {
+        api_cache::init_cache();
+        // Checked long ago, so the saved copy is due a refresh
+        let saved = make_test_world("wrld_cached_lookup");
+        let mut fetched = saved.api_data.clone();
+        fetched.world_name = "Fetched World".to_string();
+        api_cache::insert_world(fetched);
+
+        let world = ApiService::get_world_by_id(
+            "wrld_cached_lookup".to_string(),
+            Arc::new(Jar::default()),
+            vec![saved],
+            String::new(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(world.world_name, "Fetched World");
+    }
+}
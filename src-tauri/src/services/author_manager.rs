@@ -0,0 +1,216 @@
+use std::collections::HashSet;
+use std::sync::{Arc, RwLock};
+
+use chrono::Utc;
+use reqwest::cookie::Jar;
+
+use crate::definitions::{FollowedAuthor, WorldBlacklist, WorldDisplayData, WorldModel};
+use crate::errors::{AppError, ConcurrencyError, EntityError};
+
+use super::{ApiService, BlacklistManager, FileService};
+
+/// Service for managing the authors a user follows
+#[derive(Debug)]
+pub struct AuthorManager;
+
+impl AuthorManager {
+    /// Starts following an author
+    ///
+    /// # Arguments
+    /// * `author_id` - The ID of the author to follow
+    /// * `author_name` - The display name of the author
+    /// * `followed` - The list of followed authors, as a RwLock
+    ///
+    /// # Returns
+    /// Ok if the author was followed successfully
+    ///
+    /// # Errors
+    /// Returns an error if the author is already followed
+    /// Returns an error if the followed authors lock is poisoned
+    pub fn follow_author(
+        author_id: String,
+        author_name: String,
+        followed: &RwLock<Vec<FollowedAuthor>>,
+    ) -> Result<(), AppError> {
+        let mut followed_lock = followed
+            .write()
+            .map_err(|_| ConcurrencyError::PoisonedLock)?;
+
+        if followed_lock.iter().any(|a| a.author_id == author_id) {
+            return Err(EntityError::DuplicateAuthor(author_id).into());
+        }
+
+        followed_lock.push(FollowedAuthor::new(author_id, author_name));
+        FileService::write_followed_authors(&followed_lock)?;
+        Ok(())
+    }
+
+    /// Stops following an author
+    ///
+    /// # Arguments
+    /// * `author_id` - The ID of the author to unfollow
+    /// * `followed` - The list of followed authors, as a RwLock
+    ///
+    /// # Returns
+    /// Ok if the author was unfollowed successfully
+    ///
+    /// # Errors
+    /// Returns an error if the author is not followed
+    /// Returns an error if the followed authors lock is poisoned
+    pub fn unfollow_author(
+        author_id: String,
+        followed: &RwLock<Vec<FollowedAuthor>>,
+    ) -> Result<(), AppError> {
+        let mut followed_lock = followed
+            .write()
+            .map_err(|_| ConcurrencyError::PoisonedLock)?;
+
+        let index = followed_lock
+            .iter()
+            .position(|a| a.author_id == author_id)
+            .ok_or_else(|| EntityError::AuthorNotFound(author_id))?;
+
+        followed_lock.remove(index);
+        FileService::write_followed_authors(&followed_lock)?;
+        Ok(())
+    }
+
+    /// Get all followed authors
+    ///
+    /// # Arguments
+    /// * `followed` - The list of followed authors, as a RwLock
+    ///
+    /// # Returns
+    /// A vector of followed authors
+    ///
+    /// # Errors
+    /// Returns an error if the followed authors lock is poisoned
+    pub fn get_followed_authors(
+        followed: &RwLock<Vec<FollowedAuthor>>,
+    ) -> Result<Vec<FollowedAuthor>, AppError> {
+        let followed_lock = followed
+            .read()
+            .map_err(|_| ConcurrencyError::PoisonedLock)?;
+        Ok(followed_lock.clone())
+    }
+
+    /// Checks every followed author for worlds published since the last check
+    /// Worlds already in the library, or blacklisted, are not returned
+    /// The last checked time is only advanced for authors that were fetched successfully
+    ///
+    /// # Arguments
+    /// * `cookie_store` - The cookie store to use for the API
+    /// * `followed` - The list of followed authors, as a RwLock
+    /// * `worlds` - The list of worlds in the library, as a RwLock
+    /// * `blacklist` - The world blacklist, as a RwLock
+    ///
+    /// # Returns
+    /// The newly published worlds, as candidates to add to the library
+    ///
+    /// # Errors
+    /// Returns an error if any lock is poisoned
+    /// Returns an error if the followed authors could not be saved
+    pub async fn check_followed_authors_new_worlds(
+        cookie_store: Arc<Jar>,
+        followed: &RwLock<Vec<FollowedAuthor>>,
+        worlds: &RwLock<Vec<WorldModel>>,
+        blacklist: &RwLock<WorldBlacklist>,
+    ) -> Result<Vec<WorldDisplayData>, AppError> {
+        let authors = Self::get_followed_authors(followed)?;
+        let checked_at = Utc::now();
+
+        let mut candidates = vec![];
+        let mut checked_ids = HashSet::new();
+        for author in authors {
+            match ApiService::get_worlds_published_since(
+                cookie_store.clone(),
+                &author.author_id,
+                author.last_checked,
+            )
+            .await
+            {
+                Ok(new_worlds) => {
+                    candidates.extend(new_worlds);
+                    checked_ids.insert(author.author_id);
+                }
+                Err(e) => {
+                    log::warn!(
+                        "Failed to check new worlds for author {}: {}",
+                        author.author_id,
+                        e
+                    );
+                }
+            }
+        }
+
+        let known_ids: HashSet<String> = {
+            let worlds_lock = worlds
+                .read()
+                .map_err(|_| ConcurrencyError::PoisonedLock)?;
+            worlds_lock
+                .iter()
+                .map(|w| w.api_data.world_id.clone())
+                .collect()
+        };
+        candidates.retain(|w| !known_ids.contains(&w.world_id));
+        let candidates = BlacklistManager::filter_display_data(candidates, blacklist)?;
+
+        let mut followed_lock = followed
+            .write()
+            .map_err(|_| ConcurrencyError::PoisonedLock)?;
+        for author in followed_lock.iter_mut() {
+            if checked_ids.contains(&author.author_id) {
+                author.last_checked = checked_at;
+            }
+        }
+        FileService::write_followed_authors(&followed_lock)?;
+
+        Ok(candidates)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_follow_and_unfollow_author() {
+        let followed = RwLock::new(vec![]);
+        AuthorManager::follow_author("usr_a".to_string(), "Author A".to_string(), &followed)
+            .unwrap();
+        AuthorManager::follow_author("usr_b".to_string(), "Author B".to_string(), &followed)
+            .unwrap();
+
+        let result =
+            AuthorManager::follow_author("usr_a".to_string(), "Renamed".to_string(), &followed);
+        assert!(matches!(
+            result,
+            Err(AppError::Entity(EntityError::DuplicateAuthor(_)))
+        ));
+
+        AuthorManager::unfollow_author("usr_a".to_string(), &followed).unwrap();
+        let authors = AuthorManager::get_followed_authors(&followed).unwrap();
+        assert_eq!(authors.len(), 1);
+        assert_eq!(authors[0].author_name, "Author B");
+
+        // Every change is saved, so the list read back matches the one in memory
+        let saved = FileService::read_followed_authors();
+        assert_eq!(saved.len(), 1);
+        assert_eq!(saved[0].author_id, "usr_b");
+        assert_eq!(saved[0].last_checked, authors[0].last_checked);
+    }
+
+    #[test]
+    fn test_unfollow_unknown_author() {
+        let followed = RwLock::new(vec![FollowedAuthor::new(
+            "usr_a".to_string(),
+            "Author A".to_string(),
+        )]);
+        let result = AuthorManager::unfollow_author("usr_missing".to_string(), &followed);
+        assert!(matches!(
+            result,
+            Err(AppError::Entity(EntityError::AuthorNotFound(_)))
+        ));
+        assert_eq!(followed.read().unwrap().len(), 1);
+    }
+}
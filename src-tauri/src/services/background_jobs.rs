@@ -0,0 +1,23 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static PAUSED: AtomicBool = AtomicBool::new(false);
+
+/// Switch for the periodic background jobs, e.g. the recently visited import
+/// Pausing keeps them from calling the API while the user is playing, without turning
+/// them off in the preferences. Only lasts until the app is restarted
+#[derive(Debug)]
+pub struct BackgroundJobs;
+
+impl BackgroundJobs {
+    pub fn is_paused() -> bool {
+        PAUSED.load(Ordering::Relaxed)
+    }
+
+    pub fn set_paused(paused: bool) {
+        PAUSED.store(paused, Ordering::Relaxed);
+        log::info!(
+            "Background jobs {}",
+            if paused { "paused" } else { "resumed" }
+        );
+    }
+}
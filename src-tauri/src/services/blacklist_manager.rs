@@ -0,0 +1,373 @@
+use std::sync::RwLock;
+
+use crate::definitions::{WorldApiData, WorldBlacklist, WorldDisplayData};
+use crate::errors::{AppError, ConcurrencyError, EntityError};
+
+use super::FileService;
+
+/// Service for managing the user's world and author blacklist
+/// Blacklisted worlds, and worlds by blacklisted authors, are never imported
+/// into the library, nor shown in search results
+#[derive(Debug)]
+pub struct BlacklistManager;
+
+impl BlacklistManager {
+    /// Adds a world to the blacklist
+    ///
+    /// # Arguments
+    /// * `world_id` - The ID of the world to blacklist
+    /// * `blacklist` - The blacklist, as a RwLock
+    ///
+    /// # Returns
+    /// Ok if the world was added successfully
+    ///
+    /// # Errors
+    /// Returns an error if the world is already blacklisted
+    /// Returns an error if the blacklist lock is poisoned
+    pub fn add_world_to_blacklist(
+        world_id: String,
+        blacklist: &RwLock<WorldBlacklist>,
+    ) -> Result<(), AppError> {
+        let mut blacklist_lock = blacklist
+            .write()
+            .map_err(|_| ConcurrencyError::PoisonedLock)?;
+
+        if blacklist_lock.contains(&world_id) {
+            return Err(EntityError::DuplicateWorld(world_id).into());
+        }
+
+        blacklist_lock.worlds.push(world_id);
+        FileService::write_blacklist(&*blacklist_lock)?;
+        Ok(())
+    }
+
+    /// Adds several worlds to the blacklist, saving it once
+    /// Worlds which are already blacklisted are skipped
+    ///
+    /// # Arguments
+    /// * `world_ids` - The IDs of the worlds to blacklist
+    /// * `blacklist` - The blacklist, as a RwLock
+    ///
+    /// # Returns
+    /// The IDs of the worlds which were added
+    ///
+    /// # Errors
+    /// Returns an error if the blacklist lock is poisoned
+    pub fn add_worlds_to_blacklist(
+        world_ids: &[String],
+        blacklist: &RwLock<WorldBlacklist>,
+    ) -> Result<Vec<String>, AppError> {
+        let mut blacklist_lock = blacklist
+            .write()
+            .map_err(|_| ConcurrencyError::PoisonedLock)?;
+
+        let mut added = vec![];
+        for world_id in world_ids {
+            if !blacklist_lock.contains(world_id) {
+                blacklist_lock.worlds.push(world_id.clone());
+                added.push(world_id.clone());
+            }
+        }
+        if !added.is_empty() {
+            FileService::write_blacklist(&*blacklist_lock)?;
+        }
+        Ok(added)
+    }
+
+    /// Removes a world from the blacklist
+    ///
+    /// # Arguments
+    /// * `world_id` - The ID of the world to remove
+    /// * `blacklist` - The blacklist, as a RwLock
+    ///
+    /// # Returns
+    /// Ok if the world was removed successfully
+    ///
+    /// # Errors
+    /// Returns an error if the world is not blacklisted
+    /// Returns an error if the blacklist lock is poisoned
+    pub fn remove_world_from_blacklist(
+        world_id: String,
+        blacklist: &RwLock<WorldBlacklist>,
+    ) -> Result<(), AppError> {
+        let mut blacklist_lock = blacklist
+            .write()
+            .map_err(|_| ConcurrencyError::PoisonedLock)?;
+
+        let index = blacklist_lock
+            .worlds
+            .iter()
+            .position(|id| id == &world_id)
+            .ok_or_else(|| EntityError::WorldNotFound(world_id))?;
+
+        blacklist_lock.worlds.remove(index);
+        FileService::write_blacklist(&*blacklist_lock)?;
+        Ok(())
+    }
+
+    /// Get the IDs of all blacklisted worlds
+    ///
+    /// # Arguments
+    /// * `blacklist` - The blacklist, as a RwLock
+    ///
+    /// # Returns
+    /// A vector of blacklisted world IDs
+    ///
+    /// # Errors
+    /// Returns an error if the blacklist lock is poisoned
+    pub fn get_blacklist(blacklist: &RwLock<WorldBlacklist>) -> Result<Vec<String>, AppError> {
+        let blacklist_lock = blacklist
+            .read()
+            .map_err(|_| ConcurrencyError::PoisonedLock)?;
+        Ok(blacklist_lock.worlds.clone())
+    }
+
+    /// Adds an author to the blacklist
+    ///
+    /// # Arguments
+    /// * `author_id` - The ID of the author to blacklist
+    /// * `blacklist` - The blacklist, as a RwLock
+    ///
+    /// # Returns
+    /// Ok if the author was added successfully
+    ///
+    /// # Errors
+    /// Returns an error if the author is already blacklisted
+    /// Returns an error if the blacklist lock is poisoned
+    pub fn add_author_to_blacklist(
+        author_id: String,
+        blacklist: &RwLock<WorldBlacklist>,
+    ) -> Result<(), AppError> {
+        let mut blacklist_lock = blacklist
+            .write()
+            .map_err(|_| ConcurrencyError::PoisonedLock)?;
+
+        if blacklist_lock.contains_author(&author_id) {
+            return Err(EntityError::DuplicateAuthor(author_id).into());
+        }
+
+        blacklist_lock.authors.push(author_id);
+        FileService::write_blacklist(&*blacklist_lock)?;
+        Ok(())
+    }
+
+    /// Removes an author from the blacklist
+    ///
+    /// # Arguments
+    /// * `author_id` - The ID of the author to remove
+    /// * `blacklist` - The blacklist, as a RwLock
+    ///
+    /// # Returns
+    /// Ok if the author was removed successfully
+    ///
+    /// # Errors
+    /// Returns an error if the author is not blacklisted
+    /// Returns an error if the blacklist lock is poisoned
+    pub fn remove_author_from_blacklist(
+        author_id: String,
+        blacklist: &RwLock<WorldBlacklist>,
+    ) -> Result<(), AppError> {
+        let mut blacklist_lock = blacklist
+            .write()
+            .map_err(|_| ConcurrencyError::PoisonedLock)?;
+
+        let index = blacklist_lock
+            .authors
+            .iter()
+            .position(|id| id == &author_id)
+            .ok_or_else(|| EntityError::AuthorNotFound(author_id))?;
+
+        blacklist_lock.authors.remove(index);
+        FileService::write_blacklist(&*blacklist_lock)?;
+        Ok(())
+    }
+
+    /// Get the IDs of all blacklisted authors
+    ///
+    /// # Arguments
+    /// * `blacklist` - The blacklist, as a RwLock
+    ///
+    /// # Returns
+    /// A vector of blacklisted author IDs
+    ///
+    /// # Errors
+    /// Returns an error if the blacklist lock is poisoned
+    pub fn get_author_blacklist(
+        blacklist: &RwLock<WorldBlacklist>,
+    ) -> Result<Vec<String>, AppError> {
+        let blacklist_lock = blacklist
+            .read()
+            .map_err(|_| ConcurrencyError::PoisonedLock)?;
+        Ok(blacklist_lock.authors.clone())
+    }
+
+    /// Removes blacklisted worlds from a list of api data
+    ///
+    /// # Arguments
+    /// * `worlds` - The list of worlds to filter
+    /// * `blacklist` - The blacklist, as a RwLock
+    ///
+    /// # Returns
+    /// The worlds which are not blacklisted
+    ///
+    /// # Errors
+    /// Returns an error if the blacklist lock is poisoned
+    pub fn filter_api_data(
+        worlds: Vec<WorldApiData>,
+        blacklist: &RwLock<WorldBlacklist>,
+    ) -> Result<Vec<WorldApiData>, AppError> {
+        let blacklist_lock = blacklist
+            .read()
+            .map_err(|_| ConcurrencyError::PoisonedLock)?;
+        Ok(worlds
+            .into_iter()
+            .filter(|w| {
+                let blacklisted = blacklist_lock.is_blocked(&w.world_id, &w.author_id);
+                if blacklisted {
+                    log::info!("Skipping blacklisted world: {}", w.world_id);
+                }
+                !blacklisted
+            })
+            .collect())
+    }
+
+    /// Removes blacklisted worlds from a list of display data
+    ///
+    /// # Arguments
+    /// * `worlds` - The list of worlds to filter
+    /// * `blacklist` - The blacklist, as a RwLock
+    ///
+    /// # Returns
+    /// The worlds which are not blacklisted
+    ///
+    /// # Errors
+    /// Returns an error if the blacklist lock is poisoned
+    pub fn filter_display_data(
+        worlds: Vec<WorldDisplayData>,
+        blacklist: &RwLock<WorldBlacklist>,
+    ) -> Result<Vec<WorldDisplayData>, AppError> {
+        let blacklist_lock = blacklist
+            .read()
+            .map_err(|_| ConcurrencyError::PoisonedLock)?;
+        Ok(worlds
+            .into_iter()
+            .filter(|w| !blacklist_lock.is_blocked(&w.world_id, &w.author_id))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::definitions::WorldAvailability;
+
+    #[test]
+    fn test_filter_api_data_skips_blacklisted() {
+        let blacklist = RwLock::new(WorldBlacklist {
+            worlds: vec!["wrld_blocked".to_string()],
+            authors: vec!["usr_blocked".to_string()],
+        });
+        let make_world = |id: &str, author_id: &str| WorldApiData {
+            image_url: "".to_string(),
+            world_name: "Test World".to_string(),
+            world_id: id.to_string(),
+            author_name: "Test Author".to_string(),
+            author_id: author_id.to_string(),
+            capacity: 0,
+            recommended_capacity: None,
+            tags: vec![],
+            publication_date: None,
+            last_update: chrono::Utc::now(),
+            description: "".to_string(),
+            visits: None,
+            favorites: 0,
+            platform: vec![],
+            packages: vec![],
+            availability: WorldAvailability::Public,
+        };
+
+        let result = BlacklistManager::filter_api_data(
+            vec![
+                make_world("wrld_allowed", "usr_allowed"),
+                make_world("wrld_blocked", "usr_allowed"),
+                make_world("wrld_by_blocked_author", "usr_blocked"),
+            ],
+            &blacklist,
+        )
+        .unwrap();
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].world_id, "wrld_allowed");
+    }
+
+    #[test]
+    fn test_duplicate_and_unknown_entries() {
+        let blacklist = RwLock::new(WorldBlacklist {
+            worlds: vec!["wrld_a".to_string()],
+            authors: vec!["usr_a".to_string()],
+        });
+
+        let result = BlacklistManager::add_world_to_blacklist("wrld_a".to_string(), &blacklist);
+        assert!(matches!(
+            result,
+            Err(AppError::Entity(EntityError::DuplicateWorld(_)))
+        ));
+        let result = BlacklistManager::add_author_to_blacklist("usr_a".to_string(), &blacklist);
+        assert!(matches!(
+            result,
+            Err(AppError::Entity(EntityError::DuplicateAuthor(_)))
+        ));
+        let result =
+            BlacklistManager::remove_world_from_blacklist("wrld_missing".to_string(), &blacklist);
+        assert!(matches!(
+            result,
+            Err(AppError::Entity(EntityError::WorldNotFound(_)))
+        ));
+        let result =
+            BlacklistManager::remove_author_from_blacklist("usr_missing".to_string(), &blacklist);
+        assert!(matches!(
+            result,
+            Err(AppError::Entity(EntityError::AuthorNotFound(_)))
+        ));
+
+        // Failed changes leave the blacklist as it was
+        let blacklist = blacklist.read().unwrap();
+        assert_eq!(blacklist.worlds, vec!["wrld_a"]);
+        assert_eq!(blacklist.authors, vec!["usr_a"]);
+    }
+
+    #[test]
+    fn test_add_worlds_skips_blacklisted() {
+        let blacklist = RwLock::new(WorldBlacklist {
+            worlds: vec!["wrld_a".to_string()],
+            authors: vec![],
+        });
+        let added = BlacklistManager::add_worlds_to_blacklist(
+            &[
+                "wrld_a".to_string(),
+                "wrld_b".to_string(),
+                "wrld_b".to_string(),
+            ],
+            &blacklist,
+        )
+        .unwrap();
+        assert_eq!(added, vec!["wrld_b"]);
+        assert_eq!(
+            BlacklistManager::get_blacklist(&blacklist).unwrap(),
+            vec!["wrld_a", "wrld_b"]
+        );
+
+        BlacklistManager::remove_world_from_blacklist("wrld_a".to_string(), &blacklist).unwrap();
+        assert_eq!(FileService::read_blacklist().worlds, vec!["wrld_b"]);
+    }
+
+    #[test]
+    fn test_get_blacklist() {
+        let blacklist = RwLock::new(WorldBlacklist {
+            worlds: vec!["wrld_a".to_string(), "wrld_b".to_string()],
+            authors: vec![],
+        });
+        let result = BlacklistManager::get_blacklist(&blacklist).unwrap();
+        assert_eq!(result, vec!["wrld_a", "wrld_b"]);
+    }
+}
@@ -0,0 +1,429 @@
+use std::collections::HashSet;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::sync::{Mutex, OnceLock, PoisonError, RwLock};
+
+use chrono::{DateTime, Utc};
+use tauri::AppHandle;
+use tauri_specta::Event;
+
+use crate::app_state::AppState;
+use crate::definitions::{ChangeEvent, FolderNameCollision, LibraryChange, WorldFlag, WorldModel};
+use crate::errors::{AppError, ConcurrencyError, EntityError, FileError};
+
+use super::{FileService, FolderManager, Persistence};
+
+/// The feed is compacted to the newest `KEPT_EVENTS` once it grows past `MAX_EVENTS`
+const MAX_EVENTS: usize = 20_000;
+const KEPT_EVENTS: usize = 10_000;
+
+/// The id of the next change, loaded on first use
+/// Also serializes appends, as the feed is a single file
+static NEXT_ID: Mutex<Option<u64>> = Mutex::new(None);
+
+/// Set on startup, no events are emitted without it, e.g. in headless mode
+static HANDLE: OnceLock<AppHandle> = OnceLock::new();
+
+/// Append-only log of library mutations, stored in changes.jsonl
+///
+/// Changes are recorded where the user, or a background import, mutates the library, and
+/// power the change history, undo, and replaying local edits when a sync conflict is
+/// resolved by keeping the remote copy. Undoing appends an `Undone` change rather than
+/// removing the undone one
+#[derive(Debug)]
+pub struct ChangeFeed;
+
+impl ChangeFeed {
+    /// Starts emitting every recorded change as a `ChangeEvent` to all windows
+    pub fn init(handle: AppHandle) {
+        let _ = HANDLE.set(handle);
+    }
+
+    /// Appends a change to the feed, and emits it to all windows
+    /// Failures are only logged, as the change itself was already made
+    pub fn record(change: LibraryChange) {
+        let mut next_id = NEXT_ID.lock().unwrap_or_else(PoisonError::into_inner);
+        let id = *next_id.get_or_insert_with(Self::load_next_id);
+        let event = ChangeEvent {
+            id,
+            timestamp: Utc::now(),
+            change,
+        };
+
+        let result = serde_json::to_string(&event)
+            .map_err(|e| e.to_string())
+            .and_then(|line| {
+                Persistence::write_direct(&FileService::get_changes_path(), |path| {
+                    OpenOptions::new()
+                        .create(true)
+                        .append(true)
+                        .open(path)
+                        .and_then(|mut file| writeln!(file, "{}", line))
+                })
+                .map_err(|e| e.to_string())
+            });
+        match result {
+            Ok(()) => {
+                *next_id = Some(id + 1);
+                if let Some(handle) = HANDLE.get() {
+                    if let Err(e) = event.emit(handle) {
+                        log::error!("Failed to emit change event: {}", e);
+                    }
+                }
+            }
+            Err(e) => log::error!("Failed to record change {:?}: {}", event.change, e),
+        }
+    }
+
+    /// Gets the worlds of `world_ids` which are not in the library yet
+    /// Pass them to `record_worlds_added` after adding the worlds
+    ///
+    /// # Errors
+    /// Returns an error if the worlds lock is poisoned
+    pub fn missing_worlds(
+        worlds: &RwLock<Vec<WorldModel>>,
+        world_ids: Vec<String>,
+    ) -> Result<Vec<String>, AppError> {
+        let worlds = worlds.read().map_err(|_| ConcurrencyError::PoisonedLock)?;
+        Ok(world_ids
+            .into_iter()
+            .filter(|id| !worlds.iter().any(|w| &w.api_data.world_id == id))
+            .collect())
+    }
+
+    /// Records the worlds of `missing` which are now in the library as added
+    /// Blacklisted worlds are not added, so they are left out
+    pub fn record_worlds_added(worlds: &RwLock<Vec<WorldModel>>, missing: Vec<String>) {
+        let added: Vec<String> = match worlds.read() {
+            Ok(worlds) => missing
+                .into_iter()
+                .filter(|id| worlds.iter().any(|w| &w.api_data.world_id == id))
+                .collect(),
+            Err(_) => return,
+        };
+        if !added.is_empty() {
+            Self::record(LibraryChange::WorldsAdded { world_ids: added });
+        }
+    }
+
+    /// Gets the changes involving a world, newest first
+    ///
+    /// # Errors
+    /// Returns an error if the feed could not be read
+    pub fn history(world_id: &str) -> Result<Vec<ChangeEvent>, AppError> {
+        let mut events = Self::read()?;
+        events.retain(|event| event.change.concerns_world(world_id));
+        events.reverse();
+        Ok(events)
+    }
+
+    /// Gets the newest changes, newest first
+    ///
+    /// # Errors
+    /// Returns an error if the feed could not be read
+    pub fn recent(limit: usize) -> Result<Vec<ChangeEvent>, AppError> {
+        let events = Self::read()?;
+        Ok(events.into_iter().rev().take(limit).collect())
+    }
+
+    /// Gets the changes made after `since` which were not undone, oldest first
+    ///
+    /// # Errors
+    /// Returns an error if the feed could not be read
+    pub fn effective_since(since: DateTime<Utc>) -> Result<Vec<ChangeEvent>, AppError> {
+        let events = Self::read()?;
+        let undone = Self::undone_ids(&events);
+        Ok(events
+            .into_iter()
+            .filter(|event| event.timestamp > since && !undone.contains(&event.id))
+            .filter(|event| !matches!(event.change, LibraryChange::Undone { .. }))
+            .collect())
+    }
+
+    /// Undoes the newest change which was not undone yet
+    ///
+    /// # Arguments
+    /// * `state` - The app state, holding the library the change is reverted in
+    ///
+    /// # Returns
+    /// The undone change
+    ///
+    /// # Errors
+    /// Returns an error if there is nothing to undo, or the change cannot be undone
+    /// Returns an error if the feed could not be read or the library could not be written
+    pub fn undo(state: &AppState) -> Result<ChangeEvent, AppError> {
+        let events = Self::read()?;
+        let event = Self::undoable(&events)
+            .cloned()
+            .ok_or_else(|| EntityError::InvalidOperation("Nothing to undo".to_string()))?;
+        Self::revert(&event.change, state)?;
+        log::info!("Undid change {}: {:?}", event.id, event.change);
+        Self::record(LibraryChange::Undone {
+            change_id: event.id,
+        });
+        Ok(event)
+    }
+
+    /// The newest change which was not undone, and is not an undo itself
+    fn undoable(events: &[ChangeEvent]) -> Option<&ChangeEvent> {
+        let undone = Self::undone_ids(events);
+        events.iter().rev().find(|event| {
+            !undone.contains(&event.id) && !matches!(event.change, LibraryChange::Undone { .. })
+        })
+    }
+
+    fn undone_ids(events: &[ChangeEvent]) -> HashSet<u64> {
+        events
+            .iter()
+            .filter_map(|event| match event.change {
+                LibraryChange::Undone { change_id } => Some(change_id),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Applies the inverse of a change
+    fn revert(change: &LibraryChange, state: &AppState) -> Result<(), AppError> {
+        let folders = state.folder_store();
+        let worlds = state.world_store();
+        match change.clone() {
+            LibraryChange::WorldsAdded { world_ids } => {
+                for world_id in world_ids {
+                    FolderManager::delete_world(world_id, folders, worlds)?;
+                }
+            }
+            LibraryChange::WorldHidden {
+                world_id,
+                folders: hidden_from,
+            } => {
+                FolderManager::unhide_world(world_id.clone(), folders, worlds)?;
+                for folder_name in hidden_from {
+                    FolderManager::add_world_to_folder(
+                        folder_name,
+                        world_id.clone(),
+                        folders,
+                        worlds,
+                    )?;
+                }
+            }
+            LibraryChange::WorldUnhidden { world_id } => {
+                FolderManager::hide_world(world_id, folders, worlds)?;
+            }
+            LibraryChange::AddedToFolder {
+                folder_name,
+                world_ids,
+            } => {
+                for world_id in world_ids {
+                    FolderManager::remove_world_from_folder(
+                        folder_name.clone(),
+                        world_id,
+                        folders,
+                        worlds,
+                    )?;
+                }
+            }
+            LibraryChange::RemovedFromFolder {
+                folder_name,
+                world_id,
+            } => {
+                FolderManager::add_world_to_folder(folder_name, world_id, folders, worlds)?;
+            }
+            LibraryChange::FolderCreated { folder_name } => {
+                FolderManager::delete_folder(folder_name, folders, worlds)?;
+            }
+            LibraryChange::FolderDeleted {
+                folder_name,
+                world_ids,
+            } => {
+                let folder_name = FolderManager::create_folder(folder_name, folders)?;
+                FolderManager::add_worlds_to_folder(folder_name, world_ids, folders, worlds)?;
+            }
+            LibraryChange::FolderRenamed { old_name, new_name } => {
+                // A folder created with the old name since is left alone
+                FolderManager::rename_folder(
+                    new_name,
+                    old_name,
+                    FolderNameCollision::Reject,
+                    folders,
+                    worlds,
+                    state.preference_store(),
+                )?;
+            }
+            LibraryChange::FolderArchived {
+                folder_name,
+                archived,
+            } => {
+                FolderManager::set_folder_archived(folder_name, !archived, folders)?;
+            }
+            LibraryChange::FlagChanged {
+                world_id,
+                flag,
+                value,
+            } => Self::set_flag(world_id, flag, !value, state)?,
+            LibraryChange::WorldDeleted { .. } => {
+                return Err(EntityError::InvalidOperation(
+                    "Deleting a world cannot be undone".to_string(),
+                )
+                .into());
+            }
+            LibraryChange::Undone { .. } => {
+                return Err(
+                    EntityError::InvalidOperation("Undoing cannot be undone".to_string()).into(),
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Sets a world flag, the same way the world status commands do
+    ///
+    /// # Errors
+    /// Returns an error if the world is not found or the worlds could not be written
+    pub fn set_flag(
+        world_id: String,
+        flag: WorldFlag,
+        value: bool,
+        state: &AppState,
+    ) -> Result<(), AppError> {
+        let worlds = state.world_store();
+        match flag {
+            WorldFlag::Photographed => {
+                FolderManager::set_world_photographed(world_id, value, worlds)
+            }
+            WorldFlag::Shared => FolderManager::set_world_shared(world_id, value, worlds),
+            WorldFlag::Favorite => FolderManager::set_world_favorite(world_id, value, worlds),
+            WorldFlag::EventCritical => {
+                FolderManager::set_world_event_critical(world_id, value, worlds)
+            }
+        }
+    }
+
+    fn read() -> Result<Vec<ChangeEvent>, AppError> {
+        let path = FileService::get_changes_path();
+        if !path.exists() {
+            return Ok(vec![]);
+        }
+        let content = fs::read_to_string(&path).map_err(|_| FileError::FileNotFound)?;
+        Ok(Self::parse(&content))
+    }
+
+    /// Parses the feed, skipping lines cut off by a crash
+    fn parse(content: &str) -> Vec<ChangeEvent> {
+        content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .filter_map(|line| match serde_json::from_str(line) {
+                Ok(event) => Some(event),
+                Err(e) => {
+                    log::warn!("Skipping unreadable change: {}", e);
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Compacts the feed if needed, and finds the id following the newest change
+    fn load_next_id() -> u64 {
+        let mut events = match Self::read() {
+            Ok(events) => events,
+            Err(e) => {
+                log::error!("Failed to read change feed: {}", e);
+                return 0;
+            }
+        };
+        let next_id = events.last().map_or(0, |event| event.id + 1);
+
+        if events.len() > MAX_EVENTS {
+            events.drain(..events.len() - KEPT_EVENTS);
+            let content: String = events
+                .iter()
+                .filter_map(|event| serde_json::to_string(event).ok())
+                .map(|line| line + "\n")
+                .collect();
+            match FileService::atomic_write(&FileService::get_changes_path(), &content) {
+                Ok(()) => log::info!("Compacted change feed to {} changes", KEPT_EVENTS),
+                Err(e) => log::error!("Failed to compact change feed: {}", e),
+            }
+        }
+        next_id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::definitions::{FolderModel, PreferenceModel};
+    use crate::services::sorting_service::make_test_world;
+
+    fn event(id: u64, change: LibraryChange) -> ChangeEvent {
+        ChangeEvent {
+            id,
+            timestamp: Utc::now(),
+            change,
+        }
+    }
+
+    fn created(name: &str) -> LibraryChange {
+        LibraryChange::FolderCreated {
+            folder_name: name.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_undo_walks_back_through_changes() {
+        let mut events = vec![event(0, created("A")), event(1, created("B"))];
+        assert_eq!(ChangeFeed::undoable(&events).map(|e| e.id), Some(1));
+
+        events.push(event(2, LibraryChange::Undone { change_id: 1 }));
+        assert_eq!(ChangeFeed::undoable(&events).map(|e| e.id), Some(0));
+
+        events.push(event(3, LibraryChange::Undone { change_id: 0 }));
+        assert_eq!(ChangeFeed::undoable(&events), None);
+    }
+
+    #[test]
+    fn test_parse_skips_truncated_lines() {
+        let line = serde_json::to_string(&event(0, created("A"))).unwrap();
+        let content = format!("{}\n{{\"id\":1,\"times", line);
+
+        let events = ChangeFeed::parse(&content);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].change, created("A"));
+    }
+
+    #[test]
+    fn test_revert_changes() {
+        let mut world = make_test_world("wrld_reverted");
+        world.user_data.is_favorite = true;
+        let state = AppState::new(
+            PreferenceModel::new(),
+            vec![FolderModel::new("Event".to_string())],
+            vec![world],
+        );
+
+        let favorited = LibraryChange::FlagChanged {
+            world_id: "wrld_reverted".to_string(),
+            flag: WorldFlag::Favorite,
+            value: true,
+        };
+        ChangeFeed::revert(&favorited, &state).unwrap();
+        assert!(!state.worlds().unwrap()[0].user_data.is_favorite);
+
+        let removed = LibraryChange::RemovedFromFolder {
+            folder_name: "Event".to_string(),
+            world_id: "wrld_reverted".to_string(),
+        };
+        ChangeFeed::revert(&removed, &state).unwrap();
+        assert_eq!(state.folders().unwrap()[0].world_ids, vec!["wrld_reverted"]);
+
+        let deleted = LibraryChange::WorldDeleted {
+            world_id: "wrld_reverted".to_string(),
+        };
+        assert!(matches!(
+            ChangeFeed::revert(&deleted, &state),
+            Err(AppError::Entity(EntityError::InvalidOperation(_)))
+        ));
+        let undone = LibraryChange::Undone { change_id: 0 };
+        assert!(ChangeFeed::revert(&undone, &state).is_err());
+    }
+}
@@ -0,0 +1,182 @@
+use std::collections::HashSet;
+use std::time::Duration;
+
+use tauri::{AppHandle, Manager};
+use tauri_plugin_clipboard_manager::ClipboardExt;
+use tauri_specta::Event;
+
+use crate::api::world::ReleaseStatus;
+use crate::app_state::AppState;
+use crate::definitions::{ClipboardWatchMode, ClipboardWorldDetected};
+use crate::errors::{ApiError, AppError, EntityError};
+use crate::{AUTHENTICATOR, INITSTATE};
+
+use super::{
+    ApiService, ChangeFeed, FileService, FolderManager, GuestMode, InboxService, WorldReference,
+};
+
+/// How often the clipboard is checked while watching is enabled
+const POLL_INTERVAL: Duration = Duration::from_millis(1500);
+
+/// Service for collecting VRChat world URLs copied to the clipboard, e.g. while browsing
+/// the VRChat website
+#[derive(Debug)]
+pub struct ClipboardWatcher;
+
+impl ClipboardWatcher {
+    /// Checks the clipboard periodically while watching is enabled
+    /// The setting is re-read on every check, so toggling it takes effect without a restart.
+    /// Each copied world is only handled once per session, and copying the same text
+    /// again does nothing
+    ///
+    /// # Arguments
+    /// * `handle` - The app handle, to read the clipboard and reach the app state
+    pub async fn run(handle: AppHandle) {
+        // Whatever was copied before the app started is not picked up
+        let mut last_text = handle.clipboard().read_text().ok();
+        let mut handled: HashSet<String> = HashSet::new();
+
+        loop {
+            tokio::time::sleep(POLL_INTERVAL).await;
+
+            let settings = FileService::read_custom_data()
+                .preferences
+                .clipboard_watch
+                .unwrap_or_default();
+            if !settings.enabled || GuestMode::is_enabled() {
+                continue;
+            }
+
+            // Fails when the clipboard holds something other than text
+            let Ok(text) = handle.clipboard().read_text() else {
+                continue;
+            };
+            if last_text.as_ref() == Some(&text) {
+                continue;
+            }
+            last_text = Some(text.clone());
+
+            let Some(world_id) = Self::extract_world_id(&text) else {
+                continue;
+            };
+            if !handled.insert(world_id.clone()) {
+                continue;
+            }
+            log::info!("World URL copied to clipboard: {}", world_id);
+
+            let added = match settings.mode {
+                ClipboardWatchMode::Offer => false,
+                ClipboardWatchMode::AutoAdd => {
+                    let state = handle.state::<AppState>();
+                    match Self::add_to_inbox(world_id.clone(), &state).await {
+                        Ok(()) => true,
+                        Err(e) => {
+                            log::warn!("Failed to add copied world {}: {}", world_id, e);
+                            // Left for the user to add by hand
+                            false
+                        }
+                    }
+                }
+            };
+
+            if let Err(e) = (ClipboardWorldDetected { world_id, added }).emit(&handle) {
+                log::error!("Failed to emit clipboard world event: {}", e);
+            }
+        }
+    }
+
+    /// Finds the world ID in a copied vrchat.com world or launch URL
+    /// Other text is ignored, even when it contains a world ID
+    fn extract_world_id(text: &str) -> Option<String> {
+        let url = text.trim();
+        if url.contains(char::is_whitespace) {
+            return None;
+        }
+        let rest = url
+            .strip_prefix("https://")
+            .or_else(|| url.strip_prefix("http://"))?;
+        let path = rest.strip_prefix("www.").unwrap_or(rest);
+        if !path.starts_with("vrchat.com/home/") {
+            return None;
+        }
+        WorldReference::parse(url)
+            .ok()
+            .map(|reference| reference.world_id)
+    }
+
+    /// Fetches the world if needed, and puts it into the inbox
+    async fn add_to_inbox(world_id: String, state: &AppState) -> Result<(), AppError> {
+        let known = state
+            .worlds()?
+            .iter()
+            .any(|w| w.api_data.world_id == world_id);
+        if !known {
+            if INITSTATE.get().read().await.user_id.is_empty() {
+                return Err(ApiError::AuthenticationFailed.into());
+            }
+            let cookie_store = AUTHENTICATOR.get().read().await.get_cookies();
+            let (world, status) =
+                ApiService::get_world_with_release_status(cookie_store, &world_id).await?;
+            if status != ReleaseStatus::Public {
+                return Err(EntityError::InvalidOperation(format!(
+                    "World {} is not public",
+                    world_id
+                ))
+                .into());
+            }
+            FolderManager::add_worlds(state.world_store(), vec![world], state.blacklist_store())?;
+            ChangeFeed::record_worlds_added(state.world_store(), vec![world_id.clone()]);
+        }
+
+        // Blacklisted worlds were not added above, and are skipped by the inbox as well
+        InboxService::add_worlds(&[world_id], state.world_store())?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WORLD_ID: &str = "wrld_4cf554b4-430c-4f8f-b53e-1f294eed230b";
+
+    #[test]
+    fn test_extract_world_id_from_urls() {
+        for url in [
+            format!("https://vrchat.com/home/world/{}", WORLD_ID),
+            format!("https://vrchat.com/home/world/{}/info", WORLD_ID),
+            format!(
+                " https://www.vrchat.com/home/launch?worldId={}&instanceId=1 ",
+                WORLD_ID
+            ),
+        ] {
+            assert_eq!(
+                ClipboardWatcher::extract_world_id(&url).as_deref(),
+                Some(WORLD_ID)
+            );
+        }
+    }
+
+    #[test]
+    fn test_extract_world_id_ignores_other_text() {
+        assert_eq!(ClipboardWatcher::extract_world_id(WORLD_ID), None);
+        assert_eq!(
+            ClipboardWatcher::extract_world_id(&format!(
+                "see https://vrchat.com/home/world/{}",
+                WORLD_ID
+            )),
+            None
+        );
+        assert_eq!(
+            ClipboardWatcher::extract_world_id(&format!(
+                "https://example.com/home/world/{}",
+                WORLD_ID
+            )),
+            None
+        );
+        assert_eq!(
+            ClipboardWatcher::extract_world_id("https://vrchat.com/home/world/wrld_short"),
+            None
+        );
+    }
+}
@@ -0,0 +1,119 @@
+use std::future::Future;
+use std::sync::Arc;
+
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+use crate::REQUEST_SCHEDULER;
+
+/// Runs API fetches several at a time, as many as the request scheduler lets be in flight
+/// Fetches still queue in the scheduler, so the request budget and the priority of
+/// interactive requests are kept; this only stops them waiting for one another
+///
+/// Dropping the fetcher aborts the fetches which have not completed, e.g. when the task
+/// using it is cancelled
+pub struct ConcurrentFetcher<T> {
+    tasks: JoinSet<(usize, T)>,
+    permits: Arc<Semaphore>,
+    limit: usize,
+    spawned: usize,
+}
+
+impl<T: Send + 'static> ConcurrentFetcher<T> {
+    pub fn new() -> Self {
+        let limit = REQUEST_SCHEDULER
+            .try_get()
+            .map(|scheduler| scheduler.max_in_flight())
+            .unwrap_or(1);
+        Self::with_limit(limit as usize)
+    }
+
+    fn with_limit(limit: usize) -> Self {
+        let limit = limit.max(1);
+        Self {
+            tasks: JoinSet::new(),
+            permits: Arc::new(Semaphore::new(limit)),
+            limit,
+            spawned: 0,
+        }
+    }
+
+    /// How many fetches run at once
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    /// Queues a fetch, returning its index, which `next` returns alongside its result
+    pub fn spawn<F>(&mut self, fetch: F) -> usize
+    where
+        F: Future<Output = T> + Send + 'static,
+    {
+        let index = self.spawned;
+        self.spawned += 1;
+
+        let permits = Arc::clone(&self.permits);
+        self.tasks.spawn(async move {
+            // The semaphore is never closed, so the permit is always granted
+            let _permit = permits.acquire_owned().await;
+            (index, fetch.await)
+        });
+        index
+    }
+
+    /// Waits for the next fetch to complete, in the order they complete
+    /// Returns None once every fetch has completed
+    pub async fn next(&mut self) -> Option<(usize, T)> {
+        loop {
+            match self.tasks.join_next().await? {
+                Ok(result) => return Some(result),
+                Err(e) => log::error!("Fetch task failed: {}", e),
+            }
+        }
+    }
+
+    /// Waits for every fetch, returning the results in the order the fetches were queued
+    /// The results of fetches which panicked are left out
+    pub async fn collect(mut self) -> Vec<T> {
+        let mut results = Vec::with_capacity(self.spawned);
+        while let Some(result) = self.next().await {
+            results.push(result);
+        }
+        results.sort_by_key(|(index, _)| *index);
+        results.into_iter().map(|(_, result)| result).collect()
+    }
+}
+
+impl<T: Send + 'static> Default for ConcurrentFetcher<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_fetches_are_bounded_and_ordered() {
+        let mut fetcher = ConcurrentFetcher::with_limit(2);
+        let running = Arc::new(AtomicUsize::new(0));
+        let most_running = Arc::new(AtomicUsize::new(0));
+
+        for i in 0..6u64 {
+            let running = Arc::clone(&running);
+            let most_running = Arc::clone(&most_running);
+            fetcher.spawn(async move {
+                let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                most_running.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(30 - i * 5)).await;
+                running.fetch_sub(1, Ordering::SeqCst);
+                i
+            });
+        }
+
+        assert_eq!(fetcher.collect().await, vec![0, 1, 2, 3, 4, 5]);
+        assert_eq!(most_running.load(Ordering::SeqCst), 2);
+    }
+}
@@ -0,0 +1,196 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+use std::sync::RwLock;
+
+use chrono::Utc;
+
+use crate::definitions::WorldModel;
+use crate::errors::{AppError, ConcurrencyError, EntityError, FileError};
+
+use super::{FileService, Persistence};
+
+/// Image formats accepted as covers
+const COVER_EXTENSIONS: [&str; 5] = ["png", "jpg", "jpeg", "webp", "gif"];
+
+/// Largest image accepted as a cover, in bytes
+const MAX_COVER_SIZE: u64 = 20 * 1024 * 1024;
+
+/// Service for local images shown on world cards instead of the VRChat thumbnail,
+/// e.g. when the thumbnail is outdated or a curator wants consistent artwork
+#[derive(Debug)]
+pub struct CoverImageService;
+
+impl CoverImageService {
+    /// Copies an image into the app's data and shows it instead of the world's thumbnail
+    /// The world's previous cover is removed
+    ///
+    /// # Arguments
+    /// * `world_id` - The ID of the world
+    /// * `source` - The image to copy
+    /// * `worlds` - The list of worlds, as a RwLock
+    ///
+    /// # Errors
+    /// Returns an error if the image is not a PNG, JPEG, WebP or GIF file, or is too large
+    /// Returns an error if the world is not found
+    /// Returns an error if the image could not be copied or the cover could not be saved
+    pub fn set(
+        world_id: &str,
+        source: &Path,
+        worlds: &RwLock<Vec<WorldModel>>,
+    ) -> Result<(), AppError> {
+        let extension = Self::extension(source)?;
+        let size = fs::metadata(source)
+            .map_err(|_| FileError::FileNotFound)?
+            .len();
+        if size > MAX_COVER_SIZE {
+            return Err(EntityError::InvalidOperation(
+                "Cover images must be at most 20 MB".to_string(),
+            )
+            .into());
+        }
+
+        let mut worlds_lock = worlds.write().map_err(|_| ConcurrencyError::PoisonedLock)?;
+        let world = worlds_lock
+            .iter_mut()
+            .find(|w| w.api_data.world_id == world_id)
+            .ok_or_else(|| EntityError::WorldNotFound(world_id.to_string()))?;
+
+        // A new name for every image, so the webview never shows the previous one from its cache
+        let file_name = format!(
+            "{}_{}.{}",
+            world_id,
+            Utc::now().timestamp_millis(),
+            extension
+        );
+        let cover_path = FileService::get_cover_dir().join(&file_name);
+        Persistence::write_direct(&cover_path, |cover_path| {
+            cover_path.parent().map_or(Ok(()), fs::create_dir_all)?;
+            fs::copy(source, cover_path)
+        })
+        .map_err(|_| FileError::FileWriteError)?;
+
+        let mut custom_data = FileService::read_custom_data();
+        custom_data.set_world_cover(world_id, Some(&file_name));
+        if let Err(e) = FileService::write_custom_data(&custom_data) {
+            Self::remove_file(&file_name);
+            return Err(e.into());
+        }
+
+        if let Some(previous) = world.user_data.cover_image.replace(file_name) {
+            Self::remove_file(&previous);
+        }
+        Ok(())
+    }
+
+    /// Shows the world's VRChat thumbnail again, removing its cover image
+    ///
+    /// # Arguments
+    /// * `world_id` - The ID of the world
+    /// * `worlds` - The list of worlds, as a RwLock
+    ///
+    /// # Errors
+    /// Returns an error if the world is not found
+    /// Returns an error if the change could not be saved
+    pub fn clear(world_id: &str, worlds: &RwLock<Vec<WorldModel>>) -> Result<(), AppError> {
+        let mut worlds_lock = worlds.write().map_err(|_| ConcurrencyError::PoisonedLock)?;
+        let world = worlds_lock
+            .iter_mut()
+            .find(|w| w.api_data.world_id == world_id)
+            .ok_or_else(|| EntityError::WorldNotFound(world_id.to_string()))?;
+
+        let mut custom_data = FileService::read_custom_data();
+        custom_data.set_world_cover(world_id, None);
+        FileService::write_custom_data(&custom_data)?;
+
+        if let Some(previous) = world.user_data.cover_image.take() {
+            Self::remove_file(&previous);
+        }
+        Ok(())
+    }
+
+    /// Removes the covers of worlds no longer in the library, and images no world uses
+    ///
+    /// # Arguments
+    /// * `worlds` - The list of worlds, as a RwLock
+    ///
+    /// # Returns
+    /// The number of images removed
+    ///
+    /// # Errors
+    /// Returns an error if the worlds lock is poisoned
+    /// Returns an error if the removed covers could not be saved
+    pub fn remove_orphaned(worlds: &RwLock<Vec<WorldModel>>) -> Result<u32, AppError> {
+        let mut custom_data = FileService::read_custom_data();
+        {
+            let worlds_lock = worlds.read().map_err(|_| ConcurrencyError::PoisonedLock)?;
+            let library: HashSet<&str> = worlds_lock
+                .iter()
+                .map(|w| w.api_data.world_id.as_str())
+                .collect();
+            let before = custom_data.world_covers.len();
+            custom_data
+                .world_covers
+                .retain(|world_id, _| library.contains(world_id.as_str()));
+            if custom_data.world_covers.len() != before {
+                FileService::write_custom_data(&custom_data)?;
+            }
+        }
+
+        let Ok(entries) = fs::read_dir(FileService::get_cover_dir()) else {
+            return Ok(0);
+        };
+        let in_use: HashSet<&String> = custom_data.world_covers.values().collect();
+        let mut removed = 0;
+        for entry in entries.flatten() {
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            if in_use.contains(&file_name) {
+                continue;
+            }
+            match fs::remove_file(entry.path()) {
+                Ok(()) => removed += 1,
+                Err(e) => log::warn!("Failed to remove cover image {}: {}", file_name, e),
+            }
+        }
+        if removed > 0 {
+            log::info!("Removed {} unused cover images", removed);
+        }
+        Ok(removed)
+    }
+
+    fn extension(path: &Path) -> Result<String, EntityError> {
+        path.extension()
+            .and_then(|extension| extension.to_str())
+            .map(|extension| extension.to_ascii_lowercase())
+            .filter(|extension| COVER_EXTENSIONS.contains(&extension.as_str()))
+            .ok_or_else(|| {
+                EntityError::InvalidOperation(
+                    "Cover images must be PNG, JPEG, WebP or GIF files".to_string(),
+                )
+            })
+    }
+
+    /// Failures are only logged, the image is removed later as unused
+    fn remove_file(file_name: &str) {
+        let path = FileService::get_cover_dir().join(file_name);
+        if let Err(e) = fs::remove_file(&path) {
+            log::warn!("Failed to remove cover image {}: {}", path.display(), e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_images_are_covers() {
+        assert_eq!(
+            CoverImageService::extension(Path::new("/art/cover.PNG")).unwrap(),
+            "png"
+        );
+        assert!(CoverImageService::extension(Path::new("cover.webp")).is_ok());
+        assert!(CoverImageService::extension(Path::new("cover.exe")).is_err());
+        assert!(CoverImageService::extension(Path::new("cover")).is_err());
+    }
+}
@@ -0,0 +1,268 @@
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use sha2::{Digest, Sha256};
+
+use crate::app_state::AppState;
+use crate::errors::ConcurrencyError;
+use crate::{MEMO_MANAGER, RATE_LIMIT_STORE};
+
+use super::file_service::DATA_LOCATION_FILE;
+use super::persistence::WriteBarrier;
+use super::{FileService, Persistence};
+
+/// Service for moving the data directory, e.g. off a small system drive
+#[derive(Debug)]
+pub struct DataDirectoryService;
+
+impl DataDirectoryService {
+    /// Moves every data file to another directory and switches the app over to it
+    /// The files are copied and verified before anything is switched, so a failed move
+    /// leaves the data where it was. The old copies are only removed afterwards
+    /// Every write waits while the files are copied, and goes to the new directory after
+    ///
+    /// # Arguments
+    /// * `target` - The new data directory, which must be empty or not exist yet
+    /// * `state` - The app state, locked while the files are copied
+    /// * `rate_limit_default_dir` - Where the rate limit state is kept in the default location
+    ///
+    /// # Returns
+    /// The old data directory
+    ///
+    /// # Errors
+    /// Returns an error message if the files could not be copied, or the switch failed
+    pub async fn move_to(
+        target: &Path,
+        state: &AppState,
+        rate_limit_default_dir: &Path,
+    ) -> Result<PathBuf, String> {
+        let current = FileService::get_app_dir();
+        Self::validate_target(&current, target)?;
+
+        let (files, barrier) = Self::switch(&current, target, state, rate_limit_default_dir)?;
+
+        // Writes queued for the old paths are redirected until their files are removed
+        Persistence::flush().await.map_err(|e| e.to_string())?;
+        Self::remove_files(&current, &files);
+        drop(barrier);
+        // Only removed once empty, the default directory keeps the data location file
+        let _ = fs::remove_dir(&current);
+        log::info!(
+            "Moved data directory from {} to {}",
+            current.display(),
+            target.display()
+        );
+        Ok(current)
+    }
+
+    /// Checks that the data can be moved from `current` to `target`
+    ///
+    /// # Errors
+    /// Returns an error message explaining why it can't
+    pub fn validate_target(current: &Path, target: &Path) -> Result<(), String> {
+        if !target.is_absolute() {
+            return Err("The data directory must be an absolute path".to_string());
+        }
+        if target == current {
+            return Err("The data is already in this directory".to_string());
+        }
+        if target.starts_with(current) {
+            return Err("The data directory can't be moved into itself".to_string());
+        }
+        if target.exists() {
+            if !target.is_dir() {
+                return Err(format!("{} is not a directory", target.display()));
+            }
+            // The default directory keeps the data location file after the data moved out
+            let mut entries = fs::read_dir(target).map_err(|e| e.to_string())?;
+            if entries.any(|entry| match entry {
+                Ok(entry) => entry.file_name() != DATA_LOCATION_FILE,
+                Err(_) => true,
+            }) {
+                return Err("The new data directory must be empty".to_string());
+            }
+        }
+        Ok(())
+    }
+
+    /// Copies the data with every writer locked out, then points the app at the copy
+    /// The returned barrier redirects writes for the old directory to the new one
+    fn switch(
+        current: &Path,
+        target: &Path,
+        state: &AppState,
+        rate_limit_default_dir: &Path,
+    ) -> Result<(Vec<PathBuf>, WriteBarrier), String> {
+        let _preferences = state.preferences_mut().map_err(|e| e.to_string())?;
+        let _library = state.library_mut().map_err(|e| e.to_string())?;
+        let mut memos = MEMO_MANAGER
+            .get()
+            .write()
+            .map_err(|_| ConcurrencyError::PoisonedLock.to_string())?;
+        let mut rate_limits = RATE_LIMIT_STORE
+            .get()
+            .write()
+            .map_err(|_| ConcurrencyError::PoisonedLock.to_string())?;
+        // Also holds back writes made outside these locks, e.g. to custom data or worlds.log
+        let barrier = Persistence::pause().map_err(|e| e.to_string())?;
+
+        let files = Self::copy_data(current, target)?;
+
+        let is_default = target == FileService::get_default_app_dir();
+        FileService::set_app_dir((!is_default).then(|| target.to_path_buf())).map_err(|e| {
+            Self::remove_files(target, &files);
+            e.to_string()
+        })?;
+        barrier.relocate(current, target);
+
+        memos.relocate(FileService::get_memo_path());
+
+        let old_rate_limit_path = rate_limits.data_path.take();
+        rate_limits.data_path = Some(FileService::get_rate_limit_path(rate_limit_default_dir));
+        rate_limits.save();
+        // Inside the old data directory it is removed with the other files
+        if let Some(path) = old_rate_limit_path.filter(|path| !path.starts_with(current)) {
+            if let Err(e) = fs::remove_file(&path) {
+                log::warn!("Failed to remove {}: {}", path.display(), e);
+            }
+        }
+
+        Ok((files, barrier))
+    }
+
+    /// Copies every file in `current` to `target`, and checks each copy matches
+    /// On failure the copies are removed again
+    ///
+    /// # Returns
+    /// The copied files, relative to the directories
+    ///
+    /// # Errors
+    /// Returns an error message if a file could not be copied, or its copy does not match
+    pub fn copy_data(current: &Path, target: &Path) -> Result<Vec<PathBuf>, String> {
+        let mut files = vec![];
+        Self::list_files(current, Path::new(""), &mut files)
+            .map_err(|e| format!("Failed to list data files: {}", e))?;
+
+        fs::create_dir_all(target).map_err(|e| e.to_string())?;
+        for file in &files {
+            if let Err(e) = Self::copy_verified(&current.join(file), &target.join(file)) {
+                Self::remove_files(target, &files);
+                return Err(e);
+            }
+        }
+        Ok(files)
+    }
+
+    fn list_files(root: &Path, relative: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+        for entry in fs::read_dir(root.join(relative))? {
+            let entry = entry?;
+            let path = relative.join(entry.file_name());
+            if path == Path::new(DATA_LOCATION_FILE) {
+                continue;
+            }
+            if entry.file_type()?.is_dir() {
+                Self::list_files(root, &path, files)?;
+            } else {
+                files.push(path);
+            }
+        }
+        Ok(())
+    }
+
+    fn copy_verified(source: &Path, target: &Path) -> Result<(), String> {
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        fs::copy(source, target)
+            .map_err(|e| format!("Failed to copy {}: {}", source.display(), e))?;
+        if Self::hash_file(source)? != Self::hash_file(target)? {
+            return Err(format!("The copy of {} does not match", source.display()));
+        }
+        Ok(())
+    }
+
+    fn hash_file(path: &Path) -> Result<Vec<u8>, String> {
+        let data =
+            fs::read(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        Ok(Sha256::digest(&data).to_vec())
+    }
+
+    /// Removes the files, and the directories they leave empty
+    /// Failures are only logged, as the data is also in the other directory
+    fn remove_files(root: &Path, files: &[PathBuf]) {
+        for file in files {
+            let path = root.join(file);
+            if path.exists() {
+                if let Err(e) = fs::remove_file(&path) {
+                    log::warn!("Failed to remove {}: {}", path.display(), e);
+                }
+            }
+        }
+
+        let dirs: BTreeSet<&Path> = files
+            .iter()
+            .flat_map(|file| file.ancestors().skip(1))
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .collect();
+        // Deepest first, so each directory is empty by the time it is reached
+        for dir in dirs.iter().rev() {
+            let _ = fs::remove_dir(root.join(dir));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_target_must_be_empty() {
+        let temp = TempDir::new().unwrap();
+        let current = temp.path().join("current");
+        let target = temp.path().join("target");
+
+        assert!(DataDirectoryService::validate_target(&current, &target).is_ok());
+        assert!(DataDirectoryService::validate_target(&current, &current).is_err());
+        assert!(DataDirectoryService::validate_target(&current, &current.join("nested")).is_err());
+        assert!(DataDirectoryService::validate_target(&current, Path::new("relative")).is_err());
+
+        fs::create_dir_all(&target).unwrap();
+        fs::write(target.join(DATA_LOCATION_FILE), "{}").unwrap();
+        assert!(DataDirectoryService::validate_target(&current, &target).is_ok());
+
+        fs::write(target.join("worlds.json"), "[]").unwrap();
+        assert!(DataDirectoryService::validate_target(&current, &target).is_err());
+    }
+
+    #[test]
+    fn test_copy_data_copies_nested_files() {
+        let temp = TempDir::new().unwrap();
+        let current = temp.path().join("current");
+        let target = temp.path().join("target");
+        fs::create_dir_all(current.join("exports")).unwrap();
+        fs::write(current.join("worlds.json"), "[]").unwrap();
+        fs::write(current.join("exports").join("export.json"), "{}").unwrap();
+        fs::write(current.join(DATA_LOCATION_FILE), "{}").unwrap();
+
+        let mut files = DataDirectoryService::copy_data(&current, &target).unwrap();
+        files.sort();
+        assert_eq!(
+            files,
+            vec![
+                Path::new("exports").join("export.json"),
+                PathBuf::from("worlds.json")
+            ]
+        );
+        assert_eq!(
+            fs::read_to_string(target.join("worlds.json")).unwrap(),
+            "[]"
+        );
+        assert!(!target.join(DATA_LOCATION_FILE).exists());
+
+        DataDirectoryService::remove_files(&current, &files);
+        assert!(!current.join("exports").exists());
+        assert!(current.join(DATA_LOCATION_FILE).exists());
+    }
+}
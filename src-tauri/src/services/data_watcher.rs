@@ -0,0 +1,136 @@
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, PoisonError};
+use std::time::Duration;
+
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use tauri::{AppHandle, Manager};
+use tauri_specta::Event;
+use tokio::sync::mpsc::{self, UnboundedReceiver};
+
+use crate::app_state::AppState;
+use crate::definitions::DataReloaded;
+
+use super::{FileService, LibraryEncryption, Persistence};
+
+/// Files which hold state the app keeps in memory
+const WATCHED_FILES: [&str; 5] = [
+    "worlds.json",
+    "worlds.json.gz",
+    "folders.json",
+    "preferences.json",
+    "custom_data.json",
+];
+
+/// Sync clients often write a file in several steps, so changes are only applied once
+/// the directory has been quiet for this long
+const DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Watches the data directory for library files changed outside the app,
+/// e.g. restored by a cloud sync client, and reloads the library when they change
+/// Changes caused by the app's own writes are ignored
+pub struct DataWatcher {
+    watcher: Mutex<RecommendedWatcher>,
+}
+
+impl DataWatcher {
+    /// Starts watching the data directory
+    /// A `DataReloaded` event is emitted after each reload
+    ///
+    /// # Arguments
+    /// * `handle` - The app handle, to reach the app state and emit events
+    ///
+    /// # Returns
+    /// The watcher, which stops watching when dropped
+    ///
+    /// # Errors
+    /// Returns an error if the data directory could not be watched
+    pub fn start(handle: AppHandle) -> Result<Self, notify::Error> {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let mut watcher =
+            notify::recommended_watcher(move |result: notify::Result<notify::Event>| {
+                match result {
+                    Ok(event) => {
+                        if matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) {
+                            for path in event.paths {
+                                // The receiver only goes away when the app shuts down
+                                let _ = sender.send(path);
+                            }
+                        }
+                    }
+                    Err(e) => log::warn!("Data watcher error: {}", e),
+                }
+            })?;
+        watcher.watch(&FileService::get_app_dir(), RecursiveMode::NonRecursive)?;
+
+        tauri::async_runtime::spawn(Self::run(receiver, handle));
+        log::info!("Watching data directory for external changes");
+        Ok(Self {
+            watcher: Mutex::new(watcher),
+        })
+    }
+
+    /// Watches another data directory instead, after the data was moved
+    ///
+    /// # Errors
+    /// Returns an error if the new directory could not be watched
+    pub fn move_to(&self, old_dir: &Path, new_dir: &Path) -> Result<(), notify::Error> {
+        let mut watcher = self.watcher.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Err(e) = watcher.unwatch(old_dir) {
+            log::warn!("Failed to stop watching {}: {}", old_dir.display(), e);
+        }
+        watcher.watch(new_dir, RecursiveMode::NonRecursive)
+    }
+
+    async fn run(mut receiver: UnboundedReceiver<PathBuf>, handle: AppHandle) {
+        while let Some(path) = receiver.recv().await {
+            let mut changed = BTreeSet::new();
+            Self::collect(path, &mut changed);
+            while let Ok(Some(path)) = tokio::time::timeout(DEBOUNCE, receiver.recv()).await {
+                Self::collect(path, &mut changed);
+            }
+
+            changed.retain(|path| !Persistence::is_own_write(path));
+            if !changed.is_empty() {
+                Self::reload(&handle, changed).await;
+            }
+        }
+    }
+
+    /// Keeps only the watched files, under the same path the app writes them to,
+    /// so they can be compared with the app's own writes
+    fn collect(path: PathBuf, changed: &mut BTreeSet<PathBuf>) {
+        let Some(file_name) = path.file_name().and_then(|name| name.to_str()) else {
+            return;
+        };
+        if WATCHED_FILES.contains(&file_name) {
+            changed.insert(FileService::get_app_dir().join(file_name));
+        }
+    }
+
+    async fn reload(handle: &AppHandle, changed: BTreeSet<PathBuf>) {
+        // Nothing is loaded while locked, unlocking reads the new files anyway
+        if LibraryEncryption::is_locked() {
+            return;
+        }
+
+        let files: Vec<String> = changed
+            .iter()
+            .filter_map(|path| path.file_name())
+            .map(|name| name.to_string_lossy().into_owned())
+            .collect();
+        log::info!("Data files changed externally, reloading: {:?}", files);
+
+        // A file which is still incomplete fails to load and leaves the state untouched,
+        // the final write of the file triggers another reload
+        let state = handle.state::<AppState>();
+        if let Err(e) = crate::reload_library(&state).await {
+            log::warn!("Failed to reload externally changed data: {}", e);
+            return;
+        }
+
+        if let Err(e) = (DataReloaded { files }).emit(handle) {
+            log::error!("Failed to emit data reloaded event: {}", e);
+        }
+    }
+}
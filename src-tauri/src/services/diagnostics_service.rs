@@ -0,0 +1,285 @@
+use std::fs::{self, File};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use serde_json::{json, Value};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use crate::app_state::AppState;
+use crate::definitions::{AppMetrics, CustomData, SyncTarget};
+use crate::errors::{AppError, FileError};
+
+use super::{FileService, LibraryEncryption, MetricsService};
+
+/// Only the newest log files are included
+const MAX_LOG_FILES: usize = 5;
+
+/// Only the end of each log file is included, where the problem usually is
+const MAX_LOG_BYTES: u64 = 2 * 1024 * 1024;
+
+const REDACTED: &str = "[REDACTED]";
+
+/// Keys whose values are replaced in the included preferences, compared ignoring case
+const SENSITIVE_KEYS: [&str; 4] = ["token", "password", "secret", "cookie"];
+
+/// Service for collecting what is needed to look into a bug report into one zip file
+/// Usage metrics are included if any were collected
+///
+/// auth.json, saved accounts and the library itself are never included. Secrets the app
+/// knows of, such as the local API token, webhook URL and WebDAV password, are removed
+/// from every included file, as are session cookies and tokens in logged URLs
+#[derive(Debug)]
+pub struct DiagnosticsService;
+
+impl DiagnosticsService {
+    /// Writes the diagnostics bundle to the exports directory
+    ///
+    /// # Arguments
+    /// * `state` - The app state, for the library statistics and preferences
+    /// * `logs_dir` - The directory of the log files
+    ///
+    /// # Returns
+    /// The path of the written zip file
+    ///
+    /// # Errors
+    /// Returns an error if the bundle could not be written
+    pub fn create_bundle(state: &AppState, logs_dir: &Path) -> Result<PathBuf, AppError> {
+        let custom_data = FileService::read_custom_data();
+        let secrets = Self::known_secrets(&custom_data);
+
+        let exports_dir = FileService::get_exports_dir();
+        fs::create_dir_all(&exports_dir).map_err(|_| FileError::FileWriteError)?;
+        let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S").to_string();
+        let path = exports_dir.join(format!("diagnostics_{}.zip", timestamp));
+        let file = File::create(&path).map_err(|_| FileError::FileWriteError)?;
+
+        let mut zip = ZipWriter::new(file);
+        let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+        let mut add = |name: &str, content: &str| -> Result<(), AppError> {
+            zip.start_file(name, options)
+                .map_err(|_| FileError::FileWriteError)?;
+            zip.write_all(content.as_bytes())
+                .map_err(|_| FileError::FileWriteError)?;
+            Ok(())
+        };
+
+        add(
+            "environment.json",
+            &Self::pretty(&Self::environment(&custom_data)),
+        )?;
+        add("library.json", &Self::pretty(&Self::statistics(state)?))?;
+        let metrics = MetricsService::snapshot();
+        if metrics != AppMetrics::default() {
+            add("metrics.json", &Self::pretty(&json!(metrics)))?;
+        }
+
+        let mut preferences = json!({
+            "preferences": &*state.preferences()?,
+            "customPreferences": &custom_data.preferences,
+        });
+        Self::redact_value(&mut preferences);
+        add(
+            "preferences.json",
+            &Self::redact_text(&Self::pretty(&preferences), &secrets),
+        )?;
+
+        for log_path in Self::newest_logs(logs_dir) {
+            let Some(name) = log_path.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            match Self::read_tail(&log_path) {
+                Ok(content) => add(
+                    &format!("logs/{}", name),
+                    &Self::redact_text(&content, &secrets),
+                )?,
+                Err(e) => log::warn!("Failed to read log file {}: {}", log_path.display(), e),
+            }
+        }
+
+        zip.finish().map_err(|_| FileError::FileWriteError)?;
+        log::info!("Diagnostics bundle written to {}", path.display());
+        Ok(path)
+    }
+
+    fn pretty(value: &Value) -> String {
+        serde_json::to_string_pretty(value).unwrap_or_default()
+    }
+
+    fn environment(custom_data: &CustomData) -> Value {
+        json!({
+            "appVersion": env!("CARGO_PKG_VERSION"),
+            "tauriVersion": tauri::VERSION,
+            "webviewVersion": tauri::webview_version().ok(),
+            "os": std::env::consts::OS,
+            "arch": std::env::consts::ARCH,
+            "customDataVersion": custom_data.version,
+            "libraryEncrypted": LibraryEncryption::status().map(|s| s.enabled).ok(),
+        })
+    }
+
+    fn statistics(state: &AppState) -> Result<Value, AppError> {
+        let (folders, worlds) = state.library()?;
+        let blacklist = state
+            .blacklist_store()
+            .read()
+            .ok()
+            .map(|b| (b.worlds.len(), b.authors.len()));
+        let notifications = state.notification_store().read().ok().map(|n| n.len());
+        Ok(json!({
+            "worlds": worlds.len(),
+            "hiddenWorlds": worlds.iter().filter(|w| w.user_data.hidden).count(),
+            "favoriteWorlds": worlds.iter().filter(|w| w.user_data.is_favorite).count(),
+            "folders": folders.len(),
+            "largestFolder": folders.iter().map(|f| f.world_ids.len()).max().unwrap_or(0),
+            "blacklistedWorlds": blacklist.map(|b| b.0),
+            "blacklistedAuthors": blacklist.map(|b| b.1),
+            "notifications": notifications,
+        }))
+    }
+
+    /// Secrets stored in custom data, which may also have been logged
+    fn known_secrets(custom_data: &CustomData) -> Vec<String> {
+        let preferences = &custom_data.preferences;
+        let mut secrets = vec![];
+        if let Some(local_api) = &preferences.local_api {
+            secrets.push(local_api.token.clone());
+        }
+        if let Some(webhook) = &preferences.webhook {
+            secrets.push(webhook.url.clone());
+        }
+        if let Some(SyncTarget::WebDav { password, .. }) = FileService::read_sync_config().target {
+            secrets.push(password);
+        }
+        secrets.retain(|s| !s.is_empty());
+        secrets
+    }
+
+    /// Replaces the values of sensitive keys, and the webhook URL, anywhere in the value
+    fn redact_value(value: &mut Value) {
+        match value {
+            Value::Object(map) => {
+                for (key, value) in map.iter_mut() {
+                    let key = key.to_lowercase();
+                    let sensitive = SENSITIVE_KEYS.iter().any(|k| key.contains(k));
+                    if sensitive && !value.is_object() {
+                        *value = json!(REDACTED);
+                        continue;
+                    }
+                    // Discord webhook URLs hold their token
+                    if key == "webhook" {
+                        if let Some(url) = value.get_mut("url") {
+                            *url = json!(REDACTED);
+                        }
+                    }
+                    Self::redact_value(value);
+                }
+            }
+            Value::Array(items) => items.iter_mut().for_each(Self::redact_value),
+            _ => {}
+        }
+    }
+
+    /// Removes known secrets, session cookies, bearer tokens and token query parameters
+    fn redact_text(text: &str, secrets: &[String]) -> String {
+        let mut text = text.to_string();
+        for secret in secrets {
+            text = text.replace(secret.as_str(), REDACTED);
+        }
+        for prefix in ["authcookie_", "Bearer ", "token=", "auth="] {
+            text = Self::redact_after(&text, prefix);
+        }
+        text
+    }
+
+    /// Replaces the token following each occurrence of `prefix`
+    fn redact_after(text: &str, prefix: &str) -> String {
+        let mut result = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(start) = rest.find(prefix) {
+            let value_start = start + prefix.len();
+            result.push_str(&rest[..value_start]);
+            let value = &rest[value_start..];
+            let end = value
+                .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')))
+                .unwrap_or(value.len());
+            if end > 0 {
+                result.push_str(REDACTED);
+            }
+            rest = &value[end..];
+        }
+        result.push_str(rest);
+        result
+    }
+
+    fn newest_logs(logs_dir: &Path) -> Vec<PathBuf> {
+        let Ok(entries) = fs::read_dir(logs_dir) else {
+            return vec![];
+        };
+        let mut logs: Vec<(std::time::SystemTime, PathBuf)> = entries
+            .flatten()
+            .filter(|e| e.path().extension().is_some_and(|ext| ext == "log"))
+            .filter_map(|e| Some((e.metadata().ok()?.modified().ok()?, e.path())))
+            .collect();
+        logs.sort_by(|a, b| b.0.cmp(&a.0));
+        logs.into_iter()
+            .take(MAX_LOG_FILES)
+            .map(|(_, path)| path)
+            .collect()
+    }
+
+    /// Reads at most the last `MAX_LOG_BYTES` of a file, from the start of a line
+    fn read_tail(path: &Path) -> std::io::Result<String> {
+        let mut file = File::open(path)?;
+        let length = file.metadata()?.len();
+        let skipped = length.saturating_sub(MAX_LOG_BYTES);
+        file.seek(SeekFrom::Start(skipped))?;
+        let mut bytes = vec![];
+        file.read_to_end(&mut bytes)?;
+        let content = String::from_utf8_lossy(&bytes);
+        if skipped == 0 {
+            return Ok(content.into_owned());
+        }
+        let first_line = content.find('\n').map_or(content.len(), |i| i + 1);
+        Ok(format!(
+            "[{} earlier bytes omitted]\n{}",
+            skipped,
+            &content[first_line..]
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact_text() {
+        let secrets = vec!["https://discord.com/api/webhooks/1/secret".to_string()];
+        let text =
+            "cookie authcookie_abc-123; posting to https://discord.com/api/webhooks/1/secret \
+                    GET /api/worlds?token=deadbeef&q=x Bearer abc.def";
+
+        let redacted = DiagnosticsService::redact_text(text, &secrets);
+        assert!(!redacted.contains("abc-123"));
+        assert!(!redacted.contains("webhooks/1/secret"));
+        assert!(!redacted.contains("deadbeef"));
+        assert!(!redacted.contains("abc.def"));
+        assert!(redacted.contains("&q=x"));
+    }
+
+    #[test]
+    fn test_redact_value() {
+        let mut value = json!({
+            "theme": "dark",
+            "localApi": { "enabled": true, "port": 39512, "token": "abc" },
+            "webhook": { "enabled": true, "url": "https://example.com/hook" },
+        });
+
+        DiagnosticsService::redact_value(&mut value);
+        assert_eq!(value["theme"], "dark");
+        assert_eq!(value["localApi"]["port"], 39512);
+        assert_eq!(value["localApi"]["token"], REDACTED);
+        assert_eq!(value["webhook"]["url"], REDACTED);
+    }
+}
@@ -0,0 +1,213 @@
+use std::collections::HashSet;
+use std::sync::{Arc, RwLock};
+
+use chrono::{DateTime, Utc};
+use reqwest::cookie::Jar;
+
+use crate::definitions::{
+    DiscoveryCursors, DiscoveryFeed, WorldBlacklist, WorldDisplayData, WorldModel,
+};
+use crate::errors::{AppError, ConcurrencyError};
+
+use super::{ApiService, BlacklistManager, FileService};
+
+/// Service for discovering newly published worlds
+/// Each feed remembers the newest publication date it has returned,
+/// so repeated calls only return worlds which have not been seen before
+#[derive(Debug)]
+pub struct DiscoveryService;
+
+impl DiscoveryService {
+    /// Fetches the worlds published since the last call for a feed
+    ///
+    /// # Arguments
+    /// * `cookie_store` - The cookie store to use for the API
+    /// * `feed` - The discovery feed to fetch
+    /// * `cursors` - The discovery cursors, as a RwLock
+    /// * `worlds` - The list of worlds in the library, as a RwLock
+    /// * `blacklist` - The world blacklist, as a RwLock
+    ///
+    /// # Returns
+    /// The newly published worlds which are neither in the library nor blacklisted
+    ///
+    /// # Errors
+    /// Returns an error if the request fails
+    /// Returns an error if any lock is poisoned
+    /// Returns an error if the cursors could not be saved
+    pub async fn fetch_new_worlds(
+        cookie_store: Arc<Jar>,
+        feed: DiscoveryFeed,
+        cursors: &RwLock<DiscoveryCursors>,
+        worlds: &RwLock<Vec<WorldModel>>,
+        blacklist: &RwLock<WorldBlacklist>,
+    ) -> Result<Vec<WorldDisplayData>, AppError> {
+        let published = ApiService::get_recently_published_worlds(cookie_store, feed).await?;
+
+        let last_seen = {
+            let cursors_lock = cursors.read().map_err(|_| ConcurrencyError::PoisonedLock)?;
+            cursors_lock.cursors.get(&feed).copied()
+        };
+
+        let known_ids: HashSet<String> = {
+            let worlds_lock = worlds.read().map_err(|_| ConcurrencyError::PoisonedLock)?;
+            worlds_lock
+                .iter()
+                .map(|w| w.api_data.world_id.clone())
+                .collect()
+        };
+
+        let (newest, new_worlds) = Self::select_unseen(published, last_seen, &known_ids);
+        let new_worlds = BlacklistManager::filter_display_data(new_worlds, blacklist)?;
+
+        if let Some(newest) = newest {
+            let mut cursors_lock = cursors
+                .write()
+                .map_err(|_| ConcurrencyError::PoisonedLock)?;
+            cursors_lock.cursors.insert(feed, newest);
+            FileService::write_discovery_cursors(&cursors_lock)?;
+        }
+
+        Ok(new_worlds)
+    }
+
+    /// Resets the cursor of a feed, so the next fetch returns every recent world again
+    ///
+    /// # Arguments
+    /// * `feed` - The discovery feed to reset
+    /// * `cursors` - The discovery cursors, as a RwLock
+    ///
+    /// # Returns
+    /// Ok if the cursor was reset successfully
+    ///
+    /// # Errors
+    /// Returns an error if the cursors lock is poisoned
+    /// Returns an error if the cursors could not be saved
+    pub fn reset_cursor(
+        feed: DiscoveryFeed,
+        cursors: &RwLock<DiscoveryCursors>,
+    ) -> Result<(), AppError> {
+        let mut cursors_lock = cursors
+            .write()
+            .map_err(|_| ConcurrencyError::PoisonedLock)?;
+        cursors_lock.cursors.remove(&feed);
+        FileService::write_discovery_cursors(&cursors_lock)?;
+        Ok(())
+    }
+
+    /// Picks the worlds published after the cursor which are not already known
+    ///
+    /// # Returns
+    /// The newest publication date in the batch, and the unseen worlds
+    fn select_unseen(
+        published: Vec<(DateTime<Utc>, WorldDisplayData)>,
+        last_seen: Option<DateTime<Utc>>,
+        known_ids: &HashSet<String>,
+    ) -> (Option<DateTime<Utc>>, Vec<WorldDisplayData>) {
+        let newest = published.iter().map(|(date, _)| *date).max();
+        let unseen = published
+            .into_iter()
+            .filter(|(date, _)| last_seen.map_or(true, |seen| *date > seen))
+            .filter(|(_, world)| !known_ids.contains(&world.world_id))
+            .map(|(_, world)| world)
+            .collect();
+        (newest, unseen)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::definitions::{Platform, WorldAvailability};
+    use chrono::TimeZone;
+
+    fn make_world(id: &str) -> WorldDisplayData {
+        WorldDisplayData {
+            world_id: id.to_string(),
+            name: "Test World".to_string(),
+            thumbnail_url: "".to_string(),
+            author_name: "Test Author".to_string(),
+            author_id: "usr_test".to_string(),
+            favorites: 0,
+            last_updated: "".to_string(),
+            visits: 0,
+            date_added: "".to_string(),
+            platform: Platform::PC,
+            folders: vec![],
+            tags: vec![],
+            capacity: 0,
+            is_photographed: false,
+            is_shared: false,
+            is_favorite: false,
+            is_event_critical: false,
+            has_memo: false,
+            rating: None,
+            local_visit_count: 0,
+            pc_download_size: None,
+            quest_download_size: None,
+            availability: WorldAvailability::Public,
+        }
+    }
+
+    #[test]
+    fn test_select_unseen() {
+        let old = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+        let cursor = Utc.with_ymd_and_hms(2025, 1, 2, 0, 0, 0).unwrap();
+        let new = Utc.with_ymd_and_hms(2025, 1, 3, 0, 0, 0).unwrap();
+        let known: HashSet<String> = ["wrld_known".to_string()].into_iter().collect();
+
+        let (newest, unseen) = DiscoveryService::select_unseen(
+            vec![
+                (new, make_world("wrld_new")),
+                (new, make_world("wrld_known")),
+                (old, make_world("wrld_old")),
+            ],
+            Some(cursor),
+            &known,
+        );
+
+        assert_eq!(newest, Some(new));
+        assert_eq!(unseen.len(), 1);
+        assert_eq!(unseen[0].world_id, "wrld_new");
+    }
+
+    #[test]
+    fn test_select_unseen_without_cursor() {
+        let date = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+        let (newest, unseen) = DiscoveryService::select_unseen(
+            vec![(date, make_world("wrld_a")), (date, make_world("wrld_b"))],
+            None,
+            &HashSet::new(),
+        );
+        assert_eq!(newest, Some(date));
+        assert_eq!(unseen.len(), 2);
+
+        // An empty batch has no newest date, so the cursor is left where it was
+        let (newest, unseen) = DiscoveryService::select_unseen(vec![], None, &HashSet::new());
+        assert_eq!(newest, None);
+        assert!(unseen.is_empty());
+    }
+
+    #[test]
+    fn test_reset_cursor() {
+        let date = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+        let mut cursors = DiscoveryCursors::default();
+        cursors.cursors.insert(DiscoveryFeed::Published, date);
+        cursors.cursors.insert(DiscoveryFeed::LabsPublished, date);
+        let cursors = RwLock::new(cursors);
+
+        DiscoveryService::reset_cursor(DiscoveryFeed::Published, &cursors).unwrap();
+        assert!(!cursors
+            .read()
+            .unwrap()
+            .cursors
+            .contains_key(&DiscoveryFeed::Published));
+
+        // Only the reset feed starts over
+        let saved = FileService::read_discovery_cursors();
+        assert!(!saved.cursors.contains_key(&DiscoveryFeed::Published));
+        assert_eq!(
+            saved.cursors.get(&DiscoveryFeed::LabsPublished),
+            Some(&date)
+        );
+    }
+}
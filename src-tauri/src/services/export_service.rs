@@ -1,11 +1,44 @@
+use printpdf::{BuiltinFont, IndirectFontRef, Mm, PdfDocument};
 use serde::Serialize;
 use std::{fs, path::Path, sync::RwLock};
 
 use crate::{
-    definitions::{FolderModel, WorldModel},
-    services::{FileService, SortingService},
+    app_state::read_library,
+    definitions::{FolderModel, WorldListFormat, WorldModel},
+    i18n,
+    services::{
+        FileService, LibraryEncryption, SortingService, WorldLog, WorldReference, WorldStorage,
+    },
+    MEMO_MANAGER,
 };
 
+/// Styles of the HTML world list, inlined so the page is a single file
+const WORLD_LIST_STYLE: &str = "body{font-family:system-ui,sans-serif;max-width:960px;\
+margin:2rem auto;padding:0 1rem;background:#fafafa;color:#222}\
+.worlds{display:grid;grid-template-columns:repeat(auto-fill,minmax(280px,1fr));gap:1rem}\
+.world{background:#fff;border-radius:8px;overflow:hidden;box-shadow:0 1px 3px #0002}\
+.world img{width:100%;aspect-ratio:4/3;object-fit:cover}\
+.world div{padding:0.75rem}.world h2{font-size:1.1rem;margin:0}\
+.author{color:#666;margin:0.25rem 0}.description{font-size:0.9rem;white-space:pre-line}";
+
+/// TrueType fonts with Japanese glyphs, tried in order for the run sheet
+/// Without one, the built-in Helvetica is used and non-Latin text is replaced
+const RUN_SHEET_FONTS: &[&str] = &[
+    "C:\\Windows\\Fonts\\arialuni.ttf",
+    "/Library/Fonts/Arial Unicode.ttf",
+    "/System/Library/Fonts/Supplemental/Arial Unicode.ttf",
+    "/usr/share/fonts/truetype/fonts-japanese-gothic.ttf",
+    "/usr/share/fonts/opentype/ipafont-gothic/ipag.ttf",
+];
+
+/// A4 portrait, in millimetres
+const PAGE_WIDTH: f32 = 210.0;
+const PAGE_HEIGHT: f32 = 297.0;
+const PAGE_MARGIN: f32 = 15.0;
+
+/// Millimetres per point
+const PT_TO_MM: f32 = 0.3528;
+
 #[derive(Serialize)]
 struct PLSPlatform {
     #[serde(rename = "PC")]
@@ -46,6 +79,14 @@ struct PortalLibrarySystemJson {
     categorys: Vec<PLSCategory>,
 }
 
+/// One world on the run sheet
+struct RunSheetEntry {
+    name: String,
+    /// Author, capacity and platforms on one line
+    details: String,
+    memo: String,
+}
+
 struct FolderExport {
     folder_name: String,
     worlds: Vec<WorldModel>,
@@ -61,18 +102,11 @@ impl ExportService {
         sort_field: String,
         sort_direction: String,
     ) -> Result<Vec<FolderExport>, String> {
-        log::info!("Exporting to PortalLibrarySystem");
-
         let mut folders_to_export: Vec<FolderExport> = Vec::new();
 
-        let worlds_lock = worlds.read().map_err(|e| {
-            log::error!("Failed to acquire read lock for worlds: {}", e);
-            "Failed to acquire read lock for worlds".to_string()
-        })?;
-
-        let folders_lock = folders.read().map_err(|e| {
-            log::error!("Failed to acquire read lock for folders: {}", e);
-            "Failed to acquire read lock for folders".to_string()
+        let (folders_lock, worlds_lock) = read_library(folders, worlds).map_err(|e| {
+            log::error!("Failed to acquire read lock for the library: {}", e);
+            "Failed to acquire read lock for the library".to_string()
         })?;
 
         log::info!(
@@ -111,6 +145,413 @@ impl ExportService {
         sort_field: String,
         sort_direction: String,
     ) -> Result<(), String> {
+        log::info!("Exporting to PortalLibrarySystem");
+
+        let json_string = Self::portal_library_system_json(
+            folder_names,
+            folders,
+            worlds,
+            sort_field,
+            sort_direction,
+        )?;
+
+        let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S").to_string();
+        let filename = format!("portal_library_system_{}.json", timestamp);
+        FileService::export_file(&filename, &json_string).map_err(|e| {
+            log::error!("Error exporting file: {}", e);
+            e.to_string()
+        })
+    }
+
+    /// Exports a folder as a world list for posting on a blog or Discord
+    /// Each world has its thumbnail, name, author, description and vrchat.com link
+    ///
+    /// # Arguments
+    /// * `folder` - The folder to export
+    /// * `format` - Whether to write an HTML page or a Markdown document
+    /// * `folders` - The list of folders, as a RwLock
+    /// * `worlds` - The list of worlds, as a RwLock
+    /// * `sort_field` - The field to sort the worlds by
+    /// * `sort_direction` - The direction to sort the worlds in
+    ///
+    /// # Errors
+    /// Returns an error message if the folder is not found, or the file could not be written
+    pub fn export_world_list(
+        folder: String,
+        format: WorldListFormat,
+        folders: &RwLock<Vec<FolderModel>>,
+        worlds: &RwLock<Vec<WorldModel>>,
+        sort_field: String,
+        sort_direction: String,
+    ) -> Result<(), String> {
+        let description = folders
+            .read()
+            .map_err(|_| "Failed to acquire read lock for folders".to_string())?
+            .iter()
+            .find(|f| f.folder_name == folder)
+            .ok_or_else(|| format!("Folder {} not found", folder))?
+            .description
+            .clone();
+        let folder_worlds = Self::get_folders_with_worlds(
+            vec![folder],
+            folders,
+            worlds,
+            sort_field,
+            sort_direction,
+        )?
+        .remove(0);
+
+        let (content, extension) = match format {
+            WorldListFormat::Html => (
+                Self::world_list_html(&folder_worlds, description.as_deref()),
+                "html",
+            ),
+            WorldListFormat::Markdown => (
+                Self::world_list_markdown(&folder_worlds, description.as_deref()),
+                "md",
+            ),
+        };
+
+        let name: String = folder_worlds
+            .folder_name
+            .chars()
+            .map(|c| {
+                if c.is_alphanumeric() || c == '-' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S").to_string();
+        let filename = format!("{}_{}.{}", name, timestamp, extension);
+        FileService::export_file(&filename, &content).map_err(|e| {
+            log::error!("Error exporting file: {}", e);
+            e.to_string()
+        })
+    }
+
+    /// Exports a folder as a printable PDF run sheet for events
+    /// Worlds are listed in playlist order, with their capacity, platforms and memo
+    ///
+    /// # Arguments
+    /// * `folder_name` - The folder to export
+    /// * `folders` - The list of folders, as a RwLock
+    /// * `worlds` - The list of worlds, as a RwLock
+    ///
+    /// # Errors
+    /// Returns an error message if the folder is not found, or the PDF could not be written
+    pub fn export_run_sheet_pdf(
+        folder_name: String,
+        folders: &RwLock<Vec<FolderModel>>,
+        worlds: &RwLock<Vec<WorldModel>>,
+    ) -> Result<(), String> {
+        log::info!("Exporting run sheet for folder: {}", folder_name);
+
+        let entries = Self::run_sheet_entries(&folder_name, folders, worlds)?;
+        let pdf_data = Self::render_run_sheet(&folder_name, &entries)?;
+
+        let exports_dir = FileService::get_exports_dir();
+        fs::create_dir_all(&exports_dir).map_err(|e| e.to_string())?;
+        let name: String = folder_name
+            .chars()
+            .map(|c| {
+                if c.is_alphanumeric() || c == '-' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S").to_string();
+        let path = exports_dir.join(format!("run_sheet_{}_{}.pdf", name, timestamp));
+        fs::write(&path, pdf_data).map_err(|e| {
+            log::error!("Error writing run sheet: {}", e);
+            e.to_string()
+        })?;
+
+        FileService::open_path(exports_dir)
+    }
+
+    /// Gets the run sheet rows of a folder, in the order its worlds are played
+    fn run_sheet_entries(
+        folder_name: &str,
+        folders: &RwLock<Vec<FolderModel>>,
+        worlds: &RwLock<Vec<WorldModel>>,
+    ) -> Result<Vec<RunSheetEntry>, String> {
+        let world_ids = folders
+            .read()
+            .map_err(|_| "Failed to acquire read lock for folders".to_string())?
+            .iter()
+            .find(|f| f.folder_name == folder_name)
+            .map(|f| f.world_ids.clone())
+            .ok_or_else(|| format!("Folder {} not found", folder_name))?;
+        let worlds_lock = worlds
+            .read()
+            .map_err(|_| "Failed to acquire read lock for worlds".to_string())?;
+        let memos = MEMO_MANAGER
+            .get()
+            .read()
+            .map_err(|_| "Failed to acquire read lock for memos".to_string())?;
+
+        Ok(world_ids
+            .iter()
+            .filter_map(|id| worlds_lock.iter().find(|w| &w.api_data.world_id == id))
+            .map(|world| {
+                let api = &world.api_data;
+                let platforms: Vec<&str> = [
+                    ("standalonewindows", "PC"),
+                    ("android", "Quest"),
+                    ("ios", "iOS"),
+                ]
+                .iter()
+                .filter(|(platform, _)| api.platform.iter().any(|p| p == platform))
+                .map(|(_, label)| *label)
+                .collect();
+                let mut details = vec![
+                    i18n::text("backend-export:author", vec![api.author_name.clone()]),
+                    match api.recommended_capacity {
+                        Some(recommended) => i18n::text(
+                            "backend-export:capacity-recommended",
+                            vec![api.capacity.to_string(), recommended.to_string()],
+                        ),
+                        None => {
+                            i18n::text("backend-export:capacity", vec![api.capacity.to_string()])
+                        }
+                    },
+                ];
+                if !platforms.is_empty() {
+                    details.push(platforms.join(" / "));
+                }
+                RunSheetEntry {
+                    name: api.world_name.clone(),
+                    details: details.join("  |  "),
+                    memo: memos
+                        .get_memo(&api.world_id)
+                        .unwrap_or_default()
+                        .to_string(),
+                }
+            })
+            .collect())
+    }
+
+    fn render_run_sheet(title: &str, entries: &[RunSheetEntry]) -> Result<Vec<u8>, String> {
+        let (doc, page, layer) =
+            PdfDocument::new(title, Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Run sheet");
+        let external_font = RUN_SHEET_FONTS
+            .iter()
+            .filter_map(|path| fs::File::open(path).ok())
+            .find_map(|file| doc.add_external_font(file).ok());
+        let latin_only = external_font.is_none();
+        let font: IndirectFontRef = match external_font {
+            Some(font) => font,
+            None => doc
+                .add_builtin_font(BuiltinFont::Helvetica)
+                .map_err(|e| e.to_string())?,
+        };
+
+        // Font size, indent and space above each line
+        let mut lines = vec![
+            (18.0, 0.0, 0.0, title.to_string()),
+            (
+                9.0,
+                0.0,
+                0.0,
+                format!(
+                    "{}  |  {}",
+                    i18n::text(
+                        "backend-export:world-count",
+                        vec![entries.len().to_string()]
+                    ),
+                    chrono::Local::now().format("%Y-%m-%d")
+                ),
+            ),
+        ];
+        for (i, entry) in entries.iter().enumerate() {
+            lines.push((12.0, 0.0, 3.0, format!("{}. {}", i + 1, entry.name)));
+            lines.push((9.0, 6.0, 0.0, entry.details.clone()));
+            for line in entry.memo.lines().filter(|l| !l.trim().is_empty()) {
+                lines.push((9.0, 6.0, 0.0, line.to_string()));
+            }
+        }
+
+        let mut layer = doc.get_page(page).get_layer(layer);
+        let mut y = PAGE_HEIGHT - PAGE_MARGIN;
+        for (size, indent, gap, text) in lines {
+            let max_width = PAGE_WIDTH - PAGE_MARGIN * 2.0 - indent;
+            y -= gap;
+            for wrapped in Self::wrap_text(&text, size, max_width) {
+                let line_height = size * PT_TO_MM * 1.4;
+                if y - line_height < PAGE_MARGIN {
+                    let (page, new_layer) =
+                        doc.add_page(Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Run sheet");
+                    layer = doc.get_page(page).get_layer(new_layer);
+                    y = PAGE_HEIGHT - PAGE_MARGIN;
+                }
+                y -= line_height;
+                // Helvetica only has glyphs for Latin-1
+                let wrapped: String = if latin_only {
+                    wrapped
+                        .chars()
+                        .map(|c| if (c as u32) < 0x100 { c } else { '?' })
+                        .collect()
+                } else {
+                    wrapped
+                };
+                layer.use_text(wrapped, size, Mm(PAGE_MARGIN + indent), Mm(y), &font);
+            }
+        }
+
+        doc.save_to_bytes().map_err(|e| e.to_string())
+    }
+
+    /// Splits text into lines which fit the width, estimating the width of characters
+    /// Full-width characters, like Japanese, count twice as wide as Latin ones
+    fn wrap_text(text: &str, font_size: f32, max_width: f32) -> Vec<String> {
+        let half_width = font_size * PT_TO_MM * 0.55;
+        let max_units = ((max_width / half_width) as usize).max(1);
+
+        let mut lines = vec![];
+        let mut line = String::new();
+        let mut units = 0;
+        for c in text.chars() {
+            let width = Self::char_units(c);
+            if units + width > max_units {
+                if c == ' ' {
+                    lines.push(std::mem::take(&mut line));
+                    units = 0;
+                    continue;
+                }
+                // Break at the last space, if the line has one
+                match line.rfind(' ') {
+                    Some(space) if space > 0 => {
+                        let rest = line.split_off(space + 1);
+                        lines.push(line.trim_end().to_string());
+                        units = rest.chars().map(Self::char_units).sum();
+                        line = rest;
+                    }
+                    _ => {
+                        lines.push(std::mem::take(&mut line));
+                        units = 0;
+                    }
+                }
+            }
+            line.push(c);
+            units += width;
+        }
+        if !line.is_empty() || lines.is_empty() {
+            lines.push(line);
+        }
+        lines
+    }
+
+    /// Full-width characters start at the Hangul Jamo block
+    fn char_units(c: char) -> usize {
+        if (c as u32) < 0x1100 {
+            1
+        } else {
+            2
+        }
+    }
+
+    fn world_list_html(folder: &FolderExport, description: Option<&str>) -> String {
+        let title = Self::escape_html(&folder.folder_name);
+        let mut html = format!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+             <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+             <title>{title}</title>\n<style>{WORLD_LIST_STYLE}</style>\n</head>\n<body>\n\
+             <h1>{title}</h1>\n"
+        );
+        if let Some(description) = description {
+            html.push_str(&format!("<p>{}</p>\n", Self::escape_html(description)));
+        }
+        html.push_str("<div class=\"worlds\">\n");
+        for world in &folder.worlds {
+            let api = &world.api_data;
+            html.push_str(&format!(
+                "<a class=\"world\" href=\"{}\">\n<img src=\"{}\" alt=\"\" loading=\"lazy\">\n\
+                 <div>\n<h2>{}</h2>\n<p class=\"author\">{}</p>\n\
+                 <p class=\"description\">{}</p>\n</div>\n</a>\n",
+                Self::escape_html(&Self::world_url(&api.world_id)),
+                Self::escape_html(&api.image_url),
+                Self::escape_html(&api.world_name),
+                Self::escape_html(&api.author_name),
+                Self::escape_html(&api.description),
+            ));
+        }
+        html.push_str("</div>\n</body>\n</html>\n");
+        html
+    }
+
+    fn world_list_markdown(folder: &FolderExport, description: Option<&str>) -> String {
+        let mut markdown = format!("# {}\n\n", Self::escape_markdown(&folder.folder_name));
+        if let Some(description) = description {
+            markdown.push_str(&format!("{}\n\n", Self::escape_markdown(description)));
+        }
+        for world in &folder.worlds {
+            let api = &world.api_data;
+            markdown.push_str(&format!(
+                "## [{}]({})\n\n",
+                Self::escape_markdown(&api.world_name),
+                Self::world_url(&api.world_id)
+            ));
+            if !api.image_url.is_empty() {
+                markdown.push_str(&format!("![]({})\n\n", api.image_url));
+            }
+            markdown.push_str(&format!(
+                "{}\n\n",
+                i18n::text(
+                    "backend-export:author",
+                    vec![Self::escape_markdown(&api.author_name)]
+                )
+            ));
+            // Quoted so line breaks in the description don't break the list
+            for line in api.description.lines().filter(|l| !l.trim().is_empty()) {
+                markdown.push_str(&format!("> {}\n", Self::escape_markdown(line)));
+            }
+            markdown.push('\n');
+        }
+        markdown
+    }
+
+    fn world_url(world_id: &str) -> String {
+        WorldReference {
+            world_id: world_id.to_string(),
+            instance_id: None,
+        }
+        .web_url()
+    }
+
+    fn escape_html(text: &str) -> String {
+        text.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;")
+            .replace('\'', "&#39;")
+    }
+
+    fn escape_markdown(text: &str) -> String {
+        let mut escaped = String::with_capacity(text.len());
+        for c in text.chars() {
+            if matches!(
+                c,
+                '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '#' | '|' | '!'
+            ) {
+                escaped.push('\\');
+            }
+            escaped.push(c);
+        }
+        escaped
+    }
+
+    /// Builds the PortalLibrarySystem JSON for the given folders, sorted as requested
+    pub fn portal_library_system_json(
+        folder_names: Vec<String>,
+        folders: &RwLock<Vec<FolderModel>>,
+        worlds: &RwLock<Vec<WorldModel>>,
+        sort_field: String,
+        sort_direction: String,
+    ) -> Result<String, String> {
         let folders_with_worlds = Self::get_folders_with_worlds(
             folder_names,
             folders,
@@ -157,18 +598,12 @@ impl ExportService {
             categorys: categories,
         };
 
-        let json_string = serde_json::to_string(&portal_library_system_json).map_err(|e| {
+        serde_json::to_string(&portal_library_system_json).map_err(|e| {
             log::error!("Error serializing to JSON: {}", e);
             e.to_string()
-        })?;
-
-        let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S").to_string();
-        let filename = format!("portal_library_system_{}.json", timestamp);
-        FileService::export_file(&filename, &json_string).map_err(|e| {
-            log::error!("Error exporting file: {}", e);
-            e.to_string()
         })
     }
+
     pub fn export_native_data(target_dir: &str) -> Result<(), String> {
         let (_, folders_path, worlds_path, _) = FileService::get_paths();
         let target = Path::new(target_dir);
@@ -185,23 +620,26 @@ impl ExportService {
         fs::create_dir_all(&export_path)
             .map_err(|e| format!("Failed to create export folder: {}", e))?;
 
+        WorldLog::compact().map_err(|e| format!("Failed to compact worlds.log: {}", e))?;
+        // Exports are always written in plaintext, even when the library is encrypted
+        let worlds_content = WorldStorage::read_json(&worlds_path)
+            .map_err(|e| format!("Failed to read worlds.json: {}", e))?;
+        let folders_content = fs::read_to_string(&folders_path)
+            .map_err(|e| format!("Failed to read folders.json: {}", e))?;
+        let folders_content = LibraryEncryption::open(&folders_content)
+            .map_err(|e| format!("Failed to open folders.json: {}", e))?;
+
         // Copy worlds.json
         let target_worlds = export_path.join("worlds.json");
-        fs::copy(&worlds_path, &target_worlds)
+        fs::write(&target_worlds, worlds_content.as_bytes())
             .map_err(|e| format!("Failed to copy worlds.json: {}", e))?;
 
         // Copy folders.json
         let target_folders = export_path.join("folders.json");
-        fs::copy(&folders_path, &target_folders)
+        fs::write(&target_folders, folders_content.as_bytes())
             .map_err(|e| format!("Failed to copy folders.json: {}", e))?;
 
         // Generate backup_info.json
-        // We need to read the files to count items
-        let worlds_content = fs::read_to_string(&worlds_path)
-            .map_err(|e| format!("Failed to read worlds.json for metadata: {}", e))?;
-        let folders_content = fs::read_to_string(&folders_path)
-            .map_err(|e| format!("Failed to read folders.json for metadata: {}", e))?;
-
         let worlds: Vec<serde_json::Value> = serde_json::from_str(&worlds_content)
             .map_err(|e| format!("Failed to parse worlds.json: {}", e))?;
         let folders: Vec<serde_json::Value> = serde_json::from_str(&folders_content)
@@ -223,3 +661,68 @@ impl ExportService {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::definitions::{WorldApiData, WorldAvailability};
+    use chrono::Utc;
+
+    fn folder_export() -> FolderExport {
+        let world = WorldModel::new(WorldApiData {
+            world_id: "wrld_4cf554b4-430c-4f8f-b53e-1f294eed230b".to_string(),
+            world_name: "<Night> & *Street*".to_string(),
+            description: "Rainy streets\n\nBest at night".to_string(),
+            author_name: "Author".to_string(),
+            author_id: "usr_author".to_string(),
+            tags: vec![],
+            publication_date: None,
+            last_update: Utc::now(),
+            image_url: "https://example.com/image.png".to_string(),
+            capacity: 0,
+            recommended_capacity: None,
+            visits: None,
+            favorites: 0,
+            platform: vec![],
+            packages: vec![],
+            availability: WorldAvailability::Public,
+        });
+        FolderExport {
+            folder_name: "Horror".to_string(),
+            worlds: vec![world],
+        }
+    }
+
+    #[test]
+    fn test_world_list_html_escapes_text() {
+        let html = ExportService::world_list_html(&folder_export(), Some("My <favorites>"));
+
+        assert!(html.contains("<h2>&lt;Night&gt; &amp; *Street*</h2>"));
+        assert!(html.contains("<p>My &lt;favorites&gt;</p>"));
+        assert!(html.contains(
+            "href=\"https://vrchat.com/home/world/wrld_4cf554b4-430c-4f8f-b53e-1f294eed230b\""
+        ));
+        assert!(html.contains("src=\"https://example.com/image.png\""));
+    }
+
+    #[test]
+    fn test_world_list_markdown_escapes_text() {
+        let markdown = ExportService::world_list_markdown(&folder_export(), None);
+
+        assert!(markdown.starts_with("# Horror\n\n## [\\<Night\\> & \\*Street\\*]("));
+        assert!(markdown.contains("![](https://example.com/image.png)"));
+        assert!(markdown.contains("> Rainy streets\n> Best at night\n"));
+    }
+
+    #[test]
+    fn test_wrap_text_breaks_at_spaces_and_wide_characters() {
+        // 9pt text in 30mm fits about 17 Latin characters
+        let lines = ExportService::wrap_text("Meet at the spawn point at nine", 9.0, 30.0);
+        assert_eq!(lines, vec!["Meet at the spawn", "point at nine"]);
+
+        let lines = ExportService::wrap_text(&"夜".repeat(12), 9.0, 30.0);
+        assert_eq!(lines, vec!["夜".repeat(8), "夜".repeat(4)]);
+
+        assert_eq!(ExportService::wrap_text("", 9.0, 30.0), vec![""]);
+    }
+}
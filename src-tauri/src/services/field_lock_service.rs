@@ -0,0 +1,199 @@
+use std::collections::BTreeMap;
+use std::sync::{Mutex, PoisonError, RwLock};
+
+use chrono::Utc;
+
+use crate::definitions::{FieldConflict, WorldApiData, WorldField, WorldModel};
+use crate::errors::{AppError, ConcurrencyError, EntityError};
+
+use super::{FileService, WorldChangeService};
+
+/// The most conflicts kept, older ones are dropped first
+const MAX_CONFLICTS: usize = 200;
+
+/// Conflicts since startup, oldest first
+/// At most one per world and field, the latest
+static CONFLICTS: Mutex<Vec<FieldConflict>> = Mutex::new(Vec::new());
+
+/// Service for fields of a world's VRChat data which the user corrected locally
+/// Locked fields keep their value when the world is fetched again, and every fetch
+/// bringing a different value is reported as a conflict
+#[derive(Debug)]
+pub struct FieldLockService;
+
+impl FieldLockService {
+    /// Corrects a field of a world, and locks it so fetching the world keeps the value
+    ///
+    /// # Arguments
+    /// * `world_id` - The ID of the world
+    /// * `field` - The field to correct
+    /// * `value` - The value to keep
+    /// * `worlds` - The list of worlds, as a RwLock
+    ///
+    /// # Errors
+    /// Returns an error if the value is empty
+    /// Returns an error if the world is not found
+    /// Returns an error if the worlds lock is poisoned
+    /// Returns an error if the change could not be saved
+    pub fn lock_field(
+        world_id: &str,
+        field: WorldField,
+        value: &str,
+        worlds: &RwLock<Vec<WorldModel>>,
+    ) -> Result<(), AppError> {
+        let value = value.trim();
+        if value.is_empty() && field == WorldField::Name {
+            return Err(
+                EntityError::InvalidOperation("World names must not be empty".to_string()).into(),
+            );
+        }
+
+        let mut worlds_lock = worlds.write().map_err(|_| ConcurrencyError::PoisonedLock)?;
+        let world = worlds_lock
+            .iter_mut()
+            .find(|w| w.api_data.world_id == world_id)
+            .ok_or_else(|| EntityError::WorldNotFound(world_id.to_string()))?;
+
+        let mut custom_data = FileService::read_custom_data();
+        custom_data.set_world_field_lock(world_id, field, Some(value));
+        FileService::write_custom_data(&custom_data)?;
+
+        *Self::field_mut(&mut world.api_data, field) = value.to_string();
+        let display = world.to_display_data();
+        FileService::write_worlds(&*worlds_lock)?;
+        drop(worlds_lock);
+
+        Self::take_conflicts(world_id, field);
+        WorldChangeService::record(vec![display]);
+        Ok(())
+    }
+
+    /// Unlocks a field, so the next fetch of the world replaces the corrected value
+    ///
+    /// # Errors
+    /// Returns an error if the change could not be saved
+    pub fn unlock_field(world_id: &str, field: WorldField) -> Result<(), AppError> {
+        let mut custom_data = FileService::read_custom_data();
+        custom_data.set_world_field_lock(world_id, field, None);
+        FileService::write_custom_data(&custom_data)?;
+        Self::take_conflicts(world_id, field);
+        Ok(())
+    }
+
+    /// Gets the locked fields of a world, with their values
+    pub fn get_locked_fields(world_id: &str) -> BTreeMap<WorldField, String> {
+        FileService::read_custom_data()
+            .get_world_field_locks(world_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Gets the conflicts since startup, oldest first
+    pub fn get_conflicts() -> Vec<FieldConflict> {
+        CONFLICTS
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Forgets the conflicts of a world, or of every world
+    ///
+    /// # Arguments
+    /// * `world_id` - The ID of the world, or None for every world
+    pub fn clear_conflicts(world_id: Option<&str>) {
+        let mut conflicts = CONFLICTS.lock().unwrap_or_else(PoisonError::into_inner);
+        match world_id {
+            Some(world_id) => conflicts.retain(|c| c.world_id != world_id),
+            None => conflicts.clear(),
+        }
+    }
+
+    /// Puts the locked values back into freshly fetched data, recording a conflict for
+    /// every field VRChat now has a different value for
+    /// Called by `FolderManager::add_worlds` before the data replaces the stored data
+    ///
+    /// # Arguments
+    /// * `locks` - The locked fields of the world, from custom_data.json
+    /// * `fetched` - The data fetched from VRChat
+    pub fn apply(locks: Option<&BTreeMap<WorldField, String>>, fetched: &mut WorldApiData) {
+        let Some(locks) = locks else {
+            return;
+        };
+        let conflicts = Self::apply_locks(locks, fetched);
+        if conflicts.is_empty() {
+            return;
+        }
+
+        let mut stored = CONFLICTS.lock().unwrap_or_else(PoisonError::into_inner);
+        for conflict in conflicts {
+            log::info!(
+                "Kept locked {:?} of world {} over the fetched value",
+                conflict.field,
+                conflict.world_id
+            );
+            stored.retain(|c| !(c.world_id == conflict.world_id && c.field == conflict.field));
+            stored.push(conflict);
+        }
+        let excess = stored.len().saturating_sub(MAX_CONFLICTS);
+        stored.drain(..excess);
+    }
+
+    fn apply_locks(
+        locks: &BTreeMap<WorldField, String>,
+        fetched: &mut WorldApiData,
+    ) -> Vec<FieldConflict> {
+        let world_id = fetched.world_id.clone();
+        let mut conflicts = vec![];
+        for (field, locked) in locks {
+            let value = Self::field_mut(fetched, *field);
+            if *value != *locked {
+                conflicts.push(FieldConflict {
+                    world_id: world_id.clone(),
+                    field: *field,
+                    local_value: locked.clone(),
+                    remote_value: std::mem::replace(value, locked.clone()),
+                    detected_at: Utc::now(),
+                });
+            }
+        }
+        conflicts
+    }
+
+    /// Forgets the conflicts of a field, once the user decided on its value
+    fn take_conflicts(world_id: &str, field: WorldField) {
+        let mut conflicts = CONFLICTS.lock().unwrap_or_else(PoisonError::into_inner);
+        conflicts.retain(|c| !(c.world_id == world_id && c.field == field));
+    }
+
+    fn field_mut(data: &mut WorldApiData, field: WorldField) -> &mut String {
+        match field {
+            WorldField::Name => &mut data.world_name,
+            WorldField::Description => &mut data.description,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_locks_keeps_locked_values() {
+        let mut fetched = WorldApiData::placeholder("wrld_locked".to_string());
+        fetched.world_name = "Renamed Upstream".to_string();
+        fetched.description = "Same".to_string();
+        let locks = BTreeMap::from([
+            (WorldField::Name, "My Name".to_string()),
+            (WorldField::Description, "Same".to_string()),
+        ]);
+
+        let conflicts = FieldLockService::apply_locks(&locks, &mut fetched);
+
+        assert_eq!(fetched.world_name, "My Name");
+        assert_eq!(fetched.description, "Same");
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].field, WorldField::Name);
+        assert_eq!(conflicts[0].local_value, "My Name");
+        assert_eq!(conflicts[0].remote_value, "Renamed Upstream");
+    }
+}
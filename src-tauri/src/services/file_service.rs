@@ -1,32 +1,145 @@
 use crate::definitions::AuthCookies;
-use crate::definitions::{CustomData, FolderModel, PreferenceModel, WorldModel};
+use crate::definitions::{
+    CustomData, DiscoveryCursors, FolderModel, FollowedAuthor, Notification, PreferenceModel,
+    SavedAccounts, SyncConfig, SyncTarget, WorldBlacklist, WorldModel, WorldsStorageFormat,
+};
 use crate::errors::FileError;
-use crate::services::EncryptionService;
+use crate::migration::SchemaMigrations;
+use crate::services::{EncryptionService, LibraryEncryption, Persistence, WorldLog, WorldStorage};
 use directories::BaseDirs;
 use log::debug;
+use serde::{Deserialize, Serialize};
 use serde_json;
 use std::ffi::OsString;
 use std::fs;
 use std::io::Write;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{OnceLock, PoisonError, RwLock};
 use tempfile::NamedTempFile;
 
+/// File in the default data directory which points to the custom one, if the data was moved
+pub(crate) const DATA_LOCATION_FILE: &str = "data_location.json";
+
+/// The data directory, read from the data location file on first use
+static APP_DIR: OnceLock<RwLock<PathBuf>> = OnceLock::new();
+
+/// Set while the worlds are loaded in the background on startup
+/// worlds.json is not written until then, as the worlds in memory are still incomplete
+static LOADING_WORLDS: AtomicBool = AtomicBool::new(false);
+
+/// Set when blacklist.json and its backup could not be read
+/// The file is not written then, as the empty blacklist used instead would replace every entry
+static BLACKLIST_UNREADABLE: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DataLocation {
+    data_directory: PathBuf,
+}
+
 /// Service for reading and writing files to disk
 pub struct FileService;
 
 impl FileService {
     /// Gets the application directory for storing data
+    /// This is the default directory, unless the data was moved to a custom one
     ///
     /// # Returns
     /// Returns the path to the application directory
     #[must_use]
-    fn get_app_dir() -> PathBuf {
+    pub(crate) fn get_app_dir() -> PathBuf {
+        Self::app_dir_lock()
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Gets the directory data is stored in when it was never moved
+    /// Tests use a directory of their own, so they never touch the user's library
+    #[must_use]
+    pub(crate) fn get_default_app_dir() -> PathBuf {
+        if cfg!(test) {
+            return std::env::temp_dir().join("VRC_Worlds_Manager_test");
+        }
         BaseDirs::new()
             .expect("Failed to get base directories")
             .data_local_dir()
             .join("VRC_Worlds_Manager_new")
     }
 
+    /// Gets the custom data directory, None when the data is in the default one
+    #[must_use]
+    pub fn read_data_location() -> Option<PathBuf> {
+        let path = Self::get_default_app_dir().join(DATA_LOCATION_FILE);
+        let data = fs::read_to_string(path).ok()?;
+        match serde_json::from_str::<DataLocation>(&data) {
+            Ok(location) => {
+                if !location.data_directory.is_dir() {
+                    log::warn!(
+                        "Data directory {} is not available",
+                        location.data_directory.display()
+                    );
+                }
+                Some(location.data_directory)
+            }
+            Err(e) => {
+                log::error!("Failed to parse {}: {}", DATA_LOCATION_FILE, e);
+                None
+            }
+        }
+    }
+
+    /// Points the app at another data directory, which must already hold the data
+    ///
+    /// # Arguments
+    /// * `dir` - The new data directory, None for the default one
+    ///
+    /// # Errors
+    /// Returns a FileError if the data location file could not be written
+    pub(crate) fn set_app_dir(dir: Option<PathBuf>) -> Result<(), FileError> {
+        let default_dir = Self::get_default_app_dir();
+        let location_path = default_dir.join(DATA_LOCATION_FILE);
+        match &dir {
+            Some(dir) => {
+                fs::create_dir_all(&default_dir).map_err(|_| FileError::FileWriteError)?;
+                let location = DataLocation {
+                    data_directory: dir.clone(),
+                };
+                let data =
+                    serde_json::to_string_pretty(&location).map_err(|_| FileError::InvalidFile)?;
+                // Written directly, as the writer is paused while the data directory moves
+                Self::write_file_raw(&location_path, data.as_bytes())?;
+            }
+            None => {
+                if location_path.exists() {
+                    fs::remove_file(&location_path).map_err(|_| FileError::FileWriteError)?;
+                }
+            }
+        }
+
+        *Self::app_dir_lock()
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = dir.unwrap_or(default_dir);
+        Ok(())
+    }
+
+    fn app_dir_lock() -> &'static RwLock<PathBuf> {
+        APP_DIR.get_or_init(|| {
+            RwLock::new(Self::read_data_location().unwrap_or_else(Self::get_default_app_dir))
+        })
+    }
+
+    /// Gets the path for the API rate limit state
+    /// It is kept in the data directory once that was moved, and in `default_dir` otherwise
+    #[must_use]
+    pub fn get_rate_limit_path(default_dir: &Path) -> PathBuf {
+        match Self::read_data_location() {
+            Some(dir) => dir.join("rate_limits.json"),
+            None => default_dir.join("rate_limits.json"),
+        }
+    }
+
     /// Gets the paths for the configuration and data files
     ///
     /// # Returns
@@ -50,12 +163,108 @@ impl FileService {
         )
     }
 
+    /// Gets the directory exported files are written to
+    #[must_use]
+    pub fn get_exports_dir() -> std::path::PathBuf {
+        Self::get_app_dir().join("exports")
+    }
+
     /// Gets the path for custom data file
     #[must_use]
     pub fn get_custom_data_path() -> std::path::PathBuf {
         Self::get_app_dir().join("custom_data.json")
     }
 
+    /// Gets the path for the user-managed world blacklist file
+    #[must_use]
+    pub fn get_blacklist_path() -> std::path::PathBuf {
+        Self::get_app_dir().join("blacklist.json")
+    }
+
+    /// Gets the path for the followed authors file
+    #[must_use]
+    pub fn get_followed_authors_path() -> std::path::PathBuf {
+        Self::get_app_dir().join("followed_authors.json")
+    }
+
+    /// Gets the path for the world memo file
+    #[must_use]
+    pub fn get_memo_path() -> std::path::PathBuf {
+        Self::get_app_dir().join("memo.json")
+    }
+
+    /// Gets the path for the library encryption settings file
+    #[must_use]
+    pub fn get_library_encryption_path() -> std::path::PathBuf {
+        Self::get_app_dir().join("library_encryption.json")
+    }
+
+    /// Gets the path for the write-ahead journal of multi-file operations
+    #[must_use]
+    pub fn get_journal_path() -> std::path::PathBuf {
+        Self::get_app_dir().join("journal.json")
+    }
+
+    /// Gets the path for the saved accounts file
+    #[must_use]
+    pub fn get_accounts_path() -> std::path::PathBuf {
+        Self::get_app_dir().join("accounts.json")
+    }
+
+    /// Gets the path for the discovery feed cursors file
+    #[must_use]
+    pub fn get_discovery_path() -> std::path::PathBuf {
+        Self::get_app_dir().join("discovery.json")
+    }
+
+    /// Gets the path for the sync settings and state file
+    #[must_use]
+    pub fn get_sync_path() -> std::path::PathBuf {
+        Self::get_app_dir().join("sync.json")
+    }
+
+    /// Gets the directory holding each synced file as it was after the last sync
+    #[must_use]
+    pub fn get_sync_base_dir() -> std::path::PathBuf {
+        Self::get_app_dir().join("sync_base")
+    }
+
+    /// Gets the path for the notifications file
+    #[must_use]
+    pub fn get_notifications_path() -> std::path::PathBuf {
+        Self::get_app_dir().join("notifications.json")
+    }
+
+    /// Gets the path for the local usage metrics file
+    #[must_use]
+    pub fn get_metrics_path() -> std::path::PathBuf {
+        Self::get_app_dir().join("metrics.json")
+    }
+
+    /// Gets the directory the cover images chosen for worlds are kept in
+    #[must_use]
+    pub fn get_cover_dir() -> std::path::PathBuf {
+        Self::get_app_dir().join("covers")
+    }
+
+    /// Gets the directory downloaded world thumbnails are kept in
+    #[must_use]
+    pub fn get_thumbnail_cache_dir() -> std::path::PathBuf {
+        Self::get_app_dir().join("thumbnails")
+    }
+
+    /// Gets the path for the snapshots of world popularity over time
+    #[must_use]
+    pub fn get_world_history_path() -> std::path::PathBuf {
+        Self::get_app_dir().join("world_history.json")
+    }
+
+    /// Gets the path for the change feed, one JSON change per line
+    #[must_use]
+    pub fn get_changes_path() -> std::path::PathBuf {
+        Self::get_app_dir().join("changes.jsonl")
+    }
+
     /// Checks if the application is being run for the first time
     ///
     /// # Returns
@@ -72,7 +281,7 @@ impl FileService {
     ///
     /// # Returns
     /// Returns the backup file path with .bak appended
-    fn get_backup_path(path: &PathBuf) -> PathBuf {
+    pub(crate) fn get_backup_path(path: &PathBuf) -> PathBuf {
         // Use OsString to handle non-UTF-8 paths correctly
         let mut os_string = path.as_os_str().to_os_string();
         os_string.push(".bak");
@@ -86,7 +295,7 @@ impl FileService {
     ///
     /// # Returns
     /// Returns true if the file is empty or contains only null bytes
-    fn is_file_corrupted_with_null_bytes(data: &str) -> bool {
+    fn is_file_corrupted_with_null_bytes(data: &[u8]) -> bool {
         const CHECK_BYTES_LIMIT: usize = 1024;
 
         if data.is_empty() {
@@ -94,7 +303,7 @@ impl FileService {
         }
 
         let check_len = data.len().min(CHECK_BYTES_LIMIT);
-        data.as_bytes()[..check_len].iter().all(|&b| b == 0)
+        data[..check_len].iter().all(|&b| b == 0)
     }
 
     /// Restores a backup file to the primary location
@@ -127,7 +336,26 @@ impl FileService {
     ///
     /// # Errors
     /// Returns a FileError if the data could not be written
-    fn atomic_write(path: &PathBuf, data: &str) -> Result<(), FileError> {
+    /// Returns `FileError::LibraryLocked` for library files while the library is locked
+    pub(crate) fn atomic_write(path: &PathBuf, data: &str) -> Result<(), FileError> {
+        let data = LibraryEncryption::seal(path, data)?;
+        if *path != Self::get_paths().2 {
+            return Persistence::write(path, &data);
+        }
+        // Written as JSON, which replaces worlds.json.gz if the worlds were stored compressed
+        WorldStorage::write(path, data.as_bytes(), WorldsStorageFormat::Pretty)?;
+        // worlds.json was replaced as a whole, so the entries of worlds.log no longer apply
+        WorldLog::discard();
+        Ok(())
+    }
+
+    /// Writes data to disk as-is on the calling thread, bypassing library encryption
+    /// Only the persistence writer calls this; everything else goes through `Persistence`
+    /// so writes stay ordered. See `atomic_write` for how the write is made atomic
+    ///
+    /// # Errors
+    /// Returns a FileError if the data could not be written
+    pub(crate) fn write_file_raw(path: &PathBuf, data: &[u8]) -> Result<(), FileError> {
         // If the file exists, create a backup first
         if path.exists() {
             let backup_path = Self::get_backup_path(path);
@@ -146,7 +374,7 @@ impl FileService {
 
         // Write the data to the temporary file
         temp_file
-            .write_all(data.as_bytes())
+            .write_all(data)
             .map_err(|_| FileError::FileWriteError)?;
 
         // Flush and sync to ensure data is written to disk
@@ -185,8 +413,18 @@ impl FileService {
     /// Returns a FileError if access is denied, the file is not found, or the file is invalid
     #[must_use]
     fn read_file<T: serde::de::DeserializeOwned>(path: &PathBuf) -> Result<T, FileError> {
+        Self::read_file_with(path, |data| {
+            serde_json::from_str(data).map_err(|_| FileError::InvalidFile)
+        })
+    }
+
+    /// Reads a data file like `read_file`, parsing its JSON with `parse`
+    fn read_file_with<T>(
+        path: &PathBuf,
+        mut parse: impl FnMut(&str) -> Result<T, FileError>,
+    ) -> Result<T, FileError> {
         // Try to read the primary file
-        let result = fs::read_to_string(path)
+        let result = fs::read(path)
             .map_err(|e| match e.kind() {
                 std::io::ErrorKind::PermissionDenied => FileError::AccessDenied,
                 _ => FileError::FileNotFound,
@@ -197,23 +435,31 @@ impl FileService {
                     log::warn!("File {:?} is empty or contains only null bytes, attempting backup recovery", path);
                     Err(FileError::InvalidFile)
                 } else {
-                    serde_json::from_str(&data).map_err(|_| FileError::InvalidFile)
+                    let data = LibraryEncryption::open_bytes(&data)?;
+                    let data = WorldStorage::decode(&data)?;
+                    parse(&data)
                 }
             });
 
+        // A locked library is not corrupted, and its backup is just as unreadable
+        if let Err(FileError::LibraryLocked) = result {
+            return result;
+        }
+
         // If the primary file failed, try the backup
         if result.is_err() {
             let backup_path = Self::get_backup_path(path);
             if backup_path.exists() {
                 log::info!("Attempting to recover from backup: {:?}", backup_path);
-                return fs::read_to_string(&backup_path)
+                return fs::read(&backup_path)
                     .map_err(|e| match e.kind() {
                         std::io::ErrorKind::PermissionDenied => FileError::AccessDenied,
                         _ => FileError::FileNotFound,
                     })
                     .and_then(|data| {
-                        let parsed =
-                            serde_json::from_str(&data).map_err(|_| FileError::InvalidFile)?;
+                        let data = LibraryEncryption::open_bytes(&data)?;
+                        let data = WorldStorage::decode(&data)?;
+                        let parsed = parse(&data)?;
                         // Restore the backup to the primary file
                         Self::restore_backup_to_primary(&backup_path, path);
                         Ok(parsed)
@@ -233,7 +479,7 @@ impl FileService {
         let content = match content_result {
             Ok(c) => {
                 // Check if the file is corrupted (empty or contains only null bytes)
-                if Self::is_file_corrupted_with_null_bytes(&c) {
+                if Self::is_file_corrupted_with_null_bytes(c.as_bytes()) {
                     log::warn!("Auth file {:?} is empty or contains only null bytes, attempting backup recovery", path);
                     // Try backup
                     let backup_path = Self::get_backup_path(path);
@@ -277,36 +523,69 @@ impl FileService {
         };
 
         match serde_json::from_str::<AuthCookies>(&content) {
-            Ok(mut cookies) => {
-                if cookies.version == 1 {
-                    if let Some(auth) = &cookies.auth_token {
-                        if !auth.is_empty() {
-                            cookies.auth_token =
-                                Some(EncryptionService::decrypt_aes(auth).map_err(|e| {
-                                    log::error!("Failed to decrypt auth token: {}", e);
-                                    FileError::InvalidFile
-                                })?);
-                        }
-                    }
-                    if let Some(tfa) = &cookies.two_factor_auth {
-                        if !tfa.is_empty() {
-                            cookies.two_factor_auth =
-                                Some(EncryptionService::decrypt_aes(tfa).map_err(|e| {
-                                    log::error!("Failed to decrypt two-factor auth token: {}", e);
-                                    FileError::InvalidFile
-                                })?);
-                        }
-                    }
-                } else {
-                    log::info!(
-                        "Auth file has version {}, skipping decryption.",
-                        cookies.version
-                    );
+            Ok(cookies) => Self::decrypt_cookies(cookies),
+            Err(_) => Err(FileError::InvalidFile),
+        }
+    }
+
+    /// Decrypts the tokens of cookies read from disk
+    /// Cookies written before encryption was introduced (version 0) are returned as-is
+    ///
+    /// # Errors
+    /// Returns a FileError if a token could not be decrypted
+    fn decrypt_cookies(mut cookies: AuthCookies) -> Result<AuthCookies, FileError> {
+        if cookies.version == 1 {
+            if let Some(auth) = &cookies.auth_token {
+                if !auth.is_empty() {
+                    cookies.auth_token = Some(EncryptionService::decrypt_aes(auth).map_err(|e| {
+                        log::error!("Failed to decrypt auth token: {}", e);
+                        FileError::InvalidFile
+                    })?);
                 }
-                Ok(cookies)
             }
-            Err(_) => Err(FileError::InvalidFile),
+            if let Some(tfa) = &cookies.two_factor_auth {
+                if !tfa.is_empty() {
+                    cookies.two_factor_auth =
+                        Some(EncryptionService::decrypt_aes(tfa).map_err(|e| {
+                            log::error!("Failed to decrypt two-factor auth token: {}", e);
+                            FileError::InvalidFile
+                        })?);
+                }
+            }
+        } else {
+            log::info!(
+                "Auth file has version {}, skipping decryption.",
+                cookies.version
+            );
         }
+        Ok(cookies)
+    }
+
+    /// Encrypts the tokens of cookies before they are written to disk
+    fn encrypt_cookies(cookies: &AuthCookies) -> AuthCookies {
+        let mut encrypted_cookies = cookies.clone();
+
+        // Always encrypt tokens when writing (Production & Dev use same logic)
+        if let Some(auth) = &cookies.auth_token {
+            encrypted_cookies.auth_token = match EncryptionService::encrypt_aes(auth) {
+                Ok(encrypted) => Some(encrypted),
+                Err(e) => {
+                    log::error!("Failed to encrypt auth token: {}", e);
+                    None
+                }
+            };
+        }
+        if let Some(tfa) = &cookies.two_factor_auth {
+            encrypted_cookies.two_factor_auth = match EncryptionService::encrypt_aes(tfa) {
+                Ok(encrypted) => Some(encrypted),
+                Err(e) => {
+                    log::error!("Failed to encrypt two-factor auth token: {}", e);
+                    None
+                }
+            };
+        }
+        encrypted_cookies.version = 1;
+        encrypted_cookies
     }
 
     /// Loads data from disk
@@ -327,9 +606,31 @@ impl FileService {
         ),
         FileError,
     > {
-        let (config_path, folders_path, worlds_path, cookies_path) = Self::get_paths();
+        let (preferences, folders, cookies) = Self::load_data_without_worlds()?;
+        let worlds = Self::load_worlds(&folders);
+        Ok((preferences, folders, worlds, cookies))
+    }
+
+    /// Loads everything but the worlds, which make up most of the library
+    /// Runs the schema migrations, so `load_worlds` must be called after this
+    ///
+    /// # Returns
+    /// Returns the preferences, folders, and the saved session
+    ///
+    /// # Errors
+    /// Returns a FileError if the library is locked or was written by a newer version
+    pub fn load_data_without_worlds(
+    ) -> Result<(PreferenceModel, Vec<FolderModel>, AuthCookies), FileError> {
+        let (config_path, folders_path, _, cookies_path) = Self::get_paths();
+
+        // Reading a locked library would fail and get reset to empty files below
+        if LibraryEncryption::is_locked() {
+            return Err(FileError::LibraryLocked);
+        }
+
+        // Files written by a newer version are refused before anything could overwrite them
+        SchemaMigrations::run()?;
 
-        log::info!("Reading files");
         log::info!("Reading files");
 
         let preferences: PreferenceModel = match Self::read_file(&config_path) {
@@ -354,15 +655,6 @@ impl FileService {
             }
         };
 
-        let mut worlds: Vec<WorldModel> = match Self::read_file(&worlds_path) {
-            Ok(data) => data,
-            Err(_) => {
-                log::warn!("worlds.json is invalid, recreating...");
-                Self::create_empty_worlds_file().ok();
-                Vec::new()
-            }
-        };
-
         let cookies = match Self::read_auth_file(&cookies_path) {
             Ok(data) => data,
             Err(e) => {
@@ -386,60 +678,105 @@ impl FileService {
             }
         };
 
-        // populate per-world folder list
-        for world in worlds.iter_mut() {
-            world.user_data.folders = folders
-                .iter()
-                .filter(|folder| folder.world_ids.contains(&world.api_data.world_id))
-                .map(|folder| folder.folder_name.clone())
-                .collect();
-        }
-
         // Load custom data and merge with in-memory data
         let custom_data = Self::read_custom_data();
 
-        // Apply favorite status from custom_data.json
-        // Apply favorite, photographed, shared status from custom_data.json
-        for world in worlds.iter_mut() {
-            world.user_data.is_favorite = custom_data.is_world_favorite(&world.api_data.world_id);
-            world.user_data.is_photographed =
-                custom_data.is_world_photographed(&world.api_data.world_id);
-            world.user_data.is_shared = custom_data.is_world_shared(&world.api_data.world_id);
-        }
-
-        // Backwards‐compat: dedupe any duplicate platform entries in worlds.json
-        {
-            use std::collections::HashSet;
-            for world in worlds.iter_mut() {
-                let mut seen = HashSet::new();
-                world
-                    .api_data
-                    .platform
-                    .retain(|plat| seen.insert(plat.clone()));
-            }
-            // write back deduplicated worlds
-            if let Err(e) = Self::write_worlds(&worlds) {
-                log::error!("Failed to persist deduplicated worlds.json: {}", e);
-            }
-        }
-
-        // Apply folder colors from custom_data.json
+        // Apply folder colors, icons and descriptions from custom_data.json
         let mut folders = folders;
         for folder in folders.iter_mut() {
             folder.color = custom_data.get_folder_color(&folder.folder_name).cloned();
+            folder.icon = custom_data.get_folder_icon(&folder.folder_name).cloned();
+            folder.description = custom_data
+                .get_folder_description(&folder.folder_name)
+                .cloned();
         }
 
         // Apply extended preferences from custom_data.json
         let mut preferences = preferences;
         preferences.default_instance_type = custom_data.preferences.default_instance_type.clone();
+        preferences.default_search_platform = custom_data.preferences.default_search_platform;
         if let Some(vb) = &custom_data.preferences.visible_buttons {
             preferences.visible_buttons = vb.clone();
         }
         if let Some(fr) = &custom_data.preferences.dont_show_remove_from_folder {
             preferences.dont_show_remove_from_folder = fr.clone();
         }
+        preferences.worlds_storage = custom_data.preferences.worlds_storage.unwrap_or_default();
 
-        Ok((preferences, folders, worlds, cookies))
+        Ok((preferences, folders, cookies))
+    }
+
+    /// Loads the worlds, with the folders they are in and their data from custom_data.json
+    /// An invalid worlds.json is recreated empty
+    ///
+    /// # Arguments
+    /// * `folders` - The folders, from `load_data_without_worlds`
+    pub fn load_worlds(folders: &[FolderModel]) -> Vec<WorldModel> {
+        Self::load_worlds_with_progress(folders, &mut |_| {})
+    }
+
+    /// Loads the worlds like `load_worlds`, reporting progress while worlds.json is parsed
+    ///
+    /// # Arguments
+    /// * `folders` - The folders, from `load_data_without_worlds`
+    /// * `on_progress` - Called with the number of worlds parsed so far
+    pub fn load_worlds_with_progress(
+        folders: &[FolderModel],
+        on_progress: &mut dyn FnMut(u32),
+    ) -> Vec<WorldModel> {
+        let (_, _, worlds_path, _) = Self::get_paths();
+        let stored_path = WorldStorage::stored_path(&worlds_path);
+        let worlds = Self::read_file_with(&stored_path, |data| {
+            WorldStorage::parse_list(data, on_progress)
+        });
+        let mut worlds: Vec<WorldModel> = match worlds {
+            Ok(data) => data,
+            Err(_) => {
+                log::warn!("worlds.json is invalid, recreating...");
+                Self::create_empty_worlds_file().ok();
+                Vec::new()
+            }
+        };
+        WorldLog::replay(&mut worlds);
+
+        // populate per-world folder list
+        for world in worlds.iter_mut() {
+            world.user_data.folders = folders
+                .iter()
+                .filter(|folder| folder.world_ids.contains(&world.api_data.world_id))
+                .map(|folder| folder.folder_name.clone())
+                .collect();
+        }
+
+        // Apply favorite, photographed, shared status from custom_data.json
+        let custom_data = Self::read_custom_data();
+        for world in worlds.iter_mut() {
+            world.user_data.is_favorite = custom_data.is_world_favorite(&world.api_data.world_id);
+            world.user_data.is_photographed =
+                custom_data.is_world_photographed(&world.api_data.world_id);
+            world.user_data.is_shared = custom_data.is_world_shared(&world.api_data.world_id);
+            world.user_data.is_event_critical =
+                custom_data.is_world_event_critical(&world.api_data.world_id);
+            world.user_data.rating = custom_data.get_world_rating(&world.api_data.world_id);
+            world.user_data.launch_count =
+                custom_data.get_world_launch_count(&world.api_data.world_id);
+            world.user_data.cover_image = custom_data
+                .get_world_cover(&world.api_data.world_id)
+                .cloned();
+            world.user_data.in_inbox = custom_data.is_world_in_inbox(&world.api_data.world_id);
+        }
+
+        WorldLog::loaded(&worlds);
+        worlds
+    }
+
+    /// Reads worlds.json as it is on disk, without worlds.log or data from other files
+    ///
+    /// # Errors
+    /// Returns a FileError if worlds.json could not be read
+    pub(crate) fn read_worlds_file() -> Result<Vec<WorldModel>, FileError> {
+        let (_, _, worlds_path, _) = Self::get_paths();
+        Self::read_file(&WorldStorage::stored_path(&worlds_path))
     }
 
     /// Writes preference data to disk
@@ -459,9 +796,11 @@ impl FileService {
         // Also update custom_data
         let mut custom_data = Self::read_custom_data();
         custom_data.preferences.default_instance_type = preferences.default_instance_type.clone();
+        custom_data.preferences.default_search_platform = preferences.default_search_platform;
         custom_data.preferences.visible_buttons = Some(preferences.visible_buttons.clone());
         custom_data.preferences.dont_show_remove_from_folder =
             Some(preferences.dont_show_remove_from_folder.clone());
+        custom_data.preferences.worlds_storage = Some(preferences.worlds_storage);
         if let Err(e) = Self::write_custom_data(&custom_data) {
             log::error!("Failed to write custom_data preferences: {}", e);
             // Don't fail the main write? Or should we?
@@ -490,7 +829,7 @@ impl FileService {
     }
 
     /// Writes world data to disk
-    /// Serializes and writes the data to disk
+    /// Only the worlds changed since the last save are written, see `WorldLog`
     ///
     /// # Arguments
     /// * `worlds` - The world data to write
@@ -499,9 +838,12 @@ impl FileService {
     /// Ok(()) if the data was written successfully
     ///
     /// # Errors
+    /// Returns `FileError::LibraryLoading` while the worlds are still being loaded
     /// Returns a FileError if the data could not be written
     pub fn write_worlds(worlds: &Vec<WorldModel>) -> Result<(), FileError> {
-        let (_, _, worlds_path, _) = Self::get_paths();
+        if Self::is_loading_worlds() {
+            return Err(FileError::LibraryLoading);
+        }
 
         // Also update custom_data (favorites, photographed, shared)
         let mut custom_data = Self::read_custom_data();
@@ -516,14 +858,29 @@ impl FileService {
                 world.user_data.is_photographed,
             );
             custom_data.set_world_shared(&world.api_data.world_id, world.user_data.is_shared);
+            custom_data.set_world_event_critical(
+                &world.api_data.world_id,
+                world.user_data.is_event_critical,
+            );
+            custom_data.set_world_rating(&world.api_data.world_id, world.user_data.rating);
         }
 
         if let Err(e) = Self::write_custom_data(&custom_data) {
             log::error!("Failed to write custom_data worlds: {}", e);
         }
 
-        let data = serde_json::to_string_pretty(&worlds).map_err(|_| FileError::InvalidFile)?;
-        Self::atomic_write(&worlds_path, &data)
+        let format = custom_data.preferences.worlds_storage.unwrap_or_default();
+        WorldLog::save(worlds, format)
+    }
+
+    /// Returns true while the worlds are loaded in the background on startup
+    pub fn is_loading_worlds() -> bool {
+        LOADING_WORLDS.load(Ordering::Relaxed)
+    }
+
+    /// Marks whether the worlds are being loaded, see `initialize_service::stream_worlds`
+    pub fn set_loading_worlds(loading: bool) {
+        LOADING_WORLDS.store(loading, Ordering::Relaxed);
     }
 
     /// Writes authentication data to disk
@@ -539,34 +896,25 @@ impl FileService {
     /// Returns a FileError if the data could not be written
     pub fn write_auth(cookies: &AuthCookies) -> Result<(), FileError> {
         let (_, _, _, auth_path) = Self::get_paths();
-        let mut encrypted_cookies = cookies.clone();
-
-        // Always encrypt tokens when writing (Production & Dev use same logic)
-        if let Some(auth) = &cookies.auth_token {
-            encrypted_cookies.auth_token = match EncryptionService::encrypt_aes(auth) {
-                Ok(encrypted) => Some(encrypted),
-                Err(e) => {
-                    log::error!("Failed to encrypt auth token: {}", e);
-                    None
-                }
-            };
-        }
-        if let Some(tfa) = &cookies.two_factor_auth {
-            encrypted_cookies.two_factor_auth = match EncryptionService::encrypt_aes(tfa) {
-                Ok(encrypted) => Some(encrypted),
-                Err(e) => {
-                    log::error!("Failed to encrypt two-factor auth token: {}", e);
-                    None
-                }
-            };
-        }
-        encrypted_cookies.version = 1;
+        let previous = Self::read_auth_file(&auth_path).ok();
+        let mut cookies = cookies.clone();
+        cookies.carry_two_factor_issued_at(previous.as_ref(), chrono::Utc::now());
+        let encrypted_cookies = Self::encrypt_cookies(&cookies);
 
         let data =
             serde_json::to_string_pretty(&encrypted_cookies).map_err(|_| FileError::InvalidFile)?;
         Self::atomic_write(&auth_path, &data)
     }
 
+    /// Reads the saved authentication data
+    ///
+    /// # Errors
+    /// Returns a FileError if the file is missing or could not be read
+    pub fn read_auth() -> Result<AuthCookies, FileError> {
+        let (_, _, _, auth_path) = Self::get_paths();
+        Self::read_auth_file(&auth_path)
+    }
+
     /// Creates an empty authentication file if it doesn't exist
     ///
     /// Note: This uses fs::write instead of atomic_write because it's only called
@@ -597,7 +945,7 @@ impl FileService {
     /// Returns a FileError if the file could not be created
     pub fn create_empty_worlds_file() -> Result<(), FileError> {
         let (_, _, worlds_path, _) = Self::get_paths();
-        if !worlds_path.exists() {
+        if !WorldStorage::stored_path(&worlds_path).exists() {
             fs::write(worlds_path, "[]").map_err(|_| FileError::FileWriteError)?;
         }
         Ok(())
@@ -640,11 +988,15 @@ impl FileService {
                 FileError::FileNotFound
             })?;
             
-            if Self::is_file_corrupted_with_null_bytes(&data) {
+            if Self::is_file_corrupted_with_null_bytes(data.as_bytes()) {
                 log::warn!("File {:?} is corrupted (null bytes)", path);
                 return Err(FileError::InvalidFile);
             }
 
+            let data = LibraryEncryption::open(&data).map_err(|e| {
+                log::warn!("Failed to open {:?}: {}", path, e);
+                e
+            })?;
             serde_json::from_str::<CustomData>(&data).map_err(|e| {
                 log::warn!("Failed to parse JSON in {:?}: {}", path, e);
                 FileError::InvalidFile
@@ -696,11 +1048,282 @@ impl FileService {
         Self::atomic_write(&custom_data_path, &data)
     }
 
+    /// Reads the user-managed world blacklist from disk
+    /// Falls back to the backup file if the primary file is corrupted
+    ///
+    /// # Returns
+    /// Returns the blacklist, or an empty blacklist if the file doesn't exist
+    /// An empty blacklist is also returned if neither the file nor its backup can be read,
+    /// the file is then kept as it is, see `write_blacklist`
+    pub fn read_blacklist() -> WorldBlacklist {
+        let blacklist_path = Self::get_blacklist_path();
+
+        if !blacklist_path.exists() {
+            log::info!("blacklist.json does not exist, using empty blacklist.");
+            return WorldBlacklist::new();
+        }
+
+        match Self::read_file(&blacklist_path) {
+            Ok(data) => {
+                BLACKLIST_UNREADABLE.store(false, Ordering::SeqCst);
+                data
+            }
+            Err(e) => {
+                log::error!(
+                    "Failed to read blacklist.json or its backup ({}), using empty blacklist until it is fixed.",
+                    e
+                );
+                BLACKLIST_UNREADABLE.store(true, Ordering::SeqCst);
+                WorldBlacklist::new()
+            }
+        }
+    }
+
+    /// Writes the user-managed world blacklist to disk
+    ///
+    /// # Arguments
+    /// * `blacklist` - The blacklist to write
+    ///
+    /// # Returns
+    /// Ok(()) if the data was written successfully
+    ///
+    /// # Errors
+    /// Returns `FileError::InvalidFile` if blacklist.json exists but could not be read
+    /// Returns a FileError if the data could not be written
+    pub fn write_blacklist(blacklist: &WorldBlacklist) -> Result<(), FileError> {
+        let blacklist_path = Self::get_blacklist_path();
+        if BLACKLIST_UNREADABLE.load(Ordering::SeqCst) && blacklist_path.exists() {
+            log::error!("Not writing over the unreadable blacklist.json");
+            return Err(FileError::InvalidFile);
+        }
+        let data = serde_json::to_string_pretty(blacklist).map_err(|_| FileError::InvalidFile)?;
+        Self::atomic_write(&blacklist_path, &data)
+    }
+
+    /// Reads the list of followed authors from disk
+    /// Falls back to the backup file if the primary file is corrupted
+    ///
+    /// # Returns
+    /// Returns the followed authors, or an empty list if the file doesn't exist
+    pub fn read_followed_authors() -> Vec<FollowedAuthor> {
+        let followed_path = Self::get_followed_authors_path();
+
+        if !followed_path.exists() {
+            log::info!("followed_authors.json does not exist, using empty list.");
+            return vec![];
+        }
+
+        match Self::read_file(&followed_path) {
+            Ok(data) => data,
+            Err(e) => {
+                log::error!(
+                    "Failed to read followed_authors.json ({}), using empty list.",
+                    e
+                );
+                vec![]
+            }
+        }
+    }
+
+    /// Writes the list of followed authors to disk
+    ///
+    /// # Arguments
+    /// * `authors` - The followed authors to write
+    ///
+    /// # Returns
+    /// Ok(()) if the data was written successfully
+    ///
+    /// # Errors
+    /// Returns a FileError if the data could not be written
+    pub fn write_followed_authors(authors: &[FollowedAuthor]) -> Result<(), FileError> {
+        let followed_path = Self::get_followed_authors_path();
+        let data = serde_json::to_string_pretty(authors).map_err(|_| FileError::InvalidFile)?;
+        Self::atomic_write(&followed_path, &data)
+    }
+
+    /// Reads the saved accounts from disk, decrypting each account's cookies
+    ///
+    /// # Returns
+    /// Returns the saved accounts, or no accounts if the file doesn't exist
+    pub fn read_accounts() -> SavedAccounts {
+        let accounts_path = Self::get_accounts_path();
+
+        if !accounts_path.exists() {
+            return SavedAccounts::default();
+        }
+
+        let mut saved: SavedAccounts = match Self::read_file(&accounts_path) {
+            Ok(data) => data,
+            Err(e) => {
+                log::error!("Failed to read accounts.json ({}), using no accounts.", e);
+                return SavedAccounts::default();
+            }
+        };
+
+        saved.accounts.retain_mut(|account| {
+            match Self::decrypt_cookies(account.cookies.clone()) {
+                Ok(cookies) => {
+                    account.cookies = cookies;
+                    true
+                }
+                Err(e) => {
+                    log::error!("Dropping saved account {}: {}", account.name, e);
+                    false
+                }
+            }
+        });
+        saved
+    }
+
+    /// Writes the saved accounts to disk, encrypting each account's cookies
+    ///
+    /// # Arguments
+    /// * `accounts` - The saved accounts to write
+    ///
+    /// # Returns
+    /// Ok(()) if the data was written successfully
+    ///
+    /// # Errors
+    /// Returns a FileError if the data could not be written
+    pub fn write_accounts(accounts: &SavedAccounts) -> Result<(), FileError> {
+        let accounts_path = Self::get_accounts_path();
+        let mut encrypted = accounts.clone();
+        for account in &mut encrypted.accounts {
+            account.cookies = Self::encrypt_cookies(&account.cookies);
+        }
+        let data = serde_json::to_string_pretty(&encrypted).map_err(|_| FileError::InvalidFile)?;
+        Self::atomic_write(&accounts_path, &data)
+    }
+
+    /// Reads the sync settings from disk, decrypting the WebDAV password
+    ///
+    /// # Returns
+    /// Returns the sync settings, or sync not set up if the file doesn't exist
+    pub fn read_sync_config() -> SyncConfig {
+        let sync_path = Self::get_sync_path();
+
+        if !sync_path.exists() {
+            return SyncConfig::default();
+        }
+
+        let mut config: SyncConfig = match Self::read_file(&sync_path) {
+            Ok(data) => data,
+            Err(e) => {
+                log::error!("Failed to read sync.json ({}), sync is not set up.", e);
+                return SyncConfig::default();
+            }
+        };
+
+        if let Some(SyncTarget::WebDav { password, .. }) = &mut config.target {
+            *password = EncryptionService::decrypt_aes(password).unwrap_or_else(|e| {
+                log::error!("Failed to decrypt WebDAV password: {}", e);
+                String::new()
+            });
+        }
+        config
+    }
+
+    /// Writes the sync settings to disk, encrypting the WebDAV password
+    ///
+    /// # Arguments
+    /// * `config` - The sync settings to write
+    ///
+    /// # Returns
+    /// Ok(()) if the data was written successfully
+    ///
+    /// # Errors
+    /// Returns a FileError if the password could not be encrypted or the data written
+    pub fn write_sync_config(config: &SyncConfig) -> Result<(), FileError> {
+        let sync_path = Self::get_sync_path();
+        let mut encrypted = config.clone();
+        if let Some(SyncTarget::WebDav { password, .. }) = &mut encrypted.target {
+            *password = EncryptionService::encrypt_aes(password).map_err(|e| {
+                log::error!("Failed to encrypt WebDAV password: {}", e);
+                FileError::FileWriteError
+            })?;
+        }
+        let data = serde_json::to_string_pretty(&encrypted).map_err(|_| FileError::InvalidFile)?;
+        Self::atomic_write(&sync_path, &data)
+    }
+
+    /// Reads the discovery feed cursors from disk
+    ///
+    /// # Returns
+    /// Returns the cursors, or empty cursors if the file doesn't exist
+    pub fn read_discovery_cursors() -> DiscoveryCursors {
+        let discovery_path = Self::get_discovery_path();
+
+        if !discovery_path.exists() {
+            return DiscoveryCursors::default();
+        }
+
+        match Self::read_file(&discovery_path) {
+            Ok(data) => data,
+            Err(e) => {
+                log::error!("Failed to read discovery.json ({}), resetting cursors.", e);
+                DiscoveryCursors::default()
+            }
+        }
+    }
+
+    /// Writes the discovery feed cursors to disk
+    ///
+    /// # Arguments
+    /// * `cursors` - The cursors to write
+    ///
+    /// # Returns
+    /// Ok(()) if the data was written successfully
+    ///
+    /// # Errors
+    /// Returns a FileError if the data could not be written
+    pub fn write_discovery_cursors(cursors: &DiscoveryCursors) -> Result<(), FileError> {
+        let discovery_path = Self::get_discovery_path();
+        let data = serde_json::to_string_pretty(cursors).map_err(|_| FileError::InvalidFile)?;
+        Self::atomic_write(&discovery_path, &data)
+    }
+
+    /// Reads the stored notifications from disk
+    ///
+    /// # Returns
+    /// Returns the notifications, or an empty list if the file doesn't exist
+    pub fn read_notifications() -> Vec<Notification> {
+        let notifications_path = Self::get_notifications_path();
+
+        if !notifications_path.exists() {
+            return vec![];
+        }
+
+        match Self::read_file(&notifications_path) {
+            Ok(data) => data,
+            Err(e) => {
+                log::error!("Failed to read notifications.json ({}), using empty list.", e);
+                vec![]
+            }
+        }
+    }
+
+    /// Writes the notifications to disk
+    ///
+    /// # Arguments
+    /// * `notifications` - The notifications to write
+    ///
+    /// # Returns
+    /// Ok(()) if the data was written successfully
+    ///
+    /// # Errors
+    /// Returns a FileError if the data could not be written
+    pub fn write_notifications(notifications: &[Notification]) -> Result<(), FileError> {
+        let notifications_path = Self::get_notifications_path();
+        let data =
+            serde_json::to_string_pretty(notifications).map_err(|_| FileError::InvalidFile)?;
+        Self::atomic_write(&notifications_path, &data)
+    }
+
     /// Deletes data from the worlds and folders files
     /// Overwrites the files with empty data
     ///
-    /// Note: This uses fs::write instead of atomic_write because it's intentionally
-    /// clearing/deleting data, so there's no existing data to protect.
+    /// Note: The empty data is written as-is instead of through atomic_write, as it's
+    /// intentionally clearing/deleting data, so there's nothing to encrypt.
     ///
     /// # Returns
     /// Ok(()) if the data was deleted successfully
@@ -709,8 +1332,9 @@ impl FileService {
     /// Returns a FileError if the data could not be deleted
     pub fn delete_worlds_and_folders() -> Result<(), FileError> {
         let (_, folders_path, worlds_path, _) = Self::get_paths();
-        fs::write(folders_path, "[]").map_err(|_| FileError::FileWriteError)?;
-        fs::write(worlds_path, "[]").map_err(|_| FileError::FileWriteError)?;
+        Persistence::write(&folders_path, "[]")?;
+        WorldStorage::write(&worlds_path, b"[]", WorldsStorageFormat::Pretty)?;
+        WorldLog::discard();
 
         Ok(())
     }
@@ -748,11 +1372,7 @@ impl FileService {
     /// # Errors
     /// Returns a FileError if the file could not be written
     pub fn export_file(file_name: &str, data: &str) -> Result<(), FileError> {
-        let exports_dir = BaseDirs::new()
-            .expect("Failed to get base directories")
-            .data_local_dir()
-            .join("VRC_Worlds_Manager_new")
-            .join("exports");
+        let exports_dir = Self::get_exports_dir();
 
         if !exports_dir.exists() {
             fs::create_dir_all(&exports_dir).map_err(|_| FileError::FileWriteError)?;
@@ -786,6 +1406,73 @@ mod tests {
         assert!(app_dir.starts_with(BaseDirs::new().unwrap().data_local_dir()));
     }
 
+    #[test]
+    fn test_two_factor_issued_at_is_carried() {
+        let issued_at = chrono::Utc::now() - chrono::Duration::days(10);
+        let previous = AuthCookies {
+            two_factor_auth: Some("tfa".to_string()),
+            two_factor_auth_issued_at: Some(issued_at),
+            ..AuthCookies::new()
+        };
+
+        let mut same = AuthCookies {
+            two_factor_auth: Some("tfa".to_string()),
+            ..AuthCookies::new()
+        };
+        same.carry_two_factor_issued_at(Some(&previous), chrono::Utc::now());
+        assert_eq!(same.two_factor_auth_issued_at, Some(issued_at));
+        assert_eq!(
+            same.two_factor_expires_at(),
+            Some(issued_at + chrono::Duration::days(30))
+        );
+
+        let now = chrono::Utc::now();
+        let mut renewed = AuthCookies {
+            two_factor_auth: Some("new".to_string()),
+            ..AuthCookies::new()
+        };
+        renewed.carry_two_factor_issued_at(Some(&previous), now);
+        assert_eq!(renewed.two_factor_auth_issued_at, Some(now));
+
+        let mut without = AuthCookies::new();
+        without.carry_two_factor_issued_at(Some(&previous), now);
+        assert_eq!(without.two_factor_expires_at(), None);
+    }
+
+    #[test]
+    fn test_account_cookies_round_trip() {
+        let cookies = AuthCookies {
+            auth_token: Some("authcookie_main".to_string()),
+            two_factor_auth: Some("tfa_main".to_string()),
+            version: 1,
+            ..AuthCookies::new()
+        };
+
+        let encrypted = FileService::encrypt_cookies(&cookies);
+        assert_ne!(encrypted.auth_token, cookies.auth_token);
+        assert_ne!(encrypted.two_factor_auth, cookies.two_factor_auth);
+        assert_eq!(FileService::decrypt_cookies(encrypted).unwrap(), cookies);
+
+        // Cookies saved before encryption was introduced are read as-is
+        let plaintext = AuthCookies {
+            version: 0,
+            ..cookies.clone()
+        };
+        assert_eq!(
+            FileService::decrypt_cookies(plaintext.clone()).unwrap(),
+            plaintext
+        );
+
+        let corrupted = AuthCookies {
+            auth_token: Some("not encrypted".to_string()),
+            ..cookies
+        };
+        assert!(matches!(
+            FileService::decrypt_cookies(corrupted),
+            Err(FileError::InvalidFile)
+        ));
+    }
+
     #[test]
     fn test_get_paths() {
         let (preference, folders, worlds, auth) = FileService::get_paths();
@@ -1,28 +1,44 @@
 use log::info;
 
+use crate::app_state::{read_library, write_library};
 use crate::definitions::{
-    FolderModel, PreferenceModel, WorldApiData, WorldDisplayData, WorldModel,
+    FolderMembershipChange, FolderModel, FolderNameCollision, FolderRemovalPreference,
+    FolderRename, FolderShareStatus, FolderSort, FolderStats, FolderStatsWorld, PreferenceModel,
+    TagCount, WorldApiData, WorldBlacklist, WorldDisplayData, WorldFlag, WorldFlagSummary,
+    WorldModel, WorldSummary, WorldUpdate,
 };
 use crate::errors::{AppError, ConcurrencyError, EntityError};
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::sync::RwLock;
 
-use super::FileService;
+use super::journal::JournalGuard;
+use super::{
+    BlacklistManager, FieldLockService, FileService, GroupingService, InputValidator, Journal,
+    LibraryEvents, SortingService, TagService, WorldChangeService, WorldHistoryService,
+};
 
 #[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
 pub struct FolderData {
     pub name: String,
     pub world_count: u16,
     pub color: Option<String>,
+    pub icon: Option<String>,
+    pub description: Option<String>,
+    pub sort: Option<FolderSort>,
+    pub archived: bool,
 }
 
 impl FolderData {
-    pub fn new(name: String, world_count: u16, color: Option<String>) -> Self {
+    pub fn new(folder: &FolderModel) -> Self {
         Self {
-            name,
-            world_count,
-            color,
+            name: folder.folder_name.clone(),
+            world_count: folder.world_ids.len() as u16,
+            color: folder.color.clone(),
+            icon: folder.icon.clone(),
+            description: folder.description.clone(),
+            sort: folder.sort.clone(),
+            archived: folder.archived,
         }
     }
 }
@@ -53,10 +69,7 @@ impl FolderManager {
         folders: &RwLock<Vec<FolderModel>>,
         worlds: &RwLock<Vec<WorldModel>>,
     ) -> Result<(), AppError> {
-        let mut folders_lock = folders
-            .write()
-            .map_err(|_| ConcurrencyError::PoisonedLock)?;
-        let mut worlds_lock = worlds.write().map_err(|_| ConcurrencyError::PoisonedLock)?;
+        let (mut folders_lock, mut worlds_lock) = write_library(folders, worlds)?;
 
         let folder = folders_lock
             .iter_mut()
@@ -102,10 +115,7 @@ impl FolderManager {
         folders: &RwLock<Vec<FolderModel>>,
         worlds: &RwLock<Vec<WorldModel>>,
     ) -> Result<(), AppError> {
-        let mut folders_lock = folders
-            .write()
-            .map_err(|_| ConcurrencyError::PoisonedLock)?;
-        let mut worlds_lock = worlds.write().map_err(|_| ConcurrencyError::PoisonedLock)?;
+        let (mut folders_lock, mut worlds_lock) = write_library(folders, worlds)?;
 
         let folder = folders_lock
             .iter_mut()
@@ -157,6 +167,11 @@ impl FolderManager {
         if let Some(world) = world {
             world.user_data.is_photographed = is_photographed;
             FileService::write_worlds(&*worlds_lock)?;
+            LibraryEvents::world_flag_changed(
+                vec![world_id],
+                WorldFlag::Photographed,
+                is_photographed,
+            );
             Ok(())
         } else {
             Err(EntityError::WorldNotFound(world_id).into())
@@ -189,6 +204,7 @@ impl FolderManager {
         if let Some(world) = world {
             world.user_data.is_shared = is_shared;
             FileService::write_worlds(&*worlds_lock)?;
+            LibraryEvents::world_flag_changed(vec![world_id], WorldFlag::Shared, is_shared);
             Ok(())
         } else {
             Err(EntityError::WorldNotFound(world_id).into())
@@ -224,12 +240,164 @@ impl FolderManager {
             let mut custom_data = FileService::read_custom_data();
             custom_data.set_world_favorite(&world_id, is_favorite);
             FileService::write_custom_data(&custom_data)?;
+            LibraryEvents::world_flag_changed(vec![world_id], WorldFlag::Favorite, is_favorite);
+            Ok(())
+        } else {
+            Err(EntityError::WorldNotFound(world_id).into())
+        }
+    }
+
+    /// Set the rating of a world
+    ///
+    /// # Arguments
+    /// * `world_id` - The ID of the world
+    /// * `rating` - The rating from 1 to 5, or None to remove it
+    /// * `worlds` - The list of worlds, as a RwLock
+    ///
+    /// # Returns
+    /// Ok if the rating was updated successfully
+    ///
+    /// # Errors
+    /// Returns an error if the rating is out of range
+    /// Returns an error if the world is not found
+    /// Returns an error if the worlds lock is poisoned
+    pub fn set_world_rating(
+        world_id: String,
+        rating: Option<u8>,
+        worlds: &RwLock<Vec<WorldModel>>,
+    ) -> Result<(), AppError> {
+        if rating.is_some_and(|r| !(1..=5).contains(&r)) {
+            return Err(
+                EntityError::InvalidOperation("Ratings must be from 1 to 5".to_string()).into(),
+            );
+        }
+        let mut worlds_lock = worlds.write().map_err(|_| ConcurrencyError::PoisonedLock)?;
+        let world = worlds_lock
+            .iter_mut()
+            .find(|w| w.api_data.world_id == world_id)
+            .ok_or_else(|| EntityError::WorldNotFound(world_id.clone()))?;
+        world.user_data.rating = rating;
+        let mut custom_data = FileService::read_custom_data();
+        custom_data.set_world_rating(&world_id, rating);
+        FileService::write_custom_data(&custom_data)?;
+        Ok(())
+    }
+
+    /// Count an instance launched for a world
+    /// Worlds not in the library are counted too, so the count is there once they are added
+    ///
+    /// # Arguments
+    /// * `world_id` - The ID of the world
+    /// * `worlds` - The list of worlds, as a RwLock
+    ///
+    /// # Errors
+    /// Returns an error if the worlds lock is poisoned
+    /// Returns an error if the count could not be saved
+    pub fn record_world_launch(
+        world_id: &str,
+        worlds: &RwLock<Vec<WorldModel>>,
+    ) -> Result<(), AppError> {
+        let mut worlds_lock = worlds.write().map_err(|_| ConcurrencyError::PoisonedLock)?;
+        let mut custom_data = FileService::read_custom_data();
+        let count = custom_data.record_world_launch(world_id);
+        FileService::write_custom_data(&custom_data)?;
+        if let Some(world) = worlds_lock
+            .iter_mut()
+            .find(|w| w.api_data.world_id == world_id)
+        {
+            world.user_data.launch_count = count;
+        }
+        Ok(())
+    }
+
+    /// Set the event critical status of a world
+    /// Event critical worlds are watched for capacity, release status and platform changes
+    ///
+    /// # Arguments
+    /// * `world_id` - The ID of the world
+    /// * `is_event_critical` - The new status
+    /// * `worlds` - The list of worlds, as a RwLock
+    ///
+    /// # Returns
+    /// Ok if the status was updated successfully
+    ///
+    /// # Errors
+    /// Returns an error if the world is not found
+    /// Returns an error if the worlds lock is poisoned
+    pub fn set_world_event_critical(
+        world_id: String,
+        is_event_critical: bool,
+        worlds: &RwLock<Vec<WorldModel>>,
+    ) -> Result<(), AppError> {
+        let mut worlds_lock = worlds.write().map_err(|_| ConcurrencyError::PoisonedLock)?;
+        let world = worlds_lock
+            .iter_mut()
+            .find(|w| w.api_data.world_id == world_id);
+
+        if let Some(world) = world {
+            world.user_data.is_event_critical = is_event_critical;
+            // Write to custom_data.json for backward compatibility
+            let mut custom_data = FileService::read_custom_data();
+            custom_data.set_world_event_critical(&world_id, is_event_critical);
+            FileService::write_custom_data(&custom_data)?;
+            LibraryEvents::world_flag_changed(
+                vec![world_id],
+                WorldFlag::EventCritical,
+                is_event_critical,
+            );
             Ok(())
         } else {
             Err(EntityError::WorldNotFound(world_id).into())
         }
     }
 
+    /// Sets a flag on several worlds, saving them once
+    ///
+    /// # Arguments
+    /// * `world_ids` - The IDs of the worlds
+    /// * `flag` - The flag to set
+    /// * `value` - The new value of the flag
+    /// * `worlds` - The list of worlds, as a RwLock
+    ///
+    /// # Returns
+    /// Which worlds changed, and which were not found
+    ///
+    /// # Errors
+    /// Returns an error if the worlds lock is poisoned
+    /// Returns an error if the worlds could not be written
+    pub fn set_worlds_flag(
+        world_ids: &[String],
+        flag: WorldFlag,
+        value: bool,
+        worlds: &RwLock<Vec<WorldModel>>,
+    ) -> Result<WorldFlagSummary, AppError> {
+        let mut worlds_lock = worlds.write().map_err(|_| ConcurrencyError::PoisonedLock)?;
+        let mut summary = WorldFlagSummary::default();
+        let mut seen = HashSet::new();
+        for world_id in world_ids {
+            if !seen.insert(world_id) {
+                continue;
+            }
+            match worlds_lock
+                .iter_mut()
+                .find(|w| &w.api_data.world_id == world_id)
+            {
+                Some(world) if world.user_data.flag(flag) == value => summary.unchanged += 1,
+                Some(world) => {
+                    world.user_data.set_flag(flag, value);
+                    summary.changed.push(world_id.clone());
+                }
+                None => summary.not_found.push(world_id.clone()),
+            }
+        }
+
+        if !summary.changed.is_empty() {
+            FileService::write_worlds(&*worlds_lock)?;
+            LibraryEvents::world_flag_changed(summary.changed.clone(), flag, value);
+        }
+        Ok(summary)
+    }
+
     /// Removes a world from a folder
     /// Does not do anything if the world is not in the folder
     ///
@@ -251,10 +419,7 @@ impl FolderManager {
         folders: &RwLock<Vec<FolderModel>>,
         worlds: &RwLock<Vec<WorldModel>>,
     ) -> Result<(), AppError> {
-        let mut folders_lock = folders
-            .write()
-            .map_err(|_| ConcurrencyError::PoisonedLock)?;
-        let mut worlds_lock = worlds.write().map_err(|_| ConcurrencyError::PoisonedLock)?;
+        let (mut folders_lock, mut worlds_lock) = write_library(folders, worlds)?;
 
         let folder = folders_lock
             .iter_mut()
@@ -292,6 +457,136 @@ impl FolderManager {
         Ok(())
     }
 
+    /// Moves a world from one folder to another in a single write, so a failure cannot leave it
+    /// in both or neither of them
+    /// Whether the world stays in the source folder follows the folder removal preference
+    ///
+    /// # Arguments
+    /// * `world_id` - The ID of the world to move
+    /// * `from` - The folder the world is moved out of
+    /// * `to` - The folder the world is moved into
+    /// * `preference` - The folder removal preference
+    /// * `remove` - The answer of the user, only used when the preference is to ask
+    /// * `folders` - The list of folders, as a RwLock
+    /// * `worlds` - The list of worlds, as a RwLock
+    ///
+    /// # Returns
+    /// The folders the world was added to and removed from
+    ///
+    /// # Errors
+    /// Returns an error if either folder or the world is not found
+    /// Returns an error if the world is not in the source folder
+    /// Returns an error if the folders or worlds lock is poisoned
+    pub fn move_world_between_folders(
+        world_id: String,
+        from: String,
+        to: String,
+        preference: FolderRemovalPreference,
+        remove: Option<bool>,
+        folders: &RwLock<Vec<FolderModel>>,
+        worlds: &RwLock<Vec<WorldModel>>,
+    ) -> Result<FolderMembershipChange, AppError> {
+        let (mut folders_lock, mut worlds_lock) = write_library(folders, worlds)?;
+
+        for folder_name in [&from, &to] {
+            if !folders_lock.iter().any(|f| &f.folder_name == folder_name) {
+                return Err(EntityError::FolderNotFound(folder_name.clone()).into());
+            }
+        }
+        let world = worlds_lock
+            .iter_mut()
+            .find(|w| w.api_data.world_id == world_id)
+            .ok_or_else(|| EntityError::WorldNotFound(world_id.clone()))?;
+        if !world.user_data.folders.contains(&from) {
+            return Err(EntityError::FolderNotFound(from).into());
+        }
+
+        let mut change = FolderMembershipChange::default();
+        if from == to {
+            return Ok(change);
+        }
+        let remove = match preference {
+            FolderRemovalPreference::AlwaysRemove => true,
+            FolderRemovalPreference::NeverRemove => false,
+            FolderRemovalPreference::Ask => remove.unwrap_or(false),
+        };
+
+        if !world.user_data.folders.contains(&to) {
+            world.user_data.folders.push(to.clone());
+            if let Some(folder) = folders_lock.iter_mut().find(|f| f.folder_name == to) {
+                folder.world_ids.push(world_id.clone());
+            }
+            change.added_to.push(to);
+        }
+        if remove {
+            world.user_data.folders.retain(|f| f != &from);
+            if let Some(folder) = folders_lock.iter_mut().find(|f| f.folder_name == from) {
+                folder.world_ids.retain(|id| id != &world_id);
+            }
+            change.removed_from.push(from);
+        }
+
+        if change != FolderMembershipChange::default() {
+            FileService::write_folders(&*folders_lock)?;
+        }
+        Ok(change)
+    }
+
+    /// Replaces the folders a world is in, in a single write
+    /// Folders the world stays in keep its position in them
+    ///
+    /// # Arguments
+    /// * `world_id` - The ID of the world
+    /// * `folder_names` - Every folder the world should be in
+    /// * `folders` - The list of folders, as a RwLock
+    /// * `worlds` - The list of worlds, as a RwLock
+    ///
+    /// # Returns
+    /// The folders the world was added to and removed from
+    ///
+    /// # Errors
+    /// Returns an error if any of the folders or the world is not found, nothing is changed then
+    /// Returns an error if the folders or worlds lock is poisoned
+    pub fn set_world_folders(
+        world_id: String,
+        folder_names: Vec<String>,
+        folders: &RwLock<Vec<FolderModel>>,
+        worlds: &RwLock<Vec<WorldModel>>,
+    ) -> Result<FolderMembershipChange, AppError> {
+        let (mut folders_lock, mut worlds_lock) = write_library(folders, worlds)?;
+
+        if let Some(missing) = folder_names
+            .iter()
+            .find(|name| !folders_lock.iter().any(|f| &f.folder_name == *name))
+        {
+            return Err(EntityError::FolderNotFound(missing.clone()).into());
+        }
+        let world = worlds_lock
+            .iter_mut()
+            .find(|w| w.api_data.world_id == world_id)
+            .ok_or_else(|| EntityError::WorldNotFound(world_id.clone()))?;
+
+        let mut change = FolderMembershipChange::default();
+        for folder in folders_lock.iter_mut() {
+            let wanted = folder_names.contains(&folder.folder_name);
+            let present = world.user_data.folders.contains(&folder.folder_name);
+            if wanted && !present {
+                folder.world_ids.push(world_id.clone());
+                world.user_data.folders.push(folder.folder_name.clone());
+                change.added_to.push(folder.folder_name.clone());
+            } else if !wanted && present {
+                folder.world_ids.retain(|id| id != &world_id);
+                world.user_data.folders.retain(|f| f != &folder.folder_name);
+                change.removed_from.push(folder.folder_name.clone());
+            }
+        }
+
+        if change != FolderMembershipChange::default() {
+            FileService::write_folders(&*folders_lock)?;
+        }
+        Ok(change)
+    }
+
     /// Hide a world
     /// This is done by setting the hidden flag to true
     /// Remove the world from all folders
@@ -312,38 +607,67 @@ impl FolderManager {
         folders: &RwLock<Vec<FolderModel>>,
         worlds: &RwLock<Vec<WorldModel>>,
     ) -> Result<(), AppError> {
-        let mut worlds_lock = worlds.write().map_err(|_| ConcurrencyError::PoisonedLock)?;
-        let world = worlds_lock
-            .iter_mut()
-            .find(|w| w.api_data.world_id == world_id);
-        if world.is_none() {
-            return Err(EntityError::WorldNotFound(world_id).into());
-        }
-        let world = world.unwrap();
-        world.user_data.hidden = true;
-
-        let folders_lock = folders
-            .write()
-            .map_err(|_| ConcurrencyError::PoisonedLock)?;
-        let folders_to_remove: Vec<String> = folders_lock
-            .iter()
-            .filter(|folder| folder.world_ids.contains(&world_id))
-            .map(|folder| folder.folder_name.clone())
-            .collect();
-        drop(folders_lock);
-        FileService::write_worlds(&*worlds_lock)?;
-        drop(worlds_lock);
+        let journal = Self::journal_library_write("hide_world")?;
+        Self::edit_library(journal, folders, worlds, |folders, worlds| {
+            let world = worlds
+                .iter_mut()
+                .find(|w| w.api_data.world_id == world_id)
+                .ok_or_else(|| EntityError::WorldNotFound(world_id.clone()))?;
+            world.user_data.hidden = true;
 
-        for folder_name in folders_to_remove {
-            FolderManager::remove_world_from_folder(
-                folder_name,
-                world_id.clone(),
-                folders,
-                worlds,
-            )?;
-        }
+            for folder in folders.iter_mut() {
+                if let Some(index) = folder.world_ids.iter().position(|id| id == &world_id) {
+                    folder.world_ids.remove(index);
+                    world.user_data.folders.retain(|f| f != &folder.folder_name);
+                }
+            }
+            Ok(())
+        })
+    }
 
-        Ok(())
+    /// Hides several worlds, removing them from their folders, and saves them once
+    /// Worlds which are not found or already hidden are skipped
+    ///
+    /// # Arguments
+    /// * `world_ids` - The IDs of the worlds to hide
+    /// * `folders` - The list of folders, as a RwLock
+    /// * `worlds` - The list of worlds, as a RwLock
+    ///
+    /// # Returns
+    /// The hidden worlds, each with the folders it was removed from
+    ///
+    /// # Errors
+    /// Returns an error if any lock is poisoned
+    /// Returns an error if the worlds or folders could not be written
+    pub fn hide_worlds(
+        world_ids: &[String],
+        folders: &RwLock<Vec<FolderModel>>,
+        worlds: &RwLock<Vec<WorldModel>>,
+    ) -> Result<Vec<(String, Vec<String>)>, AppError> {
+        let journal = Self::journal_library_write("hide_worlds")?;
+        Self::edit_library(journal, folders, worlds, |folders, worlds| {
+            let mut hidden = vec![];
+            for world_id in world_ids {
+                let Some(world) = worlds
+                    .iter_mut()
+                    .find(|w| &w.api_data.world_id == world_id && !w.user_data.hidden)
+                else {
+                    continue;
+                };
+                world.user_data.hidden = true;
+                world.user_data.folders.clear();
+
+                let mut removed_from = vec![];
+                for folder in folders.iter_mut() {
+                    if let Some(index) = folder.world_ids.iter().position(|id| id == world_id) {
+                        folder.world_ids.remove(index);
+                        removed_from.push(folder.folder_name.clone());
+                    }
+                }
+                hidden.push((world_id.clone(), removed_from));
+            }
+            Ok(hidden)
+        })
     }
 
     /// Unhide a world
@@ -367,39 +691,31 @@ impl FolderManager {
         folders: &RwLock<Vec<FolderModel>>,
         worlds: &RwLock<Vec<WorldModel>>,
     ) -> Result<(), AppError> {
-        let mut worlds_lock = worlds.write().map_err(|_| ConcurrencyError::PoisonedLock)?;
-        let world = worlds_lock
-            .iter_mut()
-            .find(|w| w.api_data.world_id == world_id);
-        if world.is_none() {
-            return Err(EntityError::WorldNotFound(world_id).into());
-        }
-        let world = world.unwrap();
-        world.user_data.hidden = false;
-
-        let folders_lock = folders
-            .write()
-            .map_err(|_| ConcurrencyError::PoisonedLock)?;
-        let folders_to_add: Vec<String> = folders_lock
-            .iter()
-            .filter(|folder| world.user_data.folders.contains(&folder.folder_name))
-            .map(|folder| folder.folder_name.clone())
-            .collect();
-        drop(folders_lock);
-        FileService::write_worlds(&*worlds_lock)?;
-        drop(worlds_lock);
-
-        for folder_name in folders_to_add {
-            FolderManager::add_world_to_folder(folder_name, world_id.clone(), folders, worlds)?;
-        }
+        let journal = Self::journal_library_write("unhide_world")?;
+        Self::edit_library(journal, folders, worlds, |folders, worlds| {
+            let world = worlds
+                .iter_mut()
+                .find(|w| w.api_data.world_id == world_id)
+                .ok_or_else(|| EntityError::WorldNotFound(world_id.clone()))?;
+            world.user_data.hidden = false;
 
-        Ok(())
+            for folder in folders
+                .iter_mut()
+                .filter(|folder| world.user_data.folders.contains(&folder.folder_name))
+            {
+                if !folder.world_ids.contains(&world_id) {
+                    folder.world_ids.push(world_id.clone());
+                }
+            }
+            Ok(())
+        })
     }
 
     /// Get the names of all folders, and the number of worlds in each folder
     ///
     /// # Arguments
     /// * `folders` - The list of folders, as a RwLock
+    /// * `include_archived` - Whether to include archived folders
     ///
     /// # Returns
     /// A vector of folder names, each paired with the number of worlds in that folder
@@ -407,16 +723,17 @@ impl FolderManager {
     /// # Errors
     /// Returns an error if the folders lock is poisoned
     #[must_use]
-    pub fn get_folders(folders: &RwLock<Vec<FolderModel>>) -> Result<Vec<FolderData>, AppError> {
+    pub fn get_folders(
+        folders: &RwLock<Vec<FolderModel>>,
+        include_archived: bool,
+    ) -> Result<Vec<FolderData>, AppError> {
         let folders_lock = folders.read().map_err(|_| ConcurrencyError::PoisonedLock)?;
         let mut folder_data: Vec<FolderData> = Vec::new();
         for folder in folders_lock.iter() {
-            let world_count = folder.world_ids.len() as u16;
-            folder_data.push(FolderData::new(
-                folder.folder_name.clone(),
-                world_count,
-                folder.color.clone(),
-            ));
+            if folder.archived && !include_archived {
+                continue;
+            }
+            folder_data.push(FolderData::new(folder));
         }
         Ok(folder_data)
     }
@@ -441,7 +758,14 @@ impl FolderManager {
         folders: &RwLock<Vec<FolderModel>>,
     ) -> Result<String, AppError> {
         let folders_lock = folders.read().map_err(|_| ConcurrencyError::PoisonedLock)?;
+        Ok(Self::next_free_folder_name(name, |name| {
+            folders_lock.iter().any(|f| f.folder_name == name)
+        }))
+    }
 
+    /// Numbers a folder name until it is not taken, e.g. "Chill (1)", "Chill (2)"
+    /// Names already ending in a number count up from it
+    fn next_free_folder_name(name: String, taken: impl Fn(&str) -> bool) -> String {
         let mut new_name = name.clone();
         let mut base_name = name.clone();
         let mut count = 1;
@@ -458,12 +782,12 @@ impl FolderManager {
             }
         }
         // if not, check if the name already exists
-        while folders_lock.iter().any(|f| f.folder_name == new_name) {
+        while taken(&new_name) {
             log::info!("Folder name exists: {}", new_name);
             new_name = format!("{} ({})", base_name, count);
             count += 1;
         }
-        Ok(new_name)
+        new_name
     }
 
     /// Create a new folder, adding it to the list of folders
@@ -477,12 +801,14 @@ impl FolderManager {
     /// The new folder
     ///
     /// # Errors
+    /// Returns an error if the name is not a valid folder name
     /// Returns an error if the folders lock is poisoned
     #[must_use]
     pub fn create_folder(
         name: String,
         folders: &RwLock<Vec<FolderModel>>,
     ) -> Result<String, AppError> {
+        InputValidator::folder_name(&name)?;
         let new_name = FolderManager::increment_folder_name(name, folders)?;
 
         let mut folders_lock = folders
@@ -496,7 +822,7 @@ impl FolderManager {
     }
 
     /// Delete a folder by name
-    /// For each world in the folder, pass to remove_world_from_folder
+    /// The folder is also removed from the folders of each of its worlds
     ///
     ///
     /// # Arguments
@@ -514,29 +840,25 @@ impl FolderManager {
         folders: &RwLock<Vec<FolderModel>>,
         worlds: &RwLock<Vec<WorldModel>>,
     ) -> Result<(), AppError> {
-        let mut folders_lock = folders
-            .write()
-            .map_err(|_| ConcurrencyError::PoisonedLock)?;
-
-        let folder_index = folders_lock.iter().position(|f| f.folder_name == name);
-        match folder_index {
-            Some(index) => {
-                let world_ids = folders_lock[index].world_ids.clone();
-                folders_lock.remove(index);
-                FileService::write_folders(&*folders_lock)?;
-                drop(folders_lock);
-                for world_id in world_ids {
-                    FolderManager::remove_world_from_folder(
-                        name.clone(),
-                        world_id,
-                        folders,
-                        worlds,
-                    )?;
-                }
-                Ok(())
+        let journal = Self::journal_library_write("delete_folder")?;
+        Self::edit_library(journal, folders, worlds, |folders, worlds| {
+            let index = folders
+                .iter()
+                .position(|f| f.folder_name == name)
+                .ok_or_else(|| EntityError::FolderNotFound(name.clone()))?;
+            let folder = folders.remove(index);
+            for world in worlds
+                .iter_mut()
+                .filter(|w| folder.world_ids.contains(&w.api_data.world_id))
+            {
+                world.user_data.folders.retain(|f| f != &name);
             }
-            None => Err(EntityError::FolderNotFound(name).into()),
-        }
+
+            let mut custom_data = FileService::read_custom_data();
+            custom_data.remove_folder(&name);
+            FileService::write_custom_data(&custom_data)?;
+            Ok(())
+        })
     }
 
     /// Move a folder to a new position in the list
@@ -569,75 +891,234 @@ impl FolderManager {
         folders_lock.insert(new_index, folder);
 
         FileService::write_folders(&*folders_lock)?;
+        LibraryEvents::folder_reordered(
+            folders_lock.iter().map(|f| f.folder_name.clone()).collect(),
+        );
         Ok(())
     }
 
-    /// Rename a folder
-    /// This is done by removing the folder from the list, and adding it back with the new name
-    /// We also need to update the world user_data.folders list
+    /// Move a world to a new position within a folder
+    /// The order of a folder's worlds is the order used when playing the folder as a playlist
     ///
     /// # Arguments
-    /// * `old_name` - The old name of the folder
-    /// * `new_name` - The new name of the folder
+    /// * `folder_name` - The name of the folder
+    /// * `world_id` - The ID of the world to move
+    /// * `new_index` - The new index for the world, clamped to the end of the folder
     /// * `folders` - The list of folders, as a RwLock
-    /// * `worlds` - The list of worlds, as a RwLock
-    /// * `preferences` - The preferences, as a RwLock. Used to store user-specific settings
-    ///   and configurations that may influence folder renaming behavior, such as naming conventions
-    ///   or restrictions.
     ///
     /// # Returns
-    /// Ok if the folder was renamed successfully
+    /// Ok if the world was moved successfully
     ///
     /// # Errors
     /// Returns an error if the folder is not found
-    /// Returns an error if the worlds lock is poisoned
-    /// Returns an error if the folders lock is poisoned
-    pub fn rename_folder(
+    /// Returns an error if the world is not in the folder
+    pub fn move_world_in_folder(
+        folder_name: String,
+        world_id: String,
+        new_index: usize,
+        folders: &RwLock<Vec<FolderModel>>,
+    ) -> Result<(), AppError> {
+        let mut folders_lock = folders
+            .write()
+            .map_err(|_| ConcurrencyError::PoisonedLock)?;
+
+        let folder = folders_lock
+            .iter_mut()
+            .find(|f| f.folder_name == folder_name)
+            .ok_or_else(|| EntityError::FolderNotFound(folder_name))?;
+        let current_index = folder
+            .world_ids
+            .iter()
+            .position(|id| *id == world_id)
+            .ok_or_else(|| EntityError::WorldNotFound(world_id))?;
+        // Remove from current position and insert at new position
+        let world_id = folder.world_ids.remove(current_index);
+        let new_index = new_index.min(folder.world_ids.len());
+        folder.world_ids.insert(new_index, world_id);
+
+        FileService::write_folders(&*folders_lock)?;
+        Ok(())
+    }
+
+    /// Rename a folder
+    /// This is done by removing the folder from the list, and adding it back with the new name
+    /// We also need to update the world user_data.folders list
+    /// If another folder already has the new name, `on_collision` decides what happens
+    ///
+    /// # Arguments
+    /// * `old_name` - The old name of the folder
+    /// * `new_name` - The new name of the folder
+    /// * `on_collision` - What to do if another folder already has the new name
+    /// * `folders` - The list of folders, as a RwLock
+    /// * `worlds` - The list of worlds, as a RwLock
+    /// * `preferences` - The preferences, as a RwLock. Used to store user-specific settings
+    ///   and configurations that may influence folder renaming behavior, such as naming conventions
+    ///   or restrictions.
+    ///
+    /// # Returns
+    /// Whether the folder was renamed or merged, with the name its worlds are in now
+    ///
+    /// # Errors
+    /// Returns an error if the new name is not a valid folder name
+    /// Returns an error if the folder is not found
+    /// Returns an error if the new name is taken and `on_collision` is `Reject`
+    /// Returns an error if the worlds lock is poisoned
+    /// Returns an error if the folders lock is poisoned
+    pub fn rename_folder(
         old_name: String,
         new_name: String,
+        on_collision: FolderNameCollision,
         folders: &RwLock<Vec<FolderModel>>,
         worlds: &RwLock<Vec<WorldModel>>,
         preferences: &RwLock<PreferenceModel>,
-    ) -> Result<(), AppError> {
+    ) -> Result<FolderRename, AppError> {
+        InputValidator::folder_name(&new_name)?;
+        let journal = Self::journal_library_write("rename_folder")?;
         let mut preferences_lock = preferences
             .write()
             .map_err(|_| ConcurrencyError::PoisonedLock)?;
+        let mut starred_selector = preferences_lock.filter_item_selector_starred.clone();
+
+        let rename = Self::edit_library(journal, folders, worlds, |folders, worlds| {
+            let Some(index) = folders.iter().position(|f| f.folder_name == old_name) else {
+                return Err(EntityError::FolderNotFound(old_name.clone()).into());
+            };
+            // Renaming a folder to its own name is not a collision
+            let taken =
+                |name: &str| name != old_name && folders.iter().any(|f| f.folder_name == name);
+            let (new_name, merge_into) = if taken(&new_name) {
+                match on_collision {
+                    FolderNameCollision::Reject => {
+                        return Err(EntityError::DuplicateFolder(new_name).into());
+                    }
+                    FolderNameCollision::Increment => {
+                        (Self::next_free_folder_name(new_name, taken), None)
+                    }
+                    FolderNameCollision::Merge => {
+                        let target = folders.iter().position(|f| f.folder_name == new_name);
+                        (new_name, target)
+                    }
+                }
+            } else {
+                (new_name, None)
+            };
 
-        if let Some(starred_selector) = &mut preferences_lock.filter_item_selector_starred {
-            if let Some(folder_index) = starred_selector.folder.iter().position(|f| f == &old_name)
-            {
-                starred_selector.folder[folder_index] = new_name.clone();
+            if let Some(starred_selector) = &mut starred_selector {
+                if let Some(folder_index) =
+                    starred_selector.folder.iter().position(|f| f == &old_name)
+                {
+                    if starred_selector.folder.contains(&new_name) {
+                        starred_selector.folder.remove(folder_index);
+                    } else {
+                        starred_selector.folder[folder_index] = new_name.clone();
+                    }
+                }
             }
-        }
-
-        let mut folders_lock = folders
-            .write()
-            .map_err(|_| ConcurrencyError::PoisonedLock)?;
-        let mut worlds_lock = worlds.write().map_err(|_| ConcurrencyError::PoisonedLock)?;
 
-        let folder_index = folders_lock.iter().position(|f| f.folder_name == old_name);
-        match folder_index {
-            Some(index) => {
-                let world_ids = folders_lock[index].world_ids.clone();
-                folders_lock[index].folder_name = new_name.clone();
-                FileService::write_folders(&*folders_lock)?;
-                drop(folders_lock);
-                for world_id in world_ids {
-                    if let Some(world) = worlds_lock
-                        .iter_mut()
-                        .find(|w| w.api_data.world_id == world_id)
-                    {
-                        world.user_data.folders.retain(|folder| folder != &old_name);
-                        if !world.user_data.folders.contains(&new_name) {
-                            world.user_data.folders.push(new_name.clone());
+            let world_ids = folders[index].world_ids.clone();
+            let mut added = vec![];
+            match merge_into {
+                Some(target) => {
+                    let target_folder = &mut folders[target];
+                    for world_id in &world_ids {
+                        if !target_folder.world_ids.contains(world_id) {
+                            target_folder.world_ids.push(world_id.clone());
+                            added.push(world_id.clone());
                         }
                     }
+                    folders.remove(index);
                 }
-                FileService::write_worlds(&*worlds_lock)?;
-                Ok(())
+                None => folders[index].folder_name = new_name.clone(),
             }
-            None => Err(EntityError::FolderNotFound(old_name).into()),
-        }
+
+            let mut custom_data = FileService::read_custom_data();
+            if merge_into.is_some() {
+                // The folder merged into keeps its own color, icon and description
+                custom_data.remove_folder(&old_name);
+            } else {
+                custom_data.rename_folder(&old_name, &new_name);
+            }
+            FileService::write_custom_data(&custom_data)?;
+            for world_id in &world_ids {
+                if let Some(world) = worlds.iter_mut().find(|w| &w.api_data.world_id == world_id) {
+                    world.user_data.folders.retain(|folder| folder != &old_name);
+                    if !world.user_data.folders.contains(&new_name) {
+                        world.user_data.folders.push(new_name.clone());
+                    }
+                }
+            }
+
+            Ok(match merge_into {
+                Some(_) => FolderRename::Merged {
+                    folder_name: new_name,
+                    world_ids,
+                    added,
+                },
+                None => FolderRename::Renamed {
+                    folder_name: new_name,
+                },
+            })
+        })?;
+
+        preferences_lock.filter_item_selector_starred = starred_selector;
+        LibraryEvents::folder_renamed(
+            old_name,
+            rename.folder_name().to_string(),
+            matches!(rename, FolderRename::Merged { .. }),
+        );
+        Ok(rename)
+    }
+
+    /// Journals folders.json, worlds.json and custom_data.json for an operation that writes them
+    /// See `Journal::begin`, this must be called before taking the folder or world lock
+    fn journal_library_write(operation: &str) -> Result<JournalGuard, AppError> {
+        let (_, folders_path, worlds_path, _) = FileService::get_paths();
+        Journal::begin(
+            operation,
+            &[
+                folders_path,
+                worlds_path,
+                FileService::get_custom_data_path(),
+            ],
+        )
+        .map_err(AppError::from)
+    }
+
+    /// Applies a journaled change to copies of the folders and worlds, writes both, and swaps
+    /// the copies in only once the journal is committed
+    /// An operation failing midway leaves the lists in memory as they were, matching the files
+    /// the journal rolls back, so a later save cannot write a half-applied change to disk
+    ///
+    /// # Arguments
+    /// * `journal` - The journal of the operation, from `journal_library_write`
+    /// * `folders` - The list of folders, as a RwLock
+    /// * `worlds` - The list of worlds, as a RwLock
+    /// * `edit` - Applies the change to the copies
+    ///
+    /// # Returns
+    /// What `edit` returned
+    ///
+    /// # Errors
+    /// Returns the error of `edit`, in which case nothing is written or swapped in
+    /// Returns an error if any lock is poisoned
+    /// Returns an error if the worlds or folders could not be written
+    fn edit_library<T>(
+        journal: JournalGuard,
+        folders: &RwLock<Vec<FolderModel>>,
+        worlds: &RwLock<Vec<WorldModel>>,
+        edit: impl FnOnce(&mut Vec<FolderModel>, &mut Vec<WorldModel>) -> Result<T, AppError>,
+    ) -> Result<T, AppError> {
+        let (mut folders_lock, mut worlds_lock) = write_library(folders, worlds)?;
+        let mut edited_folders = folders_lock.clone();
+        let mut edited_worlds = worlds_lock.clone();
+        let result = edit(&mut edited_folders, &mut edited_worlds)?;
+
+        FileService::write_folders(&edited_folders)?;
+        FileService::write_worlds(&edited_worlds)?;
+        journal.commit();
+        *folders_lock = edited_folders;
+        *worlds_lock = edited_worlds;
+        Ok(result)
     }
 
     /// Get a world by its ID
@@ -674,6 +1155,7 @@ impl FolderManager {
     /// Ok if the color was set successfully
     ///
     /// # Errors
+    /// Returns an error if the color is not a hex color
     /// Returns an error if the folder is not found
     /// Returns an error if the folders lock is poisoned
     pub fn set_folder_color(
@@ -681,6 +1163,9 @@ impl FolderManager {
         color: Option<String>,
         folders: &RwLock<Vec<FolderModel>>,
     ) -> Result<(), AppError> {
+        if let Some(color) = &color {
+            InputValidator::color(color)?;
+        }
         let mut folders_lock = folders
             .write()
             .map_err(|_| ConcurrencyError::PoisonedLock)?;
@@ -702,8 +1187,183 @@ impl FolderManager {
         }
     }
 
+    /// Set the icon of a folder
+    ///
+    /// # Arguments
+    /// * `folder_name` - The name of the folder
+    /// * `icon` - An emoji or icon identifier, or None to reset
+    /// * `folders` - The list of folders, as a RwLock
+    ///
+    /// # Returns
+    /// Ok if the icon was set successfully
+    ///
+    /// # Errors
+    /// Returns an error if the folder is not found
+    /// Returns an error if the folders lock is poisoned
+    pub fn set_folder_icon(
+        folder_name: String,
+        icon: Option<String>,
+        folders: &RwLock<Vec<FolderModel>>,
+    ) -> Result<(), AppError> {
+        let mut folders_lock = folders
+            .write()
+            .map_err(|_| ConcurrencyError::PoisonedLock)?;
+
+        let folder = folders_lock
+            .iter_mut()
+            .find(|f| f.folder_name == folder_name)
+            .ok_or_else(|| EntityError::FolderNotFound(folder_name.clone()))?;
+        folder.icon = icon.clone();
+        let mut custom_data = FileService::read_custom_data();
+        custom_data.set_folder_icon(&folder_name, icon.as_deref());
+        FileService::write_custom_data(&custom_data)?;
+        Ok(())
+    }
+
+    /// Set the description of a folder
+    ///
+    /// # Arguments
+    /// * `folder_name` - The name of the folder
+    /// * `description` - The description, or None to reset
+    /// * `folders` - The list of folders, as a RwLock
+    ///
+    /// # Returns
+    /// Ok if the description was set successfully
+    ///
+    /// # Errors
+    /// Returns an error if the folder is not found
+    /// Returns an error if the folders lock is poisoned
+    pub fn set_folder_description(
+        folder_name: String,
+        description: Option<String>,
+        folders: &RwLock<Vec<FolderModel>>,
+    ) -> Result<(), AppError> {
+        let mut folders_lock = folders
+            .write()
+            .map_err(|_| ConcurrencyError::PoisonedLock)?;
+
+        let folder = folders_lock
+            .iter_mut()
+            .find(|f| f.folder_name == folder_name)
+            .ok_or_else(|| EntityError::FolderNotFound(folder_name.clone()))?;
+        folder.description = description.clone();
+        let mut custom_data = FileService::read_custom_data();
+        custom_data.set_folder_description(&folder_name, description.as_deref());
+        FileService::write_custom_data(&custom_data)?;
+        Ok(())
+    }
+
+    /// Archive or restore a folder
+    /// Archived folders keep their worlds, but are left out of the folder list and of
+    /// background refreshes
+    ///
+    /// # Arguments
+    /// * `folder_name` - The name of the folder
+    /// * `archived` - Whether the folder should be archived
+    /// * `folders` - The list of folders, as a RwLock
+    ///
+    /// # Returns
+    /// Whether the folder was archived before
+    ///
+    /// # Errors
+    /// Returns an error if the folder is not found
+    /// Returns an error if the folders lock is poisoned
+    /// Returns an error if the folders could not be saved
+    pub fn set_folder_archived(
+        folder_name: String,
+        archived: bool,
+        folders: &RwLock<Vec<FolderModel>>,
+    ) -> Result<bool, AppError> {
+        let mut folders_lock = folders
+            .write()
+            .map_err(|_| ConcurrencyError::PoisonedLock)?;
+
+        let folder = folders_lock
+            .iter_mut()
+            .find(|f| f.folder_name == folder_name)
+            .ok_or(EntityError::FolderNotFound(folder_name))?;
+        let was_archived = folder.archived;
+        folder.archived = archived;
+        if was_archived != archived {
+            FileService::write_folders(&*folders_lock)?;
+        }
+        Ok(was_archived)
+    }
+
+    /// Get the IDs of the worlds which are only in archived folders
+    /// Worlds in no folder at all are not included
+    ///
+    /// # Arguments
+    /// * `folders` - The list of folders, as a RwLock
+    ///
+    /// # Returns
+    /// The IDs of the worlds whose every folder is archived
+    ///
+    /// # Errors
+    /// Returns an error if the folders lock is poisoned
+    pub fn get_archived_only_world_ids(
+        folders: &RwLock<Vec<FolderModel>>,
+    ) -> Result<HashSet<String>, AppError> {
+        let folders_lock = folders.read().map_err(|_| ConcurrencyError::PoisonedLock)?;
+        let active: HashSet<&String> = folders_lock
+            .iter()
+            .filter(|f| !f.archived)
+            .flat_map(|f| f.world_ids.iter())
+            .collect();
+        Ok(folders_lock
+            .iter()
+            .filter(|f| f.archived)
+            .flat_map(|f| f.world_ids.iter())
+            .filter(|id| !active.contains(id))
+            .cloned()
+            .collect())
+    }
+
+    /// Set the sort order of a folder, overriding the global one
+    ///
+    /// # Arguments
+    /// * `folder_name` - The name of the folder
+    /// * `sort` - The sort order, or None to follow the global one again
+    /// * `folders` - The list of folders, as a RwLock
+    ///
+    /// # Returns
+    /// Ok if the sort order was set successfully
+    ///
+    /// # Errors
+    /// Returns an error if the folder is not found
+    /// Returns an error if the sort field or direction is unknown
+    /// Returns an error if the folders lock is poisoned
+    /// Returns an error if the folders could not be saved
+    pub fn set_folder_sort(
+        folder_name: String,
+        sort: Option<FolderSort>,
+        folders: &RwLock<Vec<FolderModel>>,
+    ) -> Result<(), AppError> {
+        if let Some(sort) = &sort {
+            if !SortingService::is_valid_sort(&sort.field, &sort.direction) {
+                return Err(EntityError::InvalidOperation(format!(
+                    "Cannot sort by {} {}",
+                    sort.field, sort.direction
+                ))
+                .into());
+            }
+        }
+
+        let mut folders_lock = folders
+            .write()
+            .map_err(|_| ConcurrencyError::PoisonedLock)?;
+        let folder = folders_lock
+            .iter_mut()
+            .find(|f| f.folder_name == folder_name)
+            .ok_or(EntityError::FolderNotFound(folder_name))?;
+        folder.sort = sort;
+        FileService::write_folders(&*folders_lock)?;
+        Ok(())
+    }
+
     /// Get the worlds in a folder by name
     /// Calls get_world for each world ID in the folder
+    /// Worlds are in folder order, unless the folder has its own sort order
     ///
     /// # Arguments
     /// * `folder_name` - The name of the folder
@@ -728,18 +1388,61 @@ impl FolderManager {
         match folder {
             Some(folder) => {
                 let world_ids = folder.world_ids.clone();
+                let sort = folder.sort.clone();
                 let mut folder_worlds = vec![];
                 drop(folders_lock);
                 for world_id in world_ids {
                     let world = Self::get_world(world_id, worlds)?;
                     folder_worlds.push(world.to_display_data());
                 }
-                Ok(folder_worlds)
+                Ok(match sort {
+                    Some(sort) => SortingService::sort_world_display_data(
+                        folder_worlds,
+                        &sort.field,
+                        &sort.direction,
+                    ),
+                    None => folder_worlds,
+                })
             }
             None => Err(EntityError::FolderNotFound(folder_name).into()),
         }
     }
 
+    /// Get the worlds in a folder which have not been checked recently
+    ///
+    /// # Arguments
+    /// * `folder_name` - The name of the folder
+    /// * `folders` - The list of folders, as a RwLock
+    /// * `worlds` - The list of worlds, as a RwLock
+    ///
+    /// # Returns
+    /// The stored data of every stale world in the folder, see `WorldUserData::needs_update`
+    ///
+    /// # Errors
+    /// Returns an error if the folder is not found
+    /// Returns an error if any lock is poisoned
+    pub fn get_stale_folder_worlds(
+        folder_name: String,
+        folders: &RwLock<Vec<FolderModel>>,
+        worlds: &RwLock<Vec<WorldModel>>,
+    ) -> Result<Vec<WorldApiData>, AppError> {
+        let world_ids = {
+            let folders_lock = folders.read().map_err(|_| ConcurrencyError::PoisonedLock)?;
+            folders_lock
+                .iter()
+                .find(|f| f.folder_name == folder_name)
+                .map(|f| f.world_ids.clone())
+                .ok_or(EntityError::FolderNotFound(folder_name))?
+        };
+
+        let worlds_lock = worlds.read().map_err(|_| ConcurrencyError::PoisonedLock)?;
+        Ok(worlds_lock
+            .iter()
+            .filter(|w| world_ids.contains(&w.api_data.world_id) && w.user_data.needs_update())
+            .map(|w| w.api_data.clone())
+            .collect())
+    }
+
     /// Get all worlds
     /// Hidden worlds are excluded.
     ///
@@ -768,6 +1471,7 @@ impl FolderManager {
     /// Get all worlds that are Unclassified
     /// Check all worlds, and return those that are not in any folder
     /// This is done by checking if the world's folders list is empty, and the hidden flag is false
+    /// Worlds in the inbox are left out until they are triaged
     ///
     /// # Arguments
     /// * `worlds` - The list of worlds, as a RwLock
@@ -784,7 +1488,11 @@ impl FolderManager {
         let worlds_lock = worlds.read().map_err(|_| ConcurrencyError::PoisonedLock)?;
         let unclassified_worlds = worlds_lock
             .iter()
-            .filter(|w| w.user_data.folders.is_empty() && w.user_data.hidden == false)
+            .filter(|w| {
+                w.user_data.folders.is_empty()
+                    && w.user_data.hidden == false
+                    && !w.user_data.in_inbox
+            })
             .cloned()
             .map(|w| w.to_display_data())
             .collect();
@@ -816,9 +1524,57 @@ impl FolderManager {
         Ok(hidden_worlds)
     }
 
+    /// Gets summaries of the worlds of a source, in the order of the source
+    /// Much smaller than the display data, for the grids of large folders
+    ///
+    /// # Arguments
+    /// * `source` - "All Worlds", "Unclassified Worlds", "Hidden Worlds" or a folder name
+    /// * `folders` - The list of folders, as a RwLock
+    /// * `worlds` - The list of worlds, as a RwLock
+    ///
+    /// # Errors
+    /// Returns an error if the source is not a special folder and the folder is not found
+    /// Returns an error if any lock is poisoned
+    pub fn get_world_summaries(
+        source: &str,
+        folders: &RwLock<Vec<FolderModel>>,
+        worlds: &RwLock<Vec<WorldModel>>,
+    ) -> Result<Vec<WorldSummary>, AppError> {
+        let source_worlds = GroupingService::get_source_worlds(source, folders, worlds)?;
+        Ok(source_worlds.into_iter().map(WorldSummary::from).collect())
+    }
+
+    /// Gets the display data of worlds by ID, for the cards currently shown
+    /// Worlds are in the order of `world_ids`, IDs not in the library are skipped
+    ///
+    /// # Arguments
+    /// * `world_ids` - The IDs of the worlds
+    /// * `worlds` - The list of worlds, as a RwLock
+    ///
+    /// # Errors
+    /// Returns an error if the worlds lock is poisoned
+    pub fn get_worlds_by_id(
+        world_ids: &[String],
+        worlds: &RwLock<Vec<WorldModel>>,
+    ) -> Result<Vec<WorldDisplayData>, AppError> {
+        let worlds_lock = worlds.read().map_err(|_| ConcurrencyError::PoisonedLock)?;
+        let by_id: HashMap<&str, &WorldModel> = worlds_lock
+            .iter()
+            .map(|w| (w.api_data.world_id.as_str(), w))
+            .collect();
+        Ok(world_ids
+            .iter()
+            .filter_map(|id| by_id.get(id.as_str()))
+            .map(|w| w.to_display_data())
+            .collect())
+    }
+
     /// Adds worlds to data
     /// This is called when the api returns a list of worlds
     /// or when we add via the folder sharing feature
+    /// Blacklisted worlds are skipped
+    /// If an author's name differs from the one stored, the old name is recorded as an alias
+    /// and every world by that author is updated to the new name
     /// We check if the world is already in the list
     /// If it is, we update the world data and set the last checked time
     /// If it is not, we add the world to the list
@@ -826,24 +1582,91 @@ impl FolderManager {
     /// # Arguments
     /// * `worlds` - The list of worlds, as a RwLock
     /// * `new_worlds` - The list of new worlds to add
+    /// * `blacklist` - The world blacklist, as a RwLock
+    ///
+    /// Worlds whose data changed are sent to the frontend through `WorldChangeService`, and
+    /// the worlds are snapshotted for `WorldHistoryService`
+    /// Fields the user locked keep their value, see `FieldLockService`; user data is never
+    /// replaced
     ///
     /// # Returns
-    /// Ok if the worlds were added successfully
+    /// The saved worlds which were updated by their author since they were last fetched
     ///
     /// # Errors
     /// Returns an error if the worlds lock is poisoned
+    /// Returns an error if the blacklist lock is poisoned
     pub fn add_worlds(
         worlds: &RwLock<Vec<WorldModel>>,
         new_worlds: Vec<WorldApiData>,
-    ) -> Result<(), AppError> {
+        blacklist: &RwLock<WorldBlacklist>,
+    ) -> Result<Vec<WorldUpdate>, AppError> {
+        Self::add_dated_worlds(worlds, new_worlds, &HashMap::new(), blacklist)
+            .map(|(updated_worlds, _)| updated_worlds)
+    }
+
+    /// Adds worlds as `add_worlds` does, giving the worlds which were not in the library yet
+    /// their date in `dates`, e.g. to date imported worlds in the order they were favorited
+    /// rather than the order they were fetched in
+    ///
+    /// # Arguments
+    /// * `worlds` - The list of worlds, as a RwLock
+    /// * `new_worlds` - The list of new worlds to add
+    /// * `dates` - The date added of the new worlds, by world ID
+    /// * `blacklist` - The world blacklist, as a RwLock
+    ///
+    /// # Returns
+    /// The saved worlds which were updated by their author since they were last fetched, and
+    /// the display data of the worlds which were added
+    ///
+    /// # Errors
+    /// Returns an error if the worlds lock is poisoned
+    /// Returns an error if the blacklist lock is poisoned
+    pub fn add_dated_worlds(
+        worlds: &RwLock<Vec<WorldModel>>,
+        new_worlds: Vec<WorldApiData>,
+        dates: &HashMap<String, chrono::DateTime<chrono::Utc>>,
+        blacklist: &RwLock<WorldBlacklist>,
+    ) -> Result<(Vec<WorldUpdate>, Vec<WorldDisplayData>), AppError> {
+        let new_worlds = BlacklistManager::filter_api_data(new_worlds, blacklist)?;
+        WorldHistoryService::record(&new_worlds);
         let mut worlds_lock = worlds.write().map_err(|_| ConcurrencyError::PoisonedLock)?;
 
         // Read custom data to check for existing status
-        let custom_data = FileService::read_custom_data();
+        let mut custom_data = FileService::read_custom_data();
 
-        for new_world in new_worlds {
+        let renamed_authors = Self::detect_author_renames(&worlds_lock, &new_worlds);
+        if !renamed_authors.is_empty() {
+            for world in worlds_lock.iter_mut() {
+                if let Some((old_name, new_name)) = renamed_authors.get(&world.api_data.author_id)
+                {
+                    if &world.api_data.author_name == old_name {
+                        world.api_data.author_name = new_name.clone();
+                    }
+                }
+            }
+            for (author_id, (old_name, new_name)) in renamed_authors.iter() {
+                log::info!("Author {} renamed: {} -> {}", author_id, old_name, new_name);
+                custom_data.add_author_alias(author_id, old_name);
+            }
+            FileService::write_custom_data(&custom_data)?;
+        }
+
+        let mut updated_worlds = vec![];
+        let mut added_ids = HashSet::new();
+        let mut changed_ids: HashSet<String> = renamed_authors
+            .keys()
+            .flat_map(|author_id| {
+                worlds_lock
+                    .iter()
+                    .filter(move |w| &w.api_data.author_id == author_id)
+                    .map(|w| w.api_data.world_id.clone())
+            })
+            .collect();
+        for mut new_world in new_worlds {
             let world_id = new_world.world_id.clone();
             log::info!("Adding world: {}", world_id);
+            // Fields the user corrected are kept, whichever copy of the data wins below
+            FieldLockService::apply(custom_data.get_world_field_locks(&world_id), &mut new_world);
             let existing_world = worlds_lock
                 .iter_mut()
                 .find(|w| w.api_data.world_id == world_id);
@@ -852,34 +1675,173 @@ impl FolderManager {
                     log::info!("World already exists, updating world data: {}", world_id);
                     // Only update if new_world has a more recent last_update
                     if new_world.last_update > world.api_data.last_update {
+                        updated_worlds.push(WorldUpdate {
+                            world_id: world_id.clone(),
+                            world_name: new_world.world_name.clone(),
+                            old_update: world.api_data.last_update,
+                            new_update: new_world.last_update,
+                        });
                         world.api_data = new_world;
+                        changed_ids.insert(world_id.clone());
                     } else if new_world.last_update == world.api_data.last_update {
+                        // The same build, so download sizes fetched before still apply
+                        let had_sizes = world.api_data.has_package_sizes();
+                        let gains_sizes = !had_sizes && new_world.has_package_sizes();
+                        if had_sizes && !new_world.has_package_sizes() {
+                            new_world.packages = world.api_data.packages.clone();
+                        }
                         // If updatedAt is equal, use the one with greater visits
                         let existing_visits = world.api_data.visits.unwrap_or(0);
                         let new_visits = new_world.visits.unwrap_or(0);
-                        if new_visits > existing_visits {
+                        if new_visits > existing_visits || gains_sizes {
                             world.api_data = new_world;
+                            changed_ids.insert(world_id.clone());
                         }
                     }
                     world.user_data.last_checked = chrono::Utc::now();
                 }
                 None => {
                     let mut world_model = WorldModel::new(new_world);
+                    if let Some(date) = dates.get(&world_id) {
+                        world_model.user_data.date_added = *date;
+                    }
                     // Check if the world existing status in custom_data
                     world_model.user_data.is_favorite = custom_data.is_world_favorite(&world_id);
                     world_model.user_data.is_photographed =
                         custom_data.is_world_photographed(&world_id);
                     world_model.user_data.is_shared = custom_data.is_world_shared(&world_id);
+                    world_model.user_data.is_event_critical =
+                        custom_data.is_world_event_critical(&world_id);
+                    world_model.user_data.rating = custom_data.get_world_rating(&world_id);
+                    world_model.user_data.launch_count =
+                        custom_data.get_world_launch_count(&world_id);
+                    world_model.user_data.cover_image =
+                        custom_data.get_world_cover(&world_id).cloned();
+                    world_model.user_data.in_inbox = custom_data.is_world_in_inbox(&world_id);
 
                     worlds_lock.push(world_model);
+                    added_ids.insert(world_id.clone());
+                    changed_ids.insert(world_id);
                 }
             }
         }
         FileService::write_worlds(&*worlds_lock)?;
-        Ok(())
+
+        let changed_worlds = worlds_lock
+            .iter()
+            .filter(|w| !w.user_data.hidden && changed_ids.contains(&w.api_data.world_id))
+            .map(WorldModel::to_display_data)
+            .collect();
+        let added_worlds: Vec<WorldDisplayData> = worlds_lock
+            .iter()
+            .filter(|w| !w.user_data.hidden && added_ids.contains(&w.api_data.world_id))
+            .map(WorldModel::to_display_data)
+            .collect();
+        drop(worlds_lock);
+        WorldChangeService::record(changed_worlds);
+        LibraryEvents::world_added(added_worlds.clone());
+        Ok((updated_worlds, added_worlds))
+    }
+
+    /// Gets the statistics of a folder, for the info panel in its header
+    ///
+    /// # Arguments
+    /// * `folder_name` - The name of the folder
+    /// * `folders` - The list of folders, as a RwLock
+    /// * `worlds` - The list of worlds, as a RwLock
+    ///
+    /// # Returns
+    /// The statistics of the folder
+    ///
+    /// # Errors
+    /// Returns an error if the folder is not found
+    /// Returns an error if any lock is poisoned
+    pub fn get_folder_stats(
+        folder_name: String,
+        folders: &RwLock<Vec<FolderModel>>,
+        worlds: &RwLock<Vec<WorldModel>>,
+    ) -> Result<FolderStats, AppError> {
+        let (folders_lock, worlds_lock) = read_library(folders, worlds)?;
+        let folder = folders_lock
+            .iter()
+            .find(|f| f.folder_name == folder_name)
+            .ok_or_else(|| EntityError::FolderNotFound(folder_name.clone()))?;
+        let folder_worlds: Vec<&WorldModel> = folder
+            .world_ids
+            .iter()
+            .filter_map(|id| worlds_lock.iter().find(|w| &w.api_data.world_id == id))
+            .collect();
+
+        let mut stats = FolderStats {
+            folder_name: folder.folder_name.clone(),
+            world_count: folder_worlds.len() as u32,
+            pc_only: 0,
+            quest_only: 0,
+            cross_platform: 0,
+            total_capacity: 0,
+            average_capacity: None,
+            tags: vec![],
+            oldest_added: None,
+            newest_added: None,
+            not_visited: 0,
+            share: folder.share.as_ref().map(|share| FolderShareStatus {
+                share_id: share.id.clone(),
+                expiry_time: share.expiry_time,
+                expired: share.expiry_time <= chrono::Utc::now(),
+            }),
+        };
+
+        let mut tag_counts: HashMap<String, u32> = HashMap::new();
+        for world in &folder_worlds {
+            let platforms = &world.api_data.platform;
+            let pc = platforms.iter().any(|p| p == "standalonewindows");
+            let quest = platforms.iter().any(|p| p == "android");
+            match (pc, quest) {
+                (true, true) => stats.cross_platform += 1,
+                (false, true) => stats.quest_only += 1,
+                _ => stats.pc_only += 1,
+            }
+            stats.total_capacity += world.api_data.capacity.max(0) as u32;
+            if world.user_data.launch_count == 0 {
+                stats.not_visited += 1;
+            }
+            for tag in TagService::apply(&world.api_data.tags) {
+                if let Some(tag) = tag.strip_prefix("author_tag_") {
+                    *tag_counts.entry(tag.to_string()).or_insert(0) += 1;
+                }
+            }
+        }
+        if !folder_worlds.is_empty() {
+            stats.average_capacity =
+                Some(f64::from(stats.total_capacity) / folder_worlds.len() as f64);
+        }
+
+        let mut tags: Vec<TagCount> = tag_counts
+            .into_iter()
+            .map(|(tag, count)| TagCount { tag, count })
+            .collect();
+        tags.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.tag.cmp(&b.tag)));
+        stats.tags = tags;
+
+        let added = |world: &&WorldModel| FolderStatsWorld {
+            world_id: world.api_data.world_id.clone(),
+            world_name: world.api_data.world_name.clone(),
+            date_added: world.user_data.date_added,
+        };
+        stats.oldest_added = folder_worlds
+            .iter()
+            .min_by_key(|w| w.user_data.date_added)
+            .map(added);
+        stats.newest_added = folder_worlds
+            .iter()
+            .max_by_key(|w| w.user_data.date_added)
+            .map(added);
+
+        Ok(stats)
     }
 
     /// return a list of tags, sorted by the number of worlds in each tag
+    /// Tag aliases are applied, and hidden tags left out
     ///
     /// # Arguments
     /// * `worlds` - The list of worlds, as a RwLock
@@ -895,7 +1857,7 @@ impl FolderManager {
         // create a map which contains the tag and the number of worlds in that tag
         let mut tag_map: HashMap<String, usize> = HashMap::new();
         for world in worlds_lock.iter() {
-            for tag in &world.api_data.tags {
+            for tag in &TagService::apply(&world.api_data.tags) {
                 if tag.starts_with("author_tag_") {
                     let stripped_tag = tag.strip_prefix("author_tag_").unwrap().to_string();
                     *tag_map.entry(stripped_tag).or_insert(0) += 1;
@@ -940,6 +1902,87 @@ impl FolderManager {
         Ok(authors)
     }
 
+    /// Finds authors whose name in the incoming data differs from the stored name
+    /// Incoming data is treated as the latest, as it has just been fetched
+    /// When the stored worlds of an author disagree, the name of the most recently
+    /// checked world is used, as older worlds may still have a stale name
+    ///
+    /// # Arguments
+    /// * `worlds` - The stored worlds
+    /// * `new_worlds` - The incoming world data
+    ///
+    /// # Returns
+    /// A map of author ID to (old name, new name)
+    fn detect_author_renames(
+        worlds: &[WorldModel],
+        new_worlds: &[WorldApiData],
+    ) -> HashMap<String, (String, String)> {
+        let mut stored_names: HashMap<&str, (&str, chrono::DateTime<chrono::Utc>)> = HashMap::new();
+        for world in worlds {
+            let checked = world.user_data.last_checked;
+            stored_names
+                .entry(world.api_data.author_id.as_str())
+                .and_modify(|(name, last_checked)| {
+                    if checked > *last_checked {
+                        *name = world.api_data.author_name.as_str();
+                        *last_checked = checked;
+                    }
+                })
+                .or_insert((world.api_data.author_name.as_str(), checked));
+        }
+
+        let mut renamed = HashMap::new();
+        for new_world in new_worlds {
+            if let Some((old_name, _)) = stored_names.get(new_world.author_id.as_str()) {
+                if *old_name != new_world.author_name {
+                    renamed.insert(
+                        new_world.author_id.clone(),
+                        (old_name.to_string(), new_world.author_name.clone()),
+                    );
+                }
+            }
+        }
+        renamed
+    }
+
+    /// Maps author names which are known aliases to the author's current name
+    /// Used to keep starred author filters working after an author is renamed
+    ///
+    /// # Arguments
+    /// * `names` - The author names to resolve
+    /// * `worlds` - The list of worlds, as a RwLock
+    ///
+    /// # Returns
+    /// The resolved author names, without duplicates
+    ///
+    /// # Errors
+    /// Returns an error if the worlds lock is poisoned
+    pub fn resolve_author_aliases(
+        names: Vec<String>,
+        worlds: &RwLock<Vec<WorldModel>>,
+    ) -> Result<Vec<String>, AppError> {
+        let worlds_lock = worlds.read().map_err(|_| ConcurrencyError::PoisonedLock)?;
+        let custom_data = FileService::read_custom_data();
+
+        let mut seen = HashSet::new();
+        let mut resolved = vec![];
+        for name in names {
+            let current = custom_data
+                .find_author_by_alias(&name)
+                .and_then(|author_id| {
+                    worlds_lock
+                        .iter()
+                        .find(|w| &w.api_data.author_id == author_id)
+                })
+                .map(|w| w.api_data.author_name.clone())
+                .unwrap_or(name);
+            if seen.insert(current.clone()) {
+                resolved.push(current);
+            }
+        }
+        Ok(resolved)
+    }
+
     /// Completely delete a world
     /// This is done by removing the world from all folders, and deleting the world
     ///
@@ -959,47 +2002,25 @@ impl FolderManager {
         world_id: String,
         folders: &RwLock<Vec<FolderModel>>,
         worlds: &RwLock<Vec<WorldModel>>,
-    ) -> Result<(), AppError> {
-        let mut worlds_lock = worlds.write().map_err(|_| ConcurrencyError::PoisonedLock)?;
-        let world = worlds_lock
-            .iter()
-            .position(|w| w.api_data.world_id == world_id);
-        if world.is_none() {
-            return Err(EntityError::WorldNotFound(world_id).into());
-        }
-        let world_index = world.unwrap();
-        let world = worlds_lock.remove(world_index);
-        info!("Deleting world: {}", world.api_data.world_id);
-        FileService::write_worlds(&*worlds_lock)?;
-        drop(worlds_lock);
-
-        // First, collect the folder names that contain the world
-        let folders_to_update: Vec<String> = folders
-            .read()
-            .map_err(|_| ConcurrencyError::PoisonedLock)?
-            .iter()
-            .filter(|folder| folder.world_ids.contains(&world.api_data.world_id))
-            .map(|folder| folder.folder_name.clone())
-            .collect();
-
-        // Now, for each folder, remove the world from its world_ids
-        if !folders_to_update.is_empty() {
-            let mut folders_lock = folders
-                .write()
-                .map_err(|_| ConcurrencyError::PoisonedLock)?;
-            for folder_name in folders_to_update {
-                log::info!("Removing world from folder: {}", folder_name);
-                if let Some(folder) = folders_lock
-                    .iter_mut()
-                    .find(|f| f.folder_name == folder_name)
-                {
-                    if let Some(index) = folder.world_ids.iter().position(|id| id == &world_id) {
-                        folder.world_ids.remove(index);
-                    }
+    ) -> Result<(), AppError> {
+        let journal = Self::journal_library_write("delete_world")?;
+        Self::edit_library(journal, folders, worlds, |folders, worlds| {
+            let world_index = worlds
+                .iter()
+                .position(|w| w.api_data.world_id == world_id)
+                .ok_or_else(|| EntityError::WorldNotFound(world_id.clone()))?;
+            let world = worlds.remove(world_index);
+            info!("Deleting world: {}", world.api_data.world_id);
+
+            for folder in folders.iter_mut() {
+                if let Some(index) = folder.world_ids.iter().position(|id| id == &world_id) {
+                    log::info!("Removing world from folder: {}", folder.folder_name);
+                    folder.world_ids.remove(index);
                 }
             }
-            FileService::write_folders(&*folders_lock)?;
-        }
+            Ok(())
+        })?;
+        LibraryEvents::world_removed(world_id);
         Ok(())
     }
 
@@ -1129,7 +2150,10 @@ impl FolderManager {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::definitions::{AuthCookies, FolderModel, PreferenceModel, WorldModel};
+    use crate::definitions::{
+        AuthCookies, FolderModel, PreferenceModel, WorldAvailability, WorldModel,
+    };
+    use crate::errors::ValidationError;
     use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
     use std::sync::LazyLock;
     use std::sync::RwLock;
@@ -1179,12 +2203,62 @@ mod tests {
             visits: Some(0),
             favorites: 0,
             platform: vec!["platform".to_string()],
+            packages: vec![],
+            availability: WorldAvailability::Public,
         });
         let mut worlds_lock = worlds.write().map_err(|_| ConcurrencyError::PoisonedLock)?;
         worlds_lock.push(world);
         Ok(())
     }
 
+    #[test]
+    fn test_detect_author_renames() {
+        let state = setup_test_state();
+        add_test_world_to_state("wrld_1".to_string(), &state.worlds).unwrap();
+        let worlds_lock = state.worlds.read().unwrap();
+
+        let mut renamed_world = worlds_lock[0].api_data.clone();
+        renamed_world.world_id = "wrld_2".to_string();
+        renamed_world.author_name = "Renamed Author".to_string();
+        let unchanged_world = worlds_lock[0].api_data.clone();
+
+        let result = FolderManager::detect_author_renames(&worlds_lock, &[unchanged_world]);
+        assert!(result.is_empty());
+
+        let result = FolderManager::detect_author_renames(&worlds_lock, &[renamed_world]);
+        assert_eq!(
+            result.get("test_author"),
+            Some(&("Test Author".to_string(), "Renamed Author".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_detect_author_renames_uses_latest_name() {
+        let state = setup_test_state();
+        let stored = [("Oldest Name", 10), ("Current Name", 1), ("Old Name", 5)];
+        for (index, (name, days_ago)) in stored.iter().enumerate() {
+            add_test_world_to_state(format!("wrld_{}", index), &state.worlds).unwrap();
+            let mut worlds_lock = state.worlds.write().unwrap();
+            let world = worlds_lock.last_mut().unwrap();
+            world.api_data.author_name = name.to_string();
+            world.user_data.last_checked = chrono::Utc::now() - chrono::Duration::days(*days_ago);
+        }
+        let worlds_lock = state.worlds.read().unwrap();
+
+        let mut unchanged_world = worlds_lock[0].api_data.clone();
+        unchanged_world.author_name = "Current Name".to_string();
+        let result = FolderManager::detect_author_renames(&worlds_lock, &[unchanged_world]);
+        assert!(result.is_empty());
+
+        let mut renamed_world = worlds_lock[0].api_data.clone();
+        renamed_world.author_name = "New Name".to_string();
+        let result = FolderManager::detect_author_renames(&worlds_lock, &[renamed_world]);
+        assert_eq!(
+            result.get("test_author"),
+            Some(&("Current Name".to_string(), "New Name".to_string()))
+        );
+    }
+
     fn setup_test_state() -> TestState {
         TestState {
             preferences: RwLock::new(PreferenceModel::new()),
@@ -1194,6 +2268,72 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_add_worlds_reports_updated_worlds() {
+        let worlds = RwLock::new(vec![]);
+        add_test_world_to_state("wrld_updated".to_string(), &worlds).unwrap();
+        add_test_world_to_state("wrld_unchanged".to_string(), &worlds).unwrap();
+        let blacklist = RwLock::new(WorldBlacklist {
+            worlds: vec!["wrld_blocked".to_string()],
+            authors: vec![],
+        });
+
+        let saved = worlds.read().unwrap()[0].api_data.clone();
+        let fetched = |world_id: &str, days: i64| {
+            let mut data = saved.clone();
+            data.world_id = world_id.to_string();
+            data.world_name = "Renamed World".to_string();
+            data.last_update += chrono::Duration::days(days);
+            data
+        };
+        let updated = FolderManager::add_worlds(
+            &worlds,
+            vec![
+                fetched("wrld_updated", 1),
+                fetched("wrld_unchanged", 0),
+                fetched("wrld_new", 1),
+                fetched("wrld_blocked", 1),
+            ],
+            &blacklist,
+        )
+        .unwrap();
+
+        // Only saved worlds with a newer build are reported, not new or blacklisted ones
+        assert_eq!(updated.len(), 1);
+        assert_eq!(updated[0].world_id, "wrld_updated");
+        assert_eq!(updated[0].world_name, "Renamed World");
+        assert_eq!(updated[0].old_update, saved.last_update);
+        assert_eq!(
+            updated[0].new_update,
+            saved.last_update + chrono::Duration::days(1)
+        );
+
+        let worlds = worlds.read().unwrap();
+        let ids: Vec<&str> = worlds
+            .iter()
+            .map(|w| w.api_data.world_id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["wrld_updated", "wrld_unchanged", "wrld_new"]);
+    }
+
+    #[test]
+    fn test_get_worlds_by_id() {
+        let state = setup_test_state();
+        add_test_world_to_state("wrld_1".to_string(), &state.worlds).unwrap();
+        add_test_world_to_state("wrld_2".to_string(), &state.worlds).unwrap();
+
+        let ids = ["wrld_2", "wrld_missing", "wrld_1"].map(String::from);
+        let result = FolderManager::get_worlds_by_id(&ids, &state.worlds).unwrap();
+        let result_ids: Vec<_> = result.iter().map(|w| w.world_id.as_str()).collect();
+        assert_eq!(result_ids, ["wrld_2", "wrld_1"]);
+
+        let summaries =
+            FolderManager::get_world_summaries("All Worlds", &state.folders, &state.worlds)
+                .unwrap();
+        assert_eq!(summaries.len(), 2);
+        assert_eq!(summaries[0].author_name, "Test Author");
+    }
+
     #[test]
     fn test_increment_folder_name() {
         let state = setup_test_state();
@@ -1241,6 +2381,100 @@ mod tests {
         assert_eq!(result, "Test Folder (1)");
     }
 
+    #[test]
+    fn test_create_and_rename_folder_validate_names() {
+        let state = setup_test_state();
+        let result = FolderManager::create_folder("Line\nbreak".to_string(), &state.folders);
+        assert!(matches!(
+            result,
+            Err(AppError::Validation(ValidationError::InvalidFolderName(_)))
+        ));
+
+        let name = FolderManager::create_folder("Valid".to_string(), &state.folders).unwrap();
+        let result = FolderManager::rename_folder(
+            name,
+            " ".to_string(),
+            FolderNameCollision::Reject,
+            &state.folders,
+            &state.worlds,
+            &state.preferences,
+        );
+        assert!(result.is_err());
+        assert_eq!(state.folders.read().unwrap()[0].folder_name, "Valid");
+    }
+
+    #[test]
+    fn test_rename_folder_collision() {
+        let state = setup_test_state();
+        let world_a = "world_a".to_string();
+        let world_b = "world_b".to_string();
+        add_test_world_to_state(world_a.clone(), &state.worlds).unwrap();
+        add_test_world_to_state(world_b.clone(), &state.worlds).unwrap();
+        for name in ["Old", "Taken"] {
+            FolderManager::create_folder(name.to_string(), &state.folders).unwrap();
+        }
+        FolderManager::add_worlds_to_folder(
+            "Old".to_string(),
+            vec![world_a.clone(), world_b.clone()],
+            &state.folders,
+            &state.worlds,
+        )
+        .unwrap();
+        FolderManager::add_world_to_folder(
+            "Taken".to_string(),
+            world_b.clone(),
+            &state.folders,
+            &state.worlds,
+        )
+        .unwrap();
+        let rename = |old_name: &str, on_collision| {
+            FolderManager::rename_folder(
+                old_name.to_string(),
+                "Taken".to_string(),
+                on_collision,
+                &state.folders,
+                &state.worlds,
+                &state.preferences,
+            )
+        };
+
+        let result = rename("Old", FolderNameCollision::Reject);
+        assert!(matches!(
+            result,
+            Err(AppError::Entity(EntityError::DuplicateFolder(_)))
+        ));
+        // Renaming a folder to its own name is not a collision
+        assert_eq!(
+            rename("Taken", FolderNameCollision::Reject).unwrap(),
+            FolderRename::Renamed {
+                folder_name: "Taken".to_string()
+            }
+        );
+
+        let result = rename("Old", FolderNameCollision::Increment).unwrap();
+        assert_eq!(result.folder_name(), "Taken (1)");
+        assert_eq!(state.folders.read().unwrap().len(), 2);
+
+        let result = rename("Taken (1)", FolderNameCollision::Merge).unwrap();
+        assert_eq!(
+            result,
+            FolderRename::Merged {
+                folder_name: "Taken".to_string(),
+                world_ids: vec![world_a.clone(), world_b.clone()],
+                added: vec![world_a.clone()],
+            }
+        );
+        let folders = state.folders.read().unwrap();
+        assert_eq!(folders.len(), 1);
+        assert_eq!(folders[0].world_ids, vec![world_b.clone(), world_a.clone()]);
+        drop(folders);
+        for world_id in [world_a, world_b] {
+            let world_folders =
+                FolderManager::get_folders_for_world(world_id, &state.worlds).unwrap();
+            assert_eq!(world_folders, vec!["Taken"]);
+        }
+    }
+
     #[test]
     fn test_delete_folder() {
         let state = setup_test_state();
@@ -1260,6 +2494,31 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_delete_folder_with_worlds() {
+        let state = setup_test_state();
+        let world_id = "test_world".to_string();
+        add_test_world_to_state(world_id.clone(), &state.worlds).unwrap();
+        for name in ["Deleted", "Kept"] {
+            FolderManager::create_folder(name.to_string(), &state.folders).unwrap();
+            FolderManager::add_world_to_folder(
+                name.to_string(),
+                world_id.clone(),
+                &state.folders,
+                &state.worlds,
+            )
+            .unwrap();
+        }
+
+        FolderManager::delete_folder("Deleted".to_string(), &state.folders, &state.worlds).unwrap();
+        let folders = state.folders.read().unwrap();
+        assert_eq!(folders.len(), 1);
+        assert_eq!(folders[0].world_ids, vec![world_id.clone()]);
+        drop(folders);
+        let world_folders = FolderManager::get_folders_for_world(world_id, &state.worlds).unwrap();
+        assert_eq!(world_folders, vec!["Kept"]);
+    }
+
     #[test]
     fn test_add_world_to_folder() {
         let state = setup_test_state();
@@ -1309,6 +2568,147 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_move_world_between_folders() {
+        let state = setup_test_state();
+        let world_id = "test_world".to_string();
+        add_test_world_to_state(world_id.clone(), &state.worlds).unwrap();
+        let from = FolderManager::create_folder("From".to_string(), &state.folders).unwrap();
+        let to = FolderManager::create_folder("To".to_string(), &state.folders).unwrap();
+        FolderManager::add_world_to_folder(
+            from.clone(),
+            world_id.clone(),
+            &state.folders,
+            &state.worlds,
+        )
+        .unwrap();
+
+        // Asking without an answer keeps the world in the source folder
+        let change = FolderManager::move_world_between_folders(
+            world_id.clone(),
+            from.clone(),
+            to.clone(),
+            FolderRemovalPreference::Ask,
+            None,
+            &state.folders,
+            &state.worlds,
+        )
+        .unwrap();
+        assert_eq!(change.added_to, vec![to.clone()]);
+        assert!(change.removed_from.is_empty());
+
+        let change = FolderManager::move_world_between_folders(
+            world_id.clone(),
+            from.clone(),
+            to.clone(),
+            FolderRemovalPreference::AlwaysRemove,
+            None,
+            &state.folders,
+            &state.worlds,
+        )
+        .unwrap();
+        assert!(change.added_to.is_empty());
+        assert_eq!(change.removed_from, vec![from.clone()]);
+        let folders = state.folders.read().unwrap();
+        assert!(folders[0].world_ids.is_empty());
+        assert_eq!(folders[1].world_ids, vec![world_id.clone()]);
+        drop(folders);
+
+        // The world is no longer in the source folder
+        let result = FolderManager::move_world_between_folders(
+            world_id,
+            from,
+            to,
+            FolderRemovalPreference::AlwaysRemove,
+            None,
+            &state.folders,
+            &state.worlds,
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_set_world_folders() {
+        let state = setup_test_state();
+        let world_id = "test_world".to_string();
+        add_test_world_to_state(world_id.clone(), &state.worlds).unwrap();
+        for name in ["A", "B", "C"] {
+            FolderManager::create_folder(name.to_string(), &state.folders).unwrap();
+        }
+        FolderManager::add_world_to_folder(
+            "A".to_string(),
+            world_id.clone(),
+            &state.folders,
+            &state.worlds,
+        )
+        .unwrap();
+
+        let change = FolderManager::set_world_folders(
+            world_id.clone(),
+            vec!["B".to_string(), "C".to_string()],
+            &state.folders,
+            &state.worlds,
+        )
+        .unwrap();
+        assert_eq!(change.added_to, vec!["B", "C"]);
+        assert_eq!(change.removed_from, vec!["A"]);
+        let mut world_folders =
+            FolderManager::get_folders_for_world(world_id.clone(), &state.worlds).unwrap();
+        world_folders.sort();
+        assert_eq!(world_folders, vec!["B", "C"]);
+
+        // Nothing is changed if any of the folders does not exist
+        let result = FolderManager::set_world_folders(
+            world_id.clone(),
+            vec!["A".to_string(), "Missing".to_string()],
+            &state.folders,
+            &state.worlds,
+        );
+        assert!(result.is_err());
+        assert!(state.folders.read().unwrap()[0].world_ids.is_empty());
+    }
+
+    #[test]
+    fn test_move_world_in_folder() {
+        let state = setup_test_state();
+        let mut folder = FolderModel::new("Hop".to_string());
+        folder.world_ids = vec!["wrld_a".to_string(), "wrld_b".to_string(), "wrld_c".to_string()];
+        state.folders.write().unwrap().push(folder);
+
+        FolderManager::move_world_in_folder(
+            "Hop".to_string(),
+            "wrld_c".to_string(),
+            0,
+            &state.folders,
+        )
+        .unwrap();
+        assert_eq!(
+            state.folders.read().unwrap()[0].world_ids,
+            vec!["wrld_c", "wrld_a", "wrld_b"]
+        );
+
+        // Out of range indices move the world to the end
+        FolderManager::move_world_in_folder(
+            "Hop".to_string(),
+            "wrld_c".to_string(),
+            10,
+            &state.folders,
+        )
+        .unwrap();
+        assert_eq!(
+            state.folders.read().unwrap()[0].world_ids,
+            vec!["wrld_a", "wrld_b", "wrld_c"]
+        );
+
+        let result = FolderManager::move_world_in_folder(
+            "Hop".to_string(),
+            "wrld_missing".to_string(),
+            0,
+            &state.folders,
+        );
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_get_worlds() {
         let state = setup_test_state();
@@ -1321,6 +2721,195 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_get_worlds_with_folder_sort() {
+        let state = setup_test_state();
+        add_test_world_to_state("wrld_b".to_string(), &state.worlds).unwrap();
+        add_test_world_to_state("wrld_a".to_string(), &state.worlds).unwrap();
+        let mut folder = FolderModel::new("Sorted".to_string());
+        folder.world_ids = vec!["wrld_b".to_string(), "wrld_a".to_string()];
+        state.folders.write().unwrap().push(folder);
+
+        let invalid = FolderSort {
+            field: "size".to_string(),
+            direction: "asc".to_string(),
+        };
+        let result =
+            FolderManager::set_folder_sort("Sorted".to_string(), Some(invalid), &state.folders);
+        assert!(result.is_err());
+
+        let unsorted =
+            FolderManager::get_worlds("Sorted".to_string(), &state.folders, &state.worlds).unwrap();
+        assert_eq!(unsorted[0].world_id, "wrld_b");
+
+        // Both worlds share a name, so the id tiebreaker decides
+        state.folders.write().unwrap()[0].sort = Some(FolderSort {
+            field: "name".to_string(),
+            direction: "asc".to_string(),
+        });
+        let sorted =
+            FolderManager::get_worlds("Sorted".to_string(), &state.folders, &state.worlds).unwrap();
+        assert_eq!(sorted[0].world_id, "wrld_a");
+    }
+
+    #[test]
+    fn test_set_folder_sort() {
+        let state = setup_test_state();
+        state
+            .folders
+            .write()
+            .unwrap()
+            .push(FolderModel::new("Sorted".to_string()));
+        let sort = FolderSort {
+            field: "name".to_string(),
+            direction: "desc".to_string(),
+        };
+
+        let result = FolderManager::set_folder_sort(
+            "Missing".to_string(),
+            Some(sort.clone()),
+            &state.folders,
+        );
+        assert!(result.is_err());
+
+        FolderManager::set_folder_sort("Sorted".to_string(), Some(sort), &state.folders).unwrap();
+        let saved = state.folders.read().unwrap()[0].sort.clone().unwrap();
+        assert_eq!(saved.field, "name");
+        assert_eq!(saved.direction, "desc");
+
+        FolderManager::set_folder_sort("Sorted".to_string(), None, &state.folders).unwrap();
+        assert!(state.folders.read().unwrap()[0].sort.is_none());
+    }
+
+    #[test]
+    fn test_get_stale_folder_worlds() {
+        let state = setup_test_state();
+        add_test_world_to_state("wrld_stale".to_string(), &state.worlds).unwrap();
+        add_test_world_to_state("wrld_fresh".to_string(), &state.worlds).unwrap();
+        add_test_world_to_state("wrld_elsewhere".to_string(), &state.worlds).unwrap();
+        {
+            let mut worlds_lock = state.worlds.write().unwrap();
+            for world in worlds_lock.iter_mut() {
+                if world.api_data.world_id != "wrld_fresh" {
+                    world.user_data.last_checked = chrono::Utc::now() - chrono::Duration::days(1);
+                }
+            }
+        }
+        let mut folder = FolderModel::new("Event".to_string());
+        folder.world_ids = vec!["wrld_stale".to_string(), "wrld_fresh".to_string()];
+        state.folders.write().unwrap().push(folder);
+
+        let stale = FolderManager::get_stale_folder_worlds(
+            "Event".to_string(),
+            &state.folders,
+            &state.worlds,
+        )
+        .unwrap();
+        assert_eq!(stale.len(), 1);
+        assert_eq!(stale[0].world_id, "wrld_stale");
+
+        let result = FolderManager::get_stale_folder_worlds(
+            "Missing".to_string(),
+            &state.folders,
+            &state.worlds,
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_get_archived_only_world_ids() {
+        let state = setup_test_state();
+        let mut archived = FolderModel::new("Past Event".to_string());
+        archived.world_ids = vec!["wrld_retired".to_string(), "wrld_shared".to_string()];
+        archived.archived = true;
+        let mut active = FolderModel::new("Current".to_string());
+        active.world_ids = vec!["wrld_shared".to_string()];
+        state.folders.write().unwrap().extend([archived, active]);
+
+        let ids = FolderManager::get_archived_only_world_ids(&state.folders).unwrap();
+        assert_eq!(ids, HashSet::from(["wrld_retired".to_string()]));
+
+        let listed = FolderManager::get_folders(&state.folders, false).unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].name, "Current");
+        let all = FolderManager::get_folders(&state.folders, true).unwrap();
+        assert_eq!(all.len(), 2);
+    }
+
+    #[test]
+    fn test_folder_icon_and_description() {
+        let state = setup_test_state();
+        state
+            .folders
+            .write()
+            .unwrap()
+            .push(FolderModel::new("Icon Folder".to_string()));
+
+        FolderManager::set_folder_icon(
+            "Icon Folder".to_string(),
+            Some("🌙".to_string()),
+            &state.folders,
+        )
+        .unwrap();
+        FolderManager::set_folder_description(
+            "Icon Folder".to_string(),
+            Some("Worlds for late nights".to_string()),
+            &state.folders,
+        )
+        .unwrap();
+
+        // Both are kept through a rename
+        FolderManager::rename_folder(
+            "Icon Folder".to_string(),
+            "Renamed Icon Folder".to_string(),
+            FolderNameCollision::Reject,
+            &state.folders,
+            &state.worlds,
+            &state.preferences,
+        )
+        .unwrap();
+        let listed = FolderManager::get_folders(&state.folders, false).unwrap();
+        assert_eq!(listed[0].name, "Renamed Icon Folder");
+        assert_eq!(listed[0].icon.as_deref(), Some("🌙"));
+        assert_eq!(
+            listed[0].description.as_deref(),
+            Some("Worlds for late nights")
+        );
+
+        FolderManager::set_folder_icon("Renamed Icon Folder".to_string(), None, &state.folders)
+            .unwrap();
+        assert_eq!(state.folders.read().unwrap()[0].icon, None);
+
+        let result =
+            FolderManager::set_folder_description("Icon Folder".to_string(), None, &state.folders);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_set_folder_archived() {
+        let state = setup_test_state();
+        state
+            .folders
+            .write()
+            .unwrap()
+            .push(FolderModel::new("Past Event".to_string()));
+
+        // The previous state is returned, so the change can be recorded or undone
+        let was_archived =
+            FolderManager::set_folder_archived("Past Event".to_string(), true, &state.folders)
+                .unwrap();
+        assert!(!was_archived);
+        assert!(state.folders.read().unwrap()[0].archived);
+        let was_archived =
+            FolderManager::set_folder_archived("Past Event".to_string(), true, &state.folders)
+                .unwrap();
+        assert!(was_archived);
+
+        let result =
+            FolderManager::set_folder_archived("Missing".to_string(), true, &state.folders);
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_get_world() {
         let state = setup_test_state();
@@ -1341,6 +2930,127 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_get_unclassified_worlds_skips_inbox() {
+        let state = setup_test_state();
+        add_test_world_to_state("wrld_kept".to_string(), &state.worlds).unwrap();
+        add_test_world_to_state("wrld_inbox".to_string(), &state.worlds).unwrap();
+        state.worlds.write().unwrap()[1].user_data.in_inbox = true;
+
+        let unclassified = FolderManager::get_unclassified_worlds(&state.worlds).unwrap();
+        assert_eq!(unclassified.len(), 1);
+        assert_eq!(unclassified[0].world_id, "wrld_kept");
+    }
+
+    #[test]
+    fn test_set_worlds_flag() {
+        let state = setup_test_state();
+        add_test_world_to_state("wrld_1".to_string(), &state.worlds).unwrap();
+        add_test_world_to_state("wrld_2".to_string(), &state.worlds).unwrap();
+        FolderManager::set_world_favorite("wrld_2".to_string(), true, &state.worlds).unwrap();
+
+        let ids = vec![
+            "wrld_1".to_string(),
+            "wrld_2".to_string(),
+            "wrld_1".to_string(),
+            "wrld_missing".to_string(),
+        ];
+        let summary =
+            FolderManager::set_worlds_flag(&ids, WorldFlag::Favorite, true, &state.worlds).unwrap();
+        assert_eq!(summary.changed, vec!["wrld_1".to_string()]);
+        assert_eq!(summary.unchanged, 1);
+        assert_eq!(summary.not_found, vec!["wrld_missing".to_string()]);
+        assert!(state
+            .worlds
+            .read()
+            .unwrap()
+            .iter()
+            .all(|w| w.user_data.is_favorite));
+    }
+
+    #[test]
+    fn test_set_world_rating() {
+        let state = setup_test_state();
+        add_test_world_to_state("wrld_rated".to_string(), &state.worlds).unwrap();
+
+        for rating in [0, 6] {
+            let result = FolderManager::set_world_rating(
+                "wrld_rated".to_string(),
+                Some(rating),
+                &state.worlds,
+            );
+            assert!(result.is_err());
+        }
+        let result =
+            FolderManager::set_world_rating("wrld_missing".to_string(), Some(3), &state.worlds);
+        assert!(result.is_err());
+
+        FolderManager::set_world_rating("wrld_rated".to_string(), Some(5), &state.worlds).unwrap();
+        assert_eq!(state.worlds.read().unwrap()[0].user_data.rating, Some(5));
+        FolderManager::set_world_rating("wrld_rated".to_string(), None, &state.worlds).unwrap();
+        assert_eq!(state.worlds.read().unwrap()[0].user_data.rating, None);
+    }
+
+    #[test]
+    fn test_get_folder_stats() {
+        let state = setup_test_state();
+        let folder_name = "Stats Folder".to_string();
+        FolderManager::create_folder(folder_name.clone(), &state.folders).unwrap();
+        for id in ["wrld_stats_1", "wrld_stats_2", "wrld_stats_3"] {
+            add_test_world_to_state(id.to_string(), &state.worlds).unwrap();
+            FolderManager::add_world_to_folder(
+                folder_name.clone(),
+                id.to_string(),
+                &state.folders,
+                &state.worlds,
+            )
+            .unwrap();
+        }
+        {
+            let mut worlds_lock = state.worlds.write().unwrap();
+            for (i, world) in worlds_lock.iter_mut().enumerate() {
+                world.api_data.capacity = 8 * (i as i32 + 1);
+                world.api_data.tags = vec!["author_tag_horror".to_string()];
+                world.user_data.date_added =
+                    chrono::DateTime::from_timestamp(1_700_000_000 + i as i64, 0).unwrap();
+            }
+            worlds_lock[0].api_data.platform =
+                vec!["standalonewindows".to_string(), "android".to_string()];
+            worlds_lock[1]
+                .api_data
+                .tags
+                .push("author_tag_chill".to_string());
+            worlds_lock[2].user_data.launch_count = 2;
+        }
+
+        let stats =
+            FolderManager::get_folder_stats(folder_name, &state.folders, &state.worlds).unwrap();
+        assert_eq!(stats.world_count, 3);
+        assert_eq!(
+            (stats.pc_only, stats.quest_only, stats.cross_platform),
+            (2, 0, 1)
+        );
+        assert_eq!(stats.total_capacity, 48);
+        assert_eq!(stats.average_capacity, Some(16.0));
+        assert_eq!(
+            stats.tags,
+            vec![
+                TagCount {
+                    tag: "horror".to_string(),
+                    count: 3
+                },
+                TagCount {
+                    tag: "chill".to_string(),
+                    count: 1
+                },
+            ]
+        );
+        assert_eq!(stats.oldest_added.unwrap().world_id, "wrld_stats_1");
+        assert_eq!(stats.newest_added.unwrap().world_id, "wrld_stats_3");
+        assert_eq!(stats.not_visited, 2);
+        assert!(stats.share.is_none());
+    }
+
     #[test]
     fn test_delete_world() {
         let state = setup_test_state();
@@ -0,0 +1,223 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::Utc;
+use reqwest::cookie::Jar;
+
+use crate::api::world::ReleaseStatus;
+use crate::app_state::AppState;
+use crate::definitions::{GroupFolderLink, GroupFolderSyncResult, LibraryChange};
+use crate::errors::{AppError, EntityError};
+use crate::{AUTHENTICATOR, INITSTATE};
+
+use super::{
+    ApiService, BackgroundJobs, ChangeFeed, ConcurrentFetcher, FileService, FolderManager,
+    GuestMode,
+};
+
+/// How often linked folders are synced with their groups
+const AUTO_SYNC_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+
+/// Service for folders kept filled with the worlds of a VRChat group
+///
+/// VRChat does not expose a group's world list as such, so the worlds are taken from the
+/// instances the group hosts. Syncing only adds worlds, so the folder builds up every world the
+/// group has hosted since it was linked, and worlds removed by the user are added back only if
+/// the group hosts them again
+#[derive(Debug)]
+pub struct GroupFolderService;
+
+impl GroupFolderService {
+    /// Gets the linked folders
+    ///
+    /// # Returns
+    /// Map of folder name -> the group it is filled from
+    pub fn links() -> HashMap<String, GroupFolderLink> {
+        FileService::read_custom_data().group_folders
+    }
+
+    /// Links a folder to a group, creating the folder if it does not exist
+    ///
+    /// # Arguments
+    /// * `folder_name` - The folder to fill, or None for one named after the group
+    /// * `group_id` - The ID of the group
+    /// * `group_name` - The name of the group, for display
+    /// * `state` - The app state holding the library
+    ///
+    /// # Returns
+    /// The name of the linked folder
+    ///
+    /// # Errors
+    /// Returns an error if the folder could not be created
+    /// Returns an error if the link could not be saved
+    pub fn link(
+        folder_name: Option<String>,
+        group_id: String,
+        group_name: String,
+        state: &AppState,
+    ) -> Result<String, AppError> {
+        let folder_name = folder_name.unwrap_or_else(|| group_name.clone());
+        let exists = FolderManager::get_folders(state.folder_store(), true)?
+            .iter()
+            .any(|f| f.name == folder_name);
+        let folder_name = if exists {
+            folder_name
+        } else {
+            let folder_name = FolderManager::create_folder(folder_name, state.folder_store())?;
+            ChangeFeed::record(LibraryChange::FolderCreated {
+                folder_name: folder_name.clone(),
+            });
+            folder_name
+        };
+
+        let mut custom_data = FileService::read_custom_data();
+        custom_data.group_folders.insert(
+            folder_name.clone(),
+            GroupFolderLink {
+                group_id,
+                group_name,
+                last_synced: None,
+            },
+        );
+        FileService::write_custom_data(&custom_data)?;
+        Ok(folder_name)
+    }
+
+    /// Stops filling a folder from its group, the worlds already in it are kept
+    ///
+    /// # Errors
+    /// Returns an error if the folder is not linked
+    /// Returns an error if the change could not be saved
+    pub fn unlink(folder_name: &str) -> Result<(), AppError> {
+        let mut custom_data = FileService::read_custom_data();
+        if custom_data.group_folders.remove(folder_name).is_none() {
+            return Err(EntityError::FolderNotFound(folder_name.to_string()).into());
+        }
+        FileService::write_custom_data(&custom_data)?;
+        Ok(())
+    }
+
+    /// Adds the worlds the group is hosting instances of to its linked folder
+    /// Worlds which are not in the library are fetched in full; non-public and blacklisted
+    /// worlds are skipped
+    ///
+    /// # Arguments
+    /// * `folder_name` - The linked folder
+    /// * `cookie_store` - The cookie store to use for the API
+    /// * `state` - The app state holding the library
+    ///
+    /// # Errors
+    /// Returns an error if the folder is not linked
+    /// Returns an error if the group's instances could not be fetched
+    /// Returns an error if the folder could not be saved
+    pub async fn sync(
+        folder_name: &str,
+        cookie_store: Arc<Jar>,
+        state: &AppState,
+    ) -> Result<GroupFolderSyncResult, AppError> {
+        let link = Self::links()
+            .remove(folder_name)
+            .ok_or_else(|| EntityError::FolderNotFound(folder_name.to_string()))?;
+
+        let world_ids =
+            ApiService::get_group_world_ids(cookie_store.clone(), &link.group_id).await?;
+        let missing = ChangeFeed::missing_worlds(state.world_store(), world_ids.clone())?;
+
+        let mut fetcher = ConcurrentFetcher::new();
+        for world_id in missing.clone() {
+            let cookie_store = cookie_store.clone();
+            fetcher.spawn(async move {
+                let result =
+                    ApiService::get_world_with_release_status(cookie_store, &world_id).await;
+                (world_id, result)
+            });
+        }
+        let mut fetched = vec![];
+        for (world_id, result) in fetcher.collect().await {
+            match result {
+                Ok((world, ReleaseStatus::Public)) => fetched.push(world),
+                Ok(_) => log::info!("Skipping non-public group world {}", world_id),
+                Err(e) => log::warn!("Failed to fetch group world {}: {}", world_id, e),
+            }
+        }
+        FolderManager::add_worlds(state.world_store(), fetched, state.blacklist_store())?;
+        ChangeFeed::record_worlds_added(state.world_store(), missing);
+
+        // Worlds which could not be fetched or are blacklisted are not in the library
+        let (known, in_folder): (Vec<String>, Vec<String>) = {
+            let worlds = state.worlds()?;
+            let known = world_ids
+                .iter()
+                .filter(|id| worlds.iter().any(|w| &w.api_data.world_id == *id))
+                .cloned()
+                .collect();
+            let in_folder = worlds
+                .iter()
+                .filter(|w| w.user_data.folders.iter().any(|f| f == folder_name))
+                .map(|w| w.api_data.world_id.clone())
+                .collect();
+            (known, in_folder)
+        };
+        let new_ids: Vec<String> = known
+            .into_iter()
+            .filter(|id| !in_folder.contains(id))
+            .collect();
+        if !new_ids.is_empty() {
+            FolderManager::add_worlds_to_folder(
+                folder_name.to_string(),
+                new_ids.clone(),
+                state.folder_store(),
+                state.world_store(),
+            )?;
+            ChangeFeed::record(LibraryChange::AddedToFolder {
+                folder_name: folder_name.to_string(),
+                world_ids: new_ids.clone(),
+            });
+        }
+
+        let mut custom_data = FileService::read_custom_data();
+        if let Some(link) = custom_data.group_folders.get_mut(folder_name) {
+            link.last_synced = Some(Utc::now());
+            FileService::write_custom_data(&custom_data)?;
+        }
+
+        log::info!(
+            "Synced folder {} with group {}: {} worlds found, {} added",
+            folder_name,
+            link.group_name,
+            world_ids.len(),
+            new_ids.len()
+        );
+        Ok(GroupFolderSyncResult {
+            folder_name: folder_name.to_string(),
+            worlds_found: world_ids.len() as u32,
+            worlds_added: new_ids.len() as u32,
+        })
+    }
+
+    /// Periodically syncs every linked folder
+    /// Syncs are skipped while background jobs are paused, in guest mode or logged out
+    ///
+    /// # Arguments
+    /// * `state` - The app state holding the library
+    pub async fn run_auto_sync(state: AppState) {
+        loop {
+            tokio::time::sleep(AUTO_SYNC_INTERVAL).await;
+
+            if BackgroundJobs::is_paused()
+                || GuestMode::is_enabled()
+                || INITSTATE.get().read().await.user_id.is_empty()
+            {
+                continue;
+            }
+
+            for folder_name in Self::links().into_keys() {
+                let cookie_store = AUTHENTICATOR.get().read().await.get_cookies();
+                if let Err(e) = Self::sync(&folder_name, cookie_store, &state).await {
+                    log::warn!("Failed to sync group folder {}: {}", folder_name, e);
+                }
+            }
+        }
+    }
+}
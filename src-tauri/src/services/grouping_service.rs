@@ -0,0 +1,276 @@
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use crate::definitions::{
+    FolderModel, Platform, WorldDisplayData, WorldGroup, WorldGroupBy, WorldModel,
+};
+use crate::errors::{AppError, ConcurrencyError};
+use crate::services::{FolderManager, SortingService};
+
+/// Sources named like the frontend's special folders, any other source is a folder name
+const ALL_WORLDS: &str = "All Worlds";
+const UNCLASSIFIED_WORLDS: &str = "Unclassified Worlds";
+const HIDDEN_WORLDS: &str = "Hidden Worlds";
+
+/// Service for grouping the worlds of the grid into sections, so the frontend does not have to
+/// receive and regroup every world itself
+#[derive(Debug)]
+pub struct GroupingService;
+
+impl GroupingService {
+    /// Gets the worlds of a source, grouped into ordered sections
+    ///
+    /// # Arguments
+    /// * `source` - "All Worlds", "Unclassified Worlds", "Hidden Worlds" or a folder name
+    /// * `group_by` - What to group the worlds by
+    /// * `folders` - The list of folders, as a RwLock
+    /// * `worlds` - The list of worlds, as a RwLock
+    ///
+    /// # Returns
+    /// The groups in display order, each with its worlds in the order of the source
+    ///
+    /// # Errors
+    /// Returns an error if the source is not a special folder and the folder is not found
+    /// Returns an error if any lock is poisoned
+    pub fn get_worlds_grouped(
+        source: &str,
+        group_by: WorldGroupBy,
+        folders: &RwLock<Vec<FolderModel>>,
+        worlds: &RwLock<Vec<WorldModel>>,
+    ) -> Result<Vec<WorldGroup>, AppError> {
+        let source_worlds = Self::get_source_worlds(source, folders, worlds)?;
+        let folder_order: Vec<String> = {
+            let folders_lock = folders.read().map_err(|_| ConcurrencyError::PoisonedLock)?;
+            folders_lock.iter().map(|f| f.folder_name.clone()).collect()
+        };
+        Ok(Self::group_worlds(source_worlds, group_by, &folder_order))
+    }
+
+    /// Gets the worlds of a source, in the order of the source
+    ///
+    /// # Arguments
+    /// * `source` - "All Worlds", "Unclassified Worlds", "Hidden Worlds" or a folder name
+    /// * `folders` - The list of folders, as a RwLock
+    /// * `worlds` - The list of worlds, as a RwLock
+    ///
+    /// # Errors
+    /// Returns an error if the source is not a special folder and the folder is not found
+    /// Returns an error if any lock is poisoned
+    pub fn get_source_worlds(
+        source: &str,
+        folders: &RwLock<Vec<FolderModel>>,
+        worlds: &RwLock<Vec<WorldModel>>,
+    ) -> Result<Vec<WorldDisplayData>, AppError> {
+        match source {
+            ALL_WORLDS => FolderManager::get_all_worlds(worlds),
+            UNCLASSIFIED_WORLDS => FolderManager::get_unclassified_worlds(worlds),
+            HIDDEN_WORLDS => FolderManager::get_hidden_worlds(worlds),
+            folder_name => FolderManager::get_worlds(folder_name.to_string(), folders, worlds),
+        }
+    }
+
+    /// Groups worlds, keeping their order within each group
+    ///
+    /// Author, platform, letter and tag groups are ordered by name, folder groups in the
+    /// user's folder order, and month groups newest first
+    ///
+    /// # Arguments
+    /// * `worlds` - The worlds to group
+    /// * `group_by` - What to group the worlds by
+    /// * `folder_order` - Every folder name, in the user's order
+    pub fn group_worlds(
+        worlds: Vec<WorldDisplayData>,
+        group_by: WorldGroupBy,
+        folder_order: &[String],
+    ) -> Vec<WorldGroup> {
+        let mut groups: Vec<WorldGroup> = vec![];
+        let mut indices: HashMap<String, usize> = HashMap::new();
+        for world in worlds {
+            for (key, label) in Self::group_keys(&world, group_by) {
+                let index = *indices.entry(key.clone()).or_insert_with(|| {
+                    groups.push(WorldGroup {
+                        key,
+                        label,
+                        count: 0,
+                        worlds: vec![],
+                    });
+                    groups.len() - 1
+                });
+                groups[index].count += 1;
+                groups[index].worlds.push(world.clone());
+            }
+        }
+
+        let folder_position = |name: &str| {
+            folder_order
+                .iter()
+                .position(|f| f == name)
+                .unwrap_or(usize::MAX)
+        };
+        groups.sort_by(|a, b| {
+            a.key
+                .is_empty()
+                .cmp(&b.key.is_empty())
+                .then_with(|| match group_by {
+                    WorldGroupBy::AddedMonth => b.key.cmp(&a.key),
+                    WorldGroupBy::Folder => folder_position(&a.key).cmp(&folder_position(&b.key)),
+                    _ => SortingService::cmp_case_insensitive(&a.label, &b.label),
+                })
+        });
+        groups
+    }
+
+    /// The keys and labels of the groups a world is in, a single empty key if there are none
+    fn group_keys(world: &WorldDisplayData, group_by: WorldGroupBy) -> Vec<(String, String)> {
+        let mut keys: Vec<String> = match group_by {
+            WorldGroupBy::Author => {
+                return vec![(world.author_id.clone(), world.author_name.clone())];
+            }
+            WorldGroupBy::Platform => vec![match world.platform {
+                Platform::PC => "PC",
+                Platform::Quest => "Quest",
+                Platform::CrossPlatform => "Cross-Platform",
+            }
+            .to_string()],
+            WorldGroupBy::Folder => world.folders.clone(),
+            WorldGroupBy::FirstLetter => vec![Self::first_letter(&world.name)],
+            // Dates are RFC 3339, so the month is the first 7 characters
+            WorldGroupBy::AddedMonth => vec![world.date_added.chars().take(7).collect()],
+            WorldGroupBy::Tag => world
+                .tags
+                .iter()
+                .filter_map(|tag| tag.strip_prefix("author_tag_"))
+                .map(str::to_string)
+                .collect(),
+        };
+        keys.sort();
+        keys.dedup();
+        if keys.is_empty() {
+            return vec![(String::new(), String::new())];
+        }
+        keys.into_iter().map(|key| (key.clone(), key)).collect()
+    }
+
+    /// The uppercased first letter of a name, "#" for names starting with anything else
+    fn first_letter(name: &str) -> String {
+        match SortingService::normalize_for_sorting(name).chars().next() {
+            Some(c) if c.is_alphabetic() => c.to_uppercase().collect(),
+            _ => "#".to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::definitions::WorldAvailability;
+    use crate::errors::EntityError;
+    use crate::services::sorting_service::make_test_world;
+
+    fn make_world(id: &str, name: &str, date_added: &str, tags: &[&str]) -> WorldDisplayData {
+        WorldDisplayData {
+            world_id: id.to_string(),
+            name: name.to_string(),
+            thumbnail_url: "".to_string(),
+            author_name: "Test Author".to_string(),
+            author_id: "usr_test".to_string(),
+            favorites: 0,
+            last_updated: "".to_string(),
+            visits: 0,
+            date_added: date_added.to_string(),
+            platform: Platform::PC,
+            folders: vec![],
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            capacity: 0,
+            is_photographed: false,
+            is_shared: false,
+            is_favorite: false,
+            is_event_critical: false,
+            has_memo: false,
+            rating: None,
+            local_visit_count: 0,
+            pc_download_size: None,
+            quest_download_size: None,
+            availability: WorldAvailability::Public,
+        }
+    }
+
+    fn summary(groups: &[WorldGroup]) -> Vec<(&str, usize)> {
+        groups.iter().map(|g| (g.key.as_str(), g.count)).collect()
+    }
+
+    #[test]
+    fn test_group_worlds() {
+        let worlds = vec![
+            make_world(
+                "wrld_1",
+                "beach",
+                "2025-01-05T00:00:00.000Z",
+                &["author_tag_game", "system_approved"],
+            ),
+            make_world(
+                "wrld_2",
+                "Avatar",
+                "2025-03-01T00:00:00.000Z",
+                &["author_tag_chill", "author_tag_game"],
+            ),
+            make_world("wrld_3", "2nd Street", "2025-01-20T00:00:00.000Z", &[]),
+        ];
+
+        let by_tag = GroupingService::group_worlds(worlds.clone(), WorldGroupBy::Tag, &[]);
+        assert_eq!(summary(&by_tag), vec![("chill", 1), ("game", 2), ("", 1)]);
+        assert_eq!(by_tag[1].worlds[0].world_id, "wrld_1");
+
+        let by_month = GroupingService::group_worlds(worlds.clone(), WorldGroupBy::AddedMonth, &[]);
+        assert_eq!(summary(&by_month), vec![("2025-03", 1), ("2025-01", 2)]);
+
+        let by_letter = GroupingService::group_worlds(worlds, WorldGroupBy::FirstLetter, &[]);
+        assert_eq!(summary(&by_letter), vec![("#", 1), ("A", 1), ("B", 1)]);
+    }
+
+    #[test]
+    fn test_get_worlds_grouped() {
+        let mut filed = make_test_world("wrld_filed");
+        filed.user_data.folders = vec!["A".to_string(), "B".to_string()];
+        let mut hidden = make_test_world("wrld_hidden");
+        hidden.user_data.hidden = true;
+        hidden.api_data.author_id = "usr_hidden".to_string();
+        hidden.api_data.author_name = "Hidden Author".to_string();
+        let worlds = RwLock::new(vec![filed, hidden, make_test_world("wrld_unfiled")]);
+        let mut folder_a = FolderModel::new("A".to_string());
+        folder_a.world_ids = vec!["wrld_filed".to_string()];
+        let folders = RwLock::new(vec![FolderModel::new("B".to_string()), folder_a]);
+
+        // Folder groups follow the user's folder order, worlds in no folder come last
+        let by_folder = GroupingService::get_worlds_grouped(
+            ALL_WORLDS,
+            WorldGroupBy::Folder,
+            &folders,
+            &worlds,
+        )
+        .unwrap();
+        assert_eq!(summary(&by_folder), vec![("B", 1), ("A", 1), ("", 1)]);
+
+        let by_author = GroupingService::get_worlds_grouped(
+            HIDDEN_WORLDS,
+            WorldGroupBy::Author,
+            &folders,
+            &worlds,
+        )
+        .unwrap();
+        assert_eq!(summary(&by_author), vec![("usr_hidden", 1)]);
+        assert_eq!(by_author[0].label, "Hidden Author");
+
+        let in_folder =
+            GroupingService::get_worlds_grouped("A", WorldGroupBy::Author, &folders, &worlds)
+                .unwrap();
+        assert_eq!(in_folder[0].worlds[0].world_id, "wrld_filed");
+
+        let result =
+            GroupingService::get_worlds_grouped("Missing", WorldGroupBy::Author, &folders, &worlds);
+        assert!(matches!(
+            result,
+            Err(AppError::Entity(EntityError::FolderNotFound(_)))
+        ));
+    }
+}
@@ -0,0 +1,314 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::Argon2;
+
+use crate::definitions::GuestModeStatus;
+use crate::errors::{AppError, EntityError};
+
+use super::FileService;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+const MIN_PIN_LEN: usize = 4;
+const MAX_PIN_LEN: usize = 8;
+const SALT_LEN: usize = 16;
+
+/// Incorrect PINs allowed before each further attempt has to wait
+const FREE_ATTEMPTS: u32 = 3;
+/// Wait after the first attempt over `FREE_ATTEMPTS`, doubled for each one after it
+const ATTEMPT_BASE_DELAY: Duration = Duration::from_secs(5);
+const MAX_ATTEMPT_DELAY: Duration = Duration::from_secs(300);
+
+#[derive(Debug, Default)]
+struct FailedAttempts {
+    /// Incorrect PINs in a row
+    count: u32,
+    /// When the next attempt is allowed, None if right away
+    retry_at: Option<Instant>,
+}
+
+static FAILED_ATTEMPTS: Mutex<FailedAttempts> = Mutex::new(FailedAttempts {
+    count: 0,
+    retry_at: None,
+});
+
+/// Commands guests may use: reading the library and the preferences, and leaving guest mode
+/// Every other command is rejected by the invoke handler, so new commands are read-only
+/// for guests unless they are added here. Hidden worlds stay hidden
+const GUEST_COMMANDS: &[&str] = &[
+    "get_guest_mode_status",
+    "leave_guest_mode",
+    "require_initial_setup",
+    "check_files_loaded",
+    "get_startup_deep_link",
+    "take_startup_deep_link_result",
+    "get_auth_state",
+    "get_library_encryption_status",
+    "get_changelog",
+    "get_changelog_since",
+    "get_task_status",
+    "list_active_tasks",
+    "get_task_error",
+    "get_task_progress",
+    "get_folders",
+    "open_folder_window",
+    "get_worlds",
+    "get_worlds_grouped",
+    "get_all_worlds",
+    "get_world_summaries",
+    "get_worlds_by_id",
+    "get_worlds_changed_since",
+    "get_unclassified_worlds",
+    "get_inbox_worlds",
+    "get_folder_stats",
+    "get_folders_for_world",
+    "get_tags_by_count",
+    "get_tag_rules",
+    "get_authors_by_count",
+    "get_cached_thumbnail",
+    "get_similar_worlds",
+    "get_recommendations",
+    "get_locked_world_fields",
+    "get_memo",
+    "search_memo_text",
+    "get_world_photos",
+    "get_photo_counts",
+    "get_playlist",
+    "get_all_preferences",
+    "get_theme",
+    "get_language",
+    "get_card_size",
+    "get_region",
+    "get_starred_filter_items",
+    "get_folder_removal_preference",
+    "get_update_channel",
+    "get_sort_preferences",
+    "get_natural_sort",
+    "get_keep_non_public_favorites",
+    "get_default_instance_type",
+    "get_default_search_platform",
+    "get_visible_buttons",
+    "get_tray_settings",
+    "get_pinned_world",
+    "get_worlds_storage",
+    "sort_worlds_display",
+    "filter_worlds_by_download_size",
+    "parse_world_reference",
+    "generate_instance_links",
+];
+
+/// Read-only mode for showing the library on a shared screen
+///
+/// While enabled, every command not in `GUEST_COMMANDS` is rejected with
+/// `CommandError::GuestMode`, commands needing the VRChat account fail as if logged out,
+/// and background jobs are held. Leaving guest mode needs the PIN from the preferences,
+/// and the mode is kept across restarts so closing the app does not leave it
+#[derive(Debug)]
+pub struct GuestMode;
+
+impl GuestMode {
+    /// Reads whether the app was left in guest mode, called once on startup
+    pub fn init() {
+        let enabled = FileService::read_custom_data()
+            .preferences
+            .guest_mode
+            .unwrap_or(false);
+        if enabled {
+            log::info!("Starting in guest mode");
+        }
+        ENABLED.store(enabled, Ordering::Relaxed);
+    }
+
+    pub fn is_enabled() -> bool {
+        ENABLED.load(Ordering::Relaxed)
+    }
+
+    pub fn status() -> GuestModeStatus {
+        GuestModeStatus {
+            enabled: Self::is_enabled(),
+            pin_set: FileService::read_custom_data()
+                .preferences
+                .guest_mode_pin
+                .is_some(),
+        }
+    }
+
+    /// Returns true if the command may be invoked, always the case outside guest mode
+    pub fn allows(command: &str) -> bool {
+        !Self::is_enabled() || GUEST_COMMANDS.contains(&command)
+    }
+
+    /// Sets the PIN which leaves guest mode
+    ///
+    /// # Arguments
+    /// * `current_pin` - The PIN set before, if any
+    /// * `pin` - The new PIN, 4 to 8 digits
+    ///
+    /// # Errors
+    /// Returns an error if the new PIN is not 4 to 8 digits
+    /// Returns an error if a PIN is set and `current_pin` does not match it, or too many
+    /// incorrect PINs were entered recently
+    /// Returns an error if the PIN could not be saved
+    pub fn set_pin(current_pin: Option<&str>, pin: &str) -> Result<(), AppError> {
+        if !(MIN_PIN_LEN..=MAX_PIN_LEN).contains(&pin.len())
+            || !pin.chars().all(|c| c.is_ascii_digit())
+        {
+            return Err(EntityError::InvalidOperation(format!(
+                "The PIN must be {} to {} digits",
+                MIN_PIN_LEN, MAX_PIN_LEN
+            ))
+            .into());
+        }
+
+        let mut custom_data = FileService::read_custom_data();
+        if let Some(hash) = &custom_data.preferences.guest_mode_pin {
+            Self::verify_pin(current_pin.unwrap_or_default(), hash)?;
+        }
+        custom_data.preferences.guest_mode_pin = Some(Self::hash_pin(pin)?);
+        FileService::write_custom_data(&custom_data)?;
+        Ok(())
+    }
+
+    /// Switches the app into guest mode
+    ///
+    /// # Errors
+    /// Returns an error if no PIN is set, as guest mode could not be left again
+    /// Returns an error if the mode could not be saved
+    pub fn enter() -> Result<(), AppError> {
+        let mut custom_data = FileService::read_custom_data();
+        if custom_data.preferences.guest_mode_pin.is_none() {
+            return Err(EntityError::InvalidOperation(
+                "Set a PIN before entering guest mode".to_string(),
+            )
+            .into());
+        }
+        custom_data.preferences.guest_mode = Some(true);
+        FileService::write_custom_data(&custom_data)?;
+        ENABLED.store(true, Ordering::Relaxed);
+        log::info!("Entered guest mode");
+        Ok(())
+    }
+
+    /// Leaves guest mode
+    ///
+    /// # Arguments
+    /// * `pin` - The PIN from the preferences
+    ///
+    /// # Errors
+    /// Returns an error if the PIN is incorrect, or too many incorrect PINs were entered
+    /// recently
+    /// Returns an error if the mode could not be saved
+    pub fn leave(pin: &str) -> Result<(), AppError> {
+        let mut custom_data = FileService::read_custom_data();
+        if let Some(hash) = &custom_data.preferences.guest_mode_pin {
+            Self::verify_pin(pin, hash)?;
+        }
+        custom_data.preferences.guest_mode = None;
+        FileService::write_custom_data(&custom_data)?;
+        ENABLED.store(false, Ordering::Relaxed);
+        log::info!("Left guest mode");
+        Ok(())
+    }
+
+    /// Checks a PIN, making each attempt after `FREE_ATTEMPTS` incorrect ones wait longer
+    /// so the few possible PINs can't simply be tried one after another
+    fn verify_pin(pin: &str, hash: &str) -> Result<(), AppError> {
+        let mut failed = FAILED_ATTEMPTS
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        Self::verify_pin_with(&mut failed, pin, hash)
+    }
+
+    /// Checks a PIN like `verify_pin`, counting incorrect ones in `failed`
+    fn verify_pin_with(failed: &mut FailedAttempts, pin: &str, hash: &str) -> Result<(), AppError> {
+        let wait = failed
+            .retry_at
+            .and_then(|retry_at| retry_at.checked_duration_since(Instant::now()));
+        if let Some(wait) = wait {
+            return Err(EntityError::InvalidOperation(format!(
+                "Too many incorrect PINs. Try again in {} seconds",
+                wait.as_secs() + 1
+            ))
+            .into());
+        }
+
+        let correct = PasswordHash::new(hash)
+            .map(|hash| {
+                Argon2::default()
+                    .verify_password(pin.trim().as_bytes(), &hash)
+                    .is_ok()
+            })
+            .unwrap_or(false);
+        if !correct {
+            failed.count += 1;
+            if failed.count > FREE_ATTEMPTS {
+                let delay = ATTEMPT_BASE_DELAY
+                    .saturating_mul(1 << (failed.count - FREE_ATTEMPTS - 1).min(16))
+                    .min(MAX_ATTEMPT_DELAY);
+                failed.retry_at = Some(Instant::now() + delay);
+            }
+            log::warn!(
+                "Incorrect guest mode PIN entered ({} in a row)",
+                failed.count
+            );
+            return Err(EntityError::InvalidOperation("Incorrect PIN".to_string()).into());
+        }
+        failed.count = 0;
+        failed.retry_at = None;
+        Ok(())
+    }
+
+    /// Hashes a PIN with argon2 and a random salt, as a PHC string
+    fn hash_pin(pin: &str) -> Result<String, AppError> {
+        let salt: [u8; SALT_LEN] = rand::random();
+        let hash_error = |e: argon2::password_hash::Error| {
+            EntityError::InvalidOperation(format!("Failed to hash the PIN: {}", e))
+        };
+        let salt = SaltString::encode_b64(&salt).map_err(hash_error)?;
+        let hash = Argon2::default()
+            .hash_password(pin.trim().as_bytes(), &salt)
+            .map_err(hash_error)?;
+        Ok(hash.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_guest_commands_are_read_only() {
+        for command in GUEST_COMMANDS {
+            assert!(
+                !command.starts_with("set_")
+                    && !command.starts_with("add_")
+                    && !command.starts_with("delete_")
+                    && !command.starts_with("remove_"),
+                "{} changes data",
+                command
+            );
+        }
+        assert!(GUEST_COMMANDS.contains(&"leave_guest_mode"));
+    }
+
+    #[test]
+    fn test_verify_pin() {
+        let mut failed = FailedAttempts::default();
+        let hash = GuestMode::hash_pin("1234").unwrap();
+        assert_ne!(hash, GuestMode::hash_pin("1234").unwrap());
+        assert!(GuestMode::verify_pin_with(&mut failed, "1234", &hash).is_ok());
+        assert!(GuestMode::verify_pin_with(&mut failed, "4321", &hash).is_err());
+        assert!(GuestMode::verify_pin_with(&mut failed, "1234", &hash).is_ok());
+        assert_eq!(failed.count, 0);
+
+        for _ in 0..=FREE_ATTEMPTS {
+            assert!(GuestMode::verify_pin_with(&mut failed, "4321", &hash).is_err());
+        }
+        // Even the correct PIN waits until the delay has passed
+        assert!(failed.retry_at.is_some());
+        assert!(GuestMode::verify_pin_with(&mut failed, "1234", &hash).is_err());
+    }
+}
@@ -0,0 +1,153 @@
+use std::sync::RwLock;
+
+use crate::definitions::{
+    FolderModel, LibraryChange, TriageAction, WorldBlacklist, WorldDisplayData, WorldModel,
+};
+use crate::errors::{AppError, ConcurrencyError, EntityError};
+
+use super::{BlacklistManager, ChangeFeed, FileService, FolderManager};
+
+/// Service for the inbox, which holds worlds added automatically, e.g. from the clipboard,
+/// recently visited worlds or deep links, until the user decides what to do with them
+/// Worlds in the inbox are left out of the Unclassified Worlds
+#[derive(Debug)]
+pub struct InboxService;
+
+impl InboxService {
+    /// Puts worlds of the library into the inbox
+    /// Worlds which are not in the library, e.g. because they are blacklisted, are skipped
+    ///
+    /// # Arguments
+    /// * `world_ids` - The IDs of the worlds
+    /// * `worlds` - The list of worlds, as a RwLock
+    ///
+    /// # Errors
+    /// Returns an error if the worlds lock is poisoned
+    /// Returns an error if the inbox could not be saved
+    pub fn add_worlds(
+        world_ids: &[String],
+        worlds: &RwLock<Vec<WorldModel>>,
+    ) -> Result<(), AppError> {
+        Self::set_in_inbox(world_ids, true, worlds)
+    }
+
+    /// Gets the worlds in the inbox, newest first
+    ///
+    /// # Errors
+    /// Returns an error if the worlds lock is poisoned
+    pub fn get_inbox_worlds(
+        worlds: &RwLock<Vec<WorldModel>>,
+    ) -> Result<Vec<WorldDisplayData>, AppError> {
+        let worlds_lock = worlds.read().map_err(|_| ConcurrencyError::PoisonedLock)?;
+        let mut inbox: Vec<&WorldModel> = worlds_lock
+            .iter()
+            .filter(|w| w.user_data.in_inbox)
+            .collect();
+        inbox.sort_by(|a, b| b.user_data.date_added.cmp(&a.user_data.date_added));
+        Ok(inbox.into_iter().map(|w| w.to_display_data()).collect())
+    }
+
+    /// Takes a world out of the inbox, keeping, hiding or blacklisting it
+    ///
+    /// # Arguments
+    /// * `world_id` - The ID of the world
+    /// * `action` - What to do with the world
+    /// * `folders` - The list of folders, as a RwLock
+    /// * `worlds` - The list of worlds, as a RwLock
+    /// * `blacklist` - The world blacklist, as a RwLock
+    ///
+    /// # Errors
+    /// Returns an error if the world is not in the inbox
+    /// Returns an error if the folder to keep the world in is not found
+    /// Returns an error if any lock is poisoned
+    /// Returns an error if the changes could not be saved
+    pub fn triage_world(
+        world_id: String,
+        action: TriageAction,
+        folders: &RwLock<Vec<FolderModel>>,
+        worlds: &RwLock<Vec<WorldModel>>,
+        blacklist: &RwLock<WorldBlacklist>,
+    ) -> Result<(), AppError> {
+        let current_folders = {
+            let worlds_lock = worlds.read().map_err(|_| ConcurrencyError::PoisonedLock)?;
+            let world = worlds_lock
+                .iter()
+                .find(|w| w.api_data.world_id == world_id)
+                .ok_or_else(|| EntityError::WorldNotFound(world_id.clone()))?;
+            if !world.user_data.in_inbox {
+                return Err(EntityError::InvalidOperation(format!(
+                    "World {} is not in the inbox",
+                    world_id
+                ))
+                .into());
+            }
+            world.user_data.folders.clone()
+        };
+
+        match action {
+            TriageAction::Keep { folder_name } => {
+                if let Some(folder_name) = folder_name {
+                    FolderManager::add_world_to_folder(
+                        folder_name.clone(),
+                        world_id.clone(),
+                        folders,
+                        worlds,
+                    )?;
+                    if !current_folders.contains(&folder_name) {
+                        ChangeFeed::record(LibraryChange::AddedToFolder {
+                            folder_name,
+                            world_ids: vec![world_id.clone()],
+                        });
+                    }
+                }
+            }
+            TriageAction::Hide => {
+                FolderManager::hide_world(world_id.clone(), folders, worlds)?;
+                ChangeFeed::record(LibraryChange::WorldHidden {
+                    world_id: world_id.clone(),
+                    folders: current_folders,
+                });
+            }
+            TriageAction::Blacklist => {
+                let blacklisted = blacklist
+                    .read()
+                    .map_err(|_| ConcurrencyError::PoisonedLock)?
+                    .contains(&world_id);
+                if !blacklisted {
+                    BlacklistManager::add_world_to_blacklist(world_id.clone(), blacklist)?;
+                }
+                FolderManager::delete_world(world_id.clone(), folders, worlds)?;
+                ChangeFeed::record(LibraryChange::WorldDeleted {
+                    world_id: world_id.clone(),
+                });
+            }
+        }
+
+        Self::set_in_inbox(&[world_id], false, worlds)
+    }
+
+    /// Sets whether worlds of the library are in the inbox, saving it once
+    /// Worlds not in the library are only taken out, never put in
+    fn set_in_inbox(
+        world_ids: &[String],
+        in_inbox: bool,
+        worlds: &RwLock<Vec<WorldModel>>,
+    ) -> Result<(), AppError> {
+        let mut worlds_lock = worlds.write().map_err(|_| ConcurrencyError::PoisonedLock)?;
+        let mut custom_data = FileService::read_custom_data();
+        for world_id in world_ids {
+            let world = worlds_lock
+                .iter_mut()
+                .find(|w| &w.api_data.world_id == world_id);
+            match world {
+                Some(world) => {
+                    world.user_data.in_inbox = in_inbox;
+                    custom_data.set_world_in_inbox(world_id, in_inbox);
+                }
+                None => custom_data.set_world_in_inbox(world_id, false),
+            }
+        }
+        FileService::write_custom_data(&custom_data)?;
+        Ok(())
+    }
+}
@@ -1,7 +1,16 @@
+use std::sync::{PoisonError, RwLock};
+
+use tauri::AppHandle;
+use tauri_specta::Event;
+
+use crate::app_state::AppState;
 use crate::definitions;
-use crate::definitions::{AuthCookies, FolderModel, InitState, PreferenceModel, WorldModel};
+use crate::definitions::{
+    AuthCookies, FolderModel, InitProgress, InitState, PreferenceModel, WorldModel,
+};
 use crate::services::file_service::FileService;
-use crate::PREFERENCES;
+use crate::services::Journal;
+use crate::services::WorldLog;
 
 /// Runs startup tasks for the application
 /// Checks if the app is being run for the first time, and loads the data
@@ -25,21 +34,105 @@ pub fn initialize_app() -> Result<
     ),
     String,
 > {
+    let (preferences, folders, cookies, init_state) = initialize_app_without_worlds()?;
+    let worlds = FileService::load_worlds(&folders);
+    Ok((preferences, folders, worlds, cookies, init_state))
+}
+
+/// Runs the startup tasks of `initialize_app`, but leaves the worlds to `stream_worlds`
+/// Preferences and folders are small, so the window is usable as soon as they are loaded
+///
+/// # Errors
+/// Returns a string error message if the app is being run for the first time, or if there was an error loading the data
+pub fn initialize_app_without_worlds(
+) -> Result<(PreferenceModel, Vec<FolderModel>, AuthCookies, InitState), String> {
     // Check for first time run
     let first_time = FileService::check_first_time();
     if first_time {
         return Err("First time run".to_string());
     }
 
+    // Undo a multi-file operation that was interrupted by a crash
+    match Journal::recover() {
+        Ok(Some(operation)) => log::warn!("Rolled back interrupted operation: {}", operation),
+        Ok(None) => {}
+        Err(e) => log::error!("Failed to roll back interrupted operation: {}", e),
+    }
+
     // Load data from disk
-    match FileService::load_data() {
-        Ok((preferences, folders, worlds, cookies)) => {
-            Ok((preferences, folders, worlds, cookies, InitState::success()))
+    match FileService::load_data_without_worlds() {
+        Ok((preferences, folders, cookies)) => {
+            Ok((preferences, folders, cookies, InitState::success()))
         }
         Err(e) => Err(e.to_string()),
     }
 }
 
+/// Loads the worlds into the state in a background task, emitting `InitProgress` while
+/// worlds.json is parsed
+/// Until the last event, worlds.json is not written, see `FileService::is_loading_worlds`
+///
+/// # Arguments
+/// * `handle` - The app handle, to emit the events with
+/// * `state` - The app state, holding the folders loaded by `initialize_app_without_worlds`
+pub fn stream_worlds(handle: AppHandle, state: AppState) {
+    FileService::set_loading_worlds(true);
+    tauri::async_runtime::spawn(load_worlds_into(handle, state));
+}
+
+async fn load_worlds_into(handle: AppHandle, state: AppState) {
+    let folders = match state.folders() {
+        Ok(folders) => folders.clone(),
+        Err(e) => {
+            log::error!("Failed to read folders before loading worlds: {}", e);
+            vec![]
+        }
+    };
+
+    let progress_handle = handle.clone();
+    let worlds = match tauri::async_runtime::spawn_blocking(move || {
+        FileService::load_worlds_with_progress(&folders, &mut |parsed| {
+            emit_progress(&progress_handle, parsed, None, false)
+        })
+    })
+    .await
+    {
+        Ok(worlds) => worlds,
+        Err(e) => {
+            log::error!("Failed to load worlds: {}", e);
+            vec![]
+        }
+    };
+
+    let total = worlds.len() as u32;
+    state
+        .world_store()
+        .write()
+        .unwrap_or_else(PoisonError::into_inner)
+        .extend(worlds);
+
+    FileService::set_loading_worlds(false);
+    log::info!("Loaded {} worlds", total);
+    emit_progress(&handle, total, Some(total), true);
+
+    // Fold the entries saved last session into worlds.json, now that the UI has the worlds
+    if let Ok(Err(e)) = tauri::async_runtime::spawn_blocking(WorldLog::compact).await {
+        log::warn!("Failed to compact worlds.log: {}", e);
+    }
+}
+
+fn emit_progress(handle: &AppHandle, loaded: u32, total: Option<u32>, done: bool) {
+    if let Err(e) = (InitProgress {
+        loaded,
+        total,
+        done,
+    })
+    .emit(handle)
+    {
+        log::error!("Failed to emit init progress: {}", e);
+    }
+}
+
 /// /// Set the user's preference for first time run
 /// This is called when the user has completed the initial setup
 ///
@@ -47,6 +140,7 @@ pub fn initialize_app() -> Result<
 /// * `theme` - A string indicating the theme the user has selected
 /// * `language` - A string indicating the language the user has selected
 /// * `card_size` - A string indicating the size of the cards the user has selected
+/// * `preferences` - The preferences, as a RwLock
 ///
 /// # Returns
 /// Returns a boolean indicating if the app is being run for the first time
@@ -57,14 +151,14 @@ pub fn set_preferences(
     theme: String,
     language: String,
     card_size: definitions::CardSize,
+    preferences: &RwLock<PreferenceModel>,
 ) -> Result<bool, String> {
-    let mut preferences_lock = PREFERENCES.get().write();
-    let preference = preferences_lock.as_mut().unwrap();
+    let mut preference = preferences.write().map_err(|e| e.to_string())?;
     preference.theme = theme;
     preference.language = language;
     preference.card_size = card_size;
     preference.first_time = false;
-    match FileService::write_preferences(preference) {
+    match FileService::write_preferences(&preference) {
         Ok(_) => Ok(true),
         Err(e) => Err(e.to_string()),
     }
@@ -0,0 +1,100 @@
+use crate::errors::ValidationError;
+
+use super::WorldReference;
+
+/// Folder names are shown in one line and used as keys in shares and exports
+pub const MAX_FOLDER_NAME_LENGTH: usize = 100;
+pub const MAX_MEMO_LENGTH: usize = 10_000;
+
+/// Checks user input before it changes any state, so entries which would break sharing or
+/// the export formats never get into the library
+#[derive(Debug)]
+pub struct InputValidator;
+
+impl InputValidator {
+    /// Checks that a folder name is not empty, at most `MAX_FOLDER_NAME_LENGTH` characters
+    /// and has no control characters, e.g. line breaks
+    ///
+    /// # Errors
+    /// Returns a ValidationError if the name is not valid
+    pub fn folder_name(name: &str) -> Result<(), ValidationError> {
+        if name.trim().is_empty() || name.chars().any(char::is_control) {
+            return Err(ValidationError::InvalidFolderName(name.to_string()));
+        }
+        if name.chars().count() > MAX_FOLDER_NAME_LENGTH {
+            return Err(ValidationError::FolderNameTooLong(MAX_FOLDER_NAME_LENGTH));
+        }
+        Ok(())
+    }
+
+    /// Checks that an ID is a world ID, "wrld_" followed by a UUID
+    ///
+    /// # Errors
+    /// Returns a ValidationError if the ID is not valid
+    pub fn world_id(world_id: &str) -> Result<(), ValidationError> {
+        if WorldReference::is_world_id(world_id) {
+            Ok(())
+        } else {
+            Err(ValidationError::InvalidWorldId(world_id.to_string()))
+        }
+    }
+
+    /// Checks that a color is a hex color, "#" followed by 3, 6 or 8 hex digits
+    ///
+    /// # Errors
+    /// Returns a ValidationError if the color is not valid
+    pub fn color(color: &str) -> Result<(), ValidationError> {
+        let valid = color.strip_prefix('#').is_some_and(|hex| {
+            matches!(hex.len(), 3 | 6 | 8) && hex.chars().all(|c| c.is_ascii_hexdigit())
+        });
+        if valid {
+            Ok(())
+        } else {
+            Err(ValidationError::InvalidColor(color.to_string()))
+        }
+    }
+
+    /// Checks that a memo is at most `MAX_MEMO_LENGTH` characters
+    ///
+    /// # Errors
+    /// Returns a ValidationError if the memo is too long
+    pub fn memo(memo: &str) -> Result<(), ValidationError> {
+        if memo.chars().count() > MAX_MEMO_LENGTH {
+            return Err(ValidationError::MemoTooLong(MAX_MEMO_LENGTH));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_folder_name() {
+        assert!(InputValidator::folder_name("Chill Worlds").is_ok());
+        assert!(InputValidator::folder_name("  ").is_err());
+        assert!(InputValidator::folder_name("Line\nbreak").is_err());
+        assert!(matches!(
+            InputValidator::folder_name(&"a".repeat(MAX_FOLDER_NAME_LENGTH + 1)),
+            Err(ValidationError::FolderNameTooLong(_))
+        ));
+    }
+
+    #[test]
+    fn test_world_id() {
+        assert!(InputValidator::world_id("wrld_4cf554b4-430c-4f8f-b53e-1f294eed230b").is_ok());
+        assert!(InputValidator::world_id("wrld_short").is_err());
+        assert!(InputValidator::world_id("usr_4cf554b4-430c-4f8f-b53e-1f294eed230b").is_err());
+    }
+
+    #[test]
+    fn test_color() {
+        for color in ["#a855f7", "#FFF", "#a855f7cc"] {
+            assert!(InputValidator::color(color).is_ok(), "{}", color);
+        }
+        for color in ["a855f7", "#a855f", "#gggggg", "red"] {
+            assert!(InputValidator::color(color).is_err(), "{}", color);
+        }
+    }
+}
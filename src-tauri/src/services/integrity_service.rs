@@ -0,0 +1,298 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::RwLock;
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+
+use crate::app_state::{read_library, write_library};
+use crate::definitions::{
+    FolderModel, FolderWorldRef, IntegrityReport, InvalidTimestamp, TimestampField, WorldModel,
+};
+use crate::errors::AppError;
+
+use super::{FileService, Journal};
+
+/// Timestamps slightly in the future are allowed for clock differences with the VRChat API
+const CLOCK_SKEW_HOURS: i64 = 24;
+
+/// Service for finding and fixing inconsistencies between folders and worlds
+#[derive(Debug)]
+pub struct IntegrityService;
+
+impl IntegrityService {
+    /// Checks the library for inconsistencies without changing anything
+    ///
+    /// # Arguments
+    /// * `folders` - The folders, as a RwLock
+    /// * `worlds` - The worlds, as a RwLock
+    ///
+    /// # Returns
+    /// A report of every issue found
+    ///
+    /// # Errors
+    /// Returns an error if the folders or worlds lock is poisoned
+    pub fn verify(
+        folders: &RwLock<Vec<FolderModel>>,
+        worlds: &RwLock<Vec<WorldModel>>,
+    ) -> Result<IntegrityReport, AppError> {
+        let (folders_lock, worlds_lock) = read_library(folders, worlds)?;
+        Ok(Self::check(&folders_lock, &worlds_lock, Utc::now()))
+    }
+
+    /// Fixes every issue found by `verify` and saves the library
+    /// Orphaned, duplicated and hidden entries are removed from folders,
+    /// folder lists of worlds are rebuilt, and invalid timestamps are reset
+    ///
+    /// # Arguments
+    /// * `folders` - The folders, as a RwLock
+    /// * `worlds` - The worlds, as a RwLock
+    ///
+    /// # Returns
+    /// A report of the issues that were fixed
+    ///
+    /// # Errors
+    /// Returns an error if the folders or worlds lock is poisoned
+    /// Returns an error if the repaired library could not be saved
+    pub fn repair(
+        folders: &RwLock<Vec<FolderModel>>,
+        worlds: &RwLock<Vec<WorldModel>>,
+    ) -> Result<IntegrityReport, AppError> {
+        let (_, folders_path, worlds_path, _) = FileService::get_paths();
+        let journal = Journal::begin("repair_library", &[folders_path, worlds_path])?;
+        let (mut folders_lock, mut worlds_lock) = write_library(folders, worlds)?;
+
+        let report = Self::reconcile(&mut folders_lock, &mut worlds_lock);
+        if report.is_clean() {
+            journal.commit();
+            return Ok(report);
+        }
+
+        FileService::write_folders(&folders_lock)?;
+        FileService::write_worlds(&worlds_lock)?;
+        journal.commit();
+        log::info!("Repaired library: {:?}", report);
+        Ok(report)
+    }
+
+    /// Fixes every issue in the in-memory library without saving it
+    /// Used after recovering from a panic that may have left the library half-updated
+    ///
+    /// # Arguments
+    /// * `folders` - The folders, already locked by the caller
+    /// * `worlds` - The worlds, already locked by the caller
+    ///
+    /// # Returns
+    /// A report of the issues that were fixed
+    pub fn reconcile(folders: &mut [FolderModel], worlds: &mut [WorldModel]) -> IntegrityReport {
+        let now = Utc::now();
+        let report = Self::check(folders, worlds, now);
+        if !report.is_clean() {
+            Self::fix(folders, worlds, &report, now);
+        }
+        report
+    }
+
+    fn check(
+        folders: &[FolderModel],
+        worlds: &[WorldModel],
+        now: DateTime<Utc>,
+    ) -> IntegrityReport {
+        let mut report = IntegrityReport::default();
+        let world_by_id: HashMap<&str, &WorldModel> = worlds
+            .iter()
+            .map(|w| (w.api_data.world_id.as_str(), w))
+            .collect();
+        let folder_names: HashSet<&str> = folders.iter().map(|f| f.folder_name.as_str()).collect();
+
+        for folder in folders {
+            let mut seen = HashSet::new();
+            for world_id in &folder.world_ids {
+                let entry = FolderWorldRef {
+                    folder_name: folder.folder_name.clone(),
+                    world_id: world_id.clone(),
+                };
+                if !seen.insert(world_id.as_str()) {
+                    report.duplicate_entries.push(entry);
+                    continue;
+                }
+                match world_by_id.get(world_id.as_str()) {
+                    None => report.orphaned_ids.push(entry),
+                    Some(world) if world.user_data.hidden => report.hidden_in_folders.push(entry),
+                    Some(_) => {}
+                }
+            }
+        }
+
+        for world in worlds {
+            let world_id = &world.api_data.world_id;
+            for folder_name in &world.user_data.folders {
+                if !folder_names.contains(folder_name.as_str()) {
+                    report.missing_folders.push(FolderWorldRef {
+                        folder_name: folder_name.clone(),
+                        world_id: world_id.clone(),
+                    });
+                }
+            }
+
+            let timestamps = [
+                (TimestampField::DateAdded, Some(world.user_data.date_added)),
+                (
+                    TimestampField::LastChecked,
+                    Some(world.user_data.last_checked),
+                ),
+                (TimestampField::UpdatedAt, Some(world.api_data.last_update)),
+                (
+                    TimestampField::PublicationDate,
+                    world.api_data.publication_date,
+                ),
+            ];
+            for (field, value) in timestamps {
+                if let Some(value) = value.filter(|v| !Self::is_valid_timestamp(*v, now)) {
+                    report.invalid_timestamps.push(InvalidTimestamp {
+                        world_id: world_id.clone(),
+                        field,
+                        value,
+                    });
+                }
+            }
+        }
+
+        report
+    }
+
+    fn fix(
+        folders: &mut [FolderModel],
+        worlds: &mut [WorldModel],
+        report: &IntegrityReport,
+        now: DateTime<Utc>,
+    ) {
+        let removable: HashSet<(&str, &str)> = report
+            .orphaned_ids
+            .iter()
+            .chain(&report.hidden_in_folders)
+            .map(|e| (e.folder_name.as_str(), e.world_id.as_str()))
+            .collect();
+
+        for folder in folders.iter_mut() {
+            let mut seen = HashSet::new();
+            let folder_name = folder.folder_name.clone();
+            folder.world_ids.retain(|world_id| {
+                seen.insert(world_id.clone())
+                    && !removable.contains(&(folder_name.as_str(), world_id.as_str()))
+            });
+        }
+
+        // Rebuild the folder list of every world from the repaired folders
+        let mut world_folders: HashMap<&str, Vec<String>> = HashMap::new();
+        for folder in folders.iter() {
+            for world_id in &folder.world_ids {
+                world_folders
+                    .entry(world_id.as_str())
+                    .or_default()
+                    .push(folder.folder_name.clone());
+            }
+        }
+        for world in worlds.iter_mut() {
+            let rebuilt = world_folders
+                .get(world.api_data.world_id.as_str())
+                .cloned()
+                .unwrap_or_default();
+            world.user_data.folders = rebuilt;
+        }
+
+        for invalid in &report.invalid_timestamps {
+            let Some(world) = worlds
+                .iter_mut()
+                .find(|w| w.api_data.world_id == invalid.world_id)
+            else {
+                continue;
+            };
+            match invalid.field {
+                TimestampField::DateAdded => world.user_data.date_added = now,
+                TimestampField::LastChecked => {
+                    // Far in the past, so the world is refreshed on the next check
+                    world.user_data.last_checked = Self::min_timestamp();
+                }
+                TimestampField::UpdatedAt => world.api_data.last_update = now,
+                TimestampField::PublicationDate => world.api_data.publication_date = None,
+            }
+        }
+    }
+
+    /// VRChat launched in 2017, so nothing in the library can be older
+    fn min_timestamp() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2017, 1, 1, 0, 0, 0).unwrap()
+    }
+
+    fn is_valid_timestamp(value: DateTime<Utc>, now: DateTime<Utc>) -> bool {
+        value >= Self::min_timestamp() && value <= now + Duration::hours(CLOCK_SKEW_HOURS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::sorting_service::make_test_world;
+
+    fn make_world(id: &str, hidden: bool) -> WorldModel {
+        let mut world = make_test_world(id);
+        world.user_data.hidden = hidden;
+        world
+    }
+
+    fn make_folder(name: &str, world_ids: &[&str]) -> FolderModel {
+        let mut folder = FolderModel::new(name.to_string());
+        folder.world_ids = world_ids.iter().map(|id| id.to_string()).collect();
+        folder
+    }
+
+    fn entry(folder_name: &str, world_id: &str) -> FolderWorldRef {
+        FolderWorldRef {
+            folder_name: folder_name.to_string(),
+            world_id: world_id.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_check_finds_each_issue() {
+        let now = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+        let folders = vec![make_folder(
+            "A",
+            &["wrld_1", "wrld_1", "wrld_gone", "wrld_hidden"],
+        )];
+        let mut worlds = vec![make_world("wrld_1", false), make_world("wrld_hidden", true)];
+        worlds[0].user_data.folders = vec!["A".to_string(), "Deleted".to_string()];
+        worlds[1].user_data.date_added = now + Duration::days(30);
+
+        let report = IntegrityService::check(&folders, &worlds, now);
+        assert_eq!(report.duplicate_entries, vec![entry("A", "wrld_1")]);
+        assert_eq!(report.orphaned_ids, vec![entry("A", "wrld_gone")]);
+        assert_eq!(report.hidden_in_folders, vec![entry("A", "wrld_hidden")]);
+        assert_eq!(report.missing_folders, vec![entry("Deleted", "wrld_1")]);
+        assert_eq!(report.invalid_timestamps.len(), 1);
+        assert_eq!(
+            report.invalid_timestamps[0].field,
+            TimestampField::DateAdded
+        );
+    }
+
+    #[test]
+    fn test_fix_leaves_clean_library() {
+        let now = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+        let mut folders = vec![
+            make_folder("A", &["wrld_1", "wrld_gone", "wrld_1", "wrld_hidden"]),
+            make_folder("B", &["wrld_hidden"]),
+        ];
+        let mut worlds = vec![make_world("wrld_1", false), make_world("wrld_hidden", true)];
+        worlds[0].user_data.folders = vec!["Deleted".to_string()];
+        worlds[0].api_data.publication_date = Some(Utc.timestamp_opt(0, 0).unwrap());
+
+        let report = IntegrityService::check(&folders, &worlds, now);
+        IntegrityService::fix(&mut folders, &mut worlds, &report, now);
+
+        assert!(IntegrityService::check(&folders, &worlds, now).is_clean());
+        assert_eq!(folders[0].world_ids, vec!["wrld_1"]);
+        assert!(folders[1].world_ids.is_empty());
+        assert_eq!(worlds[0].user_data.folders, vec!["A"]);
+        assert_eq!(worlds[0].api_data.publication_date, None);
+    }
+}
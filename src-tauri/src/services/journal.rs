@@ -0,0 +1,371 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::errors::FileError;
+
+use super::{FileService, Persistence, WorldLog, WorldStorage};
+
+/// Only one journaled operation runs at a time, as they share the journal file
+static JOURNAL_LOCK: Mutex<()> = Mutex::new(());
+
+/// A file as it was before a journaled operation started writing
+/// Its contents are kept next to it in a `.journal.bak` file until the operation is done
+#[derive(Debug, Serialize, Deserialize)]
+struct JournaledFile {
+    path: PathBuf,
+    /// SHA-256 of the contents, None if the file did not exist yet
+    checksum: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct JournalEntry {
+    operation: String,
+    #[serde(rename = "startedAt")]
+    started_at: DateTime<Utc>,
+    files: Vec<JournaledFile>,
+}
+
+/// Write-ahead journal for operations that update several files in separate writes
+///
+/// Before the first write, the operation and the checksum of every file it touches are
+/// recorded, and the files are kept aside as `.journal.bak` files. The journal is cleared once
+/// the operation commits, and an operation that fails midway is rolled back right away. A
+/// journal found on startup means the app stopped midway, and the files are rolled back to
+/// their consistent state from before the operation
+#[derive(Debug)]
+pub struct Journal;
+
+/// Keeps the journal open for the duration of an operation
+/// Call `commit` once every write is done; dropping the guard without committing, e.g. when
+/// the operation returns an error midway, rolls the files back
+#[must_use = "the files are rolled back as soon as the guard is dropped without committing"]
+pub struct JournalGuard {
+    journal_path: PathBuf,
+    operation: String,
+    files: Vec<JournaledFile>,
+    committed: bool,
+    _lock: MutexGuard<'static, ()>,
+}
+
+impl Journal {
+    /// Records the checksums of `paths` and sets the files aside before an operation writes
+    /// to them
+    /// Must be called before taking any folder or world lock, to keep lock order consistent
+    ///
+    /// # Arguments
+    /// * `operation` - A name for the operation, for logging
+    /// * `paths` - Every file the operation may write
+    ///
+    /// # Returns
+    /// A guard to commit when the operation is done
+    ///
+    /// # Errors
+    /// Returns an error if a file could not be read or set aside, or the journal could not be
+    /// written
+    pub fn begin(operation: &str, paths: &[PathBuf]) -> Result<JournalGuard, FileError> {
+        let (_, _, worlds_path, _) = FileService::get_paths();
+        Self::begin_at(
+            FileService::get_journal_path(),
+            &worlds_path,
+            operation,
+            paths,
+        )
+    }
+
+    fn begin_at(
+        journal_path: PathBuf,
+        worlds_path: &Path,
+        operation: &str,
+        paths: &[PathBuf],
+    ) -> Result<JournalGuard, FileError> {
+        // A poisoned lock only means another operation panicked, its files were rolled back
+        let lock = JOURNAL_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+
+        // Worlds are saved by appending to worlds.log, whose entries belong to worlds.json,
+        // so it is rolled back with it. Otherwise the entries of a half-done operation would
+        // still match the untouched worlds.json and be replayed on the next start
+        let mut paths = paths.to_vec();
+        // worlds.json.gz replaces worlds.json while the worlds are stored compressed
+        if paths.iter().any(|path| path == worlds_path) {
+            paths.push(WorldLog::log_path(worlds_path));
+            paths.push(WorldStorage::gzip_path(worlds_path));
+        }
+
+        let mut files = Vec::with_capacity(paths.len());
+        for path in &paths {
+            files.push(JournaledFile {
+                path: path.clone(),
+                checksum: Self::set_aside(path)?,
+            });
+        }
+
+        let entry = JournalEntry {
+            operation: operation.to_string(),
+            started_at: Utc::now(),
+            files,
+        };
+        let data = serde_json::to_string(&entry).map_err(|_| FileError::InvalidFile)?;
+        Persistence::write(&journal_path, &data)?;
+
+        Ok(JournalGuard {
+            journal_path,
+            operation: entry.operation,
+            files: entry.files,
+            committed: false,
+            _lock: lock,
+        })
+    }
+
+    /// Rolls back an operation that was interrupted, if the journal holds one
+    /// Called on startup, before the library is loaded
+    ///
+    /// # Returns
+    /// The name of the rolled back operation, if there was one
+    ///
+    /// # Errors
+    /// Returns an error if the journal or a file could not be read or restored
+    pub fn recover() -> Result<Option<String>, FileError> {
+        Self::recover_at(&FileService::get_journal_path())
+    }
+
+    fn recover_at(journal_path: &Path) -> Result<Option<String>, FileError> {
+        if !journal_path.exists() {
+            return Ok(None);
+        }
+
+        let content = fs::read_to_string(journal_path).map_err(|_| FileError::AccessDenied)?;
+        let entry: JournalEntry = match serde_json::from_str(&content) {
+            Ok(entry) => entry,
+            Err(_) => {
+                // The journal itself was cut short, so no file was written yet
+                log::warn!("Discarding incomplete journal");
+                Self::clear(journal_path, &[]);
+                return Ok(None);
+            }
+        };
+
+        log::warn!(
+            "Rolling back interrupted operation {} from {}",
+            entry.operation,
+            entry.started_at
+        );
+        Self::restore(&entry.files)?;
+
+        Self::clear(journal_path, &entry.files);
+        Ok(Some(entry.operation))
+    }
+
+    /// Keeps a file aside for rollback, linking it where possible as files are only replaced,
+    /// never written in place. worlds.log is appended to, so it is copied
+    ///
+    /// # Returns
+    /// The checksum of the file, None if it does not exist
+    fn set_aside(path: &Path) -> Result<Option<String>, FileError> {
+        let Some(checksum) = Self::checksum(path)? else {
+            return Ok(None);
+        };
+        let backup = Self::backup_path(path);
+        if backup.exists() {
+            fs::remove_file(&backup).map_err(|_| FileError::FileWriteError)?;
+        }
+        let appended = path.extension().is_some_and(|extension| extension == "log");
+        if appended || fs::hard_link(path, &backup).is_err() {
+            fs::copy(path, &backup).map_err(|_| FileError::FileWriteError)?;
+        }
+        Ok(Some(checksum))
+    }
+
+    /// Puts back the files as they were before the operation, deleting the files it created
+    fn restore(files: &[JournaledFile]) -> Result<(), FileError> {
+        for file in files {
+            // Files the operation did not get to are left alone
+            if Self::checksum(&file.path)? == file.checksum {
+                continue;
+            }
+            match &file.checksum {
+                Some(checksum) => {
+                    let backup = Self::backup_path(&file.path);
+                    if Self::checksum(&backup)?.as_ref() != Some(checksum) {
+                        log::error!("Journal backup of {:?} does not match", file.path);
+                        return Err(FileError::InvalidFile);
+                    }
+                    Persistence::write_direct(&file.path, |path| fs::rename(&backup, path))
+                        .map_err(|_| FileError::FileWriteError)?;
+                }
+                None => Persistence::write_direct(&file.path, fs::remove_file)
+                    .map_err(|_| FileError::FileWriteError)?,
+            }
+        }
+        Ok(())
+    }
+
+    /// SHA-256 of a file, None if it does not exist
+    fn checksum(path: &Path) -> Result<Option<String>, FileError> {
+        match fs::read(path) {
+            Ok(data) => Ok(Some(hex::encode(Sha256::digest(&data)))),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(_) => Err(FileError::AccessDenied),
+        }
+    }
+
+    /// The path a file is kept at for rollback
+    fn backup_path(path: &Path) -> PathBuf {
+        let mut backup = path.as_os_str().to_os_string();
+        backup.push(".journal.bak");
+        PathBuf::from(backup)
+    }
+
+    fn clear(journal_path: &Path, files: &[JournaledFile]) {
+        let backups = files.iter().map(|file| Self::backup_path(&file.path));
+        for path in [
+            FileService::get_backup_path(&journal_path.to_path_buf()),
+            journal_path.to_path_buf(),
+        ]
+        .into_iter()
+        .chain(backups)
+        {
+            Persistence::write_direct(&path, |path| {
+                if path.exists() {
+                    if let Err(e) = fs::remove_file(path) {
+                        log::error!("Failed to clear journal {:?}: {}", path, e);
+                    }
+                }
+            });
+        }
+    }
+}
+
+impl JournalGuard {
+    /// Marks the operation as done, keeping its writes and clearing the journal
+    pub fn commit(mut self) {
+        self.committed = true;
+    }
+}
+
+impl Drop for JournalGuard {
+    fn drop(&mut self) {
+        if !self.committed {
+            log::warn!("Rolling back unfinished operation {}", self.operation);
+            if let Err(e) = Journal::restore(&self.files) {
+                // Leave the journal in place so the next startup rolls back the operation
+                log::error!("Failed to roll back {}: {}", self.operation, e);
+                return;
+            }
+            WorldLog::invalidate();
+        }
+        Journal::clear(&self.journal_path, &self.files);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recover_rolls_back_interrupted_operation() {
+        let dir = tempfile::tempdir().unwrap();
+        let journal_path = dir.path().join("journal.json");
+        let existing = dir.path().join("folders.json");
+        let created = dir.path().join("worlds.json");
+        fs::write(&existing, "old").unwrap();
+
+        let guard = Journal::begin_at(
+            journal_path.clone(),
+            &created,
+            "test",
+            &[existing.clone(), created.clone()],
+        )
+        .unwrap();
+        fs::write(&existing, "new").unwrap();
+        fs::write(&created, "new").unwrap();
+        // The journal only records checksums, the contents are kept aside
+        assert!(!fs::read_to_string(&journal_path).unwrap().contains("old"));
+
+        // Recovering while the guard is held stands in for a restart midway through
+        assert_eq!(
+            Journal::recover_at(&journal_path).unwrap().as_deref(),
+            Some("test")
+        );
+        assert_eq!(fs::read_to_string(&existing).unwrap(), "old");
+        assert!(!created.exists());
+
+        guard.commit();
+        assert_eq!(Journal::recover_at(&journal_path).unwrap(), None);
+    }
+
+    #[test]
+    fn test_uncommitted_operation_is_rolled_back() {
+        let dir = tempfile::tempdir().unwrap();
+        let journal_path = dir.path().join("journal.json");
+        let existing = dir.path().join("folders.json");
+        let created = dir.path().join("worlds.json");
+        fs::write(&existing, "old").unwrap();
+
+        let guard = Journal::begin_at(
+            journal_path.clone(),
+            &created,
+            "test",
+            &[existing.clone(), created.clone()],
+        )
+        .unwrap();
+        fs::write(&existing, "new").unwrap();
+        fs::write(&created, "new").unwrap();
+        drop(guard);
+
+        assert_eq!(fs::read_to_string(&existing).unwrap(), "old");
+        assert!(!created.exists());
+        assert!(!journal_path.exists());
+
+        let guard =
+            Journal::begin_at(journal_path.clone(), &created, "test", &[existing.clone()]).unwrap();
+        fs::write(&existing, "new").unwrap();
+        guard.commit();
+
+        assert_eq!(fs::read_to_string(&existing).unwrap(), "new");
+        assert!(!journal_path.exists());
+        assert!(!Journal::backup_path(&existing).exists());
+    }
+
+    #[test]
+    fn test_world_log_is_rolled_back_with_worlds() {
+        let dir = tempfile::tempdir().unwrap();
+        let journal_path = dir.path().join("journal.json");
+        let worlds_path = dir.path().join("worlds.json");
+        let log_path = WorldLog::log_path(&worlds_path);
+        fs::write(&worlds_path, "[]").unwrap();
+
+        // A log started by the operation is removed again
+        let guard = Journal::begin_at(
+            journal_path.clone(),
+            &worlds_path,
+            "test",
+            &[worlds_path.clone()],
+        )
+        .unwrap();
+        fs::write(&log_path, "entries").unwrap();
+        drop(guard);
+        assert!(!log_path.exists());
+
+        // Entries appended by the operation are removed, earlier ones are kept
+        fs::write(&log_path, "entries").unwrap();
+        let guard = Journal::begin_at(
+            journal_path.clone(),
+            &worlds_path,
+            "test",
+            &[worlds_path.clone()],
+        )
+        .unwrap();
+        fs::write(&log_path, "entries\nmore entries").unwrap();
+        assert_eq!(
+            Journal::recover_at(&journal_path).unwrap().as_deref(),
+            Some("test")
+        );
+        assert_eq!(fs::read_to_string(&log_path).unwrap(), "entries");
+        guard.commit();
+    }
+}
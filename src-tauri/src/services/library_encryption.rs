@@ -0,0 +1,491 @@
+use std::borrow::Cow;
+use std::fs;
+use std::path::Path;
+use std::sync::RwLock;
+
+use aes::{
+    cipher::{BlockDecryptMut, BlockEncryptMut, KeyIvInit},
+    Aes256,
+};
+use argon2::Argon2;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use cbc::cipher::block_padding::Pkcs7;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+
+use crate::definitions::LibraryEncryptionStatus;
+use crate::errors::{AppError, ConcurrencyError, EntityError, FileError};
+
+use super::{FileService, Persistence, WorldLog, WorldStorage};
+
+/// Marks a file as encrypted with the library passphrase
+/// Files without it are read as plaintext, so data from before encryption was enabled still loads
+const ENCRYPTED_PREFIX: &str = "vrcwm-encrypted:v1:";
+/// Encrypted with the derived key to check a passphrase without touching the library files
+const CHECK_PLAINTEXT: &str = "vrc-worlds-manager-library";
+const SALT_LEN: usize = 16;
+const IV_LEN: usize = 16;
+const MAC_LEN: usize = 32;
+
+/// 32 bytes of AES-256 key followed by 32 bytes of HMAC-SHA256 key
+type LibraryKey = [u8; 64];
+
+/// Stored next to the library, never encrypted itself
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct EncryptionConfig {
+    salt: String,
+    check: String,
+}
+
+struct EncryptionState {
+    config: Option<EncryptionConfig>,
+    key: Option<LibraryKey>,
+}
+
+static STATE: RwLock<EncryptionState> = RwLock::new(EncryptionState {
+    config: None,
+    key: None,
+});
+
+/// Optional at-rest encryption of the library files with a key derived from a user passphrase
+///
+/// While encryption is enabled but the library has not been unlocked, reading an encrypted
+/// file or writing any library file fails with `FileError::LibraryLocked`, so nothing is
+/// ever written back in plaintext or overwritten with empty data
+#[derive(Debug)]
+pub struct LibraryEncryption;
+
+impl LibraryEncryption {
+    /// Loads the encryption settings from disk
+    /// Must be called before the library is loaded
+    pub fn init() {
+        let config_path = FileService::get_library_encryption_path();
+        if !config_path.exists() {
+            return;
+        }
+
+        let config = fs::read_to_string(&config_path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<EncryptionConfig>(&content).ok());
+        match config {
+            Some(config) => {
+                log::info!("Library encryption is enabled, waiting for unlock");
+                if let Ok(mut state) = STATE.write() {
+                    state.config = Some(config);
+                }
+            }
+            None => log::error!("library_encryption.json is invalid, library cannot be unlocked"),
+        }
+    }
+
+    /// Gets whether encryption is enabled and whether the library is still locked
+    pub fn status() -> Result<LibraryEncryptionStatus, AppError> {
+        let state = STATE.read().map_err(|_| ConcurrencyError::PoisonedLock)?;
+        Ok(LibraryEncryptionStatus {
+            enabled: state.config.is_some(),
+            locked: state.config.is_some() && state.key.is_none(),
+        })
+    }
+
+    /// Returns true if encryption is enabled and the library has not been unlocked yet
+    pub fn is_locked() -> bool {
+        STATE
+            .read()
+            .map(|state| state.config.is_some() && state.key.is_none())
+            .unwrap_or(true)
+    }
+
+    /// Unlocks the library for this session
+    ///
+    /// # Arguments
+    /// * `passphrase` - The library passphrase
+    ///
+    /// # Errors
+    /// Returns an error if encryption is not enabled or the passphrase is incorrect
+    pub fn unlock(passphrase: &str) -> Result<(), AppError> {
+        let mut state = STATE.write().map_err(|_| ConcurrencyError::PoisonedLock)?;
+        let config = state.config.clone().ok_or_else(Self::not_enabled)?;
+
+        state.key = Some(Self::verify_passphrase(passphrase, &config)?);
+        log::info!("Library unlocked");
+        Ok(())
+    }
+
+    /// Enables encryption and rewrites the library files encrypted
+    ///
+    /// # Arguments
+    /// * `passphrase` - The passphrase to encrypt the library with
+    ///
+    /// # Errors
+    /// Returns an error if encryption is already enabled or the passphrase is empty
+    /// Returns an error if worlds.log could not be folded into worlds.json
+    /// Returns an error if the library files could not be rewritten
+    pub fn enable(passphrase: &str) -> Result<(), AppError> {
+        WorldLog::compact()?;
+        let mut state = STATE.write().map_err(|_| ConcurrencyError::PoisonedLock)?;
+        if state.config.is_some() {
+            return Err(EntityError::InvalidOperation(
+                "Library encryption is already enabled".to_string(),
+            )
+            .into());
+        }
+
+        let (config, key) = Self::new_config(passphrase)?;
+        let contents = Self::read_library_files(None)?;
+        Self::write_config(Some(&config))?;
+        state.config = Some(config);
+        state.key = Some(key);
+        Self::write_library_files(contents, Some(&key))?;
+        log::info!("Library encryption enabled");
+        Ok(())
+    }
+
+    /// Re-encrypts the library with a new passphrase
+    ///
+    /// # Arguments
+    /// * `current` - The current passphrase
+    /// * `new` - The passphrase to encrypt the library with from now on
+    ///
+    /// # Errors
+    /// Returns an error if encryption is not enabled or the current passphrase is incorrect
+    /// Returns an error if worlds.log could not be folded into worlds.json
+    /// Returns an error if the library files could not be rewritten
+    pub fn change_passphrase(current: &str, new: &str) -> Result<(), AppError> {
+        WorldLog::compact()?;
+        let mut state = STATE.write().map_err(|_| ConcurrencyError::PoisonedLock)?;
+        let config = state.config.clone().ok_or_else(Self::not_enabled)?;
+        let old_key = Self::verify_passphrase(current, &config)?;
+
+        let (new_config, new_key) = Self::new_config(new)?;
+        // Everything is decrypted before anything is written, so a bad file aborts the change
+        let contents = Self::read_library_files(Some(&old_key))?;
+        Self::write_config(Some(&new_config))?;
+        state.config = Some(new_config);
+        state.key = Some(new_key);
+        Self::write_library_files(contents, Some(&new_key))?;
+        log::info!("Library passphrase changed");
+        Ok(())
+    }
+
+    /// Disables encryption and rewrites the library files in plaintext
+    ///
+    /// # Arguments
+    /// * `passphrase` - The current passphrase
+    ///
+    /// # Errors
+    /// Returns an error if encryption is not enabled or the passphrase is incorrect
+    /// Returns an error if worlds.log could not be folded into worlds.json
+    /// Returns an error if the library files could not be rewritten
+    pub fn disable(passphrase: &str) -> Result<(), AppError> {
+        WorldLog::compact()?;
+        let mut state = STATE.write().map_err(|_| ConcurrencyError::PoisonedLock)?;
+        let config = state.config.clone().ok_or_else(Self::not_enabled)?;
+        let key = Self::verify_passphrase(passphrase, &config)?;
+
+        let contents = Self::read_library_files(Some(&key))?;
+        state.config = None;
+        state.key = None;
+        Self::write_library_files(contents, None)?;
+        Self::write_config(None)?;
+        log::info!("Library encryption disabled");
+        Ok(())
+    }
+
+    /// Prepares file content for writing, encrypting it if it is a library file
+    ///
+    /// # Errors
+    /// Returns `FileError::LibraryLocked` for library files while the library is locked
+    pub fn seal<'a>(path: &Path, data: &'a str) -> Result<Cow<'a, str>, FileError> {
+        Ok(match Self::write_key(path)? {
+            Some(key) => Cow::Owned(Self::encrypt(&key, data.as_bytes())),
+            None => Cow::Borrowed(data),
+        })
+    }
+
+    /// Prepares binary file content for writing like `seal`, e.g. compressed worlds
+    ///
+    /// # Errors
+    /// Returns `FileError::LibraryLocked` for library files while the library is locked
+    pub fn seal_bytes<'a>(path: &Path, data: &'a [u8]) -> Result<Cow<'a, [u8]>, FileError> {
+        Ok(match Self::write_key(path)? {
+            Some(key) => Cow::Owned(Self::encrypt(&key, data).into_bytes()),
+            None => Cow::Borrowed(data),
+        })
+    }
+
+    /// Decrypts file content read from disk, passing plaintext through unchanged
+    ///
+    /// # Errors
+    /// Returns `FileError::LibraryLocked` if the content is encrypted and the library is locked
+    /// Returns `FileError::DecryptionError` if the content could not be decrypted
+    pub fn open(data: &str) -> Result<Cow<'_, str>, FileError> {
+        if !data.starts_with(ENCRYPTED_PREFIX) {
+            return Ok(Cow::Borrowed(data));
+        }
+
+        let state = STATE.read().map_err(|_| FileError::LibraryLocked)?;
+        let key = state.key.as_ref().ok_or(FileError::LibraryLocked)?;
+        Self::decrypt(key, data).map(Cow::Owned)
+    }
+
+    /// Decrypts binary file content read from disk like `open`
+    ///
+    /// # Errors
+    /// Returns `FileError::LibraryLocked` if the content is encrypted and the library is locked
+    /// Returns `FileError::DecryptionError` if the content could not be decrypted
+    pub fn open_bytes(data: &[u8]) -> Result<Cow<'_, [u8]>, FileError> {
+        if !data.starts_with(ENCRYPTED_PREFIX.as_bytes()) {
+            return Ok(Cow::Borrowed(data));
+        }
+
+        let data = std::str::from_utf8(data).map_err(|_| FileError::DecryptionError)?;
+        let state = STATE.read().map_err(|_| FileError::LibraryLocked)?;
+        let key = state.key.as_ref().ok_or(FileError::LibraryLocked)?;
+        Self::decrypt_bytes(key, data).map(Cow::Owned)
+    }
+
+    /// The key to encrypt a file with before writing it, None if it is written as-is
+    fn write_key(path: &Path) -> Result<Option<LibraryKey>, FileError> {
+        if !Self::is_library_file(path) {
+            return Ok(None);
+        }
+
+        let state = STATE.read().map_err(|_| FileError::LibraryLocked)?;
+        match (&state.config, &state.key) {
+            (None, _) => Ok(None),
+            (Some(_), None) => Err(FileError::LibraryLocked),
+            (Some(_), Some(key)) => Ok(Some(*key)),
+        }
+    }
+
+    fn is_library_file(path: &Path) -> bool {
+        Self::library_files().iter().any(|p| p == path)
+    }
+
+    fn library_files() -> [std::path::PathBuf; 5] {
+        let (_, folders_path, worlds_path, _) = FileService::get_paths();
+        [
+            WorldStorage::gzip_path(&worlds_path),
+            worlds_path,
+            folders_path,
+            FileService::get_memo_path(),
+            FileService::get_custom_data_path(),
+        ]
+    }
+
+    fn not_enabled() -> AppError {
+        EntityError::InvalidOperation("Library encryption is not enabled".to_string()).into()
+    }
+
+    fn new_config(passphrase: &str) -> Result<(EncryptionConfig, LibraryKey), AppError> {
+        if passphrase.is_empty() {
+            return Err(
+                EntityError::InvalidOperation("Passphrase cannot be empty".to_string()).into(),
+            );
+        }
+
+        let salt: [u8; SALT_LEN] = rand::random();
+        let key = Self::derive_key(passphrase, &salt)?;
+        let config = EncryptionConfig {
+            salt: STANDARD.encode(salt),
+            check: Self::encrypt(&key, CHECK_PLAINTEXT.as_bytes()),
+        };
+        Ok((config, key))
+    }
+
+    fn verify_passphrase(
+        passphrase: &str,
+        config: &EncryptionConfig,
+    ) -> Result<LibraryKey, AppError> {
+        let salt = STANDARD
+            .decode(&config.salt)
+            .map_err(|_| FileError::InvalidFile)?;
+        let key = Self::derive_key(passphrase, &salt)?;
+
+        match Self::decrypt(&key, &config.check) {
+            Ok(check) if check == CHECK_PLAINTEXT => Ok(key),
+            _ => Err(EntityError::InvalidOperation("Incorrect passphrase".to_string()).into()),
+        }
+    }
+
+    fn derive_key(passphrase: &str, salt: &[u8]) -> Result<LibraryKey, FileError> {
+        let mut key = [0u8; 64];
+        Argon2::default()
+            .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+            .map_err(|e| {
+                log::error!("Failed to derive library key: {}", e);
+                FileError::DecryptionError
+            })?;
+        Ok(key)
+    }
+
+    fn mac(key: &LibraryKey, data: &[u8]) -> Hmac<Sha256> {
+        let mut mac =
+            Hmac::<Sha256>::new_from_slice(&key[32..]).expect("HMAC accepts keys of any length");
+        mac.update(data);
+        mac
+    }
+
+    /// AES-256-CBC with a random IV, then HMAC-SHA256 over the IV and ciphertext
+    fn encrypt(key: &LibraryKey, plaintext: &[u8]) -> String {
+        let iv: [u8; IV_LEN] = rand::random();
+        let cipher = cbc::Encryptor::<Aes256>::new(key[..32].into(), iv.as_slice().into());
+
+        let mut buffer = vec![0u8; plaintext.len() + 16];
+        let ciphertext = cipher
+            .encrypt_padded_b2b_mut::<Pkcs7>(plaintext, &mut buffer)
+            .expect("buffer has room for a block of padding");
+
+        let mut payload = iv.to_vec();
+        payload.extend_from_slice(ciphertext);
+        let tag = Self::mac(key, &payload).finalize().into_bytes();
+        payload.extend_from_slice(&tag);
+
+        format!("{}{}", ENCRYPTED_PREFIX, STANDARD.encode(payload))
+    }
+
+    fn decrypt(key: &LibraryKey, data: &str) -> Result<String, FileError> {
+        String::from_utf8(Self::decrypt_bytes(key, data)?).map_err(|_| FileError::DecryptionError)
+    }
+
+    fn decrypt_bytes(key: &LibraryKey, data: &str) -> Result<Vec<u8>, FileError> {
+        let encoded = data
+            .strip_prefix(ENCRYPTED_PREFIX)
+            .ok_or(FileError::DecryptionError)?;
+        let payload = STANDARD
+            .decode(encoded.trim())
+            .map_err(|_| FileError::DecryptionError)?;
+        if payload.len() < IV_LEN + MAC_LEN {
+            return Err(FileError::DecryptionError);
+        }
+
+        let (body, tag) = payload.split_at(payload.len() - MAC_LEN);
+        Self::mac(key, body)
+            .verify_slice(tag)
+            .map_err(|_| FileError::DecryptionError)?;
+
+        let (iv, ciphertext) = body.split_at(IV_LEN);
+        let cipher = cbc::Decryptor::<Aes256>::new(key[..32].into(), iv.into());
+        let mut buffer = vec![0u8; ciphertext.len()];
+        let plaintext = cipher
+            .decrypt_padded_b2b_mut::<Pkcs7>(ciphertext, &mut buffer)
+            .map_err(|_| FileError::DecryptionError)?;
+        Ok(plaintext.to_vec())
+    }
+
+    fn write_config(config: Option<&EncryptionConfig>) -> Result<(), FileError> {
+        let config_path = FileService::get_library_encryption_path();
+        match config {
+            Some(config) => {
+                let data =
+                    serde_json::to_string_pretty(config).map_err(|_| FileError::InvalidFile)?;
+                Persistence::write(&config_path, &data)
+            }
+            None => fs::remove_file(&config_path).map_err(|_| FileError::FileWriteError),
+        }
+    }
+
+    /// Reads the plaintext of every existing library file
+    fn read_library_files(
+        key: Option<&LibraryKey>,
+    ) -> Result<Vec<(std::path::PathBuf, Vec<u8>)>, FileError> {
+        let mut contents = Vec::new();
+        for path in Self::library_files() {
+            if !path.exists() {
+                continue;
+            }
+            let data = fs::read(&path).map_err(|_| FileError::AccessDenied)?;
+            let plaintext = match key {
+                Some(key) if data.starts_with(ENCRYPTED_PREFIX.as_bytes()) => {
+                    let data =
+                        std::str::from_utf8(&data).map_err(|_| FileError::DecryptionError)?;
+                    Self::decrypt_bytes(key, data)?
+                }
+                _ => data,
+            };
+            contents.push((path, plaintext));
+        }
+        Ok(contents)
+    }
+
+    /// Rewrites the library files and drops their backups, which hold the previous form
+    fn write_library_files(
+        contents: Vec<(std::path::PathBuf, Vec<u8>)>,
+        key: Option<&LibraryKey>,
+    ) -> Result<(), FileError> {
+        for (path, plaintext) in contents {
+            let data = match key {
+                Some(key) => Self::encrypt(key, &plaintext).into_bytes(),
+                None => plaintext,
+            };
+            // Written as-is, as `seal` would need the state lock held by the caller
+            Persistence::write_bytes(&path, &data)?;
+
+            let backup_path = FileService::get_backup_path(&path);
+            if backup_path.exists() {
+                if let Err(e) = fs::remove_file(&backup_path) {
+                    log::warn!("Failed to remove backup {:?}: {}", backup_path, e);
+                }
+            }
+        }
+        // worlds.json changed underneath the log, so the next save rewrites it in full
+        WorldLog::invalidate();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encrypt_round_trip() {
+        let key = [7u8; 64];
+        let encrypted = LibraryEncryption::encrypt(&key, b"{\"worlds\":[]}");
+        assert!(encrypted.starts_with(ENCRYPTED_PREFIX));
+        assert_eq!(
+            LibraryEncryption::decrypt(&key, &encrypted).unwrap(),
+            "{\"worlds\":[]}"
+        );
+
+        // A different key fails the MAC instead of producing garbage
+        assert!(LibraryEncryption::decrypt(&[8u8; 64], &encrypted).is_err());
+
+        let mut tampered = encrypted.clone();
+        tampered.pop();
+        tampered.push(if encrypted.ends_with('A') { 'B' } else { 'A' });
+        assert!(LibraryEncryption::decrypt(&key, &tampered).is_err());
+    }
+
+    #[test]
+    fn test_verify_passphrase() {
+        let (config, key) = LibraryEncryption::new_config("hunter2").unwrap();
+        assert_eq!(
+            LibraryEncryption::verify_passphrase("hunter2", &config).unwrap(),
+            key
+        );
+        assert!(LibraryEncryption::verify_passphrase("hunter3", &config).is_err());
+        assert!(LibraryEncryption::new_config("").is_err());
+    }
+
+    #[test]
+    fn test_open_passes_plaintext_through() {
+        assert_eq!(LibraryEncryption::open("[]").unwrap(), "[]");
+        let gzip = [0x1f, 0x8b, 0x08, 0x00];
+        assert_eq!(LibraryEncryption::open_bytes(&gzip).unwrap(), &gzip[..]);
+    }
+
+    #[test]
+    fn test_encrypt_binary_round_trip() {
+        let key = [7u8; 64];
+        let data = [0x1f, 0x8b, 0x00, 0xff, 0x80];
+        let encrypted = LibraryEncryption::encrypt(&key, &data);
+        assert_eq!(
+            LibraryEncryption::decrypt_bytes(&key, &encrypted).unwrap(),
+            data
+        );
+        // Not valid UTF-8, so it can't be read back as text
+        assert!(LibraryEncryption::decrypt(&key, &encrypted).is_err());
+    }
+}
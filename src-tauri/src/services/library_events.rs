@@ -0,0 +1,66 @@
+use std::sync::OnceLock;
+
+use serde::Serialize;
+use tauri::AppHandle;
+use tauri_specta::Event;
+
+use crate::definitions::{
+    FolderRenamed, FolderReordered, WorldAdded, WorldDisplayData, WorldFlag, WorldFlagChanged,
+    WorldRemoved,
+};
+
+/// Set on startup, no events are emitted without it, e.g. in headless mode and tests
+static HANDLE: OnceLock<AppHandle> = OnceLock::new();
+
+/// Typed events for changes made through `FolderManager`, so the frontend can update its
+/// cached lists in place instead of fetching them again after each command
+/// Changes to the data of saved worlds are sent by `WorldChangeService`
+#[derive(Debug)]
+pub struct LibraryEvents;
+
+impl LibraryEvents {
+    pub fn init(handle: AppHandle) {
+        let _ = HANDLE.set(handle);
+    }
+
+    pub fn world_added(worlds: Vec<WorldDisplayData>) {
+        if !worlds.is_empty() {
+            Self::emit(WorldAdded { worlds });
+        }
+    }
+
+    pub fn world_removed(world_id: String) {
+        Self::emit(WorldRemoved { world_id });
+    }
+
+    pub fn world_flag_changed(world_ids: Vec<String>, flag: WorldFlag, value: bool) {
+        if !world_ids.is_empty() {
+            Self::emit(WorldFlagChanged {
+                world_ids,
+                flag,
+                value,
+            });
+        }
+    }
+
+    pub fn folder_renamed(old_name: String, new_name: String, merged: bool) {
+        Self::emit(FolderRenamed {
+            old_name,
+            new_name,
+            merged,
+        });
+    }
+
+    pub fn folder_reordered(folder_names: Vec<String>) {
+        Self::emit(FolderReordered { folder_names });
+    }
+
+    fn emit<E: Event + Serialize + Clone>(event: E) {
+        let Some(handle) = HANDLE.get() else {
+            return;
+        };
+        if let Err(e) = event.emit(handle) {
+            log::error!("Failed to emit {} event: {}", E::NAME, e);
+        }
+    }
+}
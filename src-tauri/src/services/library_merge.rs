@@ -0,0 +1,382 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+use std::sync::RwLock;
+
+use serde::de::DeserializeOwned;
+
+use crate::app_state::write_library;
+use crate::definitions::{CustomData, FolderModel, LibraryMergeSummary, MergePolicy, WorldModel};
+use crate::errors::{AppError, EntityError, FileError};
+use crate::migration::DataFile;
+use crate::services::memo_manager::MemoManager;
+
+use super::{FileService, IntegrityService, Journal};
+
+/// Service for merging the library of another installation into this one, e.g. to
+/// consolidate the libraries of a laptop and a desktop
+///
+/// There is no common base to compare against, so everything either library has is kept:
+/// worlds and folders are united, flags are set if either side set them, and memos and
+/// settings both sides changed are decided by the `MergePolicy`
+pub struct LibraryMerge;
+
+impl LibraryMerge {
+    /// Merges another installation's library into this one
+    ///
+    /// # Arguments
+    /// * `data_dir` - The other installation's data directory
+    /// * `policy` - Which side wins when both edited the same memo or setting
+    /// * `folders` - The list of folders, as a RwLock
+    /// * `worlds` - The list of worlds, as a RwLock
+    /// * `memos` - The memos of this installation
+    ///
+    /// # Returns
+    /// What the merge changed
+    ///
+    /// # Errors
+    /// Returns an error if the directory is this installation's own data directory
+    /// Returns an error if the other library's worlds or folders cannot be read, e.g. as it is
+    /// encrypted, or was written by a newer version of the app
+    /// Returns an error if any lock is poisoned, or the merged library could not be written
+    pub fn merge_from(
+        data_dir: &Path,
+        policy: MergePolicy,
+        folders: &RwLock<Vec<FolderModel>>,
+        worlds: &RwLock<Vec<WorldModel>>,
+        memos: &mut MemoManager,
+    ) -> Result<LibraryMergeSummary, AppError> {
+        let own_dir = FileService::get_app_dir();
+        if matches!(
+            (fs::canonicalize(data_dir), fs::canonicalize(own_dir)),
+            (Ok(other), Ok(own)) if other == own
+        ) {
+            return Err(EntityError::InvalidOperation(
+                "Cannot merge the library with itself".to_string(),
+            )
+            .into());
+        }
+
+        let other_custom_data: CustomData =
+            Self::read(&data_dir.join(DataFile::CustomData.file_name())).unwrap_or_default();
+        for file in [DataFile::Worlds, DataFile::Folders] {
+            let version = other_custom_data.schema_versions.get(file.file_name());
+            if version.is_some_and(|v| *v > file.current_version()) {
+                return Err(FileError::NewerSchema(file.file_name().to_string()).into());
+            }
+        }
+        let mut other_worlds: Vec<WorldModel> =
+            Self::read(&data_dir.join(DataFile::Worlds.file_name()))?;
+        let other_folders: Vec<FolderModel> =
+            Self::read(&data_dir.join(DataFile::Folders.file_name()))?;
+        let other_memos = MemoManager::load(data_dir.join("memo.json")).map_err(|e| {
+            log::error!("Failed to read memos to merge: {}", e);
+            FileError::InvalidFile
+        })?;
+
+        for world in other_worlds.iter_mut() {
+            let id = world.api_data.world_id.clone();
+            world.user_data.is_favorite = other_custom_data.is_world_favorite(&id);
+            world.user_data.is_photographed = other_custom_data.is_world_photographed(&id);
+            world.user_data.is_shared = other_custom_data.is_world_shared(&id);
+            world.user_data.is_event_critical = other_custom_data.is_world_event_critical(&id);
+            world.user_data.rating = other_custom_data.get_world_rating(&id);
+            world.user_data.launch_count = other_custom_data.get_world_launch_count(&id);
+        }
+
+        let mut summary = LibraryMergeSummary::default();
+        let mut custom_data = FileService::read_custom_data();
+        let other_newer = {
+            let (_, folders_path, worlds_path, _) = FileService::get_paths();
+            let journal = Journal::begin("merge_library", &[folders_path, worlds_path])?;
+            let (mut folders_lock, mut worlds_lock) = write_library(folders, worlds)?;
+
+            let other_newer =
+                Self::merge_worlds(&mut worlds_lock, other_worlds, policy, &mut summary);
+            for folder in other_folders {
+                Self::merge_folder(
+                    &mut folders_lock,
+                    folder,
+                    &other_custom_data,
+                    &mut custom_data,
+                    &mut summary,
+                );
+            }
+            IntegrityService::reconcile(&mut folders_lock, &mut worlds_lock);
+            for world in worlds_lock.iter_mut() {
+                let id = &world.api_data.world_id;
+                world.user_data.folders = folders_lock
+                    .iter()
+                    .filter(|f| f.world_ids.contains(id))
+                    .map(|f| f.folder_name.clone())
+                    .collect();
+                custom_data
+                    .world_launch_counts
+                    .insert(id.clone(), world.user_data.launch_count);
+            }
+
+            // Flags are saved to custom data by write_worlds, which reads it back first
+            FileService::write_custom_data(&custom_data)?;
+            FileService::write_folders(&folders_lock)?;
+            FileService::write_worlds(&worlds_lock)?;
+            journal.commit();
+            other_newer
+        };
+
+        for (world_id, other_memo) in other_memos.memos() {
+            match memos.get_memo(world_id) {
+                Some(memo) if memo == other_memo => {}
+                None | Some("") => {
+                    memos.set_memo(world_id, other_memo);
+                    summary.memos_added += 1;
+                }
+                Some(_) => {
+                    summary.conflicts += 1;
+                    if policy == MergePolicy::PreferNewer && other_newer.contains(world_id) {
+                        memos.set_memo(world_id, other_memo);
+                    }
+                }
+            }
+        }
+        memos.save().map_err(|e| {
+            log::error!("Failed to save merged memos: {}", e);
+            FileError::FileWriteError
+        })?;
+
+        log::info!("Merged library from {:?}: {:?}", data_dir, summary);
+        Ok(summary)
+    }
+
+    /// Merges the other library's worlds into this one's
+    /// Returns the IDs of the worlds the other library checked more recently
+    fn merge_worlds(
+        worlds: &mut Vec<WorldModel>,
+        other_worlds: Vec<WorldModel>,
+        policy: MergePolicy,
+        summary: &mut LibraryMergeSummary,
+    ) -> HashSet<String> {
+        let mut other_newer = HashSet::new();
+        for other in other_worlds {
+            let id = other.api_data.world_id.clone();
+            match worlds.iter_mut().find(|w| w.api_data.world_id == id) {
+                Some(world) => {
+                    if other.user_data.last_checked > world.user_data.last_checked {
+                        other_newer.insert(id);
+                    }
+                    let (changed, conflicts) = Self::merge_world(world, other, policy);
+                    summary.worlds_updated += usize::from(changed);
+                    summary.conflicts += conflicts;
+                }
+                None => {
+                    worlds.push(other);
+                    summary.worlds_added += 1;
+                }
+            }
+        }
+        other_newer
+    }
+
+    /// Merges the other library's copy of a world into this one's
+    /// The most recent VRChat data is kept whatever the policy, as it is not a user edit
+    ///
+    /// # Returns
+    /// Whether the world changed, and how many of its settings both sides edited
+    fn merge_world(
+        world: &mut WorldModel,
+        other: WorldModel,
+        policy: MergePolicy,
+    ) -> (bool, usize) {
+        let before = Self::snapshot(world);
+        let other_wins = policy == MergePolicy::PreferNewer
+            && other.user_data.last_checked > world.user_data.last_checked;
+        let mut conflicts = 0;
+
+        if other.api_data.last_update > world.api_data.last_update {
+            world.api_data = other.api_data;
+        }
+        let data = &mut world.user_data;
+        let other_data = other.user_data;
+        data.date_added = data.date_added.min(other_data.date_added);
+        data.last_checked = data.last_checked.max(other_data.last_checked);
+        data.is_favorite |= other_data.is_favorite;
+        data.is_photographed |= other_data.is_photographed;
+        data.is_shared |= other_data.is_shared;
+        data.is_event_critical |= other_data.is_event_critical;
+        data.launch_count = data.launch_count.max(other_data.launch_count);
+
+        match (data.rating, other_data.rating) {
+            (None, Some(_)) => data.rating = other_data.rating,
+            (Some(rating), Some(other_rating)) if rating != other_rating => {
+                conflicts += 1;
+                if other_wins {
+                    data.rating = other_data.rating;
+                }
+            }
+            _ => {}
+        }
+        if data.memo.is_empty() {
+            data.memo = other_data.memo;
+        } else if !other_data.memo.is_empty() && data.memo != other_data.memo {
+            conflicts += 1;
+            if other_wins {
+                data.memo = other_data.memo;
+            }
+        }
+        if data.hidden != other_data.hidden {
+            conflicts += 1;
+            if other_wins {
+                data.hidden = other_data.hidden;
+            }
+        }
+
+        (Self::snapshot(world) != before, conflicts)
+    }
+
+    /// Everything about a world a merge can change, including the flags kept in custom data
+    fn snapshot(world: &WorldModel) -> (Option<serde_json::Value>, [bool; 4], Option<u8>, u32) {
+        let data = &world.user_data;
+        (
+            serde_json::to_value(world).ok(),
+            [
+                data.is_favorite,
+                data.is_photographed,
+                data.is_shared,
+                data.is_event_critical,
+            ],
+            data.rating,
+            data.launch_count,
+        )
+    }
+
+    /// Merges one of the other library's folders into this one's folders
+    /// Folders are matched by name; worlds only the other folder has are appended, and
+    /// its color, icon and description fill in the ones this folder does not have
+    fn merge_folder(
+        folders: &mut Vec<FolderModel>,
+        other: FolderModel,
+        other_custom_data: &CustomData,
+        custom_data: &mut CustomData,
+        summary: &mut LibraryMergeSummary,
+    ) {
+        let name = other.folder_name.clone();
+        let index = match folders.iter().position(|f| f.folder_name == name) {
+            Some(index) => {
+                let folder = &mut folders[index];
+                let before = folder.world_ids.len();
+                for id in other.world_ids {
+                    if !folder.world_ids.contains(&id) {
+                        folder.world_ids.push(id);
+                    }
+                }
+                if folder.world_ids.len() != before {
+                    summary.folders_merged += 1;
+                }
+                index
+            }
+            None => {
+                let mut folder = other;
+                folder.share = None;
+                folders.push(folder);
+                summary.folders_added += 1;
+                folders.len() - 1
+            }
+        };
+
+        let folder = &mut folders[index];
+        if custom_data.get_folder_color(&name).is_none() {
+            let color = other_custom_data
+                .get_folder_color(&name)
+                .map(String::as_str);
+            custom_data.set_folder_color(&name, color);
+        }
+        if custom_data.get_folder_icon(&name).is_none() {
+            let icon = other_custom_data.get_folder_icon(&name).map(String::as_str);
+            custom_data.set_folder_icon(&name, icon);
+        }
+        if custom_data.get_folder_description(&name).is_none() {
+            let description = other_custom_data
+                .get_folder_description(&name)
+                .map(String::as_str);
+            custom_data.set_folder_description(&name, description);
+        }
+        folder.color = custom_data.get_folder_color(&name).cloned();
+        folder.icon = custom_data.get_folder_icon(&name).cloned();
+        folder.description = custom_data.get_folder_description(&name).cloned();
+    }
+
+    fn read<T: DeserializeOwned>(path: &Path) -> Result<T, FileError> {
+        let data = fs::read_to_string(path).map_err(|e| {
+            log::error!("Failed to read {:?} to merge: {}", path, e);
+            FileError::FileNotFound
+        })?;
+        serde_json::from_str(&data).map_err(|e| {
+            log::error!("Failed to parse {:?} to merge: {}", path, e);
+            FileError::InvalidFile
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::sorting_service::make_test_world;
+    use chrono::Duration;
+
+    fn make_world(id: &str, memo: &str) -> WorldModel {
+        let mut world = make_test_world(id);
+        world.user_data.memo = memo.to_string();
+        world
+    }
+
+    #[test]
+    fn test_merge_world_unites_flags_and_applies_policy() {
+        let local = make_world("wrld_1", "Local memo");
+        let mut other = make_world("wrld_1", "Other memo");
+        other.user_data.last_checked += Duration::days(1);
+        other.user_data.is_favorite = true;
+        other.user_data.rating = Some(4);
+
+        let mut kept = local.clone();
+        let (changed, conflicts) =
+            LibraryMerge::merge_world(&mut kept, other.clone(), MergePolicy::PreferLocal);
+        assert!(changed);
+        assert_eq!(conflicts, 1);
+        assert_eq!(kept.user_data.memo, "Local memo");
+        assert!(kept.user_data.is_favorite);
+        assert_eq!(kept.user_data.rating, Some(4));
+
+        let mut newer = local;
+        LibraryMerge::merge_world(&mut newer, other, MergePolicy::PreferNewer);
+        assert_eq!(newer.user_data.memo, "Other memo");
+    }
+
+    #[test]
+    fn test_merge_folder_appends_worlds() {
+        let mut folder = FolderModel::new("Chill".to_string());
+        folder.world_ids = vec!["wrld_1".to_string()];
+        let mut folders = vec![folder];
+        let mut other = FolderModel::new("Chill".to_string());
+        other.world_ids = vec!["wrld_2".to_string(), "wrld_1".to_string()];
+        let mut summary = LibraryMergeSummary::default();
+
+        LibraryMerge::merge_folder(
+            &mut folders,
+            other,
+            &CustomData::new(),
+            &mut CustomData::new(),
+            &mut summary,
+        );
+        LibraryMerge::merge_folder(
+            &mut folders,
+            FolderModel::new("Games".to_string()),
+            &CustomData::new(),
+            &mut CustomData::new(),
+            &mut summary,
+        );
+
+        assert_eq!(folders[0].world_ids, vec!["wrld_1", "wrld_2"]);
+        assert_eq!(folders[1].folder_name, "Games");
+        assert_eq!(summary.folders_merged, 1);
+        assert_eq!(summary.folders_added, 1);
+    }
+}
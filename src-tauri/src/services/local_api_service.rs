@@ -0,0 +1,229 @@
+use std::net::Ipv4Addr;
+use std::sync::{Arc, Mutex, PoisonError};
+
+use axum::extract::{Path, Query, Request, State};
+use axum::http::{header, HeaderValue, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use serde::Deserialize;
+use tokio::sync::oneshot;
+
+use crate::app_state::AppState;
+use crate::definitions::{LocalApiSettings, WorldDisplayData};
+use crate::errors::{AppError, EntityError, NetworkError};
+
+use super::folder_manager::FolderData;
+use super::FolderManager;
+
+/// Stops the running server when sent to, or dropped
+static SHUTDOWN: Mutex<Option<oneshot::Sender<()>>> = Mutex::new(None);
+
+#[derive(Clone)]
+struct ApiContext {
+    state: AppState,
+    token: Arc<str>,
+}
+
+#[derive(Deserialize)]
+struct SearchQuery {
+    #[serde(default)]
+    q: String,
+}
+
+/// Service for the optional localhost server, which lets external tools such as stream
+/// overlays and scripts read the library without going through the webview
+/// All endpoints are read-only, and require the generated token
+#[derive(Debug)]
+pub struct LocalApiService;
+
+impl LocalApiService {
+    /// Starts the server on 127.0.0.1, replacing the one already running
+    ///
+    /// # Arguments
+    /// * `state` - The app state the endpoints read from
+    /// * `settings` - The port to listen on and the token clients must send
+    ///
+    /// # Errors
+    /// Returns an error if the token is empty, or the port could not be bound
+    pub async fn start(state: AppState, settings: &LocalApiSettings) -> Result<(), AppError> {
+        Self::stop();
+        if settings.token.is_empty() {
+            return Err(
+                EntityError::InvalidOperation("Local API token is not set".to_string()).into(),
+            );
+        }
+
+        let listener = tokio::net::TcpListener::bind((Ipv4Addr::LOCALHOST, settings.port))
+            .await
+            .map_err(|e| {
+                log::error!("Failed to bind local API to port {}: {}", settings.port, e);
+                NetworkError::ConnectionFailed
+            })?;
+        let router = Self::router(ApiContext {
+            state,
+            token: Arc::from(settings.token.as_str()),
+        });
+
+        let (sender, receiver) = oneshot::channel();
+        *SHUTDOWN.lock().unwrap_or_else(PoisonError::into_inner) = Some(sender);
+        let port = settings.port;
+        tauri::async_runtime::spawn(async move {
+            log::info!("Local API listening on 127.0.0.1:{}", port);
+            let result = axum::serve(listener, router)
+                .with_graceful_shutdown(async {
+                    let _ = receiver.await;
+                })
+                .await;
+            match result {
+                Ok(()) => log::info!("Local API on port {} stopped", port),
+                Err(e) => log::error!("Local API on port {} failed: {}", port, e),
+            }
+        });
+        Ok(())
+    }
+
+    /// Stops the server if it is running
+    pub fn stop() {
+        if let Some(sender) = SHUTDOWN
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take()
+        {
+            let _ = sender.send(());
+        }
+    }
+
+    /// Generates a new random token
+    pub fn generate_token() -> String {
+        hex::encode(rand::random::<[u8; 32]>())
+    }
+
+    fn router(context: ApiContext) -> Router {
+        Router::new()
+            .route("/api/folders", get(folders))
+            .route("/api/folders/{name}/worlds", get(folder_worlds))
+            .route("/api/worlds", get(worlds))
+            .route("/api/search", get(search))
+            .layer(middleware::from_fn_with_state(context.clone(), authorize))
+            .with_state(context)
+    }
+}
+
+/// Rejects requests without the token, and allows browser sources on any origin to read
+/// the responses
+async fn authorize(State(context): State<ApiContext>, request: Request, next: Next) -> Response {
+    let bearer = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .map(str::to_string);
+    // Overlays loaded by URL cannot set headers
+    let provided = bearer.or_else(|| query_token(request.uri().query()?));
+    if provided.as_deref() != Some(&*context.token) {
+        return StatusCode::UNAUTHORIZED.into_response();
+    }
+
+    let mut response = next.run(request).await;
+    response.headers_mut().insert(
+        header::ACCESS_CONTROL_ALLOW_ORIGIN,
+        HeaderValue::from_static("*"),
+    );
+    response
+}
+
+fn query_token(query: &str) -> Option<String> {
+    query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(key, _)| *key == "token")
+        .and_then(|(_, value)| urlencoding::decode(value).ok())
+        .map(|value| value.into_owned())
+}
+
+fn failure(e: AppError) -> (StatusCode, String) {
+    match e {
+        AppError::Entity(EntityError::FolderNotFound(_)) => (StatusCode::NOT_FOUND, e.to_string()),
+        _ => {
+            log::error!("Local API request failed: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, e.to_string())
+        }
+    }
+}
+
+async fn folders(
+    State(context): State<ApiContext>,
+) -> Result<Json<Vec<FolderData>>, (StatusCode, String)> {
+    FolderManager::get_folders(context.state.folder_store(), false)
+        .map(Json)
+        .map_err(failure)
+}
+
+async fn folder_worlds(
+    State(context): State<ApiContext>,
+    Path(name): Path<String>,
+) -> Result<Json<Vec<WorldDisplayData>>, (StatusCode, String)> {
+    FolderManager::get_worlds(
+        name,
+        context.state.folder_store(),
+        context.state.world_store(),
+    )
+    .map(Json)
+    .map_err(failure)
+}
+
+async fn worlds(
+    State(context): State<ApiContext>,
+) -> Result<Json<Vec<WorldDisplayData>>, (StatusCode, String)> {
+    FolderManager::get_all_worlds(context.state.world_store())
+        .map(Json)
+        .map_err(failure)
+}
+
+async fn search(
+    State(context): State<ApiContext>,
+    Query(query): Query<SearchQuery>,
+) -> Result<Json<Vec<WorldDisplayData>>, (StatusCode, String)> {
+    let worlds = FolderManager::get_all_worlds(context.state.world_store()).map_err(failure)?;
+    Ok(Json(matching(worlds, &query.q)))
+}
+
+/// Worlds whose name, author or a tag contains every word of the query, ignoring case
+fn matching(worlds: Vec<WorldDisplayData>, query: &str) -> Vec<WorldDisplayData> {
+    let words: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+    worlds
+        .into_iter()
+        .filter(|world| {
+            let name = world.name.to_lowercase();
+            let author = world.author_name.to_lowercase();
+            words.iter().all(|word| {
+                name.contains(word)
+                    || author.contains(word)
+                    || world.tags.iter().any(|t| t.to_lowercase().contains(word))
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_query_token() {
+        assert_eq!(
+            query_token("q=horror&token=abc%20def").as_deref(),
+            Some("abc def")
+        );
+        assert_eq!(query_token("q=horror"), None);
+    }
+
+    #[test]
+    fn test_generated_tokens_differ() {
+        let token = LocalApiService::generate_token();
+        assert_eq!(token.len(), 64);
+        assert_ne!(token, LocalApiService::generate_token());
+    }
+}
@@ -1,9 +1,6 @@
-use std::{
-    collections::HashMap,
-    fs::File,
-    io::{BufReader, BufWriter},
-    path::PathBuf,
-};
+use std::{collections::HashMap, fs, path::PathBuf};
+
+use super::{LibraryEncryption, Persistence};
 
 pub struct MemoManager {
     path: PathBuf,
@@ -19,22 +16,27 @@ impl MemoManager {
             });
         }
 
-        let file = File::open(&path).map_err(|e| e.to_string())?;
-        let reader = BufReader::new(file);
+        let content = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+        let content = LibraryEncryption::open(&content).map_err(|e| e.to_string())?;
         let memo: HashMap<String, String> =
-            serde_json::from_reader(reader).map_err(|e| e.to_string())?;
+            serde_json::from_str(&content).map_err(|e| e.to_string())?;
 
         Ok(Self { path, memo })
     }
 
     pub fn save(&self) -> Result<(), String> {
-        let file = File::create(&self.path).map_err(|e| e.to_string())?;
-        let writer = BufWriter::new(file);
-        serde_json::to_writer_pretty(writer, &self.memo).map_err(|e| e.to_string())?;
+        let data = serde_json::to_string_pretty(&self.memo).map_err(|e| e.to_string())?;
+        let data = LibraryEncryption::seal(&self.path, &data).map_err(|e| e.to_string())?;
+        Persistence::write(&self.path, &data).map_err(|e| e.to_string())?;
 
         Ok(())
     }
 
+    /// Saves to another file from now on, after the data directory was moved
+    pub fn relocate(&mut self, path: PathBuf) {
+        self.path = path;
+    }
+
     pub fn get_memo(&self, world_id: &str) -> Option<&str> {
         self.memo.get(world_id).map(|s| s.as_str())
     }
@@ -43,6 +45,11 @@ impl MemoManager {
         self.memo.insert(world_id.to_string(), memo.to_string());
     }
 
+    /// Every memo, by world ID
+    pub fn memos(&self) -> &HashMap<String, String> {
+        &self.memo
+    }
+
     pub fn search_memo_text(&self, search_text: &str) -> Vec<String> {
         let search_text = search_text.to_lowercase();
         let results: Vec<String> = self
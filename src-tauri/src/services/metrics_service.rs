@@ -0,0 +1,178 @@
+use std::fs;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use chrono::Utc;
+
+use crate::definitions::{ApiOperationMetrics, AppMetrics};
+
+use super::{FileService, Persistence};
+
+/// Metrics are saved at most this often, as commands are invoked many times a second
+const SAVE_INTERVAL: Duration = Duration::from_secs(10);
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+struct Store {
+    metrics: AppMetrics,
+    last_saved: Option<Instant>,
+}
+
+/// Loaded on first use
+static STORE: Mutex<Option<Store>> = Mutex::new(None);
+
+/// Service for local usage metrics: command invocations, and the outcomes and durations
+/// of VRChat API requests
+///
+/// Nothing is collected unless the user opts in, and the metrics are only stored in
+/// metrics.json, never sent anywhere. They are included in diagnostics bundles
+#[derive(Debug)]
+pub struct MetricsService;
+
+impl MetricsService {
+    /// Reads whether the user opted in, called once on startup
+    pub fn init() {
+        let enabled = FileService::read_custom_data()
+            .preferences
+            .usage_metrics
+            .unwrap_or(false);
+        ENABLED.store(enabled, Ordering::Relaxed);
+    }
+
+    pub fn is_enabled() -> bool {
+        ENABLED.load(Ordering::Relaxed)
+    }
+
+    /// Starts or stops collecting, the metrics collected so far are kept
+    pub fn set_enabled(enabled: bool) {
+        ENABLED.store(enabled, Ordering::Relaxed);
+        if !enabled {
+            Self::save_now();
+        }
+    }
+
+    /// Counts an invocation of a command
+    pub fn record_command(command: &str) {
+        Self::update(|metrics| {
+            *metrics.commands.entry(command.to_string()).or_default() += 1;
+        });
+    }
+
+    /// Counts a request to the VRChat API and how long it took, response or not
+    pub fn record_api_request(operation: &str, duration: Duration) {
+        Self::update(|metrics| {
+            let api = Self::api_entry(metrics, operation);
+            api.requests += 1;
+            api.durations.record(duration.as_millis() as u64);
+        });
+    }
+
+    /// Counts a response from the VRChat API by its status
+    pub fn record_api_response(operation: &str, status: u16) {
+        Self::update(|metrics| {
+            let api = Self::api_entry(metrics, operation);
+            api.responses += 1;
+            match status {
+                429 => api.rate_limited += 1,
+                400.. => api.errors += 1,
+                _ => {}
+            }
+        });
+    }
+
+    /// The metrics collected so far, whether or not collecting is enabled
+    pub fn snapshot() -> AppMetrics {
+        let mut store = STORE.lock().unwrap_or_else(PoisonError::into_inner);
+        Self::loaded(&mut store).metrics.clone()
+    }
+
+    /// Clears the metrics, collecting starts over from now
+    pub fn reset() {
+        let mut store = STORE.lock().unwrap_or_else(PoisonError::into_inner);
+        let store = Self::loaded(&mut store);
+        store.metrics = AppMetrics {
+            since: Some(Utc::now()),
+            ..AppMetrics::default()
+        };
+        Self::save(store);
+    }
+
+    fn api_entry<'a>(metrics: &'a mut AppMetrics, operation: &str) -> &'a mut ApiOperationMetrics {
+        metrics.api.entry(operation.to_string()).or_default()
+    }
+
+    fn update(change: impl FnOnce(&mut AppMetrics)) {
+        if !Self::is_enabled() {
+            return;
+        }
+        let mut store = STORE.lock().unwrap_or_else(PoisonError::into_inner);
+        let store = Self::loaded(&mut store);
+        change(&mut store.metrics);
+        store.metrics.since.get_or_insert_with(Utc::now);
+
+        let due = store
+            .last_saved
+            .map_or(true, |saved| saved.elapsed() >= SAVE_INTERVAL);
+        if due {
+            Self::save(store);
+        }
+    }
+
+    fn save_now() {
+        let mut store = STORE.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(store) = store.as_mut() {
+            Self::save(store);
+        }
+    }
+
+    fn loaded(store: &mut Option<Store>) -> &mut Store {
+        store.get_or_insert_with(|| Store {
+            metrics: Self::read(),
+            last_saved: None,
+        })
+    }
+
+    fn read() -> AppMetrics {
+        let path = FileService::get_metrics_path();
+        if !path.exists() {
+            return AppMetrics::default();
+        }
+        fs::read_to_string(&path)
+            .ok()
+            .and_then(|data| serde_json::from_str(&data).ok())
+            .unwrap_or_else(|| {
+                log::warn!("Failed to read metrics.json, starting over");
+                AppMetrics::default()
+            })
+    }
+
+    fn save(store: &mut Store) {
+        store.last_saved = Some(Instant::now());
+        match serde_json::to_string(&store.metrics) {
+            // Losing the last few seconds of metrics on a crash does not matter
+            Ok(data) => Persistence::write_deferred(FileService::get_metrics_path(), data),
+            Err(e) => log::error!("Failed to serialize metrics: {}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::definitions::DurationHistogram;
+
+    #[test]
+    fn test_histogram_buckets() {
+        let mut histogram = DurationHistogram::default();
+        histogram.record(10);
+        histogram.record(50);
+        histogram.record(300);
+        histogram.record(60_000);
+
+        assert_eq!(histogram.counts[0], 2);
+        assert_eq!(histogram.counts[3], 1);
+        assert_eq!(histogram.counts[histogram.bounds_ms.len()], 1);
+        assert_eq!(histogram.total_ms, 60_360);
+        assert_eq!(histogram.max_ms, 60_000);
+    }
+}
@@ -1,20 +1,113 @@
+pub mod account_manager;
+pub mod api_cache;
 pub mod api_service;
+pub mod author_manager;
+pub mod background_jobs;
+pub mod blacklist_manager;
+pub mod change_feed;
+pub mod clipboard_watcher;
+pub mod concurrent_fetcher;
+pub mod cover_image_service;
+pub mod data_directory_service;
+pub mod data_watcher;
 pub mod delete_data;
+pub mod diagnostics_service;
+pub mod discovery_service;
 pub mod encryption_service;
 pub mod export_service;
+pub mod field_lock_service;
 pub mod file_service;
 pub mod folder_manager;
+pub mod group_folder_service;
+pub mod grouping_service;
+pub mod guest_mode;
+pub mod inbox_service;
 pub mod initialize_service;
+pub mod input_validator;
+pub mod integrity_service;
+pub mod journal;
+pub mod library_encryption;
+pub mod library_events;
+pub mod library_merge;
+pub mod local_api_service;
 pub mod memo_manager;
+pub mod metrics_service;
+pub mod notification_service;
+pub mod osc_service;
+pub mod persistence;
+pub mod photo_scanner;
+pub mod playlist_service;
+pub mod qr_code_service;
+pub mod recently_visited_service;
+pub mod recommendation_service;
+pub mod session_service;
+pub mod settings_profile;
 pub mod share_service;
 pub mod sorting_service;
+pub mod sync_service;
+pub mod tag_service;
+pub mod thumbnail_cache;
+pub mod webhook_service;
+pub mod world_change_service;
+pub mod world_filter;
+pub mod world_history;
+pub mod world_log;
+pub mod world_reference;
+pub mod world_storage;
+pub mod world_watch_service;
 
+pub use account_manager::AccountManager;
 pub use api_service::ApiService;
+pub use author_manager::AuthorManager;
+pub use background_jobs::BackgroundJobs;
+pub use blacklist_manager::BlacklistManager;
+pub use change_feed::ChangeFeed;
+pub use clipboard_watcher::ClipboardWatcher;
+pub use concurrent_fetcher::ConcurrentFetcher;
+pub use cover_image_service::CoverImageService;
+pub use data_directory_service::DataDirectoryService;
+pub use data_watcher::DataWatcher;
 pub use delete_data::delete_data;
+pub use diagnostics_service::DiagnosticsService;
+pub use discovery_service::DiscoveryService;
 pub use encryption_service::EncryptionService;
 pub use export_service::ExportService;
+pub use field_lock_service::FieldLockService;
 pub use file_service::FileService;
 pub use folder_manager::FolderManager;
+pub use group_folder_service::GroupFolderService;
+pub use grouping_service::GroupingService;
+pub use guest_mode::GuestMode;
+pub use inbox_service::InboxService;
 pub use initialize_service::{initialize_app, set_preferences};
+pub use input_validator::InputValidator;
+pub use integrity_service::IntegrityService;
+pub use journal::Journal;
+pub use library_encryption::LibraryEncryption;
+pub use library_events::LibraryEvents;
+pub use library_merge::LibraryMerge;
+pub use local_api_service::LocalApiService;
+pub use metrics_service::MetricsService;
+pub use notification_service::NotificationService;
+pub use osc_service::OscService;
+pub use persistence::Persistence;
+pub use photo_scanner::PhotoScanner;
+pub use playlist_service::PlaylistService;
+pub use qr_code_service::QrCodeService;
+pub use recently_visited_service::RecentlyVisitedService;
+pub use recommendation_service::RecommendationService;
+pub use session_service::SessionService;
+pub use settings_profile::SettingsProfileService;
 pub use share_service::{download_folder, share_folder};
 pub use sorting_service::SortingService;
+pub use sync_service::SyncService;
+pub use tag_service::TagService;
+pub use thumbnail_cache::ThumbnailCache;
+pub use webhook_service::WebhookService;
+pub use world_change_service::WorldChangeService;
+pub use world_filter::WorldFilterService;
+pub use world_history::WorldHistoryService;
+pub use world_log::WorldLog;
+pub use world_reference::WorldReference;
+pub use world_storage::WorldStorage;
+pub use world_watch_service::WorldWatchService;
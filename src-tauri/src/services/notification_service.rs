@@ -0,0 +1,333 @@
+use std::sync::RwLock;
+
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+use crate::definitions::{FolderModel, Notification, NotificationKind};
+use crate::errors::{AppError, ConcurrencyError};
+
+use super::FileService;
+
+/// Maximum number of notifications kept on disk, oldest are dropped first
+const MAX_NOTIFICATIONS: usize = 500;
+
+/// Shares expiring within this many days are reported
+const SHARE_EXPIRY_WARNING_DAYS: i64 = 3;
+
+/// Service for the in-app notification inbox
+/// Every background subsystem reports to the user through here
+#[derive(Debug)]
+pub struct NotificationService;
+
+impl NotificationService {
+    /// Adds notifications to the inbox and saves them
+    /// Notifications which duplicate an existing one are skipped,
+    /// see `is_duplicate` for what counts as a duplicate
+    ///
+    /// # Arguments
+    /// * `kinds` - The notifications to add
+    /// * `notifications` - The notifications store, as a RwLock
+    ///
+    /// # Returns
+    /// The notifications which were actually added
+    ///
+    /// # Errors
+    /// Returns an error if the notifications lock is poisoned
+    /// Returns an error if the notifications could not be saved
+    pub fn add_notifications(
+        kinds: Vec<NotificationKind>,
+        notifications: &RwLock<Vec<Notification>>,
+    ) -> Result<Vec<Notification>, AppError> {
+        if kinds.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut notifications_lock = notifications
+            .write()
+            .map_err(|_| ConcurrencyError::PoisonedLock)?;
+
+        let mut added = vec![];
+        for kind in kinds {
+            if notifications_lock
+                .iter()
+                .any(|n| Self::is_duplicate(n, &kind))
+            {
+                continue;
+            }
+            let notification = Notification::new(kind);
+            notifications_lock.push(notification.clone());
+            added.push(notification);
+        }
+
+        let overflow = notifications_lock.len().saturating_sub(MAX_NOTIFICATIONS);
+        notifications_lock.drain(..overflow);
+
+        FileService::write_notifications(&notifications_lock)?;
+        Ok(added)
+    }
+
+    /// Whether a new notification repeats an existing one
+    /// World notifications only repeat while unread, so a later change is reported again;
+    /// share, update and new world notifications are reported once per share ID, version
+    /// or world
+    fn is_duplicate(existing: &Notification, kind: &NotificationKind) -> bool {
+        match (&existing.kind, kind) {
+            (
+                NotificationKind::WorldUpdated { world_id: a, .. },
+                NotificationKind::WorldUpdated { world_id: b, .. },
+            )
+            | (
+                NotificationKind::WorldUnavailable { world_id: a, .. },
+                NotificationKind::WorldUnavailable { world_id: b, .. },
+            ) => !existing.read && a == b,
+            (
+                NotificationKind::ShareExpiringSoon { share_id: a, .. },
+                NotificationKind::ShareExpiringSoon { share_id: b, .. },
+            ) => a == b,
+            (
+                NotificationKind::UpdateAvailable { version: a },
+                NotificationKind::UpdateAvailable { version: b },
+            ) => a == b,
+            (
+                NotificationKind::NewWorldFromAuthor { world_id: a, .. },
+                NotificationKind::NewWorldFromAuthor { world_id: b, .. },
+            ) => a == b,
+            _ => false,
+        }
+    }
+
+    /// Get all notifications, newest first
+    ///
+    /// # Arguments
+    /// * `notifications` - The notifications store, as a RwLock
+    /// * `unread_only` - Whether to only return unread notifications
+    ///
+    /// # Returns
+    /// A vector of notifications
+    ///
+    /// # Errors
+    /// Returns an error if the notifications lock is poisoned
+    pub fn get_notifications(
+        notifications: &RwLock<Vec<Notification>>,
+        unread_only: bool,
+    ) -> Result<Vec<Notification>, AppError> {
+        let notifications_lock = notifications
+            .read()
+            .map_err(|_| ConcurrencyError::PoisonedLock)?;
+        Ok(notifications_lock
+            .iter()
+            .rev()
+            .filter(|n| !unread_only || !n.read)
+            .cloned()
+            .collect())
+    }
+
+    /// Marks notifications as read
+    /// IDs which do not exist are ignored
+    ///
+    /// # Arguments
+    /// * `ids` - The IDs of the notifications to mark as read, or None to mark all
+    /// * `notifications` - The notifications store, as a RwLock
+    ///
+    /// # Returns
+    /// Ok if the notifications were marked successfully
+    ///
+    /// # Errors
+    /// Returns an error if the notifications lock is poisoned
+    /// Returns an error if the notifications could not be saved
+    pub fn mark_read(
+        ids: Option<&[Uuid]>,
+        notifications: &RwLock<Vec<Notification>>,
+    ) -> Result<(), AppError> {
+        let mut notifications_lock = notifications
+            .write()
+            .map_err(|_| ConcurrencyError::PoisonedLock)?;
+
+        for notification in notifications_lock.iter_mut() {
+            if ids.map_or(true, |ids| ids.contains(&notification.id)) {
+                notification.read = true;
+            }
+        }
+
+        FileService::write_notifications(&notifications_lock)?;
+        Ok(())
+    }
+
+    /// Removes all read notifications
+    ///
+    /// # Arguments
+    /// * `notifications` - The notifications store, as a RwLock
+    ///
+    /// # Returns
+    /// Ok if the notifications were cleared successfully
+    ///
+    /// # Errors
+    /// Returns an error if the notifications lock is poisoned
+    /// Returns an error if the notifications could not be saved
+    pub fn clear_read(notifications: &RwLock<Vec<Notification>>) -> Result<(), AppError> {
+        let mut notifications_lock = notifications
+            .write()
+            .map_err(|_| ConcurrencyError::PoisonedLock)?;
+        notifications_lock.retain(|n| !n.read);
+        FileService::write_notifications(&notifications_lock)?;
+        Ok(())
+    }
+
+    /// Finds folder shares which will expire soon
+    ///
+    /// # Arguments
+    /// * `folders` - The list of folders, as a RwLock
+    ///
+    /// # Returns
+    /// A notification for every share expiring within the warning window
+    ///
+    /// # Errors
+    /// Returns an error if the folders lock is poisoned
+    pub fn find_expiring_shares(
+        folders: &RwLock<Vec<FolderModel>>,
+    ) -> Result<Vec<NotificationKind>, AppError> {
+        let folders_lock = folders.read().map_err(|_| ConcurrencyError::PoisonedLock)?;
+        let now = Utc::now();
+        let threshold = now + Duration::days(SHARE_EXPIRY_WARNING_DAYS);
+
+        Ok(folders_lock
+            .iter()
+            .filter_map(|folder| {
+                let share = folder.share.as_ref()?;
+                (share.expiry_time > now && share.expiry_time <= threshold).then(|| {
+                    NotificationKind::ShareExpiringSoon {
+                        folder_name: folder.folder_name.clone(),
+                        share_id: share.id.clone(),
+                        expiry_time: share.expiry_time,
+                    }
+                })
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::definitions::ShareInfo;
+
+    fn world_updated(world_id: &str) -> NotificationKind {
+        NotificationKind::WorldUpdated {
+            world_id: world_id.to_string(),
+            world_name: "World".to_string(),
+            old_update: Utc::now() - Duration::days(1),
+            new_update: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_is_duplicate() {
+        let update = NotificationKind::UpdateAvailable {
+            version: "1.0.0".to_string(),
+        };
+        let mut existing = Notification::new(update.clone());
+        existing.read = true;
+        assert!(NotificationService::is_duplicate(&existing, &update));
+
+        let world = NotificationKind::WorldUnavailable {
+            world_id: "wrld_1".to_string(),
+            world_name: "World".to_string(),
+            reason: "private".to_string(),
+        };
+        let mut existing = Notification::new(world.clone());
+        assert!(NotificationService::is_duplicate(&existing, &world));
+        existing.read = true;
+        assert!(!NotificationService::is_duplicate(&existing, &world));
+    }
+
+    #[test]
+    fn test_add_notifications_skips_duplicates() {
+        let notifications = RwLock::new(vec![]);
+
+        let added = NotificationService::add_notifications(
+            vec![world_updated("wrld_1"), world_updated("wrld_1")],
+            &notifications,
+        )
+        .unwrap();
+        assert_eq!(added.len(), 1);
+
+        // Once read, a later update of the same world is reported again
+        NotificationService::mark_read(None, &notifications).unwrap();
+        let added =
+            NotificationService::add_notifications(vec![world_updated("wrld_1")], &notifications)
+                .unwrap();
+        assert_eq!(added.len(), 1);
+        assert_eq!(notifications.read().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_add_notifications_drops_oldest() {
+        let notifications = RwLock::new(vec![]);
+        let kinds = (0..=MAX_NOTIFICATIONS)
+            .map(|i| world_updated(&format!("wrld_{}", i)))
+            .collect();
+
+        NotificationService::add_notifications(kinds, &notifications).unwrap();
+
+        let notifications = notifications.read().unwrap();
+        assert_eq!(notifications.len(), MAX_NOTIFICATIONS);
+        assert!(matches!(
+            &notifications[0].kind,
+            NotificationKind::WorldUpdated { world_id, .. } if world_id == "wrld_1"
+        ));
+    }
+
+    #[test]
+    fn test_mark_read_and_clear_read() {
+        let notifications = RwLock::new(vec![]);
+        let added = NotificationService::add_notifications(
+            vec![world_updated("wrld_1"), world_updated("wrld_2")],
+            &notifications,
+        )
+        .unwrap();
+
+        // Unknown IDs are ignored
+        NotificationService::mark_read(Some(&[added[0].id, Uuid::new_v4()]), &notifications)
+            .unwrap();
+
+        let unread = NotificationService::get_notifications(&notifications, true).unwrap();
+        assert_eq!(unread.len(), 1);
+        assert_eq!(unread[0].id, added[1].id);
+
+        // Newest first
+        let all = NotificationService::get_notifications(&notifications, false).unwrap();
+        let ids: Vec<Uuid> = all.iter().map(|n| n.id).collect();
+        assert_eq!(ids, vec![added[1].id, added[0].id]);
+
+        NotificationService::clear_read(&notifications).unwrap();
+        let remaining = NotificationService::get_notifications(&notifications, false).unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].id, added[1].id);
+    }
+
+    #[test]
+    fn test_find_expiring_shares() {
+        let shared = |name: &str, expires_in: Duration| {
+            let mut folder = FolderModel::new(name.to_string());
+            folder.share = Some(ShareInfo {
+                id: format!("share_{}", name),
+                expiry_time: Utc::now() + expires_in,
+            });
+            folder
+        };
+        let folders = RwLock::new(vec![
+            FolderModel::new("Unshared".to_string()),
+            shared("Expired", -Duration::hours(1)),
+            shared("Soon", Duration::days(1)),
+            shared("Later", Duration::days(SHARE_EXPIRY_WARNING_DAYS + 1)),
+        ]);
+
+        let expiring = NotificationService::find_expiring_shares(&folders).unwrap();
+        assert_eq!(expiring.len(), 1);
+        assert!(matches!(
+            &expiring[0],
+            NotificationKind::ShareExpiringSoon { folder_name, share_id, .. }
+                if folder_name == "Soon" && share_id == "share_Soon"
+        ));
+    }
+}
@@ -0,0 +1,123 @@
+use std::net::UdpSocket;
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use crate::errors::{AppError, NetworkError};
+
+/// VRChat listens for OSC on this address
+const VRCHAT_OSC_ADDRESS: &str = "127.0.0.1:9000";
+const CHATBOX_INPUT: &str = "/chatbox/input";
+/// Longest message the chatbox shows
+pub const CHATBOX_MAX_CHARS: usize = 144;
+/// VRChat drops chatbox messages sent in quick succession
+const CHATBOX_COOLDOWN: Duration = Duration::from_secs(3);
+
+static LAST_SENT: Mutex<Option<Instant>> = Mutex::new(None);
+
+/// Service for sending messages to VRChat over OSC
+#[derive(Debug)]
+pub struct OscService;
+
+impl OscService {
+    /// Claims the chatbox for a message, unless the previous one was sent too recently
+    ///
+    /// # Returns
+    /// Ok if a message may be sent now
+    ///
+    /// # Errors
+    /// Returns the time left until the chatbox cooldown ends
+    pub fn reserve_chatbox() -> Result<(), Duration> {
+        let mut last_sent = LAST_SENT.lock().unwrap_or_else(PoisonError::into_inner);
+        let now = Instant::now();
+        if let Some(last) = *last_sent {
+            let elapsed = now.duration_since(last);
+            if elapsed < CHATBOX_COOLDOWN {
+                return Err(CHATBOX_COOLDOWN - elapsed);
+            }
+        }
+        *last_sent = Some(now);
+        Ok(())
+    }
+
+    /// Shows a message in the user's chatbox in VRChat, cut to the chatbox length
+    /// Call `reserve_chatbox` first to respect the chatbox cooldown
+    ///
+    /// # Arguments
+    /// * `text` - The message to show
+    ///
+    /// # Errors
+    /// Returns an error if the message could not be sent
+    /// OSC is fire and forget, so a closed or OSC-disabled VRChat is not an error
+    pub fn send_chatbox(text: &str) -> Result<(), AppError> {
+        let text: String = text.chars().take(CHATBOX_MAX_CHARS).collect();
+        // Send right away instead of filling the keyboard, without the notification sound
+        let message = Self::encode_message(CHATBOX_INPUT, &text, true, false);
+
+        let socket = UdpSocket::bind("127.0.0.1:0").map_err(|e| {
+            log::error!("Failed to open OSC socket: {}", e);
+            NetworkError::ConnectionFailed
+        })?;
+        socket.send_to(&message, VRCHAT_OSC_ADDRESS).map_err(|e| {
+            log::warn!("Failed to send OSC message: {}", e);
+            NetworkError::ConnectionFailed
+        })?;
+        log::info!("Sent chatbox message over OSC: {}", text);
+        Ok(())
+    }
+
+    /// Encodes an OSC message with a string argument followed by two booleans
+    fn encode_message(address: &str, text: &str, first: bool, second: bool) -> Vec<u8> {
+        let mut message = Vec::new();
+        Self::push_string(&mut message, address);
+        let type_tags = format!(
+            ",s{}{}",
+            if first { 'T' } else { 'F' },
+            if second { 'T' } else { 'F' }
+        );
+        Self::push_string(&mut message, &type_tags);
+        Self::push_string(&mut message, text);
+        message
+    }
+
+    /// OSC strings are null terminated and padded to a multiple of 4 bytes
+    fn push_string(message: &mut Vec<u8>, value: &str) {
+        message.extend_from_slice(value.as_bytes());
+        let padding = 4 - value.len() % 4;
+        message.extend(std::iter::repeat(0).take(padding));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_message() {
+        let message = OscService::encode_message("/chatbox/input", "hi", true, false);
+        let mut expected = b"/chatbox/input\0\0".to_vec();
+        expected.extend_from_slice(b",sTF\0\0\0\0");
+        expected.extend_from_slice(b"hi\0\0");
+        assert_eq!(message, expected);
+    }
+
+    #[test]
+    fn test_chatbox_cooldown() {
+        assert!(OscService::reserve_chatbox().is_ok());
+        let remaining = OscService::reserve_chatbox().unwrap_err();
+        assert!(remaining <= CHATBOX_COOLDOWN);
+    }
+
+    #[test]
+    fn test_push_string_padding() {
+        // A string filling whole words still gets its null terminator
+        let mut message = vec![];
+        OscService::push_string(&mut message, "abcd");
+        assert_eq!(message, b"abcd\0\0\0\0");
+
+        // Padding counts bytes, not characters
+        let mut message = vec![];
+        OscService::push_string(&mut message, "ワールド");
+        assert_eq!(message.len(), 16);
+        assert_eq!(&message[12..], b"\0\0\0\0");
+    }
+}
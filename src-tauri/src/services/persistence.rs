@@ -0,0 +1,396 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::BTreeMap;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Condvar, Mutex, MutexGuard, OnceLock, PoisonError};
+
+use crate::errors::FileError;
+
+use super::file_service::DATA_LOCATION_FILE;
+use super::FileService;
+
+/// Reports the result of a write to the caller waiting for it
+enum Ack {
+    /// A synchronous caller, blocked until the write is done
+    Blocking(Sender<Result<(), FileError>>),
+    /// An async caller, awaiting the write
+    Async(tokio::sync::oneshot::Sender<Result<(), FileError>>),
+}
+
+impl Ack {
+    fn send(self, result: Result<(), FileError>) {
+        // The caller may have given up waiting, nothing to do then
+        match self {
+            Ack::Blocking(ack) => {
+                let _ = ack.send(result);
+            }
+            Ack::Async(ack) => {
+                let _ = ack.send(result);
+            }
+        }
+    }
+}
+
+enum WriteCommand {
+    Write {
+        path: PathBuf,
+        data: Vec<u8>,
+        /// None for deferred writes, which only log failures
+        ack: Option<Ack>,
+    },
+    Flush {
+        ack: tokio::sync::oneshot::Sender<()>,
+    },
+    /// Stops the writer until the write barrier is lifted
+    Pause { ack: Sender<()> },
+}
+
+/// State of the write barrier, raised while the data directory is moved
+struct Barrier {
+    /// Set while the data is copied, writes wait until it is cleared
+    paused: bool,
+    /// The old and new data directory, once the data has been copied
+    moved: Option<(PathBuf, PathBuf)>,
+}
+
+impl Barrier {
+    /// Points a path in the old data directory at the same file in the new one
+    /// The data location file stays where it is, as it is always in the default directory
+    fn rebase(&self, path: PathBuf) -> PathBuf {
+        if let Some((from, to)) = &self.moved {
+            if let Ok(relative) = path.strip_prefix(from) {
+                if relative != Path::new(DATA_LOCATION_FILE) {
+                    return to.join(relative);
+                }
+            }
+        }
+        path
+    }
+}
+
+static WRITER: OnceLock<Sender<WriteCommand>> = OnceLock::new();
+
+/// Hash of the data last written to each file, to tell the app's own writes from external ones
+static LAST_WRITTEN: Mutex<BTreeMap<PathBuf, u64>> = Mutex::new(BTreeMap::new());
+
+static BARRIER: Mutex<Barrier> = Mutex::new(Barrier {
+    paused: false,
+    moved: None,
+});
+
+/// Notified when the write barrier is lifted
+static BARRIER_LIFTED: Condvar = Condvar::new();
+
+/// Single-writer persistence actor
+///
+/// Every file write is queued to one dedicated thread and performed in the order it was
+/// queued, so writes to the same file never interleave and a later save can never be
+/// overtaken by an earlier one. The writer thread is started on first use
+#[derive(Debug)]
+pub struct Persistence;
+
+/// Holds back every write while the data directory is copied, see `Persistence::pause`
+/// Writes are let through again when it is dropped
+#[derive(Debug)]
+pub struct WriteBarrier(());
+
+impl WriteBarrier {
+    /// Lets writes through again once the data has been copied, writing those for files in
+    /// `from` to the same files in `to`, including writes queued while the data was copied
+    ///
+    /// # Arguments
+    /// * `from` - The old data directory
+    /// * `to` - The new data directory
+    pub fn relocate(&self, from: &Path, to: &Path) {
+        let mut barrier = Persistence::barrier();
+        barrier.paused = false;
+        barrier.moved = Some((from.to_path_buf(), to.to_path_buf()));
+        BARRIER_LIFTED.notify_all();
+    }
+}
+
+impl Drop for WriteBarrier {
+    fn drop(&mut self) {
+        let mut barrier = Persistence::barrier();
+        barrier.paused = false;
+        barrier.moved = None;
+        BARRIER_LIFTED.notify_all();
+    }
+}
+
+impl Persistence {
+    /// Writes data to a file and waits until it is durably on disk
+    /// Async callers use `write_async` instead. When called from a sync service inside an async
+    /// command, the runtime worker hands its other tasks off before blocking on the write
+    ///
+    /// # Arguments
+    /// * `path` - Target file path
+    /// * `data` - Data to write, as it should appear on disk
+    ///
+    /// # Returns
+    /// Ok(()) once the data has been written and synced
+    ///
+    /// # Errors
+    /// Returns a FileError if the data could not be written
+    pub fn write(path: &PathBuf, data: &str) -> Result<(), FileError> {
+        Self::write_bytes(path, data.as_bytes())
+    }
+
+    /// Writes binary data to a file like `write`, e.g. compressed worlds
+    ///
+    /// # Arguments
+    /// * `path` - Target file path
+    /// * `data` - Data to write, as it should appear on disk
+    ///
+    /// # Returns
+    /// Ok(()) once the data has been written and synced
+    ///
+    /// # Errors
+    /// Returns a FileError if the data could not be written
+    pub fn write_bytes(path: &PathBuf, data: &[u8]) -> Result<(), FileError> {
+        let (ack, done) = mpsc::channel();
+        Self::send(WriteCommand::Write {
+            path: path.clone(),
+            data: data.to_vec(),
+            ack: Some(Ack::Blocking(ack)),
+        })?;
+        Self::block(|| done.recv()).map_err(|_| FileError::FileWriteError)?
+    }
+
+    /// Writes data to a file like `write`, without blocking the calling task while waiting
+    ///
+    /// # Arguments
+    /// * `path` - Target file path
+    /// * `data` - Data to write, as it should appear on disk
+    ///
+    /// # Returns
+    /// Ok(()) once the data has been written and synced
+    ///
+    /// # Errors
+    /// Returns a FileError if the data could not be written
+    pub async fn write_async(path: PathBuf, data: String) -> Result<(), FileError> {
+        let (ack, done) = tokio::sync::oneshot::channel();
+        Self::send(WriteCommand::Write {
+            path,
+            data: data.into_bytes(),
+            ack: Some(Ack::Async(ack)),
+        })?;
+        done.await.map_err(|_| FileError::FileWriteError)?
+    }
+
+    /// Queues a write without waiting for it, for frequent saves where losing the
+    /// latest one on a crash is acceptable
+    /// Failures are logged, as there is no caller left to report them to
+    ///
+    /// # Arguments
+    /// * `path` - Target file path
+    /// * `data` - Data to write, as it should appear on disk
+    pub fn write_deferred(path: PathBuf, data: String) {
+        if let Err(e) = Self::send(WriteCommand::Write {
+            path,
+            data: data.into_bytes(),
+            ack: None,
+        }) {
+            log::error!("Failed to queue write: {}", e);
+        }
+    }
+
+    /// Waits until every write queued so far is durably on disk
+    ///
+    /// # Errors
+    /// Returns a FileError if the writer thread is no longer running
+    pub async fn flush() -> Result<(), FileError> {
+        let (ack, done) = tokio::sync::oneshot::channel();
+        Self::send(WriteCommand::Flush { ack })?;
+        done.await.map_err(|_| FileError::FileWriteError)
+    }
+
+    /// Raises the write barrier, so the data directory can be copied without changing
+    /// Writes queued before are on disk by the time it returns. Writes made while it is
+    /// held wait, queued ones in the writer and direct ones in `write_direct`
+    ///
+    /// # Returns
+    /// The barrier, which lifts when it is dropped
+    ///
+    /// # Errors
+    /// Returns a FileError if the writer thread is no longer running
+    pub fn pause() -> Result<WriteBarrier, FileError> {
+        // Waits for a direct write in progress, or another barrier
+        Self::wait_for_barrier().paused = true;
+        let barrier = WriteBarrier(());
+
+        let (ack, paused) = mpsc::channel();
+        Self::send(WriteCommand::Pause { ack })?;
+        Self::block(|| paused.recv()).map_err(|_| FileError::FileWriteError)?;
+        Ok(barrier)
+    }
+
+    /// Makes a write which can't be queued, e.g. an append, without racing a move of the
+    /// data directory
+    /// Waits while the data is copied, and writes to the new data directory once it moved
+    /// `write` must not queue writes itself, as the writer may be waiting for it
+    ///
+    /// # Arguments
+    /// * `path` - The file written to
+    /// * `write` - Writes the file, given the path to write it at
+    ///
+    /// # Returns
+    /// What `write` returned
+    pub fn write_direct<T>(path: &Path, write: impl FnOnce(&Path) -> T) -> T {
+        // Held while writing, so the data is not copied halfway through the write
+        let barrier = Self::wait_for_barrier();
+        write(&barrier.rebase(path.to_path_buf()))
+    }
+
+    /// Checks whether a file still holds exactly what the app last wrote to it
+    /// Used to ignore file change notifications caused by the app's own writes
+    ///
+    /// # Arguments
+    /// * `path` - The file to check, as passed when writing it
+    pub fn is_own_write(path: &Path) -> bool {
+        let Ok(contents) = fs::read(path) else {
+            return false;
+        };
+        LAST_WRITTEN
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(path)
+            == Some(&Self::hash(&contents))
+    }
+
+    fn barrier() -> MutexGuard<'static, Barrier> {
+        BARRIER.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn wait_for_barrier() -> MutexGuard<'static, Barrier> {
+        BARRIER_LIFTED
+            .wait_while(Self::barrier(), |barrier| barrier.paused)
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Waits on the writer, moving the other tasks of a runtime worker elsewhere first
+    /// so an async command waiting for a write does not stall them
+    fn block<T>(wait: impl FnOnce() -> T) -> T {
+        match tokio::runtime::Handle::try_current() {
+            Ok(handle) if handle.runtime_flavor() == tokio::runtime::RuntimeFlavor::MultiThread => {
+                tokio::task::block_in_place(wait)
+            }
+            _ => wait(),
+        }
+    }
+
+    fn hash(data: &[u8]) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        data.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    fn send(command: WriteCommand) -> Result<(), FileError> {
+        WRITER
+            .get_or_init(Self::spawn_writer)
+            .send(command)
+            .map_err(|_| {
+                log::error!("Persistence writer is not running");
+                FileError::FileWriteError
+            })
+    }
+
+    fn spawn_writer() -> Sender<WriteCommand> {
+        let (sender, receiver) = mpsc::channel();
+        std::thread::Builder::new()
+            .name("persistence-writer".to_string())
+            .spawn(move || Self::run(receiver))
+            .expect("Failed to start persistence writer thread");
+        sender
+    }
+
+    fn run(receiver: Receiver<WriteCommand>) {
+        for command in receiver {
+            match command {
+                WriteCommand::Write { path, data, ack } => {
+                    let path = Self::barrier().rebase(path);
+                    let result = FileService::write_file_raw(&path, &data);
+                    if result.is_ok() {
+                        LAST_WRITTEN
+                            .lock()
+                            .unwrap_or_else(PoisonError::into_inner)
+                            .insert(path.clone(), Self::hash(&data));
+                    }
+                    match ack {
+                        Some(ack) => ack.send(result),
+                        None => {
+                            if let Err(e) = result {
+                                log::error!("Deferred write to {:?} failed: {}", path, e);
+                            }
+                        }
+                    }
+                }
+                WriteCommand::Flush { ack } => {
+                    let _ = ack.send(());
+                }
+                WriteCommand::Pause { ack } => {
+                    let _ = ack.send(());
+                    drop(Self::wait_for_barrier());
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_writes_apply_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ordered.json");
+
+        for i in 0..20 {
+            Persistence::write_deferred(path.clone(), i.to_string());
+        }
+        Persistence::flush().await.unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "19");
+
+        Persistence::write(&path, "done").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "done");
+
+        Persistence::write_async(path.clone(), "awaited".to_string())
+            .await
+            .unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "awaited");
+        assert!(Persistence::is_own_write(&path));
+
+        fs::write(&path, "edited elsewhere").unwrap();
+        assert!(!Persistence::is_own_write(&path));
+    }
+
+    #[test]
+    fn test_barrier_holds_writes_until_relocated() {
+        let dir = tempfile::tempdir().unwrap();
+        let old_dir = dir.path().join("old");
+        let new_dir = dir.path().join("new");
+        fs::create_dir_all(&old_dir).unwrap();
+        fs::create_dir_all(&new_dir).unwrap();
+
+        let barrier = Persistence::pause().unwrap();
+        let path = old_dir.join("held.json");
+        let writer = {
+            let path = path.clone();
+            std::thread::spawn(move || Persistence::write(&path, "held").unwrap())
+        };
+        std::thread::sleep(std::time::Duration::from_millis(100));
+        assert!(!path.exists());
+
+        barrier.relocate(&old_dir, &new_dir);
+        writer.join().unwrap();
+        drop(barrier);
+        assert!(!path.exists());
+        assert_eq!(
+            fs::read_to_string(new_dir.join("held.json")).unwrap(),
+            "held"
+        );
+    }
+}
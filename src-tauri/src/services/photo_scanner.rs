@@ -0,0 +1,345 @@
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::{self, BufReader, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+use chrono::{DateTime, Utc};
+use directories::UserDirs;
+
+use crate::definitions::{PhotoScanSummary, WorldModel, WorldPhoto};
+use crate::errors::{AppError, ConcurrencyError, FileError};
+
+use super::{FileService, WorldReference};
+
+const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+
+/// Text chunks larger than this are skipped instead of read, metadata is far smaller
+const MAX_TEXT_CHUNK_SIZE: u32 = 1024 * 1024;
+
+/// The photos found by the last scan, cleared when the photo directory changes
+static PHOTO_INDEX: RwLock<Option<PhotoIndex>> = RwLock::new(None);
+
+/// The photos in the photo directory, grouped by the world they were taken in
+#[derive(Debug)]
+struct PhotoIndex {
+    by_world: BTreeMap<String, Vec<WorldPhoto>>,
+    without_world: u32,
+}
+
+/// Service for indexing the photos VRChat saves, using the world ID VRChat writes into
+/// their metadata to tell which world each photo was taken in
+#[derive(Debug)]
+pub struct PhotoScanner;
+
+impl PhotoScanner {
+    /// Gets the directory VRChat photos are read from
+    /// This is the configured directory, or Pictures/VRChat when none is set
+    ///
+    /// # Returns
+    /// The directory, or None if the user has no pictures directory
+    pub fn directory() -> Option<PathBuf> {
+        match FileService::read_custom_data().preferences.photo_directory {
+            Some(directory) => Some(PathBuf::from(directory)),
+            None => Self::default_directory(),
+        }
+    }
+
+    fn default_directory() -> Option<PathBuf> {
+        UserDirs::new().and_then(|dirs| dirs.picture_dir().map(|dir| dir.join("VRChat")))
+    }
+
+    /// Sets the directory VRChat photos are read from, None to use the default
+    /// The photos found in the previous directory are forgotten
+    ///
+    /// # Errors
+    /// Returns an error if the directory does not exist, or the setting could not be saved
+    pub fn set_directory(directory: Option<String>) -> Result<(), AppError> {
+        if let Some(directory) = &directory {
+            if !Path::new(directory).is_dir() {
+                return Err(FileError::FileNotFound.into());
+            }
+        }
+
+        let mut custom_data = FileService::read_custom_data();
+        custom_data.preferences.photo_directory = directory;
+        FileService::write_custom_data(&custom_data)?;
+
+        *PHOTO_INDEX
+            .write()
+            .map_err(|_| ConcurrencyError::PoisonedLock)? = None;
+        Ok(())
+    }
+
+    /// Scans the photo directory, including the monthly folders VRChat creates
+    /// Saved worlds with photos are marked as photographed. Worlds are never unmarked, as
+    /// their photos may just have been moved elsewhere
+    ///
+    /// # Arguments
+    /// * `worlds` - The list of worlds, as a RwLock
+    ///
+    /// # Returns
+    /// A summary of the photos found
+    ///
+    /// # Errors
+    /// Returns an error if the photo directory does not exist
+    /// Returns an error if the worlds lock is poisoned, or the worlds could not be saved
+    pub fn scan(worlds: &RwLock<Vec<WorldModel>>) -> Result<PhotoScanSummary, AppError> {
+        let directory = Self::directory()
+            .filter(|directory| directory.is_dir())
+            .ok_or(FileError::FileNotFound)?;
+
+        let index = Self::index(&directory);
+        let newly_photographed = Self::mark_photographed(&index, worlds)?;
+        let summary = PhotoScanSummary {
+            directory: directory.to_string_lossy().to_string(),
+            photos: index.by_world.values().map(|p| p.len() as u32).sum::<u32>()
+                + index.without_world,
+            without_world: index.without_world,
+            worlds: index.by_world.len() as u32,
+            newly_photographed,
+        };
+        log::info!(
+            "Scanned {} photos in {}, taken in {} worlds",
+            summary.photos,
+            summary.directory,
+            summary.worlds
+        );
+
+        *PHOTO_INDEX
+            .write()
+            .map_err(|_| ConcurrencyError::PoisonedLock)? = Some(index);
+        Ok(summary)
+    }
+
+    /// Gets the photos taken in a world, newest first
+    ///
+    /// # Returns
+    /// The photos found by the last scan, empty if no scan was done yet
+    ///
+    /// # Errors
+    /// Returns an error if the index lock is poisoned
+    pub fn world_photos(world_id: &str) -> Result<Vec<WorldPhoto>, AppError> {
+        let index = PHOTO_INDEX
+            .read()
+            .map_err(|_| ConcurrencyError::PoisonedLock)?;
+        Ok(index
+            .as_ref()
+            .and_then(|index| index.by_world.get(world_id))
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    /// Gets the number of photos taken in each world
+    ///
+    /// # Errors
+    /// Returns an error if the index lock is poisoned
+    pub fn photo_counts() -> Result<BTreeMap<String, u32>, AppError> {
+        let index = PHOTO_INDEX
+            .read()
+            .map_err(|_| ConcurrencyError::PoisonedLock)?;
+        Ok(index
+            .iter()
+            .flat_map(|index| index.by_world.iter())
+            .map(|(world_id, photos)| (world_id.clone(), photos.len() as u32))
+            .collect())
+    }
+
+    fn index(directory: &Path) -> PhotoIndex {
+        let mut files = vec![];
+        if let Err(e) = Self::list_photos(directory, &mut files) {
+            log::warn!("Failed to list photos in {}: {}", directory.display(), e);
+        }
+
+        let mut index = PhotoIndex {
+            by_world: BTreeMap::new(),
+            without_world: 0,
+        };
+        for path in files {
+            let world_id = Self::read_world_id(&path).unwrap_or_else(|e| {
+                log::warn!("Failed to read photo {}: {}", path.display(), e);
+                None
+            });
+            let taken_at = fs::metadata(&path)
+                .and_then(|metadata| metadata.modified())
+                .ok()
+                .map(DateTime::<Utc>::from);
+            let photo = WorldPhoto {
+                path: path.to_string_lossy().to_string(),
+                world_id: world_id.clone(),
+                taken_at,
+            };
+            match world_id {
+                Some(world_id) => index.by_world.entry(world_id).or_default().push(photo),
+                None => index.without_world += 1,
+            }
+        }
+        for photos in index.by_world.values_mut() {
+            photos.sort_by(|a, b| b.taken_at.cmp(&a.taken_at));
+        }
+        index
+    }
+
+    fn list_photos(directory: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+        for entry in fs::read_dir(directory)? {
+            let entry = entry?;
+            let path = entry.path();
+            if entry.file_type()?.is_dir() {
+                Self::list_photos(&path, files)?;
+            } else if path
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("png"))
+            {
+                files.push(path);
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads the world ID from a photo's text chunks
+    /// VRChat writes it into the XMP metadata, and tools like VRCX into the description
+    ///
+    /// # Returns
+    /// The world ID, or None if the photo has none
+    ///
+    /// # Errors
+    /// Returns an error if the file could not be read, or is not a PNG
+    pub fn read_world_id(path: &Path) -> io::Result<Option<String>> {
+        Self::find_in_chunks(BufReader::new(File::open(path)?))
+    }
+
+    fn find_in_chunks<R: Read + Seek>(mut reader: R) -> io::Result<Option<String>> {
+        let mut signature = [0u8; 8];
+        reader.read_exact(&mut signature)?;
+        if signature != PNG_SIGNATURE {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "Not a PNG file"));
+        }
+
+        loop {
+            let mut header = [0u8; 8];
+            match reader.read_exact(&mut header) {
+                Ok(()) => {}
+                // Truncated files are still searched up to where they end
+                Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+                Err(e) => return Err(e),
+            }
+            let length = u32::from_be_bytes([header[0], header[1], header[2], header[3]]);
+            let kind = &header[4..8];
+
+            if kind == b"IEND" {
+                return Ok(None);
+            }
+            if (kind == b"tEXt" || kind == b"iTXt") && length <= MAX_TEXT_CHUNK_SIZE {
+                let mut data = vec![0u8; length as usize];
+                reader.read_exact(&mut data)?;
+                if let Some(world_id) = Self::find_world_id(&String::from_utf8_lossy(&data)) {
+                    return Ok(Some(world_id));
+                }
+                reader.seek(SeekFrom::Current(4))?;
+            } else {
+                // Skips the data and the CRC
+                reader.seek(SeekFrom::Current(length as i64 + 4))?;
+            }
+        }
+    }
+
+    fn find_world_id(text: &str) -> Option<String> {
+        text.match_indices("wrld_").find_map(|(start, _)| {
+            let candidate = text.get(start..start + 41)?;
+            WorldReference::is_world_id(candidate).then(|| candidate.to_string())
+        })
+    }
+
+    /// Marks the saved worlds with photos as photographed
+    ///
+    /// # Returns
+    /// The IDs of the worlds which were not marked before
+    fn mark_photographed(
+        index: &PhotoIndex,
+        worlds: &RwLock<Vec<WorldModel>>,
+    ) -> Result<Vec<String>, AppError> {
+        let mut worlds_lock = worlds.write().map_err(|_| ConcurrencyError::PoisonedLock)?;
+        let mut newly_photographed = vec![];
+        for world in worlds_lock.iter_mut() {
+            if !world.user_data.is_photographed
+                && index.by_world.contains_key(&world.api_data.world_id)
+            {
+                world.user_data.is_photographed = true;
+                newly_photographed.push(world.api_data.world_id.clone());
+            }
+        }
+        if !newly_photographed.is_empty() {
+            FileService::write_worlds(&*worlds_lock)?;
+        }
+        Ok(newly_photographed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    const WORLD_ID: &str = "wrld_12345678-abcd-1234-abcd-123456789abc";
+
+    fn chunk(kind: &[u8], data: &[u8]) -> Vec<u8> {
+        let mut chunk = (data.len() as u32).to_be_bytes().to_vec();
+        chunk.extend_from_slice(kind);
+        chunk.extend_from_slice(data);
+        // The CRC is not checked
+        chunk.extend_from_slice(&[0; 4]);
+        chunk
+    }
+
+    fn png(chunks: &[Vec<u8>]) -> Cursor<Vec<u8>> {
+        let mut data = PNG_SIGNATURE.to_vec();
+        data.extend(chunk(b"IHDR", &[0; 13]));
+        for c in chunks {
+            data.extend_from_slice(c);
+        }
+        data.extend(chunk(b"IEND", &[]));
+        Cursor::new(data)
+    }
+
+    #[test]
+    fn test_world_id_is_read_from_xmp() {
+        let xmp = format!(
+            "XML:com.adobe.xmp\0\0\0\0\0<x:xmpmeta><vrc:WorldID>{}</vrc:WorldID></x:xmpmeta>",
+            WORLD_ID
+        );
+        let photo = png(&[
+            chunk(b"IDAT", &[1, 2, 3, 4]),
+            chunk(b"iTXt", xmp.as_bytes()),
+        ]);
+        assert_eq!(
+            PhotoScanner::find_in_chunks(photo).unwrap(),
+            Some(WORLD_ID.to_string())
+        );
+    }
+
+    #[test]
+    fn test_photos_without_world_id() {
+        let description = chunk(
+            b"tEXt",
+            b"Description\0{\"world\":{\"id\":\"wrld_invalid\"}}",
+        );
+        assert_eq!(
+            PhotoScanner::find_in_chunks(png(&[description])).unwrap(),
+            None
+        );
+        assert!(PhotoScanner::find_in_chunks(Cursor::new(b"not a png".to_vec())).is_err());
+    }
+
+    #[test]
+    fn test_find_world_id_checks_the_format() {
+        assert_eq!(
+            PhotoScanner::find_world_id(&format!("id: {}.", WORLD_ID)),
+            Some(WORLD_ID.to_string())
+        );
+        assert_eq!(PhotoScanner::find_world_id("wrld_1234"), None);
+        assert_eq!(
+            PhotoScanner::find_world_id("wrld_12345678_abcd_1234_abcd_123456789abc"),
+            None
+        );
+    }
+}
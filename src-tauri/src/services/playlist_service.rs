@@ -0,0 +1,228 @@
+use std::sync::RwLock;
+
+use crate::definitions::{FolderModel, Playlist};
+use crate::errors::{AppError, ConcurrencyError, EntityError};
+
+/// Service for playing through a folder in order, for world-hopping events
+/// Only one playlist runs at a time, and it is not persisted across restarts
+#[derive(Debug)]
+pub struct PlaylistService;
+
+impl PlaylistService {
+    /// Starts playing a folder, replacing any running playlist
+    /// No instance is created until the first call to `next_world`
+    ///
+    /// # Arguments
+    /// * `folder_name` - The name of the folder to play
+    /// * `folders` - The list of folders, as a RwLock
+    /// * `playlist` - The running playlist, as a RwLock
+    ///
+    /// # Returns
+    /// The new playlist
+    ///
+    /// # Errors
+    /// Returns an error if the folder is not found or has no worlds
+    /// Returns an error if any lock is poisoned
+    pub fn start(
+        folder_name: String,
+        folders: &RwLock<Vec<FolderModel>>,
+        playlist: &RwLock<Option<Playlist>>,
+    ) -> Result<Playlist, AppError> {
+        let world_ids = {
+            let folders_lock = folders.read().map_err(|_| ConcurrencyError::PoisonedLock)?;
+            folders_lock
+                .iter()
+                .find(|f| f.folder_name == folder_name)
+                .map(|f| f.world_ids.clone())
+                .ok_or_else(|| EntityError::FolderNotFound(folder_name.clone()))?
+        };
+        if world_ids.is_empty() {
+            return Err(EntityError::InvalidOperation(format!(
+                "folder {} has no worlds to play",
+                folder_name
+            ))
+            .into());
+        }
+
+        let new_playlist = Playlist::new(folder_name, world_ids);
+        let mut playlist_lock = playlist.write().map_err(|_| ConcurrencyError::PoisonedLock)?;
+        *playlist_lock = Some(new_playlist.clone());
+        Ok(new_playlist)
+    }
+
+    /// Stops the running playlist, if any
+    ///
+    /// # Arguments
+    /// * `playlist` - The running playlist, as a RwLock
+    ///
+    /// # Errors
+    /// Returns an error if the lock is poisoned
+    pub fn stop(playlist: &RwLock<Option<Playlist>>) -> Result<(), AppError> {
+        let mut playlist_lock = playlist.write().map_err(|_| ConcurrencyError::PoisonedLock)?;
+        *playlist_lock = None;
+        Ok(())
+    }
+
+    /// Gets the running playlist
+    ///
+    /// # Arguments
+    /// * `playlist` - The running playlist, as a RwLock
+    ///
+    /// # Returns
+    /// The running playlist, or None if no playlist is running
+    ///
+    /// # Errors
+    /// Returns an error if the lock is poisoned
+    pub fn get(playlist: &RwLock<Option<Playlist>>) -> Result<Option<Playlist>, AppError> {
+        let playlist_lock = playlist.read().map_err(|_| ConcurrencyError::PoisonedLock)?;
+        Ok(playlist_lock.clone())
+    }
+
+    /// Finds the world after the current one, without moving the playlist
+    /// The playlist only moves once `set_position` is called, so a failed launch can be retried
+    ///
+    /// # Arguments
+    /// * `playlist` - The running playlist, as a RwLock
+    ///
+    /// # Returns
+    /// The index and ID of the next world
+    ///
+    /// # Errors
+    /// Returns an error if no playlist is running or the last world has been reached
+    /// Returns an error if the lock is poisoned
+    pub fn next_world(playlist: &RwLock<Option<Playlist>>) -> Result<(usize, String), AppError> {
+        Self::world_at(playlist, |position| match position {
+            Some(position) => Some(position + 1),
+            None => Some(0),
+        })
+    }
+
+    /// Finds the world before the current one, without moving the playlist
+    ///
+    /// # Arguments
+    /// * `playlist` - The running playlist, as a RwLock
+    ///
+    /// # Returns
+    /// The index and ID of the previous world
+    ///
+    /// # Errors
+    /// Returns an error if no playlist is running or the first world is being played
+    /// Returns an error if the lock is poisoned
+    pub fn previous_world(
+        playlist: &RwLock<Option<Playlist>>,
+    ) -> Result<(usize, String), AppError> {
+        Self::world_at(playlist, |position| position.and_then(|p| p.checked_sub(1)))
+    }
+
+    /// Moves the running playlist to a world
+    ///
+    /// # Arguments
+    /// * `position` - The index of the world now being played
+    /// * `playlist` - The running playlist, as a RwLock
+    ///
+    /// # Returns
+    /// The updated playlist
+    ///
+    /// # Errors
+    /// Returns an error if no playlist is running
+    /// Returns an error if the lock is poisoned
+    pub fn set_position(
+        position: usize,
+        playlist: &RwLock<Option<Playlist>>,
+    ) -> Result<Playlist, AppError> {
+        let mut playlist_lock = playlist.write().map_err(|_| ConcurrencyError::PoisonedLock)?;
+        let current = playlist_lock.as_mut().ok_or_else(Self::not_running)?;
+        current.position = Some(position.min(current.world_ids.len().saturating_sub(1)));
+        Ok(current.clone())
+    }
+
+    fn world_at(
+        playlist: &RwLock<Option<Playlist>>,
+        target: impl FnOnce(Option<usize>) -> Option<usize>,
+    ) -> Result<(usize, String), AppError> {
+        let playlist_lock = playlist.read().map_err(|_| ConcurrencyError::PoisonedLock)?;
+        let current = playlist_lock.as_ref().ok_or_else(Self::not_running)?;
+        target(current.position)
+            .and_then(|index| current.world_ids.get(index).map(|id| (index, id.clone())))
+            .ok_or_else(|| {
+                EntityError::InvalidOperation("no more worlds in the playlist".to_string()).into()
+            })
+    }
+
+    fn not_running() -> AppError {
+        EntityError::InvalidOperation("no playlist is running".to_string()).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup_folders() -> RwLock<Vec<FolderModel>> {
+        let mut folder = FolderModel::new("Hop".to_string());
+        folder.world_ids = vec!["wrld_a".to_string(), "wrld_b".to_string()];
+        RwLock::new(vec![folder, FolderModel::new("Empty".to_string())])
+    }
+
+    #[test]
+    fn test_start_playlist() {
+        let folders = setup_folders();
+        let playlist = RwLock::new(None);
+
+        let started = PlaylistService::start("Hop".to_string(), &folders, &playlist).unwrap();
+        assert_eq!(started.world_ids, vec!["wrld_a", "wrld_b"]);
+        assert_eq!(started.position, None);
+
+        assert!(PlaylistService::start("Empty".to_string(), &folders, &playlist).is_err());
+        assert!(PlaylistService::start("Missing".to_string(), &folders, &playlist).is_err());
+        // A failed start leaves the running playlist in place
+        assert_eq!(PlaylistService::get(&playlist).unwrap(), Some(started));
+    }
+
+    #[test]
+    fn test_step_through_playlist() {
+        let folders = setup_folders();
+        let playlist = RwLock::new(None);
+        assert!(PlaylistService::next_world(&playlist).is_err());
+
+        PlaylistService::start("Hop".to_string(), &folders, &playlist).unwrap();
+        assert!(PlaylistService::previous_world(&playlist).is_err());
+
+        let (index, world_id) = PlaylistService::next_world(&playlist).unwrap();
+        assert_eq!((index, world_id.as_str()), (0, "wrld_a"));
+        // Peeking does not move the playlist
+        assert_eq!(PlaylistService::next_world(&playlist).unwrap().0, 0);
+
+        PlaylistService::set_position(index, &playlist).unwrap();
+        let (index, world_id) = PlaylistService::next_world(&playlist).unwrap();
+        assert_eq!((index, world_id.as_str()), (1, "wrld_b"));
+
+        PlaylistService::set_position(index, &playlist).unwrap();
+        assert!(PlaylistService::next_world(&playlist).is_err());
+        assert_eq!(PlaylistService::previous_world(&playlist).unwrap().0, 0);
+
+        PlaylistService::stop(&playlist).unwrap();
+        assert_eq!(PlaylistService::get(&playlist).unwrap(), None);
+    }
+
+    #[test]
+    fn test_set_position() {
+        let folders = setup_folders();
+        let playlist = RwLock::new(None);
+        let result = PlaylistService::set_position(0, &playlist);
+        assert!(matches!(
+            result,
+            Err(AppError::Entity(EntityError::InvalidOperation(_)))
+        ));
+
+        PlaylistService::start("Hop".to_string(), &folders, &playlist).unwrap();
+        // Positions past the end stop at the last world
+        let moved = PlaylistService::set_position(5, &playlist).unwrap();
+        assert_eq!(moved.position, Some(1));
+        assert_eq!(PlaylistService::get(&playlist).unwrap(), Some(moved));
+
+        // Starting again plays the folder from the beginning
+        let restarted = PlaylistService::start("Hop".to_string(), &folders, &playlist).unwrap();
+        assert_eq!(restarted.position, None);
+    }
+}
@@ -0,0 +1,120 @@
+use std::fs;
+use std::path::PathBuf;
+
+use qrcode::{Color, EcLevel, QrCode};
+
+use crate::errors::{AppError, EntityError, FileError};
+
+use super::FileService;
+
+/// Pixels per module, large enough for posters and stream overlays
+const MODULE_SIZE: u32 = 10;
+
+/// Light modules around the code, the minimum scanners expect
+const QUIET_ZONE: u32 = 4;
+
+/// Service for rendering links as QR codes
+#[derive(Debug)]
+pub struct QrCodeService;
+
+impl QrCodeService {
+    /// Renders text as a black and white QR code
+    /// Medium error correction is used, so codes still scan when partly covered
+    ///
+    /// # Returns
+    /// The PNG data
+    ///
+    /// # Errors
+    /// Returns an error if the text is too long for a QR code
+    pub fn render_png(text: &str) -> Result<Vec<u8>, AppError> {
+        let code = QrCode::with_error_correction_level(text, EcLevel::M).map_err(|e| {
+            EntityError::InvalidOperation(format!("Failed to create QR code: {}", e))
+        })?;
+        let modules = code.width() as u32;
+        let size = (modules + QUIET_ZONE * 2) * MODULE_SIZE;
+        let colors = code.to_colors();
+
+        let mut pixels = vec![0xFF_u8; (size * size) as usize];
+        for (i, color) in colors.iter().enumerate() {
+            if *color != Color::Dark {
+                continue;
+            }
+            let x = (i as u32 % modules + QUIET_ZONE) * MODULE_SIZE;
+            let y = (i as u32 / modules + QUIET_ZONE) * MODULE_SIZE;
+            for row in y..y + MODULE_SIZE {
+                let start = (row * size + x) as usize;
+                pixels[start..start + MODULE_SIZE as usize].fill(0);
+            }
+        }
+
+        let mut png_data = vec![];
+        let mut encoder = png::Encoder::new(&mut png_data, size, size);
+        encoder.set_color(png::ColorType::Grayscale);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder
+            .write_header()
+            .map_err(|_| FileError::FileWriteError)?;
+        writer
+            .write_image_data(&pixels)
+            .map_err(|_| FileError::FileWriteError)?;
+        writer.finish().map_err(|_| FileError::FileWriteError)?;
+        Ok(png_data)
+    }
+
+    /// Saves a rendered QR code to the exports folder
+    ///
+    /// # Arguments
+    /// * `name` - Identifies the code in the file name, e.g. the world ID
+    /// * `png_data` - The PNG data
+    ///
+    /// # Returns
+    /// The path of the saved file
+    ///
+    /// # Errors
+    /// Returns an error if the file could not be written
+    pub fn save_png(name: &str, png_data: &[u8]) -> Result<PathBuf, AppError> {
+        let exports_dir = FileService::get_exports_dir();
+        fs::create_dir_all(&exports_dir).map_err(|_| FileError::FileWriteError)?;
+
+        let name: String = name
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
+        let path = exports_dir.join(format!("qr_{}_{}.png", name, timestamp));
+        fs::write(&path, png_data).map_err(|_| FileError::FileWriteError)?;
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_png_is_a_scannable_size() {
+        let png_data = QrCodeService::render_png(
+            "https://vrchat.com/home/world/wrld_4cf554b4-430c-4f8f-b53e-1f294eed230b",
+        )
+        .unwrap();
+
+        let decoder = png::Decoder::new(png_data.as_slice());
+        let reader = decoder.read_info().unwrap();
+        let info = reader.info();
+        assert_eq!(info.width, info.height);
+        assert_eq!(info.width % MODULE_SIZE, 0);
+        // Version 1 codes are 21 modules wide, and this link needs a larger one
+        assert!(info.width > (21 + QUIET_ZONE * 2) * MODULE_SIZE);
+    }
+
+    #[test]
+    fn test_render_png_rejects_oversized_text() {
+        assert!(QrCodeService::render_png(&"a".repeat(5000)).is_err());
+    }
+}
@@ -0,0 +1,297 @@
+use std::collections::HashSet;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use reqwest::cookie::Jar;
+
+use crate::api::world::ReleaseStatus;
+use crate::app_state::AppState;
+use crate::definitions::{
+    FolderModel, LibraryChange, WorldBlacklist, WorldDisplayData, WorldModel,
+};
+use crate::errors::{AppError, ConcurrencyError};
+use crate::{AUTHENTICATOR, INITSTATE};
+
+use super::{
+    ApiService, BackgroundJobs, BlacklistManager, ChangeFeed, FileService, FolderManager,
+    GuestMode, InboxService,
+};
+
+/// Name of the folder recently visited worlds are imported into
+pub const RECENTLY_VISITED_FOLDER: &str = "Recently Visited";
+
+/// How often recently visited worlds are checked while the import is enabled
+const AUTO_IMPORT_INTERVAL: Duration = Duration::from_secs(30 * 60);
+
+/// Service for capturing recently visited worlds into an auto-managed folder
+#[derive(Debug)]
+pub struct RecentlyVisitedService;
+
+impl RecentlyVisitedService {
+    /// Imports the recently visited worlds which are not in the library yet
+    /// Imported worlds are appended to the Recently Visited folder, which is created if needed,
+    /// and the oldest entries of the folder are removed to keep it within the cap
+    ///
+    /// # Arguments
+    /// * `cookie_store` - The cookie store to use for the API
+    /// * `cap` - The maximum number of worlds kept in the folder
+    /// * `folders` - The list of folders, as a RwLock
+    /// * `worlds` - The list of worlds, as a RwLock
+    /// * `blacklist` - The world blacklist, as a RwLock
+    ///
+    /// # Returns
+    /// The IDs of the imported worlds
+    ///
+    /// # Errors
+    /// Returns an error if the recently visited worlds could not be fetched
+    /// Returns an error if any lock is poisoned
+    /// Returns an error if the folders could not be saved
+    pub async fn import_recently_visited(
+        cookie_store: Arc<Jar>,
+        cap: usize,
+        folders: &RwLock<Vec<FolderModel>>,
+        worlds: &RwLock<Vec<WorldModel>>,
+        blacklist: &RwLock<WorldBlacklist>,
+    ) -> Result<Vec<String>, AppError> {
+        let visited = ApiService::get_recently_visited_worlds(cookie_store.clone()).await?;
+        let visited = BlacklistManager::filter_display_data(visited, blacklist)?;
+
+        let known_ids: HashSet<String> = {
+            let worlds_lock = worlds.read().map_err(|_| ConcurrencyError::PoisonedLock)?;
+            worlds_lock
+                .iter()
+                .map(|w| w.api_data.world_id.clone())
+                .collect()
+        };
+        let new_ids = Self::select_new_worlds(visited, &known_ids, cap);
+        if new_ids.is_empty() {
+            return Ok(vec![]);
+        }
+
+        // The recently visited endpoint lacks some details, so each new world is fetched in full
+        let mut fetched = vec![];
+        for world_id in new_ids {
+            match ApiService::get_world_with_release_status(cookie_store.clone(), &world_id).await {
+                Ok((world, ReleaseStatus::Public)) => fetched.push(world),
+                Ok(_) => log::info!("Skipping non-public recently visited world {}", world_id),
+                Err(e) => log::warn!("Failed to fetch recently visited world {}: {}", world_id, e),
+            }
+        }
+        let fetched_ids: Vec<String> = fetched.iter().map(|w| w.world_id.clone()).collect();
+        FolderManager::add_worlds(worlds, fetched, blacklist)?;
+        ChangeFeed::record_worlds_added(worlds, fetched_ids.clone());
+        // Blacklisted worlds were not added
+        let imported_ids: Vec<String> = {
+            let worlds_lock = worlds.read().map_err(|_| ConcurrencyError::PoisonedLock)?;
+            fetched_ids
+                .into_iter()
+                .filter(|id| worlds_lock.iter().any(|w| &w.api_data.world_id == id))
+                .collect()
+        };
+
+        Self::ensure_folder(folders)?;
+        FolderManager::add_worlds_to_folder(
+            RECENTLY_VISITED_FOLDER.to_string(),
+            imported_ids.clone(),
+            folders,
+            worlds,
+        )?;
+        if !imported_ids.is_empty() {
+            ChangeFeed::record(LibraryChange::AddedToFolder {
+                folder_name: RECENTLY_VISITED_FOLDER.to_string(),
+                world_ids: imported_ids.clone(),
+            });
+        }
+        Self::trim_folder(cap, folders, worlds)?;
+        // Kept in the inbox after they are trimmed from the folder, until they are triaged
+        InboxService::add_worlds(&imported_ids, worlds)?;
+
+        log::info!("Imported {} recently visited worlds", imported_ids.len());
+        Ok(imported_ids)
+    }
+
+    /// Periodically imports recently visited worlds while the import is enabled
+    /// The setting is re-read on every check, so toggling it takes effect without a restart.
+    /// Checks are skipped while background jobs are paused or in guest mode
+    ///
+    /// # Arguments
+    /// * `state` - The app state holding the library
+    pub async fn run_auto_import(state: AppState) {
+        loop {
+            tokio::time::sleep(AUTO_IMPORT_INTERVAL).await;
+
+            let settings = FileService::read_custom_data()
+                .preferences
+                .recently_visited_import
+                .unwrap_or_default();
+            if !settings.enabled
+                || BackgroundJobs::is_paused()
+                || GuestMode::is_enabled()
+                || INITSTATE.get().read().await.user_id.is_empty()
+            {
+                continue;
+            }
+
+            let cookie_store = AUTHENTICATOR.get().read().await.get_cookies();
+            if let Err(e) = Self::import_recently_visited(
+                cookie_store,
+                settings.cap as usize,
+                state.folder_store(),
+                state.world_store(),
+                state.blacklist_store(),
+            )
+            .await
+            {
+                log::warn!("Failed to auto-import recently visited worlds: {}", e);
+            }
+        }
+    }
+
+    /// Picks the visited worlds which are not in the library, most recent first, up to the cap
+    /// The order is reversed so the most recent world ends up last in the folder
+    fn select_new_worlds(
+        visited: Vec<WorldDisplayData>,
+        known_ids: &HashSet<String>,
+        cap: usize,
+    ) -> Vec<String> {
+        let mut seen = HashSet::new();
+        let mut new_ids: Vec<String> = visited
+            .into_iter()
+            .map(|w| w.world_id)
+            .filter(|id| !known_ids.contains(id) && seen.insert(id.clone()))
+            .take(cap)
+            .collect();
+        new_ids.reverse();
+        new_ids
+    }
+
+    fn ensure_folder(folders: &RwLock<Vec<FolderModel>>) -> Result<(), AppError> {
+        let exists = {
+            let folders_lock = folders.read().map_err(|_| ConcurrencyError::PoisonedLock)?;
+            folders_lock
+                .iter()
+                .any(|f| f.folder_name == RECENTLY_VISITED_FOLDER)
+        };
+        if !exists {
+            let folder_name =
+                FolderManager::create_folder(RECENTLY_VISITED_FOLDER.to_string(), folders)?;
+            ChangeFeed::record(LibraryChange::FolderCreated { folder_name });
+        }
+        Ok(())
+    }
+
+    fn trim_folder(
+        cap: usize,
+        folders: &RwLock<Vec<FolderModel>>,
+        worlds: &RwLock<Vec<WorldModel>>,
+    ) -> Result<(), AppError> {
+        let overflow: Vec<String> = {
+            let folders_lock = folders.read().map_err(|_| ConcurrencyError::PoisonedLock)?;
+            folders_lock
+                .iter()
+                .find(|f| f.folder_name == RECENTLY_VISITED_FOLDER)
+                .map(|f| {
+                    let excess = f.world_ids.len().saturating_sub(cap);
+                    f.world_ids[..excess].to_vec()
+                })
+                .unwrap_or_default()
+        };
+        for world_id in overflow {
+            FolderManager::remove_world_from_folder(
+                RECENTLY_VISITED_FOLDER.to_string(),
+                world_id.clone(),
+                folders,
+                worlds,
+            )?;
+            ChangeFeed::record(LibraryChange::RemovedFromFolder {
+                folder_name: RECENTLY_VISITED_FOLDER.to_string(),
+                world_id,
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::definitions::{Platform, WorldAvailability};
+    use crate::services::sorting_service::make_test_world;
+
+    fn visited_world(world_id: &str) -> WorldDisplayData {
+        WorldDisplayData {
+            world_id: world_id.to_string(),
+            name: world_id.to_string(),
+            thumbnail_url: String::new(),
+            author_name: String::new(),
+            author_id: String::new(),
+            favorites: 0,
+            last_updated: String::new(),
+            visits: 0,
+            date_added: String::new(),
+            platform: Platform::PC,
+            folders: vec![],
+            tags: vec![],
+            capacity: 0,
+            is_photographed: false,
+            is_shared: false,
+            is_favorite: false,
+            is_event_critical: false,
+            has_memo: false,
+            rating: None,
+            local_visit_count: 0,
+            pc_download_size: None,
+            quest_download_size: None,
+            availability: WorldAvailability::Public,
+        }
+    }
+
+    #[test]
+    fn test_select_new_worlds() {
+        let visited = vec![
+            visited_world("wrld_newest"),
+            visited_world("wrld_known"),
+            visited_world("wrld_newest"),
+            visited_world("wrld_older"),
+            visited_world("wrld_oldest"),
+        ];
+        let known_ids = HashSet::from(["wrld_known".to_string()]);
+
+        let new_ids = RecentlyVisitedService::select_new_worlds(visited, &known_ids, 2);
+        assert_eq!(new_ids, vec!["wrld_older", "wrld_newest"]);
+    }
+
+    #[test]
+    fn test_ensure_and_trim_folder() {
+        let folders = RwLock::new(vec![]);
+        let world_ids = vec![
+            "wrld_oldest".to_string(),
+            "wrld_older".to_string(),
+            "wrld_newest".to_string(),
+        ];
+        let worlds = RwLock::new(world_ids.iter().map(|id| make_test_world(id)).collect());
+
+        RecentlyVisitedService::ensure_folder(&folders).unwrap();
+        // An existing folder is reused
+        RecentlyVisitedService::ensure_folder(&folders).unwrap();
+        assert_eq!(folders.read().unwrap().len(), 1);
+
+        FolderManager::add_worlds_to_folder(
+            RECENTLY_VISITED_FOLDER.to_string(),
+            world_ids,
+            &folders,
+            &worlds,
+        )
+        .unwrap();
+        RecentlyVisitedService::trim_folder(2, &folders, &worlds).unwrap();
+
+        // The oldest entries are removed from the folder, but stay in the library
+        assert_eq!(
+            folders.read().unwrap()[0].world_ids,
+            vec!["wrld_older", "wrld_newest"]
+        );
+        let worlds = worlds.read().unwrap();
+        assert_eq!(worlds.len(), 3);
+        assert!(worlds[0].user_data.folders.is_empty());
+    }
+}
@@ -0,0 +1,547 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, RwLock};
+
+use reqwest::cookie::Jar;
+
+use crate::definitions::{
+    Recommendation, RecommendationReason, SimilarWorld, WorldBlacklist, WorldDisplayData,
+    WorldModel,
+};
+use crate::errors::{AppError, ConcurrencyError, EntityError};
+
+use super::{ApiService, TagService};
+
+const AUTHOR_TAG_PREFIX: &str = "author_tag_";
+const SHARED_TAG_WEIGHT: f64 = 2.0;
+const SAME_AUTHOR_WEIGHT: f64 = 3.0;
+const CAPACITY_WEIGHT: f64 = 1.0;
+/// The number of the world's tags the remote search is made with
+/// VRChat only returns worlds having every tag, so more tags find almost nothing
+const REMOTE_SEARCH_TAGS: usize = 2;
+/// How much a launch of a world counts towards its tags, next to it being liked
+const LAUNCH_WEIGHT: f64 = 1.0;
+const LIKED_WEIGHT: f64 = 2.0;
+const LIKED_AUTHOR_WEIGHT: f64 = 1.5;
+/// Ratings from this up count as liking a world, like favoriting it
+const LIKED_RATING: u8 = 4;
+/// The number of reasons given for a recommendation, besides it never being launched
+const MAX_REASONS: usize = 2;
+
+/// What the user likes, from the launches, favorites and ratings of the library
+#[derive(Debug, Default)]
+struct TasteProfile {
+    /// Map of author tag -> launches of worlds with the tag
+    tag_launches: HashMap<String, u32>,
+    /// Map of author tag -> liked worlds with the tag
+    tag_liked: HashMap<String, u32>,
+    /// Map of author ID -> (author name, worlds launched or liked)
+    authors: HashMap<String, (String, u32)>,
+}
+
+impl TasteProfile {
+    fn tag_weight(&self, tag: &str) -> f64 {
+        let launches = self.tag_launches.get(tag).copied().unwrap_or(0);
+        let liked = self.tag_liked.get(tag).copied().unwrap_or(0);
+        launches as f64 * LAUNCH_WEIGHT + liked as f64 * LIKED_WEIGHT
+    }
+
+    /// The tags with the most weight, heaviest first
+    fn top_tags(&self, count: usize) -> Vec<String> {
+        let mut tags: Vec<(&String, f64)> = self
+            .tag_launches
+            .keys()
+            .chain(self.tag_liked.keys())
+            .collect::<HashSet<&String>>()
+            .into_iter()
+            .map(|tag| (tag, self.tag_weight(tag)))
+            .filter(|(_, weight)| *weight > 0.0)
+            .collect();
+        tags.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        tags.into_iter()
+            .take(count)
+            .map(|(tag, _)| tag.clone())
+            .collect()
+    }
+}
+
+/// Service for suggesting worlds based on the worlds the user already has
+#[derive(Debug)]
+pub struct RecommendationService;
+
+impl RecommendationService {
+    /// Ranks worlds by how much they resemble a world of the library
+    /// Worlds score for each author tag they share with it, for having the same author,
+    /// and for a capacity close to its capacity; worlds sharing neither a tag nor the
+    /// author are never suggested
+    ///
+    /// # Arguments
+    /// * `world_id` - The ID of the world to find similar worlds for
+    /// * `limit` - The maximum number of suggestions
+    /// * `cookie_store` - The cookie store to search VRChat with, or None for the library only
+    /// * `worlds` - The list of worlds in the library, as a RwLock
+    /// * `blacklist` - The world blacklist, as a RwLock
+    ///
+    /// # Returns
+    /// The suggestions, most similar first
+    ///
+    /// # Errors
+    /// Returns an error if the world is not in the library
+    /// Returns an error if any lock is poisoned
+    pub async fn get_similar_worlds(
+        world_id: &str,
+        limit: usize,
+        cookie_store: Option<Arc<Jar>>,
+        worlds: &RwLock<Vec<WorldModel>>,
+        blacklist: &RwLock<WorldBlacklist>,
+    ) -> Result<Vec<SimilarWorld>, AppError> {
+        let (source, library) = {
+            let worlds_lock = worlds.read().map_err(|_| ConcurrencyError::PoisonedLock)?;
+            let source = worlds_lock
+                .iter()
+                .find(|w| w.api_data.world_id == world_id)
+                .map(|w| w.to_display_data())
+                .ok_or_else(|| EntityError::WorldNotFound(world_id.to_string()))?;
+            let library: Vec<WorldDisplayData> = worlds_lock
+                .iter()
+                .filter(|w| !w.user_data.hidden && w.api_data.world_id != world_id)
+                .map(|w| w.to_display_data())
+                .collect();
+            (source, library)
+        };
+        let library_ids: HashSet<String> = library.iter().map(|w| w.world_id.clone()).collect();
+
+        let mut candidates: Vec<(WorldDisplayData, bool)> =
+            library.into_iter().map(|w| (w, true)).collect();
+
+        if let Some(cookie_store) = cookie_store {
+            let mut tags: Vec<String> = Self::author_tags(&source.tags).into_iter().collect();
+            tags.sort();
+            tags.truncate(REMOTE_SEARCH_TAGS);
+            let mut known_ids = library_ids;
+            known_ids.insert(world_id.to_string());
+            candidates.extend(
+                Self::search_by_tags(cookie_store, tags, &known_ids)
+                    .await
+                    .into_iter()
+                    .map(|w| (w, false)),
+            );
+        }
+
+        {
+            let blacklist_lock = blacklist
+                .read()
+                .map_err(|_| ConcurrencyError::PoisonedLock)?;
+            candidates.retain(|(w, _)| !blacklist_lock.is_blocked(&w.world_id, &w.author_id));
+        }
+
+        Ok(Self::rank_similar(&source, candidates, limit))
+    }
+
+    /// Recommends worlds matching what the user launches, favorites and rates highly
+    /// Library worlds which were never launched are recommended, and with a cookie store,
+    /// popular worlds with the user's favorite tags which are not in the library yet
+    ///
+    /// # Arguments
+    /// * `limit` - The maximum number of recommendations
+    /// * `cookie_store` - The cookie store to search VRChat with, or None for the library only
+    /// * `worlds` - The list of worlds in the library, as a RwLock
+    /// * `blacklist` - The world blacklist, as a RwLock
+    ///
+    /// # Returns
+    /// The recommendations, best match first
+    /// Empty if nothing was launched, favorited or rated yet
+    ///
+    /// # Errors
+    /// Returns an error if any lock is poisoned
+    pub async fn get_recommendations(
+        limit: usize,
+        cookie_store: Option<Arc<Jar>>,
+        worlds: &RwLock<Vec<WorldModel>>,
+        blacklist: &RwLock<WorldBlacklist>,
+    ) -> Result<Vec<Recommendation>, AppError> {
+        let (profile, library_ids, mut candidates) = {
+            let worlds_lock = worlds.read().map_err(|_| ConcurrencyError::PoisonedLock)?;
+            let profile = Self::build_profile(&worlds_lock);
+            let library_ids: HashSet<String> = worlds_lock
+                .iter()
+                .map(|w| w.api_data.world_id.clone())
+                .collect();
+            let candidates: Vec<(WorldDisplayData, bool)> = worlds_lock
+                .iter()
+                .filter(|w| !w.user_data.hidden && w.user_data.launch_count == 0)
+                .map(|w| (w.to_display_data(), true))
+                .collect();
+            (profile, library_ids, candidates)
+        };
+
+        if let Some(cookie_store) = cookie_store {
+            let tags = profile.top_tags(1);
+            candidates.extend(
+                Self::search_by_tags(cookie_store, tags, &library_ids)
+                    .await
+                    .into_iter()
+                    .map(|w| (w, false)),
+            );
+        }
+
+        {
+            let blacklist_lock = blacklist
+                .read()
+                .map_err(|_| ConcurrencyError::PoisonedLock)?;
+            candidates.retain(|(w, _)| !blacklist_lock.is_blocked(&w.world_id, &w.author_id));
+        }
+
+        Ok(Self::rank_recommendations(&profile, candidates, limit))
+    }
+
+    fn build_profile(worlds: &[WorldModel]) -> TasteProfile {
+        let mut profile = TasteProfile::default();
+        for world in worlds.iter().filter(|w| !w.user_data.hidden) {
+            let launches = world.user_data.launch_count;
+            let liked = world.user_data.is_favorite
+                || world.user_data.rating.is_some_and(|r| r >= LIKED_RATING);
+            if launches == 0 && !liked {
+                continue;
+            }
+            for tag in Self::author_tags(&world.api_data.tags) {
+                if launches > 0 {
+                    *profile.tag_launches.entry(tag.clone()).or_insert(0) += launches;
+                }
+                if liked {
+                    *profile.tag_liked.entry(tag).or_insert(0) += 1;
+                }
+            }
+            if !world.api_data.author_id.is_empty() {
+                profile
+                    .authors
+                    .entry(world.api_data.author_id.clone())
+                    .or_insert_with(|| (world.api_data.author_name.clone(), 0))
+                    .1 += 1;
+            }
+        }
+        profile
+    }
+
+    /// Scores the candidates against the profile and keeps the best ones
+    /// Candidates matching nothing the user likes are left out
+    fn rank_recommendations(
+        profile: &TasteProfile,
+        candidates: Vec<(WorldDisplayData, bool)>,
+        limit: usize,
+    ) -> Vec<Recommendation> {
+        let mut recommendations: Vec<Recommendation> = candidates
+            .into_iter()
+            .filter_map(|(world, in_library)| {
+                // Each reason with how much it adds to the score
+                let mut reasons: Vec<(f64, RecommendationReason)> = vec![];
+                for tag in Self::author_tags(&world.tags) {
+                    let weight = profile.tag_weight(&tag);
+                    if weight <= 0.0 {
+                        continue;
+                    }
+                    let launches = profile.tag_launches.get(&tag).copied().unwrap_or(0);
+                    let liked = profile.tag_liked.get(&tag).copied().unwrap_or(0);
+                    let reason = if launches as f64 * LAUNCH_WEIGHT >= liked as f64 * LIKED_WEIGHT {
+                        RecommendationReason::LaunchedTag { tag, launches }
+                    } else {
+                        RecommendationReason::FavoriteTag { tag, worlds: liked }
+                    };
+                    reasons.push((weight, reason));
+                }
+                if let Some((author_name, count)) = profile.authors.get(&world.author_id) {
+                    reasons.push((
+                        *count as f64 * LIKED_AUTHOR_WEIGHT,
+                        RecommendationReason::LikedAuthor {
+                            author_name: author_name.clone(),
+                            worlds: *count,
+                        },
+                    ));
+                }
+                if reasons.is_empty() {
+                    return None;
+                }
+
+                let score: f64 = reasons.iter().map(|(weight, _)| weight).sum();
+                reasons.sort_by(|a, b| b.0.total_cmp(&a.0));
+                let mut reasons: Vec<RecommendationReason> = reasons
+                    .into_iter()
+                    .take(MAX_REASONS)
+                    .map(|(_, reason)| reason)
+                    .collect();
+                if in_library {
+                    reasons.push(RecommendationReason::NeverLaunched);
+                }
+                Some(Recommendation {
+                    world,
+                    score,
+                    reasons,
+                    in_library,
+                })
+            })
+            .collect();
+
+        recommendations.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| b.in_library.cmp(&a.in_library))
+                .then_with(|| a.world.name.cmp(&b.world.name))
+        });
+        recommendations.truncate(limit);
+        recommendations
+    }
+
+    /// Scores the candidates against the source world and keeps the best ones
+    fn rank_similar(
+        source: &WorldDisplayData,
+        candidates: Vec<(WorldDisplayData, bool)>,
+        limit: usize,
+    ) -> Vec<SimilarWorld> {
+        let source_tags = Self::author_tags(&source.tags);
+
+        let mut similar: Vec<SimilarWorld> = candidates
+            .into_iter()
+            .filter_map(|(world, in_library)| {
+                let mut shared_tags: Vec<String> = Self::author_tags(&world.tags)
+                    .intersection(&source_tags)
+                    .cloned()
+                    .collect();
+                shared_tags.sort();
+                let same_author =
+                    !source.author_id.is_empty() && world.author_id == source.author_id;
+                if shared_tags.is_empty() && !same_author {
+                    return None;
+                }
+
+                let mut score = shared_tags.len() as f64 * SHARED_TAG_WEIGHT
+                    + Self::capacity_proximity(source.capacity, world.capacity) * CAPACITY_WEIGHT;
+                if same_author {
+                    score += SAME_AUTHOR_WEIGHT;
+                }
+                Some(SimilarWorld {
+                    world,
+                    score,
+                    shared_tags,
+                    same_author,
+                    in_library,
+                })
+            })
+            .collect();
+
+        similar.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| b.in_library.cmp(&a.in_library))
+                .then_with(|| a.world.name.cmp(&b.world.name))
+        });
+        similar.truncate(limit);
+        similar
+    }
+
+    /// Searches VRChat for popular worlds having every tag, leaving out known worlds
+    /// Failures are only logged, as the library suggestions are still worth showing
+    async fn search_by_tags(
+        cookie_store: Arc<Jar>,
+        tags: Vec<String>,
+        known_ids: &HashSet<String>,
+    ) -> Vec<WorldDisplayData> {
+        if tags.is_empty() {
+            return vec![];
+        }
+        match ApiService::search_worlds(
+            cookie_store,
+            Some("popularity".to_string()),
+            Some(tags),
+            None,
+            None,
+            None,
+            None,
+            None,
+            1,
+        )
+        .await
+        {
+            Ok(page) => page
+                .worlds
+                .into_iter()
+                .filter(|w| !known_ids.contains(&w.world_id))
+                .collect(),
+            Err(e) => {
+                log::warn!("Failed to search for suggested worlds: {}", e);
+                vec![]
+            }
+        }
+    }
+
+    /// 1.0 for equal capacities, down to 0.0 as one becomes negligible next to the other
+    fn capacity_proximity(a: i32, b: i32) -> f64 {
+        let (a, b) = (a.max(0) as f64, b.max(0) as f64);
+        if a == 0.0 || b == 0.0 {
+            return 0.0;
+        }
+        a.min(b) / a.max(b)
+    }
+
+    /// The author tags of a world after aliases are applied, lowercase and without "author_tag_"
+    fn author_tags(tags: &[String]) -> HashSet<String> {
+        TagService::apply(tags)
+            .iter()
+            .filter_map(|tag| tag.strip_prefix(AUTHOR_TAG_PREFIX))
+            .map(str::to_lowercase)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::definitions::{Platform, WorldApiData, WorldAvailability};
+
+    fn make_world(id: &str, author_id: &str, tags: &[&str], capacity: i32) -> WorldDisplayData {
+        WorldDisplayData {
+            world_id: id.to_string(),
+            name: id.to_string(),
+            thumbnail_url: "".to_string(),
+            author_name: "Test Author".to_string(),
+            author_id: author_id.to_string(),
+            favorites: 0,
+            last_updated: "".to_string(),
+            visits: 0,
+            date_added: "".to_string(),
+            platform: Platform::PC,
+            folders: vec![],
+            tags: tags
+                .iter()
+                .map(|t| format!("{}{}", AUTHOR_TAG_PREFIX, t))
+                .collect(),
+            capacity,
+            is_photographed: false,
+            is_shared: false,
+            is_favorite: false,
+            is_event_critical: false,
+            has_memo: false,
+            rating: None,
+            local_visit_count: 0,
+            pc_download_size: None,
+            quest_download_size: None,
+            availability: WorldAvailability::Public,
+        }
+    }
+
+    #[test]
+    fn test_rank_similar() {
+        let source = make_world("wrld_source", "usr_a", &["horror", "Puzzle"], 16);
+        let similar = RecommendationService::rank_similar(
+            &source,
+            vec![
+                (make_world("wrld_one_tag", "usr_b", &["horror"], 16), true),
+                (
+                    make_world("wrld_two_tags", "usr_b", &["horror", "puzzle"], 8),
+                    false,
+                ),
+                (
+                    make_world("wrld_same_author", "usr_a", &["chill"], 32),
+                    true,
+                ),
+                (make_world("wrld_unrelated", "usr_b", &["chill"], 16), true),
+            ],
+            10,
+        );
+
+        let ids: Vec<&str> = similar.iter().map(|s| s.world.world_id.as_str()).collect();
+        assert_eq!(
+            ids,
+            vec!["wrld_two_tags", "wrld_same_author", "wrld_one_tag"]
+        );
+        assert_eq!(similar[0].shared_tags, vec!["horror", "puzzle"]);
+        assert!(!similar[0].in_library);
+        assert!(similar[1].same_author);
+    }
+
+    #[test]
+    fn test_rank_similar_respects_limit() {
+        let source = make_world("wrld_source", "usr_a", &["horror"], 16);
+        let candidates = (0..5)
+            .map(|i| {
+                (
+                    make_world(&format!("wrld_{}", i), "usr_b", &["horror"], 16),
+                    true,
+                )
+            })
+            .collect();
+        assert_eq!(
+            RecommendationService::rank_similar(&source, candidates, 3).len(),
+            3
+        );
+    }
+
+    fn make_model(id: &str, author_id: &str, tags: &[&str], launches: u32) -> WorldModel {
+        let mut world = WorldModel::new(WorldApiData {
+            author_id: author_id.to_string(),
+            author_name: author_id.to_string(),
+            tags: tags
+                .iter()
+                .map(|t| format!("{}{}", AUTHOR_TAG_PREFIX, t))
+                .collect(),
+            ..WorldApiData::placeholder(id.to_string())
+        });
+        world.user_data.launch_count = launches;
+        world
+    }
+
+    #[test]
+    fn test_rank_recommendations() {
+        let mut favorite = make_model("wrld_favorite", "usr_a", &["puzzle"], 0);
+        favorite.user_data.is_favorite = true;
+        let profile = RecommendationService::build_profile(&[
+            make_model("wrld_launched", "usr_b", &["horror"], 5),
+            favorite,
+            make_model("wrld_never", "usr_c", &["chill"], 0),
+        ]);
+
+        let recommendations = RecommendationService::rank_recommendations(
+            &profile,
+            vec![
+                (make_world("wrld_horror", "usr_d", &["horror"], 16), true),
+                (make_world("wrld_puzzle", "usr_a", &["puzzle"], 16), false),
+                (make_world("wrld_chill", "usr_c", &["chill"], 16), true),
+            ],
+            10,
+        );
+
+        let ids: Vec<&str> = recommendations
+            .iter()
+            .map(|r| r.world.world_id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["wrld_horror", "wrld_puzzle"]);
+        assert_eq!(
+            recommendations[0].reasons,
+            vec![
+                RecommendationReason::LaunchedTag {
+                    tag: "horror".to_string(),
+                    launches: 5
+                },
+                RecommendationReason::NeverLaunched,
+            ]
+        );
+        assert_eq!(
+            recommendations[1].reasons,
+            vec![
+                RecommendationReason::FavoriteTag {
+                    tag: "puzzle".to_string(),
+                    worlds: 1
+                },
+                RecommendationReason::LikedAuthor {
+                    author_name: "usr_a".to_string(),
+                    worlds: 1
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_capacity_proximity() {
+        assert_eq!(RecommendationService::capacity_proximity(16, 16), 1.0);
+        assert_eq!(RecommendationService::capacity_proximity(8, 16), 0.5);
+        assert_eq!(RecommendationService::capacity_proximity(0, 16), 0.0);
+    }
+}
@@ -0,0 +1,82 @@
+use std::time::Duration;
+
+use tauri::AppHandle;
+use tauri_specta::Event;
+
+use crate::definitions::SessionExpired;
+use crate::errors::ApiError;
+use crate::{AUTHENTICATOR, INITSTATE};
+
+use super::{ApiService, BackgroundJobs};
+
+/// How often the saved session is checked while logged in
+const CHECK_INTERVAL: Duration = Duration::from_secs(30 * 60);
+
+/// Service for noticing an expired login before a command fails because of it
+#[derive(Debug)]
+pub struct SessionService;
+
+impl SessionService {
+    /// Periodically verifies the session's token while logged in
+    /// A valid token refreshes the logged-in user's info and keeps the cookies current.
+    /// A rejected one logs the user out locally and emits `SessionExpired`, while network
+    /// failures are only logged, as the session may still be fine
+    ///
+    /// # Arguments
+    /// * `handle` - The app handle, to emit the event with
+    pub async fn run_keep_alive(handle: AppHandle) {
+        loop {
+            tokio::time::sleep(CHECK_INTERVAL).await;
+
+            if BackgroundJobs::is_paused() || INITSTATE.get().read().await.user_id.is_empty() {
+                continue;
+            }
+
+            let result = ApiService::login_with_token(AUTHENTICATOR.get(), INITSTATE.get()).await;
+            let Err(e) = result else {
+                log::info!("Session is still valid");
+                continue;
+            };
+
+            if !Self::is_expired(&e) {
+                log::warn!("Failed to check session: {}", e);
+                continue;
+            }
+
+            log::warn!("Session expired: {}", e);
+            // Background jobs stop until the user logs in again
+            INITSTATE.get().write().await.user_id.clear();
+
+            let event = SessionExpired {
+                message: "Your VRChat session has expired. Please log in again.".to_string(),
+            };
+            if let Err(e) = event.emit(&handle) {
+                log::error!("Failed to emit session expired event: {}", e);
+            }
+        }
+    }
+
+    /// Whether a `login_with_token` error means the token was rejected, rather than the
+    /// check failing
+    fn is_expired(error: &ApiError) -> bool {
+        matches!(error, ApiError::Unauthorized(_))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_rejected_tokens_are_expired() {
+        assert!(SessionService::is_expired(&ApiError::Unauthorized(
+            "Invalid credentials".to_string()
+        )));
+        assert!(SessionService::is_expired(&ApiError::Unauthorized(
+            "2fa-required".to_string()
+        )));
+        assert!(!SessionService::is_expired(&ApiError::ResponseError(
+            "Login failed: Failed to send auth request: connection refused".to_string()
+        )));
+    }
+}
@@ -0,0 +1,266 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::definitions::{CustomPreferences, PreferenceModel};
+use crate::errors::{AppError, EntityError, FileError};
+use crate::migration::DataFile;
+
+use super::FileService;
+
+/// Identifies a settings profile, so other JSON files are not imported by mistake
+const PROFILE_FORMAT: &str = "vrc-worlds-manager-settings";
+
+/// Version of the profile layout, bumped when it changes incompatibly
+const PROFILE_VERSION: u32 = 1;
+
+/// The user's settings in a portable file
+/// Secrets are left out: the session is never included, and the webhook URL and local API
+/// token are blanked, keeping the importing machine's own
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SettingsProfile {
+    format: String,
+    version: u32,
+    exported_at: DateTime<Utc>,
+    preferences: PreferenceModel,
+    custom_preferences: CustomPreferences,
+}
+
+/// Service for exporting and importing settings profiles, to replicate a setup on another
+/// machine or share it
+#[derive(Debug)]
+pub struct SettingsProfileService;
+
+impl SettingsProfileService {
+    /// Writes the settings to a timestamped profile file
+    ///
+    /// # Arguments
+    /// * `preferences` - The current preferences
+    /// * `target_dir` - The directory to write the profile to
+    ///
+    /// # Returns
+    /// The path of the written profile
+    ///
+    /// # Errors
+    /// Returns an error if the directory does not exist or the file could not be written
+    pub fn export(preferences: &PreferenceModel, target_dir: &str) -> Result<PathBuf, AppError> {
+        let target = Path::new(target_dir);
+        if !target.is_dir() {
+            return Err(FileError::FileNotFound.into());
+        }
+
+        let profile = Self::to_profile(
+            preferences,
+            &FileService::read_custom_data().preferences,
+            Utc::now(),
+        );
+        let data = serde_json::to_string_pretty(&profile).map_err(|_| FileError::InvalidFile)?;
+
+        let timestamp = chrono::Local::now().format("%Y-%m-%d_%H-%M-%S");
+        let path = target.join(format!("VRC_Worlds_Manager_Settings_{}.json", timestamp));
+        fs::write(&path, data).map_err(|_| FileError::FileWriteError)?;
+        log::info!("Exported settings profile to {}", path.display());
+        Ok(path)
+    }
+
+    /// Replaces the settings with those of a profile file
+    /// Whether the first-run setup was done and the machine's secrets are kept
+    ///
+    /// # Arguments
+    /// * `path` - The profile file
+    /// * `preferences` - The current preferences, updated in place
+    ///
+    /// # Errors
+    /// Returns an error if the file is not a profile, or is from a newer version of the app
+    /// Returns an error if the settings could not be saved
+    pub fn import(path: &str, preferences: &mut PreferenceModel) -> Result<(), AppError> {
+        let data = fs::read_to_string(path).map_err(|_| FileError::FileNotFound)?;
+        let profile: SettingsProfile = serde_json::from_str(&data).map_err(|e| {
+            log::warn!("Failed to parse settings profile: {}", e);
+            EntityError::InvalidOperation("The file is not a settings profile".to_string())
+        })?;
+        Self::check(&profile)?;
+
+        let mut custom_data = FileService::read_custom_data();
+        Self::apply(profile, preferences, &mut custom_data.preferences);
+        FileService::write_custom_data(&custom_data)?;
+        FileService::write_preferences(preferences)?;
+        log::info!("Imported settings profile from {}", path);
+        Ok(())
+    }
+
+    fn to_profile(
+        preferences: &PreferenceModel,
+        custom_preferences: &CustomPreferences,
+        now: DateTime<Utc>,
+    ) -> SettingsProfile {
+        let mut custom_preferences = custom_preferences.clone();
+        if let Some(webhook) = custom_preferences.webhook.as_mut() {
+            webhook.url.clear();
+        }
+        if let Some(local_api) = custom_preferences.local_api.as_mut() {
+            local_api.token.clear();
+        }
+
+        SettingsProfile {
+            format: PROFILE_FORMAT.to_string(),
+            version: PROFILE_VERSION,
+            exported_at: now,
+            preferences: preferences.clone(),
+            custom_preferences,
+        }
+    }
+
+    fn check(profile: &SettingsProfile) -> Result<(), EntityError> {
+        if profile.format != PROFILE_FORMAT {
+            return Err(EntityError::InvalidOperation(
+                "The file is not a settings profile".to_string(),
+            ));
+        }
+        if profile.version > PROFILE_VERSION
+            || profile.preferences.schema_version > DataFile::Preferences.current_version()
+        {
+            return Err(EntityError::InvalidOperation(
+                "The settings profile is from a newer version, update the app to import it"
+                    .to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn apply(
+        profile: SettingsProfile,
+        preferences: &mut PreferenceModel,
+        custom_preferences: &mut CustomPreferences,
+    ) {
+        let mut imported = profile.custom_preferences;
+        // Blank secrets are filled from this machine's, or the feature is left off
+        if let Some(webhook) = imported.webhook.as_mut() {
+            if webhook.url.is_empty() {
+                match &custom_preferences.webhook {
+                    Some(current) if !current.url.is_empty() => webhook.url = current.url.clone(),
+                    _ => webhook.enabled = false,
+                }
+            }
+        }
+        if let Some(local_api) = imported.local_api.as_mut() {
+            if local_api.token.is_empty() {
+                match &custom_preferences.local_api {
+                    Some(current) if !current.token.is_empty() => {
+                        local_api.token = current.token.clone()
+                    }
+                    _ => local_api.enabled = false,
+                }
+            }
+        }
+
+        let first_time = preferences.first_time;
+        let schema_version = preferences.schema_version;
+        *preferences = profile.preferences;
+        preferences.first_time = first_time;
+        preferences.schema_version = schema_version;
+
+        // These are kept in custom_data.json, see `FileService::load_data`
+        preferences.default_instance_type = imported.default_instance_type.clone();
+        preferences.default_search_platform = imported.default_search_platform;
+        if let Some(visible_buttons) = &imported.visible_buttons {
+            preferences.visible_buttons = visible_buttons.clone();
+        }
+        if let Some(removal) = &imported.dont_show_remove_from_folder {
+            preferences.dont_show_remove_from_folder = removal.clone();
+        }
+        preferences.worlds_storage = imported.worlds_storage.unwrap_or_default();
+
+        *custom_preferences = imported;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::definitions::{LocalApiSettings, WebhookFormat, WebhookSettings};
+
+    fn webhook(url: &str) -> WebhookSettings {
+        WebhookSettings {
+            enabled: true,
+            url: url.to_string(),
+            format: WebhookFormat::default(),
+            events: vec![],
+        }
+    }
+
+    #[test]
+    fn test_profile_round_trip_keeps_local_secrets() {
+        let mut source = PreferenceModel::new();
+        source.theme = "dark".to_string();
+        source.first_time = false;
+        let source_custom = CustomPreferences {
+            webhook: Some(webhook("https://example.com/source")),
+            natural_sort: Some(true),
+            ..CustomPreferences::default()
+        };
+
+        let profile = SettingsProfileService::to_profile(&source, &source_custom, Utc::now());
+        let json = serde_json::to_string(&profile).unwrap();
+        assert!(!json.contains("https://example.com/source"));
+        let profile: SettingsProfile = serde_json::from_str(&json).unwrap();
+        assert!(SettingsProfileService::check(&profile).is_ok());
+
+        let mut target = PreferenceModel::new();
+        let mut target_custom = CustomPreferences {
+            webhook: Some(webhook("https://example.com/target")),
+            local_api: Some(LocalApiSettings {
+                enabled: true,
+                token: "secret".to_string(),
+                ..LocalApiSettings::default()
+            }),
+            ..CustomPreferences::default()
+        };
+        SettingsProfileService::apply(profile, &mut target, &mut target_custom);
+
+        assert_eq!(target.theme, "dark");
+        assert!(
+            target.first_time,
+            "The first-run setup state is not imported"
+        );
+        assert_eq!(target_custom.natural_sort, Some(true));
+        assert_eq!(
+            target_custom.webhook.map(|w| w.url),
+            Some("https://example.com/target".to_string())
+        );
+        // The profile had no local API settings, so they are replaced
+        assert!(target_custom.local_api.is_none());
+    }
+
+    #[test]
+    fn test_webhook_without_url_is_disabled() {
+        let source_custom = CustomPreferences {
+            webhook: Some(webhook("https://example.com/source")),
+            ..CustomPreferences::default()
+        };
+        let profile =
+            SettingsProfileService::to_profile(&PreferenceModel::new(), &source_custom, Utc::now());
+
+        let mut target_custom = CustomPreferences::default();
+        SettingsProfileService::apply(profile, &mut PreferenceModel::new(), &mut target_custom);
+        assert!(!target_custom.webhook.unwrap().enabled);
+    }
+
+    #[test]
+    fn test_newer_profiles_are_rejected() {
+        let mut profile = SettingsProfileService::to_profile(
+            &PreferenceModel::new(),
+            &Default::default(),
+            Utc::now(),
+        );
+        profile.version = PROFILE_VERSION + 1;
+        assert!(SettingsProfileService::check(&profile).is_err());
+
+        profile.version = PROFILE_VERSION;
+        profile.format = "something-else".to_string();
+        assert!(SettingsProfileService::check(&profile).is_err());
+    }
+}
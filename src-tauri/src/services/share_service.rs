@@ -19,6 +19,10 @@ pub struct ShareResponse {
 struct ShareRequestPayload<'a> {
     name: &'a str,
     worlds: &'a [WorldApiData],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    icon: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<&'a str>,
     ts: String,
     hmac: String,
 }
@@ -28,15 +32,31 @@ struct ShareRequestPayload<'a> {
 pub struct ShareRequest {
     pub name: String,
     pub worlds: Vec<WorldApiData>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
     pub ts: String,
     pub hmac: String,
 }
 
 /// The shape of the signing payload
+/// Icon and description are left out when unset, so folders shared without them still verify
 #[derive(Serialize)]
 struct SigningPayload<'a> {
     name: &'a str,
     worlds: &'a [WorldApiData],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    icon: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<&'a str>,
+}
+
+/// The icon and description shared along with a folder
+#[derive(Debug, Default)]
+struct FolderDetails {
+    icon: Option<String>,
+    description: Option<String>,
 }
 
 const HMAC_KEY: Option<&str> = option_env!("HMAC_KEY");
@@ -59,7 +79,7 @@ fn get_worlds(
     name: &str,
     folders_lock: &RwLock<Vec<FolderModel>>,
     worlds_lock: &RwLock<Vec<WorldModel>>,
-) -> Result<Vec<WorldApiData>, String> {
+) -> Result<(Vec<WorldApiData>, FolderDetails), String> {
     let folders = folders_lock
         .read()
         .map_err(|_| "Failed to read folders".to_string())?;
@@ -68,8 +88,13 @@ fn get_worlds(
         .map_err(|_| "Failed to read worlds".to_string())?;
 
     let mut world_data = Vec::new();
+    let mut details = FolderDetails::default();
     for folder in folders.iter() {
         if folder.folder_name == name {
+            details = FolderDetails {
+                icon: folder.icon.clone(),
+                description: folder.description.clone(),
+            };
             for world_id in &folder.world_ids {
                 if let Some(world) = worlds.iter().find(|w| w.api_data.world_id == *world_id) {
                     world_data.push(world.api_data.clone());
@@ -95,15 +120,26 @@ fn get_worlds(
             w
         })
         .collect();
-    Ok(truncated)
+    Ok((truncated, details))
 }
 
 // returns id and the ts for setting the expires_at field
-async fn post_folder(name: &str, worlds: &[WorldApiData]) -> Result<(String, String), String> {
+async fn post_folder(
+    name: &str,
+    worlds: &[WorldApiData],
+    details: &FolderDetails,
+) -> Result<(String, String), String> {
     let api_url = "https://folder-sharing-worker.raifaworks.workers.dev";
 
     let ts: String = Utc::now().to_rfc3339();
-    let signing = SigningPayload { name, worlds };
+    let icon = details.icon.as_deref();
+    let description = details.description.as_deref();
+    let signing = SigningPayload {
+        name,
+        worlds,
+        icon,
+        description,
+    };
     let data_str = serde_json::to_string(&signing).map_err(|e| e.to_string())?;
 
     let hmac = compute_hmac(&data_str).map_err(|e| format!("Failed to compute HMAC: {}", e))?;
@@ -114,6 +150,8 @@ async fn post_folder(name: &str, worlds: &[WorldApiData]) -> Result<(String, Str
     let req = ShareRequestPayload {
         name,
         worlds,
+        icon,
+        description,
         ts: ts.clone(),
         hmac,
     };
@@ -141,7 +179,7 @@ pub async fn share_folder(
     worlds_lock: &RwLock<Vec<WorldModel>>,
 ) -> Result<(String, String), String> {
     // 1) Load worlds from the specified folder
-    let worlds = get_worlds(name, folders_lock, worlds_lock)
+    let (worlds, details) = get_worlds(name, folders_lock, worlds_lock)
         .map_err(|e| format!("Failed to get worlds: {}", e))?;
 
     if worlds.is_empty() {
@@ -149,12 +187,13 @@ pub async fn share_folder(
     }
 
     // 2) Post the folder
-    post_folder(name, &worlds)
+    post_folder(name, &worlds, &details)
         .await
         .map_err(|e| format!("Failed to post folder: {}", e))
 }
 
-pub async fn download_folder(share_id: &str) -> Result<(String, Vec<WorldApiData>), String> {
+/// Downloads a shared folder, with its icon and description if it was shared with them
+pub async fn download_folder(share_id: &str) -> Result<ShareRequest, String> {
     let api_url = "https://folder-sharing-worker.raifaworks.workers.dev";
     let full_url = format!("{}/api/share/folder/{}", api_url, share_id);
 
@@ -176,6 +215,8 @@ pub async fn download_folder(share_id: &str) -> Result<(String, Vec<WorldApiData
     let signing = SigningPayload {
         name: &folder.name,
         worlds: &folder.worlds,
+        icon: folder.icon.as_deref(),
+        description: folder.description.as_deref(),
     };
     let data_str = serde_json::to_string(&signing).map_err(|e| e.to_string())?;
     let expected_hmac =
@@ -187,15 +228,14 @@ pub async fn download_folder(share_id: &str) -> Result<(String, Vec<WorldApiData
         ));
     }
 
-    // Return the folder name and worlds
-    Ok((folder.name, folder.worlds))
+    Ok(folder)
 }
 
 // === TESTS ===
 #[cfg(test)]
 mod integration_tests {
-    use super::post_folder;
-    use crate::definitions::WorldApiData;
+    use super::{post_folder, FolderDetails};
+    use crate::definitions::{WorldApiData, WorldAvailability};
     use serde_json::Value;
     use std::env;
     use std::fs;
@@ -223,6 +263,8 @@ mod integration_tests {
             visits: Some(590502),
             favorites: 31292,
             platform: vec!["standalonewindows".into(), "standalonewindows".into()],
+            packages: vec![],
+            availability: WorldAvailability::Public,
         }
     }
 
@@ -235,7 +277,7 @@ mod integration_tests {
         // 1) POST the folder
         let worlds = vec![dummy_world()];
         let folder_name = "IntegrationTestFolder";
-        let (id, _ts) = post_folder(folder_name, &worlds)
+        let (id, _ts) = post_folder(folder_name, &worlds, &FolderDetails::default())
             .await
             .expect("post_folder failed");
         assert!(!id.is_empty(), "received empty share ID");
@@ -246,7 +288,7 @@ mod integration_tests {
     async fn integration_no_worlds_error() {
         let _ = env::var("HMAC_KEY").expect("export HMAC_KEY for integration test");
         // posting with empty worlds should error early
-        let err = post_folder("EmptyFolder", &[])
+        let err = post_folder("EmptyFolder", &[], &FolderDetails::default())
             .await
             .expect_err("expected error for no worlds");
         assert!(err.contains("Failed to post folder"), "got: {}", err);
@@ -1,21 +1,205 @@
+use std::cell::RefCell;
 use std::cmp::Ordering;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering as AtomicOrdering};
+use std::sync::{Mutex, OnceLock, PoisonError};
 
+use icu_collator::{Collator, CollatorOptions, Numeric, Strength};
+use icu_locid::Locale;
 use unicode_normalization::UnicodeNormalization;
 
 use crate::definitions::{WorldDisplayData, WorldModel};
 
+use super::FileService;
+
+/// Seed of the "random" order, picked once so the order stays the same within a session
+static RANDOM_SEED: OnceLock<u64> = OnceLock::new();
+
+/// Whether names are compared naturally, so "World 2" sorts before "World 10"
+static NATURAL_SORT: AtomicBool = AtomicBool::new(false);
+
+/// The language names are collated for, from the preferences
+/// Unset until the library is loaded, names are compared by their normalized form until then
+static COLLATION_LANGUAGE: Mutex<Option<String>> = Mutex::new(None);
+
+/// Bumped whenever the language changes, so cached collators are rebuilt
+static COLLATION_GENERATION: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+    /// Collators cannot be shared between threads, so each thread builds its own
+    /// Tagged with the generation and natural sort setting it was built for
+    static COLLATOR: RefCell<Option<(usize, bool, Option<Collator>)>> = const { RefCell::new(None) };
+}
+
 pub struct SortingService;
 
 impl SortingService {
-    fn normalize_for_sorting(value: &str) -> String {
+    /// The fields worlds can be sorted by
+    pub const SORT_FIELDS: [&'static str; 13] = [
+        "name",
+        "authorName",
+        "visits",
+        "favorites",
+        "capacity",
+        "dateAdded",
+        "lastUpdated",
+        "hasMemo",
+        "rating",
+        "localVisitCount",
+        "pcDownloadSize",
+        "questDownloadSize",
+        "random",
+    ];
+
+    /// Whether the field and direction are ones worlds can be sorted by
+    pub fn is_valid_sort(sort_field: &str, sort_direction: &str) -> bool {
+        Self::SORT_FIELDS.contains(&sort_field) && matches!(sort_direction, "asc" | "desc")
+    }
+
+    /// Reads the natural sort preference, called once on startup
+    pub fn init() {
+        let natural = FileService::read_custom_data()
+            .preferences
+            .natural_sort
+            .unwrap_or(false);
+        NATURAL_SORT.store(natural, AtomicOrdering::Relaxed);
+    }
+
+    pub fn is_natural_sort() -> bool {
+        NATURAL_SORT.load(AtomicOrdering::Relaxed)
+    }
+
+    pub fn set_natural_sort(natural: bool) {
+        NATURAL_SORT.store(natural, AtomicOrdering::Relaxed);
+    }
+
+    /// Sets the language names are collated for, e.g. "ja" for kana and kanji aware ordering
+    pub fn set_language(language: &str) {
+        *COLLATION_LANGUAGE
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = Some(language.to_string());
+        COLLATION_GENERATION.fetch_add(1, AtomicOrdering::Relaxed);
+    }
+
+    /// Builds the collator of a language, None if ICU has no collation for it
+    fn build_collator(language: &str, natural: bool) -> Option<Collator> {
+        let locale: Locale = language.parse().ok()?;
+        let mut options = CollatorOptions::new();
+        // Matches the frontend's localeCompare(sensitivity: "base")
+        options.strength = Some(Strength::Primary);
+        if natural {
+            options.numeric = Some(Numeric::On);
+        }
+        match Collator::try_new(&(&locale).into(), options) {
+            Ok(collator) => Some(collator),
+            Err(e) => {
+                log::warn!(
+                    "No collation for {}, comparing normalized names: {}",
+                    language,
+                    e
+                );
+                None
+            }
+        }
+    }
+
+    /// Compares with the collator of the language preference, None without one
+    fn cmp_collated(left: &str, right: &str) -> Option<Ordering> {
+        let generation = COLLATION_GENERATION.load(AtomicOrdering::Relaxed);
+        if generation == 0 {
+            return None;
+        }
+        let natural = Self::is_natural_sort();
+        COLLATOR.with(|cell| {
+            let mut cached = cell.borrow_mut();
+            if !matches!(&*cached, Some((g, n, _)) if *g == generation && *n == natural) {
+                let language = COLLATION_LANGUAGE
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .clone();
+                let collator = language.and_then(|l| Self::build_collator(&l, natural));
+                *cached = Some((generation, natural, collator));
+            }
+            cached
+                .as_ref()
+                .and_then(|(_, _, collator)| collator.as_ref())
+                .map(|collator| collator.compare(left, right))
+        })
+    }
+
+    pub(crate) fn normalize_for_sorting(value: &str) -> String {
         // Approximate frontend localeCompare(sensitivity: "base") by normalizing (NFKC) and lowercasing
         value.nfkc().flat_map(|c| c.to_lowercase()).collect()
     }
 
-    fn cmp_case_insensitive(left: &str, right: &str) -> Ordering {
+    /// Compares names the way they are sorted, following the collation and natural sort settings
+    pub(crate) fn cmp_case_insensitive(left: &str, right: &str) -> Ordering {
+        if let Some(ordering) = Self::cmp_collated(left, right) {
+            return ordering;
+        }
         let l = Self::normalize_for_sorting(left);
         let r = Self::normalize_for_sorting(right);
-        l.cmp(&r)
+        if Self::is_natural_sort() {
+            Self::cmp_natural(&l, &r)
+        } else {
+            l.cmp(&r)
+        }
+    }
+
+    /// Compares runs of digits by their value and everything else by character
+    /// Equal numbers with more leading zeros sort last, so the order stays total
+    fn cmp_natural(left: &str, right: &str) -> Ordering {
+        let mut l = left.chars().peekable();
+        let mut r = right.chars().peekable();
+        loop {
+            match (l.peek().copied(), r.peek().copied()) {
+                (None, None) => return Ordering::Equal,
+                (None, Some(_)) => return Ordering::Less,
+                (Some(_), None) => return Ordering::Greater,
+                (Some(a), Some(b)) if a.is_ascii_digit() && b.is_ascii_digit() => {
+                    let a_digits = Self::take_digits(&mut l);
+                    let b_digits = Self::take_digits(&mut r);
+                    let a_value = a_digits.trim_start_matches('0');
+                    let b_value = b_digits.trim_start_matches('0');
+                    let ordering = a_value
+                        .len()
+                        .cmp(&b_value.len())
+                        .then_with(|| a_value.cmp(b_value))
+                        .then_with(|| a_digits.len().cmp(&b_digits.len()));
+                    if ordering != Ordering::Equal {
+                        return ordering;
+                    }
+                }
+                (Some(a), Some(b)) => {
+                    if a != b {
+                        return a.cmp(&b);
+                    }
+                    l.next();
+                    r.next();
+                }
+            }
+        }
+    }
+
+    fn take_digits(chars: &mut std::iter::Peekable<std::str::Chars<'_>>) -> String {
+        let mut digits = String::new();
+        while let Some(c) = chars.next_if(|c| c.is_ascii_digit()) {
+            digits.push(c);
+        }
+        digits
+    }
+
+    fn session_seed() -> u64 {
+        *RANDOM_SEED.get_or_init(rand::random)
+    }
+
+    /// A key which orders worlds randomly, but the same way for the same seed
+    fn random_key(seed: u64, world_id: &str) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        seed.hash(&mut hasher);
+        world_id.hash(&mut hasher);
+        hasher.finish()
     }
 
     fn apply_direction(ordering: Ordering, ascending: bool) -> Ordering {
@@ -41,6 +225,22 @@ impl SortingService {
             "capacity" => a.api_data.capacity.cmp(&b.api_data.capacity),
             "dateAdded" => a.user_data.date_added.cmp(&b.user_data.date_added),
             "lastUpdated" => a.api_data.last_update.cmp(&b.api_data.last_update),
+            "hasMemo" => (!a.user_data.memo.is_empty()).cmp(&!b.user_data.memo.is_empty()),
+            "rating" => a.user_data.rating.cmp(&b.user_data.rating),
+            "localVisitCount" => a.user_data.launch_count.cmp(&b.user_data.launch_count),
+            "pcDownloadSize" => Self::cmp_download_size(
+                a.api_data.download_size("standalonewindows"),
+                b.api_data.download_size("standalonewindows"),
+            ),
+            "questDownloadSize" => Self::cmp_download_size(
+                a.api_data.download_size("android"),
+                b.api_data.download_size("android"),
+            ),
+            "random" => {
+                let seed = Self::session_seed();
+                Self::random_key(seed, &a.api_data.world_id)
+                    .cmp(&Self::random_key(seed, &b.api_data.world_id))
+            }
             _ => Ordering::Equal,
         }
     }
@@ -58,10 +258,51 @@ impl SortingService {
             "capacity" => a.capacity.cmp(&b.capacity),
             "dateAdded" => a.date_added.cmp(&b.date_added),
             "lastUpdated" => a.last_updated.cmp(&b.last_updated),
+            "hasMemo" => a.has_memo.cmp(&b.has_memo),
+            "rating" => a.rating.cmp(&b.rating),
+            "localVisitCount" => a.local_visit_count.cmp(&b.local_visit_count),
+            "pcDownloadSize" => Self::cmp_download_size(a.pc_download_size, b.pc_download_size),
+            "questDownloadSize" => {
+                Self::cmp_download_size(a.quest_download_size, b.quest_download_size)
+            }
+            "random" => {
+                let seed = Self::session_seed();
+                Self::random_key(seed, &a.world_id).cmp(&Self::random_key(seed, &b.world_id))
+            }
             _ => Ordering::Equal,
         }
     }
 
+    /// Unknown sizes sort as the largest, so smallest first never starts with them
+    fn cmp_download_size(left: Option<u64>, right: Option<u64>) -> Ordering {
+        left.unwrap_or(u64::MAX).cmp(&right.unwrap_or(u64::MAX))
+    }
+
+    /// Keeps the worlds whose download for a platform is at most `max_size`
+    /// Worlds of unknown size are kept, as they may well be small
+    ///
+    /// # Arguments
+    /// * `worlds` - The worlds to filter
+    /// * `max_size` - The largest download to keep, in bytes
+    /// * `quest` - Whether to check the Quest build instead of the PC build
+    pub fn filter_by_download_size(
+        worlds: Vec<WorldDisplayData>,
+        max_size: u64,
+        quest: bool,
+    ) -> Vec<WorldDisplayData> {
+        worlds
+            .into_iter()
+            .filter(|w| {
+                let size = if quest {
+                    w.quest_download_size
+                } else {
+                    w.pc_download_size
+                };
+                !matches!(size, Some(size) if size > max_size)
+            })
+            .collect()
+    }
+
     fn apply_stable_tiebreakers_model(
         a: &WorldModel,
         b: &WorldModel,
@@ -117,10 +358,41 @@ impl SortingService {
     }
 }
 
+/// A saved world with fixed dates and otherwise blank data, shared by the tests of the
+/// services which only care about a world's ID and user data
+#[cfg(test)]
+pub(crate) fn make_test_world(id: &str) -> WorldModel {
+    use crate::definitions::{WorldApiData, WorldAvailability};
+    use chrono::{TimeZone, Utc};
+
+    let date = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    let mut world = WorldModel::new(WorldApiData {
+        world_id: id.to_string(),
+        world_name: "Test World".to_string(),
+        description: "Test Description".to_string(),
+        author_name: "Test Author".to_string(),
+        author_id: "test_author".to_string(),
+        tags: vec![],
+        publication_date: Some(date),
+        last_update: date,
+        image_url: "".to_string(),
+        capacity: 0,
+        recommended_capacity: None,
+        visits: None,
+        favorites: 0,
+        platform: vec![],
+        packages: vec![],
+        availability: WorldAvailability::Public,
+    });
+    world.user_data.date_added = date;
+    world.user_data.last_checked = date;
+    world
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::definitions::{Platform, WorldApiData, WorldUserData};
+    use crate::definitions::{Platform, WorldApiData, WorldAvailability, WorldUserData};
     use chrono::{NaiveDate, NaiveDateTime, NaiveTime, Utc};
 
     fn create_test_world_model(
@@ -153,6 +425,8 @@ mod tests {
                 visits,
                 favorites,
                 platform: vec!["standalonewindows".to_string()],
+                packages: vec![],
+                availability: WorldAvailability::Public,
             },
             user_data: WorldUserData {
                 date_added,
@@ -160,6 +434,14 @@ mod tests {
                 memo: "".to_string(),
                 folders: vec![],
                 hidden: false,
+                is_photographed: false,
+                is_shared: false,
+                is_favorite: false,
+                is_event_critical: false,
+                rating: None,
+                launch_count: 0,
+                cover_image: None,
+                in_inbox: false,
             },
         }
     }
@@ -179,6 +461,7 @@ mod tests {
             name: name.to_string(),
             thumbnail_url: "".to_string(),
             author_name: author.to_string(),
+            author_id: format!("usr_{}", author),
             favorites,
             last_updated: last_updated.to_string(),
             visits,
@@ -187,6 +470,16 @@ mod tests {
             folders: vec![],
             tags: vec![],
             capacity,
+            is_photographed: false,
+            is_shared: false,
+            is_favorite: false,
+            is_event_critical: false,
+            has_memo: false,
+            rating: None,
+            local_visit_count: 0,
+            pc_download_size: None,
+            quest_download_size: None,
+            availability: WorldAvailability::Public,
         }
     }
 
@@ -505,6 +798,114 @@ mod tests {
         assert_eq!(sorted[2].favorites, 5);
     }
 
+    #[test]
+    fn test_sort_by_memo_rating_and_local_visits() {
+        let mut worlds = vec![
+            create_test_world_model("1", "World1", "Author1", Some(100), 10, 16, 1, 1),
+            create_test_world_model("2", "World2", "Author2", Some(200), 20, 16, 2, 2),
+            create_test_world_model("3", "World3", "Author3", Some(150), 15, 16, 3, 3),
+        ];
+        worlds[1].user_data.memo = "Great mirror".to_string();
+        worlds[0].user_data.rating = Some(3);
+        worlds[2].user_data.rating = Some(5);
+        worlds[1].user_data.launch_count = 4;
+        worlds[2].user_data.launch_count = 1;
+
+        let by_memo = SortingService::sort_world_models(worlds.clone(), "hasMemo", "desc");
+        assert_eq!(by_memo[0].api_data.world_id, "2");
+
+        // Unrated worlds come last when sorting by highest rating
+        let by_rating = SortingService::sort_world_models(worlds.clone(), "rating", "desc");
+        let ids: Vec<&str> = by_rating
+            .iter()
+            .map(|w| w.api_data.world_id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["3", "1", "2"]);
+
+        let by_visits = SortingService::sort_world_models(worlds, "localVisitCount", "desc");
+        let ids: Vec<&str> = by_visits
+            .iter()
+            .map(|w| w.api_data.world_id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["2", "3", "1"]);
+    }
+
+    #[test]
+    fn test_sort_and_filter_by_download_size() {
+        let mut worlds: Vec<WorldDisplayData> = ["1", "2", "3"]
+            .iter()
+            .map(|id| create_test_world_display_data(id, id, "Author", 0, 0, 16, "", ""))
+            .collect();
+        worlds[0].quest_download_size = Some(90_000_000);
+        worlds[2].quest_download_size = Some(30_000_000);
+
+        // Unknown sizes come last when sorting by smallest download
+        let sorted =
+            SortingService::sort_world_display_data(worlds.clone(), "questDownloadSize", "asc");
+        let ids: Vec<&str> = sorted.iter().map(|w| w.world_id.as_str()).collect();
+        assert_eq!(ids, vec!["3", "1", "2"]);
+
+        let filtered = SortingService::filter_by_download_size(worlds, 50_000_000, true);
+        let ids: Vec<&str> = filtered.iter().map(|w| w.world_id.as_str()).collect();
+        assert_eq!(ids, vec!["2", "3"]);
+    }
+
+    #[test]
+    fn test_random_key_is_stable_per_seed() {
+        assert_eq!(
+            SortingService::random_key(7, "wrld_a"),
+            SortingService::random_key(7, "wrld_a")
+        );
+        let first: Vec<u64> = ["wrld_a", "wrld_b", "wrld_c"]
+            .iter()
+            .map(|id| SortingService::random_key(7, id))
+            .collect();
+        let second: Vec<u64> = ["wrld_a", "wrld_b", "wrld_c"]
+            .iter()
+            .map(|id| SortingService::random_key(8, id))
+            .collect();
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn test_cmp_natural() {
+        let mut names = vec![
+            "world 10", "world 2", "world 02", "world", "world 1b", "world 1a",
+        ];
+        names.sort_by(|a, b| SortingService::cmp_natural(a, b));
+        assert_eq!(
+            names,
+            vec!["world", "world 1a", "world 1b", "world 2", "world 02", "world 10"]
+        );
+        assert_eq!(
+            SortingService::cmp_natural("part 99999999999999999999", "part 100000000000000000000"),
+            Ordering::Less
+        );
+    }
+
+    #[test]
+    fn test_japanese_collation() {
+        let collator = SortingService::build_collator("ja", false).unwrap();
+        // Gojūon order, with hiragana and katakana equal at base strength
+        assert_eq!(collator.compare("さくら", "あおぞら"), Ordering::Greater);
+        assert_eq!(collator.compare("カフェ", "かふぇ"), Ordering::Equal);
+        assert_eq!(collator.compare("World", "world"), Ordering::Equal);
+
+        let natural = SortingService::build_collator("ja", true).unwrap();
+        assert_eq!(natural.compare("ワールド2", "ワールド10"), Ordering::Less);
+
+        assert!(SortingService::build_collator("not a language!", false).is_none());
+    }
+
+    #[test]
+    fn test_is_valid_sort() {
+        assert!(SortingService::is_valid_sort("lastUpdated", "desc"));
+        assert!(SortingService::is_valid_sort("name", "asc"));
+        assert!(SortingService::is_valid_sort("random", "asc"));
+        assert!(!SortingService::is_valid_sort("invalidField", "asc"));
+        assert!(!SortingService::is_valid_sort("name", "up"));
+    }
+
     #[test]
     fn test_empty_list() {
         let worlds: Vec<WorldModel> = vec![];
@@ -0,0 +1,794 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use reqwest::StatusCode;
+
+use crate::app_state::AppState;
+use crate::definitions::{
+    ConflictResolution, FolderModel, LibraryChange, SyncConfig, SyncStatus, SyncTarget,
+    VersionVector, WorldModel, WorldsStorageFormat,
+};
+use crate::errors::{AppError, EntityError, FileError, NetworkError};
+
+use super::{
+    ChangeFeed, FileService, IntegrityService, Journal, LibraryEncryption, Persistence, WorldLog,
+    WorldStorage,
+};
+
+/// Version vectors of the remote copies, stored next to them
+const MANIFEST: &str = "sync_manifest.json";
+const FOLDERS_FILE: &str = "folders.json";
+const WORLDS_FILE: &str = "worlds.json";
+const CUSTOM_DATA_FILE: &str = "custom_data.json";
+/// Synced as a whole, so edits made on both sides become conflicts
+const WHOLE_FILES: [&str; 2] = ["preferences.json", CUSTOM_DATA_FILE];
+const WEBDAV_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Only one sync runs at a time, as they share the manifest and the base copies
+static SYNC_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+type Manifest = BTreeMap<String, VersionVector>;
+
+/// Mirrors the library to a sync folder or WebDAV server, so several PCs can share it
+///
+/// Each remote copy has a version vector in the manifest, and a copy of every file as of
+/// the last sync is kept locally as the common base. A file edited on one side only is
+/// copied over. When both sides edited the library, folders and worlds are merged against
+/// the base: folders are united, and worlds keep the most recently fetched VRChat data along
+/// with the edits of each side. Other files edited on both sides are reported as conflicts,
+/// and keeping the remote custom data replays the world flags set locally from the change feed
+///
+/// Sync is not available while library encryption is enabled, as the other PCs could not
+/// read the encrypted files
+#[derive(Debug)]
+pub struct SyncService;
+
+impl SyncService {
+    /// Gets where the library is synced to and the files in conflict
+    pub fn status() -> SyncStatus {
+        Self::to_status(&FileService::read_sync_config())
+    }
+
+    /// Sets where the library is synced to, or turns sync off
+    /// The sync history is reset, so the first sync with the new target merges both sides
+    ///
+    /// # Arguments
+    /// * `target` - The sync folder or WebDAV server, None to turn sync off
+    ///
+    /// # Returns
+    /// The new sync status
+    ///
+    /// # Errors
+    /// Returns an error if the sync settings could not be saved
+    pub async fn set_target(target: Option<SyncTarget>) -> Result<SyncStatus, AppError> {
+        let _sync = SYNC_LOCK.lock().await;
+        let mut config = Self::load_config();
+        config.target = target;
+        config.last_synced = None;
+        config.versions.clear();
+        config.conflicts.clear();
+
+        let base_dir = FileService::get_sync_base_dir();
+        if base_dir.exists() {
+            fs::remove_dir_all(&base_dir).map_err(|_| FileError::FileWriteError)?;
+        }
+        FileService::write_sync_config(&config)?;
+        Ok(Self::to_status(&config))
+    }
+
+    /// Exchanges changes with the sync target, then reloads the library if anything
+    /// was received
+    ///
+    /// # Arguments
+    /// * `state` - The app state, reloaded after receiving changes
+    ///
+    /// # Returns
+    /// The sync status, listing any new conflicts
+    ///
+    /// # Errors
+    /// Returns an error if sync is not set up or library encryption is enabled
+    /// Returns an error if the sync target could not be read or written
+    pub async fn sync(state: &AppState) -> Result<SyncStatus, AppError> {
+        let _sync = SYNC_LOCK.lock().await;
+        let mut config = Self::load_config();
+        let target = Self::target(&config)?;
+        Self::check_unencrypted()?;
+        Persistence::flush().await?;
+        let format = state.preferences()?.worlds_storage;
+
+        let mut manifest = Self::read_manifest(&target).await?;
+        let mut received = false;
+
+        for name in WHOLE_FILES {
+            if config.conflicts.iter().any(|c| c == name) {
+                continue;
+            }
+            let Some(local) = Self::read_local(name)? else {
+                continue;
+            };
+            let local_changed = Some(&local) != Self::read_base(name)?.as_ref();
+            let remote_changed =
+                Self::remote_version(&manifest, name) != Self::version(&config, name);
+
+            match (local_changed, remote_changed) {
+                (false, false) => {}
+                (true, false) => {
+                    Self::push(&target, &mut config, &mut manifest, name, &local).await?;
+                }
+                (false, true) => {
+                    if let Some(remote) = Self::read_remote(&target, name).await? {
+                        Self::pull(&mut config, &manifest, name, &remote, format)?;
+                        received = true;
+                    }
+                }
+                (true, true) => match Self::read_remote(&target, name).await? {
+                    Some(remote) if remote != local => {
+                        log::warn!("{} was edited on both sides, waiting for resolution", name);
+                        config.conflicts.push(name.to_string());
+                    }
+                    _ => {
+                        // Both sides made the same edit, or the remote copy is gone
+                        Self::push(&target, &mut config, &mut manifest, name, &local).await?;
+                    }
+                },
+            }
+        }
+
+        Self::sync_library(state, &target, &mut config, &mut manifest, format).await?;
+
+        Self::write_manifest(&target, &manifest).await?;
+        config.last_synced = Some(Utc::now());
+        FileService::write_sync_config(&config)?;
+
+        if received {
+            Self::reload(state).await?;
+        }
+        log::info!("Synced library with {}", target.location());
+        Ok(Self::to_status(&config))
+    }
+
+    /// Resolves a conflict by keeping one copy of the file on both sides
+    ///
+    /// # Arguments
+    /// * `state` - The app state, reloaded when keeping the remote copy
+    /// * `file` - The conflicting file, as listed in the sync status
+    /// * `resolution` - Which copy to keep
+    ///
+    /// # Returns
+    /// The sync status without the resolved conflict
+    ///
+    /// # Errors
+    /// Returns an error if the file is not in conflict or sync is not available
+    /// Returns an error if the sync target could not be read or written
+    pub async fn resolve_conflict(
+        state: &AppState,
+        file: String,
+        resolution: ConflictResolution,
+    ) -> Result<SyncStatus, AppError> {
+        let _sync = SYNC_LOCK.lock().await;
+        let mut config = Self::load_config();
+        let target = Self::target(&config)?;
+        if !config.conflicts.contains(&file) {
+            return Err(
+                EntityError::InvalidOperation(format!("{} is not in conflict", file)).into(),
+            );
+        }
+        Self::check_unencrypted()?;
+        Persistence::flush().await?;
+        let format = state.preferences()?.worlds_storage;
+
+        let mut manifest = Self::read_manifest(&target).await?;
+        match resolution {
+            ConflictResolution::KeepLocal => {
+                let local = Self::read_local(&file)?.ok_or(FileError::FileNotFound)?;
+                Self::push(&target, &mut config, &mut manifest, &file, &local).await?;
+                Self::write_manifest(&target, &manifest).await?;
+            }
+            ConflictResolution::KeepRemote => {
+                let remote = Self::read_remote(&target, &file)
+                    .await?
+                    .ok_or(FileError::FileNotFound)?;
+                Self::pull(&mut config, &manifest, &file, &remote, format)?;
+                if file == CUSTOM_DATA_FILE {
+                    Self::replay_local_flags(config.last_synced)?;
+                }
+            }
+        }
+
+        config.conflicts.retain(|c| c != &file);
+        FileService::write_sync_config(&config)?;
+        if resolution == ConflictResolution::KeepRemote {
+            Self::reload(state).await?;
+        }
+        Ok(Self::to_status(&config))
+    }
+
+    /// Re-applies the world flags set locally since the last sync onto the pulled custom
+    /// data, so keeping the remote copy only drops the local edits the change feed lacks
+    /// The result differs from the base, so it is pushed on the next sync
+    fn replay_local_flags(since: Option<DateTime<Utc>>) -> Result<(), AppError> {
+        let Some(since) = since else {
+            return Ok(());
+        };
+        let mut custom_data = FileService::read_custom_data();
+        let mut replayed = 0;
+        for event in ChangeFeed::effective_since(since)? {
+            if let LibraryChange::FlagChanged {
+                world_id,
+                flag,
+                value,
+            } = event.change
+            {
+                custom_data.set_world_flag(&world_id, flag, value);
+                replayed += 1;
+            }
+        }
+        if replayed > 0 {
+            FileService::write_custom_data(&custom_data)?;
+            log::info!(
+                "Replayed {} local flag changes onto the remote copy",
+                replayed
+            );
+        }
+        Ok(())
+    }
+
+    /// Syncs folders and worlds together, as folders refer to worlds
+    /// Remote edits are applied and reloaded into the state by `apply_remote_library`
+    async fn sync_library(
+        state: &AppState,
+        target: &SyncTarget,
+        config: &mut SyncConfig,
+        manifest: &mut Manifest,
+        format: WorldsStorageFormat,
+    ) -> Result<(), AppError> {
+        let names = [FOLDERS_FILE, WORLDS_FILE];
+        let remote_changed = names
+            .iter()
+            .any(|name| Self::remote_version(manifest, name) != Self::version(config, name));
+
+        if !remote_changed {
+            // worlds.json is compared and uploaded as it is on disk
+            WorldLog::compact()?;
+            for name in names {
+                let local = Self::read_local(name)?;
+                let base = Self::read_base(name)?;
+                if let Some(local) = local.filter(|local| Some(local) != base.as_ref()) {
+                    Self::push(target, config, manifest, name, &local).await?;
+                }
+            }
+            return Ok(());
+        }
+
+        let mut remote = Vec::with_capacity(names.len());
+        for name in names {
+            remote.push(Self::read_remote(target, name).await?);
+        }
+
+        if let Some(merged) = Self::apply_remote_library(state, config, manifest, &remote, format)?
+        {
+            for (name, data) in names.iter().zip(&merged) {
+                Self::push(target, config, manifest, name, data).await?;
+            }
+            log::info!("Merged library edits made on both sides");
+        }
+        Ok(())
+    }
+
+    /// Applies the remote folders and worlds, merging them with local edits if there are
+    /// any, then reloads them into the state
+    /// The library locks are held from reading the local library until it is reloaded, so
+    /// an edit made meanwhile is neither lost nor overwritten by the reload
+    ///
+    /// # Returns
+    /// The merged folders and worlds, to be pushed, if both sides edited the library
+    fn apply_remote_library(
+        state: &AppState,
+        config: &mut SyncConfig,
+        manifest: &Manifest,
+        remote: &[Option<String>],
+        format: WorldsStorageFormat,
+    ) -> Result<Option<[String; 2]>, AppError> {
+        let (mut folders_lock, mut worlds_lock) = state.library_mut()?;
+
+        // worlds.json is compared as it is on disk
+        WorldLog::compact()?;
+        let names = [FOLDERS_FILE, WORLDS_FILE];
+        let mut local = Vec::with_capacity(names.len());
+        let mut base = Vec::with_capacity(names.len());
+        for name in names {
+            local.push(Self::read_local(name)?);
+            base.push(Self::read_base(name)?);
+        }
+
+        let journal = Journal::begin("sync_library", &Self::library_paths())?;
+        let merged = if local == base {
+            for (name, remote) in names.iter().zip(remote) {
+                if let Some(remote) = remote {
+                    Self::pull(config, manifest, name, remote, format)?;
+                }
+            }
+            None
+        } else {
+            let mut folders = Self::merge_folders(
+                &Self::parse(&base[0])?,
+                &Self::parse(&local[0])?,
+                &Self::parse(&remote[0])?,
+            );
+            let mut worlds = Self::merge_worlds(
+                &Self::parse(&base[1])?,
+                &Self::parse(&local[1])?,
+                &Self::parse(&remote[1])?,
+            );
+            IntegrityService::reconcile(&mut folders, &mut worlds);
+
+            let merged = [
+                serde_json::to_string_pretty(&folders).map_err(|_| FileError::InvalidFile)?,
+                serde_json::to_string_pretty(&worlds).map_err(|_| FileError::InvalidFile)?,
+            ];
+            for (name, data) in names.iter().zip(&merged) {
+                Self::write_local(name, data, format)?;
+            }
+            Some(merged)
+        };
+        journal.commit();
+
+        // Folder colors and world flags live in custom data, so the library is read back
+        // from disk rather than taken from the parsed files
+        let (_, folders, _) = FileService::load_data_without_worlds()?;
+        *worlds_lock = FileService::load_worlds(&folders);
+        *folders_lock = folders;
+        Ok(merged)
+    }
+
+    /// Merges the folders of both sides, keeping every folder either side has
+    fn merge_folders(
+        base: &[FolderModel],
+        local: &[FolderModel],
+        remote: &[FolderModel],
+    ) -> Vec<FolderModel> {
+        let find = |folders: &[FolderModel], name: &str| {
+            folders
+                .iter()
+                .find(|f| f.folder_name == name)
+                .map(|f| f.world_ids.clone())
+                .unwrap_or_default()
+        };
+
+        let mut merged: Vec<FolderModel> = Vec::new();
+        for folder in local.iter().chain(remote) {
+            if merged.iter().any(|f| f.folder_name == folder.folder_name) {
+                continue;
+            }
+            let name = &folder.folder_name;
+            let mut result = folder.clone();
+            result.world_ids =
+                Self::merge_ids(&find(base, name), &find(local, name), &find(remote, name));
+            if result.share.is_none() {
+                result.share = remote
+                    .iter()
+                    .find(|f| &f.folder_name == name)
+                    .and_then(|f| f.share.clone());
+            }
+            merged.push(result);
+        }
+        merged
+    }
+
+    /// Merges two edited lists of IDs against their base
+    /// The local order is kept, IDs removed on either side are dropped,
+    /// and IDs added remotely are appended
+    fn merge_ids(base: &[String], local: &[String], remote: &[String]) -> Vec<String> {
+        let removed_locally = Self::removed_ids(base, local);
+        let removed_remotely = Self::removed_ids(base, remote);
+
+        let mut merged: Vec<String> = local
+            .iter()
+            .filter(|id| !removed_remotely.contains(id.as_str()))
+            .cloned()
+            .collect();
+        for id in remote {
+            if !removed_locally.contains(id.as_str()) && !merged.contains(id) {
+                merged.push(id.clone());
+            }
+        }
+        merged
+    }
+
+    fn removed_ids<'a>(base: &'a [String], side: &[String]) -> HashSet<&'a str> {
+        base.iter()
+            .filter(|id| !side.contains(id))
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Merges the worlds of both sides
+    /// A world deleted on one side stays deleted, unless the other side edited it since
+    fn merge_worlds(
+        base: &[WorldModel],
+        local: &[WorldModel],
+        remote: &[WorldModel],
+    ) -> Vec<WorldModel> {
+        let base_by_id = Self::index(base);
+        let local_by_id = Self::index(local);
+        let remote_by_id = Self::index(remote);
+        let edited_since = |world: &WorldModel| {
+            base_by_id
+                .get(world.api_data.world_id.as_str())
+                .map_or(true, |base| {
+                    Self::user_edits(base) != Self::user_edits(world)
+                })
+        };
+
+        let mut merged = Vec::new();
+        for world in local {
+            let id = world.api_data.world_id.as_str();
+            match remote_by_id.get(id) {
+                Some(remote) => merged.push(Self::merge_world(
+                    base_by_id.get(id).copied(),
+                    world,
+                    remote,
+                )),
+                None if edited_since(world) => merged.push(world.clone()),
+                None => {}
+            }
+        }
+        for world in remote {
+            let id = world.api_data.world_id.as_str();
+            if !local_by_id.contains_key(id) && edited_since(world) {
+                merged.push(world.clone());
+            }
+        }
+        merged
+    }
+
+    /// Keeps the most recently fetched VRChat data, and each field the user edited on
+    /// one side only; local edits win when both sides edited the same field
+    fn merge_world(
+        base: Option<&WorldModel>,
+        local: &WorldModel,
+        remote: &WorldModel,
+    ) -> WorldModel {
+        let mut merged = local.clone();
+        if remote.user_data.last_checked > local.user_data.last_checked {
+            merged.api_data = remote.api_data.clone();
+            merged.user_data.last_checked = remote.user_data.last_checked;
+        }
+
+        if let Some(base) = base {
+            if local.user_data.memo == base.user_data.memo {
+                merged.user_data.memo = remote.user_data.memo.clone();
+            }
+            if local.user_data.hidden == base.user_data.hidden {
+                merged.user_data.hidden = remote.user_data.hidden;
+            }
+        }
+        merged.user_data.date_added = local.user_data.date_added.min(remote.user_data.date_added);
+        merged
+    }
+
+    fn user_edits(world: &WorldModel) -> (&str, bool) {
+        (world.user_data.memo.as_str(), world.user_data.hidden)
+    }
+
+    fn index(worlds: &[WorldModel]) -> HashMap<&str, &WorldModel> {
+        worlds
+            .iter()
+            .map(|w| (w.api_data.world_id.as_str(), w))
+            .collect()
+    }
+
+    fn parse<T: serde::de::DeserializeOwned + Default>(
+        data: &Option<String>,
+    ) -> Result<T, AppError> {
+        match data {
+            Some(data) => Ok(serde_json::from_str(data).map_err(|_| FileError::InvalidFile)?),
+            None => Ok(T::default()),
+        }
+    }
+
+    /// Uploads a file as a new version, which includes every version seen so far
+    async fn push(
+        target: &SyncTarget,
+        config: &mut SyncConfig,
+        manifest: &mut Manifest,
+        name: &str,
+        data: &str,
+    ) -> Result<(), AppError> {
+        let version = Self::version(config, name)
+            .merged(&Self::remote_version(manifest, name))
+            .bumped(&config.device_id);
+        Self::write_remote(target, name, data).await?;
+        Persistence::write_async(Self::base_path(name)?, data.to_string()).await?;
+        manifest.insert(name.to_string(), version.clone());
+        config.versions.insert(name.to_string(), version);
+        Ok(())
+    }
+
+    /// Replaces the local file with the remote copy
+    fn pull(
+        config: &mut SyncConfig,
+        manifest: &Manifest,
+        name: &str,
+        data: &str,
+        format: WorldsStorageFormat,
+    ) -> Result<(), AppError> {
+        Self::write_local(name, data, format)?;
+        Self::write_base(name, data)?;
+        config
+            .versions
+            .insert(name.to_string(), Self::remote_version(manifest, name));
+        Ok(())
+    }
+
+    async fn reload(state: &AppState) -> Result<(), AppError> {
+        crate::reload_library(state).await.map_err(|e| {
+            log::error!("Failed to reload library after sync: {}", e);
+            FileError::InvalidFile.into()
+        })
+    }
+
+    fn load_config() -> SyncConfig {
+        let mut config = FileService::read_sync_config();
+        if config.device_id.is_empty() {
+            config.device_id = uuid::Uuid::new_v4().to_string();
+        }
+        config
+    }
+
+    fn target(config: &SyncConfig) -> Result<SyncTarget, AppError> {
+        config
+            .target
+            .clone()
+            .ok_or_else(|| EntityError::InvalidOperation("Sync is not set up".to_string()).into())
+    }
+
+    fn check_unencrypted() -> Result<(), AppError> {
+        if LibraryEncryption::status()?.enabled {
+            return Err(EntityError::InvalidOperation(
+                "Sync is not available while library encryption is enabled".to_string(),
+            )
+            .into());
+        }
+        Ok(())
+    }
+
+    fn to_status(config: &SyncConfig) -> SyncStatus {
+        SyncStatus {
+            location: config.target.as_ref().map(SyncTarget::location),
+            last_synced: config.last_synced,
+            conflicts: config.conflicts.clone(),
+        }
+    }
+
+    fn version(config: &SyncConfig, name: &str) -> VersionVector {
+        config.versions.get(name).cloned().unwrap_or_default()
+    }
+
+    fn remote_version(manifest: &Manifest, name: &str) -> VersionVector {
+        manifest.get(name).cloned().unwrap_or_default()
+    }
+
+    fn local_path(name: &str) -> PathBuf {
+        FileService::get_app_dir().join(name)
+    }
+
+    fn library_paths() -> [PathBuf; 2] {
+        [
+            Self::local_path(FOLDERS_FILE),
+            Self::local_path(WORLDS_FILE),
+        ]
+    }
+
+    /// Reads a local file, worlds as JSON even while they are stored compressed, so both
+    /// sides compare and exchange the same content whichever format each stores them in
+    fn read_local(name: &str) -> Result<Option<String>, AppError> {
+        let path = Self::local_path(name);
+        if name != WORLDS_FILE {
+            return Self::read_optional(&path);
+        }
+        if !WorldStorage::stored_path(&path).exists() {
+            return Ok(None);
+        }
+        Ok(Some(WorldStorage::read_json(&path)?))
+    }
+
+    /// Writes a local file, compressing worlds if they are stored compressed
+    fn write_local(name: &str, data: &str, format: WorldsStorageFormat) -> Result<(), AppError> {
+        let path = Self::local_path(name);
+        if name != WORLDS_FILE {
+            return Ok(Persistence::write(&path, data)?);
+        }
+        Ok(WorldStorage::write_json(&path, data, format)?)
+    }
+
+    fn read_base(name: &str) -> Result<Option<String>, AppError> {
+        Self::read_optional(&FileService::get_sync_base_dir().join(name))
+    }
+
+    fn write_base(name: &str, data: &str) -> Result<(), AppError> {
+        Ok(Persistence::write(&Self::base_path(name)?, data)?)
+    }
+
+    /// The base copy of a file, creating the directory it is kept in
+    fn base_path(name: &str) -> Result<PathBuf, AppError> {
+        let base_dir = FileService::get_sync_base_dir();
+        fs::create_dir_all(&base_dir).map_err(|_| FileError::FileWriteError)?;
+        Ok(base_dir.join(name))
+    }
+
+    fn read_optional(path: &PathBuf) -> Result<Option<String>, AppError> {
+        match fs::read_to_string(path) {
+            Ok(data) => Ok(Some(data)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => {
+                log::error!("Failed to read {:?}: {}", path, e);
+                Err(FileError::AccessDenied.into())
+            }
+        }
+    }
+
+    async fn read_manifest(target: &SyncTarget) -> Result<Manifest, AppError> {
+        Self::parse(&Self::read_remote(target, MANIFEST).await?)
+    }
+
+    async fn write_manifest(target: &SyncTarget, manifest: &Manifest) -> Result<(), AppError> {
+        let data = serde_json::to_string_pretty(manifest).map_err(|_| FileError::InvalidFile)?;
+        Self::write_remote(target, MANIFEST, &data).await
+    }
+
+    async fn read_remote(target: &SyncTarget, name: &str) -> Result<Option<String>, AppError> {
+        match target {
+            SyncTarget::Folder { path } => Self::read_optional(&PathBuf::from(path).join(name)),
+            SyncTarget::WebDav {
+                url,
+                username,
+                password,
+            } => {
+                let response = Self::webdav_client()?
+                    .get(Self::webdav_url(url, name))
+                    .basic_auth(username, Some(password))
+                    .send()
+                    .await
+                    .map_err(Self::network_error)?;
+                match response.status() {
+                    StatusCode::NOT_FOUND => Ok(None),
+                    status if status.is_success() => Ok(Some(
+                        response
+                            .text()
+                            .await
+                            .map_err(|_| NetworkError::InvalidResponse)?,
+                    )),
+                    status => Err(NetworkError::HttpError(status.as_u16()).into()),
+                }
+            }
+        }
+    }
+
+    async fn write_remote(target: &SyncTarget, name: &str, data: &str) -> Result<(), AppError> {
+        match target {
+            SyncTarget::Folder { path } => {
+                // Written next to the target and renamed, so sync clients never upload half a file
+                let dir = PathBuf::from(path);
+                let temp_path = dir.join(format!("{}.tmp", name));
+                fs::create_dir_all(&dir)
+                    .and_then(|_| fs::write(&temp_path, data))
+                    .and_then(|_| fs::rename(&temp_path, dir.join(name)))
+                    .map_err(|e| {
+                        log::error!("Failed to write {} to sync folder: {}", name, e);
+                        FileError::FileWriteError.into()
+                    })
+            }
+            SyncTarget::WebDav {
+                url,
+                username,
+                password,
+            } => {
+                let response = Self::webdav_client()?
+                    .put(Self::webdav_url(url, name))
+                    .basic_auth(username, Some(password))
+                    .body(data.to_string())
+                    .send()
+                    .await
+                    .map_err(Self::network_error)?;
+                if !response.status().is_success() {
+                    return Err(NetworkError::HttpError(response.status().as_u16()).into());
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn webdav_client() -> Result<reqwest::Client, AppError> {
+        reqwest::Client::builder()
+            .timeout(WEBDAV_TIMEOUT)
+            .build()
+            .map_err(|_| NetworkError::ConnectionFailed.into())
+    }
+
+    fn webdav_url(url: &str, name: &str) -> String {
+        format!("{}/{}", url.trim_end_matches('/'), name)
+    }
+
+    fn network_error(e: reqwest::Error) -> NetworkError {
+        log::warn!("WebDAV request failed: {}", e);
+        if e.is_timeout() {
+            NetworkError::Timeout
+        } else {
+            NetworkError::ConnectionFailed
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::sorting_service::make_test_world;
+    use chrono::{TimeZone, Utc};
+
+    fn ids(ids: &[&str]) -> Vec<String> {
+        ids.iter().map(|id| id.to_string()).collect()
+    }
+
+    fn make_world(id: &str, memo: &str) -> WorldModel {
+        let mut world = make_test_world(id);
+        world.user_data.memo = memo.to_string();
+        world
+    }
+
+    #[test]
+    fn test_merge_ids_applies_both_sides() {
+        let base = ids(&["a", "b", "c"]);
+        let local = ids(&["c", "a", "d"]);
+        let remote = ids(&["a", "c", "e"]);
+
+        assert_eq!(
+            SyncService::merge_ids(&base, &local, &remote),
+            ids(&["c", "a", "d", "e"])
+        );
+    }
+
+    #[test]
+    fn test_merge_folders_unites_folders() {
+        let mut local = FolderModel::new("Local".to_string());
+        local.world_ids = ids(&["a"]);
+        let mut remote = FolderModel::new("Remote".to_string());
+        remote.world_ids = ids(&["b"]);
+
+        let merged = SyncService::merge_folders(&[], &[local], &[remote]);
+        let names: Vec<&str> = merged.iter().map(|f| f.folder_name.as_str()).collect();
+        assert_eq!(names, vec!["Local", "Remote"]);
+    }
+
+    #[test]
+    fn test_merge_worlds_keeps_edits_of_both_sides() {
+        let base = vec![make_world("wrld_1", ""), make_world("wrld_2", "")];
+        let mut local = vec![make_world("wrld_1", "")];
+        local[0].user_data.hidden = true;
+        let mut remote = base.clone();
+        remote[0].user_data.memo = "remote memo".to_string();
+        remote[0].user_data.last_checked = Utc.with_ymd_and_hms(2024, 6, 1, 0, 0, 0).unwrap();
+        remote[0].api_data.world_name = "Renamed".to_string();
+
+        let merged = SyncService::merge_worlds(&base, &local, &remote);
+        // wrld_2 was deleted locally and left untouched remotely
+        assert_eq!(merged.len(), 1);
+        assert!(merged[0].user_data.hidden);
+        assert_eq!(merged[0].user_data.memo, "remote memo");
+        assert_eq!(merged[0].api_data.world_name, "Renamed");
+    }
+
+    #[test]
+    fn test_version_vector_ordering() {
+        let base = VersionVector::default().bumped("pc_a");
+        let local = base.bumped("pc_a");
+        let remote = base.bumped("pc_b");
+
+        assert!(local.includes(&base));
+        assert!(!local.includes(&remote) && !remote.includes(&local));
+        assert!(local.merged(&remote).includes(&remote));
+    }
+}
@@ -0,0 +1,205 @@
+use std::collections::BTreeMap;
+use std::sync::{PoisonError, RwLock};
+
+use crate::definitions::TagRules;
+use crate::errors::{AppError, EntityError};
+
+use super::FileService;
+
+const AUTHOR_TAG_PREFIX: &str = "author_tag_";
+
+/// The rules from custom_data.json, kept in memory as they are applied to every world shown
+static RULES: RwLock<TagRules> = RwLock::new(TagRules {
+    aliases: BTreeMap::new(),
+    hidden: Vec::new(),
+});
+
+/// Service for consolidating messy author tags, e.g. "chill", "chill_" and "Chill",
+/// by aliasing them to one tag or hiding them
+/// Only how tags are shown and matched changes, the worlds' data from VRChat is kept
+#[derive(Debug)]
+pub struct TagService;
+
+impl TagService {
+    /// Reads the rules from disk, called on startup and when the library is reloaded
+    pub fn init() {
+        let custom_data = FileService::read_custom_data();
+        *RULES.write().unwrap_or_else(PoisonError::into_inner) = TagRules {
+            aliases: custom_data.tag_aliases,
+            hidden: custom_data.hidden_tags,
+        };
+    }
+
+    pub fn get_rules() -> TagRules {
+        RULES.read().unwrap_or_else(PoisonError::into_inner).clone()
+    }
+
+    /// Shows a tag as another one
+    /// Tags already aliased to `from` are aliased to `to` instead, and if `to` was aliased
+    /// to `from` the alias is reversed
+    ///
+    /// # Returns
+    /// The updated rules
+    ///
+    /// # Errors
+    /// Returns an error if either tag is empty, or the rules could not be saved
+    pub fn alias_tag(from: &str, to: &str) -> Result<TagRules, AppError> {
+        let (from, to) = (Self::normalize(from), Self::normalize(to));
+        if from.is_empty() || to.is_empty() {
+            return Err(EntityError::InvalidOperation("Tags must not be empty".to_string()).into());
+        }
+        Self::update(|rules| Self::add_alias(rules, from, to))
+    }
+
+    /// Shows a tag as itself again
+    ///
+    /// # Errors
+    /// Returns an error if the rules could not be saved
+    pub fn remove_alias(tag: &str) -> Result<TagRules, AppError> {
+        let tag = Self::normalize(tag);
+        Self::update(|rules| {
+            rules.aliases.remove(&tag);
+        })
+    }
+
+    /// Hides or shows a tag, after aliases are applied
+    ///
+    /// # Errors
+    /// Returns an error if the rules could not be saved
+    pub fn set_tag_hidden(tag: &str, hidden: bool) -> Result<TagRules, AppError> {
+        let tag = Self::normalize(tag);
+        Self::update(|rules| {
+            rules.hidden.retain(|t| *t != tag);
+            if hidden {
+                rules.hidden.push(tag);
+                rules.hidden.sort();
+            }
+        })
+    }
+
+    /// Applies the rules to the tags of a world
+    /// Author tags are replaced by their alias, hidden ones are removed, and duplicates
+    /// left by aliasing are dropped; other tags are kept as they are
+    pub fn apply(tags: &[String]) -> Vec<String> {
+        let rules = RULES.read().unwrap_or_else(PoisonError::into_inner);
+        Self::apply_rules(&rules, tags)
+    }
+
+    fn update(change: impl FnOnce(&mut TagRules)) -> Result<TagRules, AppError> {
+        let mut rules = RULES.write().unwrap_or_else(PoisonError::into_inner);
+        let mut custom_data = FileService::read_custom_data();
+        let mut updated = rules.clone();
+        change(&mut updated);
+        custom_data.tag_aliases = updated.aliases.clone();
+        custom_data.hidden_tags = updated.hidden.clone();
+        FileService::write_custom_data(&custom_data)?;
+        *rules = updated.clone();
+        Ok(updated)
+    }
+
+    /// Keeps aliases one step deep, so no tag is aliased to an aliased tag
+    fn add_alias(rules: &mut TagRules, from: String, mut to: String) {
+        if let Some(target) = rules.aliases.get(&to).cloned() {
+            if target == from {
+                rules.aliases.remove(&to);
+            } else {
+                to = target;
+            }
+        }
+        if from == to {
+            rules.aliases.remove(&from);
+            return;
+        }
+        for target in rules.aliases.values_mut() {
+            if *target == from {
+                *target = to.clone();
+            }
+        }
+        rules.aliases.insert(from, to);
+    }
+
+    /// The tag an author tag is shown as, without "author_tag_", or None if it is hidden
+    fn resolve_in(rules: &TagRules, tag: &str) -> Option<String> {
+        let tag = Self::normalize(tag);
+        let tag = rules.aliases.get(&tag).cloned().unwrap_or(tag);
+        (!rules.hidden.contains(&tag)).then_some(tag)
+    }
+
+    fn apply_rules(rules: &TagRules, tags: &[String]) -> Vec<String> {
+        if rules.aliases.is_empty() && rules.hidden.is_empty() {
+            return tags.to_vec();
+        }
+        let mut applied: Vec<String> = vec![];
+        for tag in tags {
+            let tag = match tag.strip_prefix(AUTHOR_TAG_PREFIX) {
+                Some(author_tag) => {
+                    let normalized = Self::normalize(author_tag);
+                    if !rules.aliases.contains_key(&normalized)
+                        && !rules.hidden.contains(&normalized)
+                    {
+                        tag.clone()
+                    } else {
+                        match Self::resolve_in(rules, author_tag) {
+                            Some(resolved) => format!("{}{}", AUTHOR_TAG_PREFIX, resolved),
+                            None => continue,
+                        }
+                    }
+                }
+                None => tag.clone(),
+            };
+            if !applied.iter().any(|t| t.eq_ignore_ascii_case(&tag)) {
+                applied.push(tag);
+            }
+        }
+        applied
+    }
+
+    fn normalize(tag: &str) -> String {
+        let tag = tag.trim().to_lowercase();
+        tag.strip_prefix(AUTHOR_TAG_PREFIX)
+            .map(str::to_string)
+            .unwrap_or(tag)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tags(tags: &[&str]) -> Vec<String> {
+        tags.iter().map(|t| t.to_string()).collect()
+    }
+
+    #[test]
+    fn test_add_alias_keeps_aliases_one_step_deep() {
+        let mut rules = TagRules::default();
+        TagService::add_alias(&mut rules, "chill_".to_string(), "chill".to_string());
+        TagService::add_alias(&mut rules, "chill".to_string(), "relax".to_string());
+        assert_eq!(rules.aliases["chill_"], "relax");
+        assert_eq!(rules.aliases["chill"], "relax");
+
+        // Aliasing the target back to the tag reverses the alias
+        TagService::add_alias(&mut rules, "relax".to_string(), "chill".to_string());
+        assert_eq!(rules.aliases["relax"], "chill");
+        assert_eq!(rules.aliases["chill_"], "chill");
+        assert!(!rules.aliases.contains_key("chill"));
+    }
+
+    #[test]
+    fn test_apply_rules_merges_and_hides_author_tags() {
+        let rules = TagRules {
+            aliases: BTreeMap::from([("chill_".to_string(), "chill".to_string())]),
+            hidden: vec!["wip".to_string()],
+        };
+        let applied = TagService::apply_rules(
+            &rules,
+            &tags(&[
+                "author_tag_Chill",
+                "author_tag_chill_",
+                "author_tag_WIP",
+                "system_approved",
+            ]),
+        );
+        assert_eq!(applied, tags(&["author_tag_Chill", "system_approved"]));
+    }
+}
@@ -0,0 +1,75 @@
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use reqwest::cookie::Jar;
+
+use super::{ApiService, FileService};
+
+/// Service for keeping world thumbnails on disk, so they show without waiting on the network,
+/// e.g. while presenting a folder at an event
+#[derive(Debug)]
+pub struct ThumbnailCache;
+
+impl ThumbnailCache {
+    /// Gets the cached thumbnail of a world, None if it was never downloaded
+    pub fn get(world_id: &str) -> Option<PathBuf> {
+        Self::path(world_id).filter(|path| path.is_file())
+    }
+
+    /// Downloads a world's thumbnail into the cache, unless it is already there
+    ///
+    /// # Arguments
+    /// * `cookie_store` - The cookie store to use for the API
+    /// * `world_id` - The world, which names the cached file
+    /// * `url` - The world's thumbnail URL
+    ///
+    /// # Returns
+    /// Whether the thumbnail was downloaded, false if it was already cached
+    ///
+    /// # Errors
+    /// Returns an error message if the thumbnail could not be downloaded or saved
+    pub async fn fetch(cookie_store: Arc<Jar>, world_id: &str, url: &str) -> Result<bool, String> {
+        let path = Self::path(world_id).ok_or_else(|| format!("Invalid world ID: {}", world_id))?;
+        if path.is_file() {
+            return Ok(false);
+        }
+        if url.is_empty() {
+            return Err(format!("World {} has no thumbnail", world_id));
+        }
+
+        let image = ApiService::get_world_image(cookie_store, url)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        fs::create_dir_all(FileService::get_thumbnail_cache_dir())
+            .map_err(|e| format!("Failed to create thumbnail cache: {}", e))?;
+        // Written under another name first, so a failed write never leaves half an image
+        let partial = path.with_extension("part");
+        fs::write(&partial, image).map_err(|e| format!("Failed to save thumbnail: {}", e))?;
+        fs::rename(&partial, &path).map_err(|e| format!("Failed to save thumbnail: {}", e))?;
+        Ok(true)
+    }
+
+    /// World IDs name the cached files, so anything that could leave the directory is rejected
+    fn path(world_id: &str) -> Option<PathBuf> {
+        let valid = !world_id.is_empty()
+            && world_id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+        valid.then(|| FileService::get_thumbnail_cache_dir().join(world_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_world_ids_name_cached_files() {
+        assert!(ThumbnailCache::path("wrld_12345678-1234-1234-1234-123456789abc").is_some());
+        assert!(ThumbnailCache::path("").is_none());
+        assert!(ThumbnailCache::path("../preferences").is_none());
+        assert!(ThumbnailCache::path("wrld_1/../../x").is_none());
+    }
+}
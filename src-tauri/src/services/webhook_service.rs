@@ -0,0 +1,218 @@
+use std::time::Duration;
+
+use serde_json::{json, Value};
+
+use crate::definitions::{Notification, NotificationKind, WebhookFormat, WebhookSettings};
+use crate::errors::{AppError, EntityError, NetworkError};
+use crate::i18n;
+
+use super::FileService;
+
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Embed colour of Discord messages, the app's accent colour
+const DISCORD_COLOR: u32 = 0xa855f7;
+
+/// Service for posting notifications to a webhook, e.g. a Discord channel
+#[derive(Debug)]
+pub struct WebhookService;
+
+impl WebhookService {
+    /// Posts new notifications to the configured webhook in the background
+    /// Does nothing if no webhook is enabled, or none of the notifications pass its filter
+    ///
+    /// # Arguments
+    /// * `notifications` - The notifications which were just added
+    pub fn dispatch(notifications: &[Notification]) {
+        let Some(settings) = FileService::read_custom_data()
+            .preferences
+            .webhook
+            .filter(|w| w.enabled)
+        else {
+            return;
+        };
+        let selected: Vec<Notification> = notifications
+            .iter()
+            .filter(|n| {
+                settings.events.is_empty() || settings.events.contains(&n.kind.notification_type())
+            })
+            .cloned()
+            .collect();
+        if selected.is_empty() {
+            return;
+        }
+
+        tauri::async_runtime::spawn(async move {
+            for notification in selected {
+                if let Err(e) = Self::send(&settings, &notification).await {
+                    log::warn!("Failed to post notification to webhook: {}", e);
+                }
+            }
+        });
+    }
+
+    /// Posts one notification to a webhook
+    ///
+    /// # Arguments
+    /// * `settings` - The webhook to post to
+    /// * `notification` - The notification to post
+    ///
+    /// # Errors
+    /// Returns an error if the URL is not http(s), or the webhook could not be reached
+    /// or answered with an error status
+    pub async fn send(
+        settings: &WebhookSettings,
+        notification: &Notification,
+    ) -> Result<(), AppError> {
+        Self::validate_url(&settings.url)?;
+        let payload = match settings.format {
+            WebhookFormat::Json => serde_json::to_value(notification)
+                .map_err(|_| EntityError::InvalidOperation("Invalid notification".to_string()))?,
+            WebhookFormat::Discord => Self::discord_payload(notification),
+        };
+
+        let response = reqwest::Client::builder()
+            .timeout(WEBHOOK_TIMEOUT)
+            .build()
+            .map_err(|_| NetworkError::ConnectionFailed)?
+            .post(&settings.url)
+            .json(&payload)
+            .send()
+            .await
+            .map_err(|e| {
+                if e.is_timeout() {
+                    NetworkError::Timeout
+                } else {
+                    NetworkError::ConnectionFailed
+                }
+            })?;
+        if !response.status().is_success() {
+            return Err(NetworkError::HttpError(response.status().as_u16()).into());
+        }
+        Ok(())
+    }
+
+    /// Checks that a webhook URL is an http(s) URL
+    ///
+    /// # Errors
+    /// Returns an error if the URL has another scheme
+    pub fn validate_url(url: &str) -> Result<(), AppError> {
+        if url.starts_with("https://") || url.starts_with("http://") {
+            Ok(())
+        } else {
+            Err(EntityError::InvalidOperation(format!(
+                "Webhook URL must start with https:// or http://: {}",
+                url
+            ))
+            .into())
+        }
+    }
+
+    fn discord_payload(notification: &Notification) -> Value {
+        let (title, description, world_id) = Self::describe(&notification.kind);
+        let mut embed = json!({
+            "title": title,
+            "description": description,
+            "color": DISCORD_COLOR,
+            "timestamp": notification.created_at.to_rfc3339(),
+        });
+        if let Some(world_id) = world_id {
+            embed["url"] = json!(format!("https://vrchat.com/home/world/{}", world_id));
+        }
+        json!({ "username": "VRC Worlds Manager", "embeds": [embed] })
+    }
+
+    /// A title and description for a notification, in the language of the preferences,
+    /// and the world it is about
+    fn describe(kind: &NotificationKind) -> (String, String, Option<&str>) {
+        let (key, params, world_id): (&str, Vec<String>, Option<&str>) = match kind {
+            NotificationKind::WorldUpdated {
+                world_id,
+                world_name,
+                new_update,
+                ..
+            } => (
+                "world-updated",
+                vec![
+                    world_name.clone(),
+                    new_update.format("%Y-%m-%d").to_string(),
+                ],
+                Some(world_id),
+            ),
+            NotificationKind::WorldUnavailable {
+                world_id,
+                world_name,
+                reason,
+            } => (
+                "world-unavailable",
+                vec![world_name.clone(), reason.clone()],
+                Some(world_id),
+            ),
+            NotificationKind::ShareExpiringSoon {
+                folder_name,
+                expiry_time,
+                ..
+            } => (
+                "share-expiring",
+                vec![
+                    folder_name.clone(),
+                    expiry_time.format("%Y-%m-%d").to_string(),
+                ],
+                None,
+            ),
+            NotificationKind::NewWorldFromAuthor {
+                world_id,
+                world_name,
+                author_name,
+            } => (
+                "new-world",
+                vec![author_name.clone(), world_name.clone()],
+                Some(world_id),
+            ),
+            NotificationKind::BackupCompleted { path } => {
+                ("backup-completed", vec![path.clone()], None)
+            }
+            // The error is shown as-is, it has no translation
+            NotificationKind::BackupFailed { error } => {
+                return (
+                    i18n::text("backend-notification:backup-failed:title", vec![]),
+                    error.clone(),
+                    None,
+                );
+            }
+            NotificationKind::UpdateAvailable { version } => {
+                ("update-available", vec![version.clone()], None)
+            }
+        };
+        (
+            i18n::text(&format!("backend-notification:{}:title", key), vec![]),
+            i18n::text(&format!("backend-notification:{}:body", key), params),
+            world_id,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_discord_payload_links_world() {
+        let notification = Notification::new(NotificationKind::NewWorldFromAuthor {
+            world_id: "wrld_1".to_string(),
+            world_name: "Test World".to_string(),
+            author_name: "Test Author".to_string(),
+        });
+
+        let payload = WebhookService::discord_payload(&notification);
+        let embed = &payload["embeds"][0];
+        assert_eq!(embed["description"], "Test Author published Test World");
+        assert_eq!(embed["url"], "https://vrchat.com/home/world/wrld_1");
+    }
+
+    #[test]
+    fn test_validate_url() {
+        assert!(WebhookService::validate_url("https://discord.com/api/webhooks/1/x").is_ok());
+        assert!(WebhookService::validate_url("file:///etc/passwd").is_err());
+    }
+}
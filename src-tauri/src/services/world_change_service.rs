@@ -0,0 +1,162 @@
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock, PoisonError, RwLock};
+
+use chrono::{DateTime, Utc};
+use tauri::AppHandle;
+use tauri_specta::Event;
+
+use crate::definitions::{WorldChanges, WorldDisplayData, WorldModel, WorldUpdated};
+use crate::errors::{AppError, ConcurrencyError};
+
+/// Set on startup, no events are emitted without it, e.g. in headless mode
+static HANDLE: OnceLock<AppHandle> = OnceLock::new();
+
+/// When each world was last changed, since tracking started
+struct ChangeLog {
+    started: DateTime<Utc>,
+    changed: HashMap<String, DateTime<Utc>>,
+}
+
+static CHANGES: Mutex<Option<ChangeLog>> = Mutex::new(None);
+
+/// Service for telling the frontend which worlds changed, so it can update them in place
+/// instead of fetching whole folders again
+///
+/// Changes are tracked in memory from startup, which is enough as the frontend starts with
+/// the backend
+#[derive(Debug)]
+pub struct WorldChangeService;
+
+impl WorldChangeService {
+    /// Starts tracking changes, and emitting `WorldUpdated` events
+    pub fn init(handle: AppHandle) {
+        let _ = HANDLE.set(handle);
+        let mut changes = CHANGES.lock().unwrap_or_else(PoisonError::into_inner);
+        Self::tracked(&mut changes);
+    }
+
+    /// Records that the given worlds changed, and emits them in one `WorldUpdated` event
+    ///
+    /// # Arguments
+    /// * `worlds` - The changed worlds, as they are now
+    pub fn record(worlds: Vec<WorldDisplayData>) {
+        if worlds.is_empty() {
+            return;
+        }
+        let timestamp = Utc::now();
+        {
+            let mut changes = CHANGES.lock().unwrap_or_else(PoisonError::into_inner);
+            let changes = Self::tracked(&mut changes);
+            for world in &worlds {
+                changes.changed.insert(world.world_id.clone(), timestamp);
+            }
+        }
+
+        let Some(handle) = HANDLE.get() else {
+            return;
+        };
+        if let Err(e) = (WorldUpdated { worlds, timestamp }).emit(handle) {
+            log::error!("Failed to emit world updated event: {}", e);
+        }
+    }
+
+    /// Gets the worlds changed after `since`
+    ///
+    /// # Arguments
+    /// * `since` - The timestamp of the last event or call the caller has seen
+    /// * `worlds` - The list of worlds, as a RwLock
+    ///
+    /// # Returns
+    /// The visible worlds changed after `since`, and whether that is every change
+    ///
+    /// # Errors
+    /// Returns an error if the worlds lock is poisoned
+    pub fn changed_since(
+        since: DateTime<Utc>,
+        worlds: &RwLock<Vec<WorldModel>>,
+    ) -> Result<WorldChanges, AppError> {
+        let timestamp = Utc::now();
+        let (complete, changed_ids) = {
+            let mut changes = CHANGES.lock().unwrap_or_else(PoisonError::into_inner);
+            let changes = Self::tracked(&mut changes);
+            (
+                since >= changes.started,
+                Self::ids_changed_after(&changes.changed, since),
+            )
+        };
+
+        let worlds = worlds.read().map_err(|_| ConcurrencyError::PoisonedLock)?;
+        let worlds = worlds
+            .iter()
+            .filter(|w| !w.user_data.hidden && changed_ids.contains(&w.api_data.world_id))
+            .map(WorldModel::to_display_data)
+            .collect();
+        Ok(WorldChanges {
+            worlds,
+            timestamp,
+            complete,
+        })
+    }
+
+    fn tracked(changes: &mut Option<ChangeLog>) -> &mut ChangeLog {
+        changes.get_or_insert_with(|| ChangeLog {
+            started: Utc::now(),
+            changed: HashMap::new(),
+        })
+    }
+
+    fn ids_changed_after(
+        changed: &HashMap<String, DateTime<Utc>>,
+        since: DateTime<Utc>,
+    ) -> Vec<String> {
+        changed
+            .iter()
+            .filter(|(_, changed_at)| **changed_at > since)
+            .map(|(world_id, _)| world_id.clone())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::sorting_service::make_test_world;
+    use chrono::Duration;
+
+    #[test]
+    fn test_ids_changed_after() {
+        let now = Utc::now();
+        let changed = HashMap::from([
+            ("wrld_old".to_string(), now - Duration::minutes(5)),
+            ("wrld_new".to_string(), now),
+        ]);
+
+        let ids = WorldChangeService::ids_changed_after(&changed, now - Duration::minutes(1));
+        assert_eq!(ids, vec!["wrld_new".to_string()]);
+        assert!(WorldChangeService::ids_changed_after(&changed, now).is_empty());
+    }
+
+    #[test]
+    fn test_changed_since() {
+        let visible = make_test_world("wrld_changed_visible");
+        let mut hidden = make_test_world("wrld_changed_hidden");
+        hidden.user_data.hidden = true;
+        let before = Utc::now();
+        WorldChangeService::record(vec![visible.to_display_data(), hidden.to_display_data()]);
+        let worlds = RwLock::new(vec![visible, hidden, make_test_world("wrld_not_changed")]);
+
+        // Hidden worlds are left out, as they are not shown
+        let changes = WorldChangeService::changed_since(before, &worlds).unwrap();
+        let ids: Vec<&str> = changes.worlds.iter().map(|w| w.world_id.as_str()).collect();
+        assert_eq!(ids, vec!["wrld_changed_visible"]);
+
+        // Changes from before tracking started are unknown
+        let changes =
+            WorldChangeService::changed_since(before - Duration::days(1), &worlds).unwrap();
+        assert!(!changes.complete);
+
+        let changes = WorldChangeService::changed_since(changes.timestamp, &worlds).unwrap();
+        assert!(changes.worlds.is_empty());
+        assert!(changes.complete);
+    }
+}
@@ -0,0 +1,202 @@
+use std::collections::HashSet;
+use std::sync::RwLock;
+
+use crate::definitions::{
+    FolderModel, Platform, SearchPlatform, WorldDisplayData, WorldFilter, WorldModel,
+};
+use crate::errors::{AppError, ConcurrencyError};
+use crate::MEMO_MANAGER;
+
+use super::GroupingService;
+
+/// Service for evaluating the grid's filters in the backend, so an operation on every
+/// matching world does not need the frontend to send their IDs back
+#[derive(Debug)]
+pub struct WorldFilterService;
+
+impl WorldFilterService {
+    /// Gets the IDs of the worlds of the filter's source which match it
+    ///
+    /// # Arguments
+    /// * `filter` - The filter to evaluate
+    /// * `folders` - The list of folders, as a RwLock
+    /// * `worlds` - The list of worlds, as a RwLock
+    ///
+    /// # Returns
+    /// The matching world IDs, in the order of the source
+    ///
+    /// # Errors
+    /// Returns an error if the source folder is not found
+    /// Returns an error if any lock is poisoned
+    pub fn matching_world_ids(
+        filter: &WorldFilter,
+        folders: &RwLock<Vec<FolderModel>>,
+        worlds: &RwLock<Vec<WorldModel>>,
+    ) -> Result<Vec<String>, AppError> {
+        let source_worlds = GroupingService::get_source_worlds(&filter.source, folders, worlds)?;
+        let memo_ids = if filter.memo_text.trim().is_empty() {
+            None
+        } else {
+            let memos = MEMO_MANAGER
+                .get()
+                .read()
+                .map_err(|_| ConcurrencyError::PoisonedLock)?;
+            Some(
+                memos
+                    .search_memo_text(&filter.memo_text)
+                    .into_iter()
+                    .collect::<HashSet<String>>(),
+            )
+        };
+
+        Ok(source_worlds
+            .into_iter()
+            .filter(|world| Self::matches(filter, world, memo_ids.as_ref()))
+            .map(|world| world.world_id)
+            .collect())
+    }
+
+    /// Checks a world against the filter, the same way the frontend's filters do
+    /// Romaji readings of names are not matched by the search query
+    fn matches(
+        filter: &WorldFilter,
+        world: &WorldDisplayData,
+        memo_ids: Option<&HashSet<String>>,
+    ) -> bool {
+        let flags = [
+            (filter.photographed, world.is_photographed),
+            (filter.shared, world.is_shared),
+            (filter.favorite, world.is_favorite),
+        ];
+        if flags
+            .iter()
+            .any(|(wanted, value)| wanted.is_some_and(|wanted| wanted != *value))
+        {
+            return false;
+        }
+        if filter.unprocessed && (world.is_photographed || world.is_shared) {
+            return false;
+        }
+
+        let query = filter.search_query.trim().to_lowercase();
+        if !query.is_empty()
+            && !world.name.to_lowercase().contains(&query)
+            && !world.author_name.to_lowercase().contains(&query)
+        {
+            return false;
+        }
+
+        let author = filter.author.trim().to_lowercase();
+        if !author.is_empty() && world.author_name.to_lowercase() != author {
+            return false;
+        }
+
+        if !filter.tags.iter().all(|tag| {
+            let tag = format!("author_tag_{}", tag).to_lowercase();
+            world.tags.iter().any(|t| t.to_lowercase() == tag)
+        }) {
+            return false;
+        }
+
+        if !filter
+            .folders
+            .iter()
+            .map(|folder| folder.to_lowercase())
+            .all(|folder| world.folders.iter().any(|f| f.to_lowercase() == folder))
+        {
+            return false;
+        }
+
+        if memo_ids.is_some_and(|ids| !ids.contains(&world.world_id)) {
+            return false;
+        }
+
+        match filter.platform {
+            None => true,
+            Some(SearchPlatform::Pc) => !matches!(world.platform, Platform::Quest),
+            Some(SearchPlatform::Android) => !matches!(world.platform, Platform::PC),
+            Some(SearchPlatform::Both) => matches!(world.platform, Platform::CrossPlatform),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::definitions::WorldAvailability;
+
+    fn world(name: &str, platform: Platform, tags: &[&str]) -> WorldDisplayData {
+        WorldDisplayData {
+            world_id: format!("wrld_{}", name),
+            name: name.to_string(),
+            thumbnail_url: String::new(),
+            author_name: "Author".to_string(),
+            author_id: "usr_author".to_string(),
+            favorites: 0,
+            last_updated: String::new(),
+            visits: 0,
+            date_added: String::new(),
+            platform,
+            folders: vec!["Horror".to_string()],
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            capacity: 0,
+            is_photographed: false,
+            is_shared: false,
+            is_favorite: false,
+            is_event_critical: false,
+            has_memo: false,
+            rating: None,
+            local_visit_count: 0,
+            pc_download_size: None,
+            quest_download_size: None,
+            availability: WorldAvailability::Public,
+        }
+    }
+
+    #[test]
+    fn test_matches_platform_and_tags() {
+        let filter = WorldFilter {
+            tags: vec!["Horror".to_string()],
+            platform: Some(SearchPlatform::Android),
+            ..WorldFilter::default()
+        };
+        let quest = world("quest", Platform::CrossPlatform, &["author_tag_horror"]);
+        let pc = world("pc", Platform::PC, &["author_tag_horror"]);
+        let untagged = world("untagged", Platform::Quest, &[]);
+
+        assert!(WorldFilterService::matches(&filter, &quest, None));
+        assert!(!WorldFilterService::matches(&filter, &pc, None));
+        assert!(!WorldFilterService::matches(&filter, &untagged, None));
+    }
+
+    #[test]
+    fn test_matches_flags_text_and_memo() {
+        let mut photographed = world("Night Street", Platform::PC, &[]);
+        photographed.is_photographed = true;
+
+        let filter = WorldFilter {
+            search_query: "night".to_string(),
+            photographed: Some(true),
+            folders: vec!["horror".to_string()],
+            ..WorldFilter::default()
+        };
+        assert!(WorldFilterService::matches(&filter, &photographed, None));
+
+        let unprocessed = WorldFilter {
+            unprocessed: true,
+            ..WorldFilter::default()
+        };
+        assert!(!WorldFilterService::matches(
+            &unprocessed,
+            &photographed,
+            None
+        ));
+
+        let memo_ids = HashSet::from(["wrld_other".to_string()]);
+        assert!(!WorldFilterService::matches(
+            &WorldFilter::default(),
+            &photographed,
+            Some(&memo_ids)
+        ));
+    }
+}
@@ -0,0 +1,250 @@
+use std::collections::HashMap;
+use std::fs;
+use std::sync::{Mutex, PoisonError};
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::definitions::{WorldApiData, WorldDisplayData, WorldTrendPoint, WorldTrends};
+
+use super::{FileService, Persistence};
+
+/// A world is snapshotted at most this often, however often it is fetched
+const SNAPSHOT_INTERVAL_DAYS: i64 = 7;
+
+/// Snapshots kept per world, about five years of weekly ones
+const MAX_SNAPSHOTS: usize = 260;
+
+/// Taken at (seconds since the epoch), favorites, visits and capacity
+/// Stored as an array to keep the file small, as every world has a snapshot a week
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+struct Snapshot(i64, i32, Option<i32>, i32);
+
+impl Snapshot {
+    fn of(world: &WorldApiData, now: DateTime<Utc>) -> Self {
+        Self(
+            now.timestamp(),
+            world.favorites,
+            world.visits,
+            world.capacity,
+        )
+    }
+
+    fn of_display(world: &WorldDisplayData, now: DateTime<Utc>) -> Self {
+        Self(
+            now.timestamp(),
+            world.favorites,
+            Some(world.visits),
+            world.capacity,
+        )
+    }
+
+    fn to_point(self) -> Option<WorldTrendPoint> {
+        Some(WorldTrendPoint {
+            taken_at: DateTime::from_timestamp(self.0, 0)?,
+            favorites: self.1,
+            visits: self.2,
+            capacity: self.3,
+        })
+    }
+}
+
+type History = HashMap<String, Vec<Snapshot>>;
+
+/// Loaded on first use
+static STORE: Mutex<Option<History>> = Mutex::new(None);
+
+/// Service for the history of worlds' favorites, visits and capacity, kept in
+/// world_history.json
+///
+/// A snapshot is taken when a world's data is fetched, if the last one is at least a week old,
+/// so worlds which are refreshed regularly build up a weekly time series
+#[derive(Debug)]
+pub struct WorldHistoryService;
+
+impl WorldHistoryService {
+    /// Snapshots the worlds which are due one
+    /// Placeholders of worlds the API would not show are skipped, as they have no data
+    ///
+    /// # Arguments
+    /// * `worlds` - Worlds just fetched from the API
+    pub fn record(worlds: &[WorldApiData]) {
+        let now = Utc::now();
+        Self::record_snapshots(
+            worlds
+                .iter()
+                .filter(|w| w.availability.is_public())
+                .map(|w| (w.world_id.as_str(), Snapshot::of(w, now))),
+        );
+    }
+
+    /// Snapshots the worlds which are due one, for worlds fetched as display data, e.g. the
+    /// user's own worlds
+    ///
+    /// # Arguments
+    /// * `worlds` - Worlds just fetched from the API
+    pub fn record_display(worlds: &[WorldDisplayData]) {
+        let now = Utc::now();
+        Self::record_snapshots(
+            worlds
+                .iter()
+                .map(|w| (w.world_id.as_str(), Snapshot::of_display(w, now))),
+        );
+    }
+
+    fn record_snapshots<'a>(snapshots: impl Iterator<Item = (&'a str, Snapshot)>) {
+        let mut store = STORE.lock().unwrap_or_else(PoisonError::into_inner);
+        let history = Self::loaded(&mut store);
+
+        let mut changed = false;
+        for (world_id, snapshot) in snapshots {
+            changed |= Self::record_into(history, world_id, snapshot);
+        }
+        if changed {
+            Self::save(history);
+        }
+    }
+
+    /// Gets the snapshots of a world
+    ///
+    /// # Arguments
+    /// * `world_id` - The ID of the world
+    ///
+    /// # Returns
+    /// The world's snapshots, oldest first, empty if none were taken
+    pub fn trends(world_id: &str) -> WorldTrends {
+        let mut store = STORE.lock().unwrap_or_else(PoisonError::into_inner);
+        let points = Self::loaded(&mut store)
+            .get(world_id)
+            .map(|snapshots| snapshots.iter().filter_map(|s| s.to_point()).collect())
+            .unwrap_or_default();
+        WorldTrends {
+            world_id: world_id.to_string(),
+            points,
+        }
+    }
+
+    /// Adds a snapshot of the world if its last one is older than the interval
+    ///
+    /// # Returns
+    /// Whether a snapshot was added
+    fn record_into(history: &mut History, world_id: &str, snapshot: Snapshot) -> bool {
+        let snapshots = history.entry(world_id.to_string()).or_default();
+        if let Some(last) = snapshots.last() {
+            if snapshot.0 - last.0 < Duration::days(SNAPSHOT_INTERVAL_DAYS).num_seconds() {
+                return false;
+            }
+        }
+
+        snapshots.push(snapshot);
+        if snapshots.len() > MAX_SNAPSHOTS {
+            let excess = snapshots.len() - MAX_SNAPSHOTS;
+            snapshots.drain(..excess);
+        }
+        true
+    }
+
+    fn loaded(store: &mut Option<History>) -> &mut History {
+        store.get_or_insert_with(Self::read)
+    }
+
+    fn read() -> History {
+        let path = FileService::get_world_history_path();
+        if !path.exists() {
+            return History::new();
+        }
+        fs::read_to_string(&path)
+            .ok()
+            .and_then(|data| serde_json::from_str(&data).ok())
+            .unwrap_or_else(|| {
+                log::warn!("Failed to read world_history.json, starting over");
+                History::new()
+            })
+    }
+
+    fn save(history: &History) {
+        match serde_json::to_string(history) {
+            // A missed snapshot is taken the next time the world is fetched
+            Ok(data) => Persistence::write_deferred(FileService::get_world_history_path(), data),
+            Err(e) => log::error!("Failed to serialize world history: {}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::definitions::WorldAvailability;
+
+    fn make_world(favorites: i32) -> WorldApiData {
+        WorldApiData {
+            image_url: "".to_string(),
+            world_name: "World".to_string(),
+            world_id: "wrld_1".to_string(),
+            author_name: "Author".to_string(),
+            author_id: "usr_1".to_string(),
+            capacity: 16,
+            recommended_capacity: None,
+            tags: vec![],
+            publication_date: None,
+            last_update: Utc::now(),
+            description: "".to_string(),
+            visits: Some(favorites * 10),
+            favorites,
+            platform: vec![],
+            packages: vec![],
+            availability: WorldAvailability::Public,
+        }
+    }
+
+    #[test]
+    fn test_snapshots_are_taken_weekly() {
+        let mut history = History::new();
+        let start = Utc::now();
+
+        let snapshot =
+            |favorites, days| Snapshot::of(&make_world(favorites), start + Duration::days(days));
+
+        assert!(WorldHistoryService::record_into(
+            &mut history,
+            "wrld_1",
+            snapshot(10, 0)
+        ));
+        assert!(!WorldHistoryService::record_into(
+            &mut history,
+            "wrld_1",
+            snapshot(12, 3)
+        ));
+        assert!(WorldHistoryService::record_into(
+            &mut history,
+            "wrld_1",
+            snapshot(15, 7)
+        ));
+
+        let favorites: Vec<i32> = history["wrld_1"].iter().map(|s| s.1).collect();
+        assert_eq!(favorites, vec![10, 15]);
+    }
+
+    #[test]
+    fn test_oldest_snapshots_are_dropped() {
+        let mut history = History::new();
+        let start = Utc::now();
+        for week in 0..MAX_SNAPSHOTS as i32 + 2 {
+            let taken_at = start + Duration::days(7 * week as i64);
+            let snapshot = Snapshot::of(&make_world(week), taken_at);
+            WorldHistoryService::record_into(&mut history, "wrld_1", snapshot);
+        }
+
+        let snapshots = &history["wrld_1"];
+        assert_eq!(snapshots.len(), MAX_SNAPSHOTS);
+        assert_eq!(snapshots[0].1, 2);
+    }
+
+    #[test]
+    fn test_snapshots_are_stored_as_arrays() {
+        let snapshot = Snapshot(1_700_000_000, 5, None, 32);
+        let json = serde_json::to_string(&snapshot).unwrap();
+        assert_eq!(json, "[1700000000,5,null,32]");
+        assert_eq!(serde_json::from_str::<Snapshot>(&json).unwrap(), snapshot);
+    }
+}
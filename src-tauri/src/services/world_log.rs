@@ -0,0 +1,543 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::hash::Hasher;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use crate::definitions::{WorldModel, WorldsStorageFormat};
+use crate::errors::FileError;
+
+use super::{FileService, LibraryEncryption, Persistence, WorldStorage};
+
+/// Entries appended before worlds.json is rewritten in full and the log cleared
+const MAX_LOG_ENTRIES: usize = 500;
+
+/// A save changing more than a quarter of the worlds rewrites worlds.json right away,
+/// as the snapshot is smaller than the entries would be
+const MAX_CHANGED_SHARE: usize = 4;
+
+/// One line of worlds.log, after its checksum
+/// Read with worlds as JSON when worlds.json is migrated to a new schema
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "camelCase")]
+enum LogEntry<W = WorldModel> {
+    /// The first line, checksum of worlds.json as the following entries apply to it
+    Base {
+        checksum: String,
+    },
+    Upsert {
+        world: W,
+    },
+    Remove {
+        #[serde(rename = "worldId")]
+        world_id: String,
+    },
+}
+
+/// Worlds the log can be applied to
+trait LoggedWorld: DeserializeOwned + Clone {
+    fn world_id(&self) -> Option<&str>;
+}
+
+impl LoggedWorld for WorldModel {
+    fn world_id(&self) -> Option<&str> {
+        Some(&self.api_data.world_id)
+    }
+}
+
+impl LoggedWorld for Value {
+    fn world_id(&self) -> Option<&str> {
+        self.get("id").and_then(Value::as_str)
+    }
+}
+
+struct LogState {
+    /// The worlds.json the hashes belong to, as the data directory can be moved
+    path: PathBuf,
+    /// Hash of every world as persisted, None until the worlds were loaded or written in full
+    persisted: Option<HashMap<String, u64>>,
+    /// Checksum of worlds.json as on disk, for the header of a new log
+    /// None until worlds.json was read or written, so appends then rewrite it instead
+    base_checksum: Option<String>,
+    /// Entries in worlds.log, 0 if there is none
+    entries: usize,
+}
+
+static STATE: Mutex<LogState> = Mutex::new(LogState {
+    path: PathBuf::new(),
+    persisted: None,
+    base_checksum: None,
+    entries: 0,
+});
+
+/// Incremental persistence of worlds.json
+///
+/// Saving the worlds only appends the worlds that changed since the last save to
+/// worlds.log, so toggling a flag no longer rewrites the whole library. worlds.json stays
+/// the complete snapshot: the log is applied on top of it when loading, and folded into it
+/// once it grows past `MAX_LOG_ENTRIES`, on startup, and before anything reads worlds.json
+/// directly (exports, sync, encryption changes, schema migrations)
+///
+/// Every line carries a SHA-256 checksum, and the log starts with the checksum of the
+/// worlds.json it belongs to. A line cut short by a crash ends the log there, and a log
+/// left over from another worlds.json is discarded
+#[derive(Debug)]
+pub struct WorldLog;
+
+/// Feeds written bytes into a hasher
+struct HashWriter(DefaultHasher);
+
+impl Write for HashWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.write(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl WorldLog {
+    /// Persists the worlds, appending the changed ones to the log when possible
+    /// Called by `FileService::write_worlds`
+    ///
+    /// # Arguments
+    /// * `worlds` - Every world
+    /// * `format` - The format to write worlds.json in, if it is rewritten
+    ///
+    /// # Errors
+    /// Returns a FileError if the worlds could not be written
+    pub fn save(worlds: &[WorldModel], format: WorldsStorageFormat) -> Result<(), FileError> {
+        let mut state = Self::state();
+        let (_, _, worlds_path, _) = FileService::get_paths();
+        if state.path != worlds_path {
+            *state = LogState {
+                path: worlds_path.clone(),
+                persisted: None,
+                base_checksum: None,
+                entries: 0,
+            };
+        }
+
+        let mut hashes = HashMap::with_capacity(worlds.len());
+        let mut entries = Vec::new();
+        for world in worlds {
+            let hash = Self::hash(world)?;
+            let id = &world.api_data.world_id;
+            if state.persisted.as_ref().and_then(|p| p.get(id)) != Some(&hash) {
+                entries.push(LogEntry::Upsert {
+                    world: world.clone(),
+                });
+            }
+            hashes.insert(id.clone(), hash);
+        }
+        if let Some(persisted) = &state.persisted {
+            entries.extend(
+                persisted
+                    .keys()
+                    .filter(|id| !hashes.contains_key(*id))
+                    .map(|id| LogEntry::Remove {
+                        world_id: id.clone(),
+                    }),
+            );
+        }
+
+        if entries.is_empty() && state.persisted.is_some() {
+            return Ok(());
+        }
+
+        let base_checksum = state
+            .base_checksum
+            .as_deref()
+            .filter(|_| state.entries == 0);
+        let incremental = state.persisted.is_some()
+            && (state.entries > 0 || base_checksum.is_some())
+            && state.entries + entries.len() <= MAX_LOG_ENTRIES
+            && entries.len() * MAX_CHANGED_SHARE <= worlds.len();
+        if incremental {
+            match Self::append(&worlds_path, base_checksum, &entries) {
+                Ok(()) => {
+                    state.persisted = Some(hashes);
+                    state.entries += entries.len();
+                    return Ok(());
+                }
+                Err(e) => log::warn!(
+                    "Failed to append to worlds.log, rewriting worlds.json: {}",
+                    e
+                ),
+            }
+        }
+
+        state.base_checksum = Some(Self::write_snapshot(&worlds_path, worlds, format)?);
+        state.persisted = Some(hashes);
+        state.entries = 0;
+        Ok(())
+    }
+
+    /// Applies worlds.log on top of the worlds read from worlds.json
+    /// Called by `FileService::load_worlds`, which then calls `loaded`
+    ///
+    /// # Arguments
+    /// * `worlds` - The worlds read from worlds.json
+    pub fn replay(worlds: &mut Vec<WorldModel>) {
+        let mut state = Self::state();
+        let (_, _, worlds_path, _) = FileService::get_paths();
+        let base_checksum = Self::snapshot_checksum(&worlds_path);
+        let (applied, damaged) = Self::replay_into(&worlds_path, &base_checksum, worlds);
+        if applied > 0 {
+            log::info!("Applied {} entries of worlds.log", applied);
+        }
+        // Entries appended after a damaged line would never be read, so the next save
+        // rewrites worlds.json instead
+        state.entries = if damaged { MAX_LOG_ENTRIES } else { applied };
+        state.base_checksum = Some(base_checksum);
+    }
+
+    /// Applies worlds.log to worlds.json parsed as JSON, before it is migrated to a new schema
+    /// Migrating rewrites worlds.json, which discards the log, so its entries are folded in
+    ///
+    /// # Arguments
+    /// * `worlds` - The contents of worlds.json
+    pub fn replay_json(worlds: &mut Value) {
+        let Some(worlds) = worlds.as_array_mut() else {
+            return;
+        };
+        let (_, _, worlds_path, _) = FileService::get_paths();
+        let base_checksum = Self::snapshot_checksum(&worlds_path);
+        let (applied, _) = Self::replay_into(&worlds_path, &base_checksum, worlds);
+        if applied > 0 {
+            log::info!("Applied {} entries of worlds.log before migrating", applied);
+        }
+    }
+
+    /// Records the worlds as persisted, once loading them has finished
+    ///
+    /// # Arguments
+    /// * `worlds` - The worlds, as they are kept in memory
+    pub fn loaded(worlds: &[WorldModel]) {
+        let hashes = worlds
+            .iter()
+            .filter_map(|world| Some((world.api_data.world_id.clone(), Self::hash(world).ok()?)))
+            .collect();
+
+        let mut state = Self::state();
+        state.path = FileService::get_paths().2;
+        state.persisted = Some(hashes);
+    }
+
+    /// Folds worlds.log into worlds.json and removes it
+    ///
+    /// # Errors
+    /// Returns `FileError::LibraryLoading` while the worlds are still being loaded
+    /// Returns a FileError if worlds.json could not be read or written
+    pub fn compact() -> Result<(), FileError> {
+        if FileService::is_loading_worlds() {
+            return Err(FileError::LibraryLoading);
+        }
+        let mut state = Self::state();
+        let (_, _, worlds_path, _) = FileService::get_paths();
+        if !Self::log_path(&worlds_path).exists() {
+            return Ok(());
+        }
+
+        let mut worlds = FileService::read_worlds_file()?;
+        let base_checksum = Self::snapshot_checksum(&worlds_path);
+        let (applied, _) = Self::replay_into(&worlds_path, &base_checksum, &mut worlds);
+        // Kept in the format it is stored in, the setting only applies from the next save
+        let format = WorldStorage::stored_format(&worlds_path);
+        let checksum = Self::write_snapshot(&worlds_path, &worlds, format)?;
+        // The effective data is unchanged, so the hashes of the persisted worlds still hold
+        state.base_checksum = Some(checksum);
+        state.entries = 0;
+        log::info!(
+            "Compacted {} entries of worlds.log into worlds.json",
+            applied
+        );
+        Ok(())
+    }
+
+    /// Forgets the log, for when worlds.json is replaced as a whole by something else
+    pub fn discard() {
+        let mut state = Self::state();
+        let (_, _, worlds_path, _) = FileService::get_paths();
+        Self::remove_log(&worlds_path);
+        state.persisted = None;
+        state.base_checksum = None;
+        state.entries = 0;
+    }
+
+    /// Forgets which worlds are persisted, after worlds.json or the log were rolled back
+    /// underneath it, so the next save rewrites worlds.json in full
+    pub fn invalidate() {
+        let mut state = Self::state();
+        state.persisted = None;
+        state.base_checksum = None;
+    }
+
+    /// The path of the log next to worlds.json
+    pub fn log_path(worlds_path: &Path) -> PathBuf {
+        worlds_path.with_extension("log")
+    }
+
+    fn state() -> MutexGuard<'static, LogState> {
+        STATE.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Appends entries, or starts a new log headed by `base_checksum`, the checksum of
+    /// worlds.json, if given
+    fn append(
+        worlds_path: &Path,
+        base_checksum: Option<&str>,
+        entries: &[LogEntry],
+    ) -> Result<(), FileError> {
+        let mut lines = String::new();
+        if let Some(checksum) = base_checksum {
+            lines.push_str(&Self::line(
+                worlds_path,
+                &LogEntry::Base {
+                    checksum: checksum.to_string(),
+                },
+            )?);
+        }
+        for entry in entries {
+            lines.push_str(&Self::line(worlds_path, entry)?);
+        }
+
+        Persistence::write_direct(&Self::log_path(worlds_path), |log_path| {
+            let mut file = OpenOptions::new()
+                .create(true)
+                .write(true)
+                .append(base_checksum.is_none())
+                .truncate(base_checksum.is_some())
+                .open(log_path)?;
+            file.write_all(lines.as_bytes())?;
+            file.sync_data()
+        })
+        .map_err(|_| FileError::FileWriteError)
+    }
+
+    /// A line of the log: the checksum, a space, and the entry encrypted like worlds.json
+    fn line(worlds_path: &Path, entry: &LogEntry) -> Result<String, FileError> {
+        let json = serde_json::to_string(entry).map_err(|_| FileError::InvalidFile)?;
+        let payload = LibraryEncryption::seal(worlds_path, &json)?;
+        Ok(format!(
+            "{} {}\n",
+            Self::checksum(payload.as_bytes()),
+            payload
+        ))
+    }
+
+    /// Applies the entries of the log to `worlds`, if it belongs to the worlds.json with
+    /// `base_checksum`
+    ///
+    /// # Returns
+    /// How many entries were applied, and whether the log ended with a damaged line
+    fn replay_into<W: LoggedWorld>(
+        worlds_path: &Path,
+        base_checksum: &str,
+        worlds: &mut Vec<W>,
+    ) -> (usize, bool) {
+        let Ok(content) = fs::read_to_string(Self::log_path(worlds_path)) else {
+            return (0, false);
+        };
+        let (entries, damaged) = Self::parse::<W>(&content);
+        let Some((LogEntry::Base { checksum }, entries)) = entries.split_first() else {
+            if !content.is_empty() {
+                log::warn!("worlds.log has no valid header, discarding it");
+            }
+            return (0, false);
+        };
+        if checksum != base_checksum {
+            log::warn!("worlds.log belongs to another worlds.json, discarding it");
+            return (0, false);
+        }
+
+        Self::apply(worlds, entries);
+        (entries.len(), damaged)
+    }
+
+    /// Parses the lines of the log, up to the first one failing its checksum
+    /// Returns the entries, and whether a line failed
+    fn parse<W: LoggedWorld>(content: &str) -> (Vec<LogEntry<W>>, bool) {
+        let mut entries = Vec::new();
+        for line in content.lines() {
+            let entry = line
+                .split_once(' ')
+                .filter(|(checksum, payload)| *checksum == Self::checksum(payload.as_bytes()))
+                .and_then(|(_, payload)| LibraryEncryption::open(payload).ok())
+                .and_then(|json| serde_json::from_str(&json).ok());
+            match entry {
+                Some(entry) => entries.push(entry),
+                None => {
+                    // Lines are only appended, so a bad line was cut short by a crash
+                    log::warn!("Ignoring worlds.log from line {}", entries.len() + 1);
+                    return (entries, true);
+                }
+            }
+        }
+        (entries, false)
+    }
+
+    fn apply<W: LoggedWorld>(worlds: &mut Vec<W>, entries: &[LogEntry<W>]) {
+        for entry in entries {
+            match entry {
+                LogEntry::Base { .. } => {}
+                LogEntry::Upsert { world } => {
+                    match worlds
+                        .iter_mut()
+                        .find(|w| w.world_id().is_some() && w.world_id() == world.world_id())
+                    {
+                        Some(existing) => *existing = world.clone(),
+                        None => worlds.push(world.clone()),
+                    }
+                }
+                LogEntry::Remove { world_id } => {
+                    worlds.retain(|w| w.world_id() != Some(world_id.as_str()));
+                }
+            }
+        }
+    }
+
+    /// Rewrites worlds.json in full and removes the log, which it now includes
+    /// Returns the checksum of the new worlds.json
+    fn write_snapshot(
+        worlds_path: &PathBuf,
+        worlds: &[WorldModel],
+        format: WorldsStorageFormat,
+    ) -> Result<String, FileError> {
+        let data = WorldStorage::encode(worlds, format)?;
+        let data = LibraryEncryption::seal_bytes(worlds_path, &data)?;
+        WorldStorage::write(worlds_path, &data, format)?;
+        Self::remove_log(worlds_path);
+        Ok(Self::checksum(&data))
+    }
+
+    /// Checksum of worlds.json as it is on disk
+    fn snapshot_checksum(worlds_path: &Path) -> String {
+        Self::checksum(&fs::read(WorldStorage::stored_path(worlds_path)).unwrap_or_default())
+    }
+
+    fn remove_log(worlds_path: &Path) {
+        Persistence::write_direct(&Self::log_path(worlds_path), |log_path| {
+            if log_path.exists() {
+                if let Err(e) = fs::remove_file(log_path) {
+                    log::error!("Failed to remove {:?}: {}", log_path, e);
+                }
+            }
+        });
+    }
+
+    /// Hash of a world's JSON, streamed into the hasher rather than built as a string
+    fn hash(world: &WorldModel) -> Result<u64, FileError> {
+        let mut hasher = HashWriter(DefaultHasher::new());
+        serde_json::to_writer(&mut hasher, world).map_err(|_| FileError::InvalidFile)?;
+        Ok(hasher.0.finish())
+    }
+
+    fn checksum(data: &[u8]) -> String {
+        hex::encode(Sha256::digest(data))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn world(id: &str, name: &str) -> WorldModel {
+        let mut world = WorldModel::new(crate::definitions::WorldApiData::placeholder(
+            id.to_string(),
+        ));
+        world.api_data.world_name = name.to_string();
+        world
+    }
+
+    #[test]
+    fn test_replay_applies_entries_on_top_of_snapshot() {
+        let dir = tempfile::tempdir().unwrap();
+        let worlds_path = dir.path().join("worlds.json");
+        let snapshot = vec![world("wrld_a", "A"), world("wrld_b", "B")];
+        fs::write(&worlds_path, serde_json::to_string(&snapshot).unwrap()).unwrap();
+
+        WorldLog::append(
+            &worlds_path,
+            Some(&WorldLog::snapshot_checksum(&worlds_path)),
+            &[LogEntry::Upsert {
+                world: world("wrld_a", "A renamed"),
+            }],
+        )
+        .unwrap();
+        WorldLog::append(
+            &worlds_path,
+            None,
+            &[
+                LogEntry::Remove {
+                    world_id: "wrld_b".to_string(),
+                },
+                LogEntry::Upsert {
+                    world: world("wrld_c", "C"),
+                },
+            ],
+        )
+        .unwrap();
+
+        let checksum = WorldLog::snapshot_checksum(&worlds_path);
+        let mut worlds = snapshot.clone();
+        assert_eq!(
+            WorldLog::replay_into(&worlds_path, &checksum, &mut worlds),
+            (3, false)
+        );
+        let names: Vec<_> = worlds
+            .iter()
+            .map(|w| w.api_data.world_name.as_str())
+            .collect();
+        assert_eq!(names, ["A renamed", "C"]);
+
+        // A log left over from another worlds.json is ignored
+        fs::write(&worlds_path, "[]").unwrap();
+        let checksum = WorldLog::snapshot_checksum(&worlds_path);
+        let mut worlds = snapshot;
+        assert_eq!(
+            WorldLog::replay_into(&worlds_path, &checksum, &mut worlds),
+            (0, false)
+        );
+        assert_eq!(worlds.len(), 2);
+    }
+
+    #[test]
+    fn test_parse_stops_at_torn_line() {
+        let dir = tempfile::tempdir().unwrap();
+        let worlds_path = dir.path().join("worlds.json");
+        fs::write(&worlds_path, "[]").unwrap();
+        WorldLog::append(
+            &worlds_path,
+            Some(&WorldLog::snapshot_checksum(&worlds_path)),
+            &[LogEntry::Upsert {
+                world: world("wrld_a", "A"),
+            }],
+        )
+        .unwrap();
+
+        let mut content = fs::read_to_string(WorldLog::log_path(&worlds_path)).unwrap();
+        let torn = WorldLog::line(
+            &worlds_path,
+            &LogEntry::Remove {
+                world_id: "wrld_a".to_string(),
+            },
+        )
+        .unwrap();
+        content.push_str(&torn[..torn.len() / 2]);
+
+        let (entries, damaged) = WorldLog::parse::<WorldModel>(&content);
+        assert!(damaged);
+        assert_eq!(entries.len(), 2);
+        assert!(matches!(entries[1], LogEntry::Upsert { .. }));
+    }
+}
@@ -0,0 +1,207 @@
+use reqwest::Url;
+use serde::Serialize;
+use specta::Type;
+
+/// A world, and optionally an instance of it, parsed from user input
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct WorldReference {
+    pub world_id: String,
+    /// The instance part of the location, e.g. "12345~region(jp)"
+    pub instance_id: Option<String>,
+}
+
+impl WorldReference {
+    /// Parses a world ID, an instance location, a vrchat.com world or launch URL,
+    /// or a vrchat:// launch URL
+    /// The world ID is lowercased, as VRChat IDs are
+    ///
+    /// # Errors
+    /// Returns an error message if the input is none of these, or has an invalid world ID
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let input = input.trim();
+        if input.is_empty() {
+            return Err("Nothing to parse".to_string());
+        }
+        if input.contains(char::is_whitespace) {
+            return Err(format!("Not a world ID or URL: {}", input));
+        }
+        if input.to_ascii_lowercase().starts_with("wrld_") {
+            return Self::parse_location(input);
+        }
+
+        let url = Url::parse(input).map_err(|_| format!("Not a world ID or URL: {}", input))?;
+        match url.scheme() {
+            "http" | "https" => Self::parse_web_url(&url),
+            "vrchat" if url.host_str() == Some("launch") => {
+                let location = Self::query_value(&url, "id")
+                    .ok_or_else(|| format!("The launch URL has no world: {}", input))?;
+                Self::parse_location(&location)
+            }
+            _ => Err(format!("Not a VRChat URL: {}", input)),
+        }
+    }
+
+    /// The vrchat.com page of the world, or the page for joining the instance
+    pub fn web_url(&self) -> String {
+        match &self.instance_id {
+            Some(instance_id) => format!(
+                "https://vrchat.com/home/launch?worldId={}&instanceId={}",
+                self.world_id,
+                urlencoding::encode(instance_id)
+            ),
+            None => format!("https://vrchat.com/home/world/{}", self.world_id),
+        }
+    }
+
+    /// The vrchat:// link which opens the world or instance in the client
+    /// The short name lets others join private instances they were not invited to
+    pub fn launch_url(&self, short_name: Option<&str>) -> String {
+        let mut location = self.world_id.clone();
+        if let Some(instance_id) = &self.instance_id {
+            location = format!("{}:{}", location, urlencoding::encode(instance_id));
+        }
+        let mut url = format!("vrchat://launch?ref=vrchat.com&id={}", location);
+        if let Some(short_name) = short_name {
+            url = format!("{}&shortName={}", url, urlencoding::encode(short_name));
+        }
+        url
+    }
+
+    /// Checks that an ID is a world ID, "wrld_" followed by a UUID
+    pub fn is_world_id(id: &str) -> bool {
+        id.strip_prefix("wrld_").is_some_and(|uuid| {
+            uuid.len() == 36
+                && uuid.char_indices().all(|(i, c)| match i {
+                    8 | 13 | 18 | 23 => c == '-',
+                    _ => c.is_ascii_hexdigit(),
+                })
+        })
+    }
+
+    fn parse_web_url(url: &Url) -> Result<Self, String> {
+        let host = url.host_str().unwrap_or_default();
+        if host != "vrchat.com" && host != "www.vrchat.com" {
+            return Err(format!("Not a VRChat URL: {}", url));
+        }
+
+        let segments: Vec<&str> = url
+            .path_segments()
+            .map(Iterator::collect)
+            .unwrap_or_default();
+        match segments.as_slice() {
+            ["home", "world", world_id, ..] => Self::parse_location(world_id),
+            ["home", "launch", ..] => {
+                let world_id = Self::query_value(url, "worldId")
+                    .ok_or_else(|| format!("The launch URL has no world: {}", url))?;
+                let mut reference = Self::parse_location(&world_id)?;
+                if reference.instance_id.is_none() {
+                    reference.instance_id = Self::query_value(url, "instanceId");
+                }
+                Ok(reference)
+            }
+            _ => Err(format!("The URL is not a world or launch URL: {}", url)),
+        }
+    }
+
+    /// Parses "wrld_...", or a location like "wrld_...:12345~region(jp)"
+    fn parse_location(location: &str) -> Result<Self, String> {
+        let (world_id, instance_id) = match location.split_once(':') {
+            Some((world_id, instance_id)) => (world_id, Some(instance_id)),
+            None => (location, None),
+        };
+        let world_id = world_id.to_ascii_lowercase();
+        if !Self::is_world_id(&world_id) {
+            return Err(format!("Invalid world ID: {}", world_id));
+        }
+        Ok(Self {
+            world_id,
+            instance_id: instance_id.filter(|id| !id.is_empty()).map(str::to_string),
+        })
+    }
+
+    fn query_value(url: &Url, key: &str) -> Option<String> {
+        url.query_pairs()
+            .find(|(k, _)| k == key)
+            .map(|(_, value)| value.into_owned())
+            .filter(|value| !value.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WORLD_ID: &str = "wrld_4cf554b4-430c-4f8f-b53e-1f294eed230b";
+
+    fn reference(instance_id: Option<&str>) -> WorldReference {
+        WorldReference {
+            world_id: WORLD_ID.to_string(),
+            instance_id: instance_id.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_parse_world_ids_and_locations() {
+        assert_eq!(WorldReference::parse(WORLD_ID), Ok(reference(None)));
+        assert_eq!(
+            WorldReference::parse(&format!(" {} ", WORLD_ID.replace("4cf554b4", "4CF554B4"))),
+            Ok(reference(None))
+        );
+        assert_eq!(
+            WorldReference::parse(&format!("{}:12345~region(jp)", WORLD_ID)),
+            Ok(reference(Some("12345~region(jp)")))
+        );
+    }
+
+    #[test]
+    fn test_parse_urls() {
+        assert_eq!(
+            WorldReference::parse(&format!("https://vrchat.com/home/world/{}/info", WORLD_ID)),
+            Ok(reference(None))
+        );
+        assert_eq!(
+            WorldReference::parse(&format!(
+                "https://www.vrchat.com/home/launch?worldId={}&instanceId=12345%7Eregion(jp)",
+                WORLD_ID
+            )),
+            Ok(reference(Some("12345~region(jp)")))
+        );
+        assert_eq!(
+            WorldReference::parse(&format!(
+                "vrchat://launch?ref=vrchat.com&id={}:12345~private(usr_1)",
+                WORLD_ID
+            )),
+            Ok(reference(Some("12345~private(usr_1)")))
+        );
+    }
+
+    #[test]
+    fn test_links_parse_back() {
+        let instance = reference(Some("12345~private(usr_1)~region(jp)"));
+        let launch_url = instance.launch_url(Some("abc123"));
+        assert!(launch_url.ends_with("&shortName=abc123"));
+        assert_eq!(WorldReference::parse(&launch_url), Ok(instance.clone()));
+        assert_eq!(WorldReference::parse(&instance.web_url()), Ok(instance));
+
+        let world = reference(None);
+        assert_eq!(
+            world.web_url(),
+            format!("https://vrchat.com/home/world/{}", WORLD_ID)
+        );
+        assert_eq!(WorldReference::parse(&world.launch_url(None)), Ok(world));
+    }
+
+    #[test]
+    fn test_parse_rejects_other_input() {
+        for input in [
+            "",
+            "wrld_short",
+            "https://example.com/home/world/wrld_4cf554b4-430c-4f8f-b53e-1f294eed230b",
+            "https://vrchat.com/home/user/usr_1",
+            "see wrld_4cf554b4-430c-4f8f-b53e-1f294eed230b",
+        ] {
+            assert!(WorldReference::parse(input).is_err(), "{}", input);
+        }
+    }
+}
@@ -0,0 +1,375 @@
+use std::borrow::Cow;
+use std::fmt;
+use std::fs;
+use std::io::{Read, Write};
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::de::{DeserializeOwned, SeqAccess, Visitor};
+use serde::{Deserializer, Serialize};
+
+use crate::definitions::{WorldModel, WorldsStorageFormat};
+use crate::errors::{AppError, ConcurrencyError, FileError};
+
+use super::{FileService, LibraryEncryption, Persistence, WorldLog};
+
+/// The first bytes of gzip compressed data. Content without them is read as JSON
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Items parsed between two calls of the progress callback of `parse_list`
+const PARSE_PROGRESS_INTERVAL: usize = 500;
+
+/// Collects a JSON array item by item, reporting how many were parsed so far
+struct ProgressVisitor<'a, T> {
+    on_progress: &'a mut dyn FnMut(u32),
+    item: PhantomData<T>,
+}
+
+impl<'de, T: DeserializeOwned> Visitor<'de> for ProgressVisitor<'_, T> {
+    type Value = Vec<T>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a list")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut items = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(item) = seq.next_element()? {
+            items.push(item);
+            if items.len() % PARSE_PROGRESS_INTERVAL == 0 {
+                (self.on_progress)(items.len() as u32);
+            }
+        }
+        Ok(items)
+    }
+}
+
+/// Storage formats of worlds.json, which grows to tens of MB when pretty printed
+///
+/// Compressed worlds are kept in worlds.json.gz instead, as raw gzip. Either file is read
+/// regardless of the setting, so switching back and forth needs no migration step besides
+/// rewriting the worlds. Backups are always written as pretty printed JSON
+#[derive(Debug)]
+pub struct WorldStorage;
+
+impl WorldStorage {
+    /// The file compressed worlds are kept in, next to worlds.json
+    pub fn gzip_path(worlds_path: &Path) -> PathBuf {
+        let mut path = worlds_path.as_os_str().to_os_string();
+        path.push(".gz");
+        PathBuf::from(path)
+    }
+
+    /// The file the worlds are stored in, worlds.json.gz while they are compressed
+    /// If both exist, e.g. after a crash while switching formats, the newer one is used
+    pub fn stored_path(worlds_path: &Path) -> PathBuf {
+        let gzip_path = Self::gzip_path(worlds_path);
+        let modified = |path: &Path| fs::metadata(path).and_then(|m| m.modified()).ok();
+        match (modified(&gzip_path), modified(worlds_path)) {
+            (Some(gzip), Some(json)) if gzip < json => worlds_path.to_path_buf(),
+            (Some(_), _) => gzip_path,
+            _ => worlds_path.to_path_buf(),
+        }
+    }
+
+    /// The format the worlds are stored in on disk, pretty printed JSON if they can't be read
+    pub fn stored_format(worlds_path: &Path) -> WorldsStorageFormat {
+        let stored_path = Self::stored_path(worlds_path);
+        if stored_path != worlds_path {
+            return WorldsStorageFormat::Gzip;
+        }
+        let Ok(raw) = fs::read(&stored_path) else {
+            return WorldsStorageFormat::default();
+        };
+        LibraryEncryption::open_bytes(&raw)
+            .map(|data| Self::detect(&data))
+            .unwrap_or_default()
+    }
+
+    /// Serializes data in a storage format
+    ///
+    /// # Errors
+    /// Returns a FileError if the data could not be serialized or compressed
+    pub fn encode<T: Serialize + ?Sized>(
+        value: &T,
+        format: WorldsStorageFormat,
+    ) -> Result<Vec<u8>, FileError> {
+        match format {
+            WorldsStorageFormat::Pretty => {
+                serde_json::to_vec_pretty(value).map_err(|_| FileError::InvalidFile)
+            }
+            WorldsStorageFormat::Compact => {
+                serde_json::to_vec(value).map_err(|_| FileError::InvalidFile)
+            }
+            WorldsStorageFormat::Gzip => {
+                let json = serde_json::to_vec(value).map_err(|_| FileError::InvalidFile)?;
+                Self::compress(&json)
+            }
+        }
+    }
+
+    /// Decompresses content read from disk, passing JSON through unchanged
+    /// Called after `LibraryEncryption::open_bytes`, as compressed files are encrypted as a whole
+    ///
+    /// # Errors
+    /// Returns `FileError::InvalidFile` if compressed content could not be decompressed,
+    /// or the JSON is not valid UTF-8
+    pub fn decode(data: &[u8]) -> Result<Cow<'_, str>, FileError> {
+        if !data.starts_with(&GZIP_MAGIC) {
+            return std::str::from_utf8(data)
+                .map(Cow::Borrowed)
+                .map_err(|_| FileError::InvalidFile);
+        }
+        let mut json = String::new();
+        GzDecoder::new(data)
+            .read_to_string(&mut json)
+            .map_err(|_| FileError::InvalidFile)?;
+        Ok(Cow::Owned(json))
+    }
+
+    /// Reads the worlds as JSON, whichever format they are stored in
+    ///
+    /// # Arguments
+    /// * `worlds_path` - The path of worlds.json, also for compressed worlds
+    ///
+    /// # Errors
+    /// Returns a FileError if the worlds could not be read, decrypted or decompressed
+    pub fn read_json(worlds_path: &Path) -> Result<String, FileError> {
+        let raw = fs::read(Self::stored_path(worlds_path)).map_err(|e| match e.kind() {
+            std::io::ErrorKind::PermissionDenied => FileError::AccessDenied,
+            _ => FileError::FileNotFound,
+        })?;
+        let data = LibraryEncryption::open_bytes(&raw)?;
+        Ok(Self::decode(&data)?.into_owned())
+    }
+
+    /// Writes worlds given as JSON, compressing them if they are stored compressed
+    /// Otherwise the JSON is written as it is, e.g. as received from another device
+    ///
+    /// # Arguments
+    /// * `worlds_path` - The path of worlds.json, also for compressed worlds
+    /// * `json` - The worlds as JSON
+    /// * `format` - The format the worlds are stored in
+    ///
+    /// # Errors
+    /// Returns a FileError if the worlds could not be compressed or written
+    pub fn write_json(
+        worlds_path: &Path,
+        json: &str,
+        format: WorldsStorageFormat,
+    ) -> Result<(), FileError> {
+        let data = match format {
+            WorldsStorageFormat::Gzip => Cow::Owned(Self::compress(json.as_bytes())?),
+            _ => Cow::Borrowed(json.as_bytes()),
+        };
+        let data = LibraryEncryption::seal_bytes(worlds_path, &data)?;
+        Self::write(worlds_path, &data, format)
+    }
+
+    /// Writes encoded and sealed worlds to the file of their format, then removes the file
+    /// of the other formats so it can't be read in their place
+    ///
+    /// # Arguments
+    /// * `worlds_path` - The path of worlds.json, also for compressed worlds
+    /// * `data` - The worlds, from `encode` and `LibraryEncryption::seal_bytes`
+    /// * `format` - The format the worlds were encoded in
+    ///
+    /// # Errors
+    /// Returns a FileError if the worlds could not be written, or the other file removed
+    pub fn write(
+        worlds_path: &Path,
+        data: &[u8],
+        format: WorldsStorageFormat,
+    ) -> Result<(), FileError> {
+        let gzip_path = Self::gzip_path(worlds_path);
+        let (path, stale) = match format {
+            WorldsStorageFormat::Gzip => (gzip_path, worlds_path.to_path_buf()),
+            _ => (worlds_path.to_path_buf(), gzip_path),
+        };
+        Persistence::write_bytes(&path, data)?;
+        Persistence::write_direct(&stale, |stale| {
+            if !stale.exists() {
+                return Ok(());
+            }
+            fs::remove_file(stale).map_err(|e| {
+                log::error!("Failed to remove {:?}: {}", stale, e);
+                FileError::FileWriteError
+            })
+        })
+    }
+
+    /// Parses a JSON array as `serde_json::from_str` would, reporting progress while parsing
+    /// so a large worlds.json does not look stuck on startup
+    ///
+    /// # Arguments
+    /// * `json` - The JSON, as returned by `decode`
+    /// * `on_progress` - Called with the number of items parsed so far, every few hundred items
+    ///
+    /// # Errors
+    /// Returns `FileError::InvalidFile` if the JSON is not an array of `T`
+    pub fn parse_list<T: DeserializeOwned>(
+        json: &str,
+        on_progress: &mut dyn FnMut(u32),
+    ) -> Result<Vec<T>, FileError> {
+        let mut deserializer = serde_json::Deserializer::from_str(json);
+        let items = (&mut deserializer)
+            .deserialize_seq(ProgressVisitor {
+                on_progress,
+                item: PhantomData,
+            })
+            .map_err(|_| FileError::InvalidFile)?;
+        deserializer.end().map_err(|_| FileError::InvalidFile)?;
+        Ok(items)
+    }
+
+    /// Switches worlds.json to another format, rewriting it right away
+    /// A copy of the file in its previous format is kept at `worlds.json.<format>.bak`,
+    /// e.g. for going back to a version of the app without gzip support
+    ///
+    /// # Arguments
+    /// * `format` - The format to store worlds.json in from now on
+    /// * `worlds` - The list of worlds, as a RwLock
+    ///
+    /// # Errors
+    /// Returns an error if the worlds lock is poisoned
+    /// Returns an error if the setting or worlds.json could not be written
+    pub fn set_format(
+        format: WorldsStorageFormat,
+        worlds: &RwLock<Vec<WorldModel>>,
+    ) -> Result<(), AppError> {
+        let worlds_lock = worlds.read().map_err(|_| ConcurrencyError::PoisonedLock)?;
+        Self::keep_copy(format);
+
+        let mut custom_data = FileService::read_custom_data();
+        custom_data.preferences.worlds_storage = Some(format);
+        FileService::write_custom_data(&custom_data)?;
+        // Without it, an unchanged library would only be appended to worlds.log
+        WorldLog::discard();
+        FileService::write_worlds(&*worlds_lock)?;
+        log::info!("Stored worlds.json as {:?}", format);
+        Ok(())
+    }
+
+    /// Copies worlds.json aside if it is stored in another format than `format`
+    /// Failures are only logged, the regular .bak file is still written on save
+    fn keep_copy(format: WorldsStorageFormat) {
+        let (_, _, worlds_path, _) = FileService::get_paths();
+        let stored_path = Self::stored_path(&worlds_path);
+        let previous = Self::stored_format(&worlds_path);
+        if previous == format || !stored_path.exists() {
+            return;
+        }
+
+        let mut backup = worlds_path.as_os_str().to_os_string();
+        backup.push(format!(".{}.bak", Self::name(previous)));
+        match fs::copy(&stored_path, &backup) {
+            Ok(_) => log::info!("Kept a copy of worlds.json at {:?}", backup),
+            Err(e) => log::warn!("Failed to keep a copy of worlds.json: {}", e),
+        }
+    }
+
+    fn compress(json: &[u8]) -> Result<Vec<u8>, FileError> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder
+            .write_all(json)
+            .map_err(|_| FileError::FileWriteError)?;
+        encoder.finish().map_err(|_| FileError::FileWriteError)
+    }
+
+    /// The format content read from disk is stored in
+    fn detect(data: &[u8]) -> WorldsStorageFormat {
+        if data.starts_with(&GZIP_MAGIC) {
+            WorldsStorageFormat::Gzip
+        } else if String::from_utf8_lossy(data).trim().contains('\n') {
+            WorldsStorageFormat::Pretty
+        } else {
+            WorldsStorageFormat::Compact
+        }
+    }
+
+    fn name(format: WorldsStorageFormat) -> &'static str {
+        match format {
+            WorldsStorageFormat::Pretty => "pretty",
+            WorldsStorageFormat::Compact => "compact",
+            WorldsStorageFormat::Gzip => "gzip",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+
+    #[test]
+    fn test_encode_decode_round_trip() {
+        let worlds = json!([{ "id": "wrld_1", "name": "Chill\nRoom" }, { "id": "wrld_2" }]);
+        for format in [
+            WorldsStorageFormat::Pretty,
+            WorldsStorageFormat::Compact,
+            WorldsStorageFormat::Gzip,
+        ] {
+            let encoded = WorldStorage::encode(&worlds, format).unwrap();
+            assert_eq!(WorldStorage::detect(&encoded), format);
+            let decoded: Value =
+                serde_json::from_str(&WorldStorage::decode(&encoded).unwrap()).unwrap();
+            assert_eq!(decoded, worlds);
+        }
+    }
+
+    #[test]
+    fn test_parse_list_reports_progress() {
+        let items: Vec<u32> = (0..1200).collect();
+        let json = serde_json::to_string(&items).unwrap();
+        let mut reported = vec![];
+
+        let parsed: Vec<u32> =
+            WorldStorage::parse_list(&json, &mut |parsed| reported.push(parsed)).unwrap();
+        assert_eq!(parsed, items);
+        assert_eq!(reported, vec![500, 1000]);
+
+        let trailing = format!("{} []", json);
+        assert!(WorldStorage::parse_list::<u32>(&trailing, &mut |_| {}).is_err());
+    }
+
+    #[test]
+    fn test_decode_rejects_corrupted_gzip() {
+        let result = WorldStorage::decode(&[0x1f, 0x8b, b'n', b'o', b't']);
+        assert!(matches!(result, Err(FileError::InvalidFile)));
+        assert!(WorldStorage::decode(&[0xff, 0xfe]).is_err());
+    }
+
+    #[test]
+    fn test_stored_path_prefers_newer_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let worlds_path = dir.path().join("worlds.json");
+        let gzip_path = WorldStorage::gzip_path(&worlds_path);
+        assert!(gzip_path.ends_with("worlds.json.gz"));
+        assert_eq!(WorldStorage::stored_path(&worlds_path), worlds_path);
+
+        fs::write(&worlds_path, "[]").unwrap();
+        fs::write(&gzip_path, WorldStorage::compress(b"[]").unwrap()).unwrap();
+        let old = std::time::SystemTime::now() - std::time::Duration::from_secs(60);
+        fs::File::options()
+            .write(true)
+            .open(&worlds_path)
+            .unwrap()
+            .set_modified(old)
+            .unwrap();
+        assert_eq!(WorldStorage::stored_path(&worlds_path), gzip_path);
+        assert_eq!(
+            WorldStorage::stored_format(&worlds_path),
+            WorldsStorageFormat::Gzip
+        );
+
+        fs::remove_file(&gzip_path).unwrap();
+        assert_eq!(
+            WorldStorage::stored_format(&worlds_path),
+            WorldsStorageFormat::Compact
+        );
+    }
+}
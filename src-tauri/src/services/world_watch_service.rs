@@ -0,0 +1,277 @@
+use std::sync::{Arc, RwLock};
+
+use reqwest::cookie::Jar;
+
+use crate::api::world::ReleaseStatus;
+use crate::definitions::{
+    CriticalWorldChange, EventCriticalWorldChanged, FolderModel, WorldApiData, WorldAvailability,
+    WorldBlacklist, WorldModel,
+};
+use crate::errors::{AppError, ConcurrencyError};
+
+use super::{ApiService, FileService, FolderManager};
+
+/// Service for watching event critical worlds for changes which could break a planned event
+#[derive(Debug)]
+pub struct WorldWatchService;
+
+impl WorldWatchService {
+    /// Compares the stored data of a world against freshly fetched data
+    /// The release status is compared against the stored availability, so a world which
+    /// stays private is only reported once
+    ///
+    /// # Arguments
+    /// * `old` - The stored world data
+    /// * `new` - The freshly fetched world data
+    /// * `release_status` - The current release status of the world
+    ///
+    /// # Returns
+    /// The list of detected changes, empty if nothing relevant changed
+    #[must_use]
+    pub fn detect_changes(
+        old: &WorldApiData,
+        new: &WorldApiData,
+        release_status: &ReleaseStatus,
+    ) -> Vec<CriticalWorldChange> {
+        let mut changes = vec![];
+
+        if old.capacity != new.capacity {
+            changes.push(CriticalWorldChange::Capacity {
+                old: old.capacity,
+                new: new.capacity,
+            });
+        }
+
+        if old.availability != Self::availability(release_status) {
+            changes.push(CriticalWorldChange::ReleaseStatus {
+                new: release_status.to_string(),
+            });
+        }
+
+        let mut old_platform = old.platform.clone();
+        let mut new_platform = new.platform.clone();
+        old_platform.sort();
+        new_platform.sort();
+        if old_platform != new_platform {
+            changes.push(CriticalWorldChange::Platform {
+                old: old_platform,
+                new: new_platform,
+            });
+        }
+
+        changes
+    }
+
+    /// Checks freshly fetched worlds for changes to event critical worlds
+    /// Called by every refresh before the fetched worlds are stored
+    /// The watched fields are stored right away, so each change is reported once, even for
+    /// worlds which are no longer public and so are not stored by the refresh
+    /// Worlds only in archived folders are skipped
+    ///
+    /// # Arguments
+    /// * `fetched` - The fetched worlds, with their release status
+    /// * `folders` - The list of folders, as a RwLock
+    /// * `worlds` - The list of worlds, as a RwLock
+    ///
+    /// # Returns
+    /// One alert per event critical world which changed
+    ///
+    /// # Errors
+    /// Returns an error if any lock is poisoned
+    /// Returns an error if the changed worlds could not be saved
+    pub fn check_fetched(
+        fetched: &[(WorldApiData, ReleaseStatus)],
+        folders: &RwLock<Vec<FolderModel>>,
+        worlds: &RwLock<Vec<WorldModel>>,
+    ) -> Result<Vec<EventCriticalWorldChanged>, AppError> {
+        let archived = FolderManager::get_archived_only_world_ids(folders)?;
+        let mut worlds_lock = worlds.write().map_err(|_| ConcurrencyError::PoisonedLock)?;
+
+        let mut alerts = vec![];
+        for (new, release_status) in fetched {
+            let Some(world) = worlds_lock
+                .iter_mut()
+                .find(|w| w.api_data.world_id == new.world_id)
+            else {
+                continue;
+            };
+            if !world.user_data.is_event_critical || archived.contains(&new.world_id) {
+                continue;
+            }
+
+            let changes = Self::detect_changes(&world.api_data, new, release_status);
+            if changes.is_empty() {
+                continue;
+            }
+            log::warn!(
+                "Event critical world {} changed: {:?}",
+                new.world_id,
+                changes
+            );
+            alerts.push(EventCriticalWorldChanged {
+                world_id: new.world_id.clone(),
+                world_name: new.world_name.clone(),
+                changes,
+            });
+
+            world.api_data.availability = Self::availability(release_status);
+            if *release_status == ReleaseStatus::Public {
+                world.api_data.capacity = new.capacity;
+                world.api_data.platform = new.platform.clone();
+            }
+        }
+
+        if !alerts.is_empty() {
+            FileService::write_worlds(&*worlds_lock)?;
+        }
+        Ok(alerts)
+    }
+
+    /// Refreshes every event critical world and reports relevant changes
+    /// Public worlds have their stored data updated; non-public worlds are left untouched
+    /// so the last known good data is kept. Worlds only in archived folders are skipped
+    ///
+    /// # Arguments
+    /// * `cookie_store` - The cookie store to use for the API
+    /// * `folders` - The list of folders, as a RwLock
+    /// * `worlds` - The list of worlds, as a RwLock
+    /// * `blacklist` - The world blacklist, as a RwLock
+    ///
+    /// # Returns
+    /// One alert per world which changed
+    ///
+    /// # Errors
+    /// Returns an error if any lock is poisoned
+    /// Returns an error if the updated worlds could not be saved
+    pub async fn refresh_event_critical_worlds(
+        cookie_store: Arc<Jar>,
+        folders: &RwLock<Vec<FolderModel>>,
+        worlds: &RwLock<Vec<WorldModel>>,
+        blacklist: &RwLock<WorldBlacklist>,
+    ) -> Result<Vec<EventCriticalWorldChanged>, AppError> {
+        let archived = FolderManager::get_archived_only_world_ids(folders)?;
+        let watched: Vec<String> = {
+            let worlds_lock = worlds.read().map_err(|_| ConcurrencyError::PoisonedLock)?;
+            worlds_lock
+                .iter()
+                .filter(|w| w.user_data.is_event_critical)
+                .filter(|w| !archived.contains(&w.api_data.world_id))
+                .map(|w| w.api_data.world_id.clone())
+                .collect()
+        };
+
+        let mut fetched = vec![];
+        for world_id in watched {
+            match ApiService::get_world_with_release_status(cookie_store.clone(), &world_id).await {
+                Ok(result) => fetched.push(result),
+                Err(e) => {
+                    log::warn!("Failed to refresh event critical world {}: {}", world_id, e);
+                }
+            }
+        }
+
+        let alerts = Self::check_fetched(&fetched, folders, worlds)?;
+        let refreshed = fetched
+            .into_iter()
+            .filter(|(_, release_status)| *release_status == ReleaseStatus::Public)
+            .map(|(world, _)| world)
+            .collect();
+        FolderManager::add_worlds(worlds, refreshed, blacklist)?;
+
+        Ok(alerts)
+    }
+
+    fn availability(release_status: &ReleaseStatus) -> WorldAvailability {
+        if *release_status == ReleaseStatus::Public {
+            WorldAvailability::Public
+        } else {
+            WorldAvailability::Private
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_world(capacity: i32, platform: Vec<&str>) -> WorldApiData {
+        WorldApiData {
+            image_url: "".to_string(),
+            world_name: "Event World".to_string(),
+            world_id: "wrld_event".to_string(),
+            author_name: "Test Author".to_string(),
+            author_id: "usr_test".to_string(),
+            capacity,
+            recommended_capacity: None,
+            tags: vec![],
+            publication_date: None,
+            last_update: chrono::Utc::now(),
+            description: "".to_string(),
+            visits: None,
+            favorites: 0,
+            platform: platform.into_iter().map(String::from).collect(),
+            packages: vec![],
+            availability: WorldAvailability::Public,
+        }
+    }
+
+    #[test]
+    fn test_detect_changes_none() {
+        let old = make_world(32, vec!["standalonewindows", "android"]);
+        let new = make_world(32, vec!["android", "standalonewindows"]);
+        assert!(WorldWatchService::detect_changes(&old, &new, &ReleaseStatus::Public).is_empty());
+    }
+
+    #[test]
+    fn test_detect_changes_all() {
+        let old = make_world(32, vec!["standalonewindows", "android"]);
+        let new = make_world(16, vec!["standalonewindows"]);
+        let changes = WorldWatchService::detect_changes(&old, &new, &ReleaseStatus::Private);
+
+        assert_eq!(changes.len(), 3);
+        assert_eq!(
+            changes[0],
+            CriticalWorldChange::Capacity { old: 32, new: 16 }
+        );
+        assert_eq!(
+            changes[1],
+            CriticalWorldChange::ReleaseStatus {
+                new: "private".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_detect_changes_still_private() {
+        let mut old = make_world(32, vec!["standalonewindows"]);
+        old.availability = WorldAvailability::Private;
+        let new = make_world(32, vec!["standalonewindows"]);
+        assert!(WorldWatchService::detect_changes(&old, &new, &ReleaseStatus::Private).is_empty());
+
+        // Going public again is reported as well
+        let changes = WorldWatchService::detect_changes(&old, &new, &ReleaseStatus::Public);
+        assert_eq!(
+            changes,
+            vec![CriticalWorldChange::ReleaseStatus {
+                new: "public".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn test_check_fetched_reports_once() {
+        let mut world = WorldModel::new(make_world(32, vec!["standalonewindows"]));
+        world.user_data.is_event_critical = true;
+        let worlds = RwLock::new(vec![world]);
+        let folders = RwLock::new(vec![]);
+        let fetched = vec![(
+            make_world(32, vec!["standalonewindows"]),
+            ReleaseStatus::Private,
+        )];
+
+        let alerts = WorldWatchService::check_fetched(&fetched, &folders, &worlds).unwrap();
+        assert_eq!(alerts.len(), 1);
+        let alerts = WorldWatchService::check_fetched(&fetched, &folders, &worlds).unwrap();
+        assert!(alerts.is_empty());
+    }
+}
@@ -0,0 +1,115 @@
+use tauri::{AppHandle, Manager};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutEvent, ShortcutState};
+
+use crate::app_state::AppState;
+use crate::commands::playlist_commands::create_default_instance;
+use crate::services::{ApiService, FileService};
+use crate::AUTHENTICATOR;
+
+/// Binding used until the user picks another one
+pub const DEFAULT_QUICK_LAUNCH_SHORTCUT: &str = "CommandOrControl+Shift+L";
+
+/// The saved quick launch binding
+pub fn saved_binding() -> String {
+    FileService::read_custom_data()
+        .preferences
+        .quick_launch_shortcut
+        .unwrap_or_else(|| DEFAULT_QUICK_LAUNCH_SHORTCUT.to_string())
+}
+
+/// Registers the saved quick launch shortcut, called once on startup
+/// Another app may already own the binding, which is only logged
+pub fn register_saved(app: &AppHandle) {
+    let binding = saved_binding();
+    let result = binding
+        .parse::<Shortcut>()
+        .map_err(|e| e.to_string())
+        .and_then(|shortcut| {
+            app.global_shortcut()
+                .register(shortcut)
+                .map_err(|e| e.to_string())
+        });
+    match result {
+        Ok(()) => log::info!("Registered quick launch shortcut {}", binding),
+        Err(e) => log::warn!(
+            "Failed to register quick launch shortcut {}: {}",
+            binding,
+            e
+        ),
+    }
+}
+
+/// Replaces the registered quick launch shortcut
+/// The previous binding is restored if the new one cannot be registered
+///
+/// # Arguments
+/// * `app` - The app handle
+/// * `binding` - The new binding, e.g. "CommandOrControl+Shift+L"
+///
+/// # Errors
+/// Returns an error message if the binding is invalid or already taken
+pub fn rebind(app: &AppHandle, binding: &str) -> Result<(), String> {
+    let shortcut = binding
+        .parse::<Shortcut>()
+        .map_err(|e| format!("Invalid shortcut {}: {}", binding, e))?;
+    let previous = saved_binding().parse::<Shortcut>().ok();
+
+    let global_shortcut = app.global_shortcut();
+    if let Some(previous) = previous {
+        // Not registered if another app owned it on startup
+        let _ = global_shortcut.unregister(previous);
+    }
+    if let Err(e) = global_shortcut.register(shortcut) {
+        if let Some(previous) = previous {
+            let _ = global_shortcut.register(previous);
+        }
+        return Err(format!("Shortcut {} is not available: {}", binding, e));
+    }
+    Ok(())
+}
+
+/// Handles the quick launch shortcut, the only one the app registers
+pub fn on_shortcut(app: &AppHandle, _shortcut: &Shortcut, event: ShortcutEvent) {
+    if event.state() != ShortcutState::Pressed {
+        return;
+    }
+    tauri::async_runtime::spawn(quick_launch(app.clone()));
+}
+
+/// Creates an instance of the pinned world with the default settings and opens it in the client
+async fn quick_launch(app: AppHandle) {
+    let Some(world_id) = FileService::read_custom_data().preferences.pinned_world else {
+        log::info!("Quick launch shortcut pressed without a pinned world");
+        return;
+    };
+
+    let state = app.state::<AppState>();
+    let info = match create_default_instance(world_id, app.clone(), &state).await {
+        Ok(info) => info,
+        Err(e) => {
+            log::warn!("Failed to quick launch pinned world: {}", e);
+            return;
+        }
+    };
+
+    let cookie_store = AUTHENTICATOR.get().read().await.get_cookies();
+    if let Err(e) =
+        ApiService::open_instance_in_client(cookie_store, &info.world_id, &info.instance_id, app)
+            .await
+    {
+        log::warn!("Failed to open quick launched instance: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_bindings() {
+        assert!(DEFAULT_QUICK_LAUNCH_SHORTCUT.parse::<Shortcut>().is_ok());
+        // Rejected by `rebind` before the registered binding is touched
+        assert!("CommandOrControl+Shift+".parse::<Shortcut>().is_err());
+        assert!("Shift+NotAKey".parse::<Shortcut>().is_err());
+    }
+}
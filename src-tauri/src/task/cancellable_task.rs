@@ -1,33 +1,75 @@
 use std::{future::Future, sync::Arc};
 
+use chrono::{DateTime, Utc};
 use tauri::AppHandle;
 use tauri_specta::Event;
 use tokio::{sync::Mutex, task::AbortHandle};
 use uuid::Uuid;
 
-use super::definitions::{TaskStatus, TaskStatusChanged};
+use super::definitions::{TaskInfo, TaskKind, TaskProgress, TaskStatus, TaskStatusChanged};
+
+/// Handed to a running task so it can report how far it has got
+/// Every report is stored on the task and emitted as a `TaskStatusChanged` event
+#[derive(Clone)]
+pub struct TaskProgressReporter {
+    id: Uuid,
+    kind: TaskKind,
+    app_handle: Option<AppHandle>,
+    progress: Arc<Mutex<Option<TaskProgress>>>,
+}
+
+impl TaskProgressReporter {
+    pub fn task_id(&self) -> Uuid {
+        self.id
+    }
+
+    pub async fn report(&self, progress: TaskProgress) {
+        *self.progress.lock().await = Some(progress);
+
+        if let Some(app_handle) = &self.app_handle {
+            let event =
+                TaskStatusChanged::new(self.id, self.kind, TaskStatus::Running, Some(progress));
+            if let Err(e) = event.emit(app_handle) {
+                log::error!("Failed to emit TaskStatusChanged event: {}", e);
+            }
+        }
+    }
+}
 
 pub struct CancellableTask {
     pub id: Uuid,
+    pub kind: TaskKind,
+    started_at: DateTime<Utc>,
     status: Arc<Mutex<TaskStatus>>,
     error: Arc<Mutex<Option<String>>>,
+    progress: Arc<Mutex<Option<TaskProgress>>>,
     abort_handle: AbortHandle,
 }
 
 impl CancellableTask {
-    fn create<F>(app_handle: Option<AppHandle>, task: F) -> Self
+    fn create<F, Fut>(app_handle: Option<AppHandle>, kind: TaskKind, task: F) -> Self
     where
-        F: Future<Output = Result<(), String>> + Send + 'static,
+        F: FnOnce(TaskProgressReporter) -> Fut,
+        Fut: Future<Output = Result<(), String>> + Send + 'static,
     {
         let id = Uuid::new_v4();
+        let progress = Arc::new(Mutex::new(None));
+
+        let reporter = TaskProgressReporter {
+            id,
+            kind,
+            app_handle: app_handle.clone(),
+            progress: Arc::clone(&progress),
+        };
 
-        let task = tokio::spawn(task);
+        let task = tokio::spawn(task(reporter));
         let abort_handle = task.abort_handle();
         let status = Arc::new(Mutex::new(TaskStatus::Running));
         let error = Arc::new(Mutex::new(None));
 
         let cloned_status = Arc::clone(&status);
         let cloned_error = Arc::clone(&error);
+        let cloned_progress = Arc::clone(&progress);
 
         tokio::spawn(async move {
             let result = task.await;
@@ -47,7 +89,9 @@ impl CancellableTask {
             };
 
             if let Some(app_handle) = app_handle {
-                if let Err(e) = TaskStatusChanged::new(id, *status).emit(&app_handle) {
+                let progress = *cloned_progress.lock().await;
+                let event = TaskStatusChanged::new(id, kind, *status, progress);
+                if let Err(e) = event.emit(&app_handle) {
                     log::error!("Failed to emit TaskStatusChanged event: {}", e);
                 }
             }
@@ -55,8 +99,11 @@ impl CancellableTask {
 
         Self {
             id,
+            kind,
+            started_at: Utc::now(),
             status,
             error,
+            progress,
             abort_handle,
         }
     }
@@ -69,6 +116,20 @@ impl CancellableTask {
         self.error.lock().await.clone()
     }
 
+    pub async fn get_progress(&self) -> Option<TaskProgress> {
+        *self.progress.lock().await
+    }
+
+    pub async fn get_info(&self) -> TaskInfo {
+        TaskInfo {
+            id: self.id,
+            kind: self.kind,
+            status: self.get_status().await,
+            progress: self.get_progress().await,
+            started_at: self.started_at,
+        }
+    }
+
     pub async fn abort(&mut self) -> Result<TaskStatus, String> {
         let status = self.get_status().await;
 
@@ -103,11 +164,20 @@ impl TaskContainer {
         }
     }
 
-    pub fn run<F>(&mut self, task: F) -> Result<Uuid, String>
+    pub fn run<F>(&mut self, kind: TaskKind, task: F) -> Result<Uuid, String>
     where
         F: Future<Output = Result<(), String>> + Send + 'static,
     {
-        let task = CancellableTask::create(self.app_handle.clone(), task);
+        self.run_with_progress(kind, |_| task)
+    }
+
+    /// Runs a task which reports its progress through the given reporter
+    pub fn run_with_progress<F, Fut>(&mut self, kind: TaskKind, task: F) -> Result<Uuid, String>
+    where
+        F: FnOnce(TaskProgressReporter) -> Fut,
+        Fut: Future<Output = Result<(), String>> + Send + 'static,
+    {
+        let task = CancellableTask::create(self.app_handle.clone(), kind, task);
 
         let id = task.id;
         self.tasks.push(Arc::new(Mutex::new(task)));
@@ -125,6 +195,18 @@ impl TaskContainer {
 
         None
     }
+
+    /// Lists the tasks which are still running, oldest first
+    pub async fn list_active(&self) -> Vec<TaskInfo> {
+        let mut active = vec![];
+        for task in &self.tasks {
+            let info = task.lock().await.get_info().await;
+            if info.status == TaskStatus::Running {
+                active.push(info);
+            }
+        }
+        active
+    }
 }
 
 #[cfg(test)]
@@ -136,7 +218,7 @@ mod tests {
         let mut task_container = TaskContainer::new_without_app_handle();
 
         let task_id = task_container
-            .run(async {
+            .run(TaskKind::UpdateDownload, async {
                 tokio::time::sleep(std::time::Duration::from_millis(50)).await;
                 Ok(())
             })
@@ -148,6 +230,8 @@ mod tests {
             assert_eq!(status, TaskStatus::Running);
         }
 
+        assert_eq!(task_container.list_active().await.len(), 1);
+
         tokio::time::sleep(std::time::Duration::from_millis(100)).await;
 
         {
@@ -155,5 +239,112 @@ mod tests {
             let status = task.lock().await.get_status().await;
             assert_eq!(status, TaskStatus::Completed);
         }
+
+        assert!(task_container.list_active().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_task_progress() {
+        let mut task_container = TaskContainer::new_without_app_handle();
+
+        let task_id = task_container
+            .run_with_progress(TaskKind::FavoritesImport, |reporter| async move {
+                reporter
+                    .report(TaskProgress::FavoritesImport {
+                        pages_fetched: 1,
+                        worlds_added: 0,
+                    })
+                    .await;
+                Ok(())
+            })
+            .unwrap();
+
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+
+        let task = task_container.get(&task_id).await.unwrap();
+        let progress = task.lock().await.get_progress().await;
+        assert_eq!(
+            progress,
+            Some(TaskProgress::FavoritesImport {
+                pages_fetched: 1,
+                worlds_added: 0
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_task_abort_and_failure() {
+        let mut task_container = TaskContainer::new_without_app_handle();
+
+        let long_id = task_container
+            .run(TaskKind::FavoritesImport, async {
+                tokio::time::sleep(std::time::Duration::from_secs(60)).await;
+                Ok(())
+            })
+            .unwrap();
+        let failing_id = task_container
+            .run(TaskKind::FavoritesImport, async {
+                Err("Failed to fetch favorites".to_string())
+            })
+            .unwrap();
+
+        let long_task = task_container.get(&long_id).await.unwrap();
+        let status = long_task.lock().await.abort().await.unwrap();
+        assert_eq!(status, TaskStatus::Cancelled);
+
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+
+        {
+            let task = long_task.lock().await;
+            assert_eq!(task.get_status().await, TaskStatus::Cancelled);
+            assert_eq!(task.get_error().await, None);
+        }
+
+        // Aborting a finished task keeps its status
+        let failing_task = task_container.get(&failing_id).await.unwrap();
+        let mut task = failing_task.lock().await;
+        assert_eq!(task.abort().await.unwrap(), TaskStatus::Failed);
+        assert_eq!(
+            task.get_error().await,
+            Some("Failed to fetch favorites".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_list_active_task_info() {
+        let mut task_container = TaskContainer::new_without_app_handle();
+        let long_task = || async {
+            tokio::time::sleep(std::time::Duration::from_secs(60)).await;
+            Ok(())
+        };
+
+        let refresh_id = task_container
+            .run(TaskKind::FolderRefresh, long_task())
+            .unwrap();
+        let cancelled_id = task_container
+            .run(TaskKind::UpdateDownload, long_task())
+            .unwrap();
+        let import_id = task_container
+            .run(TaskKind::FavoritesImport, long_task())
+            .unwrap();
+
+        let cancelled = task_container.get(&cancelled_id).await.unwrap();
+        cancelled.lock().await.abort().await.unwrap();
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+
+        // Cancelled tasks are left out, the rest are listed oldest first
+        let active = task_container.list_active().await;
+        let listed: Vec<(Uuid, TaskKind)> = active.iter().map(|t| (t.id, t.kind)).collect();
+        assert_eq!(
+            listed,
+            vec![
+                (refresh_id, TaskKind::FolderRefresh),
+                (import_id, TaskKind::FavoritesImport)
+            ]
+        );
+        assert!(active.iter().all(|t| t.progress.is_none()));
+        assert!(active[0].started_at <= active[1].started_at);
+
+        assert!(task_container.get(&Uuid::new_v4()).await.is_none());
     }
 }
@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use serde::Serialize;
 use uuid::Uuid;
 
@@ -9,14 +10,83 @@ pub enum TaskStatus {
     Failed,
 }
 
+/// What a background task is doing, so the frontend can label it
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, specta::Type)]
+pub enum TaskKind {
+    UpdateDownload,
+    FavoritesImport,
+    FolderRefresh,
+    InitialImport,
+    ThumbnailPrefetch,
+}
+
+/// A step of the first-run import, in the order the steps run
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub enum InitialImportStep {
+    Migration,
+    Favorites,
+    RecentlyVisited,
+}
+
+/// Progress of a long-running fetch task, by kind of task
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, specta::Type)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum TaskProgress {
+    #[serde(rename_all = "camelCase")]
+    FavoritesImport { pages_fetched: u32, worlds_added: u32 },
+    /// `updated` and `unavailable` are final once the task has completed
+    #[serde(rename_all = "camelCase")]
+    FolderRefresh {
+        checked: u32,
+        total: u32,
+        updated: u32,
+        unavailable: u32,
+    },
+    /// `step` is the step running, or the last one once the task has completed
+    #[serde(rename_all = "camelCase")]
+    InitialImport {
+        step: InitialImportStep,
+        completed_steps: u32,
+        failed_steps: u32,
+        total_steps: u32,
+    },
+    /// `downloaded` counts thumbnails which were not cached yet
+    #[serde(rename_all = "camelCase")]
+    ThumbnailPrefetch {
+        checked: u32,
+        total: u32,
+        downloaded: u32,
+        failed: u32,
+    },
+}
+
+/// Snapshot of a background task, as shown in the task panel
+#[derive(Serialize, Debug, Clone, specta::Type)]
+pub struct TaskInfo {
+    pub id: Uuid,
+    pub kind: TaskKind,
+    pub status: TaskStatus,
+    pub progress: Option<TaskProgress>,
+    #[serde(rename = "startedAt")]
+    pub started_at: DateTime<Utc>,
+}
+
 #[derive(Serialize, Clone, specta::Type, tauri_specta::Event)]
 pub struct TaskStatusChanged {
     id: Uuid,
+    kind: TaskKind,
     status: TaskStatus,
+    progress: Option<TaskProgress>,
 }
 
 impl TaskStatusChanged {
-    pub fn new(id: Uuid, status: TaskStatus) -> Self {
-        Self { id, status }
+    pub fn new(id: Uuid, kind: TaskKind, status: TaskStatus, progress: Option<TaskProgress>) -> Self {
+        Self {
+            id,
+            kind,
+            status,
+            progress,
+        }
     }
 }
@@ -0,0 +1,220 @@
+use tauri::menu::{CheckMenuItem, Menu, MenuEvent, MenuItem, PredefinedMenuItem};
+use tauri::tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent};
+use tauri::{AppHandle, Manager, Window, WindowEvent};
+
+use crate::app_state::AppState;
+use crate::commands::playlist_commands::create_default_instance;
+use crate::services::{BackgroundJobs, FileService, GuestMode, Persistence};
+
+const MENU_OPEN: &str = "open";
+const MENU_RANDOM_FAVORITE: &str = "random_favorite";
+const MENU_LAST_WORLD: &str = "last_world";
+const MENU_PAUSE: &str = "pause_background";
+const MENU_QUIT: &str = "quit";
+
+/// Adds the tray icon with its quick actions
+/// Left clicking the icon brings the window back
+///
+/// # Errors
+/// Returns an error if the icon or its menu could not be created
+pub fn create(app: &AppHandle) -> tauri::Result<()> {
+    let menu = Menu::with_items(
+        app,
+        &[
+            &MenuItem::with_id(app, MENU_OPEN, "Open", true, None::<&str>)?,
+            &PredefinedMenuItem::separator(app)?,
+            &MenuItem::with_id(
+                app,
+                MENU_RANDOM_FAVORITE,
+                "Launch random favorite",
+                true,
+                None::<&str>,
+            )?,
+            &MenuItem::with_id(
+                app,
+                MENU_LAST_WORLD,
+                "Create instance of last world",
+                true,
+                None::<&str>,
+            )?,
+            &CheckMenuItem::with_id(
+                app,
+                MENU_PAUSE,
+                "Pause background refresh",
+                true,
+                BackgroundJobs::is_paused(),
+                None::<&str>,
+            )?,
+            &PredefinedMenuItem::separator(app)?,
+            &MenuItem::with_id(app, MENU_QUIT, "Quit", true, None::<&str>)?,
+        ],
+    )?;
+
+    let mut builder = TrayIconBuilder::with_id("main")
+        .tooltip("VRC Worlds Manager")
+        .menu(&menu)
+        .show_menu_on_left_click(false)
+        .on_menu_event(on_menu_event)
+        .on_tray_icon_event(|tray, event| {
+            if let TrayIconEvent::Click {
+                button: MouseButton::Left,
+                button_state: MouseButtonState::Up,
+                ..
+            } = event
+            {
+                show_main_window(tray.app_handle());
+            }
+        });
+    if let Some(icon) = app.default_window_icon() {
+        builder = builder.icon(icon.clone());
+    }
+    builder.build(app)?;
+    Ok(())
+}
+
+/// Hides the window on startup if the user chose to start minimized to the tray
+pub fn apply_start_minimized(app: &AppHandle) {
+    let start_minimized = FileService::read_custom_data()
+        .preferences
+        .tray
+        .unwrap_or_default()
+        .start_minimized;
+    if !start_minimized {
+        return;
+    }
+    if let Some(window) = app.get_webview_window("main") {
+        if let Err(e) = window.hide() {
+            log::warn!("Failed to start minimized to tray: {}", e);
+        }
+    }
+}
+
+/// Hides the window instead of closing it if the user chose to close to the tray,
+/// so background jobs keep running
+pub fn on_window_event(window: &Window, event: &WindowEvent) {
+    let WindowEvent::CloseRequested { api, .. } = event else {
+        return;
+    };
+    let close_to_tray = FileService::read_custom_data()
+        .preferences
+        .tray
+        .unwrap_or_default()
+        .close_to_tray;
+    if close_to_tray && window.label() == "main" {
+        api.prevent_close();
+        if let Err(e) = window.hide() {
+            log::warn!("Failed to hide window to tray: {}", e);
+        }
+    }
+}
+
+fn show_main_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
+
+fn on_menu_event(app: &AppHandle, event: MenuEvent) {
+    match event.id().as_ref() {
+        MENU_OPEN => show_main_window(app),
+        MENU_RANDOM_FAVORITE => {
+            let app = app.clone();
+            tauri::async_runtime::spawn(async move {
+                let state = app.state::<AppState>();
+                let Some(world_id) = random_favorite(&state) else {
+                    log::info!("No favorite world to launch from the tray");
+                    return;
+                };
+                launch(world_id, app.clone(), &state).await;
+            });
+        }
+        MENU_LAST_WORLD => {
+            let app = app.clone();
+            tauri::async_runtime::spawn(async move {
+                let Some(world_id) = FileService::read_custom_data().last_instance_world else {
+                    log::info!("No instance was created yet to repeat from the tray");
+                    return;
+                };
+                launch(world_id, app.clone(), &app.state::<AppState>()).await;
+            });
+        }
+        // The check mark toggles itself, the flag follows it
+        MENU_PAUSE => BackgroundJobs::set_paused(!BackgroundJobs::is_paused()),
+        MENU_QUIT => {
+            let app = app.clone();
+            tauri::async_runtime::spawn(async move {
+                // Queued writes would be lost on exit
+                if let Err(e) = Persistence::flush().await {
+                    log::error!("Failed to flush pending writes before quitting: {}", e);
+                }
+                app.exit(0);
+            });
+        }
+        _ => {}
+    }
+}
+
+/// Picks a visible favorite world at random
+fn random_favorite(state: &AppState) -> Option<String> {
+    let worlds = state.worlds().ok()?;
+    let favorites: Vec<&str> = worlds
+        .iter()
+        .filter(|w| w.user_data.is_favorite && !w.user_data.hidden)
+        .map(|w| w.api_data.world_id.as_str())
+        .collect();
+    if favorites.is_empty() {
+        return None;
+    }
+    let index = rand::random_range(0..favorites.len());
+    Some(favorites[index].to_string())
+}
+
+async fn launch(world_id: String, app: AppHandle, state: &AppState) {
+    if GuestMode::is_enabled() {
+        log::info!("Not launching {} from the tray in guest mode", world_id);
+        return;
+    }
+    match create_default_instance(world_id.clone(), app, state).await {
+        Ok(info) => log::info!(
+            "Created instance {} of {} from the tray",
+            info.instance_id,
+            world_id
+        ),
+        Err(e) => log::warn!("Failed to create instance from the tray: {}", e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::definitions::PreferenceModel;
+    use crate::services::sorting_service::make_test_world;
+
+    #[test]
+    fn test_random_favorite() {
+        let mut favorite = make_test_world("wrld_favorite");
+        favorite.user_data.is_favorite = true;
+        let mut hidden = make_test_world("wrld_hidden_favorite");
+        hidden.user_data.is_favorite = true;
+        hidden.user_data.hidden = true;
+        let state = AppState::new(
+            PreferenceModel::new(),
+            vec![],
+            vec![favorite, hidden, make_test_world("wrld_other")],
+        );
+
+        // Hidden favorites are never picked
+        for _ in 0..10 {
+            assert_eq!(random_favorite(&state).as_deref(), Some("wrld_favorite"));
+        }
+
+        let state = AppState::new(
+            PreferenceModel::new(),
+            vec![],
+            vec![make_test_world("wrld_other")],
+        );
+        assert_eq!(random_favorite(&state), None);
+    }
+}
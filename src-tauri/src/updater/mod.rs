@@ -1 +1,3 @@
+pub mod releases;
+pub mod rollback;
 pub mod update_handler;
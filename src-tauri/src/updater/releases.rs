@@ -0,0 +1,138 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::changelog::app_version;
+
+use super::update_handler::{UpdateChannel, UpdatePolicy};
+
+const RELEASES_URL: &str =
+    "https://api.github.com/repos/maruo29/VRC-Worlds-Manager-v2-Custom/releases?per_page=30";
+
+/// At most this many releases are listed
+const MAX_RELEASES: usize = 10;
+
+/// A release as returned by the GitHub API
+#[derive(Deserialize, Debug)]
+struct GitHubRelease {
+    tag_name: String,
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    draft: bool,
+    #[serde(default)]
+    prerelease: bool,
+    #[serde(default)]
+    published_at: Option<DateTime<Utc>>,
+    html_url: String,
+}
+
+/// A published release, with whether the user skipped it
+#[derive(Serialize, Debug, Clone, PartialEq, specta::Type)]
+pub struct ReleaseInfo {
+    pub version: String,
+    pub name: String,
+    #[serde(rename = "preRelease")]
+    pub pre_release: bool,
+    #[serde(rename = "publishedAt")]
+    pub published_at: Option<DateTime<Utc>>,
+    pub url: String,
+    pub skipped: bool,
+    pub installed: bool,
+}
+
+/// Fetches the recent releases of a channel, newest first
+/// The stable channel only lists stable releases, the pre-release channel lists both
+///
+/// # Errors
+/// Returns an error if the releases could not be fetched or parsed
+pub async fn fetch_releases(
+    channel: UpdateChannel,
+    policy: &UpdatePolicy,
+) -> Result<Vec<ReleaseInfo>, String> {
+    let client = reqwest::Client::builder()
+        .user_agent(format!("VRC-Worlds-Manager-v2/{}", app_version()))
+        .timeout(Duration::from_secs(10))
+        .build()
+        .map_err(|e| format!("Failed to create reqwest client: {}", e))?;
+    let response = client
+        .get(RELEASES_URL)
+        .header("Accept", "application/vnd.github+json")
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch releases: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("Failed to fetch releases: {}", response.status()));
+    }
+    let releases: Vec<GitHubRelease> = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse releases: {}", e))?;
+    Ok(to_release_infos(releases, channel, policy))
+}
+
+fn to_release_infos(
+    releases: Vec<GitHubRelease>,
+    channel: UpdateChannel,
+    policy: &UpdatePolicy,
+) -> Vec<ReleaseInfo> {
+    releases
+        .into_iter()
+        .filter(|release| !release.draft)
+        .filter(|release| channel == UpdateChannel::PreRelease || !release.prerelease)
+        .take(MAX_RELEASES)
+        .map(|release| {
+            let version = release.tag_name.trim_start_matches('v').to_string();
+            ReleaseInfo {
+                skipped: policy.skipped_versions.contains(&version),
+                installed: version == app_version(),
+                name: release.name.unwrap_or_else(|| release.tag_name.clone()),
+                pre_release: release.prerelease,
+                published_at: release.published_at,
+                url: release.html_url,
+                version,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn release(tag: &str, prerelease: bool, draft: bool) -> GitHubRelease {
+        GitHubRelease {
+            tag_name: tag.to_string(),
+            name: None,
+            draft,
+            prerelease,
+            published_at: None,
+            html_url: format!("https://example.com/{}", tag),
+        }
+    }
+
+    #[test]
+    fn test_releases_are_filtered_by_channel() {
+        let releases = || {
+            vec![
+                release("v1.6.0-rc.1", true, false),
+                release("v1.6.0", false, true),
+                release("v1.5.0", false, false),
+            ]
+        };
+        let policy = UpdatePolicy {
+            skipped_versions: vec!["1.5.0".to_string()],
+            pinned_version: None,
+        };
+
+        let stable = to_release_infos(releases(), UpdateChannel::Stable, &policy);
+        assert_eq!(stable.len(), 1);
+        assert_eq!(stable[0].version, "1.5.0");
+        assert!(stable[0].skipped);
+
+        let pre_release = to_release_infos(releases(), UpdateChannel::PreRelease, &policy);
+        let versions: Vec<&str> = pre_release.iter().map(|r| r.version.as_str()).collect();
+        assert_eq!(versions, vec!["1.6.0-rc.1", "1.5.0"]);
+    }
+}
@@ -0,0 +1,246 @@
+use std::fs;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::changelog::app_version;
+use crate::services::FileService;
+
+/// A version is rolled back after failing to start this many times in a row
+const MAX_FAILED_STARTS: u32 = 2;
+
+/// Written when startup begins and removed once it finishes
+/// Finding it on the next start means that start crashed
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq)]
+struct StartupMarker {
+    version: String,
+    #[serde(rename = "failedStarts")]
+    failed_starts: u32,
+}
+
+/// An installer kept after installing an update
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RetainedInstaller {
+    pub version: String,
+    pub file_name: String,
+}
+
+/// The installers of the installed version and the one before it
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq)]
+struct RetainedInstallers {
+    current: Option<RetainedInstaller>,
+    previous: Option<RetainedInstaller>,
+}
+
+fn marker_path() -> PathBuf {
+    FileService::get_app_dir().join("startup_marker.json")
+}
+
+fn installers_dir() -> PathBuf {
+    FileService::get_app_dir().join("installers")
+}
+
+fn installers_path() -> PathBuf {
+    installers_dir().join("installers.json")
+}
+
+fn read_json<T: for<'de> Deserialize<'de> + Default>(path: &PathBuf) -> T {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|data| serde_json::from_str(&data).ok())
+        .unwrap_or_default()
+}
+
+fn write_json<T: Serialize>(path: &PathBuf, value: &T) {
+    let result = serde_json::to_string(value)
+        .map_err(|e| e.to_string())
+        .and_then(|data| fs::write(path, data).map_err(|e| e.to_string()));
+    if let Err(e) = result {
+        log::error!("Failed to write {}: {}", path.display(), e);
+    }
+}
+
+/// Counts the failed starts of this version, including the one before this start
+fn next_marker(previous: Option<StartupMarker>, version: &str) -> StartupMarker {
+    let failed_starts = previous
+        .filter(|marker| marker.version == version)
+        .map_or(0, |marker| marker.failed_starts + 1);
+    StartupMarker {
+        version: version.to_string(),
+        failed_starts,
+    }
+}
+
+/// Marks the start of startup, and decides whether to roll back
+///
+/// # Returns
+/// The installer of the previous version, if this version failed to start twice in a row
+/// and that installer was kept when this version was installed
+pub fn begin_startup() -> Option<RetainedInstaller> {
+    let path = marker_path();
+    let previous: Option<StartupMarker> = path.exists().then(|| read_json(&path));
+    let marker = next_marker(previous, app_version());
+    if marker.failed_starts > 0 {
+        log::warn!(
+            "Version {} failed to start {} time(s) in a row",
+            marker.version,
+            marker.failed_starts
+        );
+    }
+    write_json(&path, &marker);
+
+    if marker.failed_starts < MAX_FAILED_STARTS {
+        return None;
+    }
+    let installers: RetainedInstallers = read_json(&installers_path());
+    let current_matches = installers
+        .current
+        .as_ref()
+        .is_some_and(|current| current.version == marker.version);
+    match installers.previous.filter(|_| current_matches) {
+        Some(previous) if installers_dir().join(&previous.file_name).exists() => Some(previous),
+        _ => {
+            log::warn!("No installer of the previous version was kept, not rolling back");
+            None
+        }
+    }
+}
+
+/// Marks startup as finished, resetting the count of failed starts
+pub fn complete_startup() {
+    let path = marker_path();
+    if path.exists() {
+        if let Err(e) = fs::remove_file(&path) {
+            log::error!("Failed to remove startup marker: {}", e);
+        }
+    }
+}
+
+/// Keeps the installer of an update about to be installed, so it can be rolled back to
+/// from the next version. Only the installers of the last two versions are kept, and only
+/// on Windows, where updates are installers
+///
+/// # Arguments
+/// * `version` - The version being installed
+/// * `bytes` - The downloaded installer
+pub fn retain_installer(version: &str, bytes: &[u8]) {
+    if !cfg!(windows) {
+        return;
+    }
+    let dir = installers_dir();
+    if let Err(e) = fs::create_dir_all(&dir) {
+        log::error!("Failed to create installers directory: {}", e);
+        return;
+    }
+    let file_name = format!("{}-setup.exe", version);
+    if let Err(e) = fs::write(dir.join(&file_name), bytes) {
+        log::error!("Failed to keep installer of {}: {}", version, e);
+        return;
+    }
+
+    let mut installers: RetainedInstallers = read_json(&installers_path());
+    if let Some(outdated) = installers.previous.take() {
+        if outdated.file_name != file_name {
+            let _ = fs::remove_file(dir.join(&outdated.file_name));
+        }
+    }
+    installers.previous = installers.current.take();
+    installers.current = Some(RetainedInstaller {
+        version: version.to_string(),
+        file_name,
+    });
+    write_json(&installers_path(), &installers);
+}
+
+/// Starts the installer of the previous version, the caller should exit afterwards
+/// The failing version is skipped, so it is not offered again
+///
+/// # Errors
+/// Returns an error if the installer could not be started, or on platforms where the
+/// installer is not kept
+pub fn roll_back(installer: &RetainedInstaller) -> Result<(), String> {
+    let failed = app_version().to_string();
+    let mut custom_data = FileService::read_custom_data();
+    let policy = custom_data
+        .preferences
+        .update_policy
+        .get_or_insert_with(Default::default);
+    if !policy.skipped_versions.contains(&failed) {
+        policy.skipped_versions.push(failed.clone());
+    }
+    if let Err(e) = FileService::write_custom_data(&custom_data) {
+        log::error!("Failed to skip version {}: {}", failed, e);
+    }
+    complete_startup();
+
+    if !cfg!(windows) {
+        return Err("Rolling back is only supported on Windows".to_string());
+    }
+    let path = installers_dir().join(&installer.file_name);
+    log::warn!("Rolling back from {} to {}", failed, installer.version);
+    std::process::Command::new(&path)
+        .spawn()
+        .map(|_| ())
+        .map_err(|e| format!("Failed to start {}: {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn marker(version: &str, failed_starts: u32) -> StartupMarker {
+        StartupMarker {
+            version: version.to_string(),
+            failed_starts,
+        }
+    }
+
+    #[test]
+    fn test_failed_starts_are_counted_per_version() {
+        assert_eq!(next_marker(None, "1.2.0"), marker("1.2.0", 0));
+        assert_eq!(
+            next_marker(Some(marker("1.2.0", 0)), "1.2.0"),
+            marker("1.2.0", 1)
+        );
+        assert_eq!(
+            next_marker(Some(marker("1.2.0", 1)), "1.2.0"),
+            marker("1.2.0", 2)
+        );
+        assert_eq!(
+            next_marker(Some(marker("1.1.0", 3)), "1.2.0"),
+            marker("1.2.0", 0)
+        );
+    }
+
+    #[test]
+    fn test_rolls_back_after_repeated_failed_starts() {
+        fs::create_dir_all(installers_dir()).unwrap();
+        fs::write(installers_dir().join("1.4.0-setup.exe"), b"installer").unwrap();
+        let previous = RetainedInstaller {
+            version: "1.4.0".to_string(),
+            file_name: "1.4.0-setup.exe".to_string(),
+        };
+        write_json(
+            &installers_path(),
+            &RetainedInstallers {
+                current: Some(RetainedInstaller {
+                    version: app_version().to_string(),
+                    file_name: format!("{}-setup.exe", app_version()),
+                }),
+                previous: Some(previous.clone()),
+            },
+        );
+        complete_startup();
+
+        // A start which never completed counts as failed, and two in a row roll back
+        assert_eq!(begin_startup(), None);
+        assert_eq!(begin_startup(), None);
+        assert_eq!(begin_startup(), Some(previous));
+
+        // A finished start resets the count
+        complete_startup();
+        assert!(!marker_path().exists());
+        assert_eq!(begin_startup(), None);
+        complete_startup();
+    }
+}
@@ -64,8 +64,17 @@ impl UpdateHandler {
         Ok(())
     }
 
+    /// Installs the downloaded update, keeping its installer to roll back to from the
+    /// version after it
     pub fn install_update(&self) -> Result<(), String> {
-        Err("Auto-update disabled".to_string())
+        let (Some(update), Some(data)) = (&self.update_handler, &self.downloaded_update_data)
+        else {
+            return Err("Auto-update disabled".to_string());
+        };
+        super::rollback::retain_installer(&update.version, data);
+        update
+            .install(data)
+            .map_err(|e| format!("Failed to install update: {}", e))
     }
 
     pub fn is_initialized(&self) -> bool {
@@ -113,3 +122,40 @@ impl UpdateChannel {
         }
     }
 }
+
+/// Which versions the user does not want to be offered
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, specta::Type)]
+pub struct UpdatePolicy {
+    /// Versions the user chose to skip, or which were rolled back from
+    #[serde(rename = "skippedVersions", default)]
+    pub skipped_versions: Vec<String>,
+    /// When set, no other version is offered
+    #[serde(rename = "pinnedVersion", default)]
+    pub pinned_version: Option<String>,
+}
+
+impl UpdatePolicy {
+    /// Whether an update to `version` may be offered
+    pub fn allows(&self, version: &str) -> bool {
+        let version = version.trim_start_matches('v');
+        self.pinned_version.is_none() && !self.skipped_versions.iter().any(|v| v == version)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update_policy() {
+        let mut policy = UpdatePolicy {
+            skipped_versions: vec!["1.6.0".to_string()],
+            pinned_version: None,
+        };
+        assert!(!policy.allows("v1.6.0"));
+        assert!(policy.allows("1.6.1"));
+
+        policy.pinned_version = Some("1.5.0".to_string());
+        assert!(!policy.allows("1.6.1"));
+    }
+}
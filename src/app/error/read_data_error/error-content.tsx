@@ -44,7 +44,7 @@ export function ErrorContent() {
       if (result.status === 'ok') {
         info('Opened logs directory');
       } else {
-        error(`Failed to open logs directory: ${result.error}`);
+        error(`Failed to open logs directory: ${result.error.message}`);
         toast(t('general:error-title'), {
           description: t('general:error-open-logs'),
         });
@@ -64,7 +64,7 @@ export function ErrorContent() {
       if (result.status === 'ok') {
         info('Opened folder directory');
       } else {
-        error(`Failed to open folder directory: ${result.error}`);
+        error(`Failed to open folder directory: ${result.error.message}`);
         toast(t('general:error-title'), {
           description: t('general:error-open-folder'),
         });
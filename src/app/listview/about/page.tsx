@@ -23,7 +23,7 @@ export default function AboutSection() {
         if (result.status === 'ok') {
           setOrderedSupporters(sortSupporters(result.data));
         } else {
-          throw new Error(result.error);
+          throw new Error(result.error.message);
         }
       } catch (e) {
         error(`Failed to fetch Patreon data: ${e}`);
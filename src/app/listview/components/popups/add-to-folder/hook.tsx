@@ -88,7 +88,7 @@ export const useAddToFolderPopup = ({
                 return [w.worldId, new Set<string>(res.data)] as const;
               }
               error(
-                `[AddToFolder] getFoldersForWorld failed for ${w.worldId}: ${res.error}`,
+                `[AddToFolder] getFoldersForWorld failed for ${w.worldId}: ${res.error.message}`,
               );
               // fallback to provided world.folders if available
               return [w.worldId, new Set<string>(w.folders ?? [])] as const;
@@ -147,7 +147,7 @@ export function AddWorldPopup({ onClose, currentFolder, initialWorldId }: AddWor
       if (worldDetails.status === 'ok') {
         setPreviewWorld(worldDetails.data);
       } else {
-        setError(worldDetails.error);
+        setError(worldDetails.error.message);
       }
     } catch (err) {
       setError(`Failed to fetch world details: ${err}`);
@@ -73,7 +73,7 @@ export function ShareFolderPopup({
             if (shareRes.status === 'ok') {
               setShareId(shareRes.data);
             } else {
-              setErrorMessage(t('share-folder:error-message', shareRes.error));
+              setErrorMessage(t('share-folder:error-message', shareRes.error.message));
             }
           } catch (e) {
             setErrorMessage('Failed to create share');
@@ -81,8 +81,8 @@ export function ShareFolderPopup({
             setShareLoading(false);
           }
         } else {
-          error(`getWorlds error: ${result.error}`);
-          setErrorMessage(t('share-folder:error-message', result.error));
+          error(`getWorlds error: ${result.error.message}`);
+          setErrorMessage(t('share-folder:error-message', result.error.message));
         }
       } catch (e) {
         error(`Failed to fetch folder info: ${e}`);
@@ -103,7 +103,7 @@ export function ShareFolderPopup({
       info(`Shared folder "${folderName}" as ${id.data}`);
       setShareId(id.data);
     } else {
-      setErrorMessage(t('share-folder:error-message', id.error));
+      setErrorMessage(t('share-folder:error-message', id.error.message));
     }
     setShareLoading(false);
   };
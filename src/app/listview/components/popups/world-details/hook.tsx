@@ -23,7 +23,7 @@ export function useWorldDetailsActions(onOpenChange: (open: boolean) => void) {
         region,
       );
       if (result.status === 'error') {
-        toast(t('general:error-title'), { description: result.error });
+        toast(t('general:error-title'), { description: result.error.message });
         return;
       }
       // result.data contains InstanceInfo with world_id, instance_id, short_name
@@ -39,7 +39,7 @@ export function useWorldDetailsActions(onOpenChange: (open: boolean) => void) {
                 info.instance_id,
               );
               if (openRes.status === 'error') {
-                toast(t('general:error-title'), { description: openRes.error });
+                toast(t('general:error-title'), { description: openRes.error.message });
               }
             } catch (e) {
               error(`Failed to open instance in client: ${e}`);
@@ -73,7 +73,7 @@ export function useWorldDetailsActions(onOpenChange: (open: boolean) => void) {
         queueEnabled,
       );
       if (result.status === 'error') {
-        toast(t('general:error-title'), { description: result.error });
+        toast(t('general:error-title'), { description: result.error.message });
         return;
       }
       const info = result.data;
@@ -88,7 +88,7 @@ export function useWorldDetailsActions(onOpenChange: (open: boolean) => void) {
                 info.instance_id,
               );
               if (openRes.status === 'error') {
-                toast(t('general:error-title'), { description: openRes.error });
+                toast(t('general:error-title'), { description: openRes.error.message });
               }
             } catch (e) {
               error(`Failed to open instance in client: ${e}`);
@@ -108,7 +108,7 @@ export function useWorldDetailsActions(onOpenChange: (open: boolean) => void) {
     try {
       const result = await commands.getUserGroups();
       if (result.status === 'error') {
-        throw new Error(result.error);
+        throw new Error(result.error.message);
       }
       return result.data;
     } catch (e) {
@@ -126,7 +126,7 @@ export function useWorldDetailsActions(onOpenChange: (open: boolean) => void) {
     try {
       const result = await commands.getPermissionForCreateGroupInstance(id);
       if (result.status === 'error') {
-        throw new Error(result.error);
+        throw new Error(result.error.message);
       }
       return result.data;
     } catch (e) {
@@ -161,7 +161,7 @@ export function WorldDetailPopup({
         if (result.status === 'ok') {
           setWorldDetails(result.data);
         } else {
-          if (result.error.includes('World is not public')) {
+          if (result.error.message.includes('World is not public')) {
             setIsWorldNotPublic(true);
 
             // Get cached world data
@@ -198,13 +198,13 @@ export function WorldDetailPopup({
                   setIsCountdownActive(true); // Start the countdown only if blacklisted
                 }
               } else {
-                error(`Failed to fetch blacklist: ${blacklistResult.error}`);
+                error(`Failed to fetch blacklist: ${blacklistResult.error.message}`);
               }
             } catch (blacklistError) {
               error(`Failed to fetch blacklist: ${blacklistError}`);
             }
           }
-          setErrorState(result.error);
+          setErrorState(result.error.message);
         }
       } catch (e) {
         error(`Failed to fetch world details: ${e}`);
@@ -221,7 +221,7 @@ export function WorldDetailPopup({
         setMemo(result.data);
         setMemoInput(result.data);
       } else {
-        console.error(result.error);
+        console.error(result.error.message);
       }
     };
     const fetchWorldFolders = async () => {
@@ -232,7 +232,7 @@ export function WorldDetailPopup({
         if (result.status === 'ok') {
           setWorldFolders(result.data);
         } else {
-          error(`Failed to fetch folders for world: ${result.error}`);
+          error(`Failed to fetch folders for world: ${result.error.message}`);
         }
       } catch (e) {
         error(`Error fetching folders for world: ${e}`);
@@ -307,7 +307,7 @@ export function WorldDetailPopup({
       setMemo(memoInput);
       setIsEditingMemo(false);
     } else {
-      console.error(result.error);
+      console.error(result.error.message);
     }
   }, [worldId, memoInput]);
 
@@ -412,7 +412,7 @@ export function WorldDetailPopup({
         const result = await commands.removeWorldFromFolder(folder, worldId);
         if (result.status !== 'ok') {
           error(
-            `Failed to remove world from folder "${folder}" for world ID "${worldId}": ${result.error}`,
+            `Failed to remove world from folder "${folder}" for world ID "${worldId}": ${result.error.message}`,
           );
           return;
         }
@@ -424,7 +424,7 @@ export function WorldDetailPopup({
         const result = await commands.addWorldToFolder(folder, worldId);
         if (result.status !== 'ok') {
           error(
-            `Failed to add world to folder "${folder}" for world ID "${worldId}": ${result.error}`,
+            `Failed to add world to folder "${folder}" for world ID "${worldId}": ${result.error.message}`,
           );
           return;
         }
@@ -116,15 +116,15 @@ export function useWorldGrid(
 
         const existingWorldsResult = await commands.getAllWorlds();
         if (existingWorldsResult.status !== 'ok') {
-          error(`Error fetching worlds: ${existingWorldsResult.error}`);
-          throw new Error(existingWorldsResult.error);
+          error(`Error fetching worlds: ${existingWorldsResult.error.message}`);
+          throw new Error(existingWorldsResult.error.message);
         }
         const existingWorlds = existingWorldsResult.data;
 
         const hiddenWorldsResult = await commands.getHiddenWorlds();
         if (hiddenWorldsResult.status !== 'ok') {
-          error(`Error fetching hidden worlds: ${hiddenWorldsResult.error}`);
-          throw new Error(hiddenWorldsResult.error);
+          error(`Error fetching hidden worlds: ${hiddenWorldsResult.error.message}`);
+          throw new Error(hiddenWorldsResult.error.message);
         }
         const hiddenWorlds = hiddenWorldsResult.data;
 
@@ -125,7 +125,7 @@ export default function FindWorldsPage() {
 
       const worlds = await commands.getRecentlyVisitedWorlds();
       if (worlds.status !== 'ok') {
-        throw new Error(worlds.error);
+        throw new Error(worlds.error.message);
       } else {
         info(`Fetched recently visited worlds: ${worlds.data.length}`);
 
@@ -287,22 +287,25 @@ export default function FindWorldsPage() {
         [],
         [],
         query,
+        null, // Use the default platform preference
+        null,
+        null,
         1,
       );
 
       if (result.status === 'ok') {
-        info(`Auto-search results: ${result.data.length} worlds found`);
-        const processedData = result.data; // Note: Auto-search also needs merging if desired, but less criticial for initial load
+        info(`Auto-search results: ${result.data.worlds.length} worlds found`);
+        const processedData = result.data.worlds; // Note: Auto-search also needs merging if desired, but less criticial for initial load
         setSearchResults(processedData);
-        setHasMoreResults(result.data.length > 0);
+        setHasMoreResults(result.data.hasMore);
 
-        if (result.data.length === 0) {
+        if (result.data.worlds.length === 0) {
           toast(t('find-page:no-more-results'), {
             description: t('find-page:try-different-search'),
           });
         }
       } else {
-        throw new Error(result.error);
+        throw new Error(result.error.message);
       }
     } catch (err) {
       error(`Auto-search error: ${err}`);
@@ -345,11 +348,14 @@ export default function FindWorldsPage() {
         selectedTags,
         selectedExcludedTags,
         searchQuery,
+        null, // Use the default platform preference
+        null,
+        null,
         page,
       );
 
       if (result.status === 'ok') {
-        info(`Search results: ${result.data.length} worlds found`);
+        info(`Search results: ${result.data.worlds.length} worlds found`);
 
         const processResults = (worlds: WorldDisplayData[]) => {
           return worlds.map((world) => {
@@ -369,7 +375,7 @@ export default function FindWorldsPage() {
           });
         };
 
-        const processedData = processResults(result.data);
+        const processedData = processResults(result.data.worlds);
 
         if (loadMore) {
           // Append new results to existing ones
@@ -383,15 +389,15 @@ export default function FindWorldsPage() {
         }
 
         // Check if we've reached the end of results
-        setHasMoreResults(result.data.length > 0);
+        setHasMoreResults(result.data.hasMore);
 
-        if (result.data.length === 0 && !loadMore) {
+        if (result.data.worlds.length === 0 && !loadMore) {
           toast(t('find-page:no-more-results'), {
             description: t('find-page:try-different-search'),
           });
         }
       } else {
-        throw new Error(result.error);
+        throw new Error(result.error.message);
       }
     } catch (err) {
       error(`Search error: ${err}`);
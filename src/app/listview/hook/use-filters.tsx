@@ -303,7 +303,7 @@ export function useWorldFilters(worlds: WorldDisplayData[]) {
           if (result.status === 'ok') {
             memoIdSet = new Set(result.data);
           } else {
-            toast(t('general:error-title'), { description: result.error });
+            toast(t('general:error-title'), { description: result.error.message });
           }
         } catch (e) {
           error(`Error searching memo text: ${e}`);
@@ -352,7 +352,7 @@ export function useWorldFilters(worlds: WorldDisplayData[]) {
         if (sortRes.status === 'ok') {
           sortedList = sortRes.data;
         } else {
-          error(`[useWorldFilters] Backend sort failed: ${sortRes.error}`);
+          error(`[useWorldFilters] Backend sort failed: ${sortRes.error.message}`);
           sortedList = fallbackSort();
         }
       } catch (e) {
@@ -415,7 +415,7 @@ export function useWorldFilters(worlds: WorldDisplayData[]) {
               }
             } else {
               error(
-                `[useWorldFilters] Fallback getTagsByCount error=${fallbackRes.error}`,
+                `[useWorldFilters] Fallback getTagsByCount error=${fallbackRes.error.message}`,
               );
             }
           } catch (e) {
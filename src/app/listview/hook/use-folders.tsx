@@ -6,9 +6,9 @@ import { usePathname, useRouter } from 'next/navigation';
 import { usePopupStore } from './usePopups/store';
 
 const fetchFolders = async (): Promise<FolderData[]> => {
-  const result = await commands.getFolders();
+  const result = await commands.getFolders(null);
   if (result.status === 'ok') return result.data;
-  throw new Error(result.error);
+  throw new Error(result.error.message);
 };
 
 const createFolderCommand = async (name: string) => {
@@ -20,7 +20,7 @@ const deleteFolderCommand = async (name: string) => {
 };
 
 const renameFolderCommand = async (oldName: string, newName: string) => {
-  return await commands.renameFolder(oldName, newName);
+  return await commands.renameFolder(oldName, newName, null);
 };
 
 const moveFolderCommand = async (
@@ -62,7 +62,7 @@ export function useFolders() {
       toast(t('general:error-title'), {
         description: t('listview-page:error-create-folder'),
       });
-      throw new Error(result.error);
+      throw new Error(result.error.message);
     } else {
       toast(t('listview-page:folder-created-title'), {
         description: t('listview-page:folder-created-description', name),
@@ -78,7 +78,7 @@ export function useFolders() {
       toast(t('general:error-title'), {
         description: t('listview-page:error-delete-folder'),
       });
-      throw new Error(result.error);
+      throw new Error(result.error.message);
     } else {
       toast(t('listview-page:folder-deleted-title'), {
         description: t('listview-page:folder-deleted-description', name),
@@ -100,7 +100,7 @@ export function useFolders() {
       toast(t('general:error-title'), {
         description: t('listview-page:error-rename-folder'),
       });
-      throw new Error(result.error);
+      throw new Error(result.error.message);
     } else {
       toast(t('listview-page:folder-renamed-title'), {
         description: t('listview-page:folder-renamed-description', newName),
@@ -162,7 +162,7 @@ export function useFolders() {
       toast(t('general:error-title'), {
         description: t('listview-page:error-set-folder-color'),
       });
-      throw new Error(result.error);
+      throw new Error(result.error.message);
     }
     mutate('folders');
   };
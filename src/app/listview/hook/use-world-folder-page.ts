@@ -8,6 +8,7 @@ import { useWorldFilters } from './use-filters';
 import { useSelectedWorldsStore } from './use-selected-worlds';
 import { useWorlds } from './use-worlds';
 import { commands, WorldDisplayData } from '@/lib/bindings';
+import { waitForTask } from '@/lib/tasks';
 import { toast } from 'sonner';
 import { error } from '@tauri-apps/plugin-log';
 
@@ -100,9 +101,14 @@ export const useWorldFolderPage = (
         toast.info(t('listview-page:reloading-worlds'), { duration: 5000 });
       }
 
-      const favs = await commands.getFavoriteWorlds();
+      const favs = await commands.getFavoriteWorlds(null);
       if (favs.status === 'error') {
-        toast(t('general:error-title'), { description: favs.error });
+        toast(t('general:error-title'), { description: favs.error.message });
+        return;
+      }
+
+      const status = await waitForTask(favs.data);
+      if (status === 'Cancelled') {
         return;
       }
 
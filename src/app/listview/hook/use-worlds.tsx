@@ -1,4 +1,5 @@
 import { commands, WorldDisplayData } from '@/lib/bindings';
+import { waitForTask } from '@/lib/tasks';
 import { FolderType, isUserFolder, SpecialFolders } from '@/types/folders';
 import { create } from 'zustand';
 import { error, info } from '@tauri-apps/plugin-log';
@@ -34,23 +35,23 @@ async function fetchWorldsImpl(
   if (isUserFolder(folder)) {
     const res = await commands.getWorlds(folder as string);
     if (res.status === 'ok') return res.data;
-    throw new Error(res.error);
+    throw new Error(res.error.message);
   }
   switch (folder) {
     case SpecialFolders.All: {
       const res = await commands.getAllWorlds();
       if (res.status === 'ok') return res.data;
-      throw new Error(res.error);
+      throw new Error(res.error.message);
     }
     case SpecialFolders.Unclassified: {
       const res = await commands.getUnclassifiedWorlds();
       if (res.status === 'ok') return res.data;
-      throw new Error(res.error);
+      throw new Error(res.error.message);
     }
     case SpecialFolders.Hidden: {
       const res = await commands.getHiddenWorlds();
       if (res.status === 'ok') return res.data;
-      throw new Error(res.error);
+      throw new Error(res.error.message);
     }
     case SpecialFolders.Find:
       return [];
@@ -119,7 +120,7 @@ export const useWorldsStore = create<WorldsStoreState>((set, get) => ({
   async addWorldToFolder(folder, worldId) {
     const key = folderKey(folder);
     const res = await commands.getWorld(worldId, null);
-    if (res.status === 'error') throw new Error(res.error);
+    if (res.status === 'error') throw new Error(res.error.message);
 
     // Only call addWorldToFolder command for user folders
     if (isUserFolder(folder)) {
@@ -139,12 +140,12 @@ export const useWorldsStore = create<WorldsStoreState>((set, get) => ({
   async getAllWorlds() {
     const res = await commands.getAllWorlds();
     if (res.status === 'ok') return res.data;
-    throw new Error(res.error);
+    throw new Error(res.error.message);
   },
   async getFavoriteWorlds() {
-    const res = await commands.getFavoriteWorlds();
-    if (res.status === 'ok') return res.data;
-    throw new Error(res.error);
+    const res = await commands.getFavoriteWorlds(null);
+    if (res.status === 'ok') return waitForTask(res.data);
+    throw new Error(res.error.message);
   },
   updateWorldProperty(worldId, updates) {
     set((s) => {
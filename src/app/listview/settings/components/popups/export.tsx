@@ -66,11 +66,11 @@ export function ExportPopup({
     // get folders from backend
     async function fetchFolders() {
       try {
-        const result = await commands.getFolders();
+        const result = await commands.getFolders(true); // Archived folders can be exported too
         if (result.status === 'ok') {
           setFolders(result.data);
         } else {
-          console.error('Failed to fetch folders:', result.error);
+          console.error('Failed to fetch folders:', result.error.message);
         }
       } catch (error) {
         console.error('Error fetching folders:', error);
@@ -108,7 +108,7 @@ export function MigrationPopup({
       );
 
       if (result.status === 'error') {
-        setErrorMessage(result.error);
+        setErrorMessage(result.error.message);
         setMigrationData(null);
         setIsLoading(false);
         return;
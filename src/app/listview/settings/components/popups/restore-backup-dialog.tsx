@@ -69,7 +69,7 @@ export function RestoreBackupDialog({
       const result = await commands.getBackupMetadata(backupPath);
 
       if (result.status === 'error') {
-        setErrorMessage(result.error);
+        setErrorMessage(result.error.message);
         setSelectedPath(null);
         setMetadata(null);
         setIsLoading(false);
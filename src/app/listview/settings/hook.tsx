@@ -68,7 +68,7 @@ export const useSettingsPage = () => {
           return;
       }
       if (result.status === 'error') {
-        error(`Export failed: ${result.error}`);
+        error(`Export failed: ${result.error.message}`);
         toast(t('general:error-title'), {
           description: t('settings-page:error-export-data'),
         });
@@ -136,14 +136,14 @@ export const useSettingsPage = () => {
             description:
               t('settings-page:error-load-preferences') +
               ': ' +
-              (themeResult.status === 'error' ? themeResult.error : '') +
-              (languageResult.status === 'error' ? languageResult.error : '') +
-              (cardSizeResult.status === 'error' ? cardSizeResult.error : '') +
+              (themeResult.status === 'error' ? themeResult.error.message : '') +
+              (languageResult.status === 'error' ? languageResult.error.message : '') +
+              (cardSizeResult.status === 'error' ? cardSizeResult.error.message : '') +
               (updateChannelResult.status === 'error'
-                ? updateChannelResult.error
+                ? updateChannelResult.error.message
                 : '') +
               (folderRemovalPreferenceResult.status === 'error'
-                ? folderRemovalPreferenceResult.error
+                ? folderRemovalPreferenceResult.error.message
                 : ''),
           });
         }
@@ -183,7 +183,7 @@ export const useSettingsPage = () => {
       const result = await commands.createBackup(backupPath);
 
       if (result.status === 'error') {
-        error(`Backup creation failed: ${result.error}`);
+        error(`Backup creation failed: ${result.error.message}`);
         toast(t('general:error-title'), {
           description: t('settings-page:error-create-backup'),
         });
@@ -208,7 +208,7 @@ export const useSettingsPage = () => {
       const result = await commands.restoreFromBackup(path);
 
       if (result.status === 'error') {
-        error(`Restore failed: ${result.error}`);
+        error(`Restore failed: ${result.error.message}`);
         toast(t('general:error-title'), {
           description: t('settings-page:error-restore-backup'),
         });
@@ -248,7 +248,7 @@ export const useSettingsPage = () => {
       const result = await commands.migrateOldData(worldsPath, foldersPath);
 
       if (result.status === 'error') {
-        error(`Migration failed: ${result.error}`);
+        error(`Migration failed: ${result.error.message}`);
         toast(t('general:error-title'), {
           description: t('settings-page:error-migrate-data'),
         });
@@ -273,7 +273,7 @@ export const useSettingsPage = () => {
       info('Deleting all data...');
       const result = await commands.deleteData();
       if (result.status === 'error') {
-        error(`Data deletion failed: ${result.error}`);
+        error(`Data deletion failed: ${result.error.message}`);
         toast(t('general:error-title'), {
           description: t('settings-page:error-delete-data'),
         });
@@ -300,7 +300,7 @@ export const useSettingsPage = () => {
       const result = await commands.logout();
 
       if (result.status === 'error') {
-        error(`Logout failed: ${result.error}`);
+        error(`Logout failed: ${result.error.message}`);
         toast(t('general:error-title'), {
           description: t('settings-page:error-logout'),
         });
@@ -324,7 +324,7 @@ export const useSettingsPage = () => {
       if (result.status === 'ok') {
         info('Opened logs directory');
       } else {
-        error(`Failed to open logs directory: ${result.error}`);
+        error(`Failed to open logs directory: ${result.error.message}`);
       }
     } catch (e) {
       error(`Failed to open logs directory: ${e}`);
@@ -343,10 +343,10 @@ export const useSettingsPage = () => {
         setTheme(value);
         info(`Theme set to: ${value}`);
       } else {
-        error(`Failed to set theme: ${result.error}`);
+        error(`Failed to set theme: ${result.error.message}`);
         toast(t('general:error-title'), {
           description:
-            t('settings-page:error-save-preferences') + ': ' + result.error,
+            t('settings-page:error-save-preferences') + ': ' + result.error.message,
         });
       }
     } catch (e) {
@@ -366,10 +366,10 @@ export const useSettingsPage = () => {
         setLanguage(value);
         info(`Language set to: ${value}`);
       } else {
-        error(`Failed to set language: ${result.error}`);
+        error(`Failed to set language: ${result.error.message}`);
         toast(t('general:error-title'), {
           description:
-            t('settings-page:error-save-preferences') + ': ' + result.error,
+            t('settings-page:error-save-preferences') + ': ' + result.error.message,
         });
       }
     } catch (e) {
@@ -388,10 +388,10 @@ export const useSettingsPage = () => {
         setCardSize(value);
         info(`Card size set to: ${value}`);
       } else {
-        error(`Failed to set card size: ${result.error}`);
+        error(`Failed to set card size: ${result.error.message}`);
         toast(t('general:error-title'), {
           description:
-            t('settings-page:error-save-preferences') + ': ' + result.error,
+            t('settings-page:error-save-preferences') + ': ' + result.error.message,
         });
         return;
       }
@@ -413,10 +413,10 @@ export const useSettingsPage = () => {
         info(`Folder removal preference set to: ${value}`);
         setFolderRemovalPreference(value);
       } else {
-        error(`Failed to set folder removal preference: ${result.error}`);
+        error(`Failed to set folder removal preference: ${result.error.message}`);
         toast(t('general:error-title'), {
           description:
-            t('settings-page:error-save-preferences') + ': ' + result.error,
+            t('settings-page:error-save-preferences') + ': ' + result.error.message,
         });
       }
     } catch (e) {
@@ -435,10 +435,10 @@ export const useSettingsPage = () => {
         setUpdateChannel(value);
         info(`Update channel set to: ${value}`);
       } else {
-        error(`Failed to set update channel: ${result.error}`);
+        error(`Failed to set update channel: ${result.error.message}`);
         toast(t('general:error-title'), {
           description:
-            t('settings-page:error-save-preferences') + ': ' + result.error,
+            t('settings-page:error-save-preferences') + ': ' + result.error.message,
         });
       }
     } catch (e) {
@@ -457,10 +457,10 @@ export const useSettingsPage = () => {
         setDefaultInstanceType(value);
         info(`Default instance type set to: ${value}`);
       } else {
-        error(`Failed to set default instance type: ${result.error}`);
+        error(`Failed to set default instance type: ${result.error.message}`);
         toast(t('general:error-title'), {
           description:
-            t('settings-page:error-save-preferences') + ': ' + result.error,
+            t('settings-page:error-save-preferences') + ': ' + result.error.message,
         });
       }
     } catch (e) {
@@ -493,9 +493,9 @@ export const useSettingsPage = () => {
       const result = await commands.exportNativeData(path);
 
       if (result.status === 'error') {
-        error(`Native export failed: ${result.error}`);
+        error(`Native export failed: ${result.error.message}`);
         toast(t('general:error-title'), {
-          description: `Export failed: ${result.error}`,
+          description: `Export failed: ${result.error.message}`,
         });
         return;
       }
@@ -528,9 +528,9 @@ export const useSettingsPage = () => {
         setVisibleButtons(newVisibleButtons);
         info(`Visible buttons set: ${key}=${value}`);
       } else {
-        error(`Failed to set visible buttons: ${result.error}`);
+        error(`Failed to set visible buttons: ${result.error.message}`);
         toast(t('general:error-title'), {
-          description: t('settings-page:error-save-preferences') + ': ' + result.error,
+          description: t('settings-page:error-save-preferences') + ': ' + result.error.message,
         });
       }
     } catch (e) {
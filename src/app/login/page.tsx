@@ -40,19 +40,19 @@ export default function Login() {
       const result = await commands.loginWithCredentials(username, password);
 
       if (result.status === 'error') {
-        if (result.error == '2fa-required') {
+        if (result.error.message == '2fa-required') {
           info('2FA required, showing 2FA dialog');
           setShow2FA(true);
           setE(null);
           setTwoFactorCodeType('totp');
-        } else if (result.error == 'email-2fa-required') {
+        } else if (result.error.message == 'email-2fa-required') {
           info('Email 2FA required, showing 2FA dialog');
           setShow2FA(true);
           setE(null);
           setTwoFactorCodeType('emailOtp');
         } else {
           const errorMessage =
-            result.error || t('login-page:error-invalid-credentials');
+            result.error.message || t('login-page:error-invalid-credentials');
           error(`Login failed: ${errorMessage}`);
           setE(errorMessage);
         }
@@ -76,7 +76,7 @@ export default function Login() {
       );
 
       if (result.status === 'error') {
-        const errorMessage = result.error || t('login-page:error-invalid-2fa');
+        const errorMessage = result.error.message || t('login-page:error-invalid-2fa');
         error(`2FA verification failed: ${errorMessage}`);
         setE(errorMessage);
         return;
@@ -21,9 +21,9 @@ export default function Home() {
           const result = await commands.checkFilesLoaded();
 
           if (result.status === 'error') {
-            error(`Error loading files: ${result.error}`);
+            error(`Error loading files: ${result.error.message}`);
             router.push(
-              `${'/error/read_data_error'}?${encodeURIComponent(result.error)}`,
+              `${'/error/read_data_error'}?${encodeURIComponent(result.error.message)}`,
             );
             return;
           }
@@ -82,7 +82,7 @@ const WelcomePage: React.FC = () => {
     );
     if (result.status === 'error') {
       toast(t('general:error-title'), {
-        description: t('setup-page:toast:error:migrate:message', result.error),
+        description: t('setup-page:toast:error:migrate:message', result.error.message),
       });
       setPage(2);
       return;
@@ -100,7 +100,7 @@ const WelcomePage: React.FC = () => {
         if (hasDataResult.status === 'ok') {
           setHasExistingData(hasDataResult.data);
         } else {
-          error(`Failed to fetch existing data: ${hasDataResult.error}`);
+          error(`Failed to fetch existing data: ${hasDataResult.error.message}`);
         }
 
         const [worldsPath, foldersPath] = await invoke<[string, string]>(
@@ -232,7 +232,7 @@ const WelcomePage: React.FC = () => {
           if (result.status === 'ok') {
             setMigrationMeta(result.data);
           } else {
-            setMigrationMetaError(result.error);
+            setMigrationMetaError(result.error.message);
           }
         } catch (e: any) {
           setMigrationMetaError(e?.message || 'Unknown error');
@@ -4,7 +4,7 @@ export const checkForUpdate = async (): Promise<boolean> => {
   const result = await commands.checkForUpdate();
 
   if (result.status === 'error') {
-    console.error(result.error);
+    console.error(result.error.message);
     return false;
   }
 
@@ -19,7 +19,7 @@ export const downloadUpdate = async (setTaskId: (taskId: string) => void) => {
     return;
   }
 
-  console.error(result.error);
+  console.error(result.error.message);
 };
 
 export const dismissUpdate = async () => {
@@ -50,7 +50,7 @@ export const LocalizationContextProvider: FC<Props> = ({ children }) => {
       if (result.status === 'ok') {
         setLanguage(result.data);
       } else {
-        console.error('Failed to get language:', result.error);
+        console.error('Failed to get language:', result.error.message);
       }
     });
   }, []);
@@ -130,7 +130,7 @@ export default function MultiFilterItemSelector({
         if (result.status === 'ok') {
           setStarredItems(result.data);
         } else {
-          console.error('Failed to fetch starred items:', result.error);
+          console.error('Failed to fetch starred items:', result.error.message);
         }
       } catch (error) {
         console.error('Error fetching starred items:', error);
@@ -31,9 +31,9 @@ export const useUpdateDialog = ({
 
   const onCancelButtonClick = useCallback(async () => {
     if (taskId !== null) {
-      const result = await commands.cancelTaskRequest(taskId);
+      const result = await commands.cancelTask(taskId);
       if (result.status === 'error') {
-        console.error('Failed to cancel task:', result.error);
+        console.error('Failed to cancel task:', result.error.message);
       }
     }
 
@@ -45,7 +45,7 @@ export const useUpdateDialog = ({
     const result = await commands.installUpdate();
 
     if (result.status === 'error') {
-      console.error('Failed to install update:', result.error);
+      console.error('Failed to install update:', result.error.message);
     }
   };
 
@@ -53,7 +53,7 @@ export const useUpdateDialog = ({
     const result = await commands.getChangelog();
 
     if (result.status === 'error') {
-      console.error('Failed to get localized changes:', result.error);
+      console.error('Failed to get localized changes:', result.error.message);
       setLocalizedChanges([]);
       return;
     }
@@ -100,7 +100,7 @@ export const useUpdateDialog = ({
                   description: result.data,
                 });
               } else {
-                console.error('Failed to get task error:', result.error);
+                console.error('Failed to get task error:', result.error.message);
               }
             });
           }
@@ -118,7 +118,7 @@ export const useUpdateDialog = ({
         const result = await commands.getTaskStatus(taskId);
 
         if (result.status === 'error') {
-          console.error('Failed to get task status:', result.error);
+          console.error('Failed to get task status:', result.error.message);
           return;
         }
 
@@ -134,7 +134,7 @@ export const useUpdateDialog = ({
               description: result.data,
             });
           } else {
-            console.error('Failed to get task error:', result.error);
+            console.error('Failed to get task error:', result.error.message);
           }
         }
       } catch (error) {
@@ -6,7 +6,7 @@
 
 
 export const commands = {
-async fetchPatreonData() : Promise<Result<PatreonData, string>> {
+async fetchPatreonData() : Promise<Result<PatreonData, CommandError>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("fetch_patreon_data") };
 } catch (e) {
@@ -14,7 +14,7 @@ async fetchPatreonData() : Promise<Result<PatreonData, string>> {
     else return { status: "error", error: e  as any };
 }
 },
-async fetchPatreonVrchatNames() : Promise<Result<PatreonVRChatNames, string>> {
+async fetchPatreonVrchatNames() : Promise<Result<PatreonVRChatNames, CommandError>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("fetch_patreon_vrchat_names") };
 } catch (e) {
@@ -22,7 +22,7 @@ async fetchPatreonVrchatNames() : Promise<Result<PatreonVRChatNames, string>> {
     else return { status: "error", error: e  as any };
 }
 },
-async fetchBlacklist() : Promise<Result<WorldBlacklist, string>> {
+async fetchBlacklist() : Promise<Result<WorldBlacklist, CommandError>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("fetch_blacklist") };
 } catch (e) {
@@ -30,7 +30,7 @@ async fetchBlacklist() : Promise<Result<WorldBlacklist, string>> {
     else return { status: "error", error: e  as any };
 }
 },
-async getChangelog() : Promise<Result<LocalizedChanges[], string>> {
+async getChangelog() : Promise<Result<LocalizedChanges[], CommandError>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("get_changelog") };
 } catch (e) {
@@ -38,7 +38,33 @@ async getChangelog() : Promise<Result<LocalizedChanges[], string>> {
     else return { status: "error", error: e  as any };
 }
 },
-async getTaskStatus(id: string) : Promise<Result<TaskStatus, string>> {
+/**
+ * Gets the release notes of the versions after `version` up to the running one, newest first
+ * Without a version, the notes since the version last marked as seen are returned,
+ * or only those of the running version if none was marked yet
+ * Newer notes are fetched when possible, the ones the app was built with are used otherwise
+ */
+async getChangelogSince(version: string | null) : Promise<Result<LocalizedChanges[], CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_changelog_since", { version }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Records that the user has seen the release notes up to `version`
+ * An older version than the one already recorded is ignored
+ */
+async markChangelogSeen(version: string) : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("mark_changelog_seen", { version }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getTaskStatus(id: string) : Promise<Result<TaskStatus, CommandError>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("get_task_status", { id }) };
 } catch (e) {
@@ -46,750 +72,2552 @@ async getTaskStatus(id: string) : Promise<Result<TaskStatus, string>> {
     else return { status: "error", error: e  as any };
 }
 },
-async cancelTaskRequest(id: string) : Promise<Result<TaskStatus, string>> {
+/**
+ * Lists the background tasks which are still running, for the task panel
+ */
+async listActiveTasks() : Promise<Result<TaskInfo[], CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("cancel_task_request", { id }) };
+    return { status: "ok", data: await TAURI_INVOKE("list_active_tasks") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getTaskError(id: string) : Promise<Result<string | null, string>> {
+async cancelTask(id: string) : Promise<Result<TaskStatus, CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_task_error", { id }) };
+    return { status: "ok", data: await TAURI_INVOKE("cancel_task", { id }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async checkForUpdate() : Promise<Result<boolean, string>> {
+async getTaskError(id: string) : Promise<Result<string | null, CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("check_for_update") };
+    return { status: "ok", data: await TAURI_INVOKE("get_task_error", { id }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async downloadUpdate() : Promise<Result<string, string>> {
+async getTaskProgress(id: string) : Promise<Result<TaskProgress | null, CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("download_update") };
+    return { status: "ok", data: await TAURI_INVOKE("get_task_progress", { id }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async installUpdate() : Promise<Result<null, string>> {
+async checkForUpdate() : Promise<Result<boolean, CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("install_update") };
+    return { status: "ok", data: await TAURI_INVOKE("check_for_update") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async doNotNotifyUpdate() : Promise<Result<boolean, string>> {
+async downloadUpdate() : Promise<Result<string, CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("do_not_notify_update") };
+    return { status: "ok", data: await TAURI_INVOKE("download_update") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async addWorldToFolder(folderName: string, worldId: string) : Promise<Result<null, string>> {
+async installUpdate() : Promise<Result<null, CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("add_world_to_folder", { folderName, worldId }) };
+    return { status: "ok", data: await TAURI_INVOKE("install_update") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async addWorldsToFolder(folderName: string, worldIds: string[]) : Promise<Result<null, string>> {
+async doNotNotifyUpdate() : Promise<Result<boolean, CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("add_worlds_to_folder", { folderName, worldIds }) };
+    return { status: "ok", data: await TAURI_INVOKE("do_not_notify_update") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async removeWorldFromFolder(folderName: string, worldId: string) : Promise<Result<null, string>> {
+/**
+ * Lists the recent releases of a channel, the channel in preferences if not given
+ */
+async listReleases(channel: UpdateChannel | null) : Promise<Result<ReleaseInfo[], CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("remove_world_from_folder", { folderName, worldId }) };
+    return { status: "ok", data: await TAURI_INVOKE("list_releases", { channel }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async hideWorld(worldId: string) : Promise<Result<null, string>> {
+async getUpdatePolicy() : Promise<Result<UpdatePolicy, CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("hide_world", { worldId }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_update_policy") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async unhideWorld(worldId: string) : Promise<Result<null, string>> {
+/**
+ * Skips a version so it is not offered, or offers it again
+ */
+async skipUpdateVersion(version: string, skipped: boolean) : Promise<Result<UpdatePolicy, CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("unhide_world", { worldId }) };
+    return { status: "ok", data: await TAURI_INVOKE("skip_update_version", { version, skipped }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getFolders() : Promise<Result<FolderData[], string>> {
+/**
+ * Pins the app to the installed version, so no update is offered until unpinned
+ */
+async pinCurrentVersion(pinned: boolean) : Promise<Result<UpdatePolicy, CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_folders") };
+    return { status: "ok", data: await TAURI_INVOKE("pin_current_version", { pinned }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async createFolder(name: string) : Promise<Result<string, string>> {
+async addWorldToFolder(folderName: string, worldId: string) : Promise<Result<null, CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("create_folder", { name }) };
+    return { status: "ok", data: await TAURI_INVOKE("add_world_to_folder", { folderName, worldId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async deleteFolder(name: string) : Promise<Result<null, string>> {
+async addWorldsToFolder(folderName: string, worldIds: string[]) : Promise<Result<null, CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("delete_folder", { name }) };
+    return { status: "ok", data: await TAURI_INVOKE("add_worlds_to_folder", { folderName, worldIds }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async moveFolder(folderName: string, newIndex: number) : Promise<Result<null, string>> {
+/**
+ * Adds every world matching the filter to a folder, without the frontend sending their IDs
+ * 
+ * # Returns
+ * The number of worlds added, worlds already in the folder are not counted
+ */
+async addFilteredWorldsToFolder(filter: WorldFilter, folderName: string) : Promise<Result<number, CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("move_folder", { folderName, newIndex }) };
+    return { status: "ok", data: await TAURI_INVOKE("add_filtered_worlds_to_folder", { filter, folderName }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async renameFolder(oldName: string, newName: string) : Promise<Result<null, string>> {
+async removeWorldFromFolder(folderName: string, worldId: string) : Promise<Result<null, CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("rename_folder", { oldName, newName }) };
+    return { status: "ok", data: await TAURI_INVOKE("remove_world_from_folder", { folderName, worldId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async setFolderColor(folderName: string, color: string | null) : Promise<Result<null, string>> {
+/**
+ * Moves a world to another folder in one step, instead of adding and removing it separately
+ * Whether it stays in `from` follows the folder removal preference, `remove` is the answer
+ * of the user when the preference is to ask
+ */
+async moveWorldBetweenFolders(worldId: string, from: string, to: string, remove: boolean | null) : Promise<Result<FolderMembershipChange, CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("set_folder_color", { folderName, color }) };
+    return { status: "ok", data: await TAURI_INVOKE("move_world_between_folders", { worldId, from, to, remove }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getWorlds(folderName: string) : Promise<Result<WorldDisplayData[], string>> {
+/**
+ * Replaces the folders a world is in, e.g. from a folder picker with checkboxes
+ */
+async setWorldFolders(worldId: string, folderNames: string[]) : Promise<Result<FolderMembershipChange, CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_worlds", { folderName }) };
+    return { status: "ok", data: await TAURI_INVOKE("set_world_folders", { worldId, folderNames }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getAllWorlds() : Promise<Result<WorldDisplayData[], string>> {
+async hideWorld(worldId: string) : Promise<Result<null, CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_all_worlds") };
+    return { status: "ok", data: await TAURI_INVOKE("hide_world", { worldId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getUnclassifiedWorlds() : Promise<Result<WorldDisplayData[], string>> {
+/**
+ * Hides every world matching the filter, removing them from their folders
+ * 
+ * # Returns
+ * The number of worlds hidden
+ */
+async hideFilteredWorlds(filter: WorldFilter) : Promise<Result<number, CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_unclassified_worlds") };
+    return { status: "ok", data: await TAURI_INVOKE("hide_filtered_worlds", { filter }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getHiddenWorlds() : Promise<Result<WorldDisplayData[], string>> {
+async unhideWorld(worldId: string) : Promise<Result<null, CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_hidden_worlds") };
+    return { status: "ok", data: await TAURI_INVOKE("unhide_world", { worldId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getTagsByCount() : Promise<Result<string[], string>> {
+/**
+ * Archived folders are only listed when `include_archived` is set
+ */
+async getFolders(includeArchived: boolean | null) : Promise<Result<FolderData[], CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_tags_by_count") };
+    return { status: "ok", data: await TAURI_INVOKE("get_folders", { includeArchived }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getAuthorsByCount() : Promise<Result<string[], string>> {
+async archiveFolder(folderName: string) : Promise<Result<null, CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_authors_by_count") };
+    return { status: "ok", data: await TAURI_INVOKE("archive_folder", { folderName }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async deleteWorld(worldId: string) : Promise<Result<null, string>> {
+async unarchiveFolder(folderName: string) : Promise<Result<null, CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("delete_world", { worldId }) };
+    return { status: "ok", data: await TAURI_INVOKE("unarchive_folder", { folderName }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getFoldersForWorld(worldId: string) : Promise<Result<string[], string>> {
+async createFolder(name: string) : Promise<Result<string, CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_folders_for_world", { worldId }) };
+    return { status: "ok", data: await TAURI_INVOKE("create_folder", { name }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async shareFolder(folderName: string) : Promise<Result<string, string>> {
+async deleteFolder(name: string) : Promise<Result<null, CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("share_folder", { folderName }) };
+    return { status: "ok", data: await TAURI_INVOKE("delete_folder", { name }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async updateFolderShare(folderName: string) : Promise<Result<string | null, string>> {
+async moveFolder(folderName: string, newIndex: number) : Promise<Result<null, CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("update_folder_share", { folderName }) };
+    return { status: "ok", data: await TAURI_INVOKE("move_folder", { folderName, newIndex }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * Downloads a shared folder and adds its worlds to the local database.
- * 
- * This function attempts to download a folder using the provided `share_id`, creates the folder locally,
- * adds the worlds from the shared folder to the local world list, and then adds all non-hidden worlds to the new folder.
- * Worlds that are already hidden are not added to the folder and are returned for further handling.
- * 
- * # Arguments
- * 
- * * `share_id` - The identifier of the shared folder to download.
+ * Renames a folder, `on_collision` decides what happens if another folder already has the
+ * new name and defaults to rejecting the rename
  * 
  * # Returns
- * 
- * `Ok((String, Vec<String>))`: A tuple containing the new folder name and a vector of world IDs that were hidden and not added to the folder.
- * 
- * # Errors
- * Returns an error string if any operation fails, such as downloading the folder, creating the folder, adding worlds, or retrieving hidden worlds.
+ * The name the folder has now
  */
-async downloadFolder(shareId: string) : Promise<Result<[string, WorldDisplayData[]], string>> {
+async renameFolder(oldName: string, newName: string, onCollision: FolderNameCollision | null) : Promise<Result<string, CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("download_folder", { shareId }) };
+    return { status: "ok", data: await TAURI_INVOKE("rename_folder", { oldName, newName, onCollision }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getTheme() : Promise<Result<string, string>> {
+/**
+ * Opens a folder in a window of its own, e.g. to compare two folders side by side
+ * The window's label carries the folder name, see `folder_window::label_for`
+ */
+async openFolderWindow(folderName: string) : Promise<Result<null, CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_theme") };
+    return { status: "ok", data: await TAURI_INVOKE("open_folder_window", { folderName }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async setTheme(theme: string) : Promise<Result<null, string>> {
+async setFolderColor(folderName: string, color: string | null) : Promise<Result<null, CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("set_theme", { theme }) };
+    return { status: "ok", data: await TAURI_INVOKE("set_folder_color", { folderName, color }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getLanguage() : Promise<Result<string, string>> {
+/**
+ * Sets the emoji or icon identifier shown next to a folder, None or "" resets it
+ */
+async setFolderIcon(folderName: string, icon: string | null) : Promise<Result<null, CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_language") };
+    return { status: "ok", data: await TAURI_INVOKE("set_folder_icon", { folderName, icon }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async setLanguage(language: string) : Promise<Result<null, string>> {
+/**
+ * Sets the description of a folder, None or "" resets it
+ */
+async setFolderDescription(folderName: string, description: string | null) : Promise<Result<null, CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("set_language", { language }) };
+    return { status: "ok", data: await TAURI_INVOKE("set_folder_description", { folderName, description }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getCardSize() : Promise<Result<CardSize, string>> {
+/**
+ * Sets the sort order of a folder, overriding the global one
+ * A `field` of None makes the folder follow the global sort order again
+ */
+async setFolderSort(folderName: string, field: string | null, direction: string) : Promise<Result<null, CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_card_size") };
+    return { status: "ok", data: await TAURI_INVOKE("set_folder_sort", { folderName, field, direction }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async setCardSize(cardSize: CardSize) : Promise<Result<null, string>> {
+async getWorlds(folderName: string) : Promise<Result<WorldDisplayData[], CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("set_card_size", { cardSize }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_worlds", { folderName }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getRegion() : Promise<Result<InstanceRegion, string>> {
+/**
+ * Gets the worlds of a folder or special folder grouped into sections for the grid
+ * 
+ * # Arguments
+ * * `source` - "All Worlds", "Unclassified Worlds", "Hidden Worlds" or a folder name
+ * * `group_by` - What to group the worlds by
+ */
+async getWorldsGrouped(source: string, groupBy: WorldGroupBy) : Promise<Result<WorldGroup[], CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_region") };
+    return { status: "ok", data: await TAURI_INVOKE("get_worlds_grouped", { source, groupBy }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async setRegion(region: InstanceRegion) : Promise<Result<null, string>> {
+async getAllWorlds() : Promise<Result<WorldDisplayData[], CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("set_region", { region }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_all_worlds") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getStarredFilterItems(id: FilterItemSelectorStarredType) : Promise<Result<string[], string>> {
+/**
+ * Gets summaries of the worlds of a folder or special folder, for the grids of large folders
+ * The full data of the cards shown is fetched with `get_worlds_by_id`
+ * 
+ * # Arguments
+ * * `source` - "All Worlds", "Unclassified Worlds", "Hidden Worlds" or a folder name
+ */
+async getWorldSummaries(source: string) : Promise<Result<WorldSummary[], CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_starred_filter_items", { id }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_world_summaries", { source }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async setStarredFilterItems(id: FilterItemSelectorStarredType, values: string[]) : Promise<Result<null, string>> {
+/**
+ * Gets the display data of worlds by ID, in the order given
+ */
+async getWorldsById(worldIds: string[]) : Promise<Result<WorldDisplayData[], CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("set_starred_filter_items", { id, values }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_worlds_by_id", { worldIds }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getFolderRemovalPreference() : Promise<Result<FolderRemovalPreference, string>> {
+/**
+ * Gets the worlds changed after `timestamp`, for updating the shown worlds in place
+ * If the result is not complete, every world should be fetched again
+ */
+async getWorldsChangedSince(timestamp: string) : Promise<Result<WorldChanges, CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_folder_removal_preference") };
+    return { status: "ok", data: await TAURI_INVOKE("get_worlds_changed_since", { timestamp }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async setFolderRemovalPreference(dontShowRemoveFromFolder: FolderRemovalPreference) : Promise<Result<null, string>> {
+async getUnclassifiedWorlds() : Promise<Result<WorldDisplayData[], CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("set_folder_removal_preference", { dontShowRemoveFromFolder }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_unclassified_worlds") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getUpdateChannel() : Promise<Result<UpdateChannel, string>> {
+async getHiddenWorlds() : Promise<Result<WorldDisplayData[], CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_update_channel") };
+    return { status: "ok", data: await TAURI_INVOKE("get_hidden_worlds") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async setUpdateChannel(channel: UpdateChannel) : Promise<Result<null, string>> {
+/**
+ * Gets the statistics of a folder for its header info panel
+ */
+async getFolderStats(folderName: string) : Promise<Result<FolderStats, CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("set_update_channel", { channel }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_folder_stats", { folderName }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getSortPreferences() : Promise<Result<[string, string], string>> {
+async getTagsByCount() : Promise<Result<string[], CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_sort_preferences") };
+    return { status: "ok", data: await TAURI_INVOKE("get_tags_by_count") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async setSortPreferences(sortField: string, sortDirection: string) : Promise<Result<null, string>> {
+async getTagRules() : Promise<TagRules> {
+    return await TAURI_INVOKE("get_tag_rules");
+},
+/**
+ * Shows an author tag as another one, e.g. "chill_" as "chill"
+ * Only changes how tags are shown and filtered, not the worlds' VRChat data
+ */
+async aliasTag(from: string, to: string) : Promise<Result<TagRules, CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("set_sort_preferences", { sortField, sortDirection }) };
+    return { status: "ok", data: await TAURI_INVOKE("alias_tag", { from, to }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getDefaultInstanceType() : Promise<Result<DefaultInstanceType, string>> {
+async removeTagAlias(tag: string) : Promise<Result<TagRules, CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_default_instance_type") };
+    return { status: "ok", data: await TAURI_INVOKE("remove_tag_alias", { tag }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async setDefaultInstanceType(instanceType: DefaultInstanceType) : Promise<Result<null, string>> {
+async hideTag(tag: string) : Promise<Result<TagRules, CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("set_default_instance_type", { instanceType }) };
+    return { status: "ok", data: await TAURI_INVOKE("hide_tag", { tag }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getVisibleButtons() : Promise<Result<VisibleButtons, string>> {
+async unhideTag(tag: string) : Promise<Result<TagRules, CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_visible_buttons") };
+    return { status: "ok", data: await TAURI_INVOKE("unhide_tag", { tag }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async setVisibleButtons(visibleButtons: VisibleButtons) : Promise<Result<null, string>> {
+async getAuthorsByCount() : Promise<Result<string[], CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("set_visible_buttons", { visibleButtons }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_authors_by_count") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async tryLogin() : Promise<Result<null, string>> {
+async deleteWorld(worldId: string) : Promise<Result<null, CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("try_login") };
+    return { status: "ok", data: await TAURI_INVOKE("delete_world", { worldId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async loginWithCredentials(username: string, password: string) : Promise<Result<null, string>> {
+async getFoldersForWorld(worldId: string) : Promise<Result<string[], CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("login_with_credentials", { username, password }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_folders_for_world", { worldId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async loginWith2fa(code: string, twoFactorType: string) : Promise<Result<null, string>> {
+async shareFolder(folderName: string) : Promise<Result<string, CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("login_with_2fa", { code, twoFactorType }) };
+    return { status: "ok", data: await TAURI_INVOKE("share_folder", { folderName }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async logout() : Promise<Result<null, string>> {
+async updateFolderShare(folderName: string) : Promise<Result<string | null, CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("logout") };
+    return { status: "ok", data: await TAURI_INVOKE("update_folder_share", { folderName }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getFavoriteWorlds() : Promise<Result<null, string>> {
+/**
+ * Downloads a shared folder and adds its worlds to the local database.
+ * 
+ * This function attempts to download a folder using the provided `share_id`, creates the folder locally,
+ * adds the worlds from the shared folder to the local world list, and then adds all non-hidden worlds to the new folder.
+ * Blacklisted worlds are dropped entirely.
+ * Worlds that are already hidden are not added to the folder and are returned for further handling.
+ * 
+ * # Arguments
+ * 
+ * * `share_id` - The identifier of the shared folder to download.
+ * 
+ * # Returns
+ * 
+ * `Ok((String, Vec<String>))`: A tuple containing the new folder name and a vector of world IDs that were hidden and not added to the folder.
+ * 
+ * # Errors
+ * Returns a `CommandError` if any operation fails, such as downloading the folder, creating the folder, adding worlds, or retrieving hidden worlds.
+ */
+async downloadFolder(shareId: string) : Promise<Result<[string, WorldDisplayData[]], CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_favorite_worlds") };
+    return { status: "ok", data: await TAURI_INVOKE("download_folder", { shareId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getWorld(worldId: string, dontSaveToLocal: boolean | null) : Promise<Result<WorldDetails, string>> {
+/**
+ * Moves a world within a folder, setting the order used when playing the folder as a playlist
+ */
+async moveWorldInFolder(folderName: string, worldId: string, newIndex: number) : Promise<Result<null, CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_world", { worldId, dontSaveToLocal }) };
+    return { status: "ok", data: await TAURI_INVOKE("move_world_in_folder", { folderName, worldId, newIndex }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async checkWorldInfo(worldId: string) : Promise<Result<WorldDetails, string>> {
+/**
+ * Refreshes the stale worlds of a folder from the API as a cancellable task
+ * Returns the task ID immediately; the number of updated and unavailable worlds is reported
+ * through `TaskStatusChanged` and can be read with `get_task_progress` once completed
+ */
+async refreshFolderWorlds(folderName: string) : Promise<Result<string, CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("check_world_info", { worldId }) };
+    return { status: "ok", data: await TAURI_INVOKE("refresh_folder_worlds", { folderName }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getRecentlyVisitedWorlds() : Promise<Result<WorldDisplayData[], string>> {
+/**
+ * Downloads the thumbnails of a folder's worlds ahead of time as a cancellable task, e.g.
+ * before presenting the folder at an event, so no image has to stream in on stage
+ * Returns the task ID immediately; progress is reported through `TaskStatusChanged`
+ */
+async prefetchFolderThumbnails(folderName: string) : Promise<Result<string, CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_recently_visited_worlds") };
+    return { status: "ok", data: await TAURI_INVOKE("prefetch_folder_thumbnails", { folderName }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async searchWorlds(sort: string, tags: string[], excludeTags: string[], search: string, page: number) : Promise<Result<WorldDisplayData[], string>> {
+/**
+ * Gets the path of a world's downloaded thumbnail, None if it is not cached
+ */
+async getCachedThumbnail(worldId: string) : Promise<string | null> {
+    return await TAURI_INVOKE("get_cached_thumbnail", { worldId });
+},
+async addWorldToBlacklist(worldId: string) : Promise<Result<null, CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("search_worlds", { sort, tags, excludeTags, search, page }) };
+    return { status: "ok", data: await TAURI_INVOKE("add_world_to_blacklist", { worldId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async createWorldInstance(worldId: string, instanceTypeStr: string, regionStr: string) : Promise<Result<InstanceInfo, string>> {
+/**
+ * Blacklists every world matching the filter
+ * 
+ * # Returns
+ * The number of worlds added, worlds already blacklisted are not counted
+ */
+async blacklistFilteredWorlds(filter: WorldFilter) : Promise<Result<number, CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("create_world_instance", { worldId, instanceTypeStr, regionStr }) };
+    return { status: "ok", data: await TAURI_INVOKE("blacklist_filtered_worlds", { filter }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getUserGroups() : Promise<Result<UserGroup[], string>> {
+async removeWorldFromBlacklist(worldId: string) : Promise<Result<null, CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_user_groups") };
+    return { status: "ok", data: await TAURI_INVOKE("remove_world_from_blacklist", { worldId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getPermissionForCreateGroupInstance(groupId: string) : Promise<Result<GroupInstancePermissionInfo, string>> {
+async getBlacklist() : Promise<Result<string[], CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_permission_for_create_group_instance", { groupId }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_blacklist") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async createGroupInstance(worldId: string, groupId: string, instanceTypeStr: string, allowedRoles: string[] | null, regionStr: string, queueEnabled: boolean) : Promise<Result<InstanceInfo, string>> {
+async addAuthorToBlacklist(authorId: string) : Promise<Result<null, CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("create_group_instance", { worldId, groupId, instanceTypeStr, allowedRoles, regionStr, queueEnabled }) };
+    return { status: "ok", data: await TAURI_INVOKE("add_author_to_blacklist", { authorId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async openInstanceInClient(worldId: string, instanceId: string) : Promise<Result<string, string>> {
+async removeAuthorFromBlacklist(authorId: string) : Promise<Result<null, CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("open_instance_in_client", { worldId, instanceId }) };
+    return { status: "ok", data: await TAURI_INVOKE("remove_author_from_blacklist", { authorId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async openLogsDirectory() : Promise<Result<null, string>> {
+async getAuthorBlacklist() : Promise<Result<string[], CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("open_logs_directory") };
+    return { status: "ok", data: await TAURI_INVOKE("get_author_blacklist") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async openFolderDirectory() : Promise<Result<null, string>> {
+async followAuthor(authorId: string, authorName: string) : Promise<Result<null, CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("open_folder_directory") };
+    return { status: "ok", data: await TAURI_INVOKE("follow_author", { authorId, authorName }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-/**
- * Checks if the app is being run for the first time
- * As this is called every time / is loaded from the frontend, cache result in the state
- */
-async requireInitialSetup() : Promise<boolean> {
-    return await TAURI_INVOKE("require_initial_setup");
-},
-/**
- * Checks if files have been loaded from disk successfully
- * 
- * # Returns
- * Returns a boolean indicating if the files have been loaded successfully
- * 
- * # Errors
- * Returns a tuple containing a boolean indicating if the files have been loaded, and an error message
- */
-async checkFilesLoaded() : Promise<Result<boolean, string>> {
+async unfollowAuthor(authorId: string) : Promise<Result<null, CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("check_files_loaded") };
+    return { status: "ok", data: await TAURI_INVOKE("unfollow_author", { authorId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async detectOldInstallation() : Promise<Result<[string, string], string>> {
+async getFollowedAuthors() : Promise<Result<FollowedAuthor[], CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("detect_old_installation") };
+    return { status: "ok", data: await TAURI_INVOKE("get_followed_authors") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * Passes the paths to the frontend
- * Gets the path to the local app data directory
- * 
- * # Returns
- * Returns the path to the local app data directory
- * 
- * # Errors
- * Returns an error message if the path to the local app data directory could not be found
+ * Checks followed authors for new worlds, and adds a notification for each one
  */
-async passPaths() : Promise<Result<string, string>> {
+async checkFollowedAuthorsNewWorlds() : Promise<Result<WorldDisplayData[], CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("pass_paths") };
+    return { status: "ok", data: await TAURI_INVOKE("check_followed_authors_new_worlds") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async checkExistingData() : Promise<Result<[boolean, boolean], string>> {
+/**
+ * Fetches a page of the worlds published by an author, for "more by this author"
+ * Blacklisted worlds are left out; use `get_world` to add a result to the library
+ */
+async getWorldsByAuthor(authorId: string, page: number) : Promise<Result<WorldDisplayData[], CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("check_existing_data") };
+    return { status: "ok", data: await TAURI_INVOKE("get_worlds_by_author", { authorId, page }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getBackupMetadata(backupPath: string) : Promise<Result<BackupMetaData, string>> {
+async getDiscoveryFeed(feed: DiscoveryFeed) : Promise<Result<WorldDisplayData[], CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_backup_metadata", { backupPath }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_discovery_feed", { feed }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getMigrationMetadata(worldsPath: string, foldersPath: string) : Promise<Result<PreviousMetadata, string>> {
+async resetDiscoveryFeed(feed: DiscoveryFeed) : Promise<Result<null, CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_migration_metadata", { worldsPath, foldersPath }) };
+    return { status: "ok", data: await TAURI_INVOKE("reset_discovery_feed", { feed }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async createEmptyAuth() : Promise<Result<null, string>> {
+/**
+ * Gets the worlds most like a world of the library, for the detail pane
+ * With `include_remote`, worlds found on VRChat by the world's tags are suggested too,
+ * which needs the user to be logged in
+ */
+async getSimilarWorlds(worldId: string, limit: number, includeRemote: boolean) : Promise<Result<SimilarWorld[], CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("create_empty_auth") };
+    return { status: "ok", data: await TAURI_INVOKE("get_similar_worlds", { worldId, limit, includeRemote }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async createEmptyFiles() : Promise<Result<null, string>> {
+/**
+ * Gets worlds matching what the user launches, favorites and rates highly,
+ * each with the reasons it was recommended
+ * With `include_remote`, worlds not in the library yet are recommended too,
+ * which needs the user to be logged in
+ */
+async getRecommendations(limit: number, includeRemote: boolean) : Promise<Result<Recommendation[], CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("create_empty_files") };
+    return { status: "ok", data: await TAURI_INVOKE("get_recommendations", { limit, includeRemote }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async createBackup(backupPath: string) : Promise<Result<null, string>> {
+async getInboxWorlds() : Promise<Result<WorldDisplayData[], CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("create_backup", { backupPath }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_inbox_worlds") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async restoreFromBackup(backupPath: string) : Promise<Result<null, string>> {
+/**
+ * Takes a world out of the inbox, keeping it (optionally in a folder), hiding it,
+ * or blacklisting and deleting it
+ */
+async triageWorld(worldId: string, action: TriageAction) : Promise<Result<null, CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("restore_from_backup", { backupPath }) };
+    return { status: "ok", data: await TAURI_INVOKE("triage_world", { worldId, action }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async exportToPortalLibrarySystem(folders: string[], sortField: string, sortDirection: string) : Promise<Result<null, string>> {
+async getNotifications() : Promise<Result<Notification[], CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("export_to_portal_library_system", { folders, sortField, sortDirection }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_notifications") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async migrateOldData(worldsPath: string, foldersPath: string) : Promise<Result<null, string>> {
+async getUnreadNotifications() : Promise<Result<Notification[], CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("migrate_old_data", { worldsPath, foldersPath }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_unread_notifications") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async deleteData() : Promise<Result<null, string>> {
+async markNotificationsRead(ids: string[]) : Promise<Result<null, CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("delete_data") };
+    return { status: "ok", data: await TAURI_INVOKE("mark_notifications_read", { ids }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async exportNativeData(path: string) : Promise<Result<null, string>> {
+async markAllNotificationsRead() : Promise<Result<null, CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("export_native_data", { path }) };
+    return { status: "ok", data: await TAURI_INVOKE("mark_all_notifications_read") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getMemo(worldId: string) : Promise<Result<string, string>> {
+async clearReadNotifications() : Promise<Result<null, CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_memo", { worldId }) };
+    return { status: "ok", data: await TAURI_INVOKE("clear_read_notifications") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async setMemoAndSave(worldId: string, memo: string) : Promise<Result<null, string>> {
+/**
+ * Reports folder shares which are about to expire
+ */
+async checkExpiringShares() : Promise<Result<null, CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("set_memo_and_save", { worldId, memo }) };
+    return { status: "ok", data: await TAURI_INVOKE("check_expiring_shares") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async searchMemoText(searchText: string) : Promise<Result<string[], string>> {
+async getWebhookSettings() : Promise<Result<WebhookSettings | null, CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("search_memo_text", { searchText }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_webhook_settings") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async setWorldPhotographed(worldId: string, isPhotographed: boolean) : Promise<Result<null, string>> {
+/**
+ * Sets the webhook notifications are posted to, or removes it when `settings` is None
+ */
+async setWebhookSettings(settings: WebhookSettings | null) : Promise<Result<null, CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("set_world_photographed", { worldId, isPhotographed }) };
+    return { status: "ok", data: await TAURI_INVOKE("set_webhook_settings", { settings }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async setWorldShared(worldId: string, isShared: boolean) : Promise<Result<null, string>> {
+/**
+ * Posts a sample notification to a webhook, so the user can check it before saving it
+ */
+async testWebhook(settings: WebhookSettings) : Promise<Result<null, CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("set_world_shared", { worldId, isShared }) };
+    return { status: "ok", data: await TAURI_INVOKE("test_webhook", { settings }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async setWorldFavorite(worldId: string, isFavorite: boolean) : Promise<Result<null, string>> {
+/**
+ * Gets every preference at once, instead of one get_* call per preference
+ */
+async getAllPreferences() : Promise<Result<AllPreferences, CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("set_world_favorite", { worldId, isFavorite }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_all_preferences") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async sortWorldsDisplay(worlds: WorldDisplayData[], sortField: string, sortDirection: string) : Promise<Result<WorldDisplayData[], string>> {
+async getTheme() : Promise<Result<string, CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("sort_worlds_display", { worlds, sortField, sortDirection }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_theme") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async resolveRedirects(url: string) : Promise<Result<string, string>> {
+async setTheme(theme: string) : Promise<Result<null, CommandError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("resolve_redirects", { url }) };
+    return { status: "ok", data: await TAURI_INVOKE("set_theme", { theme }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getStartupDeepLink() : Promise<string | null> {
-    return await TAURI_INVOKE("get_startup_deep_link");
-}
+async getLanguage() : Promise<Result<string, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_language") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async setLanguage(language: string) : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_language", { language }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getCardSize() : Promise<Result<CardSize, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_card_size") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async setCardSize(cardSize: CardSize) : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_card_size", { cardSize }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getRegion() : Promise<Result<InstanceRegion, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_region") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async setRegion(region: InstanceRegion) : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_region", { region }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getStarredFilterItems(id: FilterItemSelectorStarredType) : Promise<Result<string[], CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_starred_filter_items", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async setStarredFilterItems(id: FilterItemSelectorStarredType, values: string[]) : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_starred_filter_items", { id, values }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getFolderRemovalPreference() : Promise<Result<FolderRemovalPreference, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_folder_removal_preference") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async setFolderRemovalPreference(dontShowRemoveFromFolder: FolderRemovalPreference) : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_folder_removal_preference", { dontShowRemoveFromFolder }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getUpdateChannel() : Promise<Result<UpdateChannel, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_update_channel") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async setUpdateChannel(channel: UpdateChannel) : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_update_channel", { channel }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getSortPreferences() : Promise<Result<[string, string], CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_sort_preferences") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async setSortPreferences(sortField: string, sortDirection: string) : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_sort_preferences", { sortField, sortDirection }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getNaturalSort() : Promise<Result<boolean, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_natural_sort") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Enables or disables comparing numbers in names by value, so "World 2" sorts before "World 10"
+ */
+async setNaturalSort(enabled: boolean) : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_natural_sort", { enabled }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getKeepNonPublicFavorites() : Promise<Result<boolean, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_keep_non_public_favorites") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Sets whether importing favorites keeps the ones which are not public as placeholders
+ * named "???", instead of skipping them
+ */
+async setKeepNonPublicFavorites(enabled: boolean) : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_keep_non_public_favorites", { enabled }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getDefaultInstanceType() : Promise<Result<DefaultInstanceType, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_default_instance_type") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async setDefaultInstanceType(instanceType: DefaultInstanceType) : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_default_instance_type", { instanceType }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getDefaultSearchPlatform() : Promise<Result<SearchPlatform | null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_default_search_platform") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Sets the platform filter applied to searches which don't specify one
+ * None searches every platform
+ */
+async setDefaultSearchPlatform(platform: SearchPlatform | null) : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_default_search_platform", { platform }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getRecentlyVisitedImport() : Promise<Result<RecentlyVisitedImport, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_recently_visited_import") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Enables or disables the periodic import of recently visited worlds, and sets the folder cap
+ */
+async setRecentlyVisitedImport(settings: RecentlyVisitedImport) : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_recently_visited_import", { settings }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getClipboardWatch() : Promise<Result<ClipboardWatch, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_clipboard_watch") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Enables or disables watching the clipboard for VRChat world URLs, and sets what to do with them
+ */
+async setClipboardWatch(settings: ClipboardWatch) : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_clipboard_watch", { settings }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getTraySettings() : Promise<Result<TraySettings, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_tray_settings") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Sets whether the app starts minimized to the tray and whether closing the window hides it there
+ */
+async setTraySettings(settings: TraySettings) : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_tray_settings", { settings }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getPinnedWorld() : Promise<Result<string | null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_pinned_world") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Pins a saved world, which the quick launch shortcut creates an instance of
+ */
+async setPinnedWorld(worldId: string) : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_pinned_world", { worldId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async clearPinnedWorld() : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("clear_pinned_world") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getQuickLaunchShortcut() : Promise<Result<string, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_quick_launch_shortcut") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Changes the global shortcut that launches the pinned world, e.g. "CommandOrControl+Shift+L"
+ */
+async setQuickLaunchShortcut(shortcut: string) : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_quick_launch_shortcut", { shortcut }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getVisibleButtons() : Promise<Result<VisibleButtons, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_visible_buttons") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async setVisibleButtons(visibleButtons: VisibleButtons) : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_visible_buttons", { visibleButtons }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Exports the preferences to a settings profile in `target_dir`, for another machine
+ * The login, webhook URL and local API token are left out
+ * 
+ * # Returns
+ * The path of the written profile
+ */
+async exportSettingsProfile(targetDir: string) : Promise<Result<string, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("export_settings_profile", { targetDir }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Replaces the preferences with those of a settings profile
+ * Settings of background features such as the tray take effect after a restart
+ */
+async importSettingsProfile(path: string) : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("import_settings_profile", { path }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Gets the directory VRChat photos are scanned in, Pictures/VRChat unless changed
+ */
+async getPhotoDirectory() : Promise<Result<string | null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_photo_directory") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Changes the directory VRChat photos are scanned in, None for Pictures/VRChat
+ */
+async setPhotoDirectory(directory: string | null) : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_photo_directory", { directory }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getWorldsStorage() : Promise<Result<WorldsStorageFormat, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_worlds_storage") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Changes how worlds.json is stored, rewriting it right away
+ * A copy in the previous format is kept next to it
+ */
+async setWorldsStorage(format: WorldsStorageFormat) : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_worlds_storage", { format }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Gets how far the user is through logging in, for the UI to show the right screen
+ * A restored session counts as logged in even before its token was verified again
+ * The expected 2FA expiry lets the UI warn before VRChat asks for a code again
+ */
+async getAuthState() : Promise<Result<AuthState, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_auth_state") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async tryLogin() : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("try_login") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async loginWithCredentials(username: string, password: string) : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("login_with_credentials", { username, password }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async loginWith2fa(code: string, twoFactorType: string) : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("login_with_2fa", { code, twoFactorType }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async logout() : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("logout") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Fetches the profile of the logged-in account, to show which account is in use
+ */
+async getAccountInfo() : Promise<Result<UserProfile, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_account_info") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async listAccounts() : Promise<Result<AccountInfo[], CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_accounts") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Saves the currently logged-in session under `name` and marks it as the active account
+ */
+async addAccount(name: string) : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("add_account", { name }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async removeAccount(name: string) : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("remove_account", { name }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Switches to a saved account, or to a logged-out session when `name` is None
+ * so another account can log in and be saved with `add_account`
+ * The session being switched away from stays valid and is kept on its saved account
+ */
+async switchAccount(name: string | null) : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("switch_account", { name }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getLibraryEncryptionStatus() : Promise<Result<LibraryEncryptionStatus, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_library_encryption_status") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Unlocks an encrypted library and loads it, for use when startup reported it as locked
+ */
+async unlockLibrary(passphrase: string) : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("unlock_library", { passphrase }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async enableLibraryEncryption(passphrase: string) : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("enable_library_encryption", { passphrase }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async changeLibraryPassphrase(currentPassphrase: string, newPassphrase: string) : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_library_passphrase", { currentPassphrase, newPassphrase }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async disableLibraryEncryption(passphrase: string) : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("disable_library_encryption", { passphrase }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getGuestModeStatus() : Promise<Result<GuestModeStatus, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_guest_mode_status") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Sets the PIN which leaves guest mode, the current PIN is needed to change it
+ */
+async setGuestModePin(currentPin: string | null, pin: string) : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_guest_mode_pin", { currentPin, pin }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Switches the app into read-only guest mode, for showing the library on a shared screen
+ * The UI hides screens needing the VRChat account while `GuestModeStatus::enabled` is set
+ */
+async enterGuestMode() : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("enter_guest_mode") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async leaveGuestMode(pin: string) : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("leave_guest_mode", { pin }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Cross-checks folders and worlds, reporting every inconsistency without fixing it
+ */
+async verifyLibraryIntegrity() : Promise<Result<IntegrityReport, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("verify_library_integrity") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Fixes every inconsistency `verify_library_integrity` reports
+ * Returns the issues that were fixed
+ */
+async repairLibrary() : Promise<Result<IntegrityReport, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("repair_library") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getSyncStatus() : Promise<Result<SyncStatus, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_sync_status") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Sets the sync folder or WebDAV server, or turns sync off when `target` is None
+ */
+async setSyncTarget(target: SyncTarget | null) : Promise<Result<SyncStatus, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_sync_target", { target }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async syncNow() : Promise<Result<SyncStatus, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("sync_now") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Resolves a file listed as conflicting in the sync status by keeping one side's copy
+ */
+async resolveSyncConflict(file: string, resolution: ConflictResolution) : Promise<Result<SyncStatus, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("resolve_sync_conflict", { file, resolution }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Shows a saved world's name, author and link in the user's chatbox in VRChat,
+ * for recommending it to the people in the instance
+ * VRChat must have OSC enabled. Messages sent within the chatbox cooldown are refused
+ */
+async sendWorldToChatbox(worldId: string) : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("send_world_to_chatbox", { worldId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getLocalApiSettings() : Promise<Result<LocalApiSettings, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_local_api_settings") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Enables or disables the localhost API, restarting it on the new port
+ * A token is generated the first time it is enabled
+ */
+async setLocalApiSettings(enabled: boolean, port: number) : Promise<Result<LocalApiSettings, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_local_api_settings", { enabled, port }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Replaces the token, so clients holding the old one are locked out
+ */
+async regenerateLocalApiToken() : Promise<Result<LocalApiSettings, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("regenerate_local_api_token") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Gets the log entries of this session which pass the filter, oldest first
+ * Only the latest entries are kept in memory, older ones are in the log files
+ */
+async getRecentLogs(filter: LogFilter) : Promise<Result<LogEntry[], CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_recent_logs", { filter }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getLogLevels() : Promise<Result<{ [key in string]: LogLevel }, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_log_levels") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Sets the log levels of modules, e.g. "app_lib::api" to Debug
+ * The levels apply from the next start
+ */
+async setLogLevels(levels: { [key in string]: LogLevel }) : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_log_levels", { levels }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Imports the user's favorite worlds as a cancellable task
+ * Returns the task ID immediately; progress is reported through `TaskStatusChanged`
+ * 
+ * With `create_group_folders`, each favorite group's worlds are also put in a folder named
+ * after the group, creating it if needed
+ */
+async getFavoriteWorlds(createGroupFolders: boolean | null) : Promise<Result<string, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_favorite_worlds", { createGroupFolders }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Runs the setup page's imports as one cancellable task: the old VRC Worlds Manager's data,
+ * then the user's favorite worlds, then their recently visited worlds
+ * Returns the task ID immediately; progress is reported through `TaskStatusChanged`
+ * 
+ * A failing step does not stop the ones after it; the task fails once every step has run,
+ * with an error naming the steps which failed
+ */
+async runInitialImport(options: InitialImportOptions) : Promise<Result<string, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("run_initial_import", { options }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getWorld(worldId: string, dontSaveToLocal: boolean | null) : Promise<Result<WorldDetails, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_world", { worldId, dontSaveToLocal }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async checkWorldInfo(worldId: string) : Promise<Result<WorldDetails, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("check_world_info", { worldId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getRecentlyVisitedWorlds() : Promise<Result<WorldDisplayData[], CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_recently_visited_worlds") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Imports recently visited worlds into the Recently Visited folder right away,
+ * using the configured cap even if the periodic import is disabled
+ */
+async importRecentlyVisitedWorlds() : Promise<Result<string[], CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("import_recently_visited_worlds") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Searches for worlds on the server
+ * `has_more` on the returned page tells whether the next page is worth fetching
+ */
+async searchWorlds(sort: string, tags: string[], excludeTags: string[], search: string, platform: SearchPlatform | null, featured: boolean | null, releaseStatus: ReleaseStatus | null, page: number) : Promise<Result<SearchResultPage, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("search_worlds", { sort, tags, excludeTags, search, platform, featured, releaseStatus, page }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async createWorldInstance(worldId: string, instanceTypeStr: string, regionStr: string) : Promise<Result<InstanceInfo, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("create_world_instance", { worldId, instanceTypeStr, regionStr }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getUserGroups() : Promise<Result<UserGroup[], CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_user_groups") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getPermissionForCreateGroupInstance(groupId: string) : Promise<Result<GroupInstancePermissionInfo, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_permission_for_create_group_instance", { groupId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async createGroupInstance(worldId: string, groupId: string, instanceTypeStr: string, allowedRoles: string[] | null, regionStr: string, queueEnabled: boolean) : Promise<Result<InstanceInfo, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("create_group_instance", { worldId, groupId, instanceTypeStr, allowedRoles, regionStr, queueEnabled }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async openInstanceInClient(worldId: string, instanceId: string) : Promise<Result<string, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("open_instance_in_client", { worldId, instanceId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Builds the web and launch links of an instance, for pasting into e.g. Discord
+ * The short name is resolved when logged in, otherwise the links are built without it
+ */
+async generateInstanceLinks(worldId: string, instanceId: string) : Promise<Result<InstanceLinks, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("generate_instance_links", { worldId, instanceId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Drops every cached search and world response, so the next requests hit the API
+ */
+async clearApiCache() : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("clear_api_cache") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getRateLimitStatus() : Promise<Result<RateLimitStatus[], CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_rate_limit_status") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async resetRateLimits() : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("reset_rate_limits") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getApiRequestBudget() : Promise<Result<number, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_api_request_budget") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async setApiRequestBudget(requestsPerMinute: number) : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_api_request_budget", { requestsPerMinute }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getApiConcurrentRequests() : Promise<Result<number, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_api_concurrent_requests") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async setApiConcurrentRequests(concurrentRequests: number) : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_api_concurrent_requests", { concurrentRequests }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async openLogsDirectory() : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("open_logs_directory") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async openFolderDirectory() : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("open_folder_directory") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Zips redacted logs, preferences, library statistics and environment info into the
+ * exports directory, to attach to bug reports, and opens the directory
+ * Returns the path of the zip file
+ */
+async createDiagnosticsBundle() : Promise<Result<string, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("create_diagnostics_bundle") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Gets the local usage metrics collected so far
+ */
+async getAppMetrics() : Promise<Result<AppMetrics, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_app_metrics") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async resetAppMetrics() : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("reset_app_metrics") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getUsageMetricsEnabled() : Promise<Result<boolean, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_usage_metrics_enabled") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Opts in to or out of collecting local usage metrics
+ * The metrics collected so far are kept until they are reset
+ */
+async setUsageMetricsEnabled(enabled: boolean) : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_usage_metrics_enabled", { enabled }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Gets the changes involving a world, newest first
+ */
+async getChangeHistory(worldId: string) : Promise<Result<ChangeEvent[], CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_change_history", { worldId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Gets the weekly snapshots of a world's favorites, visits and capacity, oldest first
+ */
+async getWorldTrends(worldId: string) : Promise<Result<WorldTrends, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_world_trends", { worldId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Gets the newest changes to the library, newest first
+ */
+async getRecentChanges(limit: number) : Promise<Result<ChangeEvent[], CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_recent_changes", { limit }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Undoes the newest change which was not undone yet, returning it
+ */
+async undoLastChange() : Promise<Result<ChangeEvent, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("undo_last_change") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Checks if the app is being run for the first time
+ * As this is called every time / is loaded from the frontend, cache result in the state
+ */
+async requireInitialSetup() : Promise<boolean> {
+    return await TAURI_INVOKE("require_initial_setup");
+},
+/**
+ * Checks if files have been loaded from disk successfully
+ * 
+ * # Returns
+ * Returns a boolean indicating if the files have been loaded successfully
+ * 
+ * # Errors
+ * Returns an Io error with the message of the failure if the files could not be loaded
+ */
+async checkFilesLoaded() : Promise<Result<boolean, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("check_files_loaded") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async detectOldInstallation() : Promise<Result<[string, string], CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("detect_old_installation") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Passes the paths to the frontend
+ * Gets the path to the local app data directory
+ * 
+ * # Returns
+ * Returns the path to the local app data directory
+ * 
+ * # Errors
+ * Returns an error message if the path to the local app data directory could not be found
+ */
+async passPaths() : Promise<Result<string, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("pass_paths") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Gets the directory the library is stored in
+ */
+async getDataDirectory() : Promise<string> {
+    return await TAURI_INVOKE("get_data_directory");
+},
+async checkExistingData() : Promise<Result<[boolean, boolean], CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("check_existing_data") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getBackupMetadata(backupPath: string) : Promise<Result<BackupMetaData, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_backup_metadata", { backupPath }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getMigrationMetadata(worldsPath: string, foldersPath: string) : Promise<Result<PreviousMetadata, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_migration_metadata", { worldsPath, foldersPath }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Previews what migrating the old VRC Worlds Manager's data would produce, without migrating
+ */
+async previewMigration(worldsPath: string, foldersPath: string) : Promise<Result<MigrationPreview, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("preview_migration", { worldsPath, foldersPath }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async createEmptyAuth() : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("create_empty_auth") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async createEmptyFiles() : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("create_empty_files") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async createBackup(backupPath: string) : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("create_backup", { backupPath }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async restoreFromBackup(backupPath: string) : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("restore_from_backup", { backupPath }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Merges the library of another installation, given its data directory, into this one
+ */
+async mergeLibrary(dataDir: string, policy: MergePolicy) : Promise<Result<LibraryMergeSummary, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("merge_library", { dataDir, policy }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async exportToPortalLibrarySystem(folders: string[], sortField: string, sortDirection: string) : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("export_to_portal_library_system", { folders, sortField, sortDirection }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Exports a folder as an HTML page or Markdown document listing its worlds
+ */
+async exportWorldList(folderName: string, format: WorldListFormat, sortField: string, sortDirection: string) : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("export_world_list", { folderName, format, sortField, sortDirection }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Exports a folder as a printable PDF run sheet, in playlist order
+ */
+async exportRunSheetPdf(folderName: string) : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("export_run_sheet_pdf", { folderName }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async migrateOldData(worldsPath: string, foldersPath: string) : Promise<Result<MigrationReport, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("migrate_old_data", { worldsPath, foldersPath }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async deleteData() : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_data") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async exportNativeData(path: string) : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("export_native_data", { path }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Replaces the in-memory library with what is saved on disk, without restarting
+ * Discards any in-memory state left inconsistent by a failed operation
+ */
+async reloadDataFromDisk() : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("reload_data_from_disk") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Moves the library and every other data file to `new_path`, e.g. onto a bigger drive
+ * The files are copied and verified before the app switches to them, and it keeps using
+ * the new directory after restarts
+ */
+async moveDataDirectory(newPath: string) : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("move_data_directory", { newPath }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getMemo(worldId: string) : Promise<Result<string, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_memo", { worldId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async setMemoAndSave(worldId: string, memo: string) : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_memo_and_save", { worldId, memo }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async searchMemoText(searchText: string) : Promise<Result<string[], CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("search_memo_text", { searchText }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async setWorldPhotographed(worldId: string, isPhotographed: boolean) : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_world_photographed", { worldId, isPhotographed }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async setWorldShared(worldId: string, isShared: boolean) : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_world_shared", { worldId, isShared }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async setWorldFavorite(worldId: string, isFavorite: boolean) : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_world_favorite", { worldId, isFavorite }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async setWorldEventCritical(worldId: string, isEventCritical: boolean) : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_world_event_critical", { worldId, isEventCritical }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Rates a world from 1 to 5, None removes the rating
+ */
+async setWorldRating(worldId: string, rating: number | null) : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_world_rating", { worldId, rating }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Sets a status flag on several worlds at once, e.g. the selected cards
+ * Worlds which are not in the library are skipped and listed in the summary
+ */
+async setWorldsFlag(worldIds: string[], flag: WorldFlag, value: boolean) : Promise<Result<WorldFlagSummary, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_worlds_flag", { worldIds, flag, value }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Shows a local image on the world's card instead of its VRChat thumbnail
+ * The image is copied into the app's data, so the original can be moved or deleted
+ */
+async setWorldCover(worldId: string, imagePath: string) : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_world_cover", { worldId, imagePath }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Shows the world's VRChat thumbnail again
+ */
+async clearWorldCover(worldId: string) : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("clear_world_cover", { worldId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Corrects the name or description of a world, keeping it when the world is fetched again
+ */
+async lockWorldField(worldId: string, field: WorldField, value: string) : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("lock_world_field", { worldId, field, value }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Lets the next fetch of the world replace a corrected field again
+ */
+async unlockWorldField(worldId: string, field: WorldField) : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("unlock_world_field", { worldId, field }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getLockedWorldFields(worldId: string) : Promise<Partial<{ [key in WorldField]: string }>> {
+    return await TAURI_INVOKE("get_locked_world_fields", { worldId });
+},
+/**
+ * Gets the fetches since startup which brought a different value for a locked field
+ */
+async getFieldConflicts() : Promise<FieldConflict[]> {
+    return await TAURI_INVOKE("get_field_conflicts");
+},
+/**
+ * Dismisses the conflicts of a world, or of every world if none is given
+ */
+async clearFieldConflicts(worldId: string | null) : Promise<void> {
+    await TAURI_INVOKE("clear_field_conflicts", { worldId });
+},
+/**
+ * Removes cover images of worlds which are no longer in the library
+ * 
+ * # Returns
+ * The number of images removed
+ */
+async removeOrphanedCovers() : Promise<Result<number, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("remove_orphaned_covers") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Scans the VRChat photo directory, and marks the saved worlds with photos as photographed
+ */
+async scanPhotos() : Promise<Result<PhotoScanSummary, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("scan_photos") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Gets the photos taken in a world, newest first, as found by the last scan
+ */
+async getWorldPhotos(worldId: string) : Promise<Result<WorldPhoto[], CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_world_photos", { worldId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Gets the number of photos taken in each world, as found by the last scan
+ */
+async getPhotoCounts() : Promise<Result<{ [key in string]: number }, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_photo_counts") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Refreshes all event critical worlds, emitting a high-priority
+ * `EventCriticalWorldChanged` event for each world whose capacity,
+ * release status or platform support changed
+ */
+async refreshEventCriticalWorlds() : Promise<Result<EventCriticalWorldChanged[], CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("refresh_event_critical_worlds") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Gets the folders kept filled from a VRChat group, by folder name
+ */
+async getGroupFolders() : Promise<Result<{ [key in string]: GroupFolderLink }, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_group_folders") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Links a folder to a VRChat group and syncs it right away
+ * Without a folder name, a folder named after the group is used, created if needed
+ * Linked folders are synced again every few hours
+ */
+async linkGroupFolder(groupId: string, groupName: string, folderName: string | null) : Promise<Result<GroupFolderSyncResult, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("link_group_folder", { groupId, groupName, folderName }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Stops syncing a folder with its group, keeping the worlds in it
+ */
+async unlinkGroupFolder(folderName: string) : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("unlink_group_folder", { folderName }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Adds the worlds a folder's group is hosting instances of to the folder
+ */
+async syncGroupFolder(folderName: string) : Promise<Result<GroupFolderSyncResult, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("sync_group_folder", { folderName }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Gets the worlds the logged-in user uploaded, private ones included
+ * Each call snapshots their favorites and visits, see `get_world_trends`
+ */
+async getMyWorlds() : Promise<Result<MyWorld[], CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_my_worlds") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Creates an invite instance of one of the user's own worlds, so a private world can be
+ * tested without anyone else joining
+ */
+async createMyWorldTestInstance(worldId: string, regionStr: string) : Promise<Result<InstanceInfo, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("create_my_world_test_instance", { worldId, regionStr }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Gets the user's saved invite messages of a type, ordered by slot
+ */
+async getInviteMessages(messageType: InviteMessageType) : Promise<Result<InviteMessage[], CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_invite_messages", { messageType }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Replaces the text in one of the user's invite message slots
+ * Each slot can only be edited once an hour, the messages' `remainingCooldownMinutes` tells
+ * how long is left
+ */
+async setInviteMessage(messageType: InviteMessageType, slot: number, message: string) : Promise<Result<InviteMessage[], CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_invite_message", { messageType, slot, message }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Invites friends to an instance, with the message in `message_slot` if given
+ * 
+ * # Returns
+ * The IDs of the friends who could not be invited
+ */
+async inviteFriendsToInstance(userIds: string[], worldId: string, instanceId: string, messageSlot: number | null) : Promise<Result<string[], CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("invite_friends_to_instance", { userIds, worldId, instanceId, messageSlot }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Starts playing a folder in order, replacing any running playlist
+ * Use `playlist_next` to launch the first world
+ */
+async startPlaylist(folderName: string) : Promise<Result<Playlist, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("start_playlist", { folderName }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async stopPlaylist() : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("stop_playlist") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getPlaylist() : Promise<Result<Playlist | null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_playlist") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Creates an instance of the next world in the playlist, using the default instance type
+ * The playlist only moves on once the instance has been created
+ */
+async playlistNext() : Promise<Result<InstanceInfo, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("playlist_next") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Creates an instance of the previous world in the playlist, using the default instance type
+ */
+async playlistPrevious() : Promise<Result<InstanceInfo, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("playlist_previous") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async sortWorldsDisplay(worlds: WorldDisplayData[], sortField: string, sortDirection: string) : Promise<Result<WorldDisplayData[], CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("sort_worlds_display", { worlds, sortField, sortDirection }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Keeps the worlds whose download for the platform is at most `max_size` bytes
+ * Worlds of unknown size are kept
+ */
+async filterWorldsByDownloadSize(worlds: WorldDisplayData[], maxSize: number, quest: boolean) : Promise<Result<WorldDisplayData[], CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("filter_worlds_by_download_size", { worlds, maxSize, quest }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async resolveRedirects(url: string) : Promise<Result<string, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("resolve_redirects", { url }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Parses a pasted world ID, instance location, or vrchat.com or vrchat:// URL
+ * Short links like vrch.at need `resolve_redirects` first
+ */
+async parseWorldReference(input: string) : Promise<Result<WorldReference, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("parse_world_reference", { input }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Copies text to the system clipboard, e.g. an instance link
+ */
+async copyToClipboard(text: string) : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("copy_to_clipboard", { text }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Generates a QR code for a world, instance or shared folder, e.g. for event posters
+ * With `save` the PNG is also written to the exports folder
+ */
+async generateQrPng(payload: QrPayload, save: boolean) : Promise<Result<QrCodeImage, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("generate_qr_png", { payload, save }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getStartupDeepLink() : Promise<string | null> {
+    return await TAURI_INVOKE("get_startup_deep_link");
+},
+/**
+ * Takes the result of the deep link the app was started with, if the backend handled one
+ * Its event is emitted before the frontend starts listening
+ */
+async takeStartupDeepLinkResult() : Promise<DeepLinkHandled | null> {
+    return await TAURI_INVOKE("take_startup_deep_link_result");
+},
+/**
+ * Resolves once every write queued so far is durably on disk
+ * Await this after critical operations before telling the user their changes are saved
+ */
+async flushPendingWrites() : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("flush_pending_writes") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+}
 }
 
 /** user-defined events **/
 
 
 export const events = __makeEvents__<{
-taskStatusChanged: TaskStatusChanged
+taskStatusChanged: TaskStatusChanged,
+clipboardWorldDetected: ClipboardWorldDetected,
+eventCriticalWorldChanged: EventCriticalWorldChanged,
+dataReloaded: DataReloaded,
+initProgress: InitProgress,
+deepLinkHandled: DeepLinkHandled,
+notification: Notification,
+worldUpdated: WorldUpdated,
+worldAdded: WorldAdded,
+worldRemoved: WorldRemoved,
+worldFlagChanged: WorldFlagChanged,
+folderRenamed: FolderRenamed,
+folderReordered: FolderReordered,
+favoritesImportBatch: FavoritesImportBatch,
+favoritesImportFinished: FavoritesImportFinished,
+sessionExpired: SessionExpired,
+preferencesChanged: PreferencesChanged,
+changeEvent: ChangeEvent,
+apiQueueChanged: ApiQueueChanged
 }>({
-taskStatusChanged: "task-status-changed"
+taskStatusChanged: "task-status-changed",
+clipboardWorldDetected: "clipboard-world-detected",
+eventCriticalWorldChanged: "event-critical-world-changed",
+dataReloaded: "data-reloaded",
+initProgress: "init-progress",
+deepLinkHandled: "deep-link-handled",
+notification: "notification",
+worldUpdated: "world-updated",
+worldAdded: "world-added",
+worldRemoved: "world-removed",
+worldFlagChanged: "world-flag-changed",
+folderRenamed: "folder-renamed",
+folderReordered: "folder-reordered",
+favoritesImportBatch: "favorites-import-batch",
+favoritesImportFinished: "favorites-import-finished",
+sessionExpired: "session-expired",
+preferencesChanged: "preferences-changed",
+changeEvent: "change-event",
+apiQueueChanged: "api-queue-changed"
 })
 
 /** user-defined constants **/
@@ -798,33 +2626,1118 @@ taskStatusChanged: "task-status-changed"
 
 /** user-defined types **/
 
+/**
+ * A saved account as shown to the frontend, without its session cookies
+ */
+export type AccountInfo = { name: string; displayName: string; active: boolean }
+/**
+ * Every preference at once, so the frontend can load them in one call
+ * Includes the ones kept in custom_data.json next to the `PreferenceModel` fields
+ */
+export type AllPreferences = { theme: string; language: string; cardSize: CardSize; region: InstanceRegion; filterItemSelectorStarred: FilterItemSelectorStarred | null; dontShowRemoveFromFolder: FolderRemovalPreference; updateChannel: UpdateChannel; sortField: string; sortDirection: string; defaultInstanceType: DefaultInstanceType; defaultSearchPlatform: SearchPlatform | null; visibleButtons: VisibleButtons; naturalSort: boolean; keepNonPublicFavorites: boolean; recentlyVisitedImport: RecentlyVisitedImport; clipboardWatch: ClipboardWatch; tray: TraySettings; pinnedWorld: string | null; quickLaunchShortcut: string; photoDirectory: string | null; worldsStorage: WorldsStorageFormat }
+/**
+ * Usage of one VRChat API operation
+ */
+export type ApiOperationMetrics = { requests: number; 
+/**
+ * Requests which got a response, the others failed e.g. with a timeout
+ */
+responses: number; 
+/**
+ * Responses with an error status, other than rate limits
+ */
+errors: number; rateLimited: number; durations: DurationHistogram }
+/**
+ * Emitted whenever the number of API requests waiting in the global queue changes
+ */
+export type ApiQueueChanged = { interactive: number; background: number }
+/**
+ * Local usage metrics, which never leave the machine
+ * Only collected while the user has opted in
+ */
+export type AppMetrics = { 
+/**
+ * When collecting started, or the metrics were last reset
+ */
+since: string | null; 
+/**
+ * Invocations of each command
+ */
+commands: { [key in string]: number }; 
+/**
+ * Requests of each API operation
+ */
+api: { [key in string]: ApiOperationMetrics } }
+/**
+ * How far the user is through logging in
+ */
+export type AuthState = { phase: VRChatAuthPhase; 
+/**
+ * When VRChat is expected to ask for two-factor authentication again, None if unknown
+ */
+twoFactorExpiresAt: string | null }
 export type BackupMetaData = { date: string; number_of_folders: number; number_of_worlds: number; app_version: string }
 export type CardSize = "Compact" | "Normal" | "Expanded" | "Original"
+/**
+ * An entry of the change feed
+ * Also emitted to every window when it is recorded, so windows showing the same folders
+ * stay in sync
+ */
+export type ChangeEvent = { 
+/**
+ * Increases with every change
+ */
+id: number; timestamp: string; change: LibraryChange }
+/**
+ * Settings for watching the clipboard for copied VRChat world URLs
+ */
+export type ClipboardWatch = { enabled: boolean; mode: ClipboardWatchMode }
+/**
+ * What the clipboard watcher does with a copied VRChat world URL
+ */
+export type ClipboardWatchMode = 
+/**
+ * Emit an event offering to add the world
+ */
+"offer" | 
+/**
+ * Add the world to the library and the inbox right away
+ */
+"autoAdd"
+/**
+ * Emitted when a VRChat world URL was copied to the clipboard
+ */
+export type ClipboardWorldDetected = { worldId: string; 
+/**
+ * True if the world was put into the inbox, false if the frontend should offer it
+ */
+added: boolean }
+/**
+ * Error returned by Tauri commands to the frontend
+ * Tagged by kind so the frontend can branch on it, e.g. prompt re-login on Auth
+ * Where the backend has a translation, `message` is in the language of the preferences
+ * and `localized` holds its key, so the frontend can translate it itself
+ */
+export type CommandError = 
+/**
+ * The user is not logged in, or the session has expired
+ */
+{ kind: "Auth"; message: string; localized?: LocalizedMessage | null } | 
+/**
+ * No user is logged in, so a command needing the VRChat API was not attempted
+ */
+{ kind: "NotLoggedIn"; message: string; localized?: LocalizedMessage | null } | 
+/**
+ * The VRChat API is rate limiting requests
+ */
+{ kind: "RateLimited"; message: string; localized?: LocalizedMessage | null; 
+/**
+ * Seconds until the request may be retried, if known
+ */
+retryAfter: number | null } | 
+/**
+ * The requested folder, world or other entity does not exist
+ */
+{ kind: "NotFound"; message: string; localized?: LocalizedMessage | null } | 
+/**
+ * A request to the VRChat API or another server failed
+ */
+{ kind: "Network"; message: string; localized?: LocalizedMessage | null } | 
+/**
+ * Reading or writing local files failed
+ */
+{ kind: "Io"; message: string; localized?: LocalizedMessage | null } | 
+/**
+ * The library is encrypted and must be unlocked with its passphrase first,
+ * or is still being loaded
+ */
+{ kind: "Locked"; message: string; localized?: LocalizedMessage | null } | 
+/**
+ * The app is in read-only guest mode, so a command changing data or using the
+ * VRChat account was rejected
+ */
+{ kind: "GuestMode"; message: string; localized?: LocalizedMessage | null } | 
+/**
+ * The command was called with invalid input
+ */
+{ kind: "Validation"; message: string; localized?: LocalizedMessage | null } | 
+/**
+ * Any other failure
+ */
+{ kind: "Internal"; message: string; localized?: LocalizedMessage | null }
+/**
+ * Which copy of a conflicting file to keep
+ */
+export type ConflictResolution = "keepLocal" | "keepRemote"
+/**
+ * A change detected on an event critical world during refresh
+ */
+export type CriticalWorldChange = { kind: "capacity"; old: number; new: number } | { kind: "releaseStatus"; new: string } | { kind: "platform"; old: string[]; new: string[] }
+/**
+ * Emitted after the library was reloaded because its files were changed outside the app
+ */
+export type DataReloaded = { 
+/**
+ * Names of the changed files
+ */
+files: string[] }
+/**
+ * How the old app wrote the day and month of dates, which followed the system locale
+ */
+export type DateOrder = "MonthFirst" | "DayFirst"
+/**
+ * Actions a `vrc-worlds-manager://` link can trigger
+ */
+export type DeepLinkAction = "addWorld" | "importShare" | "openFolder"
+/**
+ * Emitted after the backend handled a deep link, for the frontend to show the result
+ */
+export type DeepLinkHandled = { url: string; action: DeepLinkAction; 
+/**
+ * The added world
+ */
+worldId: string | null; 
+/**
+ * The imported or opened folder
+ */
+folderName: string | null; 
+/**
+ * True if the world was already in the library, so nothing was fetched
+ */
+alreadyAdded: boolean; 
+/**
+ * Set if the action failed
+ */
+error: string | null }
 export type DefaultInstanceType = "public" | "group" | "friends+" | "friends" | "invite+" | "invite"
+/**
+ * A feed of newly published worlds
+ */
+export type DiscoveryFeed = "published" | "labsPublished"
+/**
+ * Counts of durations, bucketed by `DURATION_BUCKETS_MS`
+ */
+export type DurationHistogram = { boundsMs: number[]; 
+/**
+ * One more count than bounds, for durations above the last bound
+ */
+counts: number[]; totalMs: number; maxMs: number }
+/**
+ * High-priority alert raised when an event critical world changes
+ */
+export type EventCriticalWorldChanged = { worldId: string; worldName: string; changes: CriticalWorldChange[] }
+/**
+ * How many favorites the import received from one VRChat favorite group
+ */
+export type FavoriteGroupCount = { 
+/**
+ * The group's name, e.g. worlds1
+ */
+group: string; worldsReceived: number }
+/**
+ * Emitted by the favorites import as each page of favorites is saved, with the worlds it added
+ */
+export type FavoritesImportBatch = { 
+/**
+ * The import's task, None when it runs as part of the first-run import
+ */
+taskId: string | null; page: number; worlds: WorldDisplayData[] }
+/**
+ * Emitted once the favorites import has saved every page
+ */
+export type FavoritesImportFinished = { taskId: string | null; 
+/**
+ * Favorites received, including the ones already in the library
+ */
+worldsReceived: number; worldsAdded: number; worldsUpdated: number; 
+/**
+ * Favorites left out as they are not public
+ */
+nonPublicSkipped: number; 
+/**
+ * The public favorites received from each favorite group, by group name
+ */
+groups: FavoriteGroupCount[] }
+/**
+ * A fetch of a world brought a different value for a field the user had locked
+ */
+export type FieldConflict = { worldId: string; field: WorldField; 
+/**
+ * The locked value, which was kept
+ */
+localValue: string; 
+/**
+ * The value from VRChat, which was discarded
+ */
+remoteValue: string; detectedAt: string }
+export type FilterItemSelectorStarred = { author: string[]; tag: string[]; exclude_tag: string[]; folder: string[] }
 export type FilterItemSelectorStarredType = "Author" | "Tag" | "ExcludeTag" | "Folder"
-export type FolderData = { name: string; world_count: number; color: string | null }
+export type FolderData = { name: string; world_count: number; color: string | null; icon: string | null; description: string | null; sort: FolderSort | null; archived: boolean }
+/**
+ * Folders a world was added to and removed from in one change of its folders
+ */
+export type FolderMembershipChange = { addedTo: string[]; removedFrom: string[] }
+/**
+ * How renaming a folder handles another folder already having the new name
+ */
+export type FolderNameCollision = 
+/**
+ * Fail with a duplicate folder error
+ */
+"reject" | 
+/**
+ * Use the next free name like creating a folder does, e.g. "Chill (1)"
+ */
+"increment" | 
+/**
+ * Move the worlds into the existing folder and delete the renamed one
+ */
+"merge"
 export type FolderRemovalPreference = "ask" | "alwaysRemove" | "neverRemove"
+/**
+ * Emitted when a folder was renamed
+ */
+export type FolderRenamed = { oldName: string; newName: string; 
+/**
+ * The folder was merged into an existing folder named `new_name`, and no longer exists
+ */
+merged: boolean }
+/**
+ * Emitted when the folders were reordered, with every folder name in the new order
+ */
+export type FolderReordered = { folderNames: string[] }
+/**
+ * The share link of a folder
+ */
+export type FolderShareStatus = { shareId: string; expiryTime: string; expired: boolean }
+/**
+ * A sort order stored on a folder, see `SortingService` for the fields
+ */
+export type FolderSort = { field: string; direction: string }
+/**
+ * Statistics of a folder, for the info panel in its header
+ */
+export type FolderStats = { folderName: string; worldCount: number; pcOnly: number; questOnly: number; crossPlatform: number; totalCapacity: number; 
+/**
+ * None if the folder has no worlds
+ */
+averageCapacity: number | null; 
+/**
+ * Author tags of the worlds, most used first
+ */
+tags: TagCount[]; oldestAdded: FolderStatsWorld | null; newestAdded: FolderStatsWorld | null; 
+/**
+ * Worlds which were never launched from the app
+ */
+notVisited: number; 
+/**
+ * None if the folder is not shared
+ */
+share: FolderShareStatus | null }
+/**
+ * A world of a folder's statistics, with when it was added
+ */
+export type FolderStatsWorld = { worldId: string; worldName: string; dateAdded: string }
+/**
+ * A world ID as it appears in a folder
+ */
+export type FolderWorldRef = { folderName: string; worldId: string }
+export type FollowedAuthor = { authorId: string; authorName: string; lastChecked: string }
+/**
+ * The VRChat group a folder is kept filled from
+ */
+export type GroupFolderLink = { groupId: string; groupName: string; 
+/**
+ * None until the folder is first synced
+ */
+lastSynced?: string | null }
+/**
+ * The outcome of syncing a folder with its group
+ */
+export type GroupFolderSyncResult = { folderName: string; 
+/**
+ * Worlds the group is hosting instances of
+ */
+worldsFound: number; 
+/**
+ * Worlds which were not in the folder yet
+ */
+worldsAdded: number }
 export type GroupInstanceCreateAllowedType = { normal: boolean; plus: boolean; public: boolean; restricted: boolean }
 export type GroupInstanceCreatePermission = { Allowed: GroupInstanceCreateAllowedType } | "NotAllowed"
 export type GroupInstancePermissionInfo = { permission: GroupInstanceCreatePermission; roles: GroupRole[] }
 export type GroupMemberVisibility = "visible" | "friends" | "hidden"
 export type GroupPermission = "*" | "group-announcement-manage" | "group-audit-view" | "group-bans-manage" | "group-data-manage" | "group-default-role-manage" | "group-galleries-manage" | "group-instance-age-gated-create" | "group-instance-join" | "group-instance-manage" | "group-instance-moderate" | "group-instance-open-create" | "group-instance-plus-create" | "group-instance-plus-portal" | "group-instance-plus-portal-unlocked" | "group-instance-public-create" | "group-instance-queue-priority" | "group-instance-restricted-create" | "group-invites-manage" | "group-members-manage" | "group-members-remove" | "group-members-viewall" | "group-roles-assign" | "group-roles-manage"
 export type GroupRole = { id: string; groupId: string; name: string; permissions: GroupPermission[]; isManagementRole: boolean }
+/**
+ * Whether the app is in read-only guest mode, and whether a PIN to leave it is set
+ */
+export type GuestModeStatus = { enabled: boolean; pinSet: boolean }
+/**
+ * Emitted while the worlds are loaded in the background on startup
+ * Preferences and folders are loaded before the window opens, worlds are added once
+ * worlds.json has been parsed
+ */
+export type InitProgress = { 
+/**
+ * Worlds parsed so far
+ */
+loaded: number; 
+/**
+ * Worlds in the library, only known on the last event
+ */
+total: number | null; 
+/**
+ * Set on the last event, once every world is loaded and the library can be changed
+ */
+done: boolean }
+/**
+ * What the first-run import brings into the library
+ */
+export type InitialImportOptions = { 
+/**
+ * The old VRC Worlds Manager's worlds and folders files, both or neither
+ */
+oldWorldsPath: string | null; oldFoldersPath: string | null; importFavorites: boolean; 
+/**
+ * Put the imported favorites in a folder per favorite group
+ */
+createGroupFolders?: boolean; importRecentlyVisited: boolean }
+/**
+ * A step of the first-run import, in the order the steps run
+ */
+export type InitialImportStep = "migration" | "favorites" | "recentlyVisited"
 export type InstanceInfo = { world_id: string; instance_id: string; short_name: string | null }
+/**
+ * Links for sharing an instance, e.g. in Discord
+ */
+export type InstanceLinks = { 
+/**
+ * The vrchat.com page for joining the instance
+ */
+webUrl: string; 
+/**
+ * Opens the instance in the client
+ */
+launchUrl: string; 
+/**
+ * The vrch.at link, when the short name could be resolved
+ */
+shortUrl: string | null }
 export type InstanceRegion = "us" | "use" | "eu" | "jp"
+/**
+ * Inconsistencies between the folders and worlds of the library, grouped by kind
+ */
+export type IntegrityReport = { 
+/**
+ * Folder entries whose world is not in the library
+ */
+orphanedIds: FolderWorldRef[]; 
+/**
+ * Extra copies of a world within the same folder
+ */
+duplicateEntries: FolderWorldRef[]; 
+/**
+ * Worlds listing a folder that does not exist
+ */
+missingFolders: FolderWorldRef[]; 
+/**
+ * Hidden worlds that are still in a folder
+ */
+hiddenInFolders: FolderWorldRef[]; invalidTimestamps: InvalidTimestamp[] }
+/**
+ * A world timestamp that is in the future or predates VRChat
+ */
+export type InvalidTimestamp = { worldId: string; field: TimestampField; value: string }
+/**
+ * One of the user's saved invite messages
+ * VRChat keeps 12 slots per message type, which can be edited once an hour each
+ */
+export type InviteMessage = { id: string; slot: number; message: string; messageType: InviteMessageType; canBeUpdated?: boolean; remainingCooldownMinutes?: number }
+export type InviteMessageType = 
+/**
+ * Sent with an invite
+ */
+"message" | 
+/**
+ * Sent when declining an invite request
+ */
+"response" | 
+/**
+ * Sent with a request for an invite
+ */
+"request" | 
+/**
+ * Sent when declining an invite
+ */
+"requestResponse"
+/**
+ * A mutation of the library, as recorded in the change feed
+ */
+export type LibraryChange = { type: "worldsAdded"; worldIds: string[] } | { type: "worldDeleted"; worldId: string } | 
+/**
+ * The world was hidden, and removed from `folders`
+ */
+{ type: "worldHidden"; worldId: string; folders: string[] } | { type: "worldUnhidden"; worldId: string } | { type: "addedToFolder"; folderName: string; worldIds: string[] } | { type: "removedFromFolder"; folderName: string; worldId: string } | { type: "folderCreated"; folderName: string } | 
+/**
+ * The folder was deleted, holding `world_ids`
+ */
+{ type: "folderDeleted"; folderName: string; worldIds: string[] } | { type: "folderRenamed"; oldName: string; newName: string } | { type: "folderArchived"; folderName: string; archived: boolean } | { type: "flagChanged"; worldId: string; flag: WorldFlag; value: boolean } | 
+/**
+ * The change with `change_id` was undone
+ */
+{ type: "undone"; changeId: number }
+/**
+ * Whether the library files are encrypted with a passphrase, and whether they are unlocked
+ */
+export type LibraryEncryptionStatus = { enabled: boolean; locked: boolean }
+/**
+ * What merging another library into this one changed
+ */
+export type LibraryMergeSummary = { worldsAdded: number; worldsUpdated: number; foldersAdded: number; foldersMerged: number; memosAdded: number; 
+/**
+ * Memos and settings both libraries edited, decided by the merge policy
+ */
+conflicts: number }
+/**
+ * The localhost server external tools read the library from, e.g. stream overlays
+ */
+export type LocalApiSettings = { enabled: boolean; port: number; 
+/**
+ * Sent by clients as a bearer token, or a `token` query parameter
+ */
+token: string }
 export type LocalizedChanges = { version: string; pre_release: boolean; features: string[]; fixes: string[]; others: string[] }
+/**
+ * A message produced by the backend, as a key of the translation catalogs
+ * Keys and the `{0}` style placeholders follow the frontend's locale files,
+ * so the frontend can show the message in its own language
+ */
+export type LocalizedMessage = { key: string; params: string[] }
+export type LogEntry = { time: string; level: LogLevel; target: string; module: string | null; message: string; 
+/**
+ * The first world ID mentioned in the message
+ */
+worldId?: string | null }
+/**
+ * Filter for `get_recent_logs`, every field is optional
+ */
+export type LogFilter = { 
+/**
+ * The least severe level to include
+ */
+level: LogLevel | null; 
+/**
+ * Prefix of the target, e.g. "app_lib::services"
+ */
+target: string | null; worldId: string | null; 
+/**
+ * Text the message contains, ignoring case
+ */
+search: string | null }
+export type LogLevel = "Error" | "Warn" | "Info" | "Debug" | "Trace"
+/**
+ * Which side wins when both libraries of a merge edited the same memo or setting
+ */
+export type MergePolicy = 
+/**
+ * The copy of the world checked most recently wins
+ */
+"preferNewer" | "preferLocal"
+/**
+ * A world whose last update date was unreadable or ambiguous
+ */
+export type MigrationDateIssue = { world_id: string; world_name: string; 
+/**
+ * The date as written in the old worlds file
+ */
+value: string }
+/**
+ * A world listed more than once in the old worlds file, which is migrated once
+ */
+export type MigrationDuplicate = { world_id: string; world_name: string; occurrences: number }
+/**
+ * A folder as it will be migrated
+ */
+export type MigrationFolderPreview = { name: string; number_of_worlds: number }
+/**
+ * What migrating the old VRC Worlds Manager's data would produce, without migrating it
+ */
+export type MigrationPreview = { 
+/**
+ * The folders in their old order, without the Hidden and Unclassified folders
+ */
+folders: MigrationFolderPreview[]; number_of_worlds: number; 
+/**
+ * Worlds in the old Hidden folder, which are migrated as hidden worlds
+ */
+number_of_hidden_worlds: number; 
+/**
+ * Worlds found only in the folders file, which are migrated too
+ */
+number_of_worlds_only_in_folders: number; duplicates: MigrationDuplicate[]; 
+/**
+ * Worlds without a date added, which are dated a minute apart before `earliest_date_added`
+ */
+number_of_worlds_without_date: number; earliest_date_added: string | null; date_order: DateOrder; 
+/**
+ * Last update dates which could not be read, and ones whose day and month could be
+ * either way round while the other dates do not settle the order
+ */
+unreadable_dates: MigrationDateIssue[]; ambiguous_dates: MigrationDateIssue[] }
+/**
+ * What migrating the old VRC Worlds Manager's data did
+ */
+export type MigrationReport = { number_of_folders: number; number_of_worlds: number; date_order: DateOrder; 
+/**
+ * Worlds whose last update could not be read, which is left unknown until they are
+ * next fetched
+ */
+unreadable_dates: MigrationDateIssue[]; ambiguous_dates: MigrationDateIssue[]; 
+/**
+ * Old memos of worlds which had none, and old memos appended to an existing one
+ */
+memos_imported: number; memos_merged: number }
+/**
+ * A world uploaded by the logged-in user
+ */
+export type MyWorld = { world: WorldDisplayData; releaseStatus: ReleaseStatus; 
+/**
+ * Whether the world is saved in the library
+ */
+inLibrary: boolean }
+/**
+ * An entry in the in-app notification inbox
+ * Also emitted as an event whenever a new notification is added
+ */
+export type Notification = { id: string; createdAt: string; read?: boolean; kind: NotificationKind }
+/**
+ * The typed payload of a notification
+ */
+export type NotificationKind = 
+/**
+ * A saved world was updated by its author
+ */
+{ type: "worldUpdated"; worldId: string; worldName: string; oldUpdate: string; newUpdate: string } | 
+/**
+ * A saved world can no longer be visited, e.g. it was made private
+ */
+{ type: "worldUnavailable"; worldId: string; worldName: string; reason: string } | 
+/**
+ * The share link of a folder is about to expire
+ */
+{ type: "shareExpiringSoon"; folderName: string; shareId: string; expiryTime: string } | 
+/**
+ * A followed author published a world which is not in the library yet
+ */
+{ type: "newWorldFromAuthor"; worldId: string; worldName: string; authorName: string } | 
+/**
+ * A backup was created
+ */
+{ type: "backupCompleted"; path: string } | 
+/**
+ * Creating a backup failed
+ */
+{ type: "backupFailed"; error: string } | 
+/**
+ * A new version of the app is available
+ */
+{ type: "updateAvailable"; version: string }
+/**
+ * The kinds of notification, without their payload, for filtering
+ */
+export type NotificationType = "worldUpdated" | "worldUnavailable" | "shareExpiringSoon" | "newWorldFromAuthor" | "backupCompleted" | "backupFailed" | "updateAvailable"
 export type PatreonData = { platinumSupporter: string[]; goldSupporter: string[]; silverSupporter: string[]; bronzeSupporter: string[]; basicSupporter: string[] }
 export type PatreonVRChatNames = { platinumSupporter: string[]; goldSupporter: string[]; silverSupporter: string[]; bronzeSupporter: string[]; basicSupporter: string[] }
+/**
+ * Outcome of scanning the photo directory
+ */
+export type PhotoScanSummary = { directory: string; photos: number; 
+/**
+ * Photos without a world ID, taken before VRChat wrote it into photos
+ */
+withoutWorld: number; 
+/**
+ * Number of worlds with photos, whether saved or not
+ */
+worlds: number; 
+/**
+ * Saved worlds which were marked as photographed by this scan
+ */
+newlyPhotographed: string[] }
 export type Platform = "PC" | "Quest" | "Cross-Platform"
+/**
+ * A folder being played through in order, creating one instance per world
+ * The world IDs are snapshotted when the playlist starts, so editing the folder
+ * does not change a running playlist
+ */
+export type Playlist = { folderName: string; worldIds: string[]; 
+/**
+ * Index of the world currently being played, None until the first world is launched
+ */
+position: number | null }
+/**
+ * Emitted after any preference is changed, so every window shows the same settings
+ */
+export type PreferencesChanged = { preferences: AllPreferences }
 export type PreviousMetadata = { number_of_folders: number; number_of_worlds: number }
+/**
+ * A generated QR code
+ */
+export type QrCodeImage = { 
+/**
+ * The text the code contains
+ */
+content: string; pngBase64: string; 
+/**
+ * Where the PNG was saved, when it was saved to the exports folder
+ */
+path: string | null }
+/**
+ * What a QR code links to
+ */
+export type QrPayload = 
+/**
+ * The world's vrchat.com page
+ */
+{ type: "world"; worldId: string } | 
+/**
+ * The vrchat.com page for joining the instance
+ */
+{ type: "instance"; worldId: string; instanceId: string } | 
+/**
+ * A deep link which imports the shared folder
+ */
+{ type: "share"; shareId: string }
+/**
+ * Rate limit state of a single API operation, as shown to the user
+ */
+export type RateLimitStatus = { operation: string; consecutiveFailures: number; currentBackoffMs: number; lastRateLimited: string | null; 
+/**
+ * When requests for this operation will be allowed again, None if they are allowed now
+ */
+nextAllowedAt: string | null; 
+/**
+ * Number of 429 responses received in the last 24 hours
+ */
+recentRateLimitCount: number }
+/**
+ * Settings for automatically importing recently visited worlds into their own folder
+ */
+export type RecentlyVisitedImport = { enabled: boolean; 
+/**
+ * Maximum number of worlds kept in the folder, the oldest are removed first
+ */
+cap: number }
+/**
+ * A world suggested from the user's launches, favorites and ratings
+ */
+export type Recommendation = { world: WorldDisplayData; 
+/**
+ * Higher is a better match; only meaningful relative to the other recommendations
+ */
+score: number; 
+/**
+ * Why the world was recommended, the strongest reason first
+ */
+reasons: RecommendationReason[]; 
+/**
+ * False for worlds found by searching VRChat
+ */
+inLibrary: boolean }
+/**
+ * Why a world was recommended, for the UI to explain it
+ * e.g. "because you launched 12 horror worlds"
+ */
+export type RecommendationReason = 
+/**
+ * The world has a tag of worlds the user often launches
+ */
+{ type: "launchedTag"; tag: string; launches: number } | 
+/**
+ * The world has a tag of worlds the user favorited or rated highly
+ */
+{ type: "favoriteTag"; tag: string; worlds: number } | 
+/**
+ * The world is by an author whose worlds the user favorited, launched or rated highly
+ */
+{ type: "likedAuthor"; authorName: string; worlds: number } | 
+/**
+ * The world is in the library but was never launched from the app
+ */
+{ type: "neverLaunched" }
+/**
+ * A published release, with whether the user skipped it
+ */
+export type ReleaseInfo = { version: string; name: string; preRelease: boolean; publishedAt: string | null; url: string; skipped: boolean; installed: boolean }
+export type ReleaseStatus = "public" | "private" | "hidden" | "all"
+/**
+ * Platform filter for remote world searches
+ */
+export type SearchPlatform = "pc" | "android" | 
+/**
+ * Only worlds available on both PC and Android
+ */
+"both"
+/**
+ * A page of remote world search results
+ */
+export type SearchResultPage = { worlds: WorldDisplayData[]; page: number; pageSize: number; 
+/**
+ * Whether the next page may have results
+ * Based on the number of worlds the API returned, before any filtering
+ */
+hasMore: boolean }
+/**
+ * Emitted when the background session check finds the saved login no longer works,
+ * so the user can be asked to log in again
+ */
+export type SessionExpired = { message: string }
+/**
+ * A world suggested because it resembles another one, with why it was suggested
+ */
+export type SimilarWorld = { world: WorldDisplayData; 
+/**
+ * Higher is more similar; only meaningful relative to the other suggestions
+ */
+score: number; 
+/**
+ * Author tags both worlds have, without "author_tag_"
+ */
+sharedTags: string[]; sameAuthor: boolean; 
+/**
+ * False for worlds found by searching VRChat
+ */
+inLibrary: boolean }
+/**
+ * What the sync settings show
+ */
+export type SyncStatus = { 
+/**
+ * The sync folder or WebDAV URL, None while sync is not set up
+ */
+location: string | null; lastSynced: string | null; conflicts: string[] }
+/**
+ * Where the library is mirrored to, so several PCs can share it
+ */
+export type SyncTarget = 
+/**
+ * A folder kept in sync by a client such as Dropbox or Google Drive
+ */
+{ type: "folder"; path: string } | 
+/**
+ * A collection on a WebDAV server
+ */
+{ type: "webDav"; url: string; username: string; password: string }
+/**
+ * An author tag, and the number of worlds which have it
+ */
+export type TagCount = { tag: string; count: number }
+/**
+ * How author tags are consolidated locally, without changing the worlds' VRChat data
+ * Tags are lowercase and without "author_tag_"
+ */
+export type TagRules = { 
+/**
+ * Map of tag -> tag it is shown as
+ */
+aliases: { [key in string]: string }; 
+/**
+ * Tags which are not shown
+ */
+hidden: string[] }
+/**
+ * Snapshot of a background task, as shown in the task panel
+ */
+export type TaskInfo = { id: string; kind: TaskKind; status: TaskStatus; progress: TaskProgress | null; startedAt: string }
+/**
+ * What a background task is doing, so the frontend can label it
+ */
+export type TaskKind = "UpdateDownload" | "FavoritesImport" | "FolderRefresh" | "InitialImport" | "ThumbnailPrefetch"
+/**
+ * Progress of a long-running fetch task, by kind of task
+ */
+export type TaskProgress = { type: "favoritesImport"; pagesFetched: number; worldsAdded: number } | 
+/**
+ * `updated` and `unavailable` are final once the task has completed
+ */
+{ type: "folderRefresh"; checked: number; total: number; updated: number; unavailable: number } | 
+/**
+ * `step` is the step running, or the last one once the task has completed
+ */
+{ type: "initialImport"; step: InitialImportStep; completedSteps: number; failedSteps: number; totalSteps: number } | 
+/**
+ * `downloaded` counts thumbnails which were not cached yet
+ */
+{ type: "thumbnailPrefetch"; checked: number; total: number; downloaded: number; failed: number }
 export type TaskStatus = "Running" | "Completed" | "Cancelled" | "Failed"
-export type TaskStatusChanged = { id: string; status: TaskStatus }
+export type TaskStatusChanged = { id: string; kind: TaskKind; status: TaskStatus; progress: TaskProgress | null }
+export type TimestampField = "dateAdded" | "lastChecked" | "updatedAt" | "publicationDate"
+/**
+ * Settings for running from the system tray
+ */
+export type TraySettings = { 
+/**
+ * Hide the window to the tray on startup
+ */
+startMinimized: boolean; 
+/**
+ * Hide the window to the tray when it is closed, so background jobs keep running
+ */
+closeToTray: boolean }
+/**
+ * What to do with a world taken out of the inbox
+ */
+export type TriageAction = 
+/**
+ * Keeps the world in the library, in a folder if one is given
+ */
+{ type: "keep"; folderName: string | null } | { type: "hide" } | 
+/**
+ * Blacklists the world and deletes it from the library
+ */
+{ type: "blacklist" }
+/**
+ * Trust rank of a VRChat user, derived from their system tags
+ */
+export type TrustRank = "visitor" | "newUser" | "user" | "knownUser" | "trustedUser"
 export type UpdateChannel = "stable" | "pre-release"
+/**
+ * Which versions the user does not want to be offered
+ */
+export type UpdatePolicy = { 
+/**
+ * Versions the user chose to skip, or which were rolled back from
+ */
+skippedVersions?: string[]; 
+/**
+ * When set, no other version is offered
+ */
+pinnedVersion?: string | null }
 export type UserGroup = { id: string; name: string; shortCode: string; discriminator: string; description: string; iconUrl?: string | null; bannerUrl?: string | null; privacy: string; memberCount: number; groupId: string; memberVisibility: GroupMemberVisibility; isRepresenting: boolean; mutualGroup: boolean }
+/**
+ * Profile of the logged-in VRChat account
+ */
+export type UserProfile = { userId: string; displayName: string; 
+/**
+ * The profile picture if one is set, otherwise the current avatar
+ */
+thumbnailUrl: string; trustRank: TrustRank; friendCount: number }
+export type VRChatAuthPhase = "none" | "twoFactorAuth" | "email2FA" | "loggedIn"
 export type VisibleButtons = { favorite: boolean; photographed: boolean; shared: boolean }
-export type WorldBlacklist = { worlds: string[] }
-export type WorldDetails = { worldId: string; name: string; thumbnailUrl: string; authorName: string; authorId: string; favorites: number; lastUpdated: string; visits: number; platform: Platform; description: string; tags: string[]; capacity: number; recommendedCapacity: number | null; publicationDate: string | null }
-export type WorldDisplayData = { worldId: string; name: string; thumbnailUrl: string; authorName: string; favorites: number; lastUpdated: string; visits: number; dateAdded: string; platform: Platform; folders: string[]; tags: string[]; capacity: number; isPhotographed: boolean; isShared: boolean; isFavorite: boolean }
+/**
+ * Payload format of a webhook
+ */
+export type WebhookFormat = 
+/**
+ * The notification as JSON
+ */
+"json" | 
+/**
+ * A Discord message with an embed
+ */
+"discord"
+/**
+ * A URL notifications are posted to
+ */
+export type WebhookSettings = { enabled: boolean; url: string; format?: WebhookFormat; 
+/**
+ * Notification types to post, every type when empty
+ */
+events?: NotificationType[] }
+/**
+ * Emitted when worlds were added to the library, with the added worlds
+ * Worlds already saved are reported by `WorldUpdated` instead
+ */
+export type WorldAdded = { worlds: WorldDisplayData[] }
+/**
+ * Whether the API shows a world to the user
+ */
+export type WorldAvailability = "public" | 
+/**
+ * Private or hidden; the world is only known by its ID
+ */
+"private"
+export type WorldBlacklist = { worlds?: string[]; authors?: string[] }
+/**
+ * The worlds changed since a point in time
+ */
+export type WorldChanges = { worlds: WorldDisplayData[]; 
+/**
+ * When the changes were collected, to pass to the next call
+ */
+timestamp: string; 
+/**
+ * False if changes were not tracked back to the requested time, e.g. as the app was
+ * restarted since, and every world should be fetched again
+ */
+complete: boolean }
+export type WorldDetails = { worldId: string; name: string; thumbnailUrl: string; authorName: string; authorId: string; favorites: number; lastUpdated: string; visits: number; platform: Platform; description: string; tags: string[]; capacity: number; recommendedCapacity: number | null; publicationDate: string | null; packages: WorldPackageInfo[] }
+export type WorldDisplayData = { worldId: string; name: string; thumbnailUrl: string; authorName: string; authorId: string; favorites: number; lastUpdated: string; visits: number; dateAdded: string; platform: Platform; folders: string[]; tags: string[]; capacity: number; isPhotographed: boolean; isShared: boolean; isFavorite: boolean; isEventCritical: boolean; hasMemo?: boolean; rating?: number | null; 
+/**
+ * Instances launched from the app
+ */
+localVisitCount?: number; 
+/**
+ * In bytes, if known
+ */
+pcDownloadSize?: number | null; 
+/**
+ * In bytes, if known
+ */
+questDownloadSize?: number | null; availability?: WorldAvailability }
+/**
+ * A field of a world's VRChat data which can be corrected and locked locally
+ */
+export type WorldField = "name" | "description"
+/**
+ * The grid's filters, for applying an operation to every matching world
+ * Empty fields and None don't filter
+ */
+export type WorldFilter = { 
+/**
+ * "All Worlds", "Unclassified Worlds", "Hidden Worlds" or a folder name
+ */
+source: string; 
+/**
+ * Matched against the world and author names, ignoring case
+ */
+searchQuery: string; author: string; 
+/**
+ * Author tags without the "author_tag_" prefix, every one must be set on the world
+ */
+tags: string[]; 
+/**
+ * The world must be in every one of these folders
+ */
+folders: string[]; memoText: string; photographed: boolean | null; shared: boolean | null; favorite: boolean | null; 
+/**
+ * Only worlds which are neither photographed nor shared
+ */
+unprocessed: boolean; platform: SearchPlatform | null }
+/**
+ * A per-world status flag
+ */
+export type WorldFlag = "photographed" | "shared" | "favorite" | "eventCritical"
+/**
+ * Emitted when a flag was set on worlds, with only the worlds whose flag changed
+ */
+export type WorldFlagChanged = { worldIds: string[]; flag: WorldFlag; value: boolean }
+/**
+ * Outcome of setting a flag on several worlds at once
+ */
+export type WorldFlagSummary = { 
+/**
+ * Worlds whose flag changed
+ */
+changed: string[]; 
+/**
+ * Number of worlds which already had the flag set to the value
+ */
+unchanged: number; 
+/**
+ * Worlds which are not in the library
+ */
+notFound: string[] }
+/**
+ * A section of the world grid
+ * A world is in every group of its folders or tags, and worlds without any are in a group
+ * with an empty key, which is always last
+ */
+export type WorldGroup = { 
+/**
+ * The author ID, platform, folder name, letter, "YYYY-MM" or tag the group is for
+ */
+key: string; 
+/**
+ * The name to show for the group, the author's name when grouped by author
+ */
+label: string; count: number; worlds: WorldDisplayData[] }
+/**
+ * What the worlds of the grid are grouped into sections by
+ */
+export type WorldGroupBy = "author" | "platform" | "folder" | "firstLetter" | "addedMonth" | "tag"
+/**
+ * Format of a folder exported as a world list
+ */
+export type WorldListFormat = 
+/**
+ * A standalone page with its styles inlined
+ */
+"html" | "markdown"
+/**
+ * The build of a world for one platform
+ */
+export type WorldPackageInfo = { 
+/**
+ * "standalonewindows" or "android"
+ */
+platform: string; 
+/**
+ * Compressed size in bytes, which is what is downloaded
+ */
+fileSize?: number | null; uncompressedSize?: number | null; performanceRating?: string | null }
+/**
+ * A VRChat photo found by the photo scanner
+ */
+export type WorldPhoto = { path: string; 
+/**
+ * The world the photo was taken in, from the photo's metadata
+ */
+worldId: string | null; takenAt: string | null }
+/**
+ * A world, and optionally an instance of it, parsed from user input
+ */
+export type WorldReference = { worldId: string; 
+/**
+ * The instance part of the location, e.g. "12345~region(jp)"
+ */
+instanceId: string | null }
+/**
+ * Emitted when a world was deleted from the library
+ */
+export type WorldRemoved = { worldId: string }
+/**
+ * The fields a world card needs, for the grids of large folders
+ * Tags, folders and statistics are left out; the full data of the cards shown is fetched
+ * with `get_worlds_by_id`, and the details with `get_world`
+ */
+export type WorldSummary = { worldId: string; name: string; thumbnailUrl: string; authorName: string; platform: Platform; isPhotographed: boolean; isShared: boolean; isFavorite: boolean; isEventCritical: boolean; hasMemo: boolean }
+/**
+ * A world's popularity at one point in time
+ */
+export type WorldTrendPoint = { takenAt: string; favorites: number; visits: number | null; capacity: number }
+/**
+ * The snapshots taken of a world, oldest first
+ */
+export type WorldTrends = { worldId: string; points: WorldTrendPoint[] }
+/**
+ * Emitted when a refresh changed the data of saved worlds, with only those worlds
+ * Hidden worlds are left out, as they are not shown
+ */
+export type WorldUpdated = { worlds: WorldDisplayData[]; 
+/**
+ * When the change was recorded, to pass to `get_worlds_changed_since` later
+ */
+timestamp: string }
+/**
+ * How worlds.json is stored, the largest file of the library
+ * Every format is read regardless of the setting, so switching takes effect on the next save
+ */
+export type WorldsStorageFormat = 
+/**
+ * Indented JSON, readable by every version of the app
+ */
+"pretty" | 
+/**
+ * JSON without whitespace, readable by every version of the app
+ */
+"compact" | 
+/**
+ * Gzip compressed JSON in worlds.json.gz, only readable by versions of the app which
+ * support it
+ */
+"gzip"
 
 /** tauri-specta globals **/
 
@@ -0,0 +1,49 @@
+import { commands, events, TaskProgress, TaskStatus } from '@/lib/bindings';
+
+/**
+ * Waits for a background task to leave the Running state.
+ * Resolves with the final status, or rejects with the task error if it failed.
+ */
+export async function waitForTask(
+  taskId: string,
+  onProgress?: (progress: TaskProgress) => void,
+): Promise<TaskStatus> {
+  let settled = false;
+  let resolveStatus: (status: TaskStatus) => void = () => {};
+  const finished = new Promise<TaskStatus>((resolve) => {
+    resolveStatus = resolve;
+  });
+
+  const settle = (status: TaskStatus) => {
+    if (settled || status === 'Running') return;
+    settled = true;
+    resolveStatus(status);
+  };
+
+  const unlisten = await events.taskStatusChanged.listen((e) => {
+    if (e.payload.id !== taskId) return;
+    if (e.payload.progress && onProgress) {
+      onProgress(e.payload.progress);
+    }
+    settle(e.payload.status);
+  });
+
+  try {
+    // The task may have finished before the listener was registered
+    const current = await commands.getTaskStatus(taskId);
+    if (current.status === 'ok') {
+      settle(current.data);
+    }
+
+    const status = await finished;
+    if (status === 'Failed') {
+      const result = await commands.getTaskError(taskId);
+      throw new Error(
+        result.status === 'ok' ? (result.data ?? status) : status,
+      );
+    }
+    return status;
+  } finally {
+    unlisten();
+  }
+}
@@ -57,7 +57,7 @@ export const usePatreonStore = create<PatreonState>((set, get) => ({
           error: null,
         });
       } else {
-        throw new Error(result.error);
+        throw new Error(result.error.message);
       }
     } catch (e) {
       const errorMessage = `Failed to fetch Patreon VRChat names: ${e}`;